@@ -0,0 +1,48 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+
+#[derive(Debug)]
+pub enum ApiError {
+    BadRequest(String),
+    NotFound(String),
+    Unavailable(&'static str),
+    Upstream(String),
+}
+
+#[derive(Serialize)]
+struct Body {
+    error: String,
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let (status, error) = match self {
+            ApiError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg),
+            ApiError::NotFound(msg) => (StatusCode::NOT_FOUND, msg),
+            ApiError::Unavailable(msg) => (StatusCode::SERVICE_UNAVAILABLE, msg.to_owned()),
+            ApiError::Upstream(msg) => {
+                tracing::warn!(error = %msg, "upstream failure");
+                (StatusCode::BAD_GATEWAY, msg)
+            }
+        };
+        (status, Json(Body { error })).into_response()
+    }
+}
+
+impl From<solana_client::client_error::ClientError> for ApiError {
+    fn from(err: solana_client::client_error::ClientError) -> Self {
+        ApiError::Upstream(err.to_string())
+    }
+}
+
+impl From<sqlx::Error> for ApiError {
+    fn from(err: sqlx::Error) -> Self {
+        ApiError::Upstream(err.to_string())
+    }
+}
+
+pub type ApiResult<T> = Result<Json<T>, ApiError>;
@@ -0,0 +1,74 @@
+//! `ml-api`: JSON views of pools for front-ends.
+//!
+//! Single-pool and participant reads go to RPC so they are never stale; the
+//! pool list and wallet history come from the `ml-indexer` database when
+//! `DATABASE_URL` is set (the pool list falls back to `getProgramAccounts`).
+
+mod error;
+mod routes;
+mod views;
+
+use std::{net::SocketAddr, sync::Arc};
+
+use anyhow::Result;
+use axum::{routing::get, Router};
+use clap::Parser;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
+use sqlx::{postgres::PgPoolOptions, PgPool};
+use tower_http::{cors::CorsLayer, trace::TraceLayer};
+use tracing_subscriber::EnvFilter;
+
+#[derive(Parser)]
+#[command(name = "ml-api", version, about = "MissOut pool HTTP API")]
+struct Config {
+    #[arg(long, env = "ML_RPC_URL", default_value = "https://api.devnet.solana.com")]
+    rpc_url: String,
+
+    /// Indexer database; without it `/users/{wallet}/history` is unavailable
+    #[arg(long, env = "DATABASE_URL")]
+    database_url: Option<String>,
+
+    #[arg(long, env = "ML_API_ADDR", default_value = "0.0.0.0:8080")]
+    addr: SocketAddr,
+}
+
+pub struct AppState {
+    pub rpc: RpcClient,
+    pub db: Option<PgPool>,
+    pub program_id: Pubkey,
+}
+
+pub fn router(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/pools", get(routes::list_pools))
+        .route("/pools/{id}", get(routes::get_pool))
+        .route("/pools/{id}/participants", get(routes::get_participants))
+        .route("/users/{wallet}/history", get(routes::user_history))
+        .layer(CorsLayer::permissive())
+        .layer(TraceLayer::new_for_http())
+        .with_state(state)
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")))
+        .init();
+
+    let config = Config::parse();
+    let db = match &config.database_url {
+        Some(url) => Some(PgPoolOptions::new().max_connections(8).connect(url).await?),
+        None => None,
+    };
+    let state = Arc::new(AppState {
+        rpc: RpcClient::new_with_commitment(config.rpc_url, CommitmentConfig::confirmed()),
+        db,
+        program_id: Pubkey::new_from_array(ml_types::PROGRAM_ID.to_bytes()),
+    });
+
+    let listener = tokio::net::TcpListener::bind(config.addr).await?;
+    tracing::info!(addr = %config.addr, "listening");
+    axum::serve(listener, router(state)).await?;
+    Ok(())
+}
@@ -0,0 +1,161 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use ml_types::{
+    state::{Participants, Pool},
+    AccountData, Discriminator,
+};
+use serde::Deserialize;
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_filter::{Memcmp, RpcFilterType},
+};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    error::{ApiError, ApiResult},
+    views::{HistoryEntry, IndexedPool, ParticipantsView, PoolList, PoolView, UserHistory, Win},
+    AppState,
+};
+
+const DEFAULT_LIMIT: i64 = 50;
+const MAX_LIMIT: i64 = 500;
+
+#[derive(Deserialize)]
+pub struct Page {
+    /// Snake-case [`ml_types::state::PoolStatus`] name
+    status: Option<String>,
+    limit: Option<i64>,
+    offset: Option<i64>,
+}
+
+impl Page {
+    fn limit(&self) -> i64 {
+        self.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT)
+    }
+
+    fn offset(&self) -> i64 {
+        self.offset.unwrap_or(0).max(0)
+    }
+}
+
+/// `GET /pools`: indexer rows when a database is configured, otherwise a
+/// `getProgramAccounts` scan of every pool account
+pub async fn list_pools(State(app): State<Arc<AppState>>, Query(page): Query<Page>) -> ApiResult<PoolList> {
+    if let Some(db) = &app.db {
+        let rows: Vec<IndexedPool> = sqlx::query_as(
+            "SELECT * FROM pools WHERE ($1::TEXT IS NULL OR status = $1)
+             ORDER BY updated_slot DESC LIMIT $2 OFFSET $3",
+        )
+        .bind(&page.status)
+        .bind(page.limit())
+        .bind(page.offset())
+        .fetch_all(db)
+        .await?;
+        return Ok(Json(PoolList::Indexed(rows)));
+    }
+
+    let filter = RpcFilterType::Memcmp(Memcmp::new_raw_bytes(0, Pool::DISCRIMINATOR.to_vec()));
+    let accounts = app
+        .rpc
+        .get_program_accounts_with_config(
+            &app.program_id,
+            RpcProgramAccountsConfig {
+                filters: Some(vec![filter]),
+                account_config: RpcAccountInfoConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        )
+        .await?;
+
+    let mut pools: Vec<_> = accounts
+        .iter()
+        .filter_map(|(address, account)| {
+            let pool = Pool::try_from_account_data(&account.data).ok()?;
+            Some(PoolView::new(address.to_string(), &pool))
+        })
+        .filter(|view| page.status.as_deref().is_none_or(|status| view.status == status))
+        .collect();
+    pools.sort_by(|a, b| b.start_time.cmp(&a.start_time));
+    let pools = pools.into_iter().skip(page.offset() as usize).take(page.limit() as usize).collect();
+    Ok(Json(PoolList::Live(pools)))
+}
+
+/// `GET /pools/{id}`: live account state
+pub async fn get_pool(State(app): State<Arc<AppState>>, Path(id): Path<String>) -> ApiResult<PoolView> {
+    let address = parse_key(&id)?;
+    let pool = fetch_pool(&app, &address).await?;
+    Ok(Json(PoolView::new(id, &pool)))
+}
+
+/// `GET /pools/{id}/participants`: live participant slots
+pub async fn get_participants(
+    State(app): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> ApiResult<ParticipantsView> {
+    let address = parse_key(&id)?;
+    let pool = fetch_pool(&app, &address).await?;
+    let participants_address = Pubkey::new_from_array(pool.participants_account.to_bytes());
+    let data = fetch_data(&app, &participants_address).await?;
+    let participants = Participants::try_from_account_data(&data)
+        .map_err(|err| ApiError::Upstream(format!("participants {participants_address}: {err}")))?;
+    Ok(Json(ParticipantsView::new(id, &participants)))
+}
+
+/// `GET /users/{wallet}/history`: joins, donations, refunds and wins from the indexer
+pub async fn user_history(
+    State(app): State<Arc<AppState>>,
+    Path(wallet): Path<String>,
+    Query(page): Query<Page>,
+) -> ApiResult<UserHistory> {
+    parse_key(&wallet)?;
+    let db = app.db.as_ref().ok_or(ApiError::Unavailable("history requires the indexer database"))?;
+
+    let entries: Vec<HistoryEntry> = sqlx::query_as(
+        "SELECT signature, event_index, pool_id, action, amount, participant_rank, slot, block_time
+         FROM entries WHERE wallet = $1 ORDER BY slot DESC, event_index DESC LIMIT $2 OFFSET $3",
+    )
+    .bind(&wallet)
+    .bind(page.limit())
+    .bind(page.offset())
+    .fetch_all(db)
+    .await?;
+    let wins: Vec<Win> = sqlx::query_as(
+        "SELECT pool_id, winner_amount, randomness, signature, slot, block_time
+         FROM payouts WHERE winner = $1 ORDER BY slot DESC",
+    )
+    .bind(&wallet)
+    .fetch_all(db)
+    .await?;
+
+    Ok(Json(UserHistory { wallet, entries, wins }))
+}
+
+fn parse_key(value: &str) -> Result<Pubkey, ApiError> {
+    value.parse().map_err(|_| ApiError::BadRequest(format!("invalid pubkey: {value}")))
+}
+
+async fn fetch_pool(app: &AppState, address: &Pubkey) -> Result<Pool, ApiError> {
+    let data = fetch_data(app, address).await?;
+    Pool::try_from_account_data(&data).map_err(|_| ApiError::NotFound(format!("{address} is not a pool")))
+}
+
+async fn fetch_data(app: &AppState, address: &Pubkey) -> Result<Vec<u8>, ApiError> {
+    let account = app
+        .rpc
+        .get_account_with_commitment(address, app.rpc.commitment())
+        .await?
+        .value
+        .ok_or_else(|| ApiError::NotFound(format!("account {address} not found")))?;
+    if account.owner != app.program_id {
+        return Err(ApiError::NotFound(format!("{address} is not owned by the program")));
+    }
+    Ok(account.data)
+}
@@ -0,0 +1,168 @@
+//! Response bodies. Pubkeys are base58 strings and amounts are base units;
+//! u64/u128 values that may exceed 2^53 are serialized as strings so JS
+//! clients don't lose precision.
+
+use chrono::{DateTime, Utc};
+use ml_types::{
+    state::{Participants, Pool},
+    Pubkey,
+};
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct PoolView {
+    pub address: String,
+    pub pool_id: u64,
+    pub mint: String,
+    pub creator: String,
+    pub status: &'static str,
+    pub status_reason: u8,
+    pub paused: bool,
+    pub amount: String,
+    pub total_amount: String,
+    pub total_volume: String,
+    pub max_participants: u8,
+    pub total_joins: u32,
+    pub total_donations: u32,
+    pub dev_fee_bps: u16,
+    pub burn_fee_bps: u16,
+    pub treasury_fee_bps: u16,
+    pub start_time: i64,
+    pub expire_time: i64,
+    pub join_deadline: i64,
+    pub lock_end_time: Option<i64>,
+    pub unlock_time: i64,
+    pub close_time: i64,
+    pub winner: Option<String>,
+    pub randomness: Option<String>,
+    pub prize_mint: Option<String>,
+    pub compressed: bool,
+    pub tickets: bool,
+}
+
+impl PoolView {
+    pub fn new(address: String, pool: &Pool) -> Self {
+        Self {
+            address,
+            pool_id: pool.pool_id,
+            mint: pool.mint.to_string(),
+            creator: pool.creator.to_string(),
+            status: pool.status.as_str(),
+            status_reason: pool.status_reason,
+            paused: pool.paused,
+            amount: pool.amount.to_string(),
+            total_amount: pool.total_amount.to_string(),
+            total_volume: pool.total_volume.to_string(),
+            max_participants: pool.max_participants,
+            total_joins: pool.total_joins,
+            total_donations: pool.total_donations,
+            dev_fee_bps: pool.dev_fee_bps,
+            burn_fee_bps: pool.burn_fee_bps,
+            treasury_fee_bps: pool.treasury_fee_bps,
+            start_time: pool.start_time,
+            expire_time: pool.expire_time,
+            join_deadline: pool.join_deadline,
+            lock_end_time: pool.lock_end_time(),
+            unlock_time: pool.unlock_time,
+            close_time: pool.close_time,
+            winner: set(pool.winner),
+            randomness: (pool.randomness != 0).then(|| pool.randomness.to_string()),
+            prize_mint: pool.has_prize_nft().then(|| pool.prize_mint.to_string()),
+            compressed: pool.is_compressed(),
+            tickets: pool.has_tickets(),
+        }
+    }
+}
+
+/// Row of the indexer's `pools` table
+#[derive(Serialize, sqlx::FromRow)]
+pub struct IndexedPool {
+    pub pool_id: String,
+    pub numerical_pool_id: i64,
+    pub status: String,
+    pub status_reason: i16,
+    pub participant_count: i16,
+    #[serde(serialize_with = "as_string")]
+    pub total_amount: i64,
+    pub dev_fee_bps: Option<i32>,
+    pub burn_fee_bps: Option<i32>,
+    pub treasury_fee_bps: Option<i32>,
+    pub created_signature: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_slot: i64,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum PoolList {
+    Live(Vec<PoolView>),
+    Indexed(Vec<IndexedPool>),
+}
+
+#[derive(Serialize)]
+pub struct ParticipantView {
+    pub wallet: String,
+    pub amount: String,
+}
+
+#[derive(Serialize)]
+pub struct ParticipantsView {
+    pub pool: String,
+    pub count: u8,
+    pub participants: Vec<ParticipantView>,
+}
+
+impl ParticipantsView {
+    pub fn new(pool: String, participants: &Participants) -> Self {
+        Self {
+            pool,
+            count: participants.count,
+            participants: participants
+                .entries()
+                .map(|(wallet, amount)| ParticipantView { wallet: wallet.to_string(), amount: amount.to_string() })
+                .collect(),
+        }
+    }
+}
+
+/// Row of the indexer's `entries` table
+#[derive(Serialize, sqlx::FromRow)]
+pub struct HistoryEntry {
+    pub signature: String,
+    pub event_index: i32,
+    pub pool_id: String,
+    pub action: String,
+    #[serde(serialize_with = "as_string")]
+    pub amount: i64,
+    pub participant_rank: Option<i16>,
+    pub slot: i64,
+    pub block_time: Option<DateTime<Utc>>,
+}
+
+/// Row of the indexer's `payouts` table
+#[derive(Serialize, sqlx::FromRow)]
+pub struct Win {
+    pub pool_id: String,
+    #[serde(serialize_with = "as_string")]
+    pub winner_amount: i64,
+    pub randomness: String,
+    pub signature: String,
+    pub slot: i64,
+    pub block_time: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize)]
+pub struct UserHistory {
+    pub wallet: String,
+    pub entries: Vec<HistoryEntry>,
+    pub wins: Vec<Win>,
+}
+
+fn set(key: Pubkey) -> Option<String> {
+    (key != Pubkey::default()).then(|| key.to_string())
+}
+
+fn as_string<S: serde::Serializer>(value: &i64, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
@@ -0,0 +1,307 @@
+//! `ml-cli`: create, join and settle MissOut pools from the terminal.
+//!
+//! Amounts are in the mint's base units. Pools are addressed by their PDA.
+
+use std::{
+    path::PathBuf,
+    rc::Rc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anchor_client::{
+    solana_sdk::{
+        pubkey::Pubkey,
+        signature::{read_keypair_file, Keypair, Signer},
+    },
+    Cluster,
+};
+use anyhow::{anyhow, bail, Context, Result};
+use clap::{Parser, Subcommand};
+use ml::state::{DonationPurpose, PoolStatus};
+use ml_client::*;
+
+#[derive(Parser)]
+#[command(name = "ml-cli", version, about = "Manage MissOut pools")]
+struct Cli {
+    /// RPC URL or moniker (mainnet, devnet, localnet)
+    #[arg(short, long, env = "ML_RPC_URL", default_value = "devnet", global = true)]
+    url: Cluster,
+
+    /// Signer keypair file; also pays fees
+    #[arg(short, long, env = "ML_KEYPAIR", global = true)]
+    keypair: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create a pool; the creator takes the first slot
+    Create {
+        #[arg(long)]
+        mint: Pubkey,
+        /// Entry price
+        #[arg(long)]
+        amount: u64,
+        /// Entry price at expiry for ramped pools (defaults to `amount`)
+        #[arg(long)]
+        end_amount: Option<u64>,
+        #[arg(long, default_value_t = ml::constants::MAX_PARTICIPANTS as u8)]
+        max_participants: u8,
+        /// Seconds between the pool filling and unlock
+        #[arg(long, default_value_t = ml::constants::MIN_LOCK_DURATION)]
+        lock_duration: i64,
+        #[arg(long)]
+        dev_wallet: Pubkey,
+        #[arg(long)]
+        treasury_wallet: Pubkey,
+        /// Fees in basis points: dev,burn,treasury
+        #[arg(long, value_delimiter = ',', num_args = 3)]
+        fees: Option<Vec<u16>>,
+        /// Cap on the sum of donations (uncapped if omitted)
+        #[arg(long)]
+        max_donations: Option<u64>,
+        /// Unix time entries close; donations stay open until expiry (default: expiry)
+        #[arg(long)]
+        join_deadline: Option<i64>,
+        /// 32-byte hex salt (random if omitted)
+        #[arg(long)]
+        salt: Option<String>,
+        /// Refuse to create the pool unless the token analyzer scores the mint
+        /// at least this (0-100); a mint that can't be analyzed is refused too
+        #[cfg(feature = "risk-gate")]
+        #[arg(long, env = "ML_MIN_SAFETY_SCORE")]
+        min_safety_score: Option<f64>,
+    },
+    /// Join a pool at the current entry price
+    Join {
+        pool: Pubkey,
+        /// Defaults to the current entry price
+        #[arg(long)]
+        amount: Option<u64>,
+    },
+    /// Donate to a pool
+    Donate {
+        pool: Pubkey,
+        #[arg(long)]
+        amount: u64,
+        /// pot, prize or burn
+        #[arg(long, default_value = "pot", value_parser = parse_purpose)]
+        purpose: DonationPurpose,
+    },
+    /// Cancel a pool (creator)
+    Cancel { pool: Pubkey },
+    /// Print pool state and participants
+    Status { pool: Pubkey },
+    /// Lock a pool whose join deadline has passed
+    Lock { pool: Pubkey },
+    /// Unlock a pool whose lock window has elapsed
+    Unlock { pool: Pubkey },
+    /// Commit randomness, or select the winner once committed
+    Draw {
+        pool: Pubkey,
+        /// Switchboard randomness account (default pubkey = mock, test builds only)
+        #[arg(long, default_value_t = Pubkey::default())]
+        randomness: Pubkey,
+    },
+    /// Pay the selected winner and fees
+    Payout { pool: Pubkey },
+    /// Claim the signer's refund from a cancelled or expired pool
+    Refund { pool: Pubkey },
+}
+
+fn parse_purpose(s: &str) -> Result<DonationPurpose, String> {
+    match s {
+        "pot" => Ok(DonationPurpose::Pot),
+        "prize" => Ok(DonationPurpose::Prize),
+        "burn" => Ok(DonationPurpose::Burn),
+        _ => Err(format!("unknown purpose `{s}` (expected pot, prize or burn)")),
+    }
+}
+
+fn parse_salt(hex: Option<String>) -> Result<[u8; 32]> {
+    let Some(hex) = hex else {
+        return Ok(rand::random());
+    };
+    let hex = hex.trim_start_matches("0x");
+    if hex.len() != 64 {
+        bail!("salt must be 32 bytes of hex");
+    }
+    let mut salt = [0u8; 32];
+    for (i, byte) in salt.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).context("invalid salt hex")?;
+    }
+    Ok(salt)
+}
+
+fn load_keypair(path: Option<PathBuf>) -> Result<Keypair> {
+    let path = match path {
+        Some(path) => path,
+        None => {
+            let home = std::env::var("HOME").context("HOME not set; pass --keypair")?;
+            PathBuf::from(home).join(".config/solana/id.json")
+        }
+    };
+    read_keypair_file(&path).map_err(|e| anyhow!("reading keypair {}: {e}", path.display()))
+}
+
+fn now() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs() as i64)
+}
+
+fn print_status(client: &MlClient<Rc<Keypair>>, pool: &PoolRef) -> Result<()> {
+    let s = &pool.state;
+    println!("pool            {}", pool.address);
+    println!("status          {:?}{}", s.status, if s.paused { " (paused)" } else { "" });
+    println!("mint            {} ({})", s.mint, pool.token_program);
+    println!("creator         {}", s.creator);
+    println!("entry           {} -> {}", s.amount, s.end_amount);
+    println!("pot             {}", s.total_amount);
+    if s.max_donations_amount > 0 {
+        println!("donations       {} of {} cap", s.total_donated, s.max_donations_amount);
+    }
+    let lock_end = s.lock_end_time().map_err(|e| anyhow!("lock end: {e}"))?;
+    println!("lock            {}s from {}, ends {}", s.lock_duration, s.lock_start_time, lock_end);
+    if s.join_deadline < s.expire_time {
+        println!("joins close     {}", s.join_deadline);
+    }
+    println!("expires         {}", s.expire_time);
+    println!("fees (bps)      dev {} / burn {} / treasury {}", s.dev_fee_bps, s.burn_fee_bps, s.treasury_fee_bps);
+    if let Some(winner) = pool.winner() {
+        println!("winner          {winner}");
+    }
+    if let Some(p) = pool.payout_preview() {
+        println!(
+            "payout          winner {} (dust {}) / dev {} / burn {} / treasury {}",
+            p.winner_amount, p.dust, p.dev_amount, p.burn_amount, p.treasury_amount
+        );
+    }
+
+    let participants: ml::state::Participants = client.program().account(pool.participants())?;
+    println!("participants    {}/{}", participants.count, s.max_participants);
+    for (i, user) in participants.list[..participants.count as usize].iter().enumerate() {
+        println!("  {i:>2}  {user}");
+    }
+    Ok(())
+}
+
+/// `token_analyzer::assert_safe_for_pool` against the cluster the pool is created on.
+/// Nothing is cached or recorded: the gate leaves the analyzer's databases alone.
+#[cfg(feature = "risk-gate")]
+fn assert_safe_for_pool(cluster: &Cluster, mint: &Pubkey, min_score: f64) -> Result<()> {
+    let analyzer = token_analyzer::TokenAnalyzer::with_config(token_analyzer::AnalyzerConfig {
+        rpc_urls: vec![cluster.url().to_string()],
+        cluster: analyzer_cluster(cluster),
+        cache: None,
+        history: None,
+        ..Default::default()
+    })?;
+    let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build()?;
+    let analysis = runtime.block_on(analyzer.assert_safe_for_pool(&mint.to_string(), min_score))?;
+    eprintln!("{mint} scores {:.1}/100 ({} risk)", analysis.safe_score, analysis.risk_level);
+    Ok(())
+}
+
+/// The analyzer's cluster for `--url`: devnet endpoints get devnet's program ids
+/// and no mainnet wallets; local validators and unrecognized URLs are `custom`
+#[cfg(feature = "risk-gate")]
+fn analyzer_cluster(cluster: &Cluster) -> token_analyzer::Cluster {
+    match cluster {
+        Cluster::Mainnet => token_analyzer::Cluster::Mainnet,
+        Cluster::Devnet => token_analyzer::Cluster::Devnet,
+        Cluster::Custom(url, _) if url.contains("devnet") => token_analyzer::Cluster::Devnet,
+        Cluster::Custom(url, _) if url.contains("mainnet") => token_analyzer::Cluster::Mainnet,
+        _ => token_analyzer::Cluster::Custom,
+    }
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let signer = Rc::new(load_keypair(cli.keypair)?);
+    let user = signer.pubkey();
+    #[cfg(feature = "risk-gate")]
+    let cluster = cli.url.clone();
+    let client = MlClient::new(cli.url, signer)?;
+
+    let instruction = match cli.command {
+        Command::Create {
+            mint,
+            amount,
+            end_amount,
+            max_participants,
+            lock_duration,
+            dev_wallet,
+            treasury_wallet,
+            fees,
+            max_donations,
+            join_deadline,
+            salt,
+            #[cfg(feature = "risk-gate")]
+            min_safety_score,
+        } => {
+            #[cfg(feature = "risk-gate")]
+            if let Some(min_score) = min_safety_score {
+                assert_safe_for_pool(&cluster, &mint, min_score)?;
+            }
+            let salt = parse_salt(salt)?;
+            let mut builder = client
+                .create_pool(user, mint, salt, amount, dev_wallet, treasury_wallet)?
+                .max_participants(max_participants)
+                .lock_duration(lock_duration);
+            if let Some(end_amount) = end_amount {
+                builder = builder.end_amount(end_amount);
+            }
+            if let Some(fees) = fees {
+                builder = builder.fees(fees[0], fees[1], fees[2]);
+            }
+            if let Some(max_donations) = max_donations {
+                builder = builder.max_donations_amount(max_donations);
+            }
+            if let Some(join_deadline) = join_deadline {
+                builder = builder.join_deadline(join_deadline);
+            }
+            println!("pool {}", builder.pool_address());
+            builder.instruction()
+        }
+        Command::Join { pool, amount } => {
+            let pool = client.pool(pool)?;
+            let amount = match amount {
+                Some(amount) => amount,
+                None => pool.state.entry_amount_at(now()).map_err(|e| anyhow!("entry price: {e}"))?,
+            };
+            JoinPoolBuilder::new(&pool, user, amount).instruction()
+        }
+        Command::Donate { pool, amount, purpose } => {
+            let pool = client.pool(pool)?;
+            DonateBuilder::new(&pool, user, amount).purpose(purpose).instruction()
+        }
+        Command::Cancel { pool } => CancelPoolBuilder::new(&client.pool(pool)?, user).instruction(),
+        Command::Status { pool } => return print_status(&client, &client.pool(pool)?),
+        Command::Lock { pool } => LockAtDeadlineBuilder::new(&client.pool(pool)?, user).instruction(),
+        Command::Unlock { pool } => UnlockPoolBuilder::new(&client.pool(pool)?, user).instruction(),
+        Command::Draw { pool, randomness } => {
+            let pool = client.pool(pool)?;
+            match pool.state.status {
+                PoolStatus::Unlocked => RequestRandomnessBuilder::new(&pool, user, randomness).instruction(),
+                PoolStatus::RandomnessCommitted => SelectWinnerBuilder::new(&pool, user).instruction(),
+                status => bail!("pool is {status:?}; draw needs Unlocked or RandomnessCommitted"),
+            }
+        }
+        Command::Payout { pool } => {
+            let pool = client.pool(pool)?;
+            if pool.winner().is_none() {
+                bail!(Error::NoWinner(pool.address));
+            }
+            // Creates the winner's ATA in the same transaction if missing
+            let instructions = PayoutWinnerBuilder::new(&pool, user).instructions();
+            println!("{}", client.send(&instructions, &[])?);
+            return Ok(());
+        }
+        Command::Refund { pool } => ClaimRefundBuilder::new(&client.pool(pool)?, user).instruction(),
+    };
+
+    let signature = client.send(&[instruction], &[])?;
+    println!("{signature}");
+    Ok(())
+}
@@ -0,0 +1,154 @@
+//! ComputeBudget sizing. Unit limits come from a per-instruction table (payout
+//! with its four token CPIs needs several times what a join does) and the unit
+//! price from recent prioritization fees on the written accounts.
+
+use anchor_client::{
+    solana_client::rpc_client::RpcClient,
+    solana_sdk::{
+        compute_budget::{self, ComputeBudgetInstruction},
+        instruction::Instruction,
+        pubkey::Pubkey,
+    },
+};
+use anchor_lang::Discriminator;
+use ml::instruction as ix;
+
+/// Runtime ceiling per transaction
+pub const MAX_COMPUTE_UNITS: u32 = 1_400_000;
+/// Limit assumed for `ml` instructions missing from the table
+pub const DEFAULT_ML_UNITS: u32 = 200_000;
+/// System, ATA and token instructions bundled alongside
+pub const OTHER_INSTRUCTION_UNITS: u32 = 30_000;
+/// Added on top of the summed estimate, in percent
+pub const HEADROOM_PERCENT: u32 = 20;
+
+/// Measured upper bounds per `ml` instruction, before headroom
+const UNITS: &[(&[u8], u32)] = &[
+    (ix::CreatePool::DISCRIMINATOR, 120_000),
+    (ix::ClonePool::DISCRIMINATOR, 120_000),
+    (ix::JoinPool::DISCRIMINATOR, 150_000),
+    (ix::Donate::DISCRIMINATOR, 50_000),
+    (ix::SetLockDuration::DISCRIMINATOR, 15_000),
+    (ix::CancelPool::DISCRIMINATOR, 40_000),
+    (ix::AdminClosePool::DISCRIMINATOR, 80_000),
+    (ix::SweepExpiredPool::DISCRIMINATOR, 60_000),
+    (ix::ClaimRefund::DISCRIMINATOR, 60_000),
+    (ix::ClaimRent::DISCRIMINATOR, 40_000),
+    (ix::LockAtDeadline::DISCRIMINATOR, 20_000),
+    (ix::UnlockPool::DISCRIMINATOR, 20_000),
+    (ix::RequestRandomness::DISCRIMINATOR, 40_000),
+    (ix::SelectWinner::DISCRIMINATOR, 60_000),
+    (ix::SettleFromCallback::DISCRIMINATOR, 60_000),
+    (ix::PayoutWinner::DISCRIMINATOR, 260_000),
+    (ix::PausePool::DISCRIMINATOR, 15_000),
+    (ix::UnpausePool::DISCRIMINATOR, 15_000),
+    (ix::FinalizeForfeitedPool::DISCRIMINATOR, 80_000),
+    (ix::DepositPrizeNft::DISCRIMINATOR, 250_000),
+    (ix::ReleasePrizeNft::DISCRIMINATOR, 250_000),
+    (ix::SetPriceFeed::DISCRIMINATOR, 20_000),
+    (ix::CreateTeam::DISCRIMINATOR, 40_000),
+    (ix::ContributeTeam::DISCRIMINATOR, 50_000),
+    (ix::EnterTeam::DISCRIMINATOR, 80_000),
+    (ix::ClaimTeamRefund::DISCRIMINATOR, 50_000),
+    (ix::DistributeTeamPrize::DISCRIMINATOR, 300_000),
+    (ix::ProposeAdminClose::DISCRIMINATOR, 30_000),
+    (ix::WithdrawAdminClose::DISCRIMINATOR, 20_000),
+    (ix::EnableCompressedEntries::DISCRIMINATOR, 80_000),
+    (ix::JoinCompressed::DISCRIMINATOR, 120_000),
+    (ix::SelectWinnerCompressed::DISCRIMINATOR, 100_000),
+    (ix::ClaimCompressedRefund::DISCRIMINATOR, 120_000),
+    (ix::EnableTickets::DISCRIMINATOR, 120_000),
+    (ix::TransferEntry::DISCRIMINATOR, 40_000),
+];
+
+/// Unit price policy; see [`priority_fee`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PriorityFees {
+    /// Percentile of recent prioritization fees to pay
+    pub percentile: u8,
+    /// Floor in micro-lamports per compute unit
+    pub min: u64,
+    /// Cap in micro-lamports per compute unit
+    pub max: u64,
+    /// Fixed unit limit instead of the per-instruction estimate
+    pub unit_limit: Option<u32>,
+}
+
+impl Default for PriorityFees {
+    fn default() -> Self {
+        Self { percentile: 75, min: 0, max: 1_000_000, unit_limit: None }
+    }
+}
+
+/// Estimated units of one instruction, without headroom
+pub fn instruction_units(instruction: &Instruction) -> u32 {
+    if instruction.program_id != ml::ID {
+        return OTHER_INSTRUCTION_UNITS;
+    }
+    UNITS
+        .iter()
+        .find(|(disc, _)| instruction.data.starts_with(disc))
+        .map_or(DEFAULT_ML_UNITS, |(_, units)| *units)
+}
+
+/// Unit limit for a transaction made of `instructions`
+pub fn compute_unit_limit(instructions: &[Instruction]) -> u32 {
+    let units: u32 = instructions.iter().map(instruction_units).sum();
+    (units + units * HEADROOM_PERCENT / 100).min(MAX_COMPUTE_UNITS)
+}
+
+/// Compute unit price from recent prioritization fees on `accounts`: the
+/// configured percentile, doubled on every retry, clamped to `[min, max]`.
+pub fn priority_fee(rpc: &RpcClient, accounts: &[Pubkey], fees: &PriorityFees, attempt: u32) -> u64 {
+    let mut recent: Vec<u64> = rpc
+        .get_recent_prioritization_fees(accounts)
+        .map(|recent| recent.into_iter().map(|f| f.prioritization_fee).collect())
+        .unwrap_or_default();
+    recent.sort_unstable();
+
+    let base = match recent.len() {
+        0 => 0,
+        n => recent[(n - 1) * fees.percentile.min(100) as usize / 100],
+    };
+    base.saturating_mul(1 << attempt.min(16)).max(fees.min).min(fees.max)
+}
+
+pub fn compute_budget(unit_limit: u32, micro_lamports: u64) -> [Instruction; 2] {
+    [
+        ComputeBudgetInstruction::set_compute_unit_limit(unit_limit),
+        ComputeBudgetInstruction::set_compute_unit_price(micro_lamports),
+    ]
+}
+
+/// Writable accounts of `instructions`; prioritization fees are per written account
+pub fn writable_accounts(instructions: &[Instruction]) -> Vec<Pubkey> {
+    let mut accounts: Vec<Pubkey> = instructions
+        .iter()
+        .flat_map(|ix| ix.accounts.iter().filter(|meta| meta.is_writable).map(|meta| meta.pubkey))
+        .collect();
+    accounts.sort_unstable();
+    accounts.dedup();
+    accounts
+}
+
+/// `instructions` prefixed with a sized ComputeBudget pair and the unit price
+/// paid, or unchanged (price 0) when they already set their own budget
+pub fn with_compute_budget(
+    rpc: &RpcClient,
+    instructions: &[Instruction],
+    fees: &PriorityFees,
+    attempt: u32,
+) -> (Vec<Instruction>, u64) {
+    if instructions.iter().any(|ix| ix.program_id == compute_budget::ID) {
+        return (instructions.to_vec(), 0);
+    }
+    let limit = fees.unit_limit.unwrap_or_else(|| compute_unit_limit(instructions));
+    // getRecentPrioritizationFees accepts at most 128 addresses
+    let mut accounts = writable_accounts(instructions);
+    accounts.truncate(128);
+    let price = priority_fee(rpc, &accounts, fees, attempt);
+
+    let mut out = compute_budget(limit, price).to_vec();
+    out.extend_from_slice(instructions);
+    (out, price)
+}
@@ -0,0 +1,241 @@
+use anchor_lang::{prelude::Pubkey, solana_program::instruction::Instruction, system_program};
+
+use super::ix;
+use crate::{pda, PoolRef};
+
+/// `claim_refund` from a cancelled or expired pool
+#[derive(Clone)]
+pub struct ClaimRefundBuilder<'a> {
+    pool: &'a PoolRef,
+    user: Pubkey,
+    user_token: Option<Pubkey>,
+}
+
+impl<'a> ClaimRefundBuilder<'a> {
+    pub fn new(pool: &'a PoolRef, user: Pubkey) -> Self {
+        Self { pool, user, user_token: None }
+    }
+
+    pub fn user_token(mut self, user_token: Pubkey) -> Self {
+        self.user_token = Some(user_token);
+        self
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let pool = self.pool;
+        let accounts = ml::accounts::ClaimRefund {
+            mint: pool.mint(),
+            pool: pool.address,
+            pool_token: pool.pool_token(),
+            user_token: self.user_token.unwrap_or_else(|| pool.ata(&self.user)),
+            treasury_token: pool.ata(&pool.state.treasury_wallet),
+            user: self.user,
+            token_program: pool.token_program,
+            participants: pool.participants(),
+        };
+        ix(accounts, ml::instruction::ClaimRefund {})
+    }
+}
+
+/// `sweep_expired_pool`
+#[derive(Clone)]
+pub struct SweepExpiredPoolBuilder<'a> {
+    pool: &'a PoolRef,
+    user: Pubkey,
+}
+
+impl<'a> SweepExpiredPoolBuilder<'a> {
+    pub fn new(pool: &'a PoolRef, user: Pubkey) -> Self {
+        Self { pool, user }
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let pool = self.pool;
+        let accounts = ml::accounts::SweepExpiredPool {
+            mint: pool.mint(),
+            pool: pool.address,
+            pool_token: pool.pool_token(),
+            user: self.user,
+            token_program: pool.token_program,
+            system_program: system_program::ID,
+            participants: pool.participants(),
+        };
+        ix(accounts, ml::instruction::SweepExpiredPool {})
+    }
+}
+
+/// `finalize_forfeited_pool`: unclaimed refunds go to the treasury (dev or treasury wallet)
+#[derive(Clone)]
+pub struct FinalizeForfeitedPoolBuilder<'a> {
+    pool: &'a PoolRef,
+    user: Pubkey,
+}
+
+impl<'a> FinalizeForfeitedPoolBuilder<'a> {
+    pub fn new(pool: &'a PoolRef, user: Pubkey) -> Self {
+        Self { pool, user }
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let pool = self.pool;
+        let accounts = ml::accounts::ForfeitUnclaimed {
+            mint: pool.mint(),
+            pool: pool.address,
+            pool_token: pool.pool_token(),
+            treasury_token: pool.ata(&pool.state.treasury_wallet),
+            user: self.user,
+            token_program: pool.token_program,
+            participants: pool.participants(),
+        };
+        ix(accounts, ml::instruction::FinalizeForfeitedPool {})
+    }
+}
+
+/// `claim_rent`. Rent goes to the creator, or to the treasury when the dev wallet closes
+/// an abandoned pool. `archive()` keeps a `PoolArchive` record, paid by the caller.
+#[derive(Clone)]
+pub struct ClaimRentBuilder<'a> {
+    pool: &'a PoolRef,
+    user: Pubkey,
+    archive: bool,
+}
+
+impl<'a> ClaimRentBuilder<'a> {
+    pub fn new(pool: &'a PoolRef, user: Pubkey) -> Self {
+        Self { pool, user, archive: false }
+    }
+
+    pub fn archive(mut self) -> Self {
+        self.archive = true;
+        self
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let pool = self.pool;
+        let close_target = if self.user == pool.state.dev_wallet {
+            pool.state.treasury_wallet
+        } else {
+            pool.state.creator
+        };
+        let accounts = ml::accounts::ClaimRent {
+            pool: pool.address,
+            mint: pool.mint(),
+            pool_token: pool.pool_token(),
+            close_target,
+            user: self.user,
+            token_program: pool.token_program,
+            participants: pool.participants(),
+            pool_archive: self.archive.then(|| pda::pool_archive(&pool.address).0),
+            system_program: self.archive.then_some(system_program::ID),
+        };
+        ix(accounts, ml::instruction::ClaimRent {})
+    }
+}
+
+/// `propose_admin_close` (dev wallet); executable after `ADMIN_CLOSE_DELAY`
+#[derive(Clone)]
+pub struct ProposeAdminCloseBuilder<'a> {
+    pool: &'a PoolRef,
+    user: Pubkey,
+    reason_code: u8,
+    uri: String,
+    slash_bond: bool,
+}
+
+impl<'a> ProposeAdminCloseBuilder<'a> {
+    pub fn new(pool: &'a PoolRef, user: Pubkey, reason_code: u8, uri: impl Into<String>) -> Self {
+        Self { pool, user, reason_code, uri: uri.into(), slash_bond: false }
+    }
+
+    /// Send the creator bond to the treasury instead of back to the creator
+    pub fn slash_bond(mut self) -> Self {
+        self.slash_bond = true;
+        self
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let accounts = ml::accounts::ProposeAdminClose {
+            pool: self.pool.address,
+            proposal: pda::admin_close(&self.pool.address).0,
+            user: self.user,
+            system_program: system_program::ID,
+        };
+        let data = ml::instruction::ProposeAdminClose {
+            reason_code: self.reason_code,
+            uri: self.uri.clone(),
+            slash_bond: self.slash_bond,
+        };
+        ix(accounts, data)
+    }
+}
+
+/// `withdraw_admin_close` (dev wallet)
+#[derive(Clone)]
+pub struct WithdrawAdminCloseBuilder<'a> {
+    pool: &'a PoolRef,
+    user: Pubkey,
+}
+
+impl<'a> WithdrawAdminCloseBuilder<'a> {
+    pub fn new(pool: &'a PoolRef, user: Pubkey) -> Self {
+        Self { pool, user }
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let accounts = ml::accounts::WithdrawAdminClose {
+            pool: self.pool.address,
+            proposal: pda::admin_close(&self.pool.address).0,
+            user: self.user,
+        };
+        ix(accounts, ml::instruction::WithdrawAdminClose {})
+    }
+}
+
+/// `admin_close_pool`, executing a matured proposal
+#[derive(Clone)]
+pub struct AdminClosePoolBuilder<'a> {
+    pool: &'a PoolRef,
+    user: Pubkey,
+}
+
+impl<'a> AdminClosePoolBuilder<'a> {
+    pub fn new(pool: &'a PoolRef, user: Pubkey) -> Self {
+        Self { pool, user }
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let pool = self.pool;
+        let accounts = ml::accounts::AdminClosePool {
+            mint: pool.mint(),
+            pool: pool.address,
+            pool_token: pool.pool_token(),
+            creator_wallet: pool.state.creator,
+            user: self.user,
+            token_program: pool.token_program,
+            system_program: system_program::ID,
+            treasury_wallet: pool.state.treasury_wallet,
+            proposal: pda::admin_close(&pool.address).0,
+        };
+        ix(accounts, ml::instruction::AdminClosePool {})
+    }
+}
+
+/// `force_expire` (test-utils program builds only)
+#[cfg(feature = "test-utils")]
+#[derive(Clone)]
+pub struct ForceExpireBuilder<'a> {
+    pool: &'a PoolRef,
+    user: Pubkey,
+}
+
+#[cfg(feature = "test-utils")]
+impl<'a> ForceExpireBuilder<'a> {
+    pub fn new(pool: &'a PoolRef, user: Pubkey) -> Self {
+        Self { pool, user }
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let accounts = ml::accounts::ForceExpire { pool: self.pool.address, user: self.user };
+        ix(accounts, ml::instruction::ForceExpire {})
+    }
+}
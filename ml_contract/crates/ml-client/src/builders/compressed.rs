@@ -0,0 +1,224 @@
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::instruction::{AccountMeta, Instruction},
+    system_program,
+};
+use ml::{
+    compression::{ACCOUNT_COMPRESSION_ID, NOOP_ID},
+    tickets::BUBBLEGUM_ID,
+};
+
+use super::{ix, ix_with_remaining};
+use crate::{pda, PoolRef};
+
+fn proof_metas(proof: &[[u8; 32]]) -> Vec<AccountMeta> {
+    proof
+        .iter()
+        .map(|node| AccountMeta::new_readonly(Pubkey::new_from_array(*node), false))
+        .collect()
+}
+
+/// `enable_compressed_entries`. `merkle_tree` must be pre-allocated and owned by
+/// the account compression program.
+#[derive(Clone)]
+pub struct EnableCompressedEntriesBuilder<'a> {
+    pool: &'a PoolRef,
+    user: Pubkey,
+    merkle_tree: Pubkey,
+    max_depth: u32,
+    max_buffer_size: u32,
+    max_entries: u32,
+}
+
+impl<'a> EnableCompressedEntriesBuilder<'a> {
+    pub fn new(
+        pool: &'a PoolRef,
+        user: Pubkey,
+        merkle_tree: Pubkey,
+        max_depth: u32,
+        max_buffer_size: u32,
+        max_entries: u32,
+    ) -> Self {
+        Self { pool, user, merkle_tree, max_depth, max_buffer_size, max_entries }
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let accounts = ml::accounts::EnableCompressedEntries {
+            pool: self.pool.address,
+            participants: self.pool.participants(),
+            user: self.user,
+            merkle_tree: self.merkle_tree,
+            compression_program: ACCOUNT_COMPRESSION_ID,
+            noop_program: NOOP_ID,
+        };
+        let data = ml::instruction::EnableCompressedEntries {
+            max_depth: self.max_depth,
+            max_buffer_size: self.max_buffer_size,
+            max_entries: self.max_entries,
+        };
+        ix(accounts, data)
+    }
+}
+
+/// `join_compressed`
+#[derive(Clone)]
+pub struct JoinCompressedBuilder<'a> {
+    pool: &'a PoolRef,
+    user: Pubkey,
+    amount: u64,
+}
+
+impl<'a> JoinCompressedBuilder<'a> {
+    pub fn new(pool: &'a PoolRef, user: Pubkey, amount: u64) -> Self {
+        Self { pool, user, amount }
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let pool = self.pool;
+        let accounts = ml::accounts::JoinCompressed {
+            mint: pool.mint(),
+            pool: pool.address,
+            pool_token: pool.pool_token(),
+            user_token: pool.ata(&self.user),
+            user: self.user,
+            token_program: pool.token_program,
+            merkle_tree: pool.state.merkle_tree,
+            compression_program: ACCOUNT_COMPRESSION_ID,
+            noop_program: NOOP_ID,
+        };
+        ix(accounts, ml::instruction::JoinCompressed { amount: self.amount })
+    }
+}
+
+/// `select_winner_compressed`: proves the drawn leaf against `root`
+#[derive(Clone)]
+pub struct SelectWinnerCompressedBuilder<'a> {
+    pool: &'a PoolRef,
+    user: Pubkey,
+    root: [u8; 32],
+    winner: Pubkey,
+    winner_amount: u64,
+    proof: Vec<[u8; 32]>,
+}
+
+impl<'a> SelectWinnerCompressedBuilder<'a> {
+    pub fn new(
+        pool: &'a PoolRef,
+        user: Pubkey,
+        root: [u8; 32],
+        winner: Pubkey,
+        winner_amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Self {
+        Self { pool, user, root, winner, winner_amount, proof }
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let pool = self.pool;
+        let accounts = ml::accounts::SelectWinnerCompressed {
+            draw: ml::accounts::SelectWinner {
+                pool: pool.address,
+                randomness: pool.state.randomness_account,
+                user: self.user,
+                participants: pool.participants(),
+            },
+            merkle_tree: pool.state.merkle_tree,
+            compression_program: ACCOUNT_COMPRESSION_ID,
+        };
+        let data = ml::instruction::SelectWinnerCompressed {
+            root: self.root,
+            winner: self.winner,
+            winner_amount: self.winner_amount,
+        };
+        ix_with_remaining(accounts, data, proof_metas(&self.proof))
+    }
+}
+
+/// `claim_compressed_refund`: proves the caller's entry leaf at `index`
+#[derive(Clone)]
+pub struct ClaimCompressedRefundBuilder<'a> {
+    pool: &'a PoolRef,
+    user: Pubkey,
+    root: [u8; 32],
+    amount: u64,
+    index: u32,
+    proof: Vec<[u8; 32]>,
+}
+
+impl<'a> ClaimCompressedRefundBuilder<'a> {
+    pub fn new(
+        pool: &'a PoolRef,
+        user: Pubkey,
+        root: [u8; 32],
+        amount: u64,
+        index: u32,
+        proof: Vec<[u8; 32]>,
+    ) -> Self {
+        Self { pool, user, root, amount, index, proof }
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let pool = self.pool;
+        let accounts = ml::accounts::ClaimCompressedRefund {
+            mint: pool.mint(),
+            pool: pool.address,
+            pool_token: pool.pool_token(),
+            user_token: pool.ata(&self.user),
+            user: self.user,
+            token_program: pool.token_program,
+            merkle_tree: pool.state.merkle_tree,
+            compression_program: ACCOUNT_COMPRESSION_ID,
+            noop_program: NOOP_ID,
+        };
+        let data = ml::instruction::ClaimCompressedRefund {
+            root: self.root,
+            amount: self.amount,
+            index: self.index,
+        };
+        ix_with_remaining(accounts, data, proof_metas(&self.proof))
+    }
+}
+
+/// `enable_tickets`: creates the Bubblegum tree for cNFT entry tickets.
+/// `merkle_tree` must be pre-allocated for the account compression program.
+#[derive(Clone)]
+pub struct EnableTicketsBuilder<'a> {
+    pool: &'a PoolRef,
+    user: Pubkey,
+    payer: Pubkey,
+    merkle_tree: Pubkey,
+    max_depth: u32,
+    max_buffer_size: u32,
+}
+
+impl<'a> EnableTicketsBuilder<'a> {
+    pub fn new(pool: &'a PoolRef, user: Pubkey, merkle_tree: Pubkey, max_depth: u32, max_buffer_size: u32) -> Self {
+        Self { pool, user, payer: user, merkle_tree, max_depth, max_buffer_size }
+    }
+
+    pub fn payer(mut self, payer: Pubkey) -> Self {
+        self.payer = payer;
+        self
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let accounts = ml::accounts::EnableTickets {
+            pool: self.pool.address,
+            participants: self.pool.participants(),
+            user: self.user,
+            payer: self.payer,
+            creator_wallet: self.pool.state.creator,
+            tree_config: pda::ticket_tree_config(&self.merkle_tree).0,
+            merkle_tree: self.merkle_tree,
+            bubblegum_program: BUBBLEGUM_ID,
+            log_wrapper: NOOP_ID,
+            compression_program: ACCOUNT_COMPRESSION_ID,
+            system_program: system_program::ID,
+        };
+        let data = ml::instruction::EnableTickets {
+            max_depth: self.max_depth,
+            max_buffer_size: self.max_buffer_size,
+        };
+        ix(accounts, data)
+    }
+}
@@ -0,0 +1,197 @@
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::{instruction::Instruction, sysvar},
+};
+use anchor_spl::associated_token::spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+
+use super::ix;
+use crate::PoolRef;
+
+/// `lock_at_deadline`: locks an open pool with two or more entries once its join deadline has passed
+#[derive(Clone)]
+pub struct LockAtDeadlineBuilder<'a> {
+    pool: &'a PoolRef,
+    user: Pubkey,
+}
+
+impl<'a> LockAtDeadlineBuilder<'a> {
+    pub fn new(pool: &'a PoolRef, user: Pubkey) -> Self {
+        Self { pool, user }
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let accounts = ml::accounts::LockAtDeadline {
+            pool: self.pool.address,
+            user: self.user,
+            participants: self.pool.participants(),
+        };
+        ix(accounts, ml::instruction::LockAtDeadline {})
+    }
+}
+
+/// `unlock_pool` once the lock window has elapsed
+#[derive(Clone)]
+pub struct UnlockPoolBuilder<'a> {
+    pool: &'a PoolRef,
+    user: Pubkey,
+}
+
+impl<'a> UnlockPoolBuilder<'a> {
+    pub fn new(pool: &'a PoolRef, user: Pubkey) -> Self {
+        Self { pool, user }
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let accounts = ml::accounts::UnlockPool {
+            pool: self.pool.address,
+            user: self.user,
+            participants: self.pool.participants(),
+        };
+        ix(accounts, ml::instruction::UnlockPool {})
+    }
+}
+
+/// `request_randomness` against a committed Switchboard randomness account.
+/// `Pubkey::default()` requests mock randomness (test-utils builds, `allow_mock` pools).
+#[derive(Clone)]
+pub struct RequestRandomnessBuilder<'a> {
+    pool: &'a PoolRef,
+    user: Pubkey,
+    randomness: Pubkey,
+}
+
+impl<'a> RequestRandomnessBuilder<'a> {
+    pub fn new(pool: &'a PoolRef, user: Pubkey, randomness: Pubkey) -> Self {
+        Self { pool, user, randomness }
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let accounts = ml::accounts::RequestRandomness {
+            randomness: self.randomness,
+            pool: self.pool.address,
+            user: self.user,
+            participants: self.pool.participants(),
+        };
+        ix(accounts, ml::instruction::RequestRandomness {})
+    }
+}
+
+fn select_winner_accounts(pool: &PoolRef, user: Pubkey, randomness: Option<Pubkey>) -> ml::accounts::SelectWinner {
+    ml::accounts::SelectWinner {
+        pool: pool.address,
+        randomness: randomness.unwrap_or(pool.state.randomness_account),
+        user,
+        participants: pool.participants(),
+    }
+}
+
+/// `select_winner`, reading the randomness account committed by `request_randomness`
+#[derive(Clone)]
+pub struct SelectWinnerBuilder<'a> {
+    pool: &'a PoolRef,
+    user: Pubkey,
+    randomness: Option<Pubkey>,
+}
+
+impl<'a> SelectWinnerBuilder<'a> {
+    pub fn new(pool: &'a PoolRef, user: Pubkey) -> Self {
+        Self { pool, user, randomness: None }
+    }
+
+    /// Overrides `pool.randomness_account` (e.g. for emergency reveals)
+    pub fn randomness(mut self, randomness: Pubkey) -> Self {
+        self.randomness = Some(randomness);
+        self
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let accounts = select_winner_accounts(self.pool, self.user, self.randomness);
+        ix(accounts, ml::instruction::SelectWinner {})
+    }
+}
+
+/// `settle_from_callback`: must share a transaction with the Switchboard reveal
+#[derive(Clone)]
+pub struct SettleFromCallbackBuilder<'a> {
+    pool: &'a PoolRef,
+    user: Pubkey,
+}
+
+impl<'a> SettleFromCallbackBuilder<'a> {
+    pub fn new(pool: &'a PoolRef, user: Pubkey) -> Self {
+        Self { pool, user }
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let accounts = ml::accounts::SettleFromCallback {
+            settle: select_winner_accounts(self.pool, self.user, None),
+            instructions_sysvar: sysvar::instructions::ID,
+        };
+        ix(accounts, ml::instruction::SettleFromCallback {})
+    }
+}
+
+/// `payout_winner`. Pays any token account the winner owns, their ATA by default;
+/// NFT-prize pools also pay the creator's ATA.
+#[derive(Clone)]
+pub struct PayoutWinnerBuilder<'a> {
+    pool: &'a PoolRef,
+    user: Pubkey,
+    winner: Option<Pubkey>,
+    winner_token: Option<Pubkey>,
+}
+
+impl<'a> PayoutWinnerBuilder<'a> {
+    pub fn new(pool: &'a PoolRef, user: Pubkey) -> Self {
+        Self { pool, user, winner: None, winner_token: None }
+    }
+
+    /// Defaults to `pool.winner`
+    pub fn winner(mut self, winner: Pubkey) -> Self {
+        self.winner = Some(winner);
+        self
+    }
+
+    /// Defaults to the winner's ATA
+    pub fn winner_token(mut self, winner_token: Pubkey) -> Self {
+        self.winner_token = Some(winner_token);
+        self
+    }
+
+    /// The payout, preceded by an idempotent create of the winner's ATA (paid by `user`)
+    /// unless `winner_token` was given
+    pub fn instructions(&self) -> Vec<Instruction> {
+        let create_ata = self.winner_token.is_none().then(|| {
+            create_associated_token_account_idempotent(
+                &self.user,
+                &self.winner_key(),
+                &self.pool.mint(),
+                &self.pool.token_program,
+            )
+        });
+        create_ata.into_iter().chain([self.instruction()]).collect()
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let pool = self.pool;
+        let winner = self.winner_key();
+        let accounts = ml::accounts::PayoutWinner {
+            mint: pool.mint(),
+            pool: pool.address,
+            pool_token: pool.pool_token(),
+            winner_token: self.winner_token.unwrap_or_else(|| pool.ata(&winner)),
+            dev_token: pool.ata(&pool.state.dev_wallet),
+            treasury_token: pool.ata(&pool.state.treasury_wallet),
+            creator_token: pool.state.has_prize_nft().then(|| pool.ata(&pool.state.creator)),
+            token_program: pool.token_program,
+            winner_pubkey: winner,
+            user: self.user,
+            participants: pool.participants(),
+        };
+        ix(accounts, ml::instruction::PayoutWinner {})
+    }
+
+    fn winner_key(&self) -> Pubkey {
+        self.winner.unwrap_or(self.pool.state.winner)
+    }
+}
@@ -0,0 +1,38 @@
+//! One builder per program instruction.
+//!
+//! Required inputs go to `new`, optional overrides are chained setters, and
+//! `instruction()` returns a ready-to-sign `Instruction`. Builders for existing
+//! pools take a [`PoolRef`](crate::PoolRef), which carries the mint's token program.
+
+mod close;
+mod compressed;
+mod draw;
+mod pool;
+mod prize;
+mod team;
+
+pub use close::*;
+pub use compressed::*;
+pub use draw::*;
+pub use pool::*;
+pub use prize::*;
+pub use team::*;
+
+use anchor_lang::{
+    solana_program::instruction::{AccountMeta, Instruction},
+    InstructionData, ToAccountMetas,
+};
+
+fn ix(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    ix_with_remaining(accounts, data, Vec::new())
+}
+
+fn ix_with_remaining(
+    accounts: impl ToAccountMetas,
+    data: impl InstructionData,
+    remaining: Vec<AccountMeta>,
+) -> Instruction {
+    let mut metas = accounts.to_account_metas(None);
+    metas.extend(remaining);
+    Instruction { program_id: ml::ID, accounts: metas, data: data.data() }
+}
@@ -0,0 +1,428 @@
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::{instruction::Instruction, sysvar},
+    system_program,
+};
+use anchor_spl::associated_token;
+use ml::{
+    compression::{ACCOUNT_COMPRESSION_ID, NOOP_ID},
+    constants::*,
+    state::DonationPurpose,
+    tickets::BUBBLEGUM_ID,
+};
+
+use super::ix;
+use crate::{pda, PoolRef};
+
+/// Fee split used when none is set: 5% dev, 2% burn, 3% treasury
+pub const DEFAULT_DEV_FEE_BPS: u16 = 500;
+pub const DEFAULT_BURN_FEE_BPS: u16 = 200;
+pub const DEFAULT_TREASURY_FEE_BPS: u16 = 300;
+
+/// `create_pool`. `pool_count` must be the creator's `CreatorPoolCounter::pool_count`
+/// (0 for a first pool); [`MlClient::create_pool`](crate::MlClient::create_pool) fills it in.
+#[derive(Clone, Debug)]
+pub struct CreatePoolBuilder {
+    creator: Pubkey,
+    payer: Pubkey,
+    mint: Pubkey,
+    token_program: Pubkey,
+    salt: [u8; 32],
+    pool_count: u64,
+    max_participants: u8,
+    lock_duration: i64,
+    amount: u64,
+    end_amount: Option<u64>,
+    dev_wallet: Pubkey,
+    dev_fee_bps: u16,
+    burn_fee_bps: u16,
+    treasury_wallet: Pubkey,
+    treasury_fee_bps: u16,
+    allow_mock: bool,
+    min_bet_override: u64,
+    max_donations_amount: u64,
+    join_deadline: i64,
+    price_update: Option<Pubkey>,
+}
+
+impl CreatePoolBuilder {
+    pub fn new(
+        creator: Pubkey,
+        mint: Pubkey,
+        token_program: Pubkey,
+        salt: [u8; 32],
+        amount: u64,
+        dev_wallet: Pubkey,
+        treasury_wallet: Pubkey,
+    ) -> Self {
+        Self {
+            creator,
+            payer: creator,
+            mint,
+            token_program,
+            salt,
+            pool_count: 0,
+            max_participants: MAX_PARTICIPANTS as u8,
+            lock_duration: MIN_LOCK_DURATION,
+            amount,
+            end_amount: None,
+            dev_wallet,
+            dev_fee_bps: DEFAULT_DEV_FEE_BPS,
+            burn_fee_bps: DEFAULT_BURN_FEE_BPS,
+            treasury_wallet,
+            treasury_fee_bps: DEFAULT_TREASURY_FEE_BPS,
+            allow_mock: false,
+            min_bet_override: 0,
+            max_donations_amount: 0,
+            join_deadline: 0,
+            price_update: None,
+        }
+    }
+
+    pub fn payer(mut self, payer: Pubkey) -> Self {
+        self.payer = payer;
+        self
+    }
+
+    pub fn pool_count(mut self, pool_count: u64) -> Self {
+        self.pool_count = pool_count;
+        self
+    }
+
+    pub fn max_participants(mut self, max_participants: u8) -> Self {
+        self.max_participants = max_participants;
+        self
+    }
+
+    pub fn lock_duration(mut self, lock_duration: i64) -> Self {
+        self.lock_duration = lock_duration;
+        self
+    }
+
+    /// Entry price at `expire_time` for ramped pools; defaults to `amount` (flat)
+    pub fn end_amount(mut self, end_amount: u64) -> Self {
+        self.end_amount = Some(end_amount);
+        self
+    }
+
+    pub fn fees(mut self, dev_fee_bps: u16, burn_fee_bps: u16, treasury_fee_bps: u16) -> Self {
+        self.dev_fee_bps = dev_fee_bps;
+        self.burn_fee_bps = burn_fee_bps;
+        self.treasury_fee_bps = treasury_fee_bps;
+        self
+    }
+
+    /// Only honoured by `test-utils` program builds
+    pub fn allow_mock(mut self, allow_mock: bool) -> Self {
+        self.allow_mock = allow_mock;
+        self
+    }
+
+    pub fn min_bet_override(mut self, min_bet_override: u64) -> Self {
+        self.min_bet_override = min_bet_override;
+        self
+    }
+
+    /// Cap on the sum of donations in native units; 0 (the default) leaves them uncapped
+    pub fn max_donations_amount(mut self, max_donations_amount: u64) -> Self {
+        self.max_donations_amount = max_donations_amount;
+        self
+    }
+
+    /// Unix time entries close, before the pool expires; donations are taken until expiry.
+    /// 0 (the default) keeps entries open until expiry
+    pub fn join_deadline(mut self, join_deadline: i64) -> Self {
+        self.join_deadline = join_deadline;
+        self
+    }
+
+    /// Pyth `PriceUpdateV2` account, required when the mint has a registered price feed
+    pub fn price_update(mut self, price_update: Pubkey) -> Self {
+        self.price_update = Some(price_update);
+        self
+    }
+
+    pub fn pool_address(&self) -> Pubkey {
+        pda::pool(&self.mint, &self.salt).0
+    }
+
+    pub(crate) fn accounts(&self) -> ml::accounts::CreatePool {
+        let pool = self.pool_address();
+        ml::accounts::CreatePool {
+            mint: self.mint,
+            pool,
+            user_token: pda::ata(&self.creator, &self.mint, &self.token_program),
+            user: self.creator,
+            payer: self.payer,
+            pool_token: pda::ata(&pool, &self.mint, &self.token_program),
+            token_program: self.token_program,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+            participants: pda::participants(&pool).0,
+            creator_counter: pda::creator_counter(&self.creator).0,
+            creator_pool_index: pda::creator_pool_index(&self.creator, self.pool_count).0,
+            price_feed: pda::price_feed(&self.mint).0,
+            price_update: self.price_update,
+        }
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let data = ml::instruction::CreatePool {
+            salt: self.salt,
+            max_participants: self.max_participants,
+            lock_duration: self.lock_duration,
+            amount: self.amount,
+            dev_wallet: self.dev_wallet,
+            dev_fee_bps: self.dev_fee_bps,
+            burn_fee_bps: self.burn_fee_bps,
+            treasury_wallet: self.treasury_wallet,
+            treasury_fee_bps: self.treasury_fee_bps,
+            allow_mock: self.allow_mock,
+            end_amount: self.end_amount.unwrap_or(self.amount),
+            min_bet_override: self.min_bet_override,
+            max_donations_amount: self.max_donations_amount,
+            join_deadline: self.join_deadline,
+        };
+        ix(self.accounts(), data)
+    }
+}
+
+/// `clone_pool`: a new pool under `salt` with the configuration of `source`
+#[derive(Clone, Debug)]
+pub struct ClonePoolBuilder {
+    create: CreatePoolBuilder,
+    source_pool: Pubkey,
+}
+
+impl ClonePoolBuilder {
+    pub fn new(source: &PoolRef, creator: Pubkey, salt: [u8; 32]) -> Self {
+        let create = CreatePoolBuilder::new(
+            creator,
+            source.mint(),
+            source.token_program,
+            salt,
+            source.state.amount,
+            source.state.dev_wallet,
+            source.state.treasury_wallet,
+        );
+        Self { create, source_pool: source.address }
+    }
+
+    pub fn payer(mut self, payer: Pubkey) -> Self {
+        self.create = self.create.payer(payer);
+        self
+    }
+
+    pub fn pool_count(mut self, pool_count: u64) -> Self {
+        self.create = self.create.pool_count(pool_count);
+        self
+    }
+
+    pub fn price_update(mut self, price_update: Pubkey) -> Self {
+        self.create = self.create.price_update(price_update);
+        self
+    }
+
+    pub fn pool_address(&self) -> Pubkey {
+        self.create.pool_address()
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let accounts = ml::accounts::ClonePool {
+            create: self.create.accounts(),
+            source_pool: self.source_pool,
+        };
+        ix(accounts, ml::instruction::ClonePool { salt: self.create.salt })
+    }
+}
+
+/// `join_pool`. Ticket accounts are added automatically for pools with tickets enabled.
+#[derive(Clone)]
+pub struct JoinPoolBuilder<'a> {
+    pool: &'a PoolRef,
+    user: Pubkey,
+    amount: u64,
+    user_token: Option<Pubkey>,
+}
+
+impl<'a> JoinPoolBuilder<'a> {
+    pub fn new(pool: &'a PoolRef, user: Pubkey, amount: u64) -> Self {
+        Self { pool, user, amount, user_token: None }
+    }
+
+    /// Source token account; defaults to the user's ATA
+    pub fn user_token(mut self, user_token: Pubkey) -> Self {
+        self.user_token = Some(user_token);
+        self
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let pool = self.pool;
+        let tickets = pool.state.has_tickets();
+        let ticket = |key: Pubkey| tickets.then_some(key);
+        let accounts = ml::accounts::JoinPool {
+            mint: pool.mint(),
+            pool: pool.address,
+            pool_token: pool.pool_token(),
+            user_token: self.user_token.unwrap_or_else(|| pool.ata(&self.user)),
+            user: self.user,
+            token_program: pool.token_program,
+            participants: pool.participants(),
+            ticket_tree_config: ticket(pda::ticket_tree_config(&pool.state.ticket_tree).0),
+            ticket_tree: ticket(pool.state.ticket_tree),
+            bubblegum_program: ticket(BUBBLEGUM_ID),
+            log_wrapper: ticket(NOOP_ID),
+            compression_program: ticket(ACCOUNT_COMPRESSION_ID),
+            system_program: ticket(system_program::ID),
+        };
+        ix(accounts, ml::instruction::JoinPool { amount: self.amount })
+    }
+}
+
+/// `donate`, to the shared pot unless another purpose is set
+#[derive(Clone)]
+pub struct DonateBuilder<'a> {
+    pool: &'a PoolRef,
+    user: Pubkey,
+    amount: u64,
+    purpose: DonationPurpose,
+    user_token: Option<Pubkey>,
+}
+
+impl<'a> DonateBuilder<'a> {
+    pub fn new(pool: &'a PoolRef, user: Pubkey, amount: u64) -> Self {
+        Self { pool, user, amount, purpose: DonationPurpose::Pot, user_token: None }
+    }
+
+    pub fn purpose(mut self, purpose: DonationPurpose) -> Self {
+        self.purpose = purpose;
+        self
+    }
+
+    pub fn user_token(mut self, user_token: Pubkey) -> Self {
+        self.user_token = Some(user_token);
+        self
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let pool = self.pool;
+        let accounts = ml::accounts::Donate {
+            mint: pool.mint(),
+            pool: pool.address,
+            pool_token: pool.pool_token(),
+            user_token: self.user_token.unwrap_or_else(|| pool.ata(&self.user)),
+            user: self.user,
+            token_program: pool.token_program,
+            participants: pool.participants(),
+        };
+        ix(accounts, ml::instruction::Donate { amount: self.amount, purpose: self.purpose })
+    }
+}
+
+/// `set_lock_duration` (creator, before anyone else joins)
+#[derive(Clone)]
+pub struct SetLockDurationBuilder<'a> {
+    pool: &'a PoolRef,
+    user: Pubkey,
+    new_lock_duration: i64,
+}
+
+impl<'a> SetLockDurationBuilder<'a> {
+    pub fn new(pool: &'a PoolRef, user: Pubkey, new_lock_duration: i64) -> Self {
+        Self { pool, user, new_lock_duration }
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let accounts = ml::accounts::SetLockDuration {
+            pool: self.pool.address,
+            user: self.user,
+            participants: self.pool.participants(),
+        };
+        ix(accounts, ml::instruction::SetLockDuration { new_lock_duration: self.new_lock_duration })
+    }
+}
+
+/// `cancel_pool` (creator)
+#[derive(Clone)]
+pub struct CancelPoolBuilder<'a> {
+    pool: &'a PoolRef,
+    user: Pubkey,
+}
+
+impl<'a> CancelPoolBuilder<'a> {
+    pub fn new(pool: &'a PoolRef, user: Pubkey) -> Self {
+        Self { pool, user }
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let pool = self.pool;
+        let accounts = ml::accounts::CancelPool {
+            mint: pool.mint(),
+            pool: pool.address,
+            pool_token: pool.pool_token(),
+            user: self.user,
+            token_program: pool.token_program,
+            system_program: system_program::ID,
+            participants: pool.participants(),
+            treasury_wallet: pool.state.treasury_wallet,
+        };
+        ix(accounts, ml::instruction::CancelPool {})
+    }
+}
+
+/// `pause_pool` / `unpause_pool` (dev wallet)
+#[derive(Clone)]
+pub struct PausePoolBuilder<'a> {
+    pool: &'a PoolRef,
+    user: Pubkey,
+    unpause: bool,
+}
+
+impl<'a> PausePoolBuilder<'a> {
+    pub fn new(pool: &'a PoolRef, user: Pubkey) -> Self {
+        Self { pool, user, unpause: false }
+    }
+
+    pub fn unpause(mut self) -> Self {
+        self.unpause = true;
+        self
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let accounts = ml::accounts::PausePool {
+            pool: self.pool.address,
+            user: self.user,
+            participants: self.pool.participants(),
+        };
+        if self.unpause {
+            ix(accounts, ml::instruction::UnpausePool {})
+        } else {
+            ix(accounts, ml::instruction::PausePool {})
+        }
+    }
+}
+
+/// `transfer_entry`: hands the caller's slot to `new_owner`
+#[derive(Clone)]
+pub struct TransferEntryBuilder<'a> {
+    pool: &'a PoolRef,
+    user: Pubkey,
+    new_owner: Pubkey,
+}
+
+impl<'a> TransferEntryBuilder<'a> {
+    pub fn new(pool: &'a PoolRef, user: Pubkey, new_owner: Pubkey) -> Self {
+        Self { pool, user, new_owner }
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let accounts = ml::accounts::TransferEntry {
+            pool: self.pool.address,
+            participants: self.pool.participants(),
+            user: self.user,
+            new_owner: self.new_owner,
+        };
+        ix(accounts, ml::instruction::TransferEntry {})
+    }
+}
@@ -0,0 +1,180 @@
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::{instruction::Instruction, sysvar},
+    system_program,
+};
+use anchor_spl::{
+    associated_token,
+    metadata::mpl_token_metadata::{
+        accounts::{MasterEdition, Metadata, TokenRecord},
+        ID as TOKEN_METADATA_ID,
+    },
+};
+use ml::constants::ZERO_PUBKEY;
+
+use super::ix;
+use crate::{pda, PoolRef};
+
+/// Token Auth Rules program, required with a pNFT rule set
+pub const AUTH_RULES_PROGRAM_ID: Pubkey =
+    anchor_lang::pubkey!("auth9SigNpDKz4sJJ1DfCTuZrZNSAgh9sFD3rboVmgg");
+
+/// Token Metadata accounts for a programmable NFT transfer
+#[derive(Clone, Copy, Debug)]
+struct Pnft {
+    metadata: Pubkey,
+    edition: Pubkey,
+    owner_token_record: Pubkey,
+    destination_token_record: Pubkey,
+    authorization_rules: Option<Pubkey>,
+}
+
+impl Pnft {
+    fn derive(mint: &Pubkey, from: &Pubkey, to: &Pubkey, authorization_rules: Option<Pubkey>) -> Self {
+        Self {
+            metadata: Metadata::find_pda(mint).0,
+            edition: MasterEdition::find_pda(mint).0,
+            owner_token_record: TokenRecord::find_pda(mint, from).0,
+            destination_token_record: TokenRecord::find_pda(mint, to).0,
+            authorization_rules,
+        }
+    }
+}
+
+/// `deposit_prize_nft`: the creator escrows an NFT as the prize
+#[derive(Clone)]
+pub struct DepositPrizeNftBuilder<'a> {
+    pool: &'a PoolRef,
+    user: Pubkey,
+    payer: Pubkey,
+    prize_mint: Pubkey,
+    prize_token_program: Pubkey,
+    pnft: Option<Option<Pubkey>>,
+}
+
+impl<'a> DepositPrizeNftBuilder<'a> {
+    pub fn new(pool: &'a PoolRef, user: Pubkey, prize_mint: Pubkey, prize_token_program: Pubkey) -> Self {
+        Self { pool, user, payer: user, prize_mint, prize_token_program, pnft: None }
+    }
+
+    pub fn payer(mut self, payer: Pubkey) -> Self {
+        self.payer = payer;
+        self
+    }
+
+    /// Transfer through Token Metadata, with the rule set if the pNFT has one
+    pub fn pnft(mut self, authorization_rules: Option<Pubkey>) -> Self {
+        self.pnft = Some(authorization_rules);
+        self
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let pool = self.pool;
+        let creator_prize_token = pda::ata(&self.user, &self.prize_mint, &self.prize_token_program);
+        let pool_prize_token = pda::ata(&pool.address, &self.prize_mint, &self.prize_token_program);
+        let pnft = self
+            .pnft
+            .map(|rules| Pnft::derive(&self.prize_mint, &creator_prize_token, &pool_prize_token, rules));
+        let accounts = ml::accounts::DepositPrizeNft {
+            pool: pool.address,
+            participants: pool.participants(),
+            user: self.user,
+            payer: self.payer,
+            prize_mint: self.prize_mint,
+            creator_prize_token,
+            pool_prize_token,
+            prize_token_program: self.prize_token_program,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            token_metadata_program: pnft.map(|_| TOKEN_METADATA_ID),
+            metadata: pnft.map(|p| p.metadata),
+            edition: pnft.map(|p| p.edition),
+            owner_token_record: pnft.map(|p| p.owner_token_record),
+            destination_token_record: pnft.map(|p| p.destination_token_record),
+            sysvar_instructions: pnft.map(|_| sysvar::instructions::ID),
+            authorization_rules_program: pnft.and_then(|p| p.authorization_rules).map(|_| AUTH_RULES_PROGRAM_ID),
+            authorization_rules: pnft.and_then(|p| p.authorization_rules),
+        };
+        ix(accounts, ml::instruction::DepositPrizeNft {})
+    }
+}
+
+/// `release_prize_nft`: to the winner once paid out, back to the creator if cancelled
+#[derive(Clone)]
+pub struct ReleasePrizeNftBuilder<'a> {
+    pool: &'a PoolRef,
+    user: Pubkey,
+    prize_token_program: Pubkey,
+    pnft: Option<Option<Pubkey>>,
+}
+
+impl<'a> ReleasePrizeNftBuilder<'a> {
+    pub fn new(pool: &'a PoolRef, user: Pubkey, prize_token_program: Pubkey) -> Self {
+        Self { pool, user, prize_token_program, pnft: None }
+    }
+
+    pub fn pnft(mut self, authorization_rules: Option<Pubkey>) -> Self {
+        self.pnft = Some(authorization_rules);
+        self
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let pool = self.pool;
+        let prize_mint = pool.state.prize_mint;
+        let recipient = if pool.state.winner != ZERO_PUBKEY {
+            pool.state.winner
+        } else {
+            pool.state.creator
+        };
+        let recipient_prize_token = pda::ata(&recipient, &prize_mint, &self.prize_token_program);
+        let pnft = self
+            .pnft
+            .map(|rules| Pnft::derive(&prize_mint, &pool.state.prize_token, &recipient_prize_token, rules));
+        let accounts = ml::accounts::ReleasePrizeNft {
+            pool: pool.address,
+            user: self.user,
+            recipient,
+            creator_wallet: pool.state.creator,
+            prize_mint,
+            pool_prize_token: pool.state.prize_token,
+            recipient_prize_token,
+            prize_token_program: self.prize_token_program,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            token_metadata_program: pnft.map(|_| TOKEN_METADATA_ID),
+            metadata: pnft.map(|p| p.metadata),
+            edition: pnft.map(|p| p.edition),
+            owner_token_record: pnft.map(|p| p.owner_token_record),
+            destination_token_record: pnft.map(|p| p.destination_token_record),
+            sysvar_instructions: pnft.map(|_| sysvar::instructions::ID),
+            authorization_rules_program: pnft.and_then(|p| p.authorization_rules).map(|_| AUTH_RULES_PROGRAM_ID),
+            authorization_rules: pnft.and_then(|p| p.authorization_rules),
+        };
+        ix(accounts, ml::instruction::ReleasePrizeNft {})
+    }
+}
+
+/// `set_price_feed` (platform admin): registers the Pyth feed used for USD minimums
+#[derive(Clone, Debug)]
+pub struct SetPriceFeedBuilder {
+    admin: Pubkey,
+    mint: Pubkey,
+    feed_id: [u8; 32],
+    max_age: u64,
+}
+
+impl SetPriceFeedBuilder {
+    pub fn new(admin: Pubkey, mint: Pubkey, feed_id: [u8; 32], max_age: u64) -> Self {
+        Self { admin, mint, feed_id, max_age }
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let accounts = ml::accounts::SetPriceFeed {
+            mint: self.mint,
+            price_feed: pda::price_feed(&self.mint).0,
+            admin: self.admin,
+            system_program: system_program::ID,
+        };
+        ix(accounts, ml::instruction::SetPriceFeed { feed_id: self.feed_id, max_age: self.max_age })
+    }
+}
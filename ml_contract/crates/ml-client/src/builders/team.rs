@@ -0,0 +1,159 @@
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::instruction::{AccountMeta, Instruction},
+    system_program,
+};
+use anchor_spl::associated_token;
+use ml::state::Team;
+
+use super::{ix, ix_with_remaining};
+use crate::{pda, PoolRef};
+
+/// `create_team`: `captain` opens a team for the pool
+#[derive(Clone)]
+pub struct CreateTeamBuilder<'a> {
+    pool: &'a PoolRef,
+    captain: Pubkey,
+}
+
+impl<'a> CreateTeamBuilder<'a> {
+    pub fn new(pool: &'a PoolRef, captain: Pubkey) -> Self {
+        Self { pool, captain }
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let accounts = ml::accounts::CreateTeam {
+            pool: self.pool.address,
+            team: self.pool.team(&self.captain),
+            captain: self.captain,
+            system_program: system_program::ID,
+        };
+        ix(accounts, ml::instruction::CreateTeam {})
+    }
+}
+
+/// `contribute_team`: `member` pays into the team vault of `captain`'s team
+#[derive(Clone)]
+pub struct ContributeTeamBuilder<'a> {
+    pool: &'a PoolRef,
+    captain: Pubkey,
+    member: Pubkey,
+    amount: u64,
+}
+
+impl<'a> ContributeTeamBuilder<'a> {
+    pub fn new(pool: &'a PoolRef, captain: Pubkey, member: Pubkey, amount: u64) -> Self {
+        Self { pool, captain, member, amount }
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let pool = self.pool;
+        let team = pool.team(&self.captain);
+        let accounts = ml::accounts::ContributeTeam {
+            mint: pool.mint(),
+            pool: pool.address,
+            team,
+            team_token: pool.ata(&team),
+            member_token: pool.ata(&self.member),
+            member: self.member,
+            token_program: pool.token_program,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        };
+        ix(accounts, ml::instruction::ContributeTeam { amount: self.amount })
+    }
+}
+
+/// `enter_team`: the captain joins the pool from the team vault
+#[derive(Clone)]
+pub struct EnterTeamBuilder<'a> {
+    pool: &'a PoolRef,
+    captain: Pubkey,
+    amount: u64,
+}
+
+impl<'a> EnterTeamBuilder<'a> {
+    pub fn new(pool: &'a PoolRef, captain: Pubkey, amount: u64) -> Self {
+        Self { pool, captain, amount }
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let pool = self.pool;
+        let team = pool.team(&self.captain);
+        let accounts = ml::accounts::EnterTeam {
+            mint: pool.mint(),
+            pool: pool.address,
+            pool_token: pool.pool_token(),
+            team,
+            team_token: pool.ata(&team),
+            captain: self.captain,
+            token_program: pool.token_program,
+            participants: pool.participants(),
+        };
+        ix(accounts, ml::instruction::EnterTeam { amount: self.amount })
+    }
+}
+
+/// `claim_team_refund`: the team's refund lands in its vault; anyone may crank
+#[derive(Clone)]
+pub struct ClaimTeamRefundBuilder<'a> {
+    pool: &'a PoolRef,
+    captain: Pubkey,
+    user: Pubkey,
+}
+
+impl<'a> ClaimTeamRefundBuilder<'a> {
+    pub fn new(pool: &'a PoolRef, captain: Pubkey, user: Pubkey) -> Self {
+        Self { pool, captain, user }
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let pool = self.pool;
+        let team = pool.team(&self.captain);
+        let accounts = ml::accounts::ClaimTeamRefund {
+            mint: pool.mint(),
+            pool: pool.address,
+            pool_token: pool.pool_token(),
+            team,
+            team_token: pool.ata(&team),
+            user: self.user,
+            token_program: pool.token_program,
+            participants: pool.participants(),
+        };
+        ix(accounts, ml::instruction::ClaimTeamRefund {})
+    }
+}
+
+/// `distribute_team_prize`. Works from the team state alone since the pool may
+/// already be closed; member ATAs are appended in `team.members` order.
+#[derive(Clone)]
+pub struct DistributeTeamPrizeBuilder<'a> {
+    team: &'a Team,
+    mint: Pubkey,
+    token_program: Pubkey,
+    user: Pubkey,
+}
+
+impl<'a> DistributeTeamPrizeBuilder<'a> {
+    pub fn new(team: &'a Team, mint: Pubkey, token_program: Pubkey, user: Pubkey) -> Self {
+        Self { team, mint, token_program, user }
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let team = pda::team(&self.team.pool, &self.team.captain).0;
+        let accounts = ml::accounts::DistributeTeamPrize {
+            mint: self.mint,
+            pool: self.team.pool,
+            team,
+            team_token: pda::ata(&team, &self.mint, &self.token_program),
+            captain: self.team.captain,
+            user: self.user,
+            token_program: self.token_program,
+        };
+        let members = self.team.members[..self.team.member_count as usize]
+            .iter()
+            .map(|member| AccountMeta::new(pda::ata(member, &self.mint, &self.token_program), false))
+            .collect();
+        ix_with_remaining(accounts, ml::instruction::DistributeTeamPrize {}, members)
+    }
+}
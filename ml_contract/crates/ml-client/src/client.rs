@@ -0,0 +1,122 @@
+use std::ops::Deref;
+
+use anchor_client::{
+    solana_sdk::{
+        commitment_config::CommitmentConfig,
+        instruction::Instruction,
+        signature::{Signature, Signer},
+        transaction::Transaction,
+    },
+    Client, ClientError, Cluster, Program,
+};
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::{token, token_2022};
+use ml::state::{CreatorPoolCounter, Pool, Team};
+
+use crate::{budget, budget::PriorityFees, pda, CreatePoolBuilder, Error, PoolRef, Result};
+
+/// RPC side of the SDK: resolves pools and mints for the builders and sends transactions
+pub struct MlClient<C> {
+    program: Program<C>,
+    payer: C,
+    /// ComputeBudget policy applied by [`MlClient::send`]; `None` sends instructions as given
+    fees: Option<PriorityFees>,
+}
+
+impl<C: Clone + Deref<Target = S>, S: Signer> MlClient<C> {
+    pub fn new(cluster: Cluster, payer: C) -> Result<Self> {
+        Self::with_commitment(cluster, payer, CommitmentConfig::confirmed())
+    }
+
+    pub fn with_commitment(cluster: Cluster, payer: C, commitment: CommitmentConfig) -> Result<Self> {
+        let program = Client::new_with_options(cluster, payer.clone(), commitment).program(ml::ID)?;
+        Ok(Self { program, payer, fees: Some(PriorityFees::default()) })
+    }
+
+    pub fn priority_fees(mut self, fees: Option<PriorityFees>) -> Self {
+        self.fees = fees;
+        self
+    }
+
+    pub fn program(&self) -> &Program<C> {
+        &self.program
+    }
+
+    pub fn payer(&self) -> Pubkey {
+        self.payer.pubkey()
+    }
+
+    /// SPL Token or Token-2022, whichever owns `mint`
+    pub fn token_program(&self, mint: &Pubkey) -> Result<Pubkey> {
+        let owner = self
+            .program
+            .rpc()
+            .get_account(mint)
+            .map_err(|_| Error::AccountNotFound(*mint))?
+            .owner;
+        if owner == token::ID || owner == token_2022::ID {
+            Ok(owner)
+        } else {
+            Err(Error::UnsupportedTokenProgram { mint: *mint, owner })
+        }
+    }
+
+    pub fn pool(&self, address: Pubkey) -> Result<PoolRef> {
+        let state: Pool = self.program.account(address)?;
+        let token_program = self.token_program(&state.mint)?;
+        Ok(PoolRef::new(address, state, token_program))
+    }
+
+    pub fn pool_by_salt(&self, mint: &Pubkey, salt: &[u8; 32]) -> Result<PoolRef> {
+        self.pool(pda::pool(mint, salt).0)
+    }
+
+    pub fn team(&self, pool: &Pubkey, captain: &Pubkey) -> Result<Team> {
+        Ok(self.program.account(pda::team(pool, captain).0)?)
+    }
+
+    /// Number of pools `creator` has created so far (0 before the first)
+    pub fn creator_pool_count(&self, creator: &Pubkey) -> Result<u64> {
+        match self.program.account::<CreatorPoolCounter>(pda::creator_counter(creator).0) {
+            Ok(counter) => Ok(counter.pool_count),
+            Err(ClientError::AccountNotFound) => Ok(0),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// `CreatePoolBuilder` with the mint's token program and the creator's pool index filled in
+    pub fn create_pool(
+        &self,
+        creator: Pubkey,
+        mint: Pubkey,
+        salt: [u8; 32],
+        amount: u64,
+        dev_wallet: Pubkey,
+        treasury_wallet: Pubkey,
+    ) -> Result<CreatePoolBuilder> {
+        let token_program = self.token_program(&mint)?;
+        let pool_count = self.creator_pool_count(&creator)?;
+        Ok(CreatePoolBuilder::new(creator, mint, token_program, salt, amount, dev_wallet, treasury_wallet)
+            .pool_count(pool_count))
+    }
+
+    /// Signs with the payer and `signers`, sends and confirms. A sized
+    /// ComputeBudget prefix is added unless the instructions carry their own.
+    pub fn send(&self, instructions: &[Instruction], signers: &[&dyn Signer]) -> Result<Signature> {
+        self.send_attempt(instructions, signers, 0)
+    }
+
+    /// [`send`](Self::send) for retry number `attempt`: the priority fee doubles per attempt
+    pub fn send_attempt(&self, instructions: &[Instruction], signers: &[&dyn Signer], attempt: u32) -> Result<Signature> {
+        let rpc = self.program.rpc();
+        let instructions = match &self.fees {
+            Some(fees) => budget::with_compute_budget(&rpc, instructions, fees, attempt).0,
+            None => instructions.to_vec(),
+        };
+        let blockhash = rpc.get_latest_blockhash().map_err(ClientError::from)?;
+        let mut all: Vec<&dyn Signer> = vec![&*self.payer];
+        all.extend(signers.iter().copied().filter(|s| s.pubkey() != self.payer.pubkey()));
+        let tx = Transaction::new_signed_with_payer(&instructions, Some(&self.payer.pubkey()), &all, blockhash);
+        Ok(rpc.send_and_confirm_transaction(&tx).map_err(ClientError::from)?)
+    }
+}
@@ -0,0 +1,18 @@
+use anchor_lang::prelude::Pubkey;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Client(#[from] anchor_client::ClientError),
+
+    #[error("account {0} not found")]
+    AccountNotFound(Pubkey),
+
+    #[error("mint {mint} is owned by {owner}, not SPL Token or Token-2022")]
+    UnsupportedTokenProgram { mint: Pubkey, owner: Pubkey },
+
+    #[error("pool {0} has no winner yet")]
+    NoWinner(Pubkey),
+}
@@ -0,0 +1,23 @@
+//! Rust client for the MissOut (`ml`) program.
+//!
+//! Every instruction has a typed builder (`CreatePoolBuilder`, `JoinPoolBuilder`, ...)
+//! that derives the program PDAs and associated token accounts itself and uses the
+//! token program owning the pool mint (SPL Token or Token-2022). Builders only
+//! produce `Instruction`s; [`MlClient`] resolves pools over RPC and sends them
+//! with a ComputeBudget prefix sized by [`budget`].
+
+pub mod budget;
+pub mod builders;
+mod client;
+mod error;
+pub mod pda;
+mod pool;
+mod preview;
+
+pub use builders::*;
+pub use client::MlClient;
+pub use error::{Error, Result};
+pub use pool::PoolRef;
+pub use preview::PayoutPreview;
+
+pub use ml::{state, ID as PROGRAM_ID};
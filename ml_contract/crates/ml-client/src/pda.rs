@@ -0,0 +1,47 @@
+//! Program-derived and associated token account addresses.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use ml_types::constants::*;
+
+pub fn pool(mint: &Pubkey, salt: &[u8; 32]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[POOL_SEED, mint.as_ref(), salt], &ml::ID)
+}
+
+pub fn participants(pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PARTICIPANTS_SEED, pool.as_ref()], &ml::ID)
+}
+
+pub fn creator_counter(creator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CREATOR_POOLS_SEED, creator.as_ref()], &ml::ID)
+}
+
+/// `index` is the creator's `CreatorPoolCounter::pool_count` before the new pool
+pub fn creator_pool_index(creator: &Pubkey, index: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CREATOR_POOL_SEED, creator.as_ref(), &index.to_le_bytes()], &ml::ID)
+}
+
+pub fn price_feed(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PRICE_FEED_SEED, mint.as_ref()], &ml::ID)
+}
+
+pub fn team(pool: &Pubkey, captain: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TEAM_SEED, pool.as_ref(), captain.as_ref()], &ml::ID)
+}
+
+pub fn admin_close(pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ADMIN_CLOSE_SEED, pool.as_ref()], &ml::ID)
+}
+
+pub fn pool_archive(pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[POOL_ARCHIVE_SEED, pool.as_ref()], &ml::ID)
+}
+
+/// Bubblegum tree config of a ticket tree
+pub fn ticket_tree_config(tree: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[tree.as_ref()], &ml::tickets::BUBBLEGUM_ID)
+}
+
+pub fn ata(owner: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(owner, mint, token_program)
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::Pubkey;
+use ml::{constants::ZERO_PUBKEY, state::Pool};
+
+use crate::{pda, PayoutPreview};
+
+/// A pool's address and state together with the token program of its mint;
+/// everything the builders need to fill in accounts for an existing pool.
+#[derive(Clone)]
+pub struct PoolRef {
+    pub address: Pubkey,
+    pub state: Pool,
+    pub token_program: Pubkey,
+}
+
+impl PoolRef {
+    pub fn new(address: Pubkey, state: Pool, token_program: Pubkey) -> Self {
+        Self { address, state, token_program }
+    }
+
+    pub fn mint(&self) -> Pubkey {
+        self.state.mint
+    }
+
+    pub fn pool_token(&self) -> Pubkey {
+        self.state.pool_token
+    }
+
+    pub fn participants(&self) -> Pubkey {
+        self.state.participants_account
+    }
+
+    /// ATA of `owner` for the pool mint
+    pub fn ata(&self, owner: &Pubkey) -> Pubkey {
+        pda::ata(owner, &self.state.mint, &self.token_program)
+    }
+
+    pub fn team(&self, captain: &Pubkey) -> Pubkey {
+        pda::team(&self.address, captain).0
+    }
+
+    pub fn winner(&self) -> Option<Pubkey> {
+        (self.state.winner != ZERO_PUBKEY).then_some(self.state.winner)
+    }
+
+    /// What `payout_winner` would pay out right now; see [`PayoutPreview::compute`]
+    pub fn payout_preview(&self) -> Option<PayoutPreview> {
+        PayoutPreview::compute(&self.state)
+    }
+}
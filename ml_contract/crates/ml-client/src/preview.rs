@@ -0,0 +1,69 @@
+use anchor_lang::prelude::Pubkey;
+use ml::{constants::ZERO_PUBKEY, state::Pool};
+use ml_core::{FeeSplit, BPS_DENOMINATOR};
+
+/// Token amounts `payout_winner` would move if it ran against the current pool state.
+///
+/// Each fee is floored independently, so the rounding remainder (`dust`) stays in
+/// the winner's share; `winner_amount` already includes it, as well as any
+/// `donated_prize`. The four amounts always add up to `total`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PayoutPreview {
+    pub total: u64,
+    /// Part of the pot the fee split applies to (excludes earmarked donations)
+    pub fee_base: u64,
+    /// Paid to the winner, or to the creator when the winner receives an escrowed NFT
+    pub winner_amount: u64,
+    /// Receiver of `winner_amount`; `None` until a winner is selected
+    pub winner_recipient: Option<Pubkey>,
+    pub dev_amount: u64,
+    /// Fee burn plus `donated_burn`
+    pub burn_amount: u64,
+    pub treasury_amount: u64,
+    /// Rounding remainder of the fee split, credited to the winner
+    pub dust: u64,
+}
+
+impl PayoutPreview {
+    /// Runs the program's fee math from `ml-core`. `None` when the state is one the
+    /// instruction itself would reject with `Overflow` (donations larger than
+    /// the pot, or a fee product past `u64::MAX`).
+    pub fn compute(pool: &Pool) -> Option<Self> {
+        let total = pool.total_amount;
+        let fee_base = ml_core::fee_base(total, pool.donated_prize, pool.donated_burn)?;
+        let FeeSplit {
+            dev: dev_amount,
+            burn: burn_amount,
+            treasury: treasury_amount,
+            winner: winner_amount,
+        } = ml_core::fee_split(
+            total,
+            fee_base,
+            pool.dev_fee_bps,
+            pool.burn_fee_bps,
+            pool.treasury_fee_bps,
+            pool.donated_burn,
+        )?;
+
+        let total_bps = pool.dev_fee_bps as u128 + pool.burn_fee_bps as u128 + pool.treasury_fee_bps as u128;
+        let exact_fees = (fee_base as u128 * total_bps / BPS_DENOMINATOR as u128) as u64;
+        let dust = exact_fees - (dev_amount + treasury_amount + (burn_amount - pool.donated_burn));
+
+        let winner_recipient = if pool.has_prize_nft() {
+            Some(pool.creator)
+        } else {
+            (pool.winner != ZERO_PUBKEY).then_some(pool.winner)
+        };
+
+        Some(Self {
+            total,
+            fee_base,
+            winner_amount,
+            winner_recipient,
+            dev_amount,
+            burn_amount,
+            treasury_amount,
+            dust,
+        })
+    }
+}
@@ -0,0 +1,43 @@
+//! Program constants and PDA seed prefixes, re-exported by the `ml` program
+//! and `ml-types` so the two cannot drift apart.
+
+// Account layout: the fixed-size arrays and strings of `Participants`,
+// `Team` and `AdminClose` are sized by these
+pub const MAX_PARTICIPANTS: usize = 20;
+pub const MAX_TEAM_MEMBERS: usize = 10;
+pub const MAX_ADMIN_CLOSE_URI_LEN: usize = 200;
+
+pub const MAX_FEE_BPS: u16 = 10000;
+pub const MIN_BET_TOKENS: u64 = 20; // 20 tokens (human-readable)
+pub const MIN_DONATE_TOKENS: u64 = 20; // 20 tokens (human-readable)
+pub const MAX_MINT_DECIMALS: u8 = 12;
+pub const MIN_LOCK_DURATION: i64 = 60;
+pub const MAX_LOCK_DURATION: i64 = 43_200;
+pub const POOL_OPEN_DURATION: i64 = 604_800;
+pub const SWEEP_DELAY: i64 = 7 * 86_400;
+pub const EMERGENCY_DELAY: i64 = 86_400;
+pub const PAYOUT_TIMEOUT: i64 = 7 * 86_400;
+pub const FORFEIT_DELAY: i64 = 30 * 86_400; // 30 days
+pub const MIN_BET_USD_MICROS: u64 = 1_000_000; // $1.00 when the mint has a registered price feed
+pub const USD_MICROS_DECIMALS: u32 = 6;
+pub const ADMIN_CLOSE_DELAY: i64 = 86_400; // notice period before admin_close_pool can execute
+pub const MAX_COMPRESSED_TREE_DEPTH: u32 = 20; // ~1M entries
+pub const CREATOR_BOND_LAMPORTS: u64 = 50_000_000; // 0.05 SOL, held in the pool account
+
+// `Pool::status_reason` values
+pub const REASON_CANCELLED: u8 = 5;
+pub const REASON_ADMIN_CLOSED: u8 = 6;
+pub const REASON_EXPIRED: u8 = 1;
+pub const REASON_PAUSED: u8 = 2;
+pub const REASON_MAX_REACHED: u8 = 4;
+pub const REASON_JOIN_DEADLINE: u8 = 7;
+
+// PDA seed prefixes
+pub const POOL_SEED: &[u8] = b"pool";
+pub const PARTICIPANTS_SEED: &[u8] = b"participants";
+pub const CREATOR_POOLS_SEED: &[u8] = b"creator_pools";
+pub const CREATOR_POOL_SEED: &[u8] = b"creator_pool";
+pub const PRICE_FEED_SEED: &[u8] = b"price_feed";
+pub const TEAM_SEED: &[u8] = b"team";
+pub const ADMIN_CLOSE_SEED: &[u8] = b"admin_close";
+pub const POOL_ARCHIVE_SEED: &[u8] = b"pool_archive";
@@ -0,0 +1,140 @@
+//! Pool math and constants shared by the `ml` program and the off-chain
+//! crates: the entry price of dutch-auction pools, the lock window, the payout
+//! fee split, and the limits, status reasons and PDA seeds in [`constants`].
+//!
+//! The program, `ml-types` and `ml-client` all call into this crate, so a
+//! fix to the math lands everywhere at once. Plain integers in, `None` on
+//! overflow out: the program maps that to `ErrorCode::Overflow`. `no_std`
+//! and dependency-free, so it builds for the SBF target unchanged.
+
+#![no_std]
+
+pub mod constants;
+
+/// Fees are expressed in basis points of the fee base
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// End of the lock window, pushed back by time spent paused while locked
+pub fn lock_end_time(lock_start_time: i64, lock_duration: i64, paused_duration: i64) -> Option<i64> {
+    lock_start_time.checked_add(lock_duration)?.checked_add(paused_duration)
+}
+
+/// Portion of `total_amount` subject to the fee split; earmarked donations pass through
+pub fn fee_base(total_amount: u64, donated_prize: u64, donated_burn: u64) -> Option<u64> {
+    total_amount.checked_sub(donated_prize)?.checked_sub(donated_burn)
+}
+
+/// `bps` of `fee_base`, rounded down
+pub fn fee(fee_base: u64, bps: u16) -> Option<u64> {
+    fee_base.checked_mul(bps as u64).map(|v| v / BPS_DENOMINATOR)
+}
+
+/// Entry price at `now`, linear from `amount` at `start_time` to `end_amount`
+/// at `expire_time`; flat-priced pools (`end_amount == amount`) always charge `amount`
+pub fn entry_amount_at(amount: u64, end_amount: u64, start_time: i64, expire_time: i64, now: i64) -> Option<u64> {
+    if end_amount == amount || expire_time <= start_time {
+        return Some(amount);
+    }
+
+    let span = (expire_time - start_time) as u128;
+    let elapsed = (now.clamp(start_time, expire_time) - start_time) as u128;
+    let start = amount as u128;
+    let end = end_amount as u128;
+
+    let price = if end < start {
+        start - (start - end) * elapsed / span
+    } else {
+        start + (end - start) * elapsed / span
+    };
+
+    u64::try_from(price).ok()
+}
+
+/// Token amounts `payout_winner` moves out of a pot
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FeeSplit {
+    pub dev: u64,
+    /// Fee burn plus `donated_burn`
+    pub burn: u64,
+    pub treasury: u64,
+    /// The rest of the pot: donated prize and rounding remainder included
+    pub winner: u64,
+}
+
+/// Splits a pot of `total`: each fee is floored independently on `fee_base`,
+/// `donated_burn` is burned on top, and whatever is left goes to the winner
+pub fn fee_split(
+    total: u64,
+    fee_base: u64,
+    dev_fee_bps: u16,
+    burn_fee_bps: u16,
+    treasury_fee_bps: u16,
+    donated_burn: u64,
+) -> Option<FeeSplit> {
+    let dev = fee(fee_base, dev_fee_bps)?;
+    let burn = fee(fee_base, burn_fee_bps)?.checked_add(donated_burn)?;
+    let treasury = fee(fee_base, treasury_fee_bps)?;
+    let winner = total.checked_sub(dev.checked_add(burn)?.checked_add(treasury)?)?;
+    Some(FeeSplit { dev, burn, treasury, winner })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fee_split_leaves_the_rounding_remainder_to_the_winner() {
+        // 999 * 100 / 10_000 = 9.99 -> 9 for each fee
+        let split = fee_split(999, 999, 100, 100, 100, 0).unwrap();
+        assert_eq!(split, FeeSplit { dev: 9, burn: 9, treasury: 9, winner: 972 });
+        assert_eq!(split.dev + split.burn + split.treasury + split.winner, 999);
+    }
+
+    #[test]
+    fn fee_split_burns_donations_on_top_of_the_fee() {
+        // 1_000 pot, 200 of it donated to the burn, fees charged on the remaining 800
+        let base = fee_base(1_000, 0, 200).unwrap();
+        let split = fee_split(1_000, base, 500, 500, 500, 200).unwrap();
+        assert_eq!(split, FeeSplit { dev: 40, burn: 240, treasury: 40, winner: 680 });
+    }
+
+    #[test]
+    fn fee_split_overflow_is_none() {
+        assert_eq!(fee(u64::MAX, 2), None);
+        assert_eq!(fee_split(u64::MAX, u64::MAX, 100, 0, 0, 0), None);
+        // Fees and burn donations exceeding the pot
+        assert_eq!(fee_split(100, 100, 0, 0, 0, 101), None);
+        assert_eq!(fee_base(100, 60, 50), None);
+    }
+
+    #[test]
+    fn entry_amount_descends_and_ascends_linearly() {
+        assert_eq!(entry_amount_at(1_000, 500, 0, 100, 0), Some(1_000));
+        assert_eq!(entry_amount_at(1_000, 500, 0, 100, 50), Some(750));
+        assert_eq!(entry_amount_at(1_000, 500, 0, 100, 100), Some(500));
+
+        assert_eq!(entry_amount_at(500, 1_000, 0, 100, 25), Some(625));
+        assert_eq!(entry_amount_at(500, 1_000, 0, 100, 100), Some(1_000));
+    }
+
+    #[test]
+    fn entry_amount_clamps_to_the_auction_window() {
+        assert_eq!(entry_amount_at(1_000, 500, 100, 200, 0), Some(1_000));
+        assert_eq!(entry_amount_at(1_000, 500, 100, 200, 1_000), Some(500));
+    }
+
+    #[test]
+    fn entry_amount_is_flat_without_a_window() {
+        assert_eq!(entry_amount_at(1_000, 500, 100, 100, 150), Some(1_000));
+        assert_eq!(entry_amount_at(1_000, 500, 200, 100, 150), Some(1_000));
+        assert_eq!(entry_amount_at(1_000, 1_000, 0, 100, 50), Some(1_000));
+    }
+
+    #[test]
+    fn lock_end_time_includes_paused_time() {
+        assert_eq!(lock_end_time(1_000, 60, 0), Some(1_060));
+        assert_eq!(lock_end_time(1_000, 60, 30), Some(1_090));
+        assert_eq!(lock_end_time(i64::MAX - 10, 60, 0), None);
+        assert_eq!(lock_end_time(i64::MAX - 100, 60, 60), None);
+    }
+}
@@ -0,0 +1,82 @@
+use std::{net::SocketAddr, path::PathBuf, time::Duration};
+
+use anchor_client::Cluster;
+use clap::Parser;
+use ml_client::budget::PriorityFees;
+
+#[derive(Parser, Debug, Clone)]
+#[command(name = "ml-crank", version, about = "MissOut settlement bot")]
+pub struct Config {
+    /// RPC URL or moniker (mainnet, devnet, localnet)
+    #[arg(long, env = "ML_RPC_URL", default_value = "devnet")]
+    pub url: Cluster,
+
+    /// Dev wallet keypair; pays fees and signs dev-only steps
+    #[arg(long, env = "ML_KEYPAIR")]
+    pub keypair: PathBuf,
+
+    /// Seconds between scans of all pools
+    #[arg(long, env = "ML_CRANK_INTERVAL", default_value = "10", value_parser = parse_secs)]
+    pub interval: Duration,
+
+    /// Attempts per transaction before giving up until the next scan
+    #[arg(long, default_value_t = 4)]
+    pub max_attempts: u32,
+
+    /// Backoff before the first retry; doubles on every attempt
+    #[arg(long, default_value = "1", value_parser = parse_secs)]
+    pub retry_backoff: Duration,
+
+    /// Percentile of recent prioritization fees paid per compute unit
+    #[arg(long, default_value_t = 75)]
+    pub fee_percentile: u8,
+
+    /// Priority fee floor (micro-lamports per CU)
+    #[arg(long, default_value_t = 0)]
+    pub min_priority_fee: u64,
+
+    /// Priority fee cap (micro-lamports per CU)
+    #[arg(long, default_value_t = 1_000_000)]
+    pub max_priority_fee: u64,
+
+    /// Fixed compute unit limit; by default sized per instruction by the SDK
+    #[arg(long)]
+    pub compute_unit_limit: Option<u32>,
+
+    /// Also sweep expired pools and forfeit unclaimed refunds to the treasury
+    #[arg(long, default_value_t = true, action = clap::ArgAction::Set)]
+    pub cleanup: bool,
+
+    /// Yellowstone gRPC endpoint; pool changes then trigger scans directly and
+    /// `getProgramAccounts` only runs every `--resync-interval`
+    #[cfg(feature = "yellowstone")]
+    #[arg(long, env = "ML_GEYSER_URL")]
+    pub geyser_url: Option<String>,
+
+    #[cfg(feature = "yellowstone")]
+    #[arg(long, env = "ML_GEYSER_X_TOKEN")]
+    pub geyser_x_token: Option<String>,
+
+    /// Seconds between full pool resyncs while the gRPC feed is active
+    #[arg(long, default_value = "300", value_parser = parse_secs)]
+    pub resync_interval: Duration,
+
+    /// Prometheus endpoint
+    #[arg(long, env = "ML_CRANK_METRICS", default_value = "0.0.0.0:9464")]
+    pub metrics_addr: SocketAddr,
+}
+
+impl Config {
+    pub fn priority_fees(&self) -> PriorityFees {
+        PriorityFees {
+            percentile: self.fee_percentile,
+            min: self.min_priority_fee,
+            max: self.max_priority_fee,
+            unit_limit: self.compute_unit_limit,
+        }
+    }
+}
+
+fn parse_secs(s: &str) -> Result<Duration, String> {
+    s.parse::<u64>().map(Duration::from_secs).map_err(|e| e.to_string())
+}
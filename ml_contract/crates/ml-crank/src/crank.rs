@@ -0,0 +1,293 @@
+use std::{
+    collections::HashMap,
+    rc::Rc,
+    sync::Arc,
+    thread,
+    time::{Duration, Instant},
+};
+
+use anchor_client::{
+    solana_client::client_error::ClientError as RpcError,
+    solana_sdk::{
+        account::from_account, clock::Clock, pubkey::Pubkey, signature::Keypair, signer::Signer, sysvar,
+        transaction::TransactionError,
+    },
+    ClientError,
+};
+use anyhow::{anyhow, Result};
+use ml::{
+    constants::*,
+    state::{Pool, PoolStatus},
+};
+use ml_client::*;
+use tokio::sync::watch;
+
+use crate::{config::Config, feed::PoolFeed, metrics::Metrics};
+
+/// Byte range of `reveal_slot` in a Switchboard `RandomnessAccountData` account
+const REVEAL_SLOT: std::ops::Range<usize> = 144..152;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    Unlock,
+    RequestRandomness,
+    SelectWinner,
+    Payout,
+    Sweep,
+    Forfeit,
+}
+
+impl Action {
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::Unlock => "unlock",
+            Action::RequestRandomness => "request_randomness",
+            Action::SelectWinner => "select_winner",
+            Action::Payout => "payout",
+            Action::Sweep => "sweep",
+            Action::Forfeit => "forfeit",
+        }
+    }
+}
+
+/// Next step the crank may take for `pool`, mirroring the program's own checks.
+/// Real (non-mock) pools only get a draw once their Switchboard commit exists.
+pub fn next_action(pool: &Pool, clock: &Clock, crank: &Pubkey, cleanup: bool) -> Option<Action> {
+    if !pool.initialized || pool.processing {
+        return None;
+    }
+
+    let now = clock.unix_timestamp;
+    // Past the sweep delay a pause no longer holds up refunds
+    let sweepable = cleanup && pool.status == PoolStatus::Open && now > pool.expire_time + SWEEP_DELAY;
+    if pool.paused {
+        return sweepable.then_some(Action::Sweep);
+    }
+    let is_dev = pool.dev_wallet == *crank;
+    let timed_out = now > pool.unlock_time + PAYOUT_TIMEOUT;
+
+    match pool.status {
+        PoolStatus::Locked
+            if is_dev && pool.lock_start_time != 0 && pool.lock_end_time().is_ok_and(|end| now >= end) =>
+        {
+            Some(Action::Unlock)
+        }
+        PoolStatus::Unlocked if is_dev && pool.allow_mock => Some(Action::RequestRandomness),
+        PoolStatus::RandomnessCommitted
+            if (is_dev || timed_out)
+                && !pool.is_compressed()
+                && clock.slot > pool.randomness_commit_slot
+                && clock.slot <= pool.randomness_commit_slot + 3000 =>
+        {
+            Some(Action::SelectWinner)
+        }
+        PoolStatus::WinnerSelected => Some(Action::Payout),
+        PoolStatus::Open if sweepable => Some(Action::Sweep),
+        PoolStatus::Cancelled
+            if cleanup
+                && (is_dev || pool.treasury_wallet == *crank)
+                && pool.close_time != 0
+                && matches!(pool.status_reason, REASON_CANCELLED | REASON_ADMIN_CLOSED | REASON_EXPIRED)
+                && now > pool.close_time + FORFEIT_DELAY =>
+        {
+            Some(Action::Forfeit)
+        }
+        _ => None,
+    }
+}
+
+/// Program errors will fail again on resend; only transport and blockhash errors are retried
+fn is_retryable(err: &Error) -> bool {
+    match err {
+        Error::Client(ClientError::SolanaClientError(err)) => retryable_rpc(err),
+        _ => false,
+    }
+}
+
+fn retryable_rpc(err: &RpcError) -> bool {
+    !matches!(
+        err.get_transaction_error(),
+        Some(TransactionError::InstructionError(..) | TransactionError::InsufficientFundsForFee)
+    )
+}
+
+pub struct Crank {
+    config: Config,
+    client: MlClient<Rc<Keypair>>,
+    signer: Pubkey,
+    metrics: Arc<Metrics>,
+    token_programs: HashMap<Pubkey, Pubkey>,
+    /// Pools whose last action was rejected by the program, skipped until the instant
+    cooldown: HashMap<Pubkey, Instant>,
+    /// gRPC-fed pool cache; `None` polls `getProgramAccounts` on every scan
+    feed: Option<Arc<PoolFeed>>,
+    last_resync: Option<Instant>,
+}
+
+impl Crank {
+    pub fn new(
+        config: Config,
+        keypair: Rc<Keypair>,
+        metrics: Arc<Metrics>,
+        feed: Option<Arc<PoolFeed>>,
+    ) -> Result<Self> {
+        let signer = keypair.pubkey();
+        let client = MlClient::new(config.url.clone(), keypair)?;
+        tracing::info!(%signer, url = %config.url, "crank started");
+        Ok(Self {
+            config,
+            client,
+            signer,
+            metrics,
+            token_programs: HashMap::new(),
+            cooldown: HashMap::new(),
+            feed,
+            last_resync: None,
+        })
+    }
+
+    pub fn run(&mut self, shutdown: watch::Receiver<bool>) -> Result<()> {
+        while !*shutdown.borrow() {
+            match self.scan() {
+                Ok(()) => self.metrics.scans.inc(),
+                Err(err) => {
+                    self.metrics.scan_errors.inc();
+                    tracing::warn!(error = %err, "scan failed");
+                }
+            }
+
+            let wake = Instant::now() + self.config.interval;
+            while Instant::now() < wake && !*shutdown.borrow() {
+                if self.feed.as_ref().is_some_and(|feed| feed.take_dirty()) {
+                    break;
+                }
+                thread::sleep(Duration::from_millis(200));
+            }
+        }
+        Ok(())
+    }
+
+    fn clock(&self) -> Result<Clock> {
+        let account = self.client.program().rpc().get_account(&sysvar::clock::ID)?;
+        from_account(&account).ok_or_else(|| anyhow!("malformed clock sysvar"))
+    }
+
+    fn scan(&mut self) -> Result<()> {
+        let clock = self.clock()?;
+        let pools = self.pools()?;
+
+        self.metrics.pools.reset();
+        for (_, pool) in &pools {
+            self.metrics.pools.with_label_values(&[&format!("{:?}", pool.status)]).inc();
+        }
+
+        let now = Instant::now();
+        self.cooldown.retain(|_, until| *until > now);
+
+        for (address, state) in pools {
+            if self.cooldown.contains_key(&address) {
+                continue;
+            }
+            let Some(action) = next_action(&state, &clock, &self.signer, self.config.cleanup) else {
+                continue;
+            };
+            if action == Action::SelectWinner && !self.randomness_revealed(&state)? {
+                continue;
+            }
+
+            let token_program = self.token_program(&state.mint)?;
+            let pool = PoolRef::new(address, state, token_program);
+            if let Err(err) = self.execute(&pool, action) {
+                tracing::warn!(pool = %address, action = action.name(), error = %err, "action failed");
+            }
+        }
+        Ok(())
+    }
+
+    fn pools(&mut self) -> Result<Vec<(Pubkey, Pool)>> {
+        let Some(feed) = &self.feed else {
+            return Ok(self.client.program().accounts::<Pool>(vec![])?);
+        };
+        if self.last_resync.is_none_or(|at| at.elapsed() >= self.config.resync_interval) {
+            // Read the slot first: the account scan is at least that recent
+            let slot = self.client.program().rpc().get_slot()?;
+            let accounts = self.client.program().accounts::<Pool>(vec![])?;
+            feed.resync(slot, &accounts);
+            self.last_resync = Some(Instant::now());
+        }
+        Ok(feed.snapshot())
+    }
+
+    fn token_program(&mut self, mint: &Pubkey) -> Result<Pubkey> {
+        if let Some(program) = self.token_programs.get(mint) {
+            return Ok(*program);
+        }
+        let program = self.client.token_program(mint)?;
+        self.token_programs.insert(*mint, program);
+        Ok(program)
+    }
+
+    /// Mock commits resolve immediately; Switchboard accounts must be revealed first
+    fn randomness_revealed(&self, pool: &Pool) -> Result<bool> {
+        if pool.randomness_account == ZERO_PUBKEY {
+            return Ok(true);
+        }
+        let data = self.client.program().rpc().get_account_data(&pool.randomness_account)?;
+        Ok(data
+            .get(REVEAL_SLOT)
+            .is_some_and(|slot| u64::from_le_bytes(slot.try_into().unwrap()) != 0))
+    }
+
+    fn execute(&mut self, pool: &PoolRef, action: Action) -> Result<()> {
+        let user = self.signer;
+        let action_instructions = match action {
+            Action::Unlock => vec![UnlockPoolBuilder::new(pool, user).instruction()],
+            Action::RequestRandomness => {
+                vec![RequestRandomnessBuilder::new(pool, user, Pubkey::default()).instruction()]
+            }
+            Action::SelectWinner => vec![SelectWinnerBuilder::new(pool, user).instruction()],
+            // The winner's ATA is created in the same transaction if missing
+            Action::Payout => PayoutWinnerBuilder::new(pool, user).instructions(),
+            Action::Sweep => vec![SweepExpiredPoolBuilder::new(pool, user).instruction()],
+            Action::Forfeit => vec![FinalizeForfeitedPoolBuilder::new(pool, user).instruction()],
+        };
+
+        let mut backoff = self.config.retry_backoff;
+        for attempt in 0..self.config.max_attempts {
+            if attempt > 0 {
+                self.metrics.retries.inc();
+                thread::sleep(backoff);
+                backoff *= 2;
+            }
+
+            let (instructions, fee) = budget::with_compute_budget(
+                &self.client.program().rpc(),
+                &action_instructions,
+                &self.config.priority_fees(),
+                attempt,
+            );
+            self.metrics.priority_fee.set(fee as i64);
+
+            let started = Instant::now();
+            match self.client.send(&instructions, &[]) {
+                Ok(signature) => {
+                    self.metrics.confirm_seconds.observe(started.elapsed().as_secs_f64());
+                    self.metrics.actions.with_label_values(&[action.name(), "ok"]).inc();
+                    tracing::info!(pool = %pool.address, action = action.name(), %signature, fee, "confirmed");
+                    return Ok(());
+                }
+                Err(err) if is_retryable(&err) => {
+                    tracing::debug!(pool = %pool.address, action = action.name(), attempt, error = %err, "retrying");
+                }
+                Err(err) => {
+                    self.metrics.actions.with_label_values(&[action.name(), "rejected"]).inc();
+                    self.cooldown.insert(pool.address, Instant::now() + self.config.interval * 10);
+                    return Err(err.into());
+                }
+            }
+        }
+
+        self.metrics.actions.with_label_values(&[action.name(), "failed"]).inc();
+        Err(anyhow!("gave up after {} attempts", self.config.max_attempts))
+    }
+}
@@ -0,0 +1,71 @@
+//! Pool cache kept current by the Yellowstone gRPC feed, so scans read local
+//! state instead of calling `getProgramAccounts` every interval.
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+};
+
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use ml::state::Pool;
+
+#[derive(Default)]
+#[cfg_attr(not(feature = "yellowstone"), allow(dead_code))]
+pub struct PoolFeed {
+    /// Address -> (slot of the state, state)
+    pools: Mutex<HashMap<Pubkey, (u64, Pool)>>,
+    /// Set on every update; the crank scans early when it sees it
+    dirty: AtomicBool,
+}
+
+impl PoolFeed {
+    pub fn snapshot(&self) -> Vec<(Pubkey, Pool)> {
+        let pools = self.pools.lock().unwrap();
+        pools.iter().map(|(address, (_, pool))| (*address, pool.clone())).collect()
+    }
+
+    /// Replaces every entry not newer than `slot` with a full `getProgramAccounts`
+    /// result read at or after `slot`; accounts missing from it were closed
+    pub fn resync(&self, slot: u64, accounts: &[(Pubkey, Pool)]) {
+        let mut pools = self.pools.lock().unwrap();
+        pools.retain(|_, (updated, _)| *updated > slot);
+        for (address, pool) in accounts {
+            pools.entry(*address).or_insert_with(|| (slot, pool.clone()));
+        }
+    }
+
+    pub fn take_dirty(&self) -> bool {
+        self.dirty.swap(false, Ordering::AcqRel)
+    }
+
+    #[cfg(feature = "yellowstone")]
+    fn update(&self, address: Pubkey, slot: u64, pool: Pool) {
+        let mut pools = self.pools.lock().unwrap();
+        if pools.get(&address).is_none_or(|(updated, _)| *updated <= slot) {
+            pools.insert(address, (slot, pool));
+            self.dirty.store(true, Ordering::Release);
+        }
+    }
+}
+
+/// Streams pool writes into `feed` until the process exits
+#[cfg(feature = "yellowstone")]
+pub async fn run(subscription: ml_events::GeyserSubscription, feed: std::sync::Arc<PoolFeed>) {
+    use anchor_lang::AnchorDeserialize;
+    use futures::StreamExt;
+    use ml_types::BorshSerialize;
+
+    let mut updates = subscription.pool_updates();
+    while let Some((address, slot, pool)) = updates.next().await {
+        // Same borsh layout on both sides; re-read as the program's own type
+        let mut data = Vec::new();
+        let decoded = pool.serialize(&mut data).ok().and_then(|()| Pool::deserialize(&mut data.as_slice()).ok());
+        match decoded {
+            Some(pool) => feed.update(Pubkey::new_from_array(address.to_bytes()), slot, pool),
+            None => tracing::warn!(pool = %address, "pool layout mismatch between ml-types and ml"),
+        }
+    }
+}
@@ -0,0 +1,74 @@
+//! `ml-crank`: polls every pool of the program and submits the next settlement
+//! step (unlock, request_randomness, select_winner, payout_winner, sweep,
+//! forfeit) as soon as its time condition holds.
+//!
+//! With the `yellowstone` feature and `--geyser-url`, pool changes arrive over
+//! gRPC and wake the loop immediately instead of waiting for the next poll.
+//!
+//! Run it with the dev wallet keypair: unlock and the draw are dev-only, while
+//! payout and sweeps are open to anyone.
+
+mod config;
+mod crank;
+mod feed;
+mod metrics;
+
+use std::{rc::Rc, sync::Arc};
+
+use anchor_client::solana_sdk::signature::read_keypair_file;
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use tokio::sync::watch;
+use tracing_subscriber::EnvFilter;
+
+use crate::{config::Config, crank::Crank, feed::PoolFeed, metrics::Metrics};
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")))
+        .init();
+
+    let config = Config::parse();
+    let metrics = Arc::new(Metrics::new()?);
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+
+    let server = tokio::spawn(metrics::serve(config.metrics_addr, metrics.clone()));
+    let feed = spawn_feed(&config);
+
+    // The RPC client is blocking; the crank loop owns a dedicated thread
+    let worker = tokio::task::spawn_blocking({
+        let metrics = metrics.clone();
+        move || -> Result<()> {
+            let keypair = read_keypair_file(&config.keypair)
+                .map_err(|e| anyhow!("reading keypair {}: {e}", config.keypair.display()))?;
+            let mut crank = Crank::new(config, Rc::new(keypair), metrics, feed)?;
+            crank.run(shutdown_rx)
+        }
+    });
+
+    tokio::signal::ctrl_c().await?;
+    tracing::info!("shutting down");
+    shutdown_tx.send(true)?;
+    worker.await??;
+    server.abort();
+    Ok(())
+}
+
+#[cfg(feature = "yellowstone")]
+fn spawn_feed(config: &Config) -> Option<Arc<PoolFeed>> {
+    let url = config.geyser_url.as_ref()?;
+    let mut subscription = ml_events::GeyserSubscription::new(url.clone());
+    if let Some(token) = &config.geyser_x_token {
+        subscription = subscription.x_token(token.clone());
+    }
+    let feed = Arc::new(PoolFeed::default());
+    tokio::spawn(feed::run(subscription, feed.clone()));
+    tracing::info!(%url, "pool feed over gRPC");
+    Some(feed)
+}
+
+#[cfg(not(feature = "yellowstone"))]
+fn spawn_feed(_config: &Config) -> Option<Arc<PoolFeed>> {
+    None
+}
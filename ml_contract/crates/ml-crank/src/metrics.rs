@@ -0,0 +1,73 @@
+use std::{net::SocketAddr, sync::Arc};
+
+use anyhow::Result;
+use axum::{extract::State, routing::get, Router};
+use prometheus::{
+    Encoder, Histogram, HistogramOpts, IntCounter, IntCounterVec, IntGauge, IntGaugeVec, Opts, Registry,
+    TextEncoder,
+};
+
+pub struct Metrics {
+    registry: Registry,
+    /// Transactions by action and outcome (ok, failed, rejected)
+    pub actions: IntCounterVec,
+    /// Send attempts beyond the first
+    pub retries: IntCounter,
+    /// Pools seen in the last scan, by status
+    pub pools: IntGaugeVec,
+    pub scans: IntCounter,
+    pub scan_errors: IntCounter,
+    /// Priority fee of the last transaction (micro-lamports per CU)
+    pub priority_fee: IntGauge,
+    /// Submission to confirmation, seconds
+    pub confirm_seconds: Histogram,
+}
+
+impl Metrics {
+    pub fn new() -> Result<Self> {
+        let registry = Registry::new();
+        let actions = IntCounterVec::new(
+            Opts::new("ml_crank_actions_total", "Crank transactions by action and outcome"),
+            &["action", "outcome"],
+        )?;
+        let retries = IntCounter::new("ml_crank_retries_total", "Transaction send retries")?;
+        let pools = IntGaugeVec::new(Opts::new("ml_crank_pools", "Pools by status at the last scan"), &["status"])?;
+        let scans = IntCounter::new("ml_crank_scans_total", "Completed pool scans")?;
+        let scan_errors = IntCounter::new("ml_crank_scan_errors_total", "Scans aborted by RPC errors")?;
+        let priority_fee = IntGauge::new(
+            "ml_crank_priority_fee_micro_lamports",
+            "Compute unit price of the last transaction",
+        )?;
+        let confirm_seconds = Histogram::with_opts(
+            HistogramOpts::new("ml_crank_confirm_seconds", "Time from submission to confirmation")
+                .buckets(vec![0.5, 1.0, 2.0, 5.0, 10.0, 20.0, 40.0, 60.0]),
+        )?;
+
+        registry.register(Box::new(actions.clone()))?;
+        registry.register(Box::new(retries.clone()))?;
+        registry.register(Box::new(pools.clone()))?;
+        registry.register(Box::new(scans.clone()))?;
+        registry.register(Box::new(scan_errors.clone()))?;
+        registry.register(Box::new(priority_fee.clone()))?;
+        registry.register(Box::new(confirm_seconds.clone()))?;
+
+        Ok(Self { registry, actions, retries, pools, scans, scan_errors, priority_fee, confirm_seconds })
+    }
+
+    fn render(&self) -> String {
+        let mut buf = Vec::new();
+        // Encoding into a Vec cannot fail
+        let _ = TextEncoder::new().encode(&self.registry.gather(), &mut buf);
+        String::from_utf8(buf).unwrap_or_default()
+    }
+}
+
+pub async fn serve(addr: SocketAddr, metrics: Arc<Metrics>) -> Result<()> {
+    let app = Router::new()
+        .route("/metrics", get(|State(m): State<Arc<Metrics>>| async move { m.render() }))
+        .with_state(metrics);
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    tracing::info!(%addr, "metrics listening");
+    axum::serve(listener, app).await?;
+    Ok(())
+}
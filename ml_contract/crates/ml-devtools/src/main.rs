@@ -0,0 +1,331 @@
+//! `ml-devtools`: devnet QA in one command.
+//!
+//! `mint` creates a throwaway SPL / Token-2022 mint that `create_pool` accepts
+//! (no freeze authority, mint authority revoked after the initial supply),
+//! `fund` generates and funds test wallets, `pool` creates a pool and lets the
+//! funded wallets join it, and `setup` does all three.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use anchor_client::{
+    solana_sdk::{
+        instruction::Instruction,
+        native_token::{lamports_to_sol, sol_to_lamports},
+        program_pack::Pack,
+        pubkey::Pubkey,
+        signature::{read_keypair_file, write_keypair_file, Keypair, Signer},
+        system_instruction,
+    },
+    Cluster,
+};
+use anchor_spl::{
+    associated_token::{get_associated_token_address_with_program_id, spl_associated_token_account},
+    token, token_2022,
+    token_2022::spl_token_2022::{self, instruction::AuthorityType, state::Mint},
+};
+use anyhow::{anyhow, bail, Context, Result};
+use clap::{Args, Parser, Subcommand};
+use ml::constants::{MAX_MINT_DECIMALS, MIN_BET_TOKENS, MIN_LOCK_DURATION};
+use ml_client::*;
+
+/// Wallets funded per transaction (SOL transfer + ATA + token transfer each)
+const FUND_BATCH: usize = 4;
+
+#[derive(Parser)]
+#[command(name = "ml-devtools", version, about = "Devnet test mints, wallets and pools")]
+struct Cli {
+    /// RPC URL or moniker (mainnet, devnet, localnet)
+    #[arg(short, long, env = "ML_RPC_URL", default_value = "devnet", global = true)]
+    url: Cluster,
+
+    /// Funding keypair; creates the mint and pools
+    #[arg(short, long, env = "ML_KEYPAIR", global = true)]
+    keypair: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create a test mint and mint its supply to the funding wallet
+    Mint(MintArgs),
+    /// Generate test wallets and send them SOL and tokens
+    Fund(FundArgs),
+    /// Create a pool and have funded wallets join it
+    Pool(PoolArgs),
+    /// `mint`, `fund` and `pool` in one go
+    Setup {
+        #[command(flatten)]
+        mint: MintArgs,
+        #[command(flatten)]
+        wallets: WalletArgs,
+        #[command(flatten)]
+        pool: PoolOptions,
+    },
+}
+
+#[derive(Args)]
+struct MintArgs {
+    #[arg(long, default_value_t = 6)]
+    decimals: u8,
+    /// Create the mint under Token-2022 instead of SPL Token
+    #[arg(long)]
+    token_2022: bool,
+    /// Initial supply in whole tokens
+    #[arg(long, default_value_t = 1_000_000_000)]
+    supply: u64,
+    /// Keep the mint authority (the program rejects such mints; for negative tests)
+    #[arg(long)]
+    keep_mint_authority: bool,
+}
+
+#[derive(Args)]
+struct WalletArgs {
+    /// Number of wallets to generate
+    #[arg(long, default_value_t = 10)]
+    wallets: usize,
+    /// Whole tokens sent to each wallet
+    #[arg(long, default_value_t = 10_000)]
+    tokens: u64,
+    /// SOL sent to each wallet for fees and rent
+    #[arg(long, default_value_t = 0.05)]
+    sol: f64,
+    /// Directory the generated keypairs are written to
+    #[arg(long, default_value = "test-wallets")]
+    out: PathBuf,
+}
+
+#[derive(Args)]
+struct FundArgs {
+    #[arg(long)]
+    mint: Pubkey,
+    #[command(flatten)]
+    wallets: WalletArgs,
+}
+
+#[derive(Args)]
+struct PoolOptions {
+    /// Entry price in base units (defaults to the program minimum for the mint)
+    #[arg(long)]
+    amount: Option<u64>,
+    #[arg(long, default_value_t = ml::constants::MAX_PARTICIPANTS as u8)]
+    max_participants: u8,
+    #[arg(long, default_value_t = MIN_LOCK_DURATION)]
+    lock_duration: i64,
+    /// How many funded wallets join after creation (the creator holds the first slot)
+    #[arg(long, default_value_t = 0)]
+    join: usize,
+}
+
+#[derive(Args)]
+struct PoolArgs {
+    #[arg(long)]
+    mint: Pubkey,
+    /// Keypairs written by `fund`
+    #[arg(long, default_value = "test-wallets")]
+    wallets_dir: PathBuf,
+    #[command(flatten)]
+    options: PoolOptions,
+}
+
+struct Devtools {
+    client: MlClient<Rc<Keypair>>,
+    payer: Rc<Keypair>,
+}
+
+impl Devtools {
+    fn send(&self, instructions: &[Instruction], signers: &[&dyn Signer]) -> Result<()> {
+        let signature = self.client.send(instructions, signers)?;
+        println!("  {signature}");
+        Ok(())
+    }
+
+    fn decimals(&self, mint: &Pubkey) -> Result<u8> {
+        let account = self.client.program().rpc().get_account(mint)?;
+        let state = spl_token_2022::extension::StateWithExtensions::<Mint>::unpack(&account.data)
+            .map_err(|e| anyhow!("{mint} is not a mint: {e}"))?;
+        Ok(state.base.decimals)
+    }
+
+    fn create_mint(&self, args: &MintArgs) -> Result<Pubkey> {
+        if args.decimals > MAX_MINT_DECIMALS {
+            bail!("decimals must be at most {MAX_MINT_DECIMALS}");
+        }
+        let token_program = if args.token_2022 { token_2022::ID } else { token::ID };
+        let payer = self.payer.pubkey();
+        let mint = Keypair::new();
+        let supply = whole_to_base(args.supply, args.decimals)?;
+        let rent = self.client.program().rpc().get_minimum_balance_for_rent_exemption(Mint::LEN)?;
+        let payer_token = get_associated_token_address_with_program_id(&payer, &mint.pubkey(), &token_program);
+
+        let mut instructions = vec![
+            system_instruction::create_account(&payer, &mint.pubkey(), rent, Mint::LEN as u64, &token_program),
+            spl_token_2022::instruction::initialize_mint2(&token_program, &mint.pubkey(), &payer, None, args.decimals)?,
+            create_ata(&payer, &payer, &mint.pubkey(), &token_program),
+            spl_token_2022::instruction::mint_to_checked(
+                &token_program,
+                &mint.pubkey(),
+                &payer_token,
+                &payer,
+                &[],
+                supply,
+                args.decimals,
+            )?,
+        ];
+        if !args.keep_mint_authority {
+            instructions.push(spl_token_2022::instruction::set_authority(
+                &token_program,
+                &mint.pubkey(),
+                None,
+                AuthorityType::MintTokens,
+                &payer,
+                &[],
+            )?);
+        }
+
+        println!("mint {} ({} decimals, {} tokens)", mint.pubkey(), args.decimals, args.supply);
+        self.send(&instructions, &[&mint])?;
+        Ok(mint.pubkey())
+    }
+
+    fn fund(&self, mint: &Pubkey, args: &WalletArgs) -> Result<Vec<Keypair>> {
+        let payer = self.payer.pubkey();
+        let token_program = self.client.token_program(mint)?;
+        let decimals = self.decimals(mint)?;
+        let tokens = whole_to_base(args.tokens, decimals)?;
+        let lamports = sol_to_lamports(args.sol);
+        let payer_token = get_associated_token_address_with_program_id(&payer, mint, &token_program);
+
+        fs::create_dir_all(&args.out).with_context(|| format!("creating {}", args.out.display()))?;
+        let wallets: Vec<Keypair> = (0..args.wallets).map(|_| Keypair::new()).collect();
+        for (i, wallet) in wallets.iter().enumerate() {
+            let path = args.out.join(format!("wallet-{i}.json"));
+            write_keypair_file(wallet, &path).map_err(|e| anyhow!("writing {}: {e}", path.display()))?;
+        }
+
+        println!(
+            "funding {} wallets with {} SOL and {} tokens each",
+            wallets.len(),
+            lamports_to_sol(lamports),
+            args.tokens
+        );
+        for batch in wallets.chunks(FUND_BATCH) {
+            let mut instructions = Vec::with_capacity(batch.len() * 3);
+            for wallet in batch {
+                let owner = wallet.pubkey();
+                instructions.push(system_instruction::transfer(&payer, &owner, lamports));
+                instructions.push(create_ata(&payer, &owner, mint, &token_program));
+                instructions.push(spl_token_2022::instruction::transfer_checked(
+                    &token_program,
+                    &payer_token,
+                    mint,
+                    &get_associated_token_address_with_program_id(&owner, mint, &token_program),
+                    &payer,
+                    &[],
+                    tokens,
+                    decimals,
+                )?);
+            }
+            self.send(&instructions, &[])?;
+        }
+        println!("keypairs written to {}", args.out.display());
+        Ok(wallets)
+    }
+
+    fn create_pool(&self, mint: &Pubkey, options: &PoolOptions, wallets: &[Keypair]) -> Result<Pubkey> {
+        if options.join >= options.max_participants as usize {
+            bail!("--join must leave room for the creator (max {})", options.max_participants as usize - 1);
+        }
+        if options.join > wallets.len() {
+            bail!("--join {} but only {} wallets are available", options.join, wallets.len());
+        }
+
+        let payer = self.payer.pubkey();
+        let amount = match options.amount {
+            Some(amount) => amount,
+            None => whole_to_base(MIN_BET_TOKENS, self.decimals(mint)?)?,
+        };
+        let builder = self
+            .client
+            .create_pool(payer, *mint, rand::random(), amount, payer, payer)?
+            .max_participants(options.max_participants)
+            .lock_duration(options.lock_duration);
+        let address = builder.pool_address();
+        println!("pool {address}");
+        self.send(&[builder.instruction()], &[])?;
+
+        let pool = self.client.pool(address)?;
+        for wallet in &wallets[..options.join] {
+            println!("join {}", wallet.pubkey());
+            let join = JoinPoolBuilder::new(&pool, wallet.pubkey(), amount).instruction();
+            self.send(&[join], &[wallet])?;
+        }
+        Ok(address)
+    }
+}
+
+fn create_ata(payer: &Pubkey, owner: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Instruction {
+    spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+        payer,
+        owner,
+        mint,
+        token_program,
+    )
+}
+
+fn whole_to_base(tokens: u64, decimals: u8) -> Result<u64> {
+    10u64
+        .checked_pow(decimals as u32)
+        .and_then(|scale| tokens.checked_mul(scale))
+        .ok_or_else(|| anyhow!("{tokens} tokens overflow u64 at {decimals} decimals"))
+}
+
+fn load_keypair(path: Option<PathBuf>) -> Result<Keypair> {
+    let path = match path {
+        Some(path) => path,
+        None => {
+            let home = std::env::var("HOME").context("HOME not set; pass --keypair")?;
+            PathBuf::from(home).join(".config/solana/id.json")
+        }
+    };
+    read_keypair_file(&path).map_err(|e| anyhow!("reading keypair {}: {e}", path.display()))
+}
+
+/// `wallet-<n>.json` files in index order
+fn load_wallets(dir: &Path) -> Vec<Keypair> {
+    let mut wallets = Vec::new();
+    while let Ok(wallet) = read_keypair_file(dir.join(format!("wallet-{}.json", wallets.len()))) {
+        wallets.push(wallet);
+    }
+    wallets
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let payer = Rc::new(load_keypair(cli.keypair)?);
+    let tools = Devtools { client: MlClient::new(cli.url, payer.clone())?, payer };
+
+    match cli.command {
+        Command::Mint(args) => {
+            tools.create_mint(&args)?;
+        }
+        Command::Fund(args) => {
+            tools.fund(&args.mint, &args.wallets)?;
+        }
+        Command::Pool(args) => {
+            let wallets = load_wallets(&args.wallets_dir);
+            tools.create_pool(&args.mint, &args.options, &wallets)?;
+        }
+        Command::Setup { mint, wallets, pool } => {
+            let mint = tools.create_mint(&mint)?;
+            let wallets = tools.fund(&mint, &wallets)?;
+            tools.create_pool(&mint, &pool, &wallets)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use futures::{Stream, StreamExt};
+use ml_types::{state::Pool, AccountData, Discriminator};
+use solana_account_decoder::{UiAccountData, UiAccountEncoding};
+use solana_client::{
+    nonblocking::pubsub_client::PubsubClient,
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_filter::{Memcmp, RpcFilterType},
+};
+use solana_sdk::pubkey::Pubkey;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::UnboundedReceiverStream;
+
+use crate::{Result, Subscription};
+
+/// `Stream<Item = (Pubkey, u64, Pool)>` of pool address, slot and new state
+pub struct PoolUpdates {
+    inner: UnboundedReceiverStream<(Pubkey, u64, Pool)>,
+}
+
+impl PoolUpdates {
+    pub(crate) fn spawn(config: Subscription) -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        tokio::spawn(run(config, sender));
+        Self { inner: UnboundedReceiverStream::new(receiver) }
+    }
+}
+
+impl Stream for PoolUpdates {
+    type Item = (Pubkey, u64, Pool);
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.inner).poll_next(cx)
+    }
+}
+
+async fn run(config: Subscription, sender: mpsc::UnboundedSender<(Pubkey, u64, Pool)>) {
+    let mut backoff = Duration::from_millis(500);
+    while !sender.is_closed() {
+        match session(&config, &sender).await {
+            Ok(()) => backoff = Duration::from_millis(500),
+            Err(err) => tracing::warn!(error = %err, "pool subscription dropped"),
+        }
+        if sender.is_closed() {
+            break;
+        }
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(config.max_backoff);
+    }
+}
+
+async fn session(config: &Subscription, sender: &mpsc::UnboundedSender<(Pubkey, u64, Pool)>) -> Result<()> {
+    let client = PubsubClient::new(&config.ws_url).await?;
+    let filter = RpcFilterType::Memcmp(Memcmp::new_raw_bytes(0, Pool::DISCRIMINATOR.to_vec()));
+    let (mut updates, unsubscribe) = client
+        .program_subscribe(
+            &config.program_id,
+            Some(RpcProgramAccountsConfig {
+                filters: Some(vec![filter]),
+                account_config: RpcAccountInfoConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    commitment: Some(config.commitment),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+        )
+        .await?;
+
+    while let Some(update) = updates.next().await {
+        let UiAccountData::Binary(data, _) = &update.value.account.data else {
+            continue;
+        };
+        let Ok(address) = update.value.pubkey.parse::<Pubkey>() else {
+            continue;
+        };
+        let Some(pool) = STANDARD.decode(data).ok().and_then(|b| Pool::try_from_account_data(&b).ok()) else {
+            continue;
+        };
+        if sender.send((address, update.context.slot, pool)).is_err() {
+            break;
+        }
+    }
+    unsubscribe().await;
+    Ok(())
+}
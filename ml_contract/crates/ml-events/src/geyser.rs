@@ -0,0 +1,262 @@
+//! Yellowstone gRPC pool feed (`yellowstone` feature).
+//!
+//! Account writes are subscribed at `processed` together with slot
+//! notifications and held per slot until that slot reaches the requested
+//! commitment. Slots that reach it are released along their parent chain;
+//! buffered slots that fall off that chain (forks) or are reported dead are
+//! discarded, so consumers only ever see writes from the canonical chain.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures::{SinkExt, Stream, StreamExt};
+use ml_types::{state::Pool, AccountData, Discriminator};
+use solana_sdk::{
+    commitment_config::{CommitmentConfig, CommitmentLevel as Commitment},
+    pubkey::Pubkey,
+};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use yellowstone_grpc_client::{ClientTlsConfig, GeyserGrpcClient};
+use yellowstone_grpc_proto::prelude::{
+    subscribe_request_filter_accounts_filter::Filter, subscribe_request_filter_accounts_filter_memcmp::Data,
+    subscribe_update::UpdateOneof, CommitmentLevel, SlotStatus, SubscribeRequest, SubscribeRequestFilterAccounts,
+    SubscribeRequestFilterAccountsFilter, SubscribeRequestFilterAccountsFilterMemcmp, SubscribeRequestFilterSlots,
+    SubscribeRequestPing,
+};
+
+use crate::{Error, Result};
+
+/// Connection settings of the gRPC feed
+#[derive(Clone, Debug)]
+pub struct GeyserSubscription {
+    endpoint: String,
+    x_token: Option<String>,
+    program_id: Pubkey,
+    commitment: CommitmentConfig,
+    max_backoff: Duration,
+}
+
+impl GeyserSubscription {
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            x_token: None,
+            program_id: Pubkey::new_from_array(ml_types::PROGRAM_ID.to_bytes()),
+            commitment: CommitmentConfig::confirmed(),
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+
+    pub fn x_token(mut self, x_token: impl Into<String>) -> Self {
+        self.x_token = Some(x_token.into());
+        self
+    }
+
+    pub fn program_id(mut self, program_id: Pubkey) -> Self {
+        self.program_id = program_id;
+        self
+    }
+
+    /// Commitment a slot must reach before its writes are released
+    pub fn commitment(mut self, commitment: CommitmentConfig) -> Self {
+        self.commitment = commitment;
+        self
+    }
+
+    /// Ceiling of the exponential reconnect delay
+    pub fn max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// `Pool` account changes, same items as [`PoolUpdates`](crate::PoolUpdates).
+    /// Writes made while disconnected are not replayed; pair with a periodic
+    /// `getProgramAccounts` resync. Must be called inside a Tokio runtime.
+    pub fn pool_updates(self) -> GeyserPoolUpdates {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        tokio::spawn(run(self, sender));
+        GeyserPoolUpdates { inner: UnboundedReceiverStream::new(receiver) }
+    }
+}
+
+/// `Stream<Item = (Pubkey, u64, Pool)>` of pool address, slot and new state
+pub struct GeyserPoolUpdates {
+    inner: UnboundedReceiverStream<(Pubkey, u64, Pool)>,
+}
+
+impl Stream for GeyserPoolUpdates {
+    type Item = (Pubkey, u64, Pool);
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.inner).poll_next(cx)
+    }
+}
+
+async fn run(config: GeyserSubscription, sender: mpsc::UnboundedSender<(Pubkey, u64, Pool)>) {
+    let mut backoff = Duration::from_millis(500);
+    while !sender.is_closed() {
+        match session(&config, &sender).await {
+            Ok(()) => backoff = Duration::from_millis(500),
+            Err(err) => tracing::warn!(error = %err, "geyser subscription dropped"),
+        }
+        if sender.is_closed() {
+            break;
+        }
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(config.max_backoff);
+    }
+}
+
+fn geyser_err(err: impl std::fmt::Display) -> Error {
+    Error::Geyser(err.to_string())
+}
+
+async fn session(config: &GeyserSubscription, sender: &mpsc::UnboundedSender<(Pubkey, u64, Pool)>) -> Result<()> {
+    let mut client = GeyserGrpcClient::build_from_shared(config.endpoint.clone())
+        .map_err(geyser_err)?
+        .x_token(config.x_token.clone())
+        .map_err(geyser_err)?
+        .tls_config(ClientTlsConfig::new().with_native_roots())
+        .map_err(geyser_err)?
+        .connect()
+        .await
+        .map_err(geyser_err)?;
+
+    let filter = SubscribeRequestFilterAccountsFilter {
+        filter: Some(Filter::Memcmp(SubscribeRequestFilterAccountsFilterMemcmp {
+            offset: 0,
+            data: Some(Data::Bytes(Pool::DISCRIMINATOR.to_vec())),
+        })),
+    };
+    let request = SubscribeRequest {
+        accounts: HashMap::from([(
+            "pools".to_owned(),
+            SubscribeRequestFilterAccounts {
+                owner: vec![config.program_id.to_string()],
+                filters: vec![filter],
+                ..Default::default()
+            },
+        )]),
+        slots: HashMap::from([("slots".to_owned(), SubscribeRequestFilterSlots::default())]),
+        commitment: Some(CommitmentLevel::Processed as i32),
+        ..Default::default()
+    };
+    let (mut sink, mut updates) = client.subscribe_with_request(Some(request)).await.map_err(geyser_err)?;
+
+    let mut forks = ForkBuffer::new(config.commitment.commitment);
+    while let Some(update) = updates.next().await {
+        let released = match update.map_err(geyser_err)?.update_oneof {
+            Some(UpdateOneof::Account(update)) => {
+                let Some(account) = update.account else {
+                    continue;
+                };
+                let (Ok(address), Ok(pool)) =
+                    (Pubkey::try_from(account.pubkey.as_slice()), Pool::try_from_account_data(&account.data))
+                else {
+                    continue;
+                };
+                forks.write(update.slot, address, pool)
+            }
+            Some(UpdateOneof::Slot(slot)) => match SlotStatus::try_from(slot.status) {
+                Ok(status) => forks.slot(slot.slot, slot.parent, status),
+                Err(_) => continue,
+            },
+            // Some providers drop idle streams unless pings are answered
+            Some(UpdateOneof::Ping(_)) => {
+                let ping = SubscribeRequest { ping: Some(SubscribeRequestPing { id: 1 }), ..Default::default() };
+                sink.send(ping).await.map_err(geyser_err)?;
+                continue;
+            }
+            _ => continue,
+        };
+        for item in released {
+            if sender.send(item).is_err() {
+                return Ok(());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Per-slot holding area for processed writes; see the module docs
+struct ForkBuffer {
+    release_at: Option<SlotStatus>,
+    parents: BTreeMap<u64, u64>,
+    pending: BTreeMap<u64, Vec<(Pubkey, Pool)>>,
+    /// Highest slot released so far; later writes at or below it are stale
+    released: u64,
+}
+
+impl ForkBuffer {
+    fn new(commitment: Commitment) -> Self {
+        let release_at = match commitment {
+            Commitment::Processed => None,
+            Commitment::Confirmed => Some(SlotStatus::SlotConfirmed),
+            Commitment::Finalized => Some(SlotStatus::SlotFinalized),
+        };
+        Self { release_at, parents: BTreeMap::new(), pending: BTreeMap::new(), released: 0 }
+    }
+
+    fn write(&mut self, slot: u64, address: Pubkey, pool: Pool) -> Vec<(Pubkey, u64, Pool)> {
+        if self.release_at.is_none() {
+            return vec![(address, slot, pool)];
+        }
+        if slot > self.released {
+            self.pending.entry(slot).or_default().push((address, pool));
+        }
+        Vec::new()
+    }
+
+    fn slot(&mut self, slot: u64, parent: Option<u64>, status: SlotStatus) -> Vec<(Pubkey, u64, Pool)> {
+        if let Some(parent) = parent {
+            self.parents.insert(slot, parent);
+        }
+        if status == SlotStatus::SlotDead {
+            if let Some(writes) = self.pending.remove(&slot) {
+                tracing::debug!(slot, writes = writes.len(), "dropped writes of dead slot");
+            }
+            return Vec::new();
+        }
+        if Some(status) != self.release_at || slot <= self.released {
+            return Vec::new();
+        }
+
+        // Walk the ancestry of the newly committed slot back to the last release
+        let mut chain = vec![slot];
+        let mut lowest = slot;
+        let mut complete = false;
+        while let Some(&parent) = self.parents.get(&lowest) {
+            if parent <= self.released {
+                complete = true;
+                break;
+            }
+            chain.push(parent);
+            lowest = parent;
+        }
+        // Without parent links below `lowest` nothing there can be proven forked: keep it
+        if !complete {
+            chain.extend(self.pending.range(self.released + 1..lowest).map(|(slot, _)| *slot));
+        }
+        chain.sort_unstable();
+
+        let mut out = Vec::new();
+        for s in chain {
+            if let Some(writes) = self.pending.remove(&s) {
+                out.extend(writes.into_iter().map(|(address, pool)| (address, s, pool)));
+            }
+        }
+        let forked: usize = self.pending.range(..=slot).map(|(_, writes)| writes.len()).sum();
+        if forked > 0 {
+            tracing::info!(slot, writes = forked, "dropped writes from abandoned forks");
+        }
+        self.pending = self.pending.split_off(&(slot + 1));
+        self.parents = self.parents.split_off(&slot);
+        self.released = slot;
+        out
+    }
+}
@@ -0,0 +1,105 @@
+//! Async event feeds for Rust bots and UIs.
+//!
+//! [`Subscription::events`] is a `Stream` of decoded [`PoolEvent`]s from
+//! `logsSubscribe`; after every reconnect the gap is backfilled through
+//! `getSignaturesForAddress` + `getTransaction`, so consumers see each
+//! transaction's events once and in order. [`Subscription::pool_updates`]
+//! streams raw `Pool` account changes from `programSubscribe`; with the
+//! `yellowstone` feature, [`GeyserSubscription`] delivers the same updates over
+//! Yellowstone gRPC with fork-aware buffering.
+
+mod accounts;
+#[cfg(feature = "yellowstone")]
+mod geyser;
+mod logs;
+mod stream;
+
+pub use accounts::PoolUpdates;
+#[cfg(feature = "yellowstone")]
+pub use geyser::{GeyserPoolUpdates, GeyserSubscription};
+pub use logs::program_events;
+pub use ml_types::events::PoolEvent;
+pub use stream::{EventEnvelope, EventStream};
+
+use std::time::Duration;
+
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Rpc(#[from] solana_client::client_error::ClientError),
+    #[error(transparent)]
+    Pubsub(#[from] solana_client::nonblocking::pubsub_client::PubsubClientError),
+    #[error("invalid signature: {0}")]
+    Signature(#[from] solana_sdk::signature::ParseSignatureError),
+    #[cfg(feature = "yellowstone")]
+    #[error("geyser: {0}")]
+    Geyser(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Connection settings shared by both feeds
+#[derive(Clone, Debug)]
+pub struct Subscription {
+    rpc_url: String,
+    ws_url: String,
+    program_id: Pubkey,
+    commitment: CommitmentConfig,
+    backfill_from: Option<Signature>,
+    backfill_limit: usize,
+    max_backoff: Duration,
+}
+
+impl Subscription {
+    pub fn new(rpc_url: impl Into<String>, ws_url: impl Into<String>) -> Self {
+        Self {
+            rpc_url: rpc_url.into(),
+            ws_url: ws_url.into(),
+            program_id: Pubkey::new_from_array(ml_types::PROGRAM_ID.to_bytes()),
+            commitment: CommitmentConfig::confirmed(),
+            backfill_from: None,
+            backfill_limit: 1_000,
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+
+    pub fn program_id(mut self, program_id: Pubkey) -> Self {
+        self.program_id = program_id;
+        self
+    }
+
+    pub fn commitment(mut self, commitment: CommitmentConfig) -> Self {
+        self.commitment = commitment;
+        self
+    }
+
+    /// Replay every transaction after `signature` before going live
+    pub fn backfill_from(mut self, signature: Signature) -> Self {
+        self.backfill_from = Some(signature);
+        self
+    }
+
+    /// Upper bound on transactions replayed per backfill
+    pub fn backfill_limit(mut self, limit: usize) -> Self {
+        self.backfill_limit = limit;
+        self
+    }
+
+    /// Ceiling of the exponential reconnect delay
+    pub fn max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Decoded events with their transaction context. Must be called inside a Tokio runtime.
+    pub fn events(self) -> EventStream {
+        EventStream::spawn(self)
+    }
+
+    /// `Pool` account changes. Must be called inside a Tokio runtime.
+    pub fn pool_updates(self) -> PoolUpdates {
+        PoolUpdates::spawn(self)
+    }
+}
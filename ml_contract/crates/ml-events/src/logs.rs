@@ -0,0 +1,36 @@
+//! Event extraction from transaction logs.
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use ml_types::events::PoolEvent;
+
+/// Events emitted by `program_id` itself, in log order. `Program data:` lines
+/// are attributed to the innermost invoked program, so data logged by other
+/// programs in the same transaction is skipped.
+pub fn program_events(program_id: &str, logs: &[String]) -> Vec<PoolEvent> {
+    let mut stack: Vec<&str> = Vec::new();
+    let mut events = Vec::new();
+
+    for line in logs {
+        let Some(rest) = line.strip_prefix("Program ") else {
+            continue;
+        };
+        if let Some(data) = rest.strip_prefix("data: ") {
+            if stack.last() == Some(&program_id) {
+                if let Some(event) = STANDARD.decode(data).ok().and_then(|b| PoolEvent::try_from_bytes(&b).ok()) {
+                    events.push(event);
+                }
+            }
+            continue;
+        }
+
+        let mut parts = rest.split_whitespace();
+        match (parts.next(), parts.next()) {
+            (Some(id), Some("invoke")) => stack.push(id),
+            (Some(_), Some("success" | "failed:")) => {
+                stack.pop();
+            }
+            _ => {}
+        }
+    }
+    events
+}
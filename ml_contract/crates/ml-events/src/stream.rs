@@ -0,0 +1,191 @@
+use std::{
+    collections::{HashSet, VecDeque},
+    pin::Pin,
+    str::FromStr,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures::{Stream, StreamExt};
+use ml_types::events::PoolEvent;
+use solana_client::{
+    nonblocking::{pubsub_client::PubsubClient, rpc_client::RpcClient},
+    rpc_client::GetConfirmedSignaturesForAddress2Config,
+    rpc_config::{RpcTransactionConfig, RpcTransactionLogsConfig, RpcTransactionLogsFilter},
+};
+use solana_sdk::signature::Signature;
+use solana_transaction_status::UiTransactionEncoding;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::UnboundedReceiverStream;
+
+use crate::{program_events, Result, Subscription};
+
+/// Signatures remembered for de-duplicating backfill against the live feed
+const SEEN_CAPACITY: usize = 4_096;
+
+/// An event and the transaction that emitted it
+#[derive(Clone, Debug)]
+pub struct EventEnvelope {
+    pub signature: Signature,
+    pub slot: u64,
+    pub event: PoolEvent,
+}
+
+/// `Stream<Item = EventEnvelope>`; the feed task stops when this is dropped
+pub struct EventStream {
+    inner: UnboundedReceiverStream<EventEnvelope>,
+}
+
+impl EventStream {
+    pub(crate) fn spawn(config: Subscription) -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        tokio::spawn(Feed::new(config, sender).run());
+        Self { inner: UnboundedReceiverStream::new(receiver) }
+    }
+
+    /// Drops the transaction context: a plain `Stream<Item = PoolEvent>`
+    pub fn pool_events(self) -> impl Stream<Item = PoolEvent> {
+        self.map(|envelope| envelope.event)
+    }
+}
+
+impl Stream for EventStream {
+    type Item = EventEnvelope;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.inner).poll_next(cx)
+    }
+}
+
+struct Feed {
+    config: Subscription,
+    rpc: RpcClient,
+    sender: mpsc::UnboundedSender<EventEnvelope>,
+    /// Newest delivered signature; the next backfill stops there
+    last: Option<Signature>,
+    seen: HashSet<Signature>,
+    seen_order: VecDeque<Signature>,
+}
+
+impl Feed {
+    fn new(config: Subscription, sender: mpsc::UnboundedSender<EventEnvelope>) -> Self {
+        let rpc = RpcClient::new_with_commitment(config.rpc_url.clone(), config.commitment);
+        Self {
+            last: config.backfill_from,
+            config,
+            rpc,
+            sender,
+            seen: HashSet::new(),
+            seen_order: VecDeque::new(),
+        }
+    }
+
+    async fn run(mut self) {
+        let mut backoff = Duration::from_millis(500);
+        while !self.sender.is_closed() {
+            match self.session().await {
+                Ok(()) => backoff = Duration::from_millis(500),
+                Err(err) => tracing::warn!(error = %err, "event subscription dropped"),
+            }
+            if self.sender.is_closed() {
+                break;
+            }
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(self.config.max_backoff);
+        }
+    }
+
+    /// One websocket connection: subscribe, fill the gap since `last`, then stream
+    async fn session(&mut self) -> Result<()> {
+        let client = PubsubClient::new(&self.config.ws_url).await?;
+        let (mut live, unsubscribe) = client
+            .logs_subscribe(
+                RpcTransactionLogsFilter::Mentions(vec![self.config.program_id.to_string()]),
+                RpcTransactionLogsConfig { commitment: Some(self.config.commitment) },
+            )
+            .await?;
+
+        if self.last.is_some() {
+            self.backfill().await?;
+        }
+
+        while let Some(notification) = live.next().await {
+            if notification.value.err.is_some() {
+                continue;
+            }
+            let signature = Signature::from_str(&notification.value.signature)?;
+            let events = program_events(&self.config.program_id.to_string(), &notification.value.logs);
+            if !self.deliver(signature, notification.context.slot, events) {
+                break;
+            }
+        }
+        unsubscribe().await;
+        Ok(())
+    }
+
+    async fn backfill(&mut self) -> Result<()> {
+        let mut pending = Vec::new();
+        let mut before = None;
+        loop {
+            let config = GetConfirmedSignaturesForAddress2Config {
+                before,
+                until: self.last,
+                limit: Some(1_000),
+                commitment: Some(self.config.commitment),
+            };
+            let page = self
+                .rpc
+                .get_signatures_for_address_with_config(&self.config.program_id, config)
+                .await?;
+            let Some(oldest) = page.last() else { break };
+            before = Some(Signature::from_str(&oldest.signature)?);
+            pending.extend(page.into_iter().filter(|s| s.err.is_none()));
+            if pending.len() >= self.config.backfill_limit {
+                pending.truncate(self.config.backfill_limit);
+                break;
+            }
+        }
+
+        tracing::debug!(count = pending.len(), "backfilling events");
+        let tx_config = RpcTransactionConfig {
+            encoding: Some(UiTransactionEncoding::Base64),
+            commitment: Some(self.config.commitment),
+            max_supported_transaction_version: Some(0),
+        };
+        for status in pending.into_iter().rev() {
+            let signature = Signature::from_str(&status.signature)?;
+            if self.seen.contains(&signature) {
+                continue;
+            }
+            let tx = self.rpc.get_transaction_with_config(&signature, tx_config).await?;
+            let logs: Vec<String> = tx
+                .transaction
+                .meta
+                .and_then(|meta| Option::from(meta.log_messages))
+                .unwrap_or_default();
+            let events = program_events(&self.config.program_id.to_string(), &logs);
+            if !self.deliver(signature, tx.slot, events) {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// `false` once the consumer is gone
+    fn deliver(&mut self, signature: Signature, slot: u64, events: Vec<crate::PoolEvent>) -> bool {
+        if !self.seen.insert(signature) {
+            return true;
+        }
+        self.seen_order.push_back(signature);
+        if self.seen_order.len() > SEEN_CAPACITY {
+            if let Some(old) = self.seen_order.pop_front() {
+                self.seen.remove(&old);
+            }
+        }
+        self.last = Some(signature);
+
+        events
+            .into_iter()
+            .all(|event| self.sender.send(EventEnvelope { signature, slot, event }).is_ok())
+    }
+}
@@ -0,0 +1,59 @@
+-- Normalized pool history built from program events.
+-- Pubkeys are base58 text; token amounts are base units (u64 fits BIGINT for supported mints).
+
+CREATE TABLE pools (
+    pool_id             TEXT PRIMARY KEY,
+    numerical_pool_id   BIGINT      NOT NULL,
+    status              TEXT        NOT NULL,
+    status_reason       SMALLINT    NOT NULL DEFAULT 0,
+    participant_count   SMALLINT    NOT NULL DEFAULT 0,
+    total_amount        BIGINT      NOT NULL DEFAULT 0,
+    dev_fee_bps         INTEGER,
+    burn_fee_bps        INTEGER,
+    treasury_fee_bps    INTEGER,
+    created_signature   TEXT,
+    created_at          TIMESTAMPTZ,
+    updated_slot        BIGINT      NOT NULL,
+    updated_at          TIMESTAMPTZ
+);
+
+CREATE INDEX pools_status ON pools (status);
+
+-- One row per wallet-level movement: join, donate, refund, transfer_in, transfer_out
+CREATE TABLE entries (
+    signature           TEXT        NOT NULL,
+    event_index         INTEGER     NOT NULL,
+    pool_id             TEXT        NOT NULL,
+    action              TEXT        NOT NULL,
+    wallet              TEXT,
+    amount              BIGINT      NOT NULL,
+    participant_rank    SMALLINT,
+    slot                BIGINT      NOT NULL,
+    block_time          TIMESTAMPTZ,
+    PRIMARY KEY (signature, event_index)
+);
+
+CREATE INDEX entries_pool ON entries (pool_id, slot);
+CREATE INDEX entries_wallet ON entries (wallet, slot);
+
+CREATE TABLE payouts (
+    pool_id             TEXT PRIMARY KEY,
+    winner              TEXT        NOT NULL,
+    winner_amount       BIGINT      NOT NULL,
+    dev_amount          BIGINT      NOT NULL,
+    burn_amount         BIGINT      NOT NULL,
+    treasury_amount     BIGINT      NOT NULL,
+    randomness          TEXT        NOT NULL,
+    signature           TEXT        NOT NULL,
+    slot                BIGINT      NOT NULL,
+    block_time          TIMESTAMPTZ
+);
+
+CREATE INDEX payouts_winner ON payouts (winner);
+
+-- Newest fully processed transaction; backfill resumes after it
+CREATE TABLE indexer_cursor (
+    id                  BOOLEAN PRIMARY KEY DEFAULT TRUE CHECK (id),
+    signature           TEXT        NOT NULL,
+    slot                BIGINT      NOT NULL
+);
@@ -0,0 +1,132 @@
+//! Live log subscription with backfill through `getSignaturesForAddress`.
+
+use std::{str::FromStr, sync::Arc, time::Duration};
+
+use anyhow::Result;
+use futures::StreamExt;
+use solana_client::{
+    nonblocking::{pubsub_client::PubsubClient, rpc_client::RpcClient},
+    rpc_client::GetConfirmedSignaturesForAddress2Config,
+    rpc_config::{RpcTransactionLogsConfig, RpcTransactionLogsFilter},
+};
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature};
+use tokio::sync::mpsc;
+
+use crate::{store::Store, tx};
+
+pub struct Indexer {
+    pub rpc: Arc<RpcClient>,
+    pub ws_url: String,
+    pub program_id: Pubkey,
+    pub commitment: CommitmentConfig,
+    pub store: Store,
+    /// Oldest-first history limit when the database is empty
+    pub backfill_limit: usize,
+}
+
+impl Indexer {
+    /// Subscribes first and backfills second so nothing lands between the two;
+    /// on disconnect the whole cycle repeats from the stored cursor.
+    pub async fn run(&self) -> Result<()> {
+        loop {
+            let (sender, mut live) = mpsc::unbounded_channel();
+            let subscription = tokio::spawn(subscribe(self.ws_url.clone(), self.program_id, self.commitment, sender));
+
+            if let Err(err) = self.backfill().await {
+                tracing::warn!(error = %err, "backfill failed");
+            }
+
+            while let Some(signature) = live.recv().await {
+                if let Err(err) = self.process(&signature).await {
+                    tracing::warn!(%signature, error = %err, "processing failed");
+                }
+            }
+
+            match subscription.await {
+                Ok(Err(err)) => tracing::warn!(error = %err, "subscription ended"),
+                Err(err) => tracing::warn!(error = %err, "subscription task panicked"),
+                Ok(Ok(())) => tracing::warn!("subscription closed"),
+            }
+            tokio::time::sleep(Duration::from_secs(2)).await;
+        }
+    }
+
+    async fn process(&self, signature: &str) -> Result<()> {
+        let Some(tx) = tx::fetch(&self.rpc, &self.program_id, signature, self.commitment).await? else {
+            return Ok(());
+        };
+        if !tx.events.is_empty() {
+            self.store.apply(&tx).await?;
+            tracing::debug!(%signature, events = tx.events.len(), "indexed");
+        }
+        self.store.set_cursor(signature, tx.slot).await
+    }
+
+    /// Pages back from the tip to the cursor, then replays oldest first
+    async fn backfill(&self) -> Result<()> {
+        let until = self.store.cursor().await?.map(|s| Signature::from_str(&s)).transpose()?;
+        let mut pending = Vec::new();
+        let mut before = None;
+
+        loop {
+            let config = GetConfirmedSignaturesForAddress2Config {
+                before,
+                until,
+                limit: Some(1000),
+                commitment: Some(self.commitment),
+            };
+            let page = self
+                .rpc
+                .get_signatures_for_address_with_config(&self.program_id, config)
+                .await?;
+            let Some(last) = page.last() else { break };
+            before = Some(Signature::from_str(&last.signature)?);
+            pending.extend(page.into_iter().filter(|s| s.err.is_none()).map(|s| s.signature));
+            if until.is_none() && pending.len() >= self.backfill_limit {
+                pending.truncate(self.backfill_limit);
+                break;
+            }
+        }
+
+        tracing::info!(count = pending.len(), "backfilling");
+        for signature in pending.iter().rev() {
+            self.process(signature).await?;
+        }
+        Ok(())
+    }
+}
+
+async fn subscribe(
+    ws_url: String,
+    program_id: Pubkey,
+    commitment: CommitmentConfig,
+    sender: mpsc::UnboundedSender<String>,
+) -> Result<()> {
+    let client = PubsubClient::new(&ws_url).await?;
+    let (mut stream, unsubscribe) = client
+        .logs_subscribe(
+            RpcTransactionLogsFilter::Mentions(vec![program_id.to_string()]),
+            RpcTransactionLogsConfig { commitment: Some(commitment) },
+        )
+        .await?;
+    tracing::info!(%program_id, "subscribed to program logs");
+
+    while let Some(notification) = stream.next().await {
+        if notification.value.err.is_none() && sender.send(notification.value.signature).is_err() {
+            break;
+        }
+    }
+    unsubscribe().await;
+    Ok(())
+}
+
+/// Applies gRPC pool account writes until the process exits
+#[cfg(feature = "yellowstone")]
+pub async fn pool_accounts(subscription: ml_events::GeyserSubscription, store: Store) {
+    let mut updates = subscription.pool_updates();
+    while let Some((address, slot, pool)) = updates.next().await {
+        if let Err(err) = store.apply_account(&address.to_string(), slot, &pool).await {
+            tracing::warn!(pool = %address, slot, error = %err, "account write failed");
+        }
+    }
+}
@@ -0,0 +1,81 @@
+//! `ml-indexer`: decodes program events (via `ml-types`) from transaction logs
+//! and maintains `pools`, `entries` and `payouts` tables in Postgres.
+//!
+//! With the `yellowstone` feature, `--geyser-url` additionally applies pool
+//! account writes from Yellowstone gRPC, so status columns move within
+//! milliseconds of confirmation.
+
+mod ingest;
+mod store;
+mod tx;
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use clap::Parser;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
+use tracing_subscriber::EnvFilter;
+
+use crate::{ingest::Indexer, store::Store};
+
+#[derive(Parser)]
+#[command(name = "ml-indexer", version, about = "Index MissOut program events into Postgres")]
+struct Config {
+    #[arg(long, env = "ML_RPC_URL", default_value = "https://api.devnet.solana.com")]
+    rpc_url: String,
+
+    /// Websocket endpoint; derived from the RPC URL when omitted
+    #[arg(long, env = "ML_WS_URL")]
+    ws_url: Option<String>,
+
+    #[arg(long, env = "DATABASE_URL")]
+    database_url: String,
+
+    /// Transactions replayed on first start with an empty database
+    #[arg(long, default_value_t = 10_000)]
+    backfill_limit: usize,
+
+    /// Yellowstone gRPC endpoint for pool account writes
+    #[cfg(feature = "yellowstone")]
+    #[arg(long, env = "ML_GEYSER_URL")]
+    geyser_url: Option<String>,
+
+    #[cfg(feature = "yellowstone")]
+    #[arg(long, env = "ML_GEYSER_X_TOKEN")]
+    geyser_x_token: Option<String>,
+}
+
+fn ws_from_http(url: &str) -> String {
+    url.replacen("https://", "wss://", 1).replacen("http://", "ws://", 1)
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")))
+        .init();
+
+    let config = Config::parse();
+    let commitment = CommitmentConfig::confirmed();
+    let store = Store::connect(&config.database_url).await?;
+
+    #[cfg(feature = "yellowstone")]
+    if let Some(url) = &config.geyser_url {
+        let mut subscription = ml_events::GeyserSubscription::new(url.clone()).commitment(commitment);
+        if let Some(token) = &config.geyser_x_token {
+            subscription = subscription.x_token(token.clone());
+        }
+        tokio::spawn(ingest::pool_accounts(subscription, store.clone()));
+    }
+
+    let indexer = Indexer {
+        rpc: Arc::new(RpcClient::new_with_commitment(config.rpc_url.clone(), commitment)),
+        ws_url: config.ws_url.unwrap_or_else(|| ws_from_http(&config.rpc_url)),
+        program_id: Pubkey::new_from_array(ml_types::PROGRAM_ID.to_bytes()),
+        commitment,
+        store,
+        backfill_limit: config.backfill_limit,
+    };
+    indexer.run().await
+}
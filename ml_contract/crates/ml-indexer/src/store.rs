@@ -0,0 +1,262 @@
+//! Postgres writes. Every statement is idempotent so backfill and the live
+//! subscription may deliver the same transaction twice.
+
+use std::collections::VecDeque;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use ml_types::{events::PoolEvent, state::ActionType};
+use sqlx::{postgres::PgPoolOptions, PgPool, Postgres, Transaction};
+
+use crate::tx::{ProgramTx, UserInstruction};
+
+#[derive(Clone)]
+pub struct Store {
+    db: PgPool,
+}
+
+struct Entry<'a> {
+    pool_id: String,
+    action: &'a str,
+    wallet: Option<String>,
+    amount: u64,
+    rank: Option<u8>,
+}
+
+impl Store {
+    pub async fn connect(url: &str) -> Result<Self> {
+        let db = PgPoolOptions::new().max_connections(8).connect(url).await?;
+        sqlx::migrate!("./migrations").run(&db).await?;
+        Ok(Self { db })
+    }
+
+    pub async fn cursor(&self) -> Result<Option<String>> {
+        let row: Option<(String,)> = sqlx::query_as("SELECT signature FROM indexer_cursor")
+            .fetch_optional(&self.db)
+            .await?;
+        Ok(row.map(|(signature,)| signature))
+    }
+
+    pub async fn set_cursor(&self, signature: &str, slot: u64) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO indexer_cursor (signature, slot) VALUES ($1, $2)
+             ON CONFLICT (id) DO UPDATE SET signature = $1, slot = $2
+             WHERE indexer_cursor.slot <= $2",
+        )
+        .bind(signature)
+        .bind(slot as i64)
+        .execute(&self.db)
+        .await?;
+        Ok(())
+    }
+
+    /// Status columns from a raw account write (gRPC feed); same slot guard as events
+    #[cfg(feature = "yellowstone")]
+    pub async fn apply_account(&self, address: &str, slot: u64, pool: &ml_types::state::Pool) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO pools (pool_id, numerical_pool_id, status, status_reason, total_amount, updated_slot)
+             VALUES ($1, $2, $3, $4, $5, $6)
+             ON CONFLICT (pool_id) DO UPDATE SET
+                 status = EXCLUDED.status,
+                 status_reason = EXCLUDED.status_reason,
+                 total_amount = EXCLUDED.total_amount,
+                 updated_slot = EXCLUDED.updated_slot
+             WHERE pools.updated_slot <= EXCLUDED.updated_slot",
+        )
+        .bind(address)
+        .bind(pool.pool_id as i64)
+        .bind(pool.status.as_str())
+        .bind(pool.status_reason as i16)
+        .bind(pool.total_amount as i64)
+        .bind(slot as i64)
+        .execute(&self.db)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn apply(&self, tx: &ProgramTx) -> Result<()> {
+        let block_time = tx.block_time.and_then(|t| DateTime::<Utc>::from_timestamp(t, 0));
+        let mut joins: VecDeque<_> = tx.users.iter().filter(|(k, _)| *k == UserInstruction::Join).map(|(_, u)| *u).collect();
+        let mut donates: VecDeque<_> =
+            tx.users.iter().filter(|(k, _)| *k == UserInstruction::Donate).map(|(_, u)| *u).collect();
+
+        let mut db = self.db.begin().await?;
+        for (index, event) in tx.events.iter().enumerate() {
+            let entry = match event {
+                PoolEvent::PoolStateEvent(e) => {
+                    upsert_pool_state(&mut db, tx, block_time, e).await?;
+                    None
+                }
+                PoolEvent::PoolActivityEvent(e) => match e.action {
+                    ActionType::Created => {
+                        record_created(&mut db, tx, block_time, e).await?;
+                        None
+                    }
+                    ActionType::Joined => Some(Entry {
+                        pool_id: e.pool_id.to_string(),
+                        action: "join",
+                        wallet: joins.pop_front().map(|u| u.to_string()),
+                        amount: e.amount,
+                        rank: Some(e.participant_rank),
+                    }),
+                    ActionType::Donated => Some(Entry {
+                        pool_id: e.pool_id.to_string(),
+                        action: "donate",
+                        wallet: donates.pop_front().map(|u| u.to_string()),
+                        amount: e.amount,
+                        rank: None,
+                    }),
+                    _ => None,
+                },
+                PoolEvent::RefundClaimedEvent(e) => Some(Entry {
+                    pool_id: e.pool_id.to_string(),
+                    action: "refund",
+                    wallet: Some(e.user.to_string()),
+                    amount: e.amount,
+                    rank: None,
+                }),
+                PoolEvent::EntryTransferred(e) => {
+                    insert_entry(
+                        &mut db,
+                        tx,
+                        block_time,
+                        index as i32 * 2,
+                        Entry {
+                            pool_id: e.pool_id.to_string(),
+                            action: "transfer_out",
+                            wallet: Some(e.from.to_string()),
+                            amount: e.amount,
+                            rank: Some(e.index),
+                        },
+                    )
+                    .await?;
+                    Some(Entry {
+                        pool_id: e.pool_id.to_string(),
+                        action: "transfer_in",
+                        wallet: Some(e.to.to_string()),
+                        amount: e.amount,
+                        rank: Some(e.index),
+                    })
+                }
+                PoolEvent::WinnerSelectedEvent(e) => {
+                    sqlx::query(
+                        "INSERT INTO payouts (pool_id, winner, winner_amount, dev_amount, burn_amount,
+                                              treasury_amount, randomness, signature, slot, block_time)
+                         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                         ON CONFLICT (pool_id) DO NOTHING",
+                    )
+                    .bind(e.pool_id.to_string())
+                    .bind(e.winner.to_string())
+                    .bind(e.winner_amount as i64)
+                    .bind(e.dev_amount as i64)
+                    .bind(e.burn_amount as i64)
+                    .bind(e.treasury_amount as i64)
+                    .bind(e.randomness.to_string())
+                    .bind(&tx.signature)
+                    .bind(tx.slot as i64)
+                    .bind(block_time)
+                    .execute(&mut *db)
+                    .await?;
+                    None
+                }
+                _ => None,
+            };
+            if let Some(entry) = entry {
+                // Odd slots leave room for the paired transfer_out row
+                insert_entry(&mut db, tx, block_time, index as i32 * 2 + 1, entry).await?;
+            }
+        }
+        db.commit().await?;
+        Ok(())
+    }
+}
+
+async fn upsert_pool_state(
+    db: &mut Transaction<'_, Postgres>,
+    tx: &ProgramTx,
+    block_time: Option<DateTime<Utc>>,
+    e: &ml_types::events::PoolStateEvent,
+) -> Result<()> {
+    // Older slots never overwrite newer state (backfill runs behind the live feed)
+    sqlx::query(
+        "INSERT INTO pools (pool_id, numerical_pool_id, status, status_reason, participant_count,
+                            total_amount, updated_slot, updated_at)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+         ON CONFLICT (pool_id) DO UPDATE SET
+             status = EXCLUDED.status,
+             status_reason = EXCLUDED.status_reason,
+             participant_count = EXCLUDED.participant_count,
+             total_amount = EXCLUDED.total_amount,
+             updated_slot = EXCLUDED.updated_slot,
+             updated_at = EXCLUDED.updated_at
+         WHERE pools.updated_slot <= EXCLUDED.updated_slot",
+    )
+    .bind(e.pool_id.to_string())
+    .bind(e.numerical_pool_id as i64)
+    .bind(e.status.as_str())
+    .bind(e.status_reason as i16)
+    .bind(e.participant_count as i16)
+    .bind(e.total_amount as i64)
+    .bind(tx.slot as i64)
+    .bind(block_time)
+    .execute(&mut **db)
+    .await?;
+    Ok(())
+}
+
+async fn record_created(
+    db: &mut Transaction<'_, Postgres>,
+    tx: &ProgramTx,
+    block_time: Option<DateTime<Utc>>,
+    e: &ml_types::events::PoolActivityEvent,
+) -> Result<()> {
+    sqlx::query(
+        "INSERT INTO pools (pool_id, numerical_pool_id, status, dev_fee_bps, burn_fee_bps, treasury_fee_bps,
+                            created_signature, created_at, updated_slot, updated_at)
+         VALUES ($1, $2, 'open', $3, $4, $5, $6, $7, $8, $7)
+         ON CONFLICT (pool_id) DO UPDATE SET
+             dev_fee_bps = EXCLUDED.dev_fee_bps,
+             burn_fee_bps = EXCLUDED.burn_fee_bps,
+             treasury_fee_bps = EXCLUDED.treasury_fee_bps,
+             created_signature = EXCLUDED.created_signature,
+             created_at = EXCLUDED.created_at",
+    )
+    .bind(e.pool_id.to_string())
+    .bind(e.numerical_pool_id as i64)
+    .bind(e.dev_fee_percent as i32)
+    .bind(e.burn_fee_percent as i32)
+    .bind(e.treasury_fee_percent as i32)
+    .bind(&tx.signature)
+    .bind(block_time)
+    .bind(tx.slot as i64)
+    .execute(&mut **db)
+    .await?;
+    Ok(())
+}
+
+async fn insert_entry(
+    db: &mut Transaction<'_, Postgres>,
+    tx: &ProgramTx,
+    block_time: Option<DateTime<Utc>>,
+    event_index: i32,
+    entry: Entry<'_>,
+) -> Result<()> {
+    sqlx::query(
+        "INSERT INTO entries (signature, event_index, pool_id, action, wallet, amount, participant_rank,
+                              slot, block_time)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+         ON CONFLICT (signature, event_index) DO NOTHING",
+    )
+    .bind(&tx.signature)
+    .bind(event_index)
+    .bind(entry.pool_id)
+    .bind(entry.action)
+    .bind(entry.wallet)
+    .bind(entry.amount as i64)
+    .bind(entry.rank.map(i16::from))
+    .bind(tx.slot as i64)
+    .bind(block_time)
+    .execute(&mut **db)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,97 @@
+//! Transaction fetch and the account context events don't carry.
+
+use anyhow::{anyhow, Result};
+use sha2::{Digest, Sha256};
+use solana_client::{nonblocking::rpc_client::RpcClient, rpc_config::RpcTransactionConfig};
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature};
+use solana_transaction_status::{option_serializer::OptionSerializer, UiTransactionEncoding};
+use std::str::FromStr;
+
+use ml_events::program_events;
+use ml_types::events::PoolEvent;
+
+/// Position of `user` in the `JoinPool` / `Donate` account lists
+const JOIN_USER_INDEX: usize = 4;
+const DONATE_USER_INDEX: usize = 4;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UserInstruction {
+    Join,
+    Donate,
+}
+
+/// A successful program transaction reduced to what the store needs
+pub struct ProgramTx {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    pub events: Vec<PoolEvent>,
+    /// Signing wallets of top-level `join_pool` / `donate` calls, in order
+    pub users: Vec<(UserInstruction, Pubkey)>,
+}
+
+fn ix_discriminator(name: &str) -> [u8; 8] {
+    let hash = Sha256::digest(format!("global:{name}"));
+    hash[..8].try_into().unwrap()
+}
+
+/// `None` for failed transactions
+pub async fn fetch(
+    rpc: &RpcClient,
+    program_id: &Pubkey,
+    signature: &str,
+    commitment: CommitmentConfig,
+) -> Result<Option<ProgramTx>> {
+    let config = RpcTransactionConfig {
+        encoding: Some(UiTransactionEncoding::Base64),
+        commitment: Some(commitment),
+        max_supported_transaction_version: Some(0),
+    };
+    let tx = rpc
+        .get_transaction_with_config(&Signature::from_str(signature)?, config)
+        .await?;
+    let meta = tx.transaction.meta.ok_or_else(|| anyhow!("{signature}: missing meta"))?;
+    if meta.err.is_some() {
+        return Ok(None);
+    }
+
+    let logs: Vec<String> = Option::from(meta.log_messages).unwrap_or_default();
+    let events = program_events(&program_id.to_string(), &logs);
+
+    let versioned = tx
+        .transaction
+        .transaction
+        .decode()
+        .ok_or_else(|| anyhow!("{signature}: undecodable transaction"))?;
+    let mut keys = versioned.message.static_account_keys().to_vec();
+    if let OptionSerializer::Some(loaded) = meta.loaded_addresses {
+        for key in loaded.writable.iter().chain(&loaded.readonly) {
+            keys.push(Pubkey::from_str(key)?);
+        }
+    }
+
+    let join = ix_discriminator("join_pool");
+    let donate = ix_discriminator("donate");
+    let mut users = Vec::new();
+    for ix in versioned.message.instructions() {
+        if keys.get(ix.program_id_index as usize) != Some(program_id) || ix.data.len() < 8 {
+            continue;
+        }
+        let (kind, index) = match &ix.data[..8] {
+            d if d == join => (UserInstruction::Join, JOIN_USER_INDEX),
+            d if d == donate => (UserInstruction::Donate, DONATE_USER_INDEX),
+            _ => continue,
+        };
+        if let Some(user) = ix.accounts.get(index).and_then(|&i| keys.get(i as usize)) {
+            users.push((kind, *user));
+        }
+    }
+
+    Ok(Some(ProgramTx {
+        signature: signature.to_owned(),
+        slot: tx.slot,
+        block_time: tx.block_time,
+        events,
+        users,
+    }))
+}
@@ -0,0 +1,9 @@
+//! Test-only fixtures for exercising the real (non-mock) randomness and price
+//! paths of the `ml` program on localnet / program-test, without the
+//! Switchboard or Pyth oracles.
+
+mod price;
+mod randomness;
+
+pub use price::*;
+pub use randomness::*;
@@ -0,0 +1,97 @@
+//! Pyth pull-oracle `PriceUpdateV2` accounts and `PriceFeedConfig` registrations,
+//! for creating USD-denominated pools without the Pyth receiver or `PLATFORM_ADMIN`.
+//!
+//! `PriceUpdateV2` layout (borsh, after the 8-byte discriminator): write_authority,
+//! verification_level (`Full` = 1), feed_id, price, conf, exponent, publish_time,
+//! prev_publish_time, ema_price, ema_conf, posted_slot.
+
+use ml_types::{state::PriceFeedConfig, BorshSerialize, Discriminator};
+use solana_sdk::{account::Account, pubkey, pubkey::Pubkey, rent::Rent};
+
+/// `PriceUpdateV2::DISCRIMINATOR`
+pub const PRICE_UPDATE_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+/// `PriceUpdateV2::LEN`, sized for the larger `Partial` verification level
+pub const PRICE_UPDATE_ACCOUNT_LEN: usize = 8 + 32 + 2 + 32 + 8 + 8 + 4 + 8 + 8 + 8 + 8 + 8;
+/// Pyth receiver program owning price update accounts
+pub const PYTH_RECEIVER_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+
+/// Contents of a fully verified Pyth price update
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MockPriceUpdate {
+    pub feed_id: [u8; 32],
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+    pub posted_slot: u64,
+}
+
+impl MockPriceUpdate {
+    /// `price * 10^exponent` USD per token, published at `publish_time`, with no confidence interval
+    pub fn new(feed_id: [u8; 32], price: i64, exponent: i32, publish_time: i64) -> Self {
+        Self { feed_id, price, exponent, publish_time, ..Self::default() }
+    }
+
+    pub fn to_account_data(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(PRICE_UPDATE_ACCOUNT_LEN);
+        data.extend_from_slice(&PRICE_UPDATE_DISCRIMINATOR);
+        data.extend_from_slice(&[0u8; 32]); // write_authority
+        data.push(1); // VerificationLevel::Full
+        data.extend_from_slice(&self.feed_id);
+        data.extend_from_slice(&self.price.to_le_bytes());
+        data.extend_from_slice(&self.conf.to_le_bytes());
+        data.extend_from_slice(&self.exponent.to_le_bytes());
+        data.extend_from_slice(&self.publish_time.to_le_bytes());
+        data.extend_from_slice(&self.publish_time.to_le_bytes()); // prev_publish_time
+        data.extend_from_slice(&self.price.to_le_bytes()); // ema_price
+        data.extend_from_slice(&self.conf.to_le_bytes()); // ema_conf
+        data.extend_from_slice(&self.posted_slot.to_le_bytes());
+        data.resize(PRICE_UPDATE_ACCOUNT_LEN, 0);
+        data
+    }
+
+    /// Rent-exempt account owned by the Pyth receiver
+    pub fn to_account(&self) -> Account {
+        Account {
+            lamports: Rent::default().minimum_balance(PRICE_UPDATE_ACCOUNT_LEN),
+            data: self.to_account_data(),
+            owner: PYTH_RECEIVER_ID,
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+}
+
+/// `PriceFeedConfig` account as `set_price_feed` would write it, owned by `program_id`
+pub fn price_feed_account(program_id: &Pubkey, config: &PriceFeedConfig) -> Account {
+    let mut data = PriceFeedConfig::DISCRIMINATOR.to_vec();
+    config.serialize(&mut data).expect("serializing into a Vec");
+    Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: *program_id,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// Writes (or overwrites) a price update account in a running program-test bank
+#[cfg(feature = "program-test")]
+pub fn install_price_update(
+    ctx: &mut solana_program_test::ProgramTestContext,
+    address: &Pubkey,
+    update: &MockPriceUpdate,
+) {
+    ctx.set_account(address, &update.to_account().into());
+}
+
+/// Registers `config` at its `price_feed` PDA in a running program-test bank, bypassing `PLATFORM_ADMIN`
+#[cfg(feature = "program-test")]
+pub fn install_price_feed(
+    ctx: &mut solana_program_test::ProgramTestContext,
+    program_id: &Pubkey,
+    address: &Pubkey,
+    config: &PriceFeedConfig,
+) {
+    ctx.set_account(address, &price_feed_account(program_id, config).into());
+}
@@ -0,0 +1,90 @@
+//! Byte-exact Switchboard On-Demand `RandomnessAccountData` accounts.
+//!
+//! Layout (after the 8-byte discriminator, `#[repr(C)]`, 440 bytes):
+//! authority, queue, seed_slothash, seed_slot, oracle, reveal_slot, value, 224 bytes padding.
+
+use ml_types::constants::{SWITCHBOARD_DEVNET, SWITCHBOARD_MAINNET};
+use solana_sdk::{account::Account, pubkey::Pubkey, rent::Rent};
+
+/// `RandomnessAccountData::DISCRIMINATOR`
+pub const RANDOMNESS_DISCRIMINATOR: [u8; 8] = [10, 66, 229, 135, 220, 239, 217, 114];
+/// Account size including the discriminator
+pub const RANDOMNESS_ACCOUNT_LEN: usize = 8 + 440;
+
+/// Switchboard program owning randomness accounts for the given program build
+pub fn switchboard_program_id(mainnet: bool) -> Pubkey {
+    let id = if mainnet { SWITCHBOARD_MAINNET } else { SWITCHBOARD_DEVNET };
+    Pubkey::new_from_array(id.to_bytes())
+}
+
+/// Contents of a Switchboard randomness account
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MockRandomness {
+    pub authority: Pubkey,
+    pub queue: Pubkey,
+    pub seed_slothash: [u8; 32],
+    pub seed_slot: u64,
+    pub oracle: Pubkey,
+    /// 0 until the oracle reveals
+    pub reveal_slot: u64,
+    pub value: [u8; 32],
+}
+
+impl MockRandomness {
+    /// Committed but not yet revealed, as seen by `request_randomness`
+    pub fn committed(seed_slot: u64) -> Self {
+        Self {
+            seed_slot,
+            seed_slothash: [seed_slot as u8; 32],
+            ..Self::default()
+        }
+    }
+
+    /// Revealed with `value`, as seen by `select_winner`
+    pub fn revealed(self, reveal_slot: u64, value: [u8; 32]) -> Self {
+        Self { reveal_slot, value, ..self }
+    }
+
+    /// Value whose first 16 bytes decode (little-endian) to `randomness`
+    pub fn with_u128(self, reveal_slot: u64, randomness: u128) -> Self {
+        let mut value = [0u8; 32];
+        value[..16].copy_from_slice(&randomness.to_le_bytes());
+        self.revealed(reveal_slot, value)
+    }
+
+    pub fn to_account_data(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(RANDOMNESS_ACCOUNT_LEN);
+        data.extend_from_slice(&RANDOMNESS_DISCRIMINATOR);
+        data.extend_from_slice(self.authority.as_ref());
+        data.extend_from_slice(self.queue.as_ref());
+        data.extend_from_slice(&self.seed_slothash);
+        data.extend_from_slice(&self.seed_slot.to_le_bytes());
+        data.extend_from_slice(self.oracle.as_ref());
+        data.extend_from_slice(&self.reveal_slot.to_le_bytes());
+        data.extend_from_slice(&self.value);
+        data.resize(RANDOMNESS_ACCOUNT_LEN, 0);
+        data
+    }
+
+    /// Rent-exempt account owned by the Switchboard program of the given build
+    pub fn to_account(&self, mainnet: bool) -> Account {
+        Account {
+            lamports: Rent::default().minimum_balance(RANDOMNESS_ACCOUNT_LEN),
+            data: self.to_account_data(),
+            owner: switchboard_program_id(mainnet),
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+}
+
+/// Writes (or overwrites) a randomness account in a running program-test bank
+#[cfg(feature = "program-test")]
+pub fn install_randomness(
+    ctx: &mut solana_program_test::ProgramTestContext,
+    address: &Pubkey,
+    randomness: &MockRandomness,
+    mainnet: bool,
+) {
+    ctx.set_account(address, &randomness.to_account(mainnet).into());
+}
@@ -0,0 +1,12 @@
+//! The `ml` program's constants: the shared limits, status reasons and PDA
+//! seed prefixes come from `ml-core`, the well-known addresses are listed here.
+
+use crate::{pubkey, Pubkey};
+
+pub use ml_core::constants::*;
+
+pub const ZERO_PUBKEY: Pubkey = Pubkey::new_from_array([0; 32]);
+
+pub const PLATFORM_ADMIN: Pubkey = pubkey!("DCHhAjoVvJ4mUUkbQrsKrPztRhivrNV3fDJEZfHNQ8d3");
+pub const SWITCHBOARD_MAINNET: Pubkey = pubkey!("SBondMDrcV3K4kxZR1HNVT7osZxAHVHgYXL5Ze1oMUv");
+pub const SWITCHBOARD_DEVNET: Pubkey = pubkey!("Aio4gaXjXzJNVLtzwtNVmSqGKpANtXhybbkhtAC94ji2");
@@ -0,0 +1,232 @@
+//! Program events, mirroring `programs/ml/src/events.rs`.
+//!
+//! Anchor logs each event as `Program data: base64(disc || borsh(event))`;
+//! [`PoolEvent::try_from_bytes`] decodes the bytes after base64.
+
+use alloc::string::String;
+
+use crate::{
+    decode_tagged, discriminator,
+    state::{ActionType, DonationPurpose, HintType, PoolStatus},
+    BorshDeserialize, BorshSerialize, Discriminator, Error, Pubkey,
+};
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PoolStateEvent {
+    pub pool_id: Pubkey,
+    pub numerical_pool_id: u64,
+    pub status: PoolStatus,
+    pub participant_count: u8,
+    pub total_amount: u64,
+    pub status_reason: u8,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PoolActivityEvent {
+    pub pool_id: Pubkey,
+    pub numerical_pool_id: u64,
+    pub action: ActionType,
+    pub amount: u64,
+    pub participant_rank: u8,
+    pub dev_fee_percent: u16,
+    pub burn_fee_percent: u16,
+    pub treasury_fee_percent: u16,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct UIHint {
+    pub pool_id: Pubkey,
+    pub hint: HintType,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RefundBurned {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub reason: u8,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RentClaimed {
+    pub pool_id: Pubkey,
+    pub caller: Pubkey,
+    pub sent_to: Pubkey,
+    pub timestamp: i64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct WinnerSelectedEvent {
+    pub pool_id: Pubkey,
+    pub numerical_pool_id: u64,
+    pub winner: Pubkey,
+    pub winner_amount: u64,
+    pub dev_amount: u64,
+    pub burn_amount: u64,
+    pub treasury_amount: u64,
+    pub randomness: u128,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RefundClaimedEvent {
+    pub pool_id: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub burn_amount: u64,
+    pub reason: u8,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ForfeitedToTreasury {
+    pub pool_id: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PrizeNftDeposited {
+    pub pool_id: Pubkey,
+    pub prize_mint: Pubkey,
+    pub creator: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PrizeNftReleased {
+    pub pool_id: Pubkey,
+    pub prize_mint: Pubkey,
+    pub recipient: Pubkey,
+    pub reason: u8,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PoolCloned {
+    pub pool_id: Pubkey,
+    pub numerical_pool_id: u64,
+    pub source_pool: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DonationEarmarked {
+    pub pool_id: Pubkey,
+    pub donor: Pubkey,
+    pub amount: u64,
+    pub purpose: DonationPurpose,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TeamEntered {
+    pub pool_id: Pubkey,
+    pub team: Pubkey,
+    pub captain: Pubkey,
+    pub member_count: u8,
+    pub amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TeamPrizeDistributed {
+    pub pool_id: Pubkey,
+    pub team: Pubkey,
+    pub amount: u64,
+    pub member_count: u8,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CreatorBondSlashed {
+    pub pool_id: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub treasury: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AdminCloseProposed {
+    pub pool_id: Pubkey,
+    pub proposer: Pubkey,
+    pub reason_code: u8,
+    pub uri: String,
+    pub slash_bond: bool,
+    pub executable_at: i64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AdminCloseExecuted {
+    pub pool_id: Pubkey,
+    pub reason_code: u8,
+    pub uri: String,
+    pub bond_slashed: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AdminCloseWithdrawn {
+    pub pool_id: Pubkey,
+    pub reason_code: u8,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CompressedEntryAppended {
+    pub pool_id: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub index: u32,
+    pub leaf: [u8; 32],
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct EntryTransferred {
+    pub pool_id: Pubkey,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub index: u8,
+    pub amount: u64,
+}
+
+macro_rules! pool_events {
+    ($($name:ident => [$($b:literal),* $(,)?]),* $(,)?) => {
+        discriminator! { $($name => [$($b),*]),* }
+
+        /// Any event emitted by the program
+        #[derive(Clone, Debug, PartialEq, Eq)]
+        pub enum PoolEvent {
+            $($name($name)),*
+        }
+
+        impl PoolEvent {
+            /// Decodes `disc || borsh(event)`, i.e. a base64-decoded `Program data:` log line
+            pub fn try_from_bytes(data: &[u8]) -> Result<Self, Error> {
+                let disc = data.first_chunk::<8>().ok_or(Error::TooShort)?;
+                $(if *disc == <$name as Discriminator>::DISCRIMINATOR {
+                    return decode_tagged::<$name>(data).map(PoolEvent::$name);
+                })*
+                Err(Error::DiscriminatorMismatch)
+            }
+
+            /// Event type name as declared in the program
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(PoolEvent::$name(_) => stringify!($name)),*
+                }
+            }
+        }
+    };
+}
+
+pool_events! {
+    PoolStateEvent => [72, 169, 77, 67, 172, 217, 3, 115],
+    PoolActivityEvent => [116, 221, 203, 110, 114, 139, 97, 124],
+    UIHint => [229, 2, 70, 121, 217, 26, 231, 151],
+    RefundBurned => [54, 180, 75, 149, 111, 26, 28, 217],
+    RentClaimed => [33, 17, 4, 121, 228, 7, 100, 136],
+    WinnerSelectedEvent => [88, 68, 76, 70, 235, 254, 7, 92],
+    RefundClaimedEvent => [77, 83, 172, 123, 235, 58, 154, 233],
+    ForfeitedToTreasury => [39, 110, 130, 240, 19, 62, 31, 116],
+    PrizeNftDeposited => [8, 101, 174, 46, 62, 7, 136, 2],
+    PrizeNftReleased => [127, 206, 114, 248, 110, 66, 84, 240],
+    PoolCloned => [151, 196, 2, 140, 66, 204, 249, 68],
+    DonationEarmarked => [71, 79, 6, 167, 168, 181, 245, 223],
+    TeamEntered => [82, 156, 177, 169, 10, 142, 222, 65],
+    TeamPrizeDistributed => [179, 18, 53, 17, 170, 244, 130, 174],
+    CreatorBondSlashed => [184, 194, 104, 150, 194, 198, 189, 175],
+    AdminCloseProposed => [153, 93, 55, 35, 187, 189, 92, 204],
+    AdminCloseExecuted => [168, 66, 116, 119, 203, 103, 128, 69],
+    AdminCloseWithdrawn => [204, 245, 18, 96, 121, 191, 151, 192],
+    CompressedEntryAppended => [52, 82, 250, 156, 15, 36, 135, 220],
+    EntryTransferred => [62, 2, 114, 122, 199, 78, 116, 16],
+}
@@ -0,0 +1,79 @@
+//! Plain-borsh mirror of the `ml` program's accounts, events and constants.
+//!
+//! Off-chain services (indexer, analyzer, CLI) decode program data with this
+//! crate instead of linking the Anchor program. `no_std` + `alloc` when the
+//! default `std` feature is disabled.
+//!
+//! Layouts must track `programs/ml/src/{state,events}.rs` field for field:
+//! Anchor serializes structs in declaration order and enums by variant
+//! position (not by their explicit discriminant). Constants and the account
+//! array sizes come from `ml-core`, the same definitions the program uses.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub mod constants;
+pub mod events;
+pub mod state;
+
+use core::fmt;
+
+pub use borsh::{BorshDeserialize, BorshSerialize};
+pub use solana_pubkey::{pubkey, Pubkey};
+
+/// Program id of the deployed `ml` program
+pub const PROGRAM_ID: Pubkey = pubkey!("4wgBJUHydWXXJKXYsmdGoGw1ufC3dxz8q2mukFYaAhSm");
+
+/// Anchor 8-byte type tag: `sha256("account:<Name>")[..8]` / `sha256("event:<Name>")[..8]`
+pub trait Discriminator {
+    const DISCRIMINATOR: [u8; 8];
+}
+
+/// Program-owned account that can be decoded from raw account data
+pub trait AccountData: Discriminator + BorshDeserialize {
+    fn try_from_account_data(data: &[u8]) -> Result<Self, Error> {
+        decode_tagged(data)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// Fewer than 8 bytes of data
+    TooShort,
+    /// Leading 8 bytes don't belong to the requested type
+    DiscriminatorMismatch,
+    /// Payload is not valid borsh for the requested type
+    InvalidData,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::TooShort => f.write_str("data shorter than discriminator"),
+            Error::DiscriminatorMismatch => f.write_str("discriminator mismatch"),
+            Error::InvalidData => f.write_str("invalid borsh payload"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+/// Decodes `disc || borsh(T)`; trailing bytes (unused account space) are ignored
+pub(crate) fn decode_tagged<T: Discriminator + BorshDeserialize>(data: &[u8]) -> Result<T, Error> {
+    let (disc, mut payload) = data.split_first_chunk::<8>().ok_or(Error::TooShort)?;
+    if *disc != T::DISCRIMINATOR {
+        return Err(Error::DiscriminatorMismatch);
+    }
+    T::deserialize(&mut payload).map_err(|_| Error::InvalidData)
+}
+
+macro_rules! discriminator {
+    ($($ty:ty => [$($b:literal),* $(,)?]),* $(,)?) => {
+        $(impl $crate::Discriminator for $ty {
+            const DISCRIMINATOR: [u8; 8] = [$($b),*];
+        })*
+    };
+}
+pub(crate) use discriminator;
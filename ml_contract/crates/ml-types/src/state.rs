@@ -0,0 +1,275 @@
+//! Program accounts, mirroring `programs/ml/src/state.rs`.
+
+use alloc::string::String;
+
+use crate::{
+    constants::{MAX_PARTICIPANTS, MAX_TEAM_MEMBERS, ZERO_PUBKEY},
+    discriminator, AccountData, BorshDeserialize, BorshSerialize, Pubkey,
+};
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Pool {
+    pub pool_id: u64,
+    pub salt: [u8; 32],
+    pub mint: Pubkey,
+    pub pool_token: Pubkey,
+    pub creator: Pubkey,
+    pub start_time: i64,
+    pub duration: i64,
+    pub expire_time: i64,
+    pub end_time: i64,
+    pub unlock_time: i64,
+    pub close_time: i64,
+    pub max_participants: u8,
+    pub lock_duration: i64,
+    pub lock_start_time: i64,
+    pub amount: u64,
+    pub total_amount: u64,
+    pub total_volume: u64,
+    pub total_joins: u32,
+    pub total_donations: u32,
+    pub dev_wallet: Pubkey,
+    pub dev_fee_bps: u16,
+    pub burn_fee_bps: u16,
+    pub treasury_wallet: Pubkey,
+    pub treasury_fee_bps: u16,
+    pub randomness: u128,
+    pub randomness_account: Pubkey,
+    pub randomness_deadline_slot: u64,
+    pub bump: u8,
+    pub status: PoolStatus,
+    pub paused: bool,
+    pub version: u8,
+    pub schema: u8,
+    pub config_hash: [u8; 32],
+    pub allow_mock: bool,
+    pub randomness_commit_slot: u64,
+    pub initialized: bool,
+    pub last_join_time: i64,
+    pub status_reason: u8,
+    pub participants_account: Pubkey,
+    pub winner: Pubkey,
+    pub processing: bool,
+    pub end_amount: u64,
+    pub prize_mint: Pubkey,
+    pub prize_token: Pubkey,
+    pub prize_released: bool,
+    pub min_bet_usd: u64,
+    pub creator_index: u64,
+    pub donated_prize: u64,
+    pub donated_burn: u64,
+    pub creator_bond: u64,
+    pub min_bet_native: u64,
+    pub merkle_tree: Pubkey,
+    pub max_entries: u32,
+    pub entry_count: u32,
+    pub entries_refunded: u32,
+    pub ticket_tree: Pubkey,
+    pub paused_at: i64,
+    pub paused_duration: i64,
+    pub max_donations_amount: u64,
+    pub total_donated: u64,
+    pub join_deadline: i64,
+}
+
+impl Pool {
+    pub fn has_prize_nft(&self) -> bool {
+        self.prize_mint != ZERO_PUBKEY
+    }
+
+    pub fn is_compressed(&self) -> bool {
+        self.merkle_tree != ZERO_PUBKEY
+    }
+
+    pub fn has_tickets(&self) -> bool {
+        self.ticket_tree != ZERO_PUBKEY
+    }
+
+    pub fn is_dutch_auction(&self) -> bool {
+        self.end_amount != self.amount
+    }
+
+    /// End of the lock window, including time paused while locked; `None` on overflow
+    pub fn lock_end_time(&self) -> Option<i64> {
+        ml_core::lock_end_time(self.lock_start_time, self.lock_duration, self.paused_duration)
+    }
+
+    /// Portion of `total_amount` subject to the fee split
+    pub fn fee_base(&self) -> Option<u64> {
+        ml_core::fee_base(self.total_amount, self.donated_prize, self.donated_burn)
+    }
+
+    /// Entry price at `now`; `None` on overflow
+    pub fn entry_amount_at(&self, now: i64) -> Option<u64> {
+        ml_core::entry_amount_at(self.amount, self.end_amount, self.start_time, self.expire_time, now)
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Participants {
+    pub list: [Pubkey; MAX_PARTICIPANTS],
+    pub count: u8,
+    pub amounts: [u64; MAX_PARTICIPANTS],
+}
+
+impl Participants {
+    /// Occupied slots as `(wallet, amount paid)`
+    pub fn entries(&self) -> impl Iterator<Item = (Pubkey, u64)> + '_ {
+        let count = (self.count as usize).min(MAX_PARTICIPANTS);
+        self.list[..count].iter().copied().zip(self.amounts[..count].iter().copied())
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CreatorPoolCounter {
+    pub creator: Pubkey,
+    pub pool_count: u64,
+    pub bump: u8,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CreatorPoolIndex {
+    pub creator: Pubkey,
+    pub index: u64,
+    pub pool: Pubkey,
+    pub mint: Pubkey,
+    pub salt: [u8; 32],
+    pub bump: u8,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PriceFeedConfig {
+    pub mint: Pubkey,
+    pub feed_id: [u8; 32],
+    pub max_age: u64,
+    pub bump: u8,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Team {
+    pub pool: Pubkey,
+    pub captain: Pubkey,
+    pub members: [Pubkey; MAX_TEAM_MEMBERS],
+    pub shares: [u64; MAX_TEAM_MEMBERS],
+    pub member_count: u8,
+    pub total_shares: u64,
+    pub entered: bool,
+    pub bump: u8,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AdminCloseProposal {
+    pub pool: Pubkey,
+    pub proposer: Pubkey,
+    pub reason_code: u8,
+    pub uri: String,
+    pub slash_bond: bool,
+    pub proposed_at: i64,
+    pub executable_at: i64,
+    pub bump: u8,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PoolArchive {
+    pub pool: Pubkey,
+    pub pool_id: u64,
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub winner: Pubkey,
+    pub total_volume: u64,
+    pub participant_count: u32,
+    pub end_time: i64,
+    pub final_status: PoolStatus,
+    pub status_reason: u8,
+    pub bump: u8,
+}
+
+discriminator! {
+    Pool => [241, 154, 109, 4, 17, 177, 109, 188],
+    Participants => [174, 28, 107, 228, 164, 250, 83, 170],
+    CreatorPoolCounter => [152, 220, 209, 144, 138, 26, 227, 112],
+    CreatorPoolIndex => [166, 169, 140, 21, 165, 156, 114, 28],
+    PriceFeedConfig => [65, 183, 139, 0, 166, 153, 246, 201],
+    Team => [140, 218, 177, 140, 193, 241, 199, 106],
+    AdminCloseProposal => [53, 66, 137, 170, 159, 72, 92, 154],
+    PoolArchive => [253, 234, 1, 73, 194, 240, 239, 113],
+}
+
+impl AccountData for Pool {}
+impl AccountData for Participants {}
+impl AccountData for CreatorPoolCounter {}
+impl AccountData for CreatorPoolIndex {}
+impl AccountData for PriceFeedConfig {}
+impl AccountData for Team {}
+impl AccountData for AdminCloseProposal {}
+impl AccountData for PoolArchive {}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[borsh(use_discriminant = false)]
+#[repr(u8)]
+pub enum PoolStatus {
+    Open = 0,
+    Locked = 1,
+    Unlocked = 2,
+    RandomnessCommitted = 3,
+    RandomnessRevealed = 4,
+    WinnerSelected = 5,
+    Ended = 6,
+    Cancelled = 7,
+    Closed = 8,
+}
+
+impl PoolStatus {
+    /// snake_case name used by the indexer schema and HTTP API
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PoolStatus::Open => "open",
+            PoolStatus::Locked => "locked",
+            PoolStatus::Unlocked => "unlocked",
+            PoolStatus::RandomnessCommitted => "randomness_committed",
+            PoolStatus::RandomnessRevealed => "randomness_revealed",
+            PoolStatus::WinnerSelected => "winner_selected",
+            PoolStatus::Ended => "ended",
+            PoolStatus::Cancelled => "cancelled",
+            PoolStatus::Closed => "closed",
+        }
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[borsh(use_discriminant = false)]
+#[repr(u8)]
+pub enum ActionType {
+    Created = 0,
+    Joined = 1,
+    Donated = 2,
+    Closed = 3,
+    Ended = 5,
+    Cancelled = 6,
+    RandomnessCommitted = 8,
+    RandomnessMockCommitted = 9,
+    ReachedMax = 10,
+    Unlocked = 11,
+    AdminClosed = 12,
+    EmergencyReveal = 13,
+    Expired = 14,
+    LockedAtDeadline = 15,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[borsh(use_discriminant = false)]
+#[repr(u8)]
+pub enum DonationPurpose {
+    Pot = 0,
+    Prize = 1,
+    Burn = 2,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[borsh(use_discriminant = false)]
+#[repr(u8)]
+pub enum HintType {
+    ReachedMax = 1,
+    NearExpire = 3,
+    Unlocked = 4,
+}
@@ -0,0 +1,143 @@
+//! Raw CPI helpers for SPL account-compression (concurrent Merkle trees).
+//!
+//! Built by hand instead of depending on `spl-account-compression`, whose published
+//! crates pin older Anchor / Solana versions than this program.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::{invoke, invoke_signed},
+};
+use sha2::Digest;
+
+pub const ACCOUNT_COMPRESSION_ID: Pubkey = pubkey!("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
+pub const NOOP_ID: Pubkey = pubkey!("noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV");
+
+// Anchor discriminators: sha256("global:<ix_name>")[..8]
+const IX_INIT_EMPTY_MERKLE_TREE: [u8; 8] = [191, 11, 119, 7, 180, 107, 220, 110];
+const IX_APPEND: [u8; 8] = [149, 120, 18, 222, 236, 225, 88, 203];
+const IX_VERIFY_LEAF: [u8; 8] = [124, 220, 22, 223, 104, 10, 250, 224];
+const IX_REPLACE_LEAF: [u8; 8] = [204, 165, 76, 100, 73, 147, 0, 128];
+
+/// Leaf committed for a compressed pool entry
+pub fn entry_leaf(pool: &Pubkey, user: &Pubkey, amount: u64, index: u32) -> [u8; 32] {
+    let mut hasher = sha2::Sha256::new();
+    hasher.update(pool.as_ref());
+    hasher.update(user.as_ref());
+    hasher.update(amount.to_le_bytes());
+    hasher.update(index.to_le_bytes());
+    hasher.finalize().into()
+}
+
+/// Accounts shared by every tree-mutating CPI; `authority` is the pool PDA
+pub struct TreeCpi<'a, 'info> {
+    pub compression_program: &'a AccountInfo<'info>,
+    pub merkle_tree: &'a AccountInfo<'info>,
+    pub authority: &'a AccountInfo<'info>,
+    pub noop: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> TreeCpi<'a, 'info> {
+    fn metas(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new(self.merkle_tree.key(), false),
+            AccountMeta::new_readonly(self.authority.key(), true),
+            AccountMeta::new_readonly(self.noop.key(), false),
+        ]
+    }
+
+    fn infos(&self) -> Vec<AccountInfo<'info>> {
+        vec![self.merkle_tree.clone(), self.authority.clone(), self.noop.clone()]
+    }
+
+    pub fn init_empty_merkle_tree(
+        &self,
+        max_depth: u32,
+        max_buffer_size: u32,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        let mut data = IX_INIT_EMPTY_MERKLE_TREE.to_vec();
+        data.extend_from_slice(&max_depth.to_le_bytes());
+        data.extend_from_slice(&max_buffer_size.to_le_bytes());
+        self.invoke(data, &[], signer_seeds)
+    }
+
+    pub fn append(&self, leaf: [u8; 32], signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        let mut data = IX_APPEND.to_vec();
+        data.extend_from_slice(&leaf);
+        self.invoke(data, &[], signer_seeds)
+    }
+
+    pub fn replace_leaf(
+        &self,
+        root: [u8; 32],
+        previous_leaf: [u8; 32],
+        new_leaf: [u8; 32],
+        index: u32,
+        proof: &[AccountInfo<'info>],
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        let mut data = IX_REPLACE_LEAF.to_vec();
+        data.extend_from_slice(&root);
+        data.extend_from_slice(&previous_leaf);
+        data.extend_from_slice(&new_leaf);
+        data.extend_from_slice(&index.to_le_bytes());
+        self.invoke(data, proof, signer_seeds)
+    }
+
+    fn invoke(
+        &self,
+        data: Vec<u8>,
+        proof: &[AccountInfo<'info>],
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        require_keys_eq!(
+            self.compression_program.key(),
+            ACCOUNT_COMPRESSION_ID,
+            crate::errors::ErrorCode::InvalidCompressionProgram
+        );
+
+        let mut accounts = self.metas();
+        accounts.extend(proof.iter().map(|node| AccountMeta::new_readonly(node.key(), false)));
+        let mut infos = self.infos();
+        infos.extend(proof.iter().cloned());
+        infos.push(self.compression_program.clone());
+
+        invoke_signed(
+            &Instruction { program_id: ACCOUNT_COMPRESSION_ID, accounts, data },
+            &infos,
+            signer_seeds,
+        )
+        .map_err(Into::into)
+    }
+}
+
+/// Fails unless `leaf` sits at `index` under `root` (proof nodes passed as remaining accounts)
+pub fn verify_leaf<'info>(
+    compression_program: &AccountInfo<'info>,
+    merkle_tree: &AccountInfo<'info>,
+    root: [u8; 32],
+    leaf: [u8; 32],
+    index: u32,
+    proof: &[AccountInfo<'info>],
+) -> Result<()> {
+    require_keys_eq!(
+        compression_program.key(),
+        ACCOUNT_COMPRESSION_ID,
+        crate::errors::ErrorCode::InvalidCompressionProgram
+    );
+
+    let mut data = IX_VERIFY_LEAF.to_vec();
+    data.extend_from_slice(&root);
+    data.extend_from_slice(&leaf);
+    data.extend_from_slice(&index.to_le_bytes());
+
+    let mut accounts = vec![AccountMeta::new_readonly(merkle_tree.key(), false)];
+    accounts.extend(proof.iter().map(|node| AccountMeta::new_readonly(node.key(), false)));
+    let mut infos = vec![merkle_tree.clone()];
+    infos.extend(proof.iter().cloned());
+    infos.push(compression_program.clone());
+
+    invoke(&Instruction { program_id: ACCOUNT_COMPRESSION_ID, accounts, data }, &infos)
+        .map_err(Into::into)
+}
@@ -74,4 +74,46 @@ pub enum ErrorCode {
     #[msg("Pool is currently processing another operation - reentrancy blocked")] PoolProcessing,
     // 🔒 Pool token validation
     #[msg("Pool token account mismatch - provided token doesn't match stored")] PoolTokenMismatch,
+    #[msg("Current entry price exceeds the provided maximum")] EntryPriceExceeded,
+    // 🖼️ NFT prize escrow
+    #[msg("Prize NFT already deposited")] PrizeAlreadyDeposited,
+    #[msg("Prize mint is not an NFT (supply 1, decimals 0)")] InvalidPrizeNft,
+    #[msg("Pool has no prize NFT")] NoPrizeNft,
+    #[msg("Prize NFT has not been released yet")] PrizeNotReleased,
+    #[msg("Incomplete pNFT accounts")] MissingPnftAccounts,
+    // 💲 Oracle-denominated minimum bet
+    #[msg("Price update account required for this mint")] MissingPriceUpdate,
+    #[msg("Price is stale or does not match the registered feed")] StalePrice,
+    #[msg("Bet is below the USD minimum")] BetBelowUsdMinimum,
+    // 👥 Team entries
+    #[msg("Team is full")] TeamFull,
+    #[msg("Team already entered the pool")] TeamAlreadyEntered,
+    #[msg("Only the team captain can do this")] NotTeamCaptain,
+    #[msg("Team funds cannot be distributed yet")] TeamNotSettled,
+    #[msg("Team member token accounts missing or out of order")] InvalidTeamMemberAccounts,
+    #[msg("Team contributions do not cover the entry price")] TeamUnderfunded,
+    // 🛑 Admin close timelock
+    #[msg("Admin close notice period has not elapsed")] AdminCloseNotReady,
+    #[msg("Admin close URI too long")] UriTooLong,
+    // 🌳 Compressed entries
+    #[msg("Operation not supported for compressed pools")] CompressedPool,
+    #[msg("Pool is not compressed")] NotCompressedPool,
+    #[msg("Invalid account compression or noop program")] InvalidCompressionProgram,
+    #[msg("Invalid Merkle tree parameters")] InvalidTreeConfig,
+    #[msg("Compressed entries still awaiting refund")] PendingCompressedRefunds,
+    // 🎟️ cNFT tickets
+    #[msg("Ticket accounts missing or invalid")] InvalidTicketAccounts,
+    #[msg("Tickets already enabled")] TicketsAlreadyEnabled,
+    // 🔁 Entry transfers
+    #[msg("Invalid entry recipient")] InvalidEntryRecipient,
+    // 🎁 Donation cap
+    #[msg("Donation would exceed the pool's donation cap")] DonationCapExceeded,
+    // ⏰ Join deadline
+    #[msg("Join deadline must fall between the pool start and expiry")] InvalidJoinDeadline,
+    #[msg("Join deadline has passed - the pool only takes donations now")] JoinDeadlinePassed,
+    // 🔁 Entry transfers
+    #[msg("The creator's entry cannot be transferred")] CreatorEntryNotTransferable,
+    // ⏰ Join deadline
+    #[msg("Join deadline has not passed yet")] JoinDeadlineNotReached,
+    #[msg("At least two entries are needed to lock at the join deadline")] NotEnoughParticipants,
 }
@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::state::{PoolStatus, ActionType, HintType};
+use crate::state::{PoolStatus, ActionType, DonationPurpose, HintType};
 
 #[event]
 pub struct PoolStateEvent {
@@ -70,3 +70,101 @@ pub struct ForfeitedToTreasury {
     pub pool_id: Pubkey,
     pub amount: u64,
 }
+
+#[event]
+pub struct PrizeNftDeposited {
+    pub pool_id: Pubkey,
+    pub prize_mint: Pubkey,
+    pub creator: Pubkey,
+}
+
+#[event]
+pub struct PrizeNftReleased {
+    pub pool_id: Pubkey,
+    pub prize_mint: Pubkey,
+    pub recipient: Pubkey,
+    pub reason: u8,
+}
+
+#[event]
+pub struct PoolCloned {
+    pub pool_id: Pubkey,
+    pub numerical_pool_id: u64,
+    pub source_pool: Pubkey,
+}
+
+#[event]
+pub struct DonationEarmarked {
+    pub pool_id: Pubkey,
+    pub donor: Pubkey,
+    pub amount: u64,
+    pub purpose: DonationPurpose,
+}
+
+#[event]
+pub struct TeamEntered {
+    pub pool_id: Pubkey,
+    pub team: Pubkey,
+    pub captain: Pubkey,
+    pub member_count: u8,
+    pub amount: u64,
+}
+
+#[event]
+pub struct TeamPrizeDistributed {
+    pub pool_id: Pubkey,
+    pub team: Pubkey,
+    pub amount: u64,
+    pub member_count: u8,
+}
+
+#[event]
+pub struct CreatorBondSlashed {
+    pub pool_id: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub treasury: Pubkey,
+}
+
+#[event]
+pub struct AdminCloseProposed {
+    pub pool_id: Pubkey,
+    pub proposer: Pubkey,
+    pub reason_code: u8,
+    pub uri: String,
+    pub slash_bond: bool,
+    pub executable_at: i64,
+}
+
+#[event]
+pub struct AdminCloseExecuted {
+    pub pool_id: Pubkey,
+    pub reason_code: u8,
+    pub uri: String,
+    pub bond_slashed: u64,
+}
+
+#[event]
+pub struct AdminCloseWithdrawn {
+    pub pool_id: Pubkey,
+    pub reason_code: u8,
+}
+
+/// Leaf data for indexers rebuilding proofs of compressed entries
+#[event]
+pub struct CompressedEntryAppended {
+    pub pool_id: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub index: u32,
+    pub leaf: [u8; 32],
+}
+
+#[event]
+pub struct EntryTransferred {
+    pub pool_id: Pubkey,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub index: u8,
+    pub amount: u64,
+}
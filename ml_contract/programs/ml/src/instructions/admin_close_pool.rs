@@ -1,6 +1,12 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
-use crate::{constants::*, errors::ErrorCode, events::*, state::{Pool, PoolStatus, ActionType}};
+use crate::{
+    constants::*,
+    errors::ErrorCode,
+    events::*,
+    state::{ActionType, AdminCloseProposal, Pool, PoolStatus},
+    utils::slash_creator_bond,
+};
 
 #[derive(Accounts)]
 pub struct AdminClosePool<'info> {
@@ -20,8 +26,20 @@ pub struct AdminClosePool<'info> {
     pub user: Signer<'info>,
     pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
+    /// Receives the creator bond when the pool is closed for abuse
+    #[account(mut, address = pool.treasury_wallet @ ErrorCode::Unauthorized)]
+    pub treasury_wallet: SystemAccount<'info>,
+    /// Created by `propose_admin_close`; rent returns to the dev wallet
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"admin_close", pool.key().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Box<Account<'info, AdminCloseProposal>>,
 }
 
+/// Step 2 of the admin close: executes a proposal once its notice period has elapsed
 pub fn admin_close_pool(ctx: Context<AdminClosePool>) -> Result<()> {
     // CRITICAL: Validate mint.owner matches token_program to prevent program mismatch DoS
     require_keys_eq!(
@@ -34,11 +52,20 @@ pub fn admin_close_pool(ctx: Context<AdminClosePool>) -> Result<()> {
     require!(ctx.accounts.user.key() == ctx.accounts.pool.dev_wallet, ErrorCode::NotDeveloper);
     ctx.accounts.pool.assert_open()?;
 
+    let now = Clock::get()?.unix_timestamp;
+    require!(now >= ctx.accounts.proposal.executable_at, ErrorCode::AdminCloseNotReady);
+
     let pool_id = ctx.accounts.pool.pool_id;
 
+    let bond_slashed = if ctx.accounts.proposal.slash_bond {
+        slash_creator_bond(&mut ctx.accounts.pool, &ctx.accounts.treasury_wallet.to_account_info())?
+    } else {
+        0
+    };
+
     ctx.accounts.pool.status = PoolStatus::Cancelled;
     ctx.accounts.pool.status_reason = REASON_ADMIN_CLOSED;
-    ctx.accounts.pool.close_time = Clock::get()?.unix_timestamp;
+    ctx.accounts.pool.close_time = now;
 
     emit!(PoolStateEvent {
         pool_id: ctx.accounts.pool.key(),
@@ -60,5 +87,12 @@ pub fn admin_close_pool(ctx: Context<AdminClosePool>) -> Result<()> {
         treasury_fee_percent: ctx.accounts.pool.treasury_fee_bps,
     });
 
+    emit!(AdminCloseExecuted {
+        pool_id: ctx.accounts.pool.key(),
+        reason_code: ctx.accounts.proposal.reason_code,
+        uri: ctx.accounts.proposal.uri.clone(),
+        bond_slashed,
+    });
+
     Ok(())
 }
@@ -1,6 +1,12 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
-use crate::{constants::*, errors::ErrorCode, events::*, state::{Pool, PoolStatus, ActionType}};
+use crate::{
+    constants::*,
+    errors::ErrorCode,
+    events::*,
+    state::{ActionType, Participants, Pool, PoolStatus},
+    utils::slash_creator_bond,
+};
 
 #[derive(Accounts)]
 pub struct CancelPool<'info> {
@@ -18,6 +24,15 @@ pub struct CancelPool<'info> {
     pub user: Signer<'info>,
     pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"participants", pool.key().as_ref()],
+        bump,
+        constraint = participants.key() == pool.participants_account @ ErrorCode::InvalidParticipantsPda
+    )]
+    pub participants: Account<'info, Participants>,
+    /// Receives the creator bond when others had already joined
+    #[account(mut, address = pool.treasury_wallet @ ErrorCode::Unauthorized)]
+    pub treasury_wallet: SystemAccount<'info>,
 }
 
 pub fn cancel_pool(ctx: Context<CancelPool>) -> Result<()> {
@@ -35,6 +50,11 @@ pub fn cancel_pool(ctx: Context<CancelPool>) -> Result<()> {
 
     let pool_id = ctx.accounts.pool.pool_id;
 
+    // 🔒 Cancelling after others joined forfeits the creator bond
+    if ctx.accounts.participants.count > 1 {
+        slash_creator_bond(&mut ctx.accounts.pool, &ctx.accounts.treasury_wallet.to_account_info())?;
+    }
+
     ctx.accounts.pool.status = PoolStatus::Cancelled;
     ctx.accounts.pool.status_reason = REASON_CANCELLED;
     ctx.accounts.pool.close_time = Clock::get()?.unix_timestamp;
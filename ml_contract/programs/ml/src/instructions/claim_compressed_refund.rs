@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::get_associated_token_address_with_program_id,
+    token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked, transfer_checked},
+};
+
+use crate::{
+    compression::{entry_leaf, TreeCpi, NOOP_ID},
+    constants::*,
+    errors::ErrorCode,
+    events::*,
+    state::{Pool, PoolStatus},
+};
+
+#[derive(Accounts)]
+pub struct ClaimCompressedRefund<'info> {
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        has_one = mint @ ErrorCode::InvalidMint,
+        has_one = merkle_tree @ ErrorCode::NotCompressedPool
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        mut,
+        constraint = pool_token.key() == pool.pool_token @ ErrorCode::PoolTokenMismatch
+    )]
+    pub pool_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = user_token.key()
+            == get_associated_token_address_with_program_id(&user.key(), &mint.key(), &token_program.key())
+            @ ErrorCode::InvalidParticipantToken
+    )]
+    pub user_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub user: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// CHECK: the pool's concurrent Merkle tree
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: checked against ACCOUNT_COMPRESSION_ID in the CPI helper
+    pub compression_program: UncheckedAccount<'info>,
+
+    /// CHECK: SPL noop (log wrapper)
+    #[account(address = NOOP_ID @ ErrorCode::InvalidCompressionProgram)]
+    pub noop_program: UncheckedAccount<'info>,
+    // remaining_accounts: proof nodes for the entry leaf
+}
+
+/// Refunds one compressed entry of a cancelled pool and nullifies its leaf.
+/// Leaf 0 (the creator) is refunded through `claim_refund`.
+pub fn claim_compressed_refund<'info>(
+    ctx: Context<'_, '_, '_, 'info, ClaimCompressedRefund<'info>>,
+    root: [u8; 32],
+    amount: u64,
+    index: u32,
+) -> Result<()> {
+    require_keys_eq!(
+        *ctx.accounts.mint.to_account_info().owner,
+        ctx.accounts.token_program.key(),
+        ErrorCode::InvalidTokenProgram
+    );
+
+    let pool = &ctx.accounts.pool;
+    pool.assert_not_processing()?;
+    require!(pool.status == PoolStatus::Cancelled, ErrorCode::InvalidPoolStatus);
+    require!(
+        pool.status_reason == REASON_CANCELLED
+            || pool.status_reason == REASON_ADMIN_CLOSED
+            || pool.status_reason == REASON_EXPIRED,
+        ErrorCode::InvalidPoolStatus
+    );
+    require!(index != 0 && index < pool.entry_count, ErrorCode::NotParticipant);
+
+    let user_key = ctx.accounts.user.key();
+    let leaf = entry_leaf(&pool.key(), &user_key, amount, index);
+    let seeds: &[&[u8]] = &[b"pool", pool.mint.as_ref(), pool.salt.as_ref(), &[pool.bump]];
+
+    // Replacing the leaf with zeroes proves ownership and prevents a second claim
+    let pool_info = pool.to_account_info();
+    TreeCpi {
+        compression_program: &ctx.accounts.compression_program,
+        merkle_tree: &ctx.accounts.merkle_tree,
+        authority: &pool_info,
+        noop: &ctx.accounts.noop_program,
+    }
+    .replace_leaf(root, leaf, [0u8; 32], index, ctx.remaining_accounts, &[seeds])?;
+
+    transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.pool_token.to_account_info(),
+                to: ctx.accounts.user_token.to_account_info(),
+                authority: pool_info,
+                mint: ctx.accounts.mint.to_account_info(),
+            },
+            &[seeds],
+        ),
+        amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    emit!(RefundClaimedEvent {
+        pool_id: pool.key(),
+        user: user_key,
+        amount,
+        burn_amount: 0,
+        reason: pool.status_reason,
+    });
+
+    let pool = &mut ctx.accounts.pool;
+    pool.entries_refunded = pool.entries_refunded.checked_add(1).ok_or(ErrorCode::Overflow)?;
+
+    Ok(())
+}
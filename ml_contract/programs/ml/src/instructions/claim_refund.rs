@@ -31,6 +31,7 @@ pub struct ClaimRefund<'info> {
     )]
     pub pool_token: InterfaceAccount<'info, TokenAccount>,
 
+    /// Refund destination: any token account of `mint` owned by `user`
     #[account(mut)]
     pub user_token: InterfaceAccount<'info, TokenAccount>,
 
@@ -151,16 +152,7 @@ pub fn claim_refund(ctx: Context<ClaimRefund>) -> Result<()> {
 
     /* ================= USER REFUND PATH ================= */
 
-    require_keys_eq!(
-        ctx.accounts.user_token.key(),
-        get_associated_token_address_with_program_id(
-            &caller,
-            &pool.mint,
-            &ctx.accounts.token_program.key()
-        ),
-        ErrorCode::InvalidParticipantToken
-    );
-
+    // Any token account of the mint owned by the participant (PDA entrants need not hold an ATA)
     validate_token_account(
         &ctx.accounts.user_token,
         &pool.mint,
@@ -177,7 +169,8 @@ pub fn claim_refund(ctx: Context<ClaimRefund>) -> Result<()> {
     }
     let index = index.ok_or(ErrorCode::NotParticipant)?;
 
-    let bet = pool.amount;
+    // dutch-auction pools charge a different price per entry
+    let bet = ctx.accounts.participants.amounts[index];
     let burn_amount = if is_creator { bet / 20 } else { 0 };
     let refund_amount = bet.saturating_sub(burn_amount);
 
@@ -230,8 +223,11 @@ pub fn claim_refund(ctx: Context<ClaimRefund>) -> Result<()> {
     for i in index..count - 1 {
         ctx.accounts.participants.list[i] =
             ctx.accounts.participants.list[i + 1];
+        ctx.accounts.participants.amounts[i] =
+            ctx.accounts.participants.amounts[i + 1];
     }
     ctx.accounts.participants.list[count - 1] = ZERO_PUBKEY;
+    ctx.accounts.participants.amounts[count - 1] = 0;
     ctx.accounts.participants.count -= 1;
 
     Ok(())
@@ -1,9 +1,10 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, Burn, burn, CloseAccount, close_account};
-use crate::{constants::*, errors::ErrorCode, events::*, state::{Pool, PoolStatus, Participants, ActionType}};
+use crate::{constants::*, errors::ErrorCode, events::*, state::{Pool, PoolArchive, PoolStatus, Participants, ActionType}};
 
 #[derive(Accounts)]
 pub struct ClaimRent<'info> {
+    /// Closing returns the rent and any unslashed creator bond to `close_target`
     #[account(mut, close = close_target)]
     pub pool: Account<'info, Pool>,
 
@@ -34,6 +35,18 @@ pub struct ClaimRent<'info> {
         constraint = participants.key() == pool.participants_account @ ErrorCode::InvalidParticipantsPda
     )]
     pub participants: Account<'info, Participants>,
+
+    /// Optional history record kept after the pool is closed; paid by `user`
+    #[account(
+        init,
+        payer = user,
+        space = 8 + PoolArchive::INIT_SPACE,
+        seeds = [b"pool_archive", pool.key().as_ref()],
+        bump
+    )]
+    pub pool_archive: Option<Account<'info, PoolArchive>>,
+
+    pub system_program: Option<Program<'info, System>>,
 }
 
 pub fn claim_rent(ctx: Context<ClaimRent>) -> Result<()> {
@@ -56,6 +69,7 @@ pub fn claim_rent(ctx: Context<ClaimRent>) -> Result<()> {
     );
 
     require!(ctx.accounts.participants.count == 0, ErrorCode::PoolNotEmpty);
+    require!(!pool.has_prize_nft() || pool.prize_released, ErrorCode::PrizeNotReleased);
 
     let caller = ctx.accounts.user.key();
     let is_creator = caller == pool.creator;
@@ -63,6 +77,15 @@ pub fn claim_rent(ctx: Context<ClaimRent>) -> Result<()> {
 
     require!(is_creator || is_dev, ErrorCode::Unauthorized);
 
+    // Cancelled compressed pools: every tree entry (leaf 0 aside) must be refunded before
+    // the remaining balance is burned; the dev path below waits out FORFEIT_DELAY instead
+    if pool.is_compressed() && pool.status == PoolStatus::Cancelled && !is_dev {
+        require!(
+            pool.entries_refunded.saturating_add(1) >= pool.entry_count,
+            ErrorCode::PendingCompressedRefunds
+        );
+    }
+
     let rent_recipient = if is_dev {
         require!(now > pool.close_time + FORFEIT_DELAY, ErrorCode::TooEarlyForEmergency);
         pool.treasury_wallet
@@ -110,6 +133,20 @@ pub fn claim_rent(ctx: Context<ClaimRent>) -> Result<()> {
         &[seeds],
     ))?;
 
+    if let Some(archive) = ctx.accounts.pool_archive.as_mut() {
+        archive.pool = pool.key();
+        archive.pool_id = pool.pool_id;
+        archive.mint = pool.mint;
+        archive.creator = pool.creator;
+        archive.winner = pool.winner;
+        archive.total_volume = pool.total_volume;
+        archive.participant_count = pool.total_joins;
+        archive.end_time = if pool.end_time != 0 { pool.end_time } else { pool.close_time };
+        archive.final_status = pool.status;
+        archive.status_reason = pool.status_reason;
+        archive.bump = ctx.bumps.pool_archive.unwrap_or_default();
+    }
+
     pool.status = PoolStatus::Closed;
     pool.status_reason = 0;
     pool.close_time = Clock::get()?.unix_timestamp;
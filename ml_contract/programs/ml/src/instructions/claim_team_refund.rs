@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked, transfer_checked};
+
+use crate::{
+    constants::*,
+    errors::ErrorCode,
+    events::*,
+    state::{Participants, Pool, PoolStatus, Team},
+};
+
+#[derive(Accounts)]
+pub struct ClaimTeamRefund<'info> {
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(has_one = mint @ ErrorCode::InvalidMint)]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        mut,
+        constraint = pool_token.key() == pool.pool_token @ ErrorCode::PoolTokenMismatch
+    )]
+    pub pool_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"team", pool.key().as_ref(), team.captain.as_ref()],
+        bump = team.bump,
+        has_one = pool
+    )]
+    pub team: Box<Account<'info, Team>>,
+
+    #[account(
+        mut,
+        constraint = team_token.mint == mint.key() @ ErrorCode::InvalidMint,
+        constraint = team_token.owner == team.key() @ ErrorCode::InvalidParticipantToken
+    )]
+    pub team_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Anyone may crank; the refund only ever lands in the team vault
+    pub user: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    #[account(
+        mut,
+        seeds = [b"participants", pool.key().as_ref()],
+        bump,
+        constraint = participants.key() == pool.participants_account @ ErrorCode::InvalidParticipantsPda
+    )]
+    pub participants: Box<Account<'info, Participants>>,
+}
+
+pub fn claim_team_refund(ctx: Context<ClaimTeamRefund>) -> Result<()> {
+    require_keys_eq!(
+        *ctx.accounts.mint.to_account_info().owner,
+        ctx.accounts.token_program.key(),
+        ErrorCode::InvalidTokenProgram
+    );
+
+    let pool = &ctx.accounts.pool;
+    pool.assert_not_processing()?;
+    require!(pool.status == PoolStatus::Cancelled, ErrorCode::InvalidPoolStatus);
+    require!(
+        pool.status_reason == REASON_CANCELLED
+            || pool.status_reason == REASON_ADMIN_CLOSED
+            || pool.status_reason == REASON_EXPIRED,
+        ErrorCode::InvalidPoolStatus
+    );
+
+    let team_key = ctx.accounts.team.key();
+    let count = ctx.accounts.participants.count as usize;
+    let index = (0..count)
+        .find(|&i| ctx.accounts.participants.list[i] == team_key)
+        .ok_or(ErrorCode::NotParticipant)?;
+    let refund_amount = ctx.accounts.participants.amounts[index];
+
+    let seeds: &[&[u8]] = &[b"pool", pool.mint.as_ref(), pool.salt.as_ref(), &[pool.bump]];
+
+    transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.pool_token.to_account_info(),
+                to: ctx.accounts.team_token.to_account_info(),
+                authority: ctx.accounts.pool.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+            },
+            &[seeds],
+        ),
+        refund_amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    emit!(RefundClaimedEvent {
+        pool_id: pool.key(),
+        user: team_key,
+        amount: refund_amount,
+        burn_amount: 0,
+        reason: pool.status_reason,
+    });
+
+    // remove team from participants
+    let participants = &mut ctx.accounts.participants;
+    for i in index..count - 1 {
+        participants.list[i] = participants.list[i + 1];
+        participants.amounts[i] = participants.amounts[i + 1];
+    }
+    participants.list[count - 1] = ZERO_PUBKEY;
+    participants.amounts[count - 1] = 0;
+    participants.count -= 1;
+
+    ctx.accounts.team.entered = false;
+
+    Ok(())
+}
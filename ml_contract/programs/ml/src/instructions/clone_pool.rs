@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::ErrorCode,
+    events::*,
+    instructions::create_pool::{initialize_pool, CreatePool, PoolConfig},
+    state::Pool,
+};
+
+#[derive(Accounts)]
+#[instruction(salt: [u8; 32])]
+pub struct ClonePool<'info> {
+    /// Accounts of the new pool, identical to `create_pool`
+    pub create: CreatePool<'info>,
+
+    /// Existing pool whose configuration is copied
+    #[account(constraint = source_pool.mint == create.mint.key() @ ErrorCode::InvalidMint)]
+    pub source_pool: Box<Account<'info, Pool>>,
+}
+
+pub fn clone_pool(ctx: Context<ClonePool>, salt: [u8; 32]) -> Result<()> {
+    let source = &ctx.accounts.source_pool;
+    require!(source.initialized, ErrorCode::UninitializedAccount);
+    require!(salt != source.salt, ErrorCode::AlreadyInitialized);
+
+    // A deadline before expiry keeps its offset from the start; one at expiry keeps following it
+    let join_deadline = if source.join_deadline < source.expire_time {
+        let offset = source.join_deadline - source.start_time;
+        Clock::get()?.unix_timestamp.checked_add(offset).ok_or(ErrorCode::Overflow)?
+    } else {
+        0
+    };
+
+    // fees, lock, size, pricing, wallets and join deadline carry over; prize NFT and USD floor are per-pool
+    let config = PoolConfig {
+        max_participants: source.max_participants,
+        lock_duration: source.lock_duration,
+        amount: source.amount,
+        end_amount: source.end_amount,
+        dev_wallet: source.dev_wallet,
+        dev_fee_bps: source.dev_fee_bps,
+        burn_fee_bps: source.burn_fee_bps,
+        treasury_wallet: source.treasury_wallet,
+        treasury_fee_bps: source.treasury_fee_bps,
+        allow_mock: source.allow_mock,
+        min_bet_override: source.min_bet_native,
+        max_donations_amount: source.max_donations_amount,
+        join_deadline,
+    };
+    let source_key = source.key();
+
+    initialize_pool(&mut ctx.accounts.create, &ctx.bumps.create, salt, config)?;
+
+    emit!(PoolCloned {
+        pool_id: ctx.accounts.create.pool.key(),
+        numerical_pool_id: ctx.accounts.create.pool.pool_id,
+        source_pool: source_key,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked, transfer_checked},
+};
+
+use crate::{
+    constants::*,
+    errors::ErrorCode,
+    state::{Pool, Team},
+    utils::validate_token_account,
+};
+
+#[derive(Accounts)]
+pub struct ContributeTeam<'info> {
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(has_one = mint @ ErrorCode::InvalidMint)]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        mut,
+        seeds = [b"team", pool.key().as_ref(), team.captain.as_ref()],
+        bump = team.bump,
+        has_one = pool
+    )]
+    pub team: Box<Account<'info, Team>>,
+
+    /// Team vault
+    #[account(
+        init_if_needed,
+        payer = member,
+        associated_token::mint = mint,
+        associated_token::authority = team,
+        associated_token::token_program = token_program
+    )]
+    pub team_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = member_token.mint == mint.key() @ ErrorCode::InvalidMint,
+        constraint = member_token.owner == member.key() @ ErrorCode::InvalidParticipantToken
+    )]
+    pub member_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub member: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn contribute_team(ctx: Context<ContributeTeam>, amount: u64) -> Result<()> {
+    require_keys_eq!(
+        *ctx.accounts.mint.to_account_info().owner,
+        ctx.accounts.token_program.key(),
+        ErrorCode::InvalidTokenProgram
+    );
+
+    ctx.accounts.pool.assert_open_not_paused()?;
+    require!(!ctx.accounts.team.entered, ErrorCode::TeamAlreadyEntered);
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    let member_key = ctx.accounts.member.key();
+    validate_token_account(&ctx.accounts.member_token, &ctx.accounts.mint.key(), &member_key, false)?;
+    require_gte!(ctx.accounts.member_token.amount, amount, ErrorCode::InsufficientFunds);
+
+    transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.member_token.to_account_info(),
+                to: ctx.accounts.team_token.to_account_info(),
+                authority: ctx.accounts.member.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+            },
+        ),
+        amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    let team = &mut ctx.accounts.team;
+    let count = team.member_count as usize;
+    let index = match (0..count).find(|&i| team.members[i] == member_key) {
+        Some(i) => i,
+        None => {
+            require!(count < MAX_TEAM_MEMBERS, ErrorCode::TeamFull);
+            team.members[count] = member_key;
+            team.member_count += 1;
+            count
+        }
+    };
+
+    team.shares[index] = team.shares[index].checked_add(amount).ok_or(ErrorCode::Overflow)?;
+    team.total_shares = team.total_shares.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+
+    Ok(())
+}
@@ -1,18 +1,23 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
 use anchor_spl::{
-    associated_token::{self, AssociatedToken},
+    associated_token::AssociatedToken,
     token_interface::{
         Mint, TokenAccount, TokenInterface, TransferChecked, transfer_checked,
     },
 };
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
 use sha2::Digest;
 
 use crate::{
     constants::*,
     errors::ErrorCode,
     events::*,
-    state::{ActionType, Participants, Pool, PoolStatus},
-    utils::{validate_token_account, validate_token2022_mint},
+    state::{
+        ActionType, CreatorPoolCounter, CreatorPoolIndex, Participants, Pool, PoolStatus,
+        PriceFeedConfig,
+    },
+    utils::{to_native_amount, usd_value_micros, validate_token_account, validate_token2022_mint},
 };
 
 #[derive(Accounts)]
@@ -23,30 +28,31 @@ pub struct CreatePool<'info> {
 
     #[account(
         init_if_needed,
-        payer = user,
+        payer = payer,
         space = 8 + Pool::INIT_SPACE,
         seeds = [b"pool", mint.key().as_ref(), salt.as_ref()],
         bump
     )]
     pub pool: Box<Account<'info, Pool>>,
 
+    /// Any token account of `mint` owned by `user` (PDA vaults need not be ATAs)
     #[account(
         mut,
-        constraint = user_token.key()
-            == associated_token::get_associated_token_address_with_program_id(
-                &user.key(),
-                &mint.key(),
-                &token_program.key()
-            ) @ ErrorCode::InvalidParticipantToken
+        constraint = user_token.mint == mint.key() @ ErrorCode::InvalidMint,
+        constraint = user_token.owner == user.key() @ ErrorCode::InvalidParticipantToken
     )]
     pub user_token: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    #[account(mut)]
+    /// Pool creator and entry authority - a wallet or another program's PDA signing via CPI
     pub user: Signer<'info>,
 
+    /// Pays rent for the pool accounts (may be the same key as `user`)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
     #[account(
         init_if_needed,
-        payer = user,
+        payer = payer,
         associated_token::mint = mint,
         associated_token::authority = pool,
         associated_token::token_program = token_program
@@ -62,12 +68,61 @@ pub struct CreatePool<'info> {
 
     #[account(
         init,
-        payer = user,
+        payer = payer,
         space = 8 + Participants::INIT_SPACE,
         seeds = [b"participants", pool.key().as_ref()],
         bump
     )]
     pub participants: Box<Account<'info, Participants>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + CreatorPoolCounter::INIT_SPACE,
+        seeds = [b"creator_pools", user.key().as_ref()],
+        bump
+    )]
+    pub creator_counter: Box<Account<'info, CreatorPoolCounter>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + CreatorPoolIndex::INIT_SPACE,
+        seeds = [
+            b"creator_pool",
+            user.key().as_ref(),
+            creator_counter.pool_count.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub creator_pool_index: Box<Account<'info, CreatorPoolIndex>>,
+
+    /// CHECK: `PriceFeedConfig` PDA for `mint`; when initialized the min bet is USD-denominated
+    #[account(seeds = [b"price_feed", mint.key().as_ref()], bump)]
+    pub price_feed: UncheckedAccount<'info>,
+
+    /// Pyth pull-oracle price update, required when `price_feed` is registered
+    pub price_update: Option<Box<Account<'info, PriceUpdateV2>>>,
+}
+
+/// Pool parameters shared by `create_pool` and `clone_pool`
+pub struct PoolConfig {
+    pub max_participants: u8,
+    pub lock_duration: i64,
+    pub amount: u64,
+    pub end_amount: u64,
+    pub dev_wallet: Pubkey,
+    pub dev_fee_bps: u16,
+    pub burn_fee_bps: u16,
+    pub treasury_wallet: Pubkey,
+    pub treasury_fee_bps: u16,
+    pub allow_mock: bool,
+    /// Minimum entry in native units; 0 = `MIN_BET_TOKENS` scaled by the mint decimals
+    pub min_bet_override: u64,
+    /// Cap on the sum of donations in native units; 0 = uncapped
+    pub max_donations_amount: u64,
+    /// Unix time entries close; 0 = at `expire_time`
+    pub join_deadline: i64,
 }
 
 pub fn create_pool(
@@ -82,15 +137,64 @@ pub fn create_pool(
     treasury_wallet: Pubkey,
     treasury_fee_bps: u16,
     allow_mock: bool,
+    end_amount: u64,
+    min_bet_override: u64,
+    max_donations_amount: u64,
+    join_deadline: i64,
 ) -> Result<()> {
-    let pool = &mut ctx.accounts.pool;
+    initialize_pool(
+        ctx.accounts,
+        &ctx.bumps,
+        salt,
+        PoolConfig {
+            max_participants,
+            lock_duration,
+            amount,
+            end_amount,
+            dev_wallet,
+            dev_fee_bps,
+            burn_fee_bps,
+            treasury_wallet,
+            treasury_fee_bps,
+            allow_mock,
+            min_bet_override,
+            max_donations_amount,
+            join_deadline,
+        },
+    )
+}
+
+/// Validates the config, takes the creator's initial bet and initializes all pool accounts
+pub(crate) fn initialize_pool<'info>(
+    accounts: &mut CreatePool<'info>,
+    bumps: &CreatePoolBumps,
+    salt: [u8; 32],
+    config: PoolConfig,
+) -> Result<()> {
+    let PoolConfig {
+        max_participants,
+        lock_duration,
+        amount,
+        end_amount,
+        dev_wallet,
+        dev_fee_bps,
+        burn_fee_bps,
+        treasury_wallet,
+        treasury_fee_bps,
+        allow_mock,
+        min_bet_override,
+        max_donations_amount,
+        join_deadline,
+    } = config;
+
+    let pool = &mut accounts.pool;
 
     require!(!pool.initialized, ErrorCode::AlreadyInitialized);
 
     // Token program safety (SPL vs Token-2022)
     require_keys_eq!(
-        *ctx.accounts.mint.to_account_info().owner,
-        ctx.accounts.token_program.key(),
+        *accounts.mint.to_account_info().owner,
+        accounts.token_program.key(),
         ErrorCode::InvalidTokenProgram
     );
 
@@ -100,26 +204,23 @@ pub fn create_pool(
     );
 
     require!(
-        ctx.accounts.mint.freeze_authority.is_none(),
+        accounts.mint.freeze_authority.is_none(),
         ErrorCode::MintHasFreezeAuthority
     );
 
     require!(
-        ctx.accounts.mint.mint_authority.is_none()
-            || ctx.accounts.mint.mint_authority.unwrap() == ZERO_PUBKEY,
+        accounts.mint.mint_authority.is_none()
+            || accounts.mint.mint_authority.unwrap() == ZERO_PUBKEY,
         ErrorCode::MintHasMintAuthority
     );
 
     // Token-2022 extension validation
-    validate_token2022_mint(&ctx.accounts.mint.to_account_info())?;
+    validate_token2022_mint(&accounts.mint.to_account_info())?;
 
-    require_gt!(ctx.accounts.mint.supply, 0, ErrorCode::ZeroSupply);
+    require_gt!(accounts.mint.supply, 0, ErrorCode::ZeroSupply);
 
-    let decimals = ctx.accounts.mint.decimals;
-    require!(
-        matches!(decimals, 6 | 8 | 9 | 10),
-        ErrorCode::InvalidDecimals
-    );
+    let decimals = accounts.mint.decimals;
+    require!(decimals <= MAX_MINT_DECIMALS, ErrorCode::InvalidDecimals);
 
     require!(
         max_participants as usize <= MAX_PARTICIPANTS,
@@ -127,24 +228,48 @@ pub fn create_pool(
     );
     require!(max_participants >= 2, ErrorCode::InvalidParticipantRange);
 
-    let min_native = MIN_BET_TOKENS
-        .checked_mul(10_u64.pow(decimals as u32))
-        .ok_or(ErrorCode::Overflow)?;
-    require!(amount >= min_native, ErrorCode::InvalidAmount);
+    let clock = Clock::get()?;
+
+    // Min bet: USD-denominated when the mint has a registered Pyth feed, tokens otherwise.
+    // Dutch-auction end price must respect the same minimum (end_amount == amount => flat pricing)
+    // A per-pool override replaces the default token minimum (e.g. for low-decimal mints)
+    let (min_bet_usd, min_bet_native) = if accounts.price_feed.data_is_empty() {
+        let min_native = if min_bet_override > 0 {
+            min_bet_override
+        } else {
+            to_native_amount(MIN_BET_TOKENS, decimals)?
+        };
+        require!(amount >= min_native, ErrorCode::InvalidAmount);
+        require!(end_amount >= min_native, ErrorCode::InvalidAmount);
+        (0, min_native)
+    } else {
+        let price_feed =
+            Account::<PriceFeedConfig>::try_from(&accounts.price_feed.to_account_info())?;
+        let price_update = accounts
+            .price_update
+            .as_ref()
+            .ok_or(ErrorCode::MissingPriceUpdate)?;
+        let price = price_update
+            .get_price_no_older_than(&clock, price_feed.max_age, &price_feed.feed_id)
+            .map_err(|_| ErrorCode::StalePrice)?;
+
+        let lowest_entry = amount.min(end_amount);
+        let entry_usd = usd_value_micros(lowest_entry, decimals, price.price, price.conf, price.exponent)?;
+        require!(entry_usd >= MIN_BET_USD_MICROS, ErrorCode::BetBelowUsdMinimum);
+        (MIN_BET_USD_MICROS, 0)
+    };
 
     require!(
         lock_duration >= MIN_LOCK_DURATION && lock_duration <= MAX_LOCK_DURATION,
         ErrorCode::InvalidLockDuration
     );
 
-    let clock = Clock::get()?;
-
     // deterministic numeric pool id
     let pool_id = {
         let mut hasher = sha2::Sha256::new();
         hasher.update(salt);
         hasher.update(clock.slot.to_le_bytes());
-        hasher.update(ctx.accounts.user.key().as_ref());
+        hasher.update(accounts.user.key().as_ref());
         let hash = hasher.finalize();
         u64::from_le_bytes(hash[..8].try_into().unwrap())
     };
@@ -154,13 +279,19 @@ pub fn create_pool(
        ======================= */
 
     pool.pool_id = pool_id;
-    pool.pool_token = ctx.accounts.pool_token.key(); // ✅ CRITICAL FIX
+    pool.pool_token = accounts.pool_token.key(); // ✅ CRITICAL FIX
     pool.salt = salt;
-    pool.mint = ctx.accounts.mint.key();
-    pool.creator = ctx.accounts.user.key();
+    pool.mint = accounts.mint.key();
+    pool.creator = accounts.user.key();
     pool.start_time = clock.unix_timestamp;
     pool.duration = POOL_OPEN_DURATION;
     pool.expire_time = clock.unix_timestamp + POOL_OPEN_DURATION;
+    // Entries may close early so the pot can still grow by donations before the draw
+    pool.join_deadline = if join_deadline == 0 { pool.expire_time } else { join_deadline };
+    require!(
+        pool.join_deadline > pool.start_time && pool.join_deadline <= pool.expire_time,
+        ErrorCode::InvalidJoinDeadline
+    );
     pool.end_time = 0;
     pool.unlock_time = 0;
     pool.close_time = 0;
@@ -180,60 +311,61 @@ pub fn create_pool(
     pool.randomness = 0;
     pool.randomness_account = ZERO_PUBKEY;
     pool.randomness_deadline_slot = 0;
-    pool.bump = ctx.bumps.pool;
+    pool.bump = bumps.pool;
     pool.status = PoolStatus::Open;
     pool.status_reason = 0;
     pool.paused = false;
     pool.version = 1;
     pool.schema = 1;
-    // 🔒 SECURITY: On mainnet, ALWAYS disable mock mode to prevent manipulation
-    #[cfg(feature = "mainnet")]
-    let allow_mock = false;
-    pool.allow_mock = allow_mock;
+    // 🔒 SECURITY: mock mode only exists in `test-utils` builds (never mainnet)
+    pool.allow_mock = allow_mock && cfg!(feature = "test-utils");
     pool.randomness_commit_slot = 0;
     pool.last_join_time = clock.unix_timestamp;
     pool.winner = ZERO_PUBKEY;
     pool.processing = false; // 🔒 Initialize reentrancy guard
+    pool.end_amount = end_amount;
+    pool.prize_mint = ZERO_PUBKEY; // set later by deposit_prize_nft for NFT-prize pools
+    pool.prize_token = ZERO_PUBKEY;
+    pool.prize_released = false;
+    pool.min_bet_usd = min_bet_usd;
+    pool.min_bet_native = min_bet_native;
+    pool.max_donations_amount = max_donations_amount;
+    pool.total_donated = 0;
+
+    // per-creator index: "my n-th pool" without persisting salts client-side
+    let creator_index = accounts.creator_counter.pool_count;
+    pool.creator_index = creator_index;
+
+    let counter = &mut accounts.creator_counter;
+    counter.creator = accounts.user.key();
+    counter.pool_count = creator_index.checked_add(1).ok_or(ErrorCode::Overflow)?;
+    counter.bump = bumps.creator_counter;
+
+    let index_entry = &mut accounts.creator_pool_index;
+    index_entry.creator = accounts.user.key();
+    index_entry.index = creator_index;
+    index_entry.pool = pool.key();
+    index_entry.mint = accounts.mint.key();
+    index_entry.salt = salt;
+    index_entry.bump = bumps.creator_pool_index;
 
     // config hash (anti-tamper)
-    let mut hasher = sha2::Sha256::new();
-    hasher.update(salt);
-    hasher.update(max_participants.to_le_bytes());
-    hasher.update(lock_duration.to_le_bytes());
-    hasher.update(amount.to_le_bytes());
-    hasher.update(dev_wallet.as_ref());
-    hasher.update(dev_fee_bps.to_le_bytes());
-    hasher.update(burn_fee_bps.to_le_bytes());
-    hasher.update(treasury_wallet.as_ref());
-    hasher.update(treasury_fee_bps.to_le_bytes());
-    hasher.update(pool.start_time.to_le_bytes());
-    hasher.update(pool.duration.to_le_bytes());
-    pool.config_hash = hasher.finalize().into();
+    pool.config_hash = pool.compute_config_hash();
 
     /* =======================
         USER TOKEN CHECK
        ======================= */
 
-    let expected_ata = associated_token::get_associated_token_address_with_program_id(
-        &ctx.accounts.user.key(),
-        &ctx.accounts.mint.key(),
-        &ctx.accounts.token_program.key(),
-    );
-    require_keys_eq!(
-        expected_ata,
-        ctx.accounts.user_token.key(),
-        ErrorCode::InvalidParticipantToken
-    );
-
+    // owner/mint/frozen checks only: CPI callers may fund entries from non-ATA vaults
     validate_token_account(
-        &ctx.accounts.user_token,
-        &ctx.accounts.mint.key(),
-        &ctx.accounts.user.key(),
+        &accounts.user_token,
+        &accounts.mint.key(),
+        &accounts.user.key(),
         false,
     )?;
 
     require_gte!(
-        ctx.accounts.user_token.amount,
+        accounts.user_token.amount,
         amount,
         ErrorCode::InsufficientFunds
     );
@@ -241,12 +373,12 @@ pub fn create_pool(
     // initial bet transfer
     transfer_checked(
         CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
+            accounts.token_program.to_account_info(),
             TransferChecked {
-                from: ctx.accounts.user_token.to_account_info(),
-                to: ctx.accounts.pool_token.to_account_info(),
-                authority: ctx.accounts.user.to_account_info(),
-                mint: ctx.accounts.mint.to_account_info(),
+                from: accounts.user_token.to_account_info(),
+                to: accounts.pool_token.to_account_info(),
+                authority: accounts.user.to_account_info(),
+                mint: accounts.mint.to_account_info(),
             },
         ),
         amount,
@@ -254,9 +386,23 @@ pub fn create_pool(
     )?;
 
     // participants init
-    ctx.accounts.participants.list[0] = ctx.accounts.user.key();
-    ctx.accounts.participants.count = 1;
-    pool.participants_account = ctx.accounts.participants.key();
+    accounts.participants.list[0] = accounts.user.key();
+    accounts.participants.amounts[0] = amount;
+    accounts.participants.count = 1;
+    pool.participants_account = accounts.participants.key();
+
+    // 🔒 Anti-spam bond: slashed on abusive cancel/admin close, otherwise returned with the rent
+    transfer(
+        CpiContext::new(
+            accounts.system_program.to_account_info(),
+            Transfer {
+                from: accounts.payer.to_account_info(),
+                to: pool.to_account_info(),
+            },
+        ),
+        CREATOR_BOND_LAMPORTS,
+    )?;
+    pool.creator_bond = CREATOR_BOND_LAMPORTS;
 
     emit!(PoolStateEvent {
         pool_id: pool.key(),
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::ErrorCode,
+    state::{Pool, Team},
+};
+
+#[derive(Accounts)]
+pub struct CreateTeam<'info> {
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        init,
+        payer = captain,
+        space = 8 + Team::INIT_SPACE,
+        seeds = [b"team", pool.key().as_ref(), captain.key().as_ref()],
+        bump
+    )]
+    pub team: Box<Account<'info, Team>>,
+
+    #[account(mut)]
+    pub captain: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_team(ctx: Context<CreateTeam>) -> Result<()> {
+    let pool = &ctx.accounts.pool;
+
+    require!(pool.initialized, ErrorCode::UninitializedAccount);
+    pool.assert_open_not_paused()?;
+    // Team vault can't receive an escrowed NFT
+    require!(!pool.has_prize_nft(), ErrorCode::InvalidPrizeNft);
+
+    let team = &mut ctx.accounts.team;
+    team.pool = pool.key();
+    team.captain = ctx.accounts.captain.key();
+    team.member_count = 0;
+    team.total_shares = 0;
+    team.entered = false;
+    team.bump = ctx.bumps.team;
+
+    Ok(())
+}
@@ -0,0 +1,168 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    metadata::mpl_token_metadata,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    errors::ErrorCode,
+    events::*,
+    state::{Participants, Pool},
+    utils::{transfer_prize_nft, validate_token2022_mint, validate_token_account, PnftAccounts, PrizeNftTransfer},
+};
+
+#[derive(Accounts)]
+pub struct DepositPrizeNft<'info> {
+    #[account(mut)]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        seeds = [b"participants", pool.key().as_ref()],
+        bump,
+        constraint = participants.key() == pool.participants_account @ ErrorCode::InvalidParticipantsPda
+    )]
+    pub participants: Box<Account<'info, Participants>>,
+
+    /// Pool creator - owner of the NFT being escrowed
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(mut)]
+    pub prize_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = creator_prize_token.mint == prize_mint.key() @ ErrorCode::InvalidMint,
+        constraint = creator_prize_token.owner == user.key() @ ErrorCode::InvalidParticipantToken
+    )]
+    pub creator_prize_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = prize_mint,
+        associated_token::authority = pool,
+        associated_token::token_program = prize_token_program
+    )]
+    pub pool_prize_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program of the prize mint (may differ from the entry mint's program)
+    pub prize_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    // ---- pNFT only (Token Metadata TransferV1) ----
+    /// CHECK: Token Metadata program
+    #[account(address = mpl_token_metadata::ID)]
+    pub token_metadata_program: Option<UncheckedAccount<'info>>,
+    /// CHECK: validated by Token Metadata
+    #[account(mut)]
+    pub metadata: Option<UncheckedAccount<'info>>,
+    /// CHECK: validated by Token Metadata
+    pub edition: Option<UncheckedAccount<'info>>,
+    /// CHECK: validated by Token Metadata
+    #[account(mut)]
+    pub owner_token_record: Option<UncheckedAccount<'info>>,
+    /// CHECK: validated by Token Metadata
+    #[account(mut)]
+    pub destination_token_record: Option<UncheckedAccount<'info>>,
+    /// CHECK: instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub sysvar_instructions: Option<UncheckedAccount<'info>>,
+    /// CHECK: validated by Token Metadata
+    pub authorization_rules_program: Option<UncheckedAccount<'info>>,
+    /// CHECK: validated by Token Metadata
+    pub authorization_rules: Option<UncheckedAccount<'info>>,
+}
+
+pub fn deposit_prize_nft(ctx: Context<DepositPrizeNft>) -> Result<()> {
+    require_keys_eq!(
+        *ctx.accounts.prize_mint.to_account_info().owner,
+        ctx.accounts.prize_token_program.key(),
+        ErrorCode::InvalidTokenProgram
+    );
+
+    let pool = &ctx.accounts.pool;
+
+    require!(pool.initialized, ErrorCode::UninitializedAccount);
+    pool.assert_not_paused()?;
+    pool.assert_not_processing()?;
+    pool.assert_owner(&ctx.accounts.user.key())?;
+    pool.assert_open()?;
+    require!(!pool.has_prize_nft(), ErrorCode::PrizeAlreadyDeposited);
+
+    // Prize must be escrowed at creation time, before anyone else joins
+    require!(ctx.accounts.participants.count == 1, ErrorCode::CannotChangeAfterJoins);
+    require!(pool.lock_start_time == 0, ErrorCode::PoolLockedForJoin);
+
+    // NFT shape: supply 1, decimals 0, not the entry token
+    require!(
+        ctx.accounts.prize_mint.decimals == 0 && ctx.accounts.prize_mint.supply == 1,
+        ErrorCode::InvalidPrizeNft
+    );
+    require_keys_neq!(ctx.accounts.prize_mint.key(), pool.mint, ErrorCode::InvalidPrizeNft);
+    validate_token2022_mint(&ctx.accounts.prize_mint.to_account_info())?;
+
+    validate_token_account(
+        &ctx.accounts.creator_prize_token,
+        &ctx.accounts.prize_mint.key(),
+        &ctx.accounts.user.key(),
+        false,
+    )?;
+    require_eq!(ctx.accounts.creator_prize_token.amount, 1, ErrorCode::InsufficientFunds);
+
+    let pnft = match ctx.accounts.token_metadata_program.as_ref() {
+        Some(token_metadata_program) => Some(PnftAccounts {
+            token_metadata_program,
+            metadata: ctx.accounts.metadata.as_ref().ok_or(ErrorCode::MissingPnftAccounts)?,
+            edition: ctx.accounts.edition.as_ref().ok_or(ErrorCode::MissingPnftAccounts)?,
+            from_token_record: ctx.accounts.owner_token_record.as_ref().ok_or(ErrorCode::MissingPnftAccounts)?,
+            to_token_record: ctx.accounts.destination_token_record.as_ref().ok_or(ErrorCode::MissingPnftAccounts)?,
+            sysvar_instructions: ctx.accounts.sysvar_instructions.as_ref().ok_or(ErrorCode::MissingPnftAccounts)?,
+            authorization_rules_program: ctx.accounts.authorization_rules_program.as_deref(),
+            authorization_rules: ctx.accounts.authorization_rules.as_deref(),
+        }),
+        None => None,
+    };
+
+    let prize_mint_info = ctx.accounts.prize_mint.to_account_info();
+    let from_info = ctx.accounts.creator_prize_token.to_account_info();
+    let to_info = ctx.accounts.pool_prize_token.to_account_info();
+    let pool_info = ctx.accounts.pool.to_account_info();
+
+    transfer_prize_nft(
+        PrizeNftTransfer {
+            token_program: &ctx.accounts.prize_token_program,
+            associated_token_program: &ctx.accounts.associated_token_program,
+            system_program: &ctx.accounts.system_program,
+            mint: &prize_mint_info,
+            from: &from_info,
+            from_owner: &ctx.accounts.user,
+            to: &to_info,
+            to_owner: &pool_info,
+            authority: &ctx.accounts.user,
+            payer: &ctx.accounts.payer,
+            pnft,
+        },
+        &[],
+    )?;
+
+    ctx.accounts.pool_prize_token.reload()?;
+    require_eq!(ctx.accounts.pool_prize_token.amount, 1, ErrorCode::InvalidPrizeNft);
+
+    let pool = &mut ctx.accounts.pool;
+    pool.prize_mint = ctx.accounts.prize_mint.key();
+    pool.prize_token = ctx.accounts.pool_prize_token.key();
+    pool.prize_released = false;
+
+    emit!(PrizeNftDeposited {
+        pool_id: pool.key(),
+        prize_mint: pool.prize_mint,
+        creator: pool.creator,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,147 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::get_associated_token_address_with_program_id,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked,
+    },
+};
+
+use crate::{
+    errors::ErrorCode,
+    events::*,
+    state::{Pool, PoolStatus, Team},
+};
+
+#[derive(Accounts)]
+pub struct DistributeTeamPrize<'info> {
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: the team's pool; may already be closed by `claim_rent`
+    #[account(address = team.pool)]
+    pub pool: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"team", pool.key().as_ref(), team.captain.as_ref()],
+        bump = team.bump,
+        close = captain
+    )]
+    pub team: Box<Account<'info, Team>>,
+
+    #[account(
+        mut,
+        constraint = team_token.mint == mint.key() @ ErrorCode::InvalidMint,
+        constraint = team_token.owner == team.key() @ ErrorCode::InvalidParticipantToken
+    )]
+    pub team_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Receives the team and vault rent
+    #[account(mut, address = team.captain @ ErrorCode::NotTeamCaptain)]
+    pub captain: SystemAccount<'info>,
+
+    /// Anyone may crank the split
+    pub user: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    // remaining_accounts: member ATAs (mint, token_program), in `team.members` order
+}
+
+/// Splits the team vault (prize and/or refund and leftovers) pro rata to `team.shares`,
+/// then closes the team. Allowed once the pool is finished and the team holds no entry.
+pub fn distribute_team_prize<'info>(
+    ctx: Context<'_, '_, '_, 'info, DistributeTeamPrize<'info>>,
+) -> Result<()> {
+    require_keys_eq!(
+        *ctx.accounts.mint.to_account_info().owner,
+        ctx.accounts.token_program.key(),
+        ErrorCode::InvalidTokenProgram
+    );
+
+    let team = &ctx.accounts.team;
+
+    // Pool finished (or already closed); a cancelled team must claim its refund first
+    let pool_info = ctx.accounts.pool.to_account_info();
+    if pool_info.owner == &crate::ID && !pool_info.data_is_empty() {
+        let pool = Pool::try_deserialize(&mut &pool_info.data.borrow()[..])?;
+        require_keys_eq!(pool.mint, ctx.accounts.mint.key(), ErrorCode::InvalidMint);
+        let settled = match pool.status {
+            PoolStatus::Ended | PoolStatus::Closed => true,
+            PoolStatus::Cancelled => !team.entered,
+            _ => false,
+        };
+        require!(settled, ErrorCode::TeamNotSettled);
+    }
+
+    let member_count = team.member_count as usize;
+    require!(
+        ctx.remaining_accounts.len() == member_count,
+        ErrorCode::InvalidTeamMemberAccounts
+    );
+
+    let balance = ctx.accounts.team_token.amount;
+    let pool_key = ctx.accounts.pool.key();
+    let seeds: &[&[u8]] = &[b"team", pool_key.as_ref(), team.captain.as_ref(), &[team.bump]];
+
+    let mut remaining = balance;
+    for i in 0..member_count {
+        let member_token = &ctx.remaining_accounts[i];
+        require_keys_eq!(
+            member_token.key(),
+            get_associated_token_address_with_program_id(
+                &team.members[i],
+                &ctx.accounts.mint.key(),
+                &ctx.accounts.token_program.key()
+            ),
+            ErrorCode::InvalidTeamMemberAccounts
+        );
+
+        // Last member takes the rounding dust
+        let share = if i + 1 == member_count {
+            remaining
+        } else {
+            (balance as u128)
+                .checked_mul(team.shares[i] as u128)
+                .ok_or(ErrorCode::Overflow)?
+                .checked_div(team.total_shares as u128)
+                .ok_or(ErrorCode::Overflow)? as u64
+        };
+        if share == 0 {
+            continue;
+        }
+        remaining = remaining.checked_sub(share).ok_or(ErrorCode::Overflow)?;
+
+        transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.team_token.to_account_info(),
+                    to: member_token.clone(),
+                    authority: ctx.accounts.team.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                },
+                &[seeds],
+            ),
+            share,
+            ctx.accounts.mint.decimals,
+        )?;
+    }
+
+    close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.team_token.to_account_info(),
+            destination: ctx.accounts.captain.to_account_info(),
+            authority: ctx.accounts.team.to_account_info(),
+        },
+        &[seeds],
+    ))?;
+
+    emit!(TeamPrizeDistributed {
+        pool_id: pool_key,
+        team: ctx.accounts.team.key(),
+        amount: balance,
+        member_count: member_count as u8,
+    });
+
+    Ok(())
+}
@@ -2,14 +2,13 @@ use anchor_lang::prelude::*;
 use anchor_spl::{
     token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked, transfer_checked},
 };
-use sha2::Digest;
 
 use crate::{
     constants::*,
     errors::ErrorCode,
     events::*,
-    state::{ActionType, HintType, Participants, Pool, PoolStatus},
-    utils::validate_token_account,
+    state::{ActionType, DonationPurpose, HintType, Participants, Pool, PoolStatus},
+    utils::{to_native_amount, validate_token_account},
 };
 
 #[derive(Accounts)]
@@ -39,7 +38,7 @@ pub struct Donate<'info> {
     pub participants: Account<'info, Participants>,
 }
 
-pub fn donate(ctx: Context<Donate>, amount: u64) -> Result<()> {
+pub fn donate(ctx: Context<Donate>, amount: u64, purpose: DonationPurpose) -> Result<()> {
     // CRITICAL: Validate mint.owner matches token_program to prevent program mismatch DoS
     require_keys_eq!(
         *ctx.accounts.mint.to_account_info().owner,
@@ -68,20 +67,10 @@ pub fn donate(ctx: Context<Donate>, amount: u64) -> Result<()> {
     );
 
     // FIX: Validate config hash to prevent parameter tampering
-    let mut hasher = sha2::Sha256::new();
-    hasher.update(ctx.accounts.pool.salt);
-    hasher.update(ctx.accounts.pool.max_participants.to_le_bytes());
-    hasher.update(ctx.accounts.pool.lock_duration.to_le_bytes());
-    hasher.update(ctx.accounts.pool.amount.to_le_bytes());
-    hasher.update(ctx.accounts.pool.dev_wallet.as_ref());
-    hasher.update(ctx.accounts.pool.dev_fee_bps.to_le_bytes());
-    hasher.update(ctx.accounts.pool.burn_fee_bps.to_le_bytes());
-    hasher.update(ctx.accounts.pool.treasury_wallet.as_ref());
-    hasher.update(ctx.accounts.pool.treasury_fee_bps.to_le_bytes());
-    hasher.update(ctx.accounts.pool.start_time.to_le_bytes());
-    hasher.update(ctx.accounts.pool.duration.to_le_bytes());
-    let current_hash: [u8; 32] = hasher.finalize().into();
-    require!(current_hash == ctx.accounts.pool.config_hash, ErrorCode::ConfigMismatch);
+    require!(
+        ctx.accounts.pool.compute_config_hash() == ctx.accounts.pool.config_hash,
+        ErrorCode::ConfigMismatch
+    );
 
     validate_token_account(
         &ctx.accounts.user_token,
@@ -93,9 +82,12 @@ pub fn donate(ctx: Context<Donate>, amount: u64) -> Result<()> {
     ctx.accounts.pool.can_donate(now)?;
 
     let decimals = ctx.accounts.mint.decimals;
-    let min_native = MIN_DONATE_TOKENS * 10_u64.pow(decimals as u32);
+    let min_native = to_native_amount(MIN_DONATE_TOKENS, decimals)?;
     require!(amount >= min_native, ErrorCode::InvalidAmount);
 
+    // A capped pot can't be inflated past what the creator allowed
+    ctx.accounts.pool.assert_donation_within_cap(amount)?;
+
     require_gte!(ctx.accounts.user_token.amount, amount, ErrorCode::InsufficientFunds);
 
     validate_token_account(
@@ -125,6 +117,20 @@ pub fn donate(ctx: Context<Donate>, amount: u64) -> Result<()> {
     ctx.accounts.pool.total_amount = ctx.accounts.pool.total_amount.checked_add(amount).ok_or(ErrorCode::Overflow)?;
     ctx.accounts.pool.total_volume = ctx.accounts.pool.total_volume.checked_add(amount).ok_or(ErrorCode::Overflow)?;
     ctx.accounts.pool.total_donations += 1;
+    ctx.accounts.pool.total_donated = ctx.accounts.pool.total_donated.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+
+    // Earmarked donations bypass the fee split at payout
+    match purpose {
+        DonationPurpose::Pot => {}
+        DonationPurpose::Prize => {
+            ctx.accounts.pool.donated_prize =
+                ctx.accounts.pool.donated_prize.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        }
+        DonationPurpose::Burn => {
+            ctx.accounts.pool.donated_burn =
+                ctx.accounts.pool.donated_burn.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        }
+    }
 
     let participants_count = ctx.accounts.participants.count;
 
@@ -148,6 +154,15 @@ pub fn donate(ctx: Context<Donate>, amount: u64) -> Result<()> {
         treasury_fee_percent: ctx.accounts.pool.treasury_fee_bps,
     });
 
+    if purpose != DonationPurpose::Pot {
+        emit!(DonationEarmarked {
+            pool_id: ctx.accounts.pool.key(),
+            donor: ctx.accounts.user.key(),
+            amount,
+            purpose,
+        });
+    }
+
     if now > ctx.accounts.pool.start_time + ctx.accounts.pool.duration - 60 {
         emit!(UIHint { pool_id: ctx.accounts.pool.key(), hint: HintType::NearExpire });
     }
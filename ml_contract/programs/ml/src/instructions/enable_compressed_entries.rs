@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compression::{entry_leaf, TreeCpi, NOOP_ID},
+    constants::*,
+    errors::ErrorCode,
+    events::*,
+    state::{Participants, Pool},
+};
+
+#[derive(Accounts)]
+pub struct EnableCompressedEntries<'info> {
+    #[account(mut)]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        seeds = [b"participants", pool.key().as_ref()],
+        bump,
+        constraint = participants.key() == pool.participants_account @ ErrorCode::InvalidParticipantsPda
+    )]
+    pub participants: Box<Account<'info, Participants>>,
+
+    pub user: Signer<'info>,
+
+    /// CHECK: pre-allocated, zeroed tree account owned by the compression program;
+    /// initialized here with the pool PDA as tree authority
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: checked against ACCOUNT_COMPRESSION_ID in the CPI helper
+    pub compression_program: UncheckedAccount<'info>,
+
+    /// CHECK: SPL noop (log wrapper)
+    #[account(address = NOOP_ID @ ErrorCode::InvalidCompressionProgram)]
+    pub noop_program: UncheckedAccount<'info>,
+}
+
+/// Switches a fresh pool to Merkle-tree entries so it can take far more than
+/// `MAX_PARTICIPANTS` joins. The creator's bet becomes leaf 0.
+pub fn enable_compressed_entries(
+    ctx: Context<EnableCompressedEntries>,
+    max_depth: u32,
+    max_buffer_size: u32,
+    max_entries: u32,
+) -> Result<()> {
+    let pool = &ctx.accounts.pool;
+
+    require!(pool.initialized, ErrorCode::UninitializedAccount);
+    pool.assert_open_not_paused()?;
+    pool.assert_owner(&ctx.accounts.user.key())?;
+    require!(!pool.is_compressed(), ErrorCode::CompressedPool);
+    require!(!pool.has_prize_nft(), ErrorCode::InvalidPrizeNft);
+    require!(ctx.accounts.participants.count == 1, ErrorCode::CannotChangeAfterJoins);
+
+    require!(
+        max_depth > 0 && max_depth <= MAX_COMPRESSED_TREE_DEPTH,
+        ErrorCode::InvalidTreeConfig
+    );
+    require!(
+        max_entries >= 2 && (max_entries as u64) <= 1u64 << max_depth,
+        ErrorCode::InvalidTreeConfig
+    );
+
+    let creator = pool.creator;
+    let creator_amount = ctx.accounts.participants.amounts[0];
+    let pool_key = pool.key();
+    let seeds: &[&[u8]] = &[b"pool", pool.mint.as_ref(), pool.salt.as_ref(), &[pool.bump]];
+
+    let pool_info = pool.to_account_info();
+    let tree = TreeCpi {
+        compression_program: &ctx.accounts.compression_program,
+        merkle_tree: &ctx.accounts.merkle_tree,
+        authority: &pool_info,
+        noop: &ctx.accounts.noop_program,
+    };
+    tree.init_empty_merkle_tree(max_depth, max_buffer_size, &[seeds])?;
+
+    let leaf = entry_leaf(&pool_key, &creator, creator_amount, 0);
+    tree.append(leaf, &[seeds])?;
+
+    let pool = &mut ctx.accounts.pool;
+    pool.merkle_tree = ctx.accounts.merkle_tree.key();
+    pool.max_entries = max_entries;
+    pool.entry_count = 1;
+    pool.entries_refunded = 0;
+    pool.config_hash = pool.compute_config_hash();
+
+    emit!(CompressedEntryAppended {
+        pool_id: pool_key,
+        user: creator,
+        amount: creator_amount,
+        index: 0,
+        leaf,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compression::{ACCOUNT_COMPRESSION_ID, NOOP_ID},
+    errors::ErrorCode,
+    state::{Participants, Pool},
+    tickets::{TicketCpi, BUBBLEGUM_ID},
+};
+
+#[derive(Accounts)]
+pub struct EnableTickets<'info> {
+    #[account(mut)]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        seeds = [b"participants", pool.key().as_ref()],
+        bump,
+        constraint = participants.key() == pool.participants_account @ ErrorCode::InvalidParticipantsPda
+    )]
+    pub participants: Box<Account<'info, Participants>>,
+
+    /// Pool creator
+    pub user: Signer<'info>,
+
+    /// Pays for the Bubblegum tree config
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: creator's wallet, receives ticket #0
+    #[account(address = pool.creator @ ErrorCode::NotCreator)]
+    pub creator_wallet: UncheckedAccount<'info>,
+
+    /// CHECK: Bubblegum tree config PDA, created by the CPI
+    #[account(mut)]
+    pub tree_config: UncheckedAccount<'info>,
+
+    /// CHECK: pre-allocated tree account, initialized by the CPI
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: Bubblegum
+    #[account(address = BUBBLEGUM_ID @ ErrorCode::InvalidTicketAccounts)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+
+    /// CHECK: SPL noop
+    #[account(address = NOOP_ID @ ErrorCode::InvalidTicketAccounts)]
+    pub log_wrapper: UncheckedAccount<'info>,
+
+    /// CHECK: SPL account compression
+    #[account(address = ACCOUNT_COMPRESSION_ID @ ErrorCode::InvalidTicketAccounts)]
+    pub compression_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the pool's Bubblegum ticket tree (pool PDA as tree creator) and mints the
+/// creator's ticket; every later `join_pool` mints one ticket per entry.
+pub fn enable_tickets(ctx: Context<EnableTickets>, max_depth: u32, max_buffer_size: u32) -> Result<()> {
+    let pool = &ctx.accounts.pool;
+
+    require!(pool.initialized, ErrorCode::UninitializedAccount);
+    pool.assert_open_not_paused()?;
+    pool.assert_owner(&ctx.accounts.user.key())?;
+    require!(!pool.has_tickets(), ErrorCode::TicketsAlreadyEnabled);
+    require!(!pool.is_compressed(), ErrorCode::CompressedPool);
+    require!(ctx.accounts.participants.count == 1, ErrorCode::CannotChangeAfterJoins);
+
+    let pool_key = pool.key();
+    let pool_id = pool.pool_id;
+    let seeds: &[&[u8]] = &[b"pool", pool.mint.as_ref(), pool.salt.as_ref(), &[pool.bump]];
+
+    let pool_info = pool.to_account_info();
+    let payer_info = ctx.accounts.payer.to_account_info();
+    let system_info = ctx.accounts.system_program.to_account_info();
+    let tickets = TicketCpi {
+        bubblegum_program: &ctx.accounts.bubblegum_program,
+        tree_config: &ctx.accounts.tree_config,
+        merkle_tree: &ctx.accounts.merkle_tree,
+        tree_creator: &pool_info,
+        payer: &payer_info,
+        log_wrapper: &ctx.accounts.log_wrapper,
+        compression_program: &ctx.accounts.compression_program,
+        system_program: &system_info,
+    };
+
+    tickets.create_tree(max_depth, max_buffer_size, &[seeds])?;
+    tickets.mint_ticket(&ctx.accounts.creator_wallet, &pool_key, pool_id, 0, &[seeds])?;
+
+    ctx.accounts.pool.ticket_tree = ctx.accounts.merkle_tree.key();
+
+    Ok(())
+}
@@ -0,0 +1,158 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked, transfer_checked};
+
+use crate::{
+    constants::*,
+    errors::ErrorCode,
+    events::*,
+    state::{ActionType, Participants, Pool, PoolStatus, Team},
+};
+
+#[derive(Accounts)]
+pub struct EnterTeam<'info> {
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut, has_one = mint @ ErrorCode::InvalidMint)]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        mut,
+        constraint = pool_token.key() == pool.pool_token @ ErrorCode::PoolTokenMismatch
+    )]
+    pub pool_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"team", pool.key().as_ref(), captain.key().as_ref()],
+        bump = team.bump,
+        has_one = pool,
+        has_one = captain @ ErrorCode::NotTeamCaptain
+    )]
+    pub team: Box<Account<'info, Team>>,
+
+    #[account(
+        mut,
+        constraint = team_token.mint == mint.key() @ ErrorCode::InvalidMint,
+        constraint = team_token.owner == team.key() @ ErrorCode::InvalidParticipantToken
+    )]
+    pub team_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub captain: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    #[account(
+        mut,
+        seeds = [b"participants", pool.key().as_ref()],
+        bump,
+        constraint = participants.key() == pool.participants_account @ ErrorCode::InvalidParticipantsPda
+    )]
+    pub participants: Box<Account<'info, Participants>>,
+}
+
+/// Enters the team as a single participant, paying the entry price from the team vault.
+/// `amount` is the max price accepted, as in `join_pool`.
+pub fn enter_team(ctx: Context<EnterTeam>, amount: u64) -> Result<()> {
+    require_keys_eq!(
+        *ctx.accounts.mint.to_account_info().owner,
+        ctx.accounts.token_program.key(),
+        ErrorCode::InvalidTokenProgram
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let pool = &mut ctx.accounts.pool;
+
+    require!(pool.initialized, ErrorCode::UninitializedAccount);
+    pool.assert_not_paused()?;
+    pool.assert_active_join_period(now)?;
+    require!(pool.can_join_status(), ErrorCode::PoolUnavailableForJoin);
+    require!(pool.lock_start_time == 0, ErrorCode::JoinClosedAfterUnlock);
+    pool.assert_not_processing()?;
+    require!(pool.compute_config_hash() == pool.config_hash, ErrorCode::ConfigMismatch);
+    require!(!pool.has_prize_nft(), ErrorCode::InvalidPrizeNft);
+    require!(!pool.is_compressed(), ErrorCode::CompressedPool);
+
+    let team = &ctx.accounts.team;
+    require!(!team.entered, ErrorCode::TeamAlreadyEntered);
+
+    let entry_amount = pool.entry_amount_at(now)?;
+    require!(amount >= entry_amount, ErrorCode::EntryPriceExceeded);
+    if pool.min_bet_usd == 0 {
+        require!(entry_amount >= pool.min_bet_native, ErrorCode::InvalidAmount);
+    }
+    require_gte!(ctx.accounts.team_token.amount, entry_amount, ErrorCode::TeamUnderfunded);
+
+    let team_key = team.key();
+    let current_count = ctx.accounts.participants.count;
+    let new_count = current_count.checked_add(1).ok_or(ErrorCode::Overflow)?;
+    require!(new_count <= pool.max_participants, ErrorCode::MaxParticipantsReached);
+    require!(
+        (0..current_count as usize).all(|i| ctx.accounts.participants.list[i] != team_key),
+        ErrorCode::AlreadyParticipated
+    );
+
+    let pool_key = pool.key();
+    let seeds: &[&[u8]] = &[b"team", pool_key.as_ref(), team.captain.as_ref(), &[team.bump]];
+
+    transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.team_token.to_account_info(),
+                to: ctx.accounts.pool_token.to_account_info(),
+                authority: ctx.accounts.team.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+            },
+            &[seeds],
+        ),
+        entry_amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    ctx.accounts.participants.list[current_count as usize] = team_key;
+    ctx.accounts.participants.amounts[current_count as usize] = entry_amount;
+    ctx.accounts.participants.count = new_count;
+
+    pool.total_amount = pool.total_amount.checked_add(entry_amount).ok_or(ErrorCode::Overflow)?;
+    pool.total_volume = pool.total_volume.checked_add(entry_amount).ok_or(ErrorCode::Overflow)?;
+    pool.total_joins = pool.total_joins.checked_add(1).ok_or(ErrorCode::Overflow)?;
+    pool.last_join_time = now;
+
+    ctx.accounts.team.entered = true;
+
+    emit!(TeamEntered {
+        pool_id: pool_key,
+        team: team_key,
+        captain: ctx.accounts.captain.key(),
+        member_count: ctx.accounts.team.member_count,
+        amount: entry_amount,
+    });
+
+    emit!(PoolActivityEvent {
+        pool_id: pool_key,
+        numerical_pool_id: pool.pool_id,
+        action: ActionType::Joined,
+        amount: entry_amount,
+        participant_rank: new_count,
+        dev_fee_percent: pool.dev_fee_bps,
+        burn_fee_percent: pool.burn_fee_bps,
+        treasury_fee_percent: pool.treasury_fee_bps,
+    });
+
+    if new_count == pool.max_participants {
+        pool.status = PoolStatus::Locked;
+        pool.status_reason = REASON_MAX_REACHED;
+        pool.lock_start_time = now;
+
+        emit!(PoolStateEvent {
+            pool_id: pool_key,
+            numerical_pool_id: pool.pool_id,
+            status: PoolStatus::Locked,
+            participant_count: new_count,
+            total_amount: pool.total_amount,
+            status_reason: REASON_MAX_REACHED,
+        });
+    }
+
+    Ok(())
+}
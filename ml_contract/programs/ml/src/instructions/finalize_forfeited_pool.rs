@@ -90,8 +90,12 @@ pub fn finalize_forfeited_pool(ctx: Context<ForfeitUnclaimed>) -> Result<()> {
     );
     require!(pool.close_time != 0, ErrorCode::InvalidPoolStatus);
 
-    // ✅ Delay gate (unless allow_mock is enabled)
-    if now <= pool.close_time + FORFEIT_DELAY && !pool.allow_mock {
+    // ✅ Delay gate (bypassable by mock pools in test-utils builds only)
+    #[cfg(feature = "test-utils")]
+    let skip_delay = pool.allow_mock;
+    #[cfg(not(feature = "test-utils"))]
+    let skip_delay = false;
+    if now <= pool.close_time + FORFEIT_DELAY && !skip_delay {
         return err!(ErrorCode::TooEarlyForEmergency);
     }
 
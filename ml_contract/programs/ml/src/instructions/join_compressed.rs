@@ -0,0 +1,156 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked, transfer_checked};
+
+use crate::{
+    compression::{entry_leaf, TreeCpi, NOOP_ID},
+    constants::*,
+    errors::ErrorCode,
+    events::*,
+    state::{ActionType, Pool, PoolStatus},
+    utils::validate_token_account,
+};
+
+#[derive(Accounts)]
+pub struct JoinCompressed<'info> {
+    #[account(mut)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        has_one = mint @ ErrorCode::InvalidMint,
+        has_one = merkle_tree @ ErrorCode::NotCompressedPool
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        mut,
+        constraint = pool_token.key() == pool.pool_token @ ErrorCode::PoolTokenMismatch
+    )]
+    pub pool_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = user_token.mint == mint.key() @ ErrorCode::InvalidMint,
+        constraint = user_token.owner == user.key() @ ErrorCode::InvalidParticipantToken
+    )]
+    pub user_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub user: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// CHECK: the pool's concurrent Merkle tree
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: checked against ACCOUNT_COMPRESSION_ID in the CPI helper
+    pub compression_program: UncheckedAccount<'info>,
+
+    /// CHECK: SPL noop (log wrapper)
+    #[account(address = NOOP_ID @ ErrorCode::InvalidCompressionProgram)]
+    pub noop_program: UncheckedAccount<'info>,
+}
+
+/// `join_pool` for compressed pools: the entry is appended as a tree leaf. Wallets may
+/// hold several entries, so there is no duplicate check.
+pub fn join_compressed(ctx: Context<JoinCompressed>, amount: u64) -> Result<()> {
+    require_keys_eq!(
+        *ctx.accounts.mint.to_account_info().owner,
+        ctx.accounts.token_program.key(),
+        ErrorCode::InvalidTokenProgram
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let pool = &ctx.accounts.pool;
+
+    require!(pool.initialized, ErrorCode::UninitializedAccount);
+    require!(pool.is_compressed(), ErrorCode::NotCompressedPool);
+    pool.assert_not_paused()?;
+    pool.assert_active_join_period(now)?;
+    require!(pool.can_join_status(), ErrorCode::PoolUnavailableForJoin);
+    require!(pool.lock_start_time == 0, ErrorCode::JoinClosedAfterUnlock);
+    pool.assert_not_processing()?;
+    require!(pool.compute_config_hash() == pool.config_hash, ErrorCode::ConfigMismatch);
+    require!(pool.entry_count < pool.max_entries, ErrorCode::MaxParticipantsReached);
+
+    let entry_amount = pool.entry_amount_at(now)?;
+    require!(amount >= entry_amount, ErrorCode::EntryPriceExceeded);
+    if pool.min_bet_usd == 0 {
+        require!(entry_amount >= pool.min_bet_native, ErrorCode::InvalidAmount);
+    }
+
+    let user_key = ctx.accounts.user.key();
+    validate_token_account(&ctx.accounts.user_token, &ctx.accounts.mint.key(), &user_key, false)?;
+    require_gte!(ctx.accounts.user_token.amount, entry_amount, ErrorCode::InsufficientFunds);
+
+    transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.user_token.to_account_info(),
+                to: ctx.accounts.pool_token.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+            },
+        ),
+        entry_amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    let pool_key = pool.key();
+    let index = pool.entry_count;
+    let leaf = entry_leaf(&pool_key, &user_key, entry_amount, index);
+    let seeds: &[&[u8]] = &[b"pool", pool.mint.as_ref(), pool.salt.as_ref(), &[pool.bump]];
+
+    let pool_info = pool.to_account_info();
+    TreeCpi {
+        compression_program: &ctx.accounts.compression_program,
+        merkle_tree: &ctx.accounts.merkle_tree,
+        authority: &pool_info,
+        noop: &ctx.accounts.noop_program,
+    }
+    .append(leaf, &[seeds])?;
+
+    let pool = &mut ctx.accounts.pool;
+    pool.entry_count = index.checked_add(1).ok_or(ErrorCode::Overflow)?;
+    pool.total_amount = pool.total_amount.checked_add(entry_amount).ok_or(ErrorCode::Overflow)?;
+    pool.total_volume = pool.total_volume.checked_add(entry_amount).ok_or(ErrorCode::Overflow)?;
+    pool.total_joins = pool.total_joins.checked_add(1).ok_or(ErrorCode::Overflow)?;
+    pool.last_join_time = now;
+
+    emit!(CompressedEntryAppended {
+        pool_id: pool_key,
+        user: user_key,
+        amount: entry_amount,
+        index,
+        leaf,
+    });
+
+    emit!(PoolActivityEvent {
+        pool_id: pool_key,
+        numerical_pool_id: pool.pool_id,
+        action: ActionType::Joined,
+        amount: entry_amount,
+        participant_rank: 0,
+        dev_fee_percent: pool.dev_fee_bps,
+        burn_fee_percent: pool.burn_fee_bps,
+        treasury_fee_percent: pool.treasury_fee_bps,
+    });
+
+    if pool.entry_count == pool.max_entries {
+        pool.status = PoolStatus::Locked;
+        pool.status_reason = REASON_MAX_REACHED;
+        pool.lock_start_time = now;
+
+        emit!(PoolStateEvent {
+            pool_id: pool_key,
+            numerical_pool_id: pool.pool_id,
+            status: PoolStatus::Locked,
+            participant_count: 0,
+            total_amount: pool.total_amount,
+            status_reason: REASON_MAX_REACHED,
+        });
+    }
+
+    Ok(())
+}
@@ -1,15 +1,13 @@
 use anchor_lang::prelude::*;
-use anchor_spl::{
-    associated_token::get_associated_token_address_with_program_id,
-    token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked, transfer_checked},
-};
-use sha2::Digest;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked, transfer_checked};
 
 use crate::{
+    compression::{ACCOUNT_COMPRESSION_ID, NOOP_ID},
     constants::*,
     errors::ErrorCode,
     events::*,
     state::{ActionType, HintType, Participants, Pool, PoolStatus},
+    tickets::{TicketCpi, BUBBLEGUM_ID},
     utils::validate_token_account,
 };
 
@@ -28,15 +26,15 @@ pub struct JoinPool<'info> {
     )]
     pub pool_token: InterfaceAccount<'info, TokenAccount>,
 
+    /// Any token account of `mint` owned by `user` (PDA vaults need not be ATAs)
     #[account(
         mut,
-        constraint = user_token.key()
-            == get_associated_token_address_with_program_id(&user.key(), &mint.key(), &token_program.key())
-            @ ErrorCode::InvalidParticipantToken
+        constraint = user_token.mint == mint.key() @ ErrorCode::InvalidMint,
+        constraint = user_token.owner == user.key() @ ErrorCode::InvalidParticipantToken
     )]
     pub user_token: InterfaceAccount<'info, TokenAccount>,
 
-    #[account(mut)]
+    /// Participant - a wallet or another program's PDA signing via CPI
     pub user: Signer<'info>,
 
     pub token_program: Interface<'info, TokenInterface>,
@@ -48,6 +46,24 @@ pub struct JoinPool<'info> {
         constraint = participants.key() == pool.participants_account @ ErrorCode::InvalidParticipantsPda
     )]
     pub participants: Account<'info, Participants>,
+
+    // ---- cNFT tickets only (pool.ticket_tree set) ----
+    /// CHECK: Bubblegum tree config PDA, validated by Bubblegum
+    #[account(mut)]
+    pub ticket_tree_config: Option<UncheckedAccount<'info>>,
+    /// CHECK: must equal pool.ticket_tree
+    #[account(mut)]
+    pub ticket_tree: Option<UncheckedAccount<'info>>,
+    /// CHECK: Bubblegum
+    #[account(address = BUBBLEGUM_ID @ ErrorCode::InvalidTicketAccounts)]
+    pub bubblegum_program: Option<UncheckedAccount<'info>>,
+    /// CHECK: SPL noop
+    #[account(address = NOOP_ID @ ErrorCode::InvalidTicketAccounts)]
+    pub log_wrapper: Option<UncheckedAccount<'info>>,
+    /// CHECK: SPL account compression
+    #[account(address = ACCOUNT_COMPRESSION_ID @ ErrorCode::InvalidTicketAccounts)]
+    pub compression_program: Option<UncheckedAccount<'info>>,
+    pub system_program: Option<Program<'info, System>>,
 }
 
 pub fn join_pool(ctx: Context<JoinPool>, amount: u64) -> Result<()> {
@@ -73,6 +89,7 @@ pub fn join_pool(ctx: Context<JoinPool>, amount: u64) -> Result<()> {
 
     // Status gates
     require!(pool.can_join_status(), ErrorCode::PoolUnavailableForJoin);
+    require!(!pool.is_compressed(), ErrorCode::CompressedPool);
 
     // Don't allow join after lock started (or if already unlocked path happened)
     require!(pool.lock_start_time == 0, ErrorCode::JoinClosedAfterUnlock);
@@ -88,42 +105,19 @@ pub fn join_pool(ctx: Context<JoinPool>, amount: u64) -> Result<()> {
     pool.assert_not_processing()?;
 
     // ✅ FIX: Validate config hash to prevent parameter tampering
-    let mut hasher = sha2::Sha256::new();
-    hasher.update(pool.salt);
-    hasher.update(pool.max_participants.to_le_bytes());
-    hasher.update(pool.lock_duration.to_le_bytes());
-    hasher.update(pool.amount.to_le_bytes());
-    hasher.update(pool.dev_wallet.as_ref());
-    hasher.update(pool.dev_fee_bps.to_le_bytes());
-    hasher.update(pool.burn_fee_bps.to_le_bytes());
-    hasher.update(pool.treasury_wallet.as_ref());
-    hasher.update(pool.treasury_fee_bps.to_le_bytes());
-    hasher.update(pool.start_time.to_le_bytes());
-    hasher.update(pool.duration.to_le_bytes());
-    let current_hash: [u8; 32] = hasher.finalize().into();
-    require!(current_hash == pool.config_hash, ErrorCode::ConfigMismatch);
-
-    // Amount checks (exact bet)
+    require!(pool.compute_config_hash() == pool.config_hash, ErrorCode::ConfigMismatch);
+
+    // Amount checks: `amount` is the max the user accepts, `entry_amount` is what gets charged
     let decimals = ctx.accounts.mint.decimals;
-    let min_native = MIN_BET_TOKENS
-        .checked_mul(10_u64.pow(decimals as u32))
-        .ok_or(ErrorCode::Overflow)?;
+    let entry_amount = pool.entry_amount_at(now)?;
+    require!(amount >= entry_amount, ErrorCode::EntryPriceExceeded);
 
-    require!(amount == pool.amount, ErrorCode::InvalidAmount);
-    require!(pool.amount >= min_native, ErrorCode::InvalidAmount);
+    // USD-denominated pools were priced against the oracle at creation (both auction ends)
+    if pool.min_bet_usd == 0 {
+        require!(entry_amount >= pool.min_bet_native, ErrorCode::InvalidAmount);
+    }
 
-    // ATA checks (prevents spoofed token account)
     let user_key = ctx.accounts.user.key();
-    let expected_ata = get_associated_token_address_with_program_id(
-        &user_key,
-        &ctx.accounts.mint.key(),
-        &ctx.accounts.token_program.key(),
-    );
-    require_keys_eq!(
-        expected_ata,
-        ctx.accounts.user_token.key(),
-        ErrorCode::InvalidParticipantToken
-    );
 
     // Validate user token account (owner/mint/frozen + optional strict)
     validate_token_account(
@@ -134,7 +128,7 @@ pub fn join_pool(ctx: Context<JoinPool>, amount: u64) -> Result<()> {
     )?;
 
     // Balance check
-    require_gte!(ctx.accounts.user_token.amount, amount, ErrorCode::InsufficientFunds);
+    require_gte!(ctx.accounts.user_token.amount, entry_amount, ErrorCode::InsufficientFunds);
 
     // ✅ FIX: Atomic check-and-increment (overflow-safe)
     let current_count = ctx.accounts.participants.count;
@@ -162,20 +156,46 @@ pub fn join_pool(ctx: Context<JoinPool>, amount: u64) -> Result<()> {
                 mint: ctx.accounts.mint.to_account_info(),
             },
         ),
-        amount,
+        entry_amount,
         decimals,
     )?;
 
     // Update participants after transfer succeeds
     ctx.accounts.participants.list[current_count as usize] = user_key;
+    ctx.accounts.participants.amounts[current_count as usize] = entry_amount;
     ctx.accounts.participants.count = new_count;
 
     // Update pool accounting
-    pool.total_amount = pool.total_amount.checked_add(amount).ok_or(ErrorCode::Overflow)?;
-    pool.total_volume = pool.total_volume.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+    pool.total_amount = pool.total_amount.checked_add(entry_amount).ok_or(ErrorCode::Overflow)?;
+    pool.total_volume = pool.total_volume.checked_add(entry_amount).ok_or(ErrorCode::Overflow)?;
     pool.total_joins = pool.total_joins.checked_add(1).ok_or(ErrorCode::Overflow)?;
     pool.last_join_time = now;
 
+    // 🎟️ cNFT ticket encoding pool id and entry index
+    if pool.has_tickets() {
+        let missing = || error!(ErrorCode::InvalidTicketAccounts);
+        let ticket_tree = ctx.accounts.ticket_tree.as_ref().ok_or_else(missing)?;
+        require_keys_eq!(ticket_tree.key(), pool.ticket_tree, ErrorCode::InvalidTicketAccounts);
+
+        let pool_info = pool.to_account_info();
+        let user_info = ctx.accounts.user.to_account_info();
+        let system_info = ctx.accounts.system_program.as_ref().ok_or_else(missing)?.to_account_info();
+        let pool_key = pool.key();
+        let seeds: &[&[u8]] = &[b"pool", pool.mint.as_ref(), pool.salt.as_ref(), &[pool.bump]];
+
+        TicketCpi {
+            bubblegum_program: ctx.accounts.bubblegum_program.as_ref().ok_or_else(missing)?,
+            tree_config: ctx.accounts.ticket_tree_config.as_ref().ok_or_else(missing)?,
+            merkle_tree: ticket_tree,
+            tree_creator: &pool_info,
+            payer: &user_info,
+            log_wrapper: ctx.accounts.log_wrapper.as_ref().ok_or_else(missing)?,
+            compression_program: ctx.accounts.compression_program.as_ref().ok_or_else(missing)?,
+            system_program: &system_info,
+        }
+        .mint_ticket(&user_info, &pool_key, pool.pool_id, current_count as u32, &[seeds])?;
+    }
+
     let pool_id = pool.pool_id;
     let participants_count = ctx.accounts.participants.count;
 
@@ -192,7 +212,7 @@ pub fn join_pool(ctx: Context<JoinPool>, amount: u64) -> Result<()> {
         pool_id: pool.key(),
         numerical_pool_id: pool_id,
         action: ActionType::Joined,
-        amount,
+        amount: entry_amount,
         participant_rank: participants_count,
         dev_fee_percent: pool.dev_fee_bps,
         burn_fee_percent: pool.burn_fee_bps,
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::*,
+    errors::ErrorCode,
+    events::*,
+    state::{ActionType, Participants, Pool, PoolStatus},
+};
+
+#[derive(Accounts)]
+pub struct LockAtDeadline<'info> {
+    #[account(mut)]
+    pub pool: Account<'info, Pool>,
+
+    /// Anyone may lock a pool whose join deadline has passed
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [b"participants", pool.key().as_ref()],
+        bump,
+        constraint = participants.key() == pool.participants_account @ ErrorCode::InvalidParticipantsPda
+    )]
+    pub participants: Account<'info, Participants>,
+}
+
+/// Starts the lock of an open pool once entries have closed at `join_deadline`,
+/// so a pool that never fills still goes on to a draw instead of sitting open until expiry
+pub fn lock_at_deadline(ctx: Context<LockAtDeadline>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let pool = &mut ctx.accounts.pool;
+
+    require!(pool.initialized, ErrorCode::UninitializedAccount);
+    pool.assert_not_paused()?;
+    pool.assert_open()?;
+    pool.assert_not_processing()?;
+
+    // Entries must be closed, but an expired pool is left to the sweep/refund path
+    require!(now > pool.join_deadline, ErrorCode::JoinDeadlineNotReached);
+    pool.assert_not_expired(now)?;
+
+    let participants_count = ctx.accounts.participants.count;
+    require!(participants_count >= 2, ErrorCode::NotEnoughParticipants);
+
+    pool.status = PoolStatus::Locked;
+    pool.status_reason = REASON_JOIN_DEADLINE;
+    pool.lock_start_time = now;
+
+    emit!(PoolStateEvent {
+        pool_id: pool.key(),
+        numerical_pool_id: pool.pool_id,
+        status: PoolStatus::Locked,
+        participant_count: participants_count,
+        total_amount: pool.total_amount,
+        status_reason: REASON_JOIN_DEADLINE,
+    });
+
+    emit!(PoolActivityEvent {
+        pool_id: pool.key(),
+        numerical_pool_id: pool.pool_id,
+        action: ActionType::LockedAtDeadline,
+        amount: pool.total_amount,
+        participant_rank: 0,
+        dev_fee_percent: pool.dev_fee_bps,
+        burn_fee_percent: pool.burn_fee_bps,
+        treasury_fee_percent: pool.treasury_fee_bps,
+    });
+
+    Ok(())
+}
@@ -13,8 +13,28 @@ pub mod select_winner;
 pub mod payout_winner;
 pub mod pause_pool;
 pub mod unpause_pool;
+#[cfg(feature = "test-utils")]
 pub mod force_expire;
 pub mod finalize_forfeited_pool;
+pub mod deposit_prize_nft;
+pub mod release_prize_nft;
+pub mod set_price_feed;
+pub mod clone_pool;
+pub mod create_team;
+pub mod contribute_team;
+pub mod enter_team;
+pub mod claim_team_refund;
+pub mod distribute_team_prize;
+pub mod propose_admin_close;
+pub mod withdraw_admin_close;
+pub mod settle_from_callback;
+pub mod enable_compressed_entries;
+pub mod join_compressed;
+pub mod select_winner_compressed;
+pub mod claim_compressed_refund;
+pub mod enable_tickets;
+pub mod transfer_entry;
+pub mod lock_at_deadline;
 
 // Re-export accounts types
 pub use create_pool::CreatePool;
@@ -31,8 +51,28 @@ pub use request_randomness::RequestRandomness;
 pub use select_winner::SelectWinner;
 pub use payout_winner::PayoutWinner;
 pub use pause_pool::PausePool;
+#[cfg(feature = "test-utils")]
 pub use force_expire::ForceExpire;
 pub use finalize_forfeited_pool::ForfeitUnclaimed;
+pub use deposit_prize_nft::DepositPrizeNft;
+pub use release_prize_nft::ReleasePrizeNft;
+pub use set_price_feed::SetPriceFeed;
+pub use clone_pool::ClonePool;
+pub use create_team::CreateTeam;
+pub use contribute_team::ContributeTeam;
+pub use enter_team::EnterTeam;
+pub use claim_team_refund::ClaimTeamRefund;
+pub use distribute_team_prize::DistributeTeamPrize;
+pub use propose_admin_close::ProposeAdminClose;
+pub use withdraw_admin_close::WithdrawAdminClose;
+pub use settle_from_callback::SettleFromCallback;
+pub use enable_compressed_entries::EnableCompressedEntries;
+pub use join_compressed::JoinCompressed;
+pub use select_winner_compressed::SelectWinnerCompressed;
+pub use claim_compressed_refund::ClaimCompressedRefund;
+pub use enable_tickets::EnableTickets;
+pub use transfer_entry::TransferEntry;
+pub use lock_at_deadline::LockAtDeadline;
 
 // Re-export instruction handlers
 pub use create_pool::create_pool;
@@ -50,5 +90,25 @@ pub use select_winner::select_winner;
 pub use payout_winner::payout_winner;
 pub use pause_pool::pause_pool;
 pub use unpause_pool::unpause_pool;
+#[cfg(feature = "test-utils")]
 pub use force_expire::force_expire;
 pub use finalize_forfeited_pool::finalize_forfeited_pool;
+pub use deposit_prize_nft::deposit_prize_nft;
+pub use release_prize_nft::release_prize_nft;
+pub use set_price_feed::set_price_feed;
+pub use clone_pool::clone_pool;
+pub use create_team::create_team;
+pub use contribute_team::contribute_team;
+pub use enter_team::enter_team;
+pub use claim_team_refund::claim_team_refund;
+pub use distribute_team_prize::distribute_team_prize;
+pub use propose_admin_close::propose_admin_close;
+pub use withdraw_admin_close::withdraw_admin_close;
+pub use settle_from_callback::settle_from_callback;
+pub use enable_compressed_entries::enable_compressed_entries;
+pub use join_compressed::join_compressed;
+pub use select_winner_compressed::select_winner_compressed;
+pub use claim_compressed_refund::claim_compressed_refund;
+pub use enable_tickets::enable_tickets;
+pub use transfer_entry::transfer_entry;
+pub use lock_at_deadline::lock_at_deadline;
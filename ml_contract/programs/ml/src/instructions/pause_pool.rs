@@ -25,6 +25,13 @@ pub fn pause_pool(ctx: Context<PausePool>) -> Result<()> {
         ErrorCode::InvalidPoolStatus
     );
 
+    // ⏸️ Pausing mid-lock freezes the countdown (see unpause_pool)
+    let now = Clock::get()?.unix_timestamp;
+    let pool = &mut ctx.accounts.pool;
+    if !pool.paused && pool.status == PoolStatus::Locked && now < pool.lock_end_time()? {
+        pool.paused_at = now;
+    }
+
     ctx.accounts.pool.paused = true;
     ctx.accounts.pool.status_reason = REASON_PAUSED;
 
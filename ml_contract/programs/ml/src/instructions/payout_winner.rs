@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
-    associated_token::{self, AssociatedToken},
+    associated_token,
     token_interface::{
         Mint, TokenAccount, TokenInterface, TransferChecked, transfer_checked, BurnChecked,
         burn_checked,
@@ -30,14 +30,8 @@ pub struct PayoutWinner<'info> {
     )]
     pub pool_token: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    // ✅ Dual-program safe: ATA managed by Anchor, works for SPL + Token-2022
-    #[account(
-        init_if_needed,
-        payer = user,
-        associated_token::mint = mint,
-        associated_token::authority = winner_pubkey,
-        associated_token::token_program = token_program
-    )]
+    /// Any token account of `mint` owned by the winner (PDA winners need not hold an ATA)
+    #[account(mut)]
     pub winner_token: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(mut)]
@@ -46,14 +40,15 @@ pub struct PayoutWinner<'info> {
     #[account(mut)]
     pub treasury_token: Box<InterfaceAccount<'info, TokenAccount>>,
 
+    /// NFT-prize pools only: creator ATA receiving the winner's share of the pot
+    #[account(mut)]
+    pub creator_token: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
     pub token_program: Interface<'info, TokenInterface>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub system_program: Program<'info, System>,
 
     /// CHECK: validated against pool.winner
     pub winner_pubkey: UncheckedAccount<'info>,
 
-    #[account(mut)]
     pub user: Signer<'info>,
 
     #[account(
@@ -114,7 +109,7 @@ pub fn payout_winner(ctx: Context<PayoutWinner>) -> Result<()> {
         );
     }
 
-    // Validate ATA addresses for dev/treasury/winner (prevents spoofed accounts)
+    // Validate ATA addresses for dev/treasury (prevents spoofed accounts)
     let expected_dev_ata = associated_token::get_associated_token_address_with_program_id(
         &ctx.accounts.pool.dev_wallet,
         &ctx.accounts.mint.key(),
@@ -149,16 +144,6 @@ pub fn payout_winner(ctx: Context<PayoutWinner>) -> Result<()> {
         true,
     )?;
 
-    let expected_winner_ata = associated_token::get_associated_token_address_with_program_id(
-        &winner_pubkey,
-        &ctx.accounts.mint.key(),
-        &ctx.accounts.token_program.key(),
-    );
-    require_keys_eq!(
-        expected_winner_ata,
-        ctx.accounts.winner_token.key(),
-        ErrorCode::InvalidParticipantToken
-    );
     validate_token_account(
         &ctx.accounts.winner_token,
         &ctx.accounts.mint.key(),
@@ -166,34 +151,54 @@ pub fn payout_winner(ctx: Context<PayoutWinner>) -> Result<()> {
         true,
     )?;
 
+    // NFT-prize pools: winner receives the escrowed NFT, the pot share routes to the creator
+    let prize_destination = if ctx.accounts.pool.has_prize_nft() {
+        let creator_token = ctx
+            .accounts
+            .creator_token
+            .as_ref()
+            .ok_or(ErrorCode::InvalidParticipantToken)?;
+        let expected_creator_ata = associated_token::get_associated_token_address_with_program_id(
+            &ctx.accounts.pool.creator,
+            &ctx.accounts.mint.key(),
+            &ctx.accounts.token_program.key(),
+        );
+        require_keys_eq!(
+            expected_creator_ata,
+            creator_token.key(),
+            ErrorCode::InvalidParticipantToken
+        );
+        validate_token_account(
+            creator_token,
+            &ctx.accounts.mint.key(),
+            &ctx.accounts.pool.creator,
+            true,
+        )?;
+        creator_token.to_account_info()
+    } else {
+        ctx.accounts.winner_token.to_account_info()
+    };
+
     // Balance sanity
     let total = ctx.accounts.pool.total_amount;
     require_eq!(ctx.accounts.pool_token.amount, total, ErrorCode::SpoofedDonation);
 
-    // Compute payouts
-    let denominator = 10_000_u64;
-    let dev_amount = total
-        .checked_mul(ctx.accounts.pool.dev_fee_bps as u64)
-        .ok_or(ErrorCode::Overflow)?
-        / denominator;
-
-    let burn_amount = total
-        .checked_mul(ctx.accounts.pool.burn_fee_bps as u64)
-        .ok_or(ErrorCode::Overflow)?
-        / denominator;
-
-    let treasury_amount = total
-        .checked_mul(ctx.accounts.pool.treasury_fee_bps as u64)
-        .ok_or(ErrorCode::Overflow)?
-        / denominator;
-
-    let paid = dev_amount
-        .checked_add(burn_amount)
-        .ok_or(ErrorCode::Overflow)?
-        .checked_add(treasury_amount)
-        .ok_or(ErrorCode::Overflow)?;
-
-    let winner_amount = total.checked_sub(paid).ok_or(ErrorCode::Overflow)?;
+    // Compute payouts - fees apply to the shared pot only, earmarked donations pass through
+    // and the remainder, donated_prize included, goes to the winner
+    let ml_core::FeeSplit {
+        dev: dev_amount,
+        burn: burn_amount,
+        treasury: treasury_amount,
+        winner: winner_amount,
+    } = ml_core::fee_split(
+        total,
+        ctx.accounts.pool.fee_base()?,
+        ctx.accounts.pool.dev_fee_bps,
+        ctx.accounts.pool.burn_fee_bps,
+        ctx.accounts.pool.treasury_fee_bps,
+        ctx.accounts.pool.donated_burn,
+    )
+    .ok_or(ErrorCode::Overflow)?;
 
     let pool_id = ctx.accounts.pool.pool_id;
 
@@ -213,7 +218,7 @@ pub fn payout_winner(ctx: Context<PayoutWinner>) -> Result<()> {
                 ctx.accounts.token_program.to_account_info(),
                 TransferChecked {
                     from: ctx.accounts.pool_token.to_account_info(),
-                    to: ctx.accounts.winner_token.to_account_info(),
+                    to: prize_destination,
                     authority: ctx.accounts.pool.to_account_info(),
                     mint: ctx.accounts.mint.to_account_info(),
                 },
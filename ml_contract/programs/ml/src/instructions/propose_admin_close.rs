@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::*,
+    errors::ErrorCode,
+    events::*,
+    state::{AdminCloseProposal, Pool},
+};
+
+#[derive(Accounts)]
+pub struct ProposeAdminClose<'info> {
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        init,
+        payer = user,
+        space = 8 + AdminCloseProposal::INIT_SPACE,
+        seeds = [b"admin_close", pool.key().as_ref()],
+        bump
+    )]
+    pub proposal: Box<Account<'info, AdminCloseProposal>>,
+
+    #[account(mut, address = pool.dev_wallet @ ErrorCode::NotDeveloper)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Step 1 of the admin close: publish the reason and start the `ADMIN_CLOSE_DELAY` notice period
+pub fn propose_admin_close(
+    ctx: Context<ProposeAdminClose>,
+    reason_code: u8,
+    uri: String,
+    slash_bond: bool,
+) -> Result<()> {
+    require!(uri.len() <= MAX_ADMIN_CLOSE_URI_LEN, ErrorCode::UriTooLong);
+    ctx.accounts.pool.assert_open()?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let executable_at = now.checked_add(ADMIN_CLOSE_DELAY).ok_or(ErrorCode::Overflow)?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.pool = ctx.accounts.pool.key();
+    proposal.proposer = ctx.accounts.user.key();
+    proposal.reason_code = reason_code;
+    proposal.uri = uri.clone();
+    proposal.slash_bond = slash_bond;
+    proposal.proposed_at = now;
+    proposal.executable_at = executable_at;
+    proposal.bump = ctx.bumps.proposal;
+
+    emit!(AdminCloseProposed {
+        pool_id: ctx.accounts.pool.key(),
+        proposer: ctx.accounts.user.key(),
+        reason_code,
+        uri,
+        slash_bond,
+        executable_at,
+    });
+
+    Ok(())
+}
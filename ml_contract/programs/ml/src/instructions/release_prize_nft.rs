@@ -0,0 +1,166 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    metadata::mpl_token_metadata,
+    token_interface::{close_account, CloseAccount, Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    errors::ErrorCode,
+    events::*,
+    state::{Pool, PoolStatus},
+    utils::{transfer_prize_nft, PnftAccounts, PrizeNftTransfer},
+};
+
+#[derive(Accounts)]
+pub struct ReleasePrizeNft<'info> {
+    #[account(mut)]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// Anyone may crank the release; pays for the recipient ATA if missing
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// CHECK: validated against pool.winner (Ended) or pool.creator (Cancelled)
+    pub recipient: UncheckedAccount<'info>,
+
+    /// Receives the escrow account rent
+    #[account(mut, address = pool.creator @ ErrorCode::NotCreator)]
+    pub creator_wallet: SystemAccount<'info>,
+
+    #[account(mut, address = pool.prize_mint @ ErrorCode::InvalidMint)]
+    pub prize_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut, address = pool.prize_token @ ErrorCode::PoolTokenMismatch)]
+    pub pool_prize_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = prize_mint,
+        associated_token::authority = recipient,
+        associated_token::token_program = prize_token_program
+    )]
+    pub recipient_prize_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub prize_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    // ---- pNFT only (Token Metadata TransferV1) ----
+    /// CHECK: Token Metadata program
+    #[account(address = mpl_token_metadata::ID)]
+    pub token_metadata_program: Option<UncheckedAccount<'info>>,
+    /// CHECK: validated by Token Metadata
+    #[account(mut)]
+    pub metadata: Option<UncheckedAccount<'info>>,
+    /// CHECK: validated by Token Metadata
+    pub edition: Option<UncheckedAccount<'info>>,
+    /// CHECK: validated by Token Metadata
+    #[account(mut)]
+    pub owner_token_record: Option<UncheckedAccount<'info>>,
+    /// CHECK: validated by Token Metadata
+    #[account(mut)]
+    pub destination_token_record: Option<UncheckedAccount<'info>>,
+    /// CHECK: instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub sysvar_instructions: Option<UncheckedAccount<'info>>,
+    /// CHECK: validated by Token Metadata
+    pub authorization_rules_program: Option<UncheckedAccount<'info>>,
+    /// CHECK: validated by Token Metadata
+    pub authorization_rules: Option<UncheckedAccount<'info>>,
+}
+
+pub fn release_prize_nft(ctx: Context<ReleasePrizeNft>) -> Result<()> {
+    require_keys_eq!(
+        *ctx.accounts.prize_mint.to_account_info().owner,
+        ctx.accounts.prize_token_program.key(),
+        ErrorCode::InvalidTokenProgram
+    );
+
+    let pool = &ctx.accounts.pool;
+
+    require!(pool.has_prize_nft(), ErrorCode::NoPrizeNft);
+    require!(!pool.prize_released, ErrorCode::AlreadyEnded);
+    pool.assert_not_processing()?;
+
+    // Ended (payout done) => winner; Cancelled => back to creator
+    let expected_recipient = match pool.status {
+        PoolStatus::Ended => pool.winner,
+        PoolStatus::Cancelled => pool.creator,
+        _ => return err!(ErrorCode::InvalidPoolStatus),
+    };
+    require!(expected_recipient != Pubkey::default(), ErrorCode::NoWinnerSelected);
+    require_keys_eq!(
+        ctx.accounts.recipient.key(),
+        expected_recipient,
+        ErrorCode::InvalidWinnerPubkey
+    );
+
+    let pnft = match ctx.accounts.token_metadata_program.as_ref() {
+        Some(token_metadata_program) => Some(PnftAccounts {
+            token_metadata_program,
+            metadata: ctx.accounts.metadata.as_ref().ok_or(ErrorCode::MissingPnftAccounts)?,
+            edition: ctx.accounts.edition.as_ref().ok_or(ErrorCode::MissingPnftAccounts)?,
+            from_token_record: ctx.accounts.owner_token_record.as_ref().ok_or(ErrorCode::MissingPnftAccounts)?,
+            to_token_record: ctx.accounts.destination_token_record.as_ref().ok_or(ErrorCode::MissingPnftAccounts)?,
+            sysvar_instructions: ctx.accounts.sysvar_instructions.as_ref().ok_or(ErrorCode::MissingPnftAccounts)?,
+            authorization_rules_program: ctx.accounts.authorization_rules_program.as_deref(),
+            authorization_rules: ctx.accounts.authorization_rules.as_deref(),
+        }),
+        None => None,
+    };
+
+    let seeds: &[&[u8]] = &[
+        b"pool",
+        pool.mint.as_ref(),
+        pool.salt.as_ref(),
+        &[pool.bump],
+    ];
+
+    let prize_mint_info = ctx.accounts.prize_mint.to_account_info();
+    let from_info = ctx.accounts.pool_prize_token.to_account_info();
+    let to_info = ctx.accounts.recipient_prize_token.to_account_info();
+    let pool_info = pool.to_account_info();
+
+    transfer_prize_nft(
+        PrizeNftTransfer {
+            token_program: &ctx.accounts.prize_token_program,
+            associated_token_program: &ctx.accounts.associated_token_program,
+            system_program: &ctx.accounts.system_program,
+            mint: &prize_mint_info,
+            from: &from_info,
+            from_owner: &pool_info,
+            to: &to_info,
+            to_owner: &ctx.accounts.recipient,
+            authority: &pool_info,
+            payer: &ctx.accounts.user,
+            pnft,
+        },
+        &[seeds],
+    )?;
+
+    // Return escrow rent to the creator who funded it
+    close_account(CpiContext::new_with_signer(
+        ctx.accounts.prize_token_program.to_account_info(),
+        CloseAccount {
+            account: from_info,
+            destination: ctx.accounts.creator_wallet.to_account_info(),
+            authority: pool_info,
+        },
+        &[seeds],
+    ))?;
+
+    let status_reason = pool.status_reason;
+    let pool = &mut ctx.accounts.pool;
+    pool.prize_released = true;
+
+    emit!(PrizeNftReleased {
+        pool_id: pool.key(),
+        prize_mint: pool.prize_mint,
+        recipient: expected_recipient,
+        reason: status_reason,
+    });
+
+    Ok(())
+}
@@ -1,6 +1,4 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program;
-use sha2::Digest;
 use switchboard_on_demand::RandomnessAccountData;
 
 use crate::{
@@ -33,10 +31,7 @@ pub fn request_randomness(ctx: Context<RequestRandomness>) -> Result<()> {
     require!(ctx.accounts.pool.status == PoolStatus::Unlocked, ErrorCode::InvalidPoolStatus);
 
     let clock = Clock::get()?;
-    require!(
-        clock.unix_timestamp >= ctx.accounts.pool.lock_start_time + ctx.accounts.pool.lock_duration,
-        ErrorCode::PoolStillLocked
-    );
+    ctx.accounts.pool.assert_unlocked_time(clock.unix_timestamp)?;
 
     let now = clock.unix_timestamp;
     let caller = ctx.accounts.user.key();
@@ -50,31 +45,13 @@ pub fn request_randomness(ctx: Context<RequestRandomness>) -> Result<()> {
 
     let rk = ctx.accounts.randomness.key();
 
-    // mock if randomness is default OR System Program
-    let is_mock = ctx.accounts.pool.allow_mock && (rk == Pubkey::default() || rk == system_program::ID);
+    // mock if randomness is default OR System Program (test-utils builds only)
+    #[cfg(feature = "test-utils")]
+    let is_mock = crate::mock::commit_mock_randomness(&mut ctx.accounts.pool, rk, clock.slot)?;
+    #[cfg(not(feature = "test-utils"))]
+    let is_mock = false;
 
-    if is_mock {
-        require!(ctx.accounts.pool.randomness_account == ZERO_PUBKEY, ErrorCode::RandomnessAlreadySet);
-        require!(
-            rk == Pubkey::default() || rk == system_program::ID,
-            ErrorCode::InvalidRandomnessAccount
-        );
-
-        let mock_randomness = {
-            let mut hasher = sha2::Sha256::new();
-            hasher.update(ctx.accounts.pool.pool_id.to_le_bytes());
-            hasher.update(clock.slot.to_le_bytes());
-            hasher.update(ctx.accounts.pool.creator.as_ref());
-            let hash = hasher.finalize();
-            let mut bytes = [0u8; 16];
-            bytes.copy_from_slice(&hash[..16]);
-            u128::from_le_bytes(bytes)
-        };
-
-        ctx.accounts.pool.randomness = mock_randomness;
-        ctx.accounts.pool.randomness_account = Pubkey::default();
-        ctx.accounts.pool.status = PoolStatus::RandomnessCommitted;
-    } else {
+    if !is_mock {
         require!(ctx.accounts.pool.randomness_account == ZERO_PUBKEY, ErrorCode::RandomnessAlreadySet);
 
         require_keys_eq!(
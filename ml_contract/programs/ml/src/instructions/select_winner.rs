@@ -29,198 +29,160 @@ pub struct SelectWinner<'info> {
 
 pub fn select_winner(ctx: Context<SelectWinner>) -> Result<()> {
     let now = Clock::get()?;
-    let now_ts = now.unix_timestamp;
 
-    ctx.accounts.pool.assert_not_paused()?;
+    let is_timeout = now.unix_timestamp > ctx.accounts.pool.unlock_time + PAYOUT_TIMEOUT;
+    if !is_timeout {
+        require_keys_eq!(ctx.accounts.user.key(), ctx.accounts.pool.dev_wallet, ErrorCode::Unauthorized);
+    }
+
+    draw_winner(ctx.accounts, &now)
+}
+
+/// Resolves the randomness and records the winner; shared by `select_winner` and
+/// `settle_from_callback`, which differ only in who may trigger it
+pub(crate) fn draw_winner(accounts: &mut SelectWinner, now: &Clock) -> Result<()> {
+    require!(!accounts.pool.is_compressed(), ErrorCode::CompressedPool);
+
+    let (randomness_u128, normalized) = resolve_randomness(accounts, now)?;
+
+    let participant_count = accounts.participants.count as u64;
+    let winner_index = (normalized % participant_count) as usize;
+    require!(
+        winner_index < accounts.participants.count as usize,
+        ErrorCode::InvalidWinnerAccount
+    );
+
+    let winner_pubkey = accounts.participants.list[winner_index];
+    finish_selection(accounts, winner_pubkey, randomness_u128)
+}
+
+/// Pre-selection checks and randomness resolution; opens the reentrancy critical section
+/// and leaves the pool `RandomnessRevealed`. Returns `(randomness, normalized)`.
+pub(crate) fn resolve_randomness(accounts: &mut SelectWinner, now: &Clock) -> Result<(u128, u64)> {
+    accounts.pool.assert_not_paused()?;
 
     // 🔒 Reentrancy guard - start critical section
-    ctx.accounts.pool.start_processing()?;
+    accounts.pool.start_processing()?;
 
-    require!(ctx.accounts.pool.status != PoolStatus::Ended, ErrorCode::AlreadyEnded);
+    require!(accounts.pool.status != PoolStatus::Ended, ErrorCode::AlreadyEnded);
     require!(
-        ctx.accounts.pool.status != PoolStatus::Ended
-            && ctx.accounts.pool.status != PoolStatus::Cancelled
-            && ctx.accounts.pool.status != PoolStatus::Closed,
+        accounts.pool.status != PoolStatus::Ended
+            && accounts.pool.status != PoolStatus::Cancelled
+            && accounts.pool.status != PoolStatus::Closed,
         ErrorCode::AlreadyEnded
     );
 
-    if ctx.accounts.pool.randomness_commit_slot != 0 {
+    if accounts.pool.randomness_commit_slot != 0 {
         require!(
-            now.slot <= ctx.accounts.pool.randomness_commit_slot + 3000,
+            now.slot <= accounts.pool.randomness_commit_slot + 3000,
             ErrorCode::RandomnessExpired
         );
     }
 
-    let is_timeout = now_ts > ctx.accounts.pool.unlock_time + PAYOUT_TIMEOUT;
-    if !is_timeout {
-        require_keys_eq!(ctx.accounts.user.key(), ctx.accounts.pool.dev_wallet, ErrorCode::Unauthorized);
-    }
-
     require!(
         matches!(
-            ctx.accounts.pool.status,
+            accounts.pool.status,
             PoolStatus::Unlocked | PoolStatus::RandomnessCommitted | PoolStatus::RandomnessRevealed
         ),
         ErrorCode::InvalidPoolStatus
     );
 
-    let participant_count = ctx.accounts.participants.count as u64;
+    let participant_count = accounts.participants.count as u64;
     require!(participant_count > 0, ErrorCode::NoParticipants);
 
-    let pool_id = ctx.accounts.pool.pool_id;
+    let pool_id = accounts.pool.pool_id;
 
     // config hash check
-    let mut hasher = sha2::Sha256::new();
-    hasher.update(ctx.accounts.pool.salt);
-    hasher.update(ctx.accounts.pool.max_participants.to_le_bytes());
-    hasher.update(ctx.accounts.pool.lock_duration.to_le_bytes());
-    hasher.update(ctx.accounts.pool.amount.to_le_bytes());
-    hasher.update(ctx.accounts.pool.dev_wallet.as_ref());
-    hasher.update(ctx.accounts.pool.dev_fee_bps.to_le_bytes());
-    hasher.update(ctx.accounts.pool.burn_fee_bps.to_le_bytes());
-    hasher.update(ctx.accounts.pool.treasury_wallet.as_ref());
-    hasher.update(ctx.accounts.pool.treasury_fee_bps.to_le_bytes());
-    hasher.update(ctx.accounts.pool.start_time.to_le_bytes());
-    hasher.update(ctx.accounts.pool.duration.to_le_bytes());
-    let current_hash: [u8; 32] = hasher.finalize().into();
-    require!(current_hash == ctx.accounts.pool.config_hash, ErrorCode::ConfigMismatch);
-
-    let (randomness_u128, normalized): (u128, u64) =
-        if ctx.accounts.pool.allow_mock && ctx.accounts.pool.randomness_account == Pubkey::default() {
-            let mock_u128 = ctx.accounts.pool.randomness;
-            require!(mock_u128 != 0, ErrorCode::RandomnessNotCommitted);
-
-            let normalized = {
-                let mut hasher = sha2::Sha256::new();
-                hasher.update(pool_id.to_le_bytes());
-                hasher.update(&mock_u128.to_le_bytes());
-                let hash = hasher.finalize();
-                u64::from_le_bytes(hash[0..8].try_into().unwrap())
-            };
-
-            ctx.accounts.pool.status = PoolStatus::RandomnessRevealed;
-            (mock_u128, normalized)
-        } else {
-            if !ctx.accounts.pool.allow_mock {
-                require_keys_eq!(
-                    ctx.accounts.randomness.owner.key(),
-                    SWITCHBOARD_ID,
-                    ErrorCode::InvalidRandomnessAccount
-                );
-                require_keys_eq!(
-                    ctx.accounts.randomness.key(),
-                    ctx.accounts.pool.randomness_account,
-                    ErrorCode::InvalidRandomnessAccount
-                );
-            }
-
-            let randomness_data = RandomnessAccountData::parse(ctx.accounts.randomness.data.borrow())
+    require!(
+        accounts.pool.compute_config_hash() == accounts.pool.config_hash,
+        ErrorCode::ConfigMismatch
+    );
+
+    #[cfg(feature = "test-utils")]
+    let mock = crate::mock::resolve_mock_randomness(
+        &mut accounts.pool,
+        &accounts.randomness,
+        accounts.user.key(),
+        now,
+    )?;
+    #[cfg(not(feature = "test-utils"))]
+    let mock: Option<(u128, bool)> = None;
+
+    let (randomness_u128, is_emergency) = match mock {
+        Some(resolved) => resolved,
+        None => {
+            require_keys_eq!(
+                accounts.randomness.owner.key(),
+                SWITCHBOARD_ID,
+                ErrorCode::InvalidRandomnessAccount
+            );
+            require_keys_eq!(
+                accounts.randomness.key(),
+                accounts.pool.randomness_account,
+                ErrorCode::InvalidRandomnessAccount
+            );
+
+            let randomness_data = RandomnessAccountData::parse(accounts.randomness.data.borrow())
                 .map_err(|_| ErrorCode::InvalidRandomness)?;
 
-            if !ctx.accounts.pool.allow_mock {
-                require!(randomness_data.seed_slot != 0, ErrorCode::RandomnessNotCommitted);
-            }
-
-            let mut is_emergency = false;
-            let randomness_u128: u128;
-            let normalized: u64;
-
-            if randomness_data.reveal_slot == 0 {
-                require!(ctx.accounts.pool.allow_mock, ErrorCode::InvalidRandomness);
-                require!(
-                    now.unix_timestamp > ctx.accounts.pool.unlock_time + EMERGENCY_DELAY,
-                    ErrorCode::TooEarlyForEmergency
-                );
-
-                let caller = ctx.accounts.user.key();
-                let allowed = caller == ctx.accounts.pool.dev_wallet || caller == ctx.accounts.pool.creator;
-                require!(allowed, ErrorCode::Unauthorized);
-
-                let mock_randomness = {
-                    let mut hasher = sha2::Sha256::new();
-                    hasher.update(ctx.accounts.pool.pool_id.to_le_bytes());
-                    hasher.update(now.slot.to_le_bytes());
-                    hasher.update(ctx.accounts.pool.creator.as_ref());
-                    let hash = hasher.finalize();
-                    let mut bytes = [0u8; 16];
-                    bytes.copy_from_slice(&hash[..16]);
-                    u128::from_le_bytes(bytes)
-                };
-
-                randomness_u128 = mock_randomness;
-
-                normalized = {
-                    let mut hasher = sha2::Sha256::new();
-                    hasher.update(pool_id.to_le_bytes());
-                    hasher.update(&mock_randomness.to_le_bytes());
-                    let hash = hasher.finalize();
-                    u64::from_le_bytes(hash[0..8].try_into().unwrap())
-                };
-
-                ctx.accounts.pool.randomness_account = ZERO_PUBKEY;
-                is_emergency = true;
-            } else {
-                if !ctx.accounts.pool.allow_mock {
-                    require!(
-                        !randomness_data.value.iter().all(|&x| x == 0),
-                        ErrorCode::RandomnessNotResolved
-                    );
-                }
-                require!(randomness_data.value != [0u8; 32], ErrorCode::RandomnessNotResolved);
-
-                randomness_u128 = u128::from_le_bytes(randomness_data.value[0..16].try_into().unwrap());
-
-                normalized = {
-                    let mut hasher = sha2::Sha256::new();
-                    hasher.update(pool_id.to_le_bytes());
-                    hasher.update(&randomness_data.value[0..16]);
-                    let hash = hasher.finalize();
-                    u64::from_le_bytes(hash[0..8].try_into().unwrap())
-                };
-            }
-
-            ctx.accounts.pool.status = PoolStatus::RandomnessRevealed;
-
-            if is_emergency {
-                emit!(PoolActivityEvent {
-                    pool_id: ctx.accounts.pool.key(),
-                    numerical_pool_id: pool_id,
-                    action: ActionType::EmergencyReveal,
-                    amount: 0,
-                    participant_rank: 0,
-                    dev_fee_percent: ctx.accounts.pool.dev_fee_bps,
-                    burn_fee_percent: ctx.accounts.pool.burn_fee_bps,
-                    treasury_fee_percent: ctx.accounts.pool.treasury_fee_bps,
-                });
-            }
-
-            (randomness_u128, normalized)
-        };
+            require!(randomness_data.seed_slot != 0, ErrorCode::RandomnessNotCommitted);
+            require!(randomness_data.reveal_slot != 0, ErrorCode::InvalidRandomness);
+            require!(randomness_data.value != [0u8; 32], ErrorCode::RandomnessNotResolved);
+
+            (u128::from_le_bytes(randomness_data.value[0..16].try_into().unwrap()), false)
+        }
+    };
+
+    let normalized = {
+        let mut hasher = sha2::Sha256::new();
+        hasher.update(pool_id.to_le_bytes());
+        hasher.update(randomness_u128.to_le_bytes());
+        let hash = hasher.finalize();
+        u64::from_le_bytes(hash[0..8].try_into().unwrap())
+    };
+
+    accounts.pool.status = PoolStatus::RandomnessRevealed;
+
+    if is_emergency {
+        emit!(PoolActivityEvent {
+            pool_id: accounts.pool.key(),
+            numerical_pool_id: pool_id,
+            action: ActionType::EmergencyReveal,
+            amount: 0,
+            participant_rank: 0,
+            dev_fee_percent: accounts.pool.dev_fee_bps,
+            burn_fee_percent: accounts.pool.burn_fee_bps,
+            treasury_fee_percent: accounts.pool.treasury_fee_bps,
+        });
+    }
 
-    let winner_index = (normalized % participant_count) as usize;
-    require!(
-        winner_index < ctx.accounts.participants.count as usize,
-        ErrorCode::InvalidWinnerAccount
-    );
+    Ok((randomness_u128, normalized))
+}
 
-    let winner_pubkey = ctx.accounts.participants.list[winner_index];
+/// Records the winner and closes the critical section opened by `resolve_randomness`
+pub(crate) fn finish_selection(
+    accounts: &mut SelectWinner,
+    winner_pubkey: Pubkey,
+    randomness_u128: u128,
+) -> Result<()> {
+    let pool_id = accounts.pool.pool_id;
 
-    ctx.accounts.pool.winner = winner_pubkey;
-    ctx.accounts.pool.randomness = randomness_u128;
-    ctx.accounts.pool.status = PoolStatus::WinnerSelected;
-    ctx.accounts.pool.status_reason = 0;
+    accounts.pool.winner = winner_pubkey;
+    accounts.pool.randomness = randomness_u128;
+    accounts.pool.status = PoolStatus::WinnerSelected;
+    accounts.pool.status_reason = 0;
 
     // 🔒 End critical section
-    ctx.accounts.pool.end_processing();
+    accounts.pool.end_processing();
 
-    let participants_count_u8 = ctx.accounts.participants.count;
+    let participants_count_u8 = accounts.participants.count;
 
     emit!(PoolStateEvent {
-        pool_id: ctx.accounts.pool.key(),
+        pool_id: accounts.pool.key(),
         numerical_pool_id: pool_id,
         status: PoolStatus::WinnerSelected,
         participant_count: participants_count_u8,
-        total_amount: ctx.accounts.pool.total_amount,
+        total_amount: accounts.pool.total_amount,
         status_reason: 0,
     });
 
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    compression::{entry_leaf, verify_leaf},
+    constants::*,
+    errors::ErrorCode,
+    instructions::select_winner::{finish_selection, resolve_randomness, SelectWinner},
+};
+
+#[derive(Accounts)]
+pub struct SelectWinnerCompressed<'info> {
+    pub draw: SelectWinner<'info>,
+
+    /// CHECK: the pool's concurrent Merkle tree
+    #[account(address = draw.pool.merkle_tree @ ErrorCode::NotCompressedPool)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: checked against ACCOUNT_COMPRESSION_ID in the CPI helper
+    pub compression_program: UncheckedAccount<'info>,
+    // remaining_accounts: proof nodes for the drawn leaf
+}
+
+/// `select_winner` for compressed pools. The caller supplies the drawn leaf's contents and a
+/// proof against a recent `root`; the leaf index itself comes from the randomness.
+pub fn select_winner_compressed<'info>(
+    ctx: Context<'_, '_, '_, 'info, SelectWinnerCompressed<'info>>,
+    root: [u8; 32],
+    winner: Pubkey,
+    winner_amount: u64,
+) -> Result<()> {
+    let now = Clock::get()?;
+    let draw = &mut ctx.accounts.draw;
+
+    require!(draw.pool.is_compressed(), ErrorCode::NotCompressedPool);
+
+    let is_timeout = now.unix_timestamp > draw.pool.unlock_time + PAYOUT_TIMEOUT;
+    if !is_timeout {
+        require_keys_eq!(draw.user.key(), draw.pool.dev_wallet, ErrorCode::Unauthorized);
+    }
+
+    let (randomness_u128, normalized) = resolve_randomness(draw, &now)?;
+
+    let index = (normalized % draw.pool.entry_count as u64) as u32;
+    let leaf = entry_leaf(&draw.pool.key(), &winner, winner_amount, index);
+
+    verify_leaf(
+        &ctx.accounts.compression_program,
+        &ctx.accounts.merkle_tree,
+        root,
+        leaf,
+        index,
+        ctx.remaining_accounts,
+    )?;
+
+    finish_selection(&mut ctx.accounts.draw, winner, randomness_u128)
+}
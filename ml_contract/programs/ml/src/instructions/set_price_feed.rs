@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+use crate::{constants::*, errors::ErrorCode, state::PriceFeedConfig};
+
+#[derive(Accounts)]
+pub struct SetPriceFeed<'info> {
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + PriceFeedConfig::INIT_SPACE,
+        seeds = [b"price_feed", mint.key().as_ref()],
+        bump
+    )]
+    pub price_feed: Account<'info, PriceFeedConfig>,
+
+    #[account(mut, address = PLATFORM_ADMIN @ ErrorCode::Unauthorized)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_price_feed(ctx: Context<SetPriceFeed>, feed_id: [u8; 32], max_age: u64) -> Result<()> {
+    require!(feed_id != [0u8; 32], ErrorCode::StalePrice);
+    require!(max_age > 0, ErrorCode::StalePrice);
+
+    let price_feed = &mut ctx.accounts.price_feed;
+    price_feed.mint = ctx.accounts.mint.key();
+    price_feed.feed_id = feed_id;
+    price_feed.max_age = max_age;
+    price_feed.bump = ctx.bumps.price_feed;
+
+    Ok(())
+}
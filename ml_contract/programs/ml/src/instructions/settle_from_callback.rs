@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+
+use crate::{
+    constants::*,
+    errors::ErrorCode,
+    instructions::select_winner::{draw_winner, SelectWinner},
+};
+
+// Anchor discriminator of Switchboard's `randomness_reveal`: sha256("global:randomness_reveal")[..8]
+const IX_RANDOMNESS_REVEAL: [u8; 8] = [197, 181, 187, 10, 30, 58, 20, 73];
+
+#[derive(Accounts)]
+pub struct SettleFromCallback<'info> {
+    pub settle: SelectWinner<'info>,
+
+    /// CHECK: instructions sysvar, used to find the Switchboard reveal in this transaction
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
+
+/// Permissionless winner selection, bundled right after the Switchboard `randomness_reveal`
+/// instruction so the oracle fulfillment transaction settles the pool without a dev crank.
+pub fn settle_from_callback(ctx: Context<SettleFromCallback>) -> Result<()> {
+    let ix_sysvar = ctx.accounts.instructions_sysvar.to_account_info();
+    let current = load_current_index_checked(&ix_sysvar)?;
+    require!(current > 0, ErrorCode::InvalidRandomness);
+
+    // The preceding instruction must be the Switchboard reveal for this pool's randomness account
+    let reveal = load_instruction_at_checked((current - 1) as usize, &ix_sysvar)?;
+    require_keys_eq!(reveal.program_id, SWITCHBOARD_ID, ErrorCode::InvalidRandomnessAccount);
+    // Commit and close touch the same account; only the reveal sets the value being drawn
+    require!(
+        reveal.data.starts_with(&IX_RANDOMNESS_REVEAL),
+        ErrorCode::InvalidRandomnessAccount
+    );
+    let randomness_key = ctx.accounts.settle.pool.randomness_account;
+    require!(
+        reveal.accounts.iter().any(|meta| meta.pubkey == randomness_key),
+        ErrorCode::InvalidRandomnessAccount
+    );
+
+    let now = Clock::get()?;
+    draw_winner(&mut ctx.accounts.settle, &now)
+}
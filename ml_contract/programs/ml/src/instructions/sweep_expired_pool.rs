@@ -27,18 +27,21 @@ pub struct SweepExpiredPool<'info> {
 }
 
 pub fn sweep_expired_pool(ctx: Context<SweepExpiredPool>) -> Result<()> {
-    ctx.accounts.pool.assert_not_paused()?;
-
     let now = Clock::get()?.unix_timestamp;
     let pool = &mut ctx.accounts.pool;
 
+    #[cfg(feature = "test-utils")]
     let can_force = pool.allow_mock;
+    #[cfg(not(feature = "test-utils"))]
+    let can_force = false;
     let too_early = now <= pool.expire_time + SWEEP_DELAY;
-    if too_early && !can_force {
-        return err!(ErrorCode::PoolNotExpired);
+    if too_early {
+        // Early (mock) sweeps stay dev-only
+        require!(can_force, ErrorCode::PoolNotExpired);
+        require!(ctx.accounts.user.key() == pool.dev_wallet, ErrorCode::NotDeveloper);
+        pool.assert_not_paused()?;
     }
-
-    require!(ctx.accounts.user.key() == pool.dev_wallet, ErrorCode::NotDeveloper);
+    // ✅ Past expire_time + SWEEP_DELAY anyone may cancel, paused or not, so refunds never wait on the dev
     pool.assert_open()?;
 
     pool.status = PoolStatus::Cancelled;
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::ErrorCode,
+    events::*,
+    state::{Participants, Pool, PoolStatus},
+};
+
+#[derive(Accounts)]
+pub struct TransferEntry<'info> {
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        mut,
+        seeds = [b"participants", pool.key().as_ref()],
+        bump,
+        constraint = participants.key() == pool.participants_account @ ErrorCode::InvalidParticipantsPda
+    )]
+    pub participants: Account<'info, Participants>,
+
+    /// Current holder of the entry
+    pub user: Signer<'info>,
+
+    /// CHECK: any wallet or PDA; becomes the refund and prize recipient for the slot
+    pub new_owner: UncheckedAccount<'info>,
+}
+
+/// Reassigns the caller's `Participants` slot to `new_owner` before the draw.
+/// Payouts and refunds are keyed off the slot, so the prize destination follows.
+/// Ticket cNFTs are receipts only and move independently through Bubblegum.
+/// The creator's slot stays put: `claim_refund` charges the creator penalty to
+/// whoever signs as `pool.creator`, so handing it off would dodge the burn.
+pub fn transfer_entry(ctx: Context<TransferEntry>) -> Result<()> {
+    let pool = &ctx.accounts.pool;
+
+    require!(pool.initialized, ErrorCode::UninitializedAccount);
+    pool.assert_not_paused()?;
+    pool.assert_not_processing()?;
+    require!(!pool.is_compressed(), ErrorCode::CompressedPool);
+
+    // Only before randomness is committed
+    require!(
+        matches!(pool.status, PoolStatus::Open | PoolStatus::Locked | PoolStatus::Unlocked),
+        ErrorCode::InvalidPoolStatus
+    );
+
+    let from = ctx.accounts.user.key();
+    let to = ctx.accounts.new_owner.key();
+    require!(to != Pubkey::default() && to != from, ErrorCode::InvalidEntryRecipient);
+    require!(from != pool.creator, ErrorCode::CreatorEntryNotTransferable);
+
+    let participants = &mut ctx.accounts.participants;
+    let count = participants.count as usize;
+
+    let index = (0..count)
+        .find(|&i| participants.list[i] == from)
+        .ok_or(ErrorCode::NotParticipant)?;
+
+    // One slot per wallet, as in join_pool
+    require!(
+        (0..count).all(|i| participants.list[i] != to),
+        ErrorCode::AlreadyParticipated
+    );
+
+    participants.list[index] = to;
+
+    emit!(EntryTransferred {
+        pool_id: pool.key(),
+        from,
+        to,
+        index: index as u8,
+        amount: participants.amounts[index],
+    });
+
+    Ok(())
+}
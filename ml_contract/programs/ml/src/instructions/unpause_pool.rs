@@ -15,6 +15,17 @@ pub fn unpause_pool(ctx: Context<PausePool>) -> Result<()> {
             && ctx.accounts.pool.status != PoolStatus::Closed,
         ErrorCode::InvalidPoolStatus
     );
+    // ⏸️ Extend the lock by the time spent paused inside it
+    let pool = &mut ctx.accounts.pool;
+    if pool.paused_at != 0 {
+        let now = Clock::get()?.unix_timestamp;
+        let paused_for = now.saturating_sub(pool.paused_at).max(0);
+        pool.paused_duration = pool
+            .paused_duration
+            .checked_add(paused_for)
+            .ok_or(ErrorCode::Overflow)?;
+        pool.paused_at = 0;
+    }
     ctx.accounts.pool.paused = false;
     ctx.accounts.pool.status_reason = 0;
     let participants_count = ctx.accounts.participants.count;
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::ErrorCode,
+    events::*,
+    state::{AdminCloseProposal, Pool},
+};
+
+#[derive(Accounts)]
+pub struct WithdrawAdminClose<'info> {
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"admin_close", pool.key().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Box<Account<'info, AdminCloseProposal>>,
+
+    #[account(mut, address = pool.dev_wallet @ ErrorCode::NotDeveloper)]
+    pub user: Signer<'info>,
+}
+
+pub fn withdraw_admin_close(ctx: Context<WithdrawAdminClose>) -> Result<()> {
+    emit!(AdminCloseWithdrawn {
+        pool_id: ctx.accounts.pool.key(),
+        reason_code: ctx.accounts.proposal.reason_code,
+    });
+
+    Ok(())
+}
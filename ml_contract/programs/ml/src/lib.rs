@@ -2,13 +2,21 @@ use anchor_lang::prelude::*;
 
 declare_id!("4wgBJUHydWXXJKXYsmdGoGw1ufC3dxz8q2mukFYaAhSm");
 
+pub mod compression;
 pub mod constants;
 pub mod errors;
 pub mod events;
 pub mod instructions;
 pub mod state;
+pub mod tickets;
 pub mod utils;
 
+#[cfg(feature = "test-utils")]
+pub mod mock;
+
+#[cfg(all(feature = "mainnet", feature = "test-utils"))]
+compile_error!("`test-utils` must never be enabled together with `mainnet`");
+
 // ✅ Anchor 0.31: #[program] caută crate::__client_accounts_*
 // Dar în submodule ele sunt doar pub(crate), deci NU le re-exportăm public,
 // ci le aducem la crate root cu pub(crate) use.
@@ -16,25 +24,51 @@ pub(crate) use instructions::admin_close_pool::__client_accounts_admin_close_poo
 pub(crate) use instructions::cancel_pool::__client_accounts_cancel_pool;
 pub(crate) use instructions::claim_refund::__client_accounts_claim_refund;
 pub(crate) use instructions::claim_rent::__client_accounts_claim_rent;
+pub(crate) use instructions::clone_pool::__client_accounts_clone_pool;
 pub(crate) use instructions::create_pool::__client_accounts_create_pool;
+pub(crate) use instructions::deposit_prize_nft::__client_accounts_deposit_prize_nft;
 pub(crate) use instructions::donate::__client_accounts_donate;
 pub(crate) use instructions::finalize_forfeited_pool::__client_accounts_forfeit_unclaimed;
+#[cfg(feature = "test-utils")]
 pub(crate) use instructions::force_expire::__client_accounts_force_expire;
 pub(crate) use instructions::join_pool::__client_accounts_join_pool;
 pub(crate) use instructions::pause_pool::__client_accounts_pause_pool;
 pub(crate) use instructions::payout_winner::__client_accounts_payout_winner;
+pub(crate) use instructions::release_prize_nft::__client_accounts_release_prize_nft;
 pub(crate) use instructions::request_randomness::__client_accounts_request_randomness;
 pub(crate) use instructions::select_winner::__client_accounts_select_winner;
 pub(crate) use instructions::set_lock_duration::__client_accounts_set_lock_duration;
+pub(crate) use instructions::set_price_feed::__client_accounts_set_price_feed;
 pub(crate) use instructions::sweep_expired_pool::__client_accounts_sweep_expired_pool;
 pub(crate) use instructions::unlock_pool::__client_accounts_unlock_pool;
+pub(crate) use instructions::create_team::__client_accounts_create_team;
+pub(crate) use instructions::contribute_team::__client_accounts_contribute_team;
+pub(crate) use instructions::enter_team::__client_accounts_enter_team;
+pub(crate) use instructions::claim_team_refund::__client_accounts_claim_team_refund;
+pub(crate) use instructions::distribute_team_prize::__client_accounts_distribute_team_prize;
+pub(crate) use instructions::propose_admin_close::__client_accounts_propose_admin_close;
+pub(crate) use instructions::withdraw_admin_close::__client_accounts_withdraw_admin_close;
+pub(crate) use instructions::settle_from_callback::__client_accounts_settle_from_callback;
+pub(crate) use instructions::enable_compressed_entries::__client_accounts_enable_compressed_entries;
+pub(crate) use instructions::join_compressed::__client_accounts_join_compressed;
+pub(crate) use instructions::select_winner_compressed::__client_accounts_select_winner_compressed;
+pub(crate) use instructions::claim_compressed_refund::__client_accounts_claim_compressed_refund;
+pub(crate) use instructions::enable_tickets::__client_accounts_enable_tickets;
+pub(crate) use instructions::transfer_entry::__client_accounts_transfer_entry;
+pub(crate) use instructions::lock_at_deadline::__client_accounts_lock_at_deadline;
 
 // Accounts types “flat”
 use crate::instructions::{
-    AdminClosePool, CancelPool, ClaimRefund, ClaimRent, CreatePool, Donate, ForceExpire,
-    ForfeitUnclaimed, JoinPool, PayoutWinner, PausePool, RequestRandomness, SelectWinner,
-    SetLockDuration, SweepExpiredPool, UnlockPool,
+    AdminClosePool, CancelPool, ClaimCompressedRefund, ClaimRefund, ClaimRent, ClaimTeamRefund,
+    ClonePool, ContributeTeam, CreatePool, CreateTeam, DepositPrizeNft, DistributeTeamPrize, Donate,
+    EnableCompressedEntries, EnableTickets, EnterTeam, ForfeitUnclaimed, JoinCompressed, JoinPool,
+    LockAtDeadline, PausePool, PayoutWinner, ProposeAdminClose, ReleasePrizeNft, RequestRandomness,
+    SelectWinner, SelectWinnerCompressed, SetLockDuration, SetPriceFeed, SettleFromCallback,
+    SweepExpiredPool, TransferEntry, UnlockPool, WithdrawAdminClose,
 };
+#[cfg(feature = "test-utils")]
+use crate::instructions::ForceExpire;
+use crate::state::DonationPurpose;
 
 #[program]
 pub mod ml {
@@ -52,6 +86,10 @@ pub mod ml {
         treasury_wallet: Pubkey,
         treasury_fee_bps: u16,
         allow_mock: bool,
+        end_amount: u64,
+        min_bet_override: u64,
+        max_donations_amount: u64,
+        join_deadline: i64,
     ) -> Result<()> {
         crate::instructions::create_pool(
             ctx,
@@ -65,6 +103,10 @@ pub mod ml {
             treasury_wallet,
             treasury_fee_bps,
             allow_mock,
+            end_amount,
+            min_bet_override,
+            max_donations_amount,
+            join_deadline,
         )
     }
 
@@ -72,8 +114,8 @@ pub mod ml {
         crate::instructions::join_pool(ctx, amount)
     }
 
-    pub fn donate(ctx: Context<Donate>, amount: u64) -> Result<()> {
-        crate::instructions::donate(ctx, amount)
+    pub fn donate(ctx: Context<Donate>, amount: u64, purpose: DonationPurpose) -> Result<()> {
+        crate::instructions::donate(ctx, amount, purpose)
     }
 
     pub fn set_lock_duration(ctx: Context<SetLockDuration>, new_lock_duration: i64) -> Result<()> {
@@ -124,6 +166,7 @@ pub mod ml {
         crate::instructions::unpause_pool(ctx)
     }
 
+    #[cfg(feature = "test-utils")]
     pub fn force_expire(ctx: Context<ForceExpire>) -> Result<()> {
         crate::instructions::force_expire(ctx)
     }
@@ -131,4 +174,106 @@ pub mod ml {
     pub fn finalize_forfeited_pool(ctx: Context<ForfeitUnclaimed>) -> Result<()> {
         crate::instructions::finalize_forfeited_pool(ctx)
     }
+
+    pub fn deposit_prize_nft(ctx: Context<DepositPrizeNft>) -> Result<()> {
+        crate::instructions::deposit_prize_nft(ctx)
+    }
+
+    pub fn release_prize_nft(ctx: Context<ReleasePrizeNft>) -> Result<()> {
+        crate::instructions::release_prize_nft(ctx)
+    }
+
+    pub fn clone_pool(ctx: Context<ClonePool>, salt: [u8; 32]) -> Result<()> {
+        crate::instructions::clone_pool(ctx, salt)
+    }
+
+    pub fn set_price_feed(ctx: Context<SetPriceFeed>, feed_id: [u8; 32], max_age: u64) -> Result<()> {
+        crate::instructions::set_price_feed(ctx, feed_id, max_age)
+    }
+
+    pub fn create_team(ctx: Context<CreateTeam>) -> Result<()> {
+        crate::instructions::create_team(ctx)
+    }
+
+    pub fn contribute_team(ctx: Context<ContributeTeam>, amount: u64) -> Result<()> {
+        crate::instructions::contribute_team(ctx, amount)
+    }
+
+    pub fn enter_team(ctx: Context<EnterTeam>, amount: u64) -> Result<()> {
+        crate::instructions::enter_team(ctx, amount)
+    }
+
+    pub fn claim_team_refund(ctx: Context<ClaimTeamRefund>) -> Result<()> {
+        crate::instructions::claim_team_refund(ctx)
+    }
+
+    pub fn distribute_team_prize<'info>(
+        ctx: Context<'_, '_, '_, 'info, DistributeTeamPrize<'info>>,
+    ) -> Result<()> {
+        crate::instructions::distribute_team_prize(ctx)
+    }
+
+    pub fn propose_admin_close(
+        ctx: Context<ProposeAdminClose>,
+        reason_code: u8,
+        uri: String,
+        slash_bond: bool,
+    ) -> Result<()> {
+        crate::instructions::propose_admin_close(ctx, reason_code, uri, slash_bond)
+    }
+
+    pub fn withdraw_admin_close(ctx: Context<WithdrawAdminClose>) -> Result<()> {
+        crate::instructions::withdraw_admin_close(ctx)
+    }
+
+    pub fn settle_from_callback(ctx: Context<SettleFromCallback>) -> Result<()> {
+        crate::instructions::settle_from_callback(ctx)
+    }
+
+    pub fn enable_compressed_entries(
+        ctx: Context<EnableCompressedEntries>,
+        max_depth: u32,
+        max_buffer_size: u32,
+        max_entries: u32,
+    ) -> Result<()> {
+        crate::instructions::enable_compressed_entries(ctx, max_depth, max_buffer_size, max_entries)
+    }
+
+    pub fn join_compressed(ctx: Context<JoinCompressed>, amount: u64) -> Result<()> {
+        crate::instructions::join_compressed(ctx, amount)
+    }
+
+    pub fn select_winner_compressed<'info>(
+        ctx: Context<'_, '_, '_, 'info, SelectWinnerCompressed<'info>>,
+        root: [u8; 32],
+        winner: Pubkey,
+        winner_amount: u64,
+    ) -> Result<()> {
+        crate::instructions::select_winner_compressed(ctx, root, winner, winner_amount)
+    }
+
+    pub fn claim_compressed_refund<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimCompressedRefund<'info>>,
+        root: [u8; 32],
+        amount: u64,
+        index: u32,
+    ) -> Result<()> {
+        crate::instructions::claim_compressed_refund(ctx, root, amount, index)
+    }
+
+    pub fn enable_tickets(
+        ctx: Context<EnableTickets>,
+        max_depth: u32,
+        max_buffer_size: u32,
+    ) -> Result<()> {
+        crate::instructions::enable_tickets(ctx, max_depth, max_buffer_size)
+    }
+
+    pub fn transfer_entry(ctx: Context<TransferEntry>) -> Result<()> {
+        crate::instructions::transfer_entry(ctx)
+    }
+
+    pub fn lock_at_deadline(ctx: Context<LockAtDeadline>) -> Result<()> {
+        crate::instructions::lock_at_deadline(ctx)
+    }
 }
@@ -0,0 +1,90 @@
+//! Mock randomness for localnet / devnet testing.
+//!
+//! Compiled only with the `test-utils` feature: mainnet builds contain none of
+//! these code paths, so `pool.allow_mock` can never be honoured on-chain there.
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use sha2::Digest;
+use switchboard_on_demand::RandomnessAccountData;
+
+use crate::{
+    constants::*,
+    errors::ErrorCode,
+    state::{Pool, PoolStatus},
+};
+
+/// Deterministic pseudo-randomness from pool id, slot and creator
+pub fn derive_mock_randomness(pool: &Pool, slot: u64) -> u128 {
+    let mut hasher = sha2::Sha256::new();
+    hasher.update(pool.pool_id.to_le_bytes());
+    hasher.update(slot.to_le_bytes());
+    hasher.update(pool.creator.as_ref());
+    let hash = hasher.finalize();
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&hash[..16]);
+    u128::from_le_bytes(bytes)
+}
+
+/// Commits mock randomness when the pool allows it and the caller passed
+/// the default pubkey or the System Program as the randomness account.
+/// Returns `false` when the real Switchboard flow must be used.
+pub fn commit_mock_randomness(pool: &mut Pool, randomness_key: Pubkey, slot: u64) -> Result<bool> {
+    let is_mock = pool.allow_mock
+        && (randomness_key == Pubkey::default() || randomness_key == system_program::ID);
+    if !is_mock {
+        return Ok(false);
+    }
+
+    require!(pool.randomness_account == ZERO_PUBKEY, ErrorCode::RandomnessAlreadySet);
+
+    pool.randomness = derive_mock_randomness(pool, slot);
+    pool.randomness_account = Pubkey::default();
+    pool.status = PoolStatus::RandomnessCommitted;
+
+    Ok(true)
+}
+
+/// Resolves winner randomness for mock pools: a committed mock value,
+/// a relaxed Switchboard read, or an emergency reveal once the oracle has
+/// failed to reveal for `EMERGENCY_DELAY`. Returns `(randomness, is_emergency)`,
+/// or `None` for pools that must go through the strict Switchboard path.
+pub fn resolve_mock_randomness(
+    pool: &mut Pool,
+    randomness: &AccountInfo,
+    caller: Pubkey,
+    now: &Clock,
+) -> Result<Option<(u128, bool)>> {
+    if !pool.allow_mock {
+        return Ok(None);
+    }
+
+    if pool.randomness_account == Pubkey::default() {
+        require!(pool.randomness != 0, ErrorCode::RandomnessNotCommitted);
+        return Ok(Some((pool.randomness, false)));
+    }
+
+    let randomness_data = RandomnessAccountData::parse(randomness.data.borrow())
+        .map_err(|_| ErrorCode::InvalidRandomness)?;
+
+    if randomness_data.reveal_slot != 0 {
+        require!(randomness_data.value != [0u8; 32], ErrorCode::RandomnessNotResolved);
+        let value = u128::from_le_bytes(randomness_data.value[0..16].try_into().unwrap());
+        return Ok(Some((value, false)));
+    }
+
+    // Oracle never revealed - emergency mock reveal
+    require!(
+        now.unix_timestamp > pool.unlock_time + EMERGENCY_DELAY,
+        ErrorCode::TooEarlyForEmergency
+    );
+    require!(
+        caller == pool.dev_wallet || caller == pool.creator,
+        ErrorCode::Unauthorized
+    );
+
+    let value = derive_mock_randomness(pool, now.slot);
+    pool.randomness_account = ZERO_PUBKEY;
+
+    Ok(Some((value, true)))
+}
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 use crate::constants::*;
 use crate::errors::ErrorCode;
+use sha2::Digest;
 
 #[account]
 #[derive(InitSpace)]
@@ -47,6 +48,43 @@ pub struct Pool {
     pub winner: Pubkey,
     /// 🔒 Reentrancy guard - prevents concurrent critical operations
     pub processing: bool,
+    /// Entry price at `expire_time`; equal to `amount` for flat-priced pools
+    pub end_amount: u64,
+    /// Escrowed prize NFT mint (ZERO_PUBKEY for token-prize pools)
+    pub prize_mint: Pubkey,
+    /// Pool-owned token account holding the prize NFT
+    pub prize_token: Pubkey,
+    pub prize_released: bool,
+    /// USD floor (micro-USD) enforced at creation; 0 = token-denominated MIN_BET_TOKENS
+    pub min_bet_usd: u64,
+    /// Position of this pool among the creator's pools (see `CreatorPoolIndex`)
+    pub creator_index: u64,
+    /// Donations earmarked for the winner (fee-free)
+    pub donated_prize: u64,
+    /// Donations earmarked for the burn share
+    pub donated_burn: u64,
+    /// Anti-spam bond (lamports) held in this account; returned with the rent on `claim_rent`
+    pub creator_bond: u64,
+    /// Token-denominated minimum entry (native units); 0 for USD-denominated pools
+    pub min_bet_native: u64,
+    /// Concurrent Merkle tree holding entries (ZERO_PUBKEY for array-backed pools)
+    pub merkle_tree: Pubkey,
+    /// Entry cap for compressed pools (leaf 0 is the creator's entry)
+    pub max_entries: u32,
+    pub entry_count: u32,
+    pub entries_refunded: u32,
+    /// Bubblegum tree receiving cNFT entry tickets (ZERO_PUBKEY = tickets off)
+    pub ticket_tree: Pubkey,
+    /// Start of the current pause if it began during the lock window (0 otherwise)
+    pub paused_at: i64,
+    /// Cumulative seconds paused during the lock window; extends the lock
+    pub paused_duration: i64,
+    /// Cap on the sum of all donations (native units); 0 = uncapped
+    pub max_donations_amount: u64,
+    /// Sum of all donations so far, whatever their purpose
+    pub total_donated: u64,
+    /// Last moment entries are accepted; past it the pool takes donations again once `lock_at_deadline` locks it
+    pub join_deadline: i64,
 }
 
 impl Pool {
@@ -74,12 +112,18 @@ impl Pool {
         self.status == PoolStatus::Open && !self.paused && !self.is_expired(now)
     }
 
+    /// End of the lock window, pushed back by time spent paused while locked
+    pub fn lock_end_time(&self) -> Result<i64> {
+        ml_core::lock_end_time(self.lock_start_time, self.lock_duration, self.paused_duration)
+            .ok_or_else(|| error!(ErrorCode::Overflow))
+    }
+
     pub fn is_locked(&self, now: i64) -> bool {
         self.lock_start_time != 0 && now >= self.lock_start_time
     }
 
     pub fn can_join(&self, now: i64) -> bool {
-        self.is_active(now) && !self.is_locked(now)
+        self.is_active(now) && !self.is_locked(now) && now <= self.join_deadline
     }
 
     pub fn can_join_status(&self) -> bool {
@@ -88,10 +132,11 @@ impl Pool {
 
     pub fn can_donate(&self, now: i64) -> Result<()> {
         if self.status == PoolStatus::Open {
+            // Past the deadline an open pool takes donations again only once it has locked
             self.assert_active_join_period(now)?;
         } else if self.status == PoolStatus::Locked {
             require!(
-                now < self.lock_start_time + self.lock_duration,
+                now < self.lock_end_time()?,
                 ErrorCode::DonateClosedAfterUnlock
             );
         } else {
@@ -100,24 +145,97 @@ impl Pool {
         Ok(())
     }
 
+    /// Rejects a donation that would take the pool's donations past `max_donations_amount`
+    pub fn assert_donation_within_cap(&self, amount: u64) -> Result<()> {
+        if self.max_donations_amount == 0 {
+            return Ok(());
+        }
+        let donated = self.total_donated.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        require!(donated <= self.max_donations_amount, ErrorCode::DonationCapExceeded);
+        Ok(())
+    }
+
     pub fn assert_not_paused(&self) -> Result<()> {
         require!(!self.paused, ErrorCode::Paused);
         Ok(())
     }
 
+    /// Entries close at `join_deadline`, or at expiry if that comes first
     pub fn assert_active_join_period(&self, now: i64) -> Result<()> {
+        self.assert_not_expired(now)?;
+        require!(now <= self.join_deadline, ErrorCode::JoinDeadlinePassed);
+        Ok(())
+    }
+
+    pub fn assert_not_expired(&self, now: i64) -> Result<()> {
         require!(now <= self.start_time + self.duration, ErrorCode::PoolExpired);
         Ok(())
     }
 
     pub fn assert_unlocked_time(&self, now: i64) -> Result<()> {
         require!(
-            now >= self.lock_start_time + self.lock_duration,
+            now >= self.lock_end_time()?,
             ErrorCode::PoolStillLocked
         );
         Ok(())
     }
 
+    /// Entries live in a concurrent Merkle tree instead of `Participants`
+    pub fn is_compressed(&self) -> bool {
+        self.merkle_tree != ZERO_PUBKEY
+    }
+
+    pub fn has_tickets(&self) -> bool {
+        self.ticket_tree != ZERO_PUBKEY
+    }
+
+    /// Dutch-auction pools interpolate the entry price between `amount` and `end_amount`
+    pub fn is_dutch_auction(&self) -> bool {
+        self.end_amount != self.amount
+    }
+
+    /// Portion of `total_amount` subject to the fee split (excludes earmarked donations)
+    pub fn fee_base(&self) -> Result<u64> {
+        ml_core::fee_base(self.total_amount, self.donated_prize, self.donated_burn)
+            .ok_or_else(|| error!(ErrorCode::Overflow))
+    }
+
+    /// Entry price at `now`, linear from `amount` at `start_time` to `end_amount` at `expire_time`
+    pub fn entry_amount_at(&self, now: i64) -> Result<u64> {
+        ml_core::entry_amount_at(self.amount, self.end_amount, self.start_time, self.expire_time, now)
+            .ok_or_else(|| error!(ErrorCode::Overflow))
+    }
+
+    /// NFT-prize pools pay the winner the escrowed NFT instead of the token pot
+    pub fn has_prize_nft(&self) -> bool {
+        self.prize_mint != ZERO_PUBKEY
+    }
+
+    /// Anti-tamper hash over the immutable pool configuration
+    pub fn compute_config_hash(&self) -> [u8; 32] {
+        let mut hasher = sha2::Sha256::new();
+        hasher.update(self.salt);
+        hasher.update(self.max_participants.to_le_bytes());
+        hasher.update(self.lock_duration.to_le_bytes());
+        hasher.update(self.amount.to_le_bytes());
+        hasher.update(self.dev_wallet.as_ref());
+        hasher.update(self.dev_fee_bps.to_le_bytes());
+        hasher.update(self.burn_fee_bps.to_le_bytes());
+        hasher.update(self.treasury_wallet.as_ref());
+        hasher.update(self.treasury_fee_bps.to_le_bytes());
+        hasher.update(self.start_time.to_le_bytes());
+        hasher.update(self.duration.to_le_bytes());
+        hasher.update(self.expire_time.to_le_bytes());
+        hasher.update(self.join_deadline.to_le_bytes());
+        hasher.update(self.end_amount.to_le_bytes());
+        hasher.update(self.min_bet_usd.to_le_bytes());
+        hasher.update(self.min_bet_native.to_le_bytes());
+        hasher.update(self.merkle_tree.as_ref());
+        hasher.update(self.max_entries.to_le_bytes());
+        hasher.update(self.max_donations_amount.to_le_bytes());
+        hasher.finalize().into()
+    }
+
     /// 🔒 Reentrancy guard - assert not currently processing
     pub fn assert_not_processing(&self) -> Result<()> {
         require!(!self.processing, ErrorCode::PoolProcessing);
@@ -142,6 +260,90 @@ impl Pool {
 pub struct Participants {
     pub list: [Pubkey; MAX_PARTICIPANTS],
     pub count: u8,
+    /// Amount each participant actually paid (varies for dutch-auction pools)
+    pub amounts: [u64; MAX_PARTICIPANTS],
+}
+
+/// Number of pools ever created by `creator`; seeds `[b"creator_pools", creator]`
+#[account]
+#[derive(InitSpace)]
+pub struct CreatorPoolCounter {
+    pub creator: Pubkey,
+    pub pool_count: u64,
+    pub bump: u8,
+}
+
+/// Deterministic pointer to the creator's n-th pool; seeds `[b"creator_pool", creator, index_le]`
+#[account]
+#[derive(InitSpace)]
+pub struct CreatorPoolIndex {
+    pub creator: Pubkey,
+    pub index: u64,
+    pub pool: Pubkey,
+    pub mint: Pubkey,
+    pub salt: [u8; 32],
+    pub bump: u8,
+}
+
+/// Per-mint Pyth feed registry, maintained by PLATFORM_ADMIN
+#[account]
+#[derive(InitSpace)]
+pub struct PriceFeedConfig {
+    pub mint: Pubkey,
+    pub feed_id: [u8; 32],
+    pub max_age: u64,
+    pub bump: u8,
+}
+
+/// Team entry: members pool funds into the team vault (ATA of this PDA) and the team
+/// occupies a single `Participants` slot; seeds `[b"team", pool, captain]`
+#[account]
+#[derive(InitSpace)]
+pub struct Team {
+    pub pool: Pubkey,
+    pub captain: Pubkey,
+    pub members: [Pubkey; MAX_TEAM_MEMBERS],
+    /// Contribution per member; prize and leftovers are split pro rata
+    pub shares: [u64; MAX_TEAM_MEMBERS],
+    pub member_count: u8,
+    pub total_shares: u64,
+    pub entered: bool,
+    pub bump: u8,
+}
+
+/// Pending admin close; seeds `[b"admin_close", pool]`
+#[account]
+#[derive(InitSpace)]
+pub struct AdminCloseProposal {
+    pub pool: Pubkey,
+    pub proposer: Pubkey,
+    pub reason_code: u8,
+    /// Off-chain justification (report, governance post, ...)
+    #[max_len(MAX_ADMIN_CLOSE_URI_LEN)]
+    pub uri: String,
+    pub slash_bond: bool,
+    pub proposed_at: i64,
+    pub executable_at: i64,
+    pub bump: u8,
+}
+
+/// Pool summary that survives `claim_rent`; seeds `[b"pool_archive", pool]`
+#[account]
+#[derive(InitSpace)]
+pub struct PoolArchive {
+    pub pool: Pubkey,
+    pub pool_id: u64,
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    /// ZERO_PUBKEY for cancelled pools
+    pub winner: Pubkey,
+    pub total_volume: u64,
+    pub participant_count: u32,
+    pub end_time: i64,
+    /// Status before closure (Ended / Cancelled / WinnerSelected)
+    pub final_status: PoolStatus,
+    pub status_reason: u8,
+    pub bump: u8,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
@@ -174,6 +376,19 @@ pub enum ActionType {
     AdminClosed = 12,
     EmergencyReveal = 13,
     Expired = 14,
+    LockedAtDeadline = 15,
+}
+
+/// Where a donation goes at payout
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+#[repr(u8)]
+pub enum DonationPurpose {
+    /// Shared pot, split by the pool fee schedule
+    Pot = 0,
+    /// Added to the winner amount, no fees
+    Prize = 1,
+    /// Added to the burn amount
+    Burn = 2,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
@@ -0,0 +1,156 @@
+//! Compressed NFT entry tickets minted through Bubblegum.
+//!
+//! Raw CPI like `compression.rs`: `mpl-bubblegum` pins an older Solana SDK than Anchor 0.31.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+};
+
+pub const BUBBLEGUM_ID: Pubkey = pubkey!("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY");
+
+pub const TICKET_SYMBOL: &str = "MOTIX";
+pub const TICKET_URI_BASE: &str = "https://www.missout.fun/api/tickets/";
+
+// Anchor discriminators: sha256("global:<ix_name>")[..8]
+const IX_CREATE_TREE: [u8; 8] = [165, 83, 136, 142, 89, 202, 47, 220];
+const IX_MINT_V1: [u8; 8] = [145, 98, 192, 118, 184, 147, 118, 104];
+
+/// Borsh layout of Bubblegum `MetadataArgs` (unused optional structs encoded as `None`)
+#[derive(AnchorSerialize)]
+struct MetadataArgs {
+    name: String,
+    symbol: String,
+    uri: String,
+    seller_fee_basis_points: u16,
+    primary_sale_happened: bool,
+    is_mutable: bool,
+    edition_nonce: Option<u8>,
+    /// `TokenStandard::NonFungible`
+    token_standard: Option<u8>,
+    collection: Option<u8>,
+    uses: Option<u8>,
+    /// `TokenProgramVersion::Original`
+    token_program_version: u8,
+    creators: Vec<(Pubkey, bool, u8)>,
+}
+
+/// Bubblegum accounts shared by tree creation and minting; `tree_creator` is the pool PDA
+pub struct TicketCpi<'a, 'info> {
+    pub bubblegum_program: &'a AccountInfo<'info>,
+    pub tree_config: &'a AccountInfo<'info>,
+    pub merkle_tree: &'a AccountInfo<'info>,
+    pub tree_creator: &'a AccountInfo<'info>,
+    pub payer: &'a AccountInfo<'info>,
+    pub log_wrapper: &'a AccountInfo<'info>,
+    pub compression_program: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> TicketCpi<'a, 'info> {
+    pub fn create_tree(
+        &self,
+        max_depth: u32,
+        max_buffer_size: u32,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        let mut data = IX_CREATE_TREE.to_vec();
+        data.extend_from_slice(&max_depth.to_le_bytes());
+        data.extend_from_slice(&max_buffer_size.to_le_bytes());
+        // public: Some(false) - only the pool PDA may mint
+        data.extend_from_slice(&[1, 0]);
+
+        let accounts = vec![
+            AccountMeta::new(self.tree_config.key(), false),
+            AccountMeta::new(self.merkle_tree.key(), false),
+            AccountMeta::new(self.payer.key(), true),
+            AccountMeta::new_readonly(self.tree_creator.key(), true),
+            AccountMeta::new_readonly(self.log_wrapper.key(), false),
+            AccountMeta::new_readonly(self.compression_program.key(), false),
+            AccountMeta::new_readonly(self.system_program.key(), false),
+        ];
+        let infos = [
+            self.tree_config.clone(),
+            self.merkle_tree.clone(),
+            self.payer.clone(),
+            self.tree_creator.clone(),
+            self.log_wrapper.clone(),
+            self.compression_program.clone(),
+            self.system_program.clone(),
+            self.bubblegum_program.clone(),
+        ];
+
+        self.invoke(accounts, &infos, data, signer_seeds)
+    }
+
+    /// Mints the ticket for entry `index` of pool `pool_id` to `owner`
+    pub fn mint_ticket(
+        &self,
+        owner: &AccountInfo<'info>,
+        pool: &Pubkey,
+        pool_id: u64,
+        index: u32,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        let metadata = MetadataArgs {
+            name: format!("MissOut {:016x}#{}", pool_id, index),
+            symbol: TICKET_SYMBOL.to_string(),
+            uri: format!("{}{}/{}", TICKET_URI_BASE, pool, index),
+            seller_fee_basis_points: 0,
+            primary_sale_happened: true,
+            is_mutable: false,
+            edition_nonce: None,
+            token_standard: Some(0),
+            collection: None,
+            uses: None,
+            token_program_version: 0,
+            creators: Vec::new(),
+        };
+
+        let mut data = IX_MINT_V1.to_vec();
+        metadata.serialize(&mut data)?;
+
+        let accounts = vec![
+            AccountMeta::new(self.tree_config.key(), false),
+            AccountMeta::new_readonly(owner.key(), false),
+            AccountMeta::new_readonly(owner.key(), false),
+            AccountMeta::new(self.merkle_tree.key(), false),
+            AccountMeta::new_readonly(self.payer.key(), true),
+            AccountMeta::new_readonly(self.tree_creator.key(), true),
+            AccountMeta::new_readonly(self.log_wrapper.key(), false),
+            AccountMeta::new_readonly(self.compression_program.key(), false),
+            AccountMeta::new_readonly(self.system_program.key(), false),
+        ];
+        let infos = [
+            self.tree_config.clone(),
+            owner.clone(),
+            self.merkle_tree.clone(),
+            self.payer.clone(),
+            self.tree_creator.clone(),
+            self.log_wrapper.clone(),
+            self.compression_program.clone(),
+            self.system_program.clone(),
+            self.bubblegum_program.clone(),
+        ];
+
+        self.invoke(accounts, &infos, data, signer_seeds)
+    }
+
+    fn invoke(
+        &self,
+        accounts: Vec<AccountMeta>,
+        infos: &[AccountInfo<'info>],
+        data: Vec<u8>,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        require_keys_eq!(
+            self.bubblegum_program.key(),
+            BUBBLEGUM_ID,
+            crate::errors::ErrorCode::InvalidTicketAccounts
+        );
+
+        invoke_signed(&Instruction { program_id: BUBBLEGUM_ID, accounts, data }, infos, signer_seeds)
+            .map_err(Into::into)
+    }
+}
@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface::TokenAccount;
+use anchor_spl::metadata::mpl_token_metadata::instructions::TransferV1CpiBuilder;
+use anchor_spl::token_interface::{transfer_checked, TokenAccount, TransferChecked};
 
 // ✅ IMPORTANT: use Anchor's re-export of spl_token_2022 to avoid version/type mismatch
 use anchor_spl::token_2022::spl_token_2022 as spl_token_2022;
@@ -20,6 +21,7 @@ use spl_token_2022::{
     state::{Mint as Token2022Mint, AccountState},
 };
 
+use crate::constants::USD_MICROS_DECIMALS;
 use crate::errors::ErrorCode;
 
 /// ✅ SPL Classic + Token-2022 compatible token account validation
@@ -108,3 +110,136 @@ pub fn validate_token2022_mint(mint_account: &AccountInfo) -> Result<()> {
     msg!("✅ ACCEPTED: Token-2022 mint passed extension checks");
     Ok(())
 }
+
+/// Token Metadata accounts required to move a programmable NFT (pNFT).
+pub struct PnftAccounts<'a, 'info> {
+    pub token_metadata_program: &'a AccountInfo<'info>,
+    pub metadata: &'a AccountInfo<'info>,
+    pub edition: &'a AccountInfo<'info>,
+    pub from_token_record: &'a AccountInfo<'info>,
+    pub to_token_record: &'a AccountInfo<'info>,
+    pub sysvar_instructions: &'a AccountInfo<'info>,
+    pub authorization_rules_program: Option<&'a AccountInfo<'info>>,
+    pub authorization_rules: Option<&'a AccountInfo<'info>>,
+}
+
+/// Accounts used to move a prize NFT between two token accounts.
+pub struct PrizeNftTransfer<'a, 'info> {
+    pub token_program: &'a AccountInfo<'info>,
+    pub associated_token_program: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+    pub mint: &'a AccountInfo<'info>,
+    pub from: &'a AccountInfo<'info>,
+    pub from_owner: &'a AccountInfo<'info>,
+    pub to: &'a AccountInfo<'info>,
+    pub to_owner: &'a AccountInfo<'info>,
+    pub authority: &'a AccountInfo<'info>,
+    pub payer: &'a AccountInfo<'info>,
+    /// Present only for pNFTs, which stay frozen and must go through Token Metadata
+    pub pnft: Option<PnftAccounts<'a, 'info>>,
+}
+
+/// ✅ Move exactly one prize NFT (classic SPL / Token-2022 NFT or Metaplex pNFT)
+pub fn transfer_prize_nft(accounts: PrizeNftTransfer, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+    let Some(pnft) = accounts.pnft else {
+        return transfer_checked(
+            CpiContext::new_with_signer(
+                accounts.token_program.clone(),
+                TransferChecked {
+                    from: accounts.from.clone(),
+                    to: accounts.to.clone(),
+                    authority: accounts.authority.clone(),
+                    mint: accounts.mint.clone(),
+                },
+                signer_seeds,
+            ),
+            1,
+            0,
+        );
+    };
+
+    TransferV1CpiBuilder::new(pnft.token_metadata_program)
+        .token(accounts.from)
+        .token_owner(accounts.from_owner)
+        .destination_token(accounts.to)
+        .destination_owner(accounts.to_owner)
+        .mint(accounts.mint)
+        .metadata(pnft.metadata)
+        .edition(Some(pnft.edition))
+        .token_record(Some(pnft.from_token_record))
+        .destination_token_record(Some(pnft.to_token_record))
+        .authority(accounts.authority)
+        .payer(accounts.payer)
+        .system_program(accounts.system_program)
+        .sysvar_instructions(pnft.sysvar_instructions)
+        .spl_token_program(accounts.token_program)
+        .spl_ata_program(accounts.associated_token_program)
+        .authorization_rules_program(pnft.authorization_rules_program)
+        .authorization_rules(pnft.authorization_rules)
+        .amount(1)
+        .invoke_signed(signer_seeds)?;
+
+    Ok(())
+}
+
+/// ✅ Conservative USD value (micro-USD) of `amount` native units, priced at `price - conf`
+pub fn usd_value_micros(amount: u64, decimals: u8, price: i64, conf: u64, exponent: i32) -> Result<u64> {
+    let conservative = (price as i128)
+        .checked_sub(conf as i128)
+        .ok_or(ErrorCode::Overflow)?;
+    if conservative <= 0 {
+        return Ok(0);
+    }
+
+    let mut value = (amount as u128)
+        .checked_mul(conservative as u128)
+        .and_then(|v| v.checked_mul(10_u128.pow(USD_MICROS_DECIMALS)))
+        .ok_or(ErrorCode::Overflow)?;
+
+    let mut denominator = 10_u128
+        .checked_pow(decimals as u32)
+        .ok_or(ErrorCode::Overflow)?;
+    if exponent >= 0 {
+        value = value
+            .checked_mul(10_u128.checked_pow(exponent as u32).ok_or(ErrorCode::Overflow)?)
+            .ok_or(ErrorCode::Overflow)?;
+    } else {
+        denominator = denominator
+            .checked_mul(10_u128.checked_pow(exponent.unsigned_abs()).ok_or(ErrorCode::Overflow)?)
+            .ok_or(ErrorCode::Overflow)?;
+    }
+
+    Ok(u64::try_from(value / denominator).unwrap_or(u64::MAX))
+}
+
+/// Moves the creator bond out of the pool account into `treasury`. Returns the slashed amount.
+pub fn slash_creator_bond<'info>(
+    pool: &mut Account<'info, crate::state::Pool>,
+    treasury: &AccountInfo<'info>,
+) -> Result<u64> {
+    let bond = pool.creator_bond;
+    if bond == 0 {
+        return Ok(0);
+    }
+
+    pool.sub_lamports(bond)?;
+    treasury.add_lamports(bond)?;
+    pool.creator_bond = 0;
+
+    emit!(crate::events::CreatorBondSlashed {
+        pool_id: pool.key(),
+        creator: pool.creator,
+        amount: bond,
+        treasury: treasury.key(),
+    });
+
+    Ok(bond)
+}
+
+/// `tokens * 10^decimals` with overflow checks (human-readable -> native units)
+pub fn to_native_amount(tokens: u64, decimals: u8) -> Result<u64> {
+    10_u64
+        .checked_pow(decimals as u32)
+        .and_then(|scale| tokens.checked_mul(scale))
+        .ok_or_else(|| error!(ErrorCode::Overflow))
+}
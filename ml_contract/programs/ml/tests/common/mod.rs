@@ -0,0 +1,637 @@
+//! solana-program-test harness for the `ml` program.
+//!
+//! Runs the program natively (built with `test-utils`, so pools may use mock
+//! randomness) next to the SPL Token and ATA programs bundled by program-test.
+
+#![allow(dead_code)]
+
+use anchor_lang::{
+    prelude::AccountInfo,
+    solana_program::{entrypoint::ProgramResult, program_pack::Pack, system_instruction},
+    AccountDeserialize, InstructionData, ToAccountMetas,
+};
+use anchor_spl::token::spl_token;
+use ml::{
+    constants::*,
+    state::{DonationPurpose, Participants, Pool},
+};
+use ml_test_utils::{install_price_feed, install_price_update, MockPriceUpdate};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    clock::Clock,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    sysvar,
+    transaction::Transaction,
+};
+use spl_associated_token_account::{
+    get_associated_token_address, instruction::create_associated_token_account,
+};
+
+pub const DECIMALS: u8 = 6;
+pub const ENTRY: u64 = 25 * 10u64.pow(DECIMALS as u32);
+pub const LOCK_DURATION: i64 = MIN_LOCK_DURATION;
+pub const DEV_FEE_BPS: u16 = 500;
+pub const BURN_FEE_BPS: u16 = 200;
+pub const TREASURY_FEE_BPS: u16 = 300;
+
+const WALLET_LAMPORTS: u64 = 10_000_000_000;
+const WALLET_TOKENS: u64 = 1_000 * 10u64.pow(DECIMALS as u32);
+
+// Anchor's entry ties the account slice to `'info`; program-test hands out shorter borrows
+fn process_instruction(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    ml::entry(program_id, accounts, data)
+}
+
+/// Addresses of one pool and its PDAs
+#[derive(Clone, Copy, Debug)]
+pub struct PoolKeys {
+    pub pool: Pubkey,
+    pub participants: Pubkey,
+    pub pool_token: Pubkey,
+    pub creator: Pubkey,
+}
+
+pub struct Harness {
+    pub ctx: ProgramTestContext,
+    pub mint: Pubkey,
+    pub dev: Keypair,
+    pub treasury: Keypair,
+}
+
+impl Harness {
+    /// Boots the bank with a fixed-supply mint (no mint/freeze authority) and funded
+    /// dev/treasury wallets that already own their ATAs
+    pub async fn new() -> Self {
+        let mut program = ProgramTest::new("ml", ml::ID, processor!(process_instruction));
+        program.prefer_bpf(false);
+        let ctx = program.start_with_context().await;
+
+        let mut harness = Self {
+            ctx,
+            mint: Pubkey::default(),
+            dev: Keypair::new(),
+            treasury: Keypair::new(),
+        };
+        harness.mint = harness.create_mint().await;
+
+        let (dev, treasury) = (harness.dev.insecure_clone(), harness.treasury.insecure_clone());
+        harness.fund(&dev.pubkey(), WALLET_LAMPORTS).await;
+        harness.fund(&treasury.pubkey(), WALLET_LAMPORTS).await;
+        harness.create_ata(&dev.pubkey()).await;
+        harness.create_ata(&treasury.pubkey()).await;
+        harness
+    }
+
+    pub fn payer(&self) -> Keypair {
+        self.ctx.payer.insecure_clone()
+    }
+
+    /// Sends `ixs` in one transaction paid by the context payer
+    pub async fn send(&mut self, ixs: &[Instruction], signers: &[&Keypair]) -> Result<(), BanksClientError> {
+        let payer = self.payer();
+        let blockhash = self.ctx.get_new_latest_blockhash().await?;
+        let mut all_signers = vec![&payer];
+        all_signers.extend_from_slice(signers);
+        let tx = Transaction::new_signed_with_payer(ixs, Some(&payer.pubkey()), &all_signers, blockhash);
+        self.ctx.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn fund(&mut self, to: &Pubkey, lamports: u64) {
+        let ix = system_instruction::transfer(&self.ctx.payer.pubkey(), to, lamports);
+        self.send(&[ix], &[]).await.unwrap();
+    }
+
+    /// Fixed-supply test mint: everything is minted to the payer, then the mint authority is revoked
+    async fn create_mint(&mut self) -> Pubkey {
+        let mint = Keypair::new();
+        let payer = self.payer();
+        let rent = self.ctx.banks_client.get_rent().await.unwrap();
+
+        let payer_ata = get_associated_token_address(&payer.pubkey(), &mint.pubkey());
+        let ixs = [
+            system_instruction::create_account(
+                &payer.pubkey(),
+                &mint.pubkey(),
+                rent.minimum_balance(spl_token::state::Mint::LEN),
+                spl_token::state::Mint::LEN as u64,
+                &spl_token::ID,
+            ),
+            spl_token::instruction::initialize_mint2(&spl_token::ID, &mint.pubkey(), &payer.pubkey(), None, DECIMALS)
+                .unwrap(),
+            create_associated_token_account(&payer.pubkey(), &payer.pubkey(), &mint.pubkey(), &spl_token::ID),
+            spl_token::instruction::mint_to(
+                &spl_token::ID,
+                &mint.pubkey(),
+                &payer_ata,
+                &payer.pubkey(),
+                &[],
+                100 * WALLET_TOKENS,
+            )
+            .unwrap(),
+            spl_token::instruction::set_authority(
+                &spl_token::ID,
+                &mint.pubkey(),
+                None,
+                spl_token::instruction::AuthorityType::MintTokens,
+                &payer.pubkey(),
+                &[],
+            )
+            .unwrap(),
+        ];
+        self.send(&ixs, &[&mint]).await.unwrap();
+        mint.pubkey()
+    }
+
+    pub async fn create_ata(&mut self, owner: &Pubkey) -> Pubkey {
+        let ix = create_associated_token_account(&self.ctx.payer.pubkey(), owner, &self.mint, &spl_token::ID);
+        self.send(&[ix], &[]).await.unwrap();
+        self.ata(owner)
+    }
+
+    pub fn ata(&self, owner: &Pubkey) -> Pubkey {
+        get_associated_token_address(owner, &self.mint)
+    }
+
+    /// Empty token account of the test mint owned by `owner` at a fresh, non-ATA address
+    pub async fn token_account(&mut self, owner: &Pubkey) -> Pubkey {
+        let account = Keypair::new();
+        let payer = self.payer();
+        let rent = self.ctx.banks_client.get_rent().await.unwrap();
+        let ixs = [
+            system_instruction::create_account(
+                &payer.pubkey(),
+                &account.pubkey(),
+                rent.minimum_balance(spl_token::state::Account::LEN),
+                spl_token::state::Account::LEN as u64,
+                &spl_token::ID,
+            ),
+            spl_token::instruction::initialize_account3(&spl_token::ID, &account.pubkey(), &self.mint, owner).unwrap(),
+        ];
+        self.send(&ixs, &[&account]).await.unwrap();
+        account.pubkey()
+    }
+
+    /// New wallet with SOL, an ATA and `WALLET_TOKENS` of the test mint
+    pub async fn wallet(&mut self) -> Keypair {
+        let wallet = Keypair::new();
+        self.fund(&wallet.pubkey(), WALLET_LAMPORTS).await;
+        let ata = self.create_ata(&wallet.pubkey()).await;
+
+        let payer = self.payer();
+        let ix = spl_token::instruction::transfer(
+            &spl_token::ID,
+            &self.ata(&payer.pubkey()),
+            &ata,
+            &payer.pubkey(),
+            &[],
+            WALLET_TOKENS,
+        )
+        .unwrap();
+        self.send(&[ix], &[]).await.unwrap();
+        wallet
+    }
+
+    pub fn pool_keys(&self, creator: &Pubkey, salt: [u8; 32]) -> PoolKeys {
+        let (pool, _) = Pubkey::find_program_address(&[b"pool", self.mint.as_ref(), &salt], &ml::ID);
+        let (participants, _) = Pubkey::find_program_address(&[b"participants", pool.as_ref()], &ml::ID);
+        PoolKeys {
+            pool,
+            participants,
+            pool_token: get_associated_token_address(&pool, &self.mint),
+            creator: *creator,
+        }
+    }
+
+    /* ---------- clock ---------- */
+
+    pub async fn clock(&mut self) -> Clock {
+        self.ctx.banks_client.get_sysvar::<Clock>().await.unwrap()
+    }
+
+    /// Advances one slot and moves `unix_timestamp` forward by `seconds`
+    pub async fn warp_seconds(&mut self, seconds: i64) {
+        let before = self.clock().await;
+        self.warp_slots(1).await;
+        let mut clock = self.clock().await;
+        clock.unix_timestamp = before.unix_timestamp + seconds;
+        self.ctx.set_sysvar(&clock);
+    }
+
+    /// Advances `slots` slots; the timestamp never moves backwards
+    pub async fn warp_slots(&mut self, slots: u64) {
+        let before = self.clock().await;
+        self.ctx.warp_to_slot(before.slot + slots).unwrap();
+        let mut clock = self.clock().await;
+        if clock.unix_timestamp < before.unix_timestamp {
+            clock.unix_timestamp = before.unix_timestamp;
+            self.ctx.set_sysvar(&clock);
+        }
+    }
+
+    /* ---------- state ---------- */
+
+    pub async fn account<T: AccountDeserialize>(&mut self, address: &Pubkey) -> T {
+        let account = self.ctx.banks_client.get_account(*address).await.unwrap().expect("account missing");
+        T::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    pub async fn pool(&mut self, keys: &PoolKeys) -> Pool {
+        self.account(&keys.pool).await
+    }
+
+    pub async fn participants(&mut self, keys: &PoolKeys) -> Participants {
+        self.account(&keys.participants).await
+    }
+
+    pub async fn lamports(&mut self, address: &Pubkey) -> u64 {
+        self.ctx.banks_client.get_balance(*address).await.unwrap()
+    }
+
+    pub async fn balance(&mut self, token_account: &Pubkey) -> u64 {
+        let account = self.ctx.banks_client.get_account(*token_account).await.unwrap().expect("token account missing");
+        spl_token::state::Account::unpack(&account.data).unwrap().amount
+    }
+
+    /* ---------- oracle ---------- */
+
+    /// Registers a Pyth feed for the test mint, as `set_price_feed` would
+    pub fn register_price_feed(&mut self, feed_id: [u8; 32], max_age: u64) {
+        let (address, bump) = Pubkey::find_program_address(&[b"price_feed", self.mint.as_ref()], &ml::ID);
+        let config = ml_types::state::PriceFeedConfig {
+            mint: ml_types::Pubkey::new_from_array(self.mint.to_bytes()),
+            feed_id,
+            max_age,
+            bump,
+        };
+        install_price_feed(&mut self.ctx, &ml::ID, &address, &config);
+    }
+
+    /// Fully verified price update of `price * 10^exponent` USD per token, published `age` seconds ago
+    pub async fn price_update(&mut self, feed_id: [u8; 32], price: i64, exponent: i32, age: i64) -> Pubkey {
+        let address = Keypair::new().pubkey();
+        let publish_time = self.clock().await.unix_timestamp - age;
+        install_price_update(&mut self.ctx, &address, &MockPriceUpdate::new(feed_id, price, exponent, publish_time));
+        address
+    }
+
+    /* ---------- instructions ---------- */
+
+    /// Mock-randomness pool with `ENTRY` flat pricing and the default fee split
+    pub async fn create_pool(
+        &mut self,
+        creator: &Keypair,
+        salt: [u8; 32],
+        max_participants: u8,
+    ) -> Result<PoolKeys, BanksClientError> {
+        self.create_pool_with(creator, salt, max_participants, true).await
+    }
+
+    /// `allow_mock = false` forces the strict Switchboard path in `select_winner`
+    pub async fn create_pool_with(
+        &mut self,
+        creator: &Keypair,
+        salt: [u8; 32],
+        max_participants: u8,
+        allow_mock: bool,
+    ) -> Result<PoolKeys, BanksClientError> {
+        let data = ml::instruction::CreatePool { allow_mock, ..self.pool_config(salt, max_participants) };
+        self.create_pool_from(creator, data).await
+    }
+
+    /// `create_pool` arguments of a mock-randomness `ENTRY` pool, for tests
+    /// adjusting the rest
+    pub fn pool_config(&self, salt: [u8; 32], max_participants: u8) -> ml::instruction::CreatePool {
+        ml::instruction::CreatePool {
+            salt,
+            max_participants,
+            lock_duration: LOCK_DURATION,
+            amount: ENTRY,
+            dev_wallet: self.dev.pubkey(),
+            dev_fee_bps: DEV_FEE_BPS,
+            burn_fee_bps: BURN_FEE_BPS,
+            treasury_wallet: self.treasury.pubkey(),
+            treasury_fee_bps: TREASURY_FEE_BPS,
+            allow_mock: true,
+            end_amount: ENTRY,
+            min_bet_override: 0,
+            max_donations_amount: 0,
+            join_deadline: 0,
+        }
+    }
+
+    pub async fn create_pool_from(
+        &mut self,
+        creator: &Keypair,
+        data: ml::instruction::CreatePool,
+    ) -> Result<PoolKeys, BanksClientError> {
+        let (keys, accounts) = self.create_accounts(creator, data.salt).await;
+        self.send(&[ix(accounts, data)], &[creator]).await?;
+        Ok(keys)
+    }
+
+    /// `create_pool` for a mint with a registered price feed, priced by `price_update`
+    pub async fn create_pool_priced(
+        &mut self,
+        creator: &Keypair,
+        data: ml::instruction::CreatePool,
+        price_update: Pubkey,
+    ) -> Result<PoolKeys, BanksClientError> {
+        let (keys, mut accounts) = self.create_accounts(creator, data.salt).await;
+        accounts.price_update = Some(price_update);
+        self.send(&[ix(accounts, data)], &[creator]).await?;
+        Ok(keys)
+    }
+
+    /// New pool under `salt` copying the configuration of `source`
+    pub async fn clone_pool(
+        &mut self,
+        creator: &Keypair,
+        source: &PoolKeys,
+        salt: [u8; 32],
+    ) -> Result<PoolKeys, BanksClientError> {
+        let (keys, create) = self.create_accounts(creator, salt).await;
+        let accounts = ml::accounts::ClonePool { create, source_pool: source.pool };
+        self.send(&[ix(accounts, ml::instruction::ClonePool { salt })], &[creator]).await?;
+        Ok(keys)
+    }
+
+    /// `create_pool` accounts of `creator`'s next pool under `salt`
+    async fn create_accounts(&mut self, creator: &Keypair, salt: [u8; 32]) -> (PoolKeys, ml::accounts::CreatePool) {
+        let keys = self.pool_keys(&creator.pubkey(), salt);
+        let (creator_counter, _) =
+            Pubkey::find_program_address(&[b"creator_pools", creator.pubkey().as_ref()], &ml::ID);
+        let pool_count = match self.ctx.banks_client.get_account(creator_counter).await.unwrap() {
+            Some(_) => self.account::<ml::state::CreatorPoolCounter>(&creator_counter).await.pool_count,
+            None => 0,
+        };
+        let (creator_pool_index, _) = Pubkey::find_program_address(
+            &[b"creator_pool", creator.pubkey().as_ref(), &pool_count.to_le_bytes()],
+            &ml::ID,
+        );
+        let (price_feed, _) = Pubkey::find_program_address(&[b"price_feed", self.mint.as_ref()], &ml::ID);
+
+        let accounts = ml::accounts::CreatePool {
+            mint: self.mint,
+            pool: keys.pool,
+            user_token: self.ata(&creator.pubkey()),
+            user: creator.pubkey(),
+            payer: creator.pubkey(),
+            pool_token: keys.pool_token,
+            token_program: spl_token::ID,
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: anchor_lang::system_program::ID,
+            rent: sysvar::rent::ID,
+            participants: keys.participants,
+            creator_counter,
+            creator_pool_index,
+            price_feed,
+            price_update: None,
+        };
+        (keys, accounts)
+    }
+
+    pub async fn join(&mut self, keys: &PoolKeys, user: &Keypair) -> Result<(), BanksClientError> {
+        let accounts = ml::accounts::JoinPool {
+            mint: self.mint,
+            pool: keys.pool,
+            pool_token: keys.pool_token,
+            user_token: self.ata(&user.pubkey()),
+            user: user.pubkey(),
+            token_program: spl_token::ID,
+            participants: keys.participants,
+            ticket_tree_config: None,
+            ticket_tree: None,
+            bubblegum_program: None,
+            log_wrapper: None,
+            compression_program: None,
+            system_program: None,
+        };
+        self.send(&[ix(accounts, ml::instruction::JoinPool { amount: ENTRY })], &[user]).await
+    }
+
+    pub async fn donate(&mut self, keys: &PoolKeys, user: &Keypair, amount: u64) -> Result<(), BanksClientError> {
+        let accounts = ml::accounts::Donate {
+            mint: self.mint,
+            pool: keys.pool,
+            pool_token: keys.pool_token,
+            user_token: self.ata(&user.pubkey()),
+            user: user.pubkey(),
+            token_program: spl_token::ID,
+            participants: keys.participants,
+        };
+        let data = ml::instruction::Donate { amount, purpose: DonationPurpose::Pot };
+        self.send(&[ix(accounts, data)], &[user]).await
+    }
+
+    pub async fn unlock(&mut self, keys: &PoolKeys) -> Result<(), BanksClientError> {
+        let dev = self.dev.insecure_clone();
+        let accounts = ml::accounts::UnlockPool {
+            pool: keys.pool,
+            user: dev.pubkey(),
+            participants: keys.participants,
+        };
+        self.send(&[ix(accounts, ml::instruction::UnlockPool {})], &[&dev]).await
+    }
+
+    /// Permissionless: the context payer locks the pool once its join deadline has passed
+    pub async fn lock_at_deadline(&mut self, keys: &PoolKeys) -> Result<(), BanksClientError> {
+        let accounts = ml::accounts::LockAtDeadline {
+            pool: keys.pool,
+            user: self.payer().pubkey(),
+            participants: keys.participants,
+        };
+        self.send(&[ix(accounts, ml::instruction::LockAtDeadline {})], &[]).await
+    }
+
+    /// Commits mock randomness (randomness account = default pubkey)
+    pub async fn request_randomness(&mut self, keys: &PoolKeys) -> Result<(), BanksClientError> {
+        self.request_randomness_with(keys, Pubkey::default()).await
+    }
+
+    pub async fn request_randomness_with(
+        &mut self,
+        keys: &PoolKeys,
+        randomness: Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let dev = self.dev.insecure_clone();
+        let accounts = ml::accounts::RequestRandomness {
+            randomness,
+            pool: keys.pool,
+            user: dev.pubkey(),
+            participants: keys.participants,
+        };
+        self.send(&[ix(accounts, ml::instruction::RequestRandomness {})], &[&dev]).await
+    }
+
+    pub async fn select_winner(&mut self, keys: &PoolKeys) -> Result<(), BanksClientError> {
+        self.select_winner_with(keys, Pubkey::default()).await
+    }
+
+    pub async fn select_winner_with(&mut self, keys: &PoolKeys, randomness: Pubkey) -> Result<(), BanksClientError> {
+        let dev = self.dev.insecure_clone();
+        let accounts = ml::accounts::SelectWinner {
+            pool: keys.pool,
+            randomness,
+            user: dev.pubkey(),
+            participants: keys.participants,
+        };
+        self.send(&[ix(accounts, ml::instruction::SelectWinner {})], &[&dev]).await
+    }
+
+    pub async fn payout(&mut self, keys: &PoolKeys) -> Result<(), BanksClientError> {
+        let winner = self.pool(keys).await.winner;
+        self.payout_to(keys, self.ata(&winner)).await
+    }
+
+    /// Pays the winner's share into `winner_token` instead of their ATA
+    pub async fn payout_to(&mut self, keys: &PoolKeys, winner_token: Pubkey) -> Result<(), BanksClientError> {
+        let dev = self.dev.insecure_clone();
+        let winner = self.pool(keys).await.winner;
+        let accounts = ml::accounts::PayoutWinner {
+            mint: self.mint,
+            pool: keys.pool,
+            pool_token: keys.pool_token,
+            winner_token,
+            dev_token: self.ata(&dev.pubkey()),
+            treasury_token: self.ata(&self.treasury.pubkey()),
+            creator_token: None,
+            token_program: spl_token::ID,
+            winner_pubkey: winner,
+            user: dev.pubkey(),
+            participants: keys.participants,
+        };
+        self.send(&[ix(accounts, ml::instruction::PayoutWinner {})], &[&dev]).await
+    }
+
+    pub async fn pause(&mut self, keys: &PoolKeys) -> Result<(), BanksClientError> {
+        let dev = self.dev.insecure_clone();
+        let accounts = ml::accounts::PausePool {
+            pool: keys.pool,
+            user: dev.pubkey(),
+            participants: keys.participants,
+        };
+        self.send(&[ix(accounts, ml::instruction::PausePool {})], &[&dev]).await
+    }
+
+    pub async fn unpause(&mut self, keys: &PoolKeys) -> Result<(), BanksClientError> {
+        let dev = self.dev.insecure_clone();
+        let accounts = ml::accounts::PausePool {
+            pool: keys.pool,
+            user: dev.pubkey(),
+            participants: keys.participants,
+        };
+        self.send(&[ix(accounts, ml::instruction::UnpausePool {})], &[&dev]).await
+    }
+
+    pub async fn cancel(&mut self, keys: &PoolKeys, creator: &Keypair) -> Result<(), BanksClientError> {
+        let accounts = ml::accounts::CancelPool {
+            mint: self.mint,
+            pool: keys.pool,
+            pool_token: keys.pool_token,
+            user: creator.pubkey(),
+            token_program: spl_token::ID,
+            system_program: anchor_lang::system_program::ID,
+            participants: keys.participants,
+            treasury_wallet: self.treasury.pubkey(),
+        };
+        self.send(&[ix(accounts, ml::instruction::CancelPool {})], &[creator]).await
+    }
+
+    /// Cancels an expired pool; anyone may once `SWEEP_DELAY` has passed
+    pub async fn sweep(&mut self, keys: &PoolKeys, user: &Keypair) -> Result<(), BanksClientError> {
+        let accounts = ml::accounts::SweepExpiredPool {
+            mint: self.mint,
+            pool: keys.pool,
+            pool_token: keys.pool_token,
+            user: user.pubkey(),
+            token_program: spl_token::ID,
+            system_program: anchor_lang::system_program::ID,
+            participants: keys.participants,
+        };
+        self.send(&[ix(accounts, ml::instruction::SweepExpiredPool {})], &[user]).await
+    }
+
+    pub async fn claim_refund(&mut self, keys: &PoolKeys, user: &Keypair) -> Result<(), BanksClientError> {
+        self.claim_refund_to(keys, user, self.ata(&user.pubkey())).await
+    }
+
+    /// Refunds `user`'s entry into `user_token` instead of their ATA
+    pub async fn claim_refund_to(
+        &mut self,
+        keys: &PoolKeys,
+        user: &Keypair,
+        user_token: Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let accounts = ml::accounts::ClaimRefund {
+            mint: self.mint,
+            pool: keys.pool,
+            pool_token: keys.pool_token,
+            user_token,
+            treasury_token: self.ata(&self.treasury.pubkey()),
+            user: user.pubkey(),
+            token_program: spl_token::ID,
+            participants: keys.participants,
+        };
+        self.send(&[ix(accounts, ml::instruction::ClaimRefund {})], &[user]).await
+    }
+
+    pub async fn transfer_entry(
+        &mut self,
+        keys: &PoolKeys,
+        user: &Keypair,
+        new_owner: &Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let accounts = ml::accounts::TransferEntry {
+            pool: keys.pool,
+            participants: keys.participants,
+            user: user.pubkey(),
+            new_owner: *new_owner,
+        };
+        self.send(&[ix(accounts, ml::instruction::TransferEntry {})], &[user]).await
+    }
+
+    /// Sweeps unclaimed refunds to the treasury (dev-signed)
+    pub async fn finalize_forfeited(&mut self, keys: &PoolKeys) -> Result<(), BanksClientError> {
+        let dev = self.dev.insecure_clone();
+        let accounts = ml::accounts::ForfeitUnclaimed {
+            mint: self.mint,
+            pool: keys.pool,
+            pool_token: keys.pool_token,
+            treasury_token: self.ata(&self.treasury.pubkey()),
+            user: dev.pubkey(),
+            token_program: spl_token::ID,
+            participants: keys.participants,
+        };
+        self.send(&[ix(accounts, ml::instruction::FinalizeForfeitedPool {})], &[&dev]).await
+    }
+
+    /// Create → fill → lock → unlock → mock randomness → draw → payout
+    pub async fn run_to_payout(&mut self, creator: &Keypair, joiners: &[Keypair], salt: [u8; 32]) -> PoolKeys {
+        let keys = self.create_pool(creator, salt, joiners.len() as u8 + 1).await.unwrap();
+        for joiner in joiners {
+            self.join(&keys, joiner).await.unwrap();
+        }
+        self.warp_seconds(LOCK_DURATION + 1).await;
+        self.unlock(&keys).await.unwrap();
+        self.request_randomness(&keys).await.unwrap();
+        self.warp_slots(1).await;
+        self.select_winner(&keys).await.unwrap();
+        self.payout(&keys).await.unwrap();
+        keys
+    }
+}
+
+pub fn ix(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: ml::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+pub fn salt(n: u8) -> [u8; 32] {
+    [n; 32]
+}
@@ -0,0 +1,468 @@
+//! End-to-end pool lifecycle against the natively-built program.
+//!
+//! Run with `cargo test -p ml --features test-utils`.
+
+mod common;
+
+use common::*;
+use ml::{constants::*, state::PoolStatus};
+use solana_sdk::signature::Signer;
+
+#[tokio::test]
+async fn full_lifecycle_pays_winner_and_fees() {
+    let mut h = Harness::new().await;
+    let creator = h.wallet().await;
+    let joiners = [h.wallet().await, h.wallet().await, h.wallet().await];
+
+    let keys = h.create_pool(&creator, salt(1), 4).await.unwrap();
+    assert_eq!(h.pool(&keys).await.status, PoolStatus::Open);
+
+    for (i, joiner) in joiners.iter().enumerate() {
+        h.join(&keys, joiner).await.unwrap();
+        assert_eq!(h.participants(&keys).await.count as usize, i + 2);
+    }
+
+    // Last join fills the pool and starts the lock
+    let pool = h.pool(&keys).await;
+    assert_eq!(pool.status, PoolStatus::Locked);
+    assert_eq!(pool.total_amount, 4 * ENTRY);
+    assert_eq!(h.balance(&keys.pool_token).await, 4 * ENTRY);
+
+    // Still inside the lock window
+    assert!(h.unlock(&keys).await.is_err());
+    h.warp_seconds(LOCK_DURATION + 1).await;
+    h.unlock(&keys).await.unwrap();
+    assert_eq!(h.pool(&keys).await.status, PoolStatus::Unlocked);
+
+    h.request_randomness(&keys).await.unwrap();
+    assert_eq!(h.pool(&keys).await.status, PoolStatus::RandomnessCommitted);
+
+    h.warp_slots(1).await;
+    h.select_winner(&keys).await.unwrap();
+    let pool = h.pool(&keys).await;
+    assert_eq!(pool.status, PoolStatus::WinnerSelected);
+    let entrants: Vec<_> = std::iter::once(&creator).chain(&joiners).map(|k| k.pubkey()).collect();
+    assert!(entrants.contains(&pool.winner));
+
+    let winner_before = h.balance(&h.ata(&pool.winner)).await;
+    let dev_before = h.balance(&h.ata(&h.dev.pubkey())).await;
+    let treasury_before = h.balance(&h.ata(&h.treasury.pubkey())).await;
+
+    h.payout(&keys).await.unwrap();
+
+    let total = 4 * ENTRY;
+    let dev_amount = total * DEV_FEE_BPS as u64 / 10_000;
+    let burn_amount = total * BURN_FEE_BPS as u64 / 10_000;
+    let treasury_amount = total * TREASURY_FEE_BPS as u64 / 10_000;
+    let winner_amount = total - dev_amount - burn_amount - treasury_amount;
+
+    assert_eq!(h.balance(&h.ata(&pool.winner)).await - winner_before, winner_amount);
+    assert_eq!(h.balance(&h.ata(&h.dev.pubkey())).await - dev_before, dev_amount);
+    assert_eq!(h.balance(&h.ata(&h.treasury.pubkey())).await - treasury_before, treasury_amount);
+    assert_eq!(h.balance(&keys.pool_token).await, 0);
+    assert_eq!(h.pool(&keys).await.status, PoolStatus::Ended);
+
+    // No second payout
+    assert!(h.payout(&keys).await.is_err());
+}
+
+#[tokio::test]
+async fn join_rejected_after_lock() {
+    let mut h = Harness::new().await;
+    let creator = h.wallet().await;
+    let joiner = h.wallet().await;
+    let late = h.wallet().await;
+
+    let keys = h.create_pool(&creator, salt(2), 2).await.unwrap();
+    h.join(&keys, &joiner).await.unwrap();
+    assert_eq!(h.pool(&keys).await.status, PoolStatus::Locked);
+
+    assert!(h.join(&keys, &late).await.is_err());
+    assert_eq!(h.participants(&keys).await.count, 2);
+}
+
+#[tokio::test]
+async fn duplicate_join_rejected() {
+    let mut h = Harness::new().await;
+    let creator = h.wallet().await;
+    let joiner = h.wallet().await;
+
+    let keys = h.create_pool(&creator, salt(3), 5).await.unwrap();
+    h.join(&keys, &joiner).await.unwrap();
+    assert!(h.join(&keys, &joiner).await.is_err());
+}
+
+#[tokio::test]
+async fn cancel_then_refund() {
+    let mut h = Harness::new().await;
+    let creator = h.wallet().await;
+    let joiner = h.wallet().await;
+
+    let keys = h.create_pool(&creator, salt(4), 5).await.unwrap();
+    h.join(&keys, &joiner).await.unwrap();
+
+    // Only the creator may cancel
+    assert!(h.cancel(&keys, &joiner).await.is_err());
+    h.cancel(&keys, &creator).await.unwrap();
+
+    let pool = h.pool(&keys).await;
+    assert_eq!(pool.status, PoolStatus::Cancelled);
+    assert_eq!(pool.status_reason, REASON_CANCELLED);
+    // Others had joined: the bond went to the treasury
+    assert_eq!(pool.creator_bond, 0);
+
+    let joiner_ata = h.ata(&joiner.pubkey());
+    let before = h.balance(&joiner_ata).await;
+    h.claim_refund(&keys, &joiner).await.unwrap();
+    assert_eq!(h.balance(&joiner_ata).await - before, ENTRY);
+
+    // Slot released, second claim fails
+    assert_eq!(h.participants(&keys).await.count, 1);
+    assert!(h.claim_refund(&keys, &joiner).await.is_err());
+
+    // Creator refund burns 5%
+    let creator_ata = h.ata(&creator.pubkey());
+    let before = h.balance(&creator_ata).await;
+    h.claim_refund(&keys, &creator).await.unwrap();
+    assert_eq!(h.balance(&creator_ata).await - before, ENTRY - ENTRY / 20);
+    assert_eq!(h.balance(&keys.pool_token).await, 0);
+}
+
+#[tokio::test]
+async fn payout_goes_to_any_token_account_of_the_winner() {
+    let mut h = Harness::new().await;
+    let creator = h.wallet().await;
+    let joiner = h.wallet().await;
+
+    let keys = h.create_pool(&creator, salt(15), 2).await.unwrap();
+    h.join(&keys, &joiner).await.unwrap();
+    h.warp_seconds(LOCK_DURATION + 1).await;
+    h.unlock(&keys).await.unwrap();
+    h.request_randomness(&keys).await.unwrap();
+    h.warp_slots(1).await;
+    h.select_winner(&keys).await.unwrap();
+
+    // A vault that is not the winner's ATA, as a PDA entrant would hold
+    let winner = h.pool(&keys).await.winner;
+    let vault = h.token_account(&winner).await;
+    let stranger = h.token_account(&h.dev.pubkey()).await;
+    assert!(h.payout_to(&keys, stranger).await.is_err());
+    h.payout_to(&keys, vault).await.unwrap();
+
+    let total = 2 * ENTRY;
+    let fees = total * (DEV_FEE_BPS + BURN_FEE_BPS + TREASURY_FEE_BPS) as u64 / 10_000;
+    assert_eq!(h.balance(&vault).await, total - fees);
+    assert_eq!(h.pool(&keys).await.status, PoolStatus::Ended);
+}
+
+#[tokio::test]
+async fn refund_goes_to_any_token_account_of_the_participant() {
+    let mut h = Harness::new().await;
+    let creator = h.wallet().await;
+    let joiner = h.wallet().await;
+
+    let keys = h.create_pool(&creator, salt(16), 5).await.unwrap();
+    h.join(&keys, &joiner).await.unwrap();
+    h.cancel(&keys, &creator).await.unwrap();
+
+    let creator_vault = h.token_account(&creator.pubkey()).await;
+    assert!(h.claim_refund_to(&keys, &joiner, creator_vault).await.is_err());
+
+    let vault = h.token_account(&joiner.pubkey()).await;
+    h.claim_refund_to(&keys, &joiner, vault).await.unwrap();
+    assert_eq!(h.balance(&vault).await, ENTRY);
+}
+
+#[tokio::test]
+async fn creator_entry_stays_with_the_creator() {
+    let mut h = Harness::new().await;
+    let creator = h.wallet().await;
+    let joiner = h.wallet().await;
+    let buyer = h.wallet().await;
+
+    let keys = h.create_pool(&creator, salt(10), 5).await.unwrap();
+    h.join(&keys, &joiner).await.unwrap();
+
+    // Giving the slot away would let the new holder refund without the 5% burn
+    assert!(h.transfer_entry(&keys, &creator, &buyer.pubkey()).await.is_err());
+    assert_eq!(h.participants(&keys).await.list[0], creator.pubkey());
+
+    h.transfer_entry(&keys, &joiner, &buyer.pubkey()).await.unwrap();
+    assert_eq!(h.participants(&keys).await.list[1], buyer.pubkey());
+}
+
+#[tokio::test]
+async fn unclaimed_refunds_forfeit_to_treasury() {
+    let mut h = Harness::new().await;
+    let creator = h.wallet().await;
+    let joiner = h.wallet().await;
+
+    let keys = h.create_pool(&creator, salt(5), 5).await.unwrap();
+    h.join(&keys, &joiner).await.unwrap();
+    h.cancel(&keys, &creator).await.unwrap();
+
+    h.warp_seconds(FORFEIT_DELAY + 1).await;
+
+    let treasury_ata = h.ata(&h.treasury.pubkey());
+    let before = h.balance(&treasury_ata).await;
+    h.finalize_forfeited(&keys).await.unwrap();
+
+    assert_eq!(h.balance(&treasury_ata).await - before, 2 * ENTRY);
+    assert_eq!(h.balance(&keys.pool_token).await, 0);
+    assert_eq!(h.pool(&keys).await.status, PoolStatus::Closed);
+
+    // Nothing left to refund
+    assert!(h.claim_refund(&keys, &joiner).await.is_err());
+}
+
+#[tokio::test]
+async fn anyone_sweeps_a_paused_pool_after_the_delay() {
+    let mut h = Harness::new().await;
+    let creator = h.wallet().await;
+    let joiner = h.wallet().await;
+
+    let keys = h.create_pool(&creator, salt(17), 5).await.unwrap();
+    h.join(&keys, &joiner).await.unwrap();
+    h.pause(&keys).await.unwrap();
+
+    h.warp_seconds(POOL_OPEN_DURATION).await;
+    assert!(h.sweep(&keys, &joiner).await.is_err());
+
+    h.warp_seconds(SWEEP_DELAY + 1).await;
+    h.sweep(&keys, &joiner).await.unwrap();
+    let pool = h.pool(&keys).await;
+    assert_eq!(pool.status, PoolStatus::Cancelled);
+    assert_eq!(pool.status_reason, REASON_EXPIRED);
+
+    let joiner_ata = h.ata(&joiner.pubkey());
+    let before = h.balance(&joiner_ata).await;
+    h.claim_refund(&keys, &joiner).await.unwrap();
+    assert_eq!(h.balance(&joiner_ata).await - before, ENTRY);
+}
+
+#[tokio::test]
+async fn pause_extends_lock_window() {
+    let mut h = Harness::new().await;
+    let creator = h.wallet().await;
+    let joiner = h.wallet().await;
+
+    let keys = h.create_pool(&creator, salt(6), 2).await.unwrap();
+    h.join(&keys, &joiner).await.unwrap();
+
+    h.pause(&keys).await.unwrap();
+    h.warp_seconds(LOCK_DURATION).await;
+    h.unpause(&keys).await.unwrap();
+
+    // The paused stretch doesn't count towards the lock
+    h.warp_seconds(1).await;
+    assert!(h.unlock(&keys).await.is_err());
+
+    h.warp_seconds(LOCK_DURATION).await;
+    h.unlock(&keys).await.unwrap();
+}
+
+#[tokio::test]
+async fn run_to_payout_helper_ends_pool() {
+    let mut h = Harness::new().await;
+    let creator = h.wallet().await;
+    let joiners = [h.wallet().await];
+
+    let keys = h.run_to_payout(&creator, &joiners, salt(7)).await;
+    assert_eq!(h.pool(&keys).await.status, PoolStatus::Ended);
+}
+
+#[tokio::test]
+async fn donations_past_the_cap_are_rejected() {
+    let mut h = Harness::new().await;
+    let creator = h.wallet().await;
+    let donor = h.wallet().await;
+
+    let data = ml::instruction::CreatePool { max_donations_amount: 2 * ENTRY, ..h.pool_config(salt(8), 4) };
+    let keys = h.create_pool_from(&creator, data).await.unwrap();
+
+    h.donate(&keys, &donor, ENTRY).await.unwrap();
+    h.donate(&keys, &donor, ENTRY).await.unwrap();
+    // A third would take the donations past the cap
+    assert!(h.donate(&keys, &donor, ENTRY).await.is_err());
+
+    let pool = h.pool(&keys).await;
+    assert_eq!(pool.total_donated, 2 * ENTRY);
+    assert_eq!(pool.total_amount, 3 * ENTRY);
+}
+
+#[tokio::test]
+async fn donations_resume_once_the_pool_locks_at_the_join_deadline() {
+    let mut h = Harness::new().await;
+    let creator = h.wallet().await;
+    let joiner = h.wallet().await;
+    let late = h.wallet().await;
+    let donor = h.wallet().await;
+
+    let join_deadline = h.clock().await.unix_timestamp + 3_600;
+    let data = ml::instruction::CreatePool { join_deadline, ..h.pool_config(salt(9), 4) };
+    let keys = h.create_pool_from(&creator, data).await.unwrap();
+    assert_eq!(h.pool(&keys).await.join_deadline, join_deadline);
+    h.join(&keys, &joiner).await.unwrap();
+
+    // Entries closed but not yet locked: neither joins nor donations go through
+    assert!(h.lock_at_deadline(&keys).await.is_err());
+    h.warp_seconds(3_601).await;
+    assert!(h.join(&keys, &late).await.is_err());
+    assert!(h.donate(&keys, &donor, ENTRY).await.is_err());
+
+    h.lock_at_deadline(&keys).await.unwrap();
+    let pool = h.pool(&keys).await;
+    assert_eq!(pool.status, PoolStatus::Locked);
+    assert_eq!(pool.status_reason, REASON_JOIN_DEADLINE);
+
+    h.donate(&keys, &donor, ENTRY).await.unwrap();
+    assert_eq!(h.pool(&keys).await.total_amount, 3 * ENTRY);
+}
+
+#[tokio::test]
+async fn a_lone_creator_cannot_lock_at_the_join_deadline() {
+    let mut h = Harness::new().await;
+    let creator = h.wallet().await;
+    let donor = h.wallet().await;
+
+    let join_deadline = h.clock().await.unix_timestamp + 3_600;
+    let data = ml::instruction::CreatePool { join_deadline, ..h.pool_config(salt(11), 4) };
+    let keys = h.create_pool_from(&creator, data).await.unwrap();
+
+    h.warp_seconds(3_601).await;
+    assert!(h.lock_at_deadline(&keys).await.is_err());
+    assert!(h.donate(&keys, &donor, ENTRY).await.is_err());
+    assert_eq!(h.pool(&keys).await.status, PoolStatus::Open);
+}
+
+#[tokio::test]
+async fn clone_keeps_the_join_deadline_offset() {
+    let mut h = Harness::new().await;
+    let creator = h.wallet().await;
+
+    let join_deadline = h.clock().await.unix_timestamp + 3_600;
+    let data = ml::instruction::CreatePool { join_deadline, ..h.pool_config(salt(13), 4) };
+    let source = h.create_pool_from(&creator, data).await.unwrap();
+
+    h.warp_seconds(600).await;
+    let keys = h.clone_pool(&creator, &source, salt(14)).await.unwrap();
+
+    let (source, pool) = (h.pool(&source).await, h.pool(&keys).await);
+    assert_eq!(pool.join_deadline - pool.start_time, source.join_deadline - source.start_time);
+    assert!(pool.join_deadline < pool.expire_time);
+    assert_eq!(pool.config_hash, pool.compute_config_hash());
+    assert_ne!(pool.config_hash, source.config_hash);
+}
+
+#[tokio::test]
+async fn pool_locked_at_the_join_deadline_runs_to_payout() {
+    let mut h = Harness::new().await;
+    let creator = h.wallet().await;
+    let joiner = h.wallet().await;
+
+    let join_deadline = h.clock().await.unix_timestamp + 3_600;
+    let data = ml::instruction::CreatePool { join_deadline, ..h.pool_config(salt(12), 4) };
+    let keys = h.create_pool_from(&creator, data).await.unwrap();
+    h.join(&keys, &joiner).await.unwrap();
+
+    h.warp_seconds(3_601).await;
+    h.lock_at_deadline(&keys).await.unwrap();
+
+    assert!(h.unlock(&keys).await.is_err());
+    h.warp_seconds(LOCK_DURATION + 1).await;
+    h.unlock(&keys).await.unwrap();
+    h.request_randomness(&keys).await.unwrap();
+    h.warp_slots(1).await;
+    h.select_winner(&keys).await.unwrap();
+
+    let winner = h.pool(&keys).await.winner;
+    assert!(winner == creator.pubkey() || winner == joiner.pubkey());
+    h.payout(&keys).await.unwrap();
+
+    assert_eq!(h.balance(&keys.pool_token).await, 0);
+    assert_eq!(h.pool(&keys).await.status, PoolStatus::Ended);
+}
+
+#[tokio::test]
+async fn cancelling_after_others_joined_slashes_the_creator_bond() {
+    let mut h = Harness::new().await;
+    let creator = h.wallet().await;
+    let joiner = h.wallet().await;
+    let treasury = h.treasury.pubkey();
+
+    // Alone in the pool the creator keeps the bond
+    let solo = h.create_pool(&creator, salt(18), 5).await.unwrap();
+    assert_eq!(h.pool(&solo).await.creator_bond, CREATOR_BOND_LAMPORTS);
+    let before = h.lamports(&treasury).await;
+    h.cancel(&solo, &creator).await.unwrap();
+    assert_eq!(h.pool(&solo).await.creator_bond, CREATOR_BOND_LAMPORTS);
+    assert_eq!(h.lamports(&treasury).await, before);
+
+    let keys = h.create_pool(&creator, salt(19), 5).await.unwrap();
+    h.join(&keys, &joiner).await.unwrap();
+    let pool_before = h.lamports(&keys.pool).await;
+    h.cancel(&keys, &creator).await.unwrap();
+
+    assert_eq!(h.pool(&keys).await.creator_bond, 0);
+    assert_eq!(h.lamports(&treasury).await - before, CREATOR_BOND_LAMPORTS);
+    assert_eq!(pool_before - h.lamports(&keys.pool).await, CREATOR_BOND_LAMPORTS);
+}
+
+#[tokio::test]
+async fn usd_minimum_is_checked_against_the_price_feed() {
+    let mut h = Harness::new().await;
+    let creator = h.wallet().await;
+    let feed_id = [7; 32];
+    h.register_price_feed(feed_id, 60);
+
+    // A registered feed makes the price update mandatory
+    assert!(h.create_pool_from(&creator, h.pool_config(salt(24), 4)).await.is_err());
+
+    // ENTRY is 25 tokens: $0.03 a token is below the $1 minimum, $0.05 clears it
+    let cheap = h.price_update(feed_id, 3, -2, 0).await;
+    assert!(h.create_pool_priced(&creator, h.pool_config(salt(24), 4), cheap).await.is_err());
+
+    let stale = h.price_update(feed_id, 5, -2, 61).await;
+    assert!(h.create_pool_priced(&creator, h.pool_config(salt(24), 4), stale).await.is_err());
+
+    let other_feed = h.price_update([8; 32], 5, -2, 0).await;
+    assert!(h.create_pool_priced(&creator, h.pool_config(salt(24), 4), other_feed).await.is_err());
+
+    let fresh = h.price_update(feed_id, 5, -2, 0).await;
+    let keys = h.create_pool_priced(&creator, h.pool_config(salt(24), 4), fresh).await.unwrap();
+    let pool = h.pool(&keys).await;
+    assert_eq!(pool.min_bet_usd, MIN_BET_USD_MICROS);
+    assert_eq!(pool.min_bet_native, 0);
+}
+
+#[tokio::test]
+async fn clone_carries_the_source_config() {
+    let mut h = Harness::new().await;
+    let creator = h.wallet().await;
+
+    let data = ml::instruction::CreatePool {
+        lock_duration: 2 * LOCK_DURATION,
+        end_amount: 2 * ENTRY,
+        max_donations_amount: 3 * ENTRY,
+        ..h.pool_config(salt(22), 6)
+    };
+    let source = h.create_pool_from(&creator, data).await.unwrap();
+    let keys = h.clone_pool(&creator, &source, salt(23)).await.unwrap();
+
+    let (source, pool) = (h.pool(&source).await, h.pool(&keys).await);
+    assert_eq!(pool.max_participants, source.max_participants);
+    assert_eq!(pool.lock_duration, source.lock_duration);
+    assert_eq!((pool.amount, pool.end_amount), (source.amount, source.end_amount));
+    assert_eq!(pool.max_donations_amount, source.max_donations_amount);
+    assert_eq!(
+        (pool.dev_fee_bps, pool.burn_fee_bps, pool.treasury_fee_bps),
+        (source.dev_fee_bps, source.burn_fee_bps, source.treasury_fee_bps)
+    );
+    assert_eq!((pool.dev_wallet, pool.treasury_wallet), (source.dev_wallet, source.treasury_wallet));
+    assert_eq!(pool.allow_mock, source.allow_mock);
+
+    // The hash covers the new pool's own salt and times
+    assert_eq!(pool.config_hash, pool.compute_config_hash());
+    assert_ne!(pool.config_hash, source.config_hash);
+    assert_eq!(h.participants(&keys).await.list[0], creator.pubkey());
+}
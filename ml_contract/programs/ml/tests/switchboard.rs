@@ -0,0 +1,79 @@
+//! Strict Switchboard randomness path, driven by mock `RandomnessAccountData`
+//! accounts written straight into the bank.
+//!
+//! Run with `cargo test -p ml --features test-utils`.
+
+mod common;
+
+use common::*;
+use ml::state::PoolStatus;
+use ml_test_utils::{install_randomness, MockRandomness};
+use solana_sdk::signature::{Keypair, Signer};
+
+const MAINNET: bool = cfg!(feature = "mainnet");
+
+#[tokio::test]
+async fn select_winner_reads_revealed_randomness() {
+    let mut h = Harness::new().await;
+    let creator = h.wallet().await;
+    let joiners = [h.wallet().await, h.wallet().await];
+
+    let keys = h.create_pool_with(&creator, salt(20), 3, false).await.unwrap();
+    for joiner in &joiners {
+        h.join(&keys, joiner).await.unwrap();
+    }
+    h.warp_seconds(LOCK_DURATION + 1).await;
+    h.unlock(&keys).await.unwrap();
+
+    // Committed but unrevealed, seeded at the current slot
+    let randomness = Keypair::new().pubkey();
+    let committed = MockRandomness::committed(h.clock().await.slot);
+    install_randomness(&mut h.ctx, &randomness, &committed, MAINNET);
+
+    h.request_randomness_with(&keys, randomness).await.unwrap();
+    let pool = h.pool(&keys).await;
+    assert_eq!(pool.status, PoolStatus::RandomnessCommitted);
+    assert_eq!(pool.randomness_account, randomness);
+
+    // Oracle has not revealed yet
+    h.warp_slots(1).await;
+    assert!(h.select_winner_with(&keys, randomness).await.is_err());
+
+    let reveal_slot = h.clock().await.slot;
+    install_randomness(&mut h.ctx, &randomness, &committed.clone().with_u128(reveal_slot, 0xC0FFEE), MAINNET);
+
+    // Only the committed account is accepted
+    let other = Keypair::new().pubkey();
+    install_randomness(&mut h.ctx, &other, &committed.with_u128(reveal_slot, 1), MAINNET);
+    assert!(h.select_winner_with(&keys, other).await.is_err());
+
+    h.select_winner_with(&keys, randomness).await.unwrap();
+    let pool = h.pool(&keys).await;
+    assert_eq!(pool.status, PoolStatus::WinnerSelected);
+    assert_eq!(pool.randomness, 0xC0FFEE);
+    let entrants: Vec<_> = std::iter::once(&creator).chain(&joiners).map(|k| k.pubkey()).collect();
+    assert!(entrants.contains(&pool.winner));
+
+    h.payout(&keys).await.unwrap();
+    assert_eq!(h.pool(&keys).await.status, PoolStatus::Ended);
+}
+
+#[tokio::test]
+async fn request_randomness_rejects_stale_seed() {
+    let mut h = Harness::new().await;
+    let creator = h.wallet().await;
+    let joiner = h.wallet().await;
+
+    let keys = h.create_pool_with(&creator, salt(21), 2, false).await.unwrap();
+    h.join(&keys, &joiner).await.unwrap();
+    h.warp_seconds(LOCK_DURATION + 1).await;
+    h.unlock(&keys).await.unwrap();
+
+    let randomness = Keypair::new().pubkey();
+    let slot = h.clock().await.slot;
+    install_randomness(&mut h.ctx, &randomness, &MockRandomness::committed(slot), MAINNET);
+    h.warp_slots(301).await;
+
+    assert!(h.request_randomness_with(&keys, randomness).await.is_err());
+    assert_eq!(h.pool(&keys).await.status, PoolStatus::Unlocked);
+}
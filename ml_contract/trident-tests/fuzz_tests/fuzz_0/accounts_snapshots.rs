@@ -0,0 +1,118 @@
+//! Pre/post instruction views of the pool state used by the `check` invariants.
+//!
+//! Only the pool, its `Participants` and the pool vault are decoded; other accounts
+//! are skipped positionally, so the field order below follows each `#[derive(Accounts)]`.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+use ml::state::{Participants, Pool};
+use trident_client::fuzzing::{anchor_lang, FuzzingError};
+
+/// Pool-centric snapshot shared by every fuzzed instruction
+pub struct PoolSnapshot<'info> {
+    pub pool: Option<Account<'info, Pool>>,
+    pub participants: Option<Account<'info, Participants>>,
+    pub pool_token: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+pub type CreatePoolSnapshot<'info> = PoolSnapshot<'info>;
+pub type JoinPoolSnapshot<'info> = PoolSnapshot<'info>;
+pub type CancelPoolSnapshot<'info> = PoolSnapshot<'info>;
+pub type ClaimRefundSnapshot<'info> = PoolSnapshot<'info>;
+pub type UnlockPoolSnapshot<'info> = PoolSnapshot<'info>;
+pub type RequestRandomnessSnapshot<'info> = PoolSnapshot<'info>;
+pub type SelectWinnerSnapshot<'info> = PoolSnapshot<'info>;
+pub type PayoutWinnerSnapshot<'info> = PoolSnapshot<'info>;
+
+/// Positions of the pool accounts inside an instruction's account list
+struct Layout {
+    pool: usize,
+    participants: usize,
+    pool_token: Option<usize>,
+}
+
+fn decode<'info, T: AccountSerialize + AccountDeserialize + Owner + Clone>(
+    accounts: &'info [Option<AccountInfo<'info>>],
+    index: usize,
+    name: &str,
+) -> core::result::Result<Option<Account<'info, T>>, FuzzingError> {
+    match accounts.get(index).ok_or(FuzzingError::NotEnoughAccounts(name.to_string()))? {
+        Some(info) if !info.data_is_empty() => Account::try_from(info)
+            .map(Some)
+            .map_err(|_| FuzzingError::CannotDeserializeAccount(name.to_string())),
+        _ => Ok(None),
+    }
+}
+
+impl<'info> PoolSnapshot<'info> {
+    fn deserialize_layout(
+        accounts: &'info mut [Option<AccountInfo<'info>>],
+        layout: Layout,
+    ) -> core::result::Result<Self, FuzzingError> {
+        let accounts: &'info [Option<AccountInfo<'info>>] = accounts;
+        let pool = decode::<Pool>(accounts, layout.pool, "pool")?;
+        let participants = decode::<Participants>(accounts, layout.participants, "participants")?;
+        let pool_token = match layout.pool_token.and_then(|i| accounts.get(i)).and_then(Option::as_ref) {
+            Some(info) if !info.data_is_empty() => InterfaceAccount::try_from(info).ok(),
+            _ => None,
+        };
+        Ok(Self { pool, participants, pool_token })
+    }
+}
+
+// mint, pool, user_token, user, payer, pool_token, token_program, ata_program, system, rent, participants
+pub fn create_pool_deserialize<'info>(
+    accounts: &'info mut [Option<AccountInfo<'info>>],
+) -> core::result::Result<CreatePoolSnapshot<'info>, FuzzingError> {
+    PoolSnapshot::deserialize_layout(accounts, Layout { pool: 1, participants: 10, pool_token: Some(5) })
+}
+
+// mint, pool, pool_token, user_token, user, token_program, participants
+pub fn join_pool_deserialize<'info>(
+    accounts: &'info mut [Option<AccountInfo<'info>>],
+) -> core::result::Result<JoinPoolSnapshot<'info>, FuzzingError> {
+    PoolSnapshot::deserialize_layout(accounts, Layout { pool: 1, participants: 6, pool_token: Some(2) })
+}
+
+// mint, pool, pool_token, user, token_program, system_program, participants
+pub fn cancel_pool_deserialize<'info>(
+    accounts: &'info mut [Option<AccountInfo<'info>>],
+) -> core::result::Result<CancelPoolSnapshot<'info>, FuzzingError> {
+    PoolSnapshot::deserialize_layout(accounts, Layout { pool: 1, participants: 6, pool_token: Some(2) })
+}
+
+// mint, pool, pool_token, user_token, treasury_token, user, token_program, participants
+pub fn claim_refund_deserialize<'info>(
+    accounts: &'info mut [Option<AccountInfo<'info>>],
+) -> core::result::Result<ClaimRefundSnapshot<'info>, FuzzingError> {
+    PoolSnapshot::deserialize_layout(accounts, Layout { pool: 1, participants: 7, pool_token: Some(2) })
+}
+
+// pool, user, participants
+pub fn unlock_pool_deserialize<'info>(
+    accounts: &'info mut [Option<AccountInfo<'info>>],
+) -> core::result::Result<UnlockPoolSnapshot<'info>, FuzzingError> {
+    PoolSnapshot::deserialize_layout(accounts, Layout { pool: 0, participants: 2, pool_token: None })
+}
+
+// randomness, pool, user, participants
+pub fn request_randomness_deserialize<'info>(
+    accounts: &'info mut [Option<AccountInfo<'info>>],
+) -> core::result::Result<RequestRandomnessSnapshot<'info>, FuzzingError> {
+    PoolSnapshot::deserialize_layout(accounts, Layout { pool: 1, participants: 3, pool_token: None })
+}
+
+// pool, randomness, user, participants
+pub fn select_winner_deserialize<'info>(
+    accounts: &'info mut [Option<AccountInfo<'info>>],
+) -> core::result::Result<SelectWinnerSnapshot<'info>, FuzzingError> {
+    PoolSnapshot::deserialize_layout(accounts, Layout { pool: 0, participants: 3, pool_token: None })
+}
+
+// mint, pool, pool_token, winner_token, dev_token, treasury_token, creator_token,
+// token_program, ata_program, system_program, winner_pubkey, user, participants
+pub fn payout_winner_deserialize<'info>(
+    accounts: &'info mut [Option<AccountInfo<'info>>],
+) -> core::result::Result<PayoutWinnerSnapshot<'info>, FuzzingError> {
+    PoolSnapshot::deserialize_layout(accounts, Layout { pool: 1, participants: 12, pool_token: Some(2) })
+}
@@ -0,0 +1,648 @@
+pub mod ml_fuzz_instructions {
+    use crate::accounts_snapshots::*;
+    use anchor_lang::solana_program::{program_option::COption, program_pack::Pack, sysvar};
+    use anchor_spl::token::spl_token;
+    use ml::{
+        constants::MIN_LOCK_DURATION,
+        state::{Pool, PoolStatus},
+    };
+    use solana_sdk::account::AccountSharedData;
+    use spl_associated_token_account::get_associated_token_address;
+    use trident_client::fuzzing::*;
+
+    // Invariant violations reported through `FuzzingError::Custom`
+    pub const JOIN_AFTER_LOCK: u32 = 1;
+    pub const DOUBLE_PAYOUT: u32 = 2;
+    pub const REFUND_AFTER_PAYOUT: u32 = 3;
+    pub const WINNER_NOT_ENTRANT: u32 = 4;
+    pub const VAULT_NOT_DRAINED: u32 = 5;
+    pub const ILLEGAL_TRANSITION: u32 = 6;
+    pub const OVERFILLED: u32 = 7;
+
+    /// Fixed-supply test mint: no mint or freeze authority, as `create_pool` requires
+    const MINT: Pubkey = Pubkey::new_from_array([0x4d; 32]);
+    const DECIMALS: u8 = 6;
+    const ENTRY: u64 = 25_000_000;
+    const WALLET_TOKENS: u64 = 10_000 * ENTRY;
+    const WALLET_LAMPORTS: u64 = 10 * LAMPORTS_PER_SOL;
+
+    #[derive(Arbitrary, DisplayIx, FuzzTestExecutor, FuzzDeserialize)]
+    pub enum FuzzInstruction {
+        CreatePool(CreatePool),
+        JoinPool(JoinPool),
+        CancelPool(CancelPool),
+        ClaimRefund(ClaimRefund),
+        UnlockPool(UnlockPool),
+        RequestRandomness(RequestRandomness),
+        SelectWinner(SelectWinner),
+        PayoutWinner(PayoutWinner),
+    }
+
+    /// Pool chosen by the fuzzer (salt = `[pool; 32]`) and the signing wallet
+    #[derive(Arbitrary, Debug, Clone, Copy)]
+    pub struct PoolAccounts {
+        pub pool: AccountId,
+        pub user: AccountId,
+    }
+
+    #[derive(Arbitrary, Debug)]
+    pub struct CreatePool {
+        pub accounts: PoolAccounts,
+        pub data: CreatePoolData,
+    }
+    #[derive(Arbitrary, Debug)]
+    pub struct CreatePoolData {
+        pub max_participants: u8,
+        pub lock_extra: u8,
+        pub dev: AccountId,
+        pub treasury: AccountId,
+        pub dev_fee_bps: u16,
+        pub burn_fee_bps: u16,
+        pub treasury_fee_bps: u16,
+    }
+
+    #[derive(Arbitrary, Debug)]
+    pub struct JoinPool {
+        pub accounts: PoolAccounts,
+    }
+
+    #[derive(Arbitrary, Debug)]
+    pub struct CancelPool {
+        pub accounts: PoolAccounts,
+        pub treasury: AccountId,
+    }
+
+    #[derive(Arbitrary, Debug)]
+    pub struct ClaimRefund {
+        pub accounts: PoolAccounts,
+        pub treasury: AccountId,
+    }
+
+    #[derive(Arbitrary, Debug)]
+    pub struct UnlockPool {
+        pub accounts: PoolAccounts,
+        /// Seconds to advance the clock before unlocking
+        pub wait: u16,
+    }
+
+    #[derive(Arbitrary, Debug)]
+    pub struct RequestRandomness {
+        pub accounts: PoolAccounts,
+    }
+
+    #[derive(Arbitrary, Debug)]
+    pub struct SelectWinner {
+        pub accounts: PoolAccounts,
+    }
+
+    #[derive(Arbitrary, Debug)]
+    pub struct PayoutWinner {
+        pub accounts: PoolAccounts,
+        /// Substitutes for the pool's recorded winner / dev / treasury wallets
+        pub winner: Option<AccountId>,
+        pub dev: Option<AccountId>,
+        pub treasury: Option<AccountId>,
+    }
+
+    /// Use AccountsStorage<T> where T can be one of:
+    /// Keypair, PdaStore, TokenStore, MintStore, ProgramStore
+    #[derive(Default)]
+    pub struct FuzzAccounts {
+        users: AccountsStorage<Keypair>,
+    }
+
+    /* ---------- helpers ---------- */
+
+    struct PoolKeys {
+        pool: Pubkey,
+        participants: Pubkey,
+        pool_token: Pubkey,
+    }
+
+    fn pool_keys(pool: AccountId) -> PoolKeys {
+        let salt = [pool; 32];
+        let (pool, _) = Pubkey::find_program_address(&[b"pool", MINT.as_ref(), &salt], &ml::ID);
+        let (participants, _) =
+            Pubkey::find_program_address(&[b"participants", pool.as_ref()], &ml::ID);
+        PoolKeys { pool, participants, pool_token: get_associated_token_address(&pool, &MINT) }
+    }
+
+    fn user(fuzz_accounts: &mut FuzzAccounts, id: AccountId, client: &mut impl FuzzClient) -> Keypair {
+        let user = fuzz_accounts.users.get_or_create_account(id, client, WALLET_LAMPORTS);
+        ensure_mint(client);
+        ensure_ata(client, &user.pubkey());
+        user
+    }
+
+    fn exists(client: &mut impl FuzzClient, address: &Pubkey) -> bool {
+        matches!(client.get_account(address), Ok(Some(_)))
+    }
+
+    fn ensure_mint(client: &mut impl FuzzClient) {
+        if exists(client, &MINT) {
+            return;
+        }
+        let mut data = vec![0u8; spl_token::state::Mint::LEN];
+        spl_token::state::Mint {
+            mint_authority: COption::None,
+            supply: u64::MAX / 2,
+            decimals: DECIMALS,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        }
+        .pack_into_slice(&mut data);
+        client.set_account_custom(
+            &MINT,
+            &AccountSharedData::create(LAMPORTS_PER_SOL, data, spl_token::ID, false, 0),
+        );
+    }
+
+    fn ensure_ata(client: &mut impl FuzzClient, owner: &Pubkey) -> Pubkey {
+        let ata = get_associated_token_address(owner, &MINT);
+        if !exists(client, &ata) {
+            let mut data = vec![0u8; spl_token::state::Account::LEN];
+            spl_token::state::Account {
+                mint: MINT,
+                owner: *owner,
+                amount: WALLET_TOKENS,
+                delegate: COption::None,
+                state: spl_token::state::AccountState::Initialized,
+                is_native: COption::None,
+                delegated_amount: 0,
+                close_authority: COption::None,
+            }
+            .pack_into_slice(&mut data);
+            client.set_account_custom(
+                &ata,
+                &AccountSharedData::create(LAMPORTS_PER_SOL, data, spl_token::ID, false, 0),
+            );
+        }
+        ata
+    }
+
+    fn read_pool(client: &mut impl FuzzClient, pool: &Pubkey) -> Option<Pool> {
+        let account = client.get_account(pool).ok()??;
+        Pool::try_deserialize(&mut account.data.as_slice()).ok()
+    }
+
+    fn status(snapshot: &PoolSnapshot) -> Option<PoolStatus> {
+        snapshot.pool.as_ref().map(|pool| pool.status)
+    }
+
+    fn expect(condition: bool, code: u32) -> Result<(), FuzzingError> {
+        if condition {
+            Ok(())
+        } else {
+            Err(FuzzingError::Custom(code))
+        }
+    }
+
+    /* ---------- instructions ---------- */
+
+    impl<'info> IxOps<'info> for CreatePool {
+        type IxData = ml::instruction::CreatePool;
+        type IxAccounts = FuzzAccounts;
+        type IxSnapshot = CreatePoolSnapshot<'info>;
+
+        fn get_data(
+            &self,
+            client: &mut impl FuzzClient,
+            fuzz_accounts: &mut FuzzAccounts,
+        ) -> Result<Self::IxData, FuzzingError> {
+            let d = &self.data;
+            Ok(ml::instruction::CreatePool {
+                salt: [self.accounts.pool; 32],
+                max_participants: 2 + d.max_participants % 6,
+                lock_duration: MIN_LOCK_DURATION + d.lock_extra as i64,
+                amount: ENTRY,
+                dev_wallet: user(fuzz_accounts, d.dev, client).pubkey(),
+                dev_fee_bps: d.dev_fee_bps % 1_000,
+                burn_fee_bps: d.burn_fee_bps % 1_000,
+                treasury_wallet: user(fuzz_accounts, d.treasury, client).pubkey(),
+                treasury_fee_bps: d.treasury_fee_bps % 1_000,
+                allow_mock: true,
+                end_amount: ENTRY,
+                min_bet_override: 0,
+                max_donations_amount: 0,
+                join_deadline: 0,
+            })
+        }
+
+        fn get_accounts(
+            &self,
+            client: &mut impl FuzzClient,
+            fuzz_accounts: &mut FuzzAccounts,
+        ) -> Result<(Vec<Keypair>, Vec<AccountMeta>), FuzzingError> {
+            let creator = user(fuzz_accounts, self.accounts.user, client);
+            let keys = pool_keys(self.accounts.pool);
+
+            let (creator_counter, _) =
+                Pubkey::find_program_address(&[b"creator_pools", creator.pubkey().as_ref()], &ml::ID);
+            let pool_count = client
+                .get_account(&creator_counter)
+                .ok()
+                .flatten()
+                .and_then(|a| ml::state::CreatorPoolCounter::try_deserialize(&mut a.data.as_slice()).ok())
+                .map_or(0, |c| c.pool_count);
+            let (creator_pool_index, _) = Pubkey::find_program_address(
+                &[b"creator_pool", creator.pubkey().as_ref(), &pool_count.to_le_bytes()],
+                &ml::ID,
+            );
+            let (price_feed, _) = Pubkey::find_program_address(&[b"price_feed", MINT.as_ref()], &ml::ID);
+
+            let metas = ml::accounts::CreatePool {
+                mint: MINT,
+                pool: keys.pool,
+                user_token: get_associated_token_address(&creator.pubkey(), &MINT),
+                user: creator.pubkey(),
+                payer: creator.pubkey(),
+                pool_token: keys.pool_token,
+                token_program: spl_token::ID,
+                associated_token_program: spl_associated_token_account::ID,
+                system_program: solana_sdk::system_program::ID,
+                rent: sysvar::rent::ID,
+                participants: keys.participants,
+                creator_counter,
+                creator_pool_index,
+                price_feed,
+                price_update: None,
+            }
+            .to_account_metas(None);
+            Ok((vec![creator], metas))
+        }
+
+        fn check(
+            &self,
+            _pre_ix: Self::IxSnapshot,
+            post_ix: Self::IxSnapshot,
+            _ix_data: Self::IxData,
+        ) -> Result<(), FuzzingError> {
+            if let (Some(pool), Some(participants), Some(vault)) =
+                (&post_ix.pool, &post_ix.participants, &post_ix.pool_token)
+            {
+                expect(pool.status == PoolStatus::Open, ILLEGAL_TRANSITION)?;
+                expect(participants.count == 1, ILLEGAL_TRANSITION)?;
+                expect(vault.amount == pool.total_amount, ILLEGAL_TRANSITION)?;
+            }
+            Ok(())
+        }
+    }
+
+    impl<'info> IxOps<'info> for JoinPool {
+        type IxData = ml::instruction::JoinPool;
+        type IxAccounts = FuzzAccounts;
+        type IxSnapshot = JoinPoolSnapshot<'info>;
+
+        fn get_data(
+            &self,
+            _client: &mut impl FuzzClient,
+            _fuzz_accounts: &mut FuzzAccounts,
+        ) -> Result<Self::IxData, FuzzingError> {
+            Ok(ml::instruction::JoinPool { amount: ENTRY })
+        }
+
+        fn get_accounts(
+            &self,
+            client: &mut impl FuzzClient,
+            fuzz_accounts: &mut FuzzAccounts,
+        ) -> Result<(Vec<Keypair>, Vec<AccountMeta>), FuzzingError> {
+            let joiner = user(fuzz_accounts, self.accounts.user, client);
+            let keys = pool_keys(self.accounts.pool);
+            let metas = ml::accounts::JoinPool {
+                mint: MINT,
+                pool: keys.pool,
+                pool_token: keys.pool_token,
+                user_token: get_associated_token_address(&joiner.pubkey(), &MINT),
+                user: joiner.pubkey(),
+                token_program: spl_token::ID,
+                participants: keys.participants,
+                ticket_tree_config: None,
+                ticket_tree: None,
+                bubblegum_program: None,
+                log_wrapper: None,
+                compression_program: None,
+                system_program: None,
+            }
+            .to_account_metas(None);
+            Ok((vec![joiner], metas))
+        }
+
+        fn check(
+            &self,
+            pre_ix: Self::IxSnapshot,
+            post_ix: Self::IxSnapshot,
+            _ix_data: Self::IxData,
+        ) -> Result<(), FuzzingError> {
+            let (Some(pre_pool), Some(pre_participants), Some(post_participants)) =
+                (&pre_ix.pool, &pre_ix.participants, &post_ix.participants)
+            else {
+                return Ok(());
+            };
+            expect(pre_pool.status == PoolStatus::Open, JOIN_AFTER_LOCK)?;
+            expect(pre_pool.lock_start_time == 0, JOIN_AFTER_LOCK)?;
+            expect(post_participants.count == pre_participants.count + 1, ILLEGAL_TRANSITION)?;
+            expect(post_participants.count <= pre_pool.max_participants, OVERFILLED)
+        }
+    }
+
+    impl<'info> IxOps<'info> for CancelPool {
+        type IxData = ml::instruction::CancelPool;
+        type IxAccounts = FuzzAccounts;
+        type IxSnapshot = CancelPoolSnapshot<'info>;
+
+        fn get_data(
+            &self,
+            _client: &mut impl FuzzClient,
+            _fuzz_accounts: &mut FuzzAccounts,
+        ) -> Result<Self::IxData, FuzzingError> {
+            Ok(ml::instruction::CancelPool {})
+        }
+
+        fn get_accounts(
+            &self,
+            client: &mut impl FuzzClient,
+            fuzz_accounts: &mut FuzzAccounts,
+        ) -> Result<(Vec<Keypair>, Vec<AccountMeta>), FuzzingError> {
+            let caller = user(fuzz_accounts, self.accounts.user, client);
+            let treasury = user(fuzz_accounts, self.treasury, client);
+            let keys = pool_keys(self.accounts.pool);
+            let metas = ml::accounts::CancelPool {
+                mint: MINT,
+                pool: keys.pool,
+                pool_token: keys.pool_token,
+                user: caller.pubkey(),
+                token_program: spl_token::ID,
+                system_program: solana_sdk::system_program::ID,
+                participants: keys.participants,
+                treasury_wallet: treasury.pubkey(),
+            }
+            .to_account_metas(None);
+            Ok((vec![caller], metas))
+        }
+
+        fn check(
+            &self,
+            pre_ix: Self::IxSnapshot,
+            post_ix: Self::IxSnapshot,
+            _ix_data: Self::IxData,
+        ) -> Result<(), FuzzingError> {
+            if let Some(pre) = status(&pre_ix) {
+                expect(pre == PoolStatus::Open, ILLEGAL_TRANSITION)?;
+                expect(status(&post_ix) == Some(PoolStatus::Cancelled), ILLEGAL_TRANSITION)?;
+            }
+            Ok(())
+        }
+    }
+
+    impl<'info> IxOps<'info> for ClaimRefund {
+        type IxData = ml::instruction::ClaimRefund;
+        type IxAccounts = FuzzAccounts;
+        type IxSnapshot = ClaimRefundSnapshot<'info>;
+
+        fn get_data(
+            &self,
+            _client: &mut impl FuzzClient,
+            _fuzz_accounts: &mut FuzzAccounts,
+        ) -> Result<Self::IxData, FuzzingError> {
+            Ok(ml::instruction::ClaimRefund {})
+        }
+
+        fn get_accounts(
+            &self,
+            client: &mut impl FuzzClient,
+            fuzz_accounts: &mut FuzzAccounts,
+        ) -> Result<(Vec<Keypair>, Vec<AccountMeta>), FuzzingError> {
+            let claimer = user(fuzz_accounts, self.accounts.user, client);
+            let treasury = user(fuzz_accounts, self.treasury, client);
+            let keys = pool_keys(self.accounts.pool);
+            let metas = ml::accounts::ClaimRefund {
+                mint: MINT,
+                pool: keys.pool,
+                pool_token: keys.pool_token,
+                user_token: get_associated_token_address(&claimer.pubkey(), &MINT),
+                treasury_token: get_associated_token_address(&treasury.pubkey(), &MINT),
+                user: claimer.pubkey(),
+                token_program: spl_token::ID,
+                participants: keys.participants,
+            }
+            .to_account_metas(None);
+            Ok((vec![claimer], metas))
+        }
+
+        fn check(
+            &self,
+            pre_ix: Self::IxSnapshot,
+            post_ix: Self::IxSnapshot,
+            _ix_data: Self::IxData,
+        ) -> Result<(), FuzzingError> {
+            if let Some(pre) = status(&pre_ix) {
+                expect(pre == PoolStatus::Cancelled, REFUND_AFTER_PAYOUT)?;
+            }
+            if let (Some(pre), Some(post)) = (&pre_ix.participants, &post_ix.participants) {
+                // a refund releases exactly one slot (or all of them on the dev forfeit path)
+                expect(post.count < pre.count, ILLEGAL_TRANSITION)?;
+            }
+            Ok(())
+        }
+    }
+
+    impl<'info> IxOps<'info> for UnlockPool {
+        type IxData = ml::instruction::UnlockPool;
+        type IxAccounts = FuzzAccounts;
+        type IxSnapshot = UnlockPoolSnapshot<'info>;
+
+        fn get_data(
+            &self,
+            _client: &mut impl FuzzClient,
+            _fuzz_accounts: &mut FuzzAccounts,
+        ) -> Result<Self::IxData, FuzzingError> {
+            Ok(ml::instruction::UnlockPool {})
+        }
+
+        fn get_accounts(
+            &self,
+            client: &mut impl FuzzClient,
+            fuzz_accounts: &mut FuzzAccounts,
+        ) -> Result<(Vec<Keypair>, Vec<AccountMeta>), FuzzingError> {
+            client.forward_in_time(self.wait as i64).map_err(|_| FuzzingError::Custom(0))?;
+            let caller = user(fuzz_accounts, self.accounts.user, client);
+            let keys = pool_keys(self.accounts.pool);
+            let metas = ml::accounts::UnlockPool {
+                pool: keys.pool,
+                user: caller.pubkey(),
+                participants: keys.participants,
+            }
+            .to_account_metas(None);
+            Ok((vec![caller], metas))
+        }
+
+        fn check(
+            &self,
+            pre_ix: Self::IxSnapshot,
+            _post_ix: Self::IxSnapshot,
+            _ix_data: Self::IxData,
+        ) -> Result<(), FuzzingError> {
+            if let Some(pre) = status(&pre_ix) {
+                expect(pre == PoolStatus::Locked, ILLEGAL_TRANSITION)?;
+            }
+            Ok(())
+        }
+    }
+
+    impl<'info> IxOps<'info> for RequestRandomness {
+        type IxData = ml::instruction::RequestRandomness;
+        type IxAccounts = FuzzAccounts;
+        type IxSnapshot = RequestRandomnessSnapshot<'info>;
+
+        fn get_data(
+            &self,
+            _client: &mut impl FuzzClient,
+            _fuzz_accounts: &mut FuzzAccounts,
+        ) -> Result<Self::IxData, FuzzingError> {
+            Ok(ml::instruction::RequestRandomness {})
+        }
+
+        fn get_accounts(
+            &self,
+            client: &mut impl FuzzClient,
+            fuzz_accounts: &mut FuzzAccounts,
+        ) -> Result<(Vec<Keypair>, Vec<AccountMeta>), FuzzingError> {
+            let caller = user(fuzz_accounts, self.accounts.user, client);
+            let keys = pool_keys(self.accounts.pool);
+            let metas = ml::accounts::RequestRandomness {
+                // default pubkey = mock randomness (test-utils build)
+                randomness: Pubkey::default(),
+                pool: keys.pool,
+                user: caller.pubkey(),
+                participants: keys.participants,
+            }
+            .to_account_metas(None);
+            Ok((vec![caller], metas))
+        }
+
+        fn check(
+            &self,
+            pre_ix: Self::IxSnapshot,
+            _post_ix: Self::IxSnapshot,
+            _ix_data: Self::IxData,
+        ) -> Result<(), FuzzingError> {
+            if let Some(pre) = status(&pre_ix) {
+                expect(pre == PoolStatus::Unlocked, ILLEGAL_TRANSITION)?;
+            }
+            Ok(())
+        }
+    }
+
+    impl<'info> IxOps<'info> for SelectWinner {
+        type IxData = ml::instruction::SelectWinner;
+        type IxAccounts = FuzzAccounts;
+        type IxSnapshot = SelectWinnerSnapshot<'info>;
+
+        fn get_data(
+            &self,
+            _client: &mut impl FuzzClient,
+            _fuzz_accounts: &mut FuzzAccounts,
+        ) -> Result<Self::IxData, FuzzingError> {
+            Ok(ml::instruction::SelectWinner {})
+        }
+
+        fn get_accounts(
+            &self,
+            client: &mut impl FuzzClient,
+            fuzz_accounts: &mut FuzzAccounts,
+        ) -> Result<(Vec<Keypair>, Vec<AccountMeta>), FuzzingError> {
+            let caller = user(fuzz_accounts, self.accounts.user, client);
+            let keys = pool_keys(self.accounts.pool);
+            let metas = ml::accounts::SelectWinner {
+                pool: keys.pool,
+                randomness: Pubkey::default(),
+                user: caller.pubkey(),
+                participants: keys.participants,
+            }
+            .to_account_metas(None);
+            Ok((vec![caller], metas))
+        }
+
+        fn check(
+            &self,
+            pre_ix: Self::IxSnapshot,
+            post_ix: Self::IxSnapshot,
+            _ix_data: Self::IxData,
+        ) -> Result<(), FuzzingError> {
+            let (Some(participants), Some(post_pool)) = (&pre_ix.participants, &post_ix.pool) else {
+                return Ok(());
+            };
+            expect(post_pool.status == PoolStatus::WinnerSelected, ILLEGAL_TRANSITION)?;
+            let entrants = &participants.list[..participants.count as usize];
+            expect(entrants.contains(&post_pool.winner), WINNER_NOT_ENTRANT)
+        }
+    }
+
+    impl<'info> IxOps<'info> for PayoutWinner {
+        type IxData = ml::instruction::PayoutWinner;
+        type IxAccounts = FuzzAccounts;
+        type IxSnapshot = PayoutWinnerSnapshot<'info>;
+
+        fn get_data(
+            &self,
+            _client: &mut impl FuzzClient,
+            _fuzz_accounts: &mut FuzzAccounts,
+        ) -> Result<Self::IxData, FuzzingError> {
+            Ok(ml::instruction::PayoutWinner {})
+        }
+
+        fn get_accounts(
+            &self,
+            client: &mut impl FuzzClient,
+            fuzz_accounts: &mut FuzzAccounts,
+        ) -> Result<(Vec<Keypair>, Vec<AccountMeta>), FuzzingError> {
+            let caller = user(fuzz_accounts, self.accounts.user, client);
+            let keys = pool_keys(self.accounts.pool);
+
+            // Recorded wallets unless the fuzzer substitutes its own
+            let recorded = read_pool(client, &keys.pool);
+            let mut pick = |id: Option<AccountId>, recorded: Option<Pubkey>| match (id, recorded) {
+                (None, Some(key)) => key,
+                (id, _) => user(fuzz_accounts, id.unwrap_or_default(), client).pubkey(),
+            };
+            let winner = pick(self.winner, recorded.as_ref().map(|p| p.winner));
+            let dev = pick(self.dev, recorded.as_ref().map(|p| p.dev_wallet));
+            let treasury = pick(self.treasury, recorded.as_ref().map(|p| p.treasury_wallet));
+            ensure_ata(client, &winner);
+            ensure_ata(client, &dev);
+            ensure_ata(client, &treasury);
+
+            let metas = ml::accounts::PayoutWinner {
+                mint: MINT,
+                pool: keys.pool,
+                pool_token: keys.pool_token,
+                winner_token: get_associated_token_address(&winner, &MINT),
+                dev_token: get_associated_token_address(&dev, &MINT),
+                treasury_token: get_associated_token_address(&treasury, &MINT),
+                creator_token: None,
+                token_program: spl_token::ID,
+                winner_pubkey: winner,
+                user: caller.pubkey(),
+                participants: keys.participants,
+            }
+            .to_account_metas(None);
+            Ok((vec![caller], metas))
+        }
+
+        fn check(
+            &self,
+            pre_ix: Self::IxSnapshot,
+            post_ix: Self::IxSnapshot,
+            _ix_data: Self::IxData,
+        ) -> Result<(), FuzzingError> {
+            if let Some(pre) = status(&pre_ix) {
+                expect(pre == PoolStatus::WinnerSelected, DOUBLE_PAYOUT)?;
+                expect(status(&post_ix) == Some(PoolStatus::Ended), ILLEGAL_TRANSITION)?;
+            }
+            if let Some(vault) = &post_ix.pool_token {
+                expect(vault.amount == 0, VAULT_NOT_DRAINED)?;
+            }
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,35 @@
+//! Instruction-sequence fuzzer for the `ml` program.
+//!
+//! `trident fuzz run fuzz_0` replays arbitrary sequences of pool instructions with
+//! substituted signers and pools; the `check` hooks in `fuzz_instructions.rs` fail the
+//! run on state-machine violations (double payout, refund after payout, join after lock).
+
+use fuzz_instructions::ml_fuzz_instructions::FuzzInstruction;
+use ml::entry as entry_ml;
+use ml::ID as PROGRAM_ID_ML;
+use trident_client::fuzzing::*;
+
+mod accounts_snapshots;
+mod fuzz_instructions;
+
+const PROGRAM_NAME_ML: &str = "ml";
+
+struct InstructionsSequence;
+
+impl FuzzDataBuilder<FuzzInstruction> for InstructionsSequence {}
+
+fn main() {
+    loop {
+        fuzz_trident!(fuzz_ix: FuzzInstruction, |fuzz_data: InstructionsSequence| {
+            let fuzzing_program_ml = FuzzingProgram::new(
+                PROGRAM_NAME_ML,
+                &PROGRAM_ID_ML,
+                processor!(convert_entry!(entry_ml)),
+            );
+
+            let mut client = ProgramTestClientBlocking::new(&[fuzzing_program_ml]).unwrap();
+
+            let _ = fuzz_data.run_with_runtime(PROGRAM_ID_ML, &mut client);
+        });
+    }
+}
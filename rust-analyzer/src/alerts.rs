@@ -0,0 +1,146 @@
+//! Alert sinks: when an analysis scores below `--alert-below` or a detector
+//! reports a CRITICAL finding, the result is posted to Discord, Slack,
+//! Telegram or a plain JSON webhook. Delivery failures are logged, never fatal.
+
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Result};
+use reqwest::Client;
+use serde_json::json;
+use tracing::warn;
+
+use crate::analysis::{AnalysisOutput, SafetyAnalysis};
+use crate::report;
+
+/// Discord rejects messages longer than this
+const DISCORD_LIMIT: usize = 2000;
+/// Telegram rejects messages longer than this
+const TELEGRAM_LIMIT: usize = 4096;
+const TELEGRAM_API: &str = "https://api.telegram.org";
+
+/// Where alerts go, parsed from `<kind>=<target>`:
+/// `discord=<webhook url>`, `slack=<webhook url>`, `webhook=<url>`,
+/// `telegram=<bot token>/<chat id>`
+#[derive(Debug, Clone)]
+pub enum AlertSink {
+    Discord(String),
+    Slack(String),
+    Telegram { bot_token: String, chat_id: String },
+    /// POSTs `{"mint", "triggers", "output"}` as JSON
+    Webhook(String),
+}
+
+impl FromStr for AlertSink {
+    type Err = anyhow::Error;
+
+    fn from_str(spec: &str) -> Result<Self> {
+        let (kind, target) = spec
+            .split_once('=')
+            .ok_or_else(|| anyhow!("alert sink `{}` must look like <kind>=<target>", spec))?;
+        Ok(match kind {
+            "discord" => Self::Discord(target.to_string()),
+            "slack" => Self::Slack(target.to_string()),
+            "webhook" => Self::Webhook(target.to_string()),
+            "telegram" => {
+                let (bot_token, chat_id) = target
+                    .rsplit_once('/')
+                    .ok_or_else(|| anyhow!("telegram sink must be telegram=<bot token>/<chat id>"))?;
+                Self::Telegram { bot_token: bot_token.to_string(), chat_id: chat_id.to_string() }
+            }
+            _ => bail!("unknown alert sink `{}` (discord, slack, telegram or webhook)", kind),
+        })
+    }
+}
+
+impl AlertSink {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Discord(_) => "discord",
+            Self::Slack(_) => "slack",
+            Self::Telegram { .. } => "telegram",
+            Self::Webhook(_) => "webhook",
+        }
+    }
+}
+
+pub struct Alerter {
+    client: Client,
+    sinks: Vec<AlertSink>,
+    /// Scores below this alert; `None` alerts on critical findings only
+    below: Option<f64>,
+}
+
+impl Alerter {
+    pub fn new(sinks: Vec<AlertSink>, below: Option<f64>, timeout: Duration) -> Result<Self> {
+        let client = Client::builder().timeout(timeout).build()?;
+        Ok(Self { client, sinks, below })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sinks.is_empty()
+    }
+
+    /// Why `analysis` warrants an alert; empty when it doesn't
+    pub fn triggers(&self, analysis: &SafetyAnalysis) -> Vec<String> {
+        let low_score = self
+            .below
+            .filter(|&below| analysis.safe_score < below)
+            .map(|below| format!("score {:.1} below {}", analysis.safe_score, below));
+        let critical = analysis
+            .pattern_signals
+            .iter()
+            .filter(|s| s.details.starts_with("CRITICAL"))
+            .map(|s| format!("{}: {}", s.name, s.details));
+        low_score.into_iter().chain(critical).collect()
+    }
+
+    /// Posts `output` to every sink, concurrently
+    pub async fn send(&self, output: &AnalysisOutput, triggers: &[String]) {
+        let Some(analysis) = &output.data else {
+            return;
+        };
+        let deliveries = self.sinks.iter().map(|sink| async move {
+            if let Err(e) = self.deliver(sink, output, analysis, triggers).await {
+                warn!(sink = sink.name(), mint = %analysis.mint_address, "alert delivery failed: {:#}", e);
+            }
+        });
+        futures::future::join_all(deliveries).await;
+    }
+
+    async fn deliver(&self, sink: &AlertSink, output: &AnalysisOutput, analysis: &SafetyAnalysis, triggers: &[String]) -> Result<()> {
+        let text = format!("🚨 **Alert:** {}\n\n{}", triggers.join("; "), report::markdown(output));
+        let request = match sink {
+            AlertSink::Discord(url) => self.client.post(url).json(&json!({ "content": truncate(&text, DISCORD_LIMIT) })),
+            // Slack's mrkdwn bolds with single asterisks
+            AlertSink::Slack(url) => self.client.post(url).json(&json!({ "text": text.replace("**", "*") })),
+            // Plain text: Telegram's Markdown modes reject unescaped `_` and `.` in mints and details
+            AlertSink::Telegram { bot_token, chat_id } => self
+                .client
+                .post(format!("{}/bot{}/sendMessage", TELEGRAM_API, bot_token))
+                .json(&json!({ "chat_id": chat_id, "text": truncate(&text.replace("**", ""), TELEGRAM_LIMIT) })),
+            AlertSink::Webhook(url) => self.client.post(url).json(&json!({
+                "mint": analysis.mint_address,
+                "triggers": triggers,
+                "output": output,
+            })),
+        };
+        // Webhook URLs and bot tokens are secrets; keep them out of the logged error
+        request
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| e.without_url())?;
+        Ok(())
+    }
+}
+
+/// At most `limit` characters, ending in `…` when cut
+fn truncate(text: &str, limit: usize) -> String {
+    if text.chars().count() <= limit {
+        return text.to_string();
+    }
+    let mut cut: String = text.chars().take(limit - 1).collect();
+    cut.push('…');
+    cut
+}
@@ -0,0 +1,186 @@
+//! Mint and metadata account decoding. The mint comes from `jsonParsed`
+//! `getAccountInfo`; metadata from the Metaplex metadata PDA, or from the
+//! Token-2022 `tokenMetadata` extension when the mint carries one.
+
+use std::str::FromStr;
+
+use anyhow::{anyhow, Result};
+use base64::Engine;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use solana_pubkey::Pubkey;
+
+pub const TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+pub const TOKEN_2022_PROGRAM_ID: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+pub const METADATA_PROGRAM_ID: &str = "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MintInfo {
+    /// Owning token program
+    pub program: String,
+    pub decimals: u8,
+    /// Raw supply in base units
+    pub supply: u64,
+    pub mint_authority: Option<String>,
+    pub freeze_authority: Option<String>,
+    /// Token-2022 extensions as reported by `jsonParsed`
+    #[serde(default)]
+    pub extensions: Vec<MintExtension>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MintExtension {
+    pub name: String,
+    #[serde(default)]
+    pub state: Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TokenMetadata {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub update_authority: Option<String>,
+    /// `None` when the source doesn't say (Token-2022 metadata)
+    pub is_mutable: Option<bool>,
+}
+
+impl MintInfo {
+    pub fn extension(&self, name: &str) -> Option<&MintExtension> {
+        self.extensions.iter().find(|e| e.name == name)
+    }
+
+    /// Supply in UI units
+    pub fn ui_supply(&self) -> f64 {
+        self.supply as f64 / 10f64.powi(self.decimals as i32)
+    }
+
+    /// Decodes the `value` of a `jsonParsed` `getAccountInfo` response
+    pub fn from_parsed(value: &Value) -> Result<Self> {
+        if value.is_null() {
+            return Err(anyhow!("mint account not found"));
+        }
+        let program = value["owner"].as_str().unwrap_or_default().to_string();
+        if program != TOKEN_PROGRAM_ID && program != TOKEN_2022_PROGRAM_ID {
+            return Err(anyhow!("account is not a token mint (owner {})", program));
+        }
+        let parsed = &value["data"]["parsed"];
+        if parsed["type"] != "mint" {
+            return Err(anyhow!("account is not a token mint"));
+        }
+        let info = &parsed["info"];
+
+        let authority = |key: &str| info[key].as_str().map(str::to_string);
+        let extensions = info["extensions"]
+            .as_array()
+            .map(|exts| {
+                exts.iter()
+                    .filter_map(|e| {
+                        Some(MintExtension {
+                            name: e["extension"].as_str()?.to_string(),
+                            state: e["state"].clone(),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(Self {
+            program,
+            decimals: info["decimals"]
+                .as_u64()
+                .ok_or_else(|| anyhow!("mint has no decimals"))? as u8,
+            supply: info["supply"]
+                .as_str()
+                .and_then(|s| s.parse().ok())
+                .ok_or_else(|| anyhow!("mint has no supply"))?,
+            mint_authority: authority("mintAuthority"),
+            freeze_authority: authority("freezeAuthority"),
+            extensions,
+        })
+    }
+
+    /// Metadata stored on the mint itself (Token-2022 `tokenMetadata`)
+    pub fn embedded_metadata(&self) -> Option<TokenMetadata> {
+        let state = &self.extension("tokenMetadata")?.state;
+        Some(TokenMetadata {
+            name: state["name"].as_str()?.to_string(),
+            symbol: state["symbol"].as_str().unwrap_or_default().to_string(),
+            uri: state["uri"].as_str().unwrap_or_default().to_string(),
+            update_authority: state["updateAuthority"].as_str().map(str::to_string),
+            is_mutable: None,
+        })
+    }
+}
+
+/// Metaplex metadata PDA of `mint`
+pub fn metadata_address(mint: &str) -> Result<String> {
+    let mint = Pubkey::from_str(mint).map_err(|e| anyhow!("invalid mint address: {}", e))?;
+    let program = Pubkey::from_str(METADATA_PROGRAM_ID).expect("valid program id");
+    let (pda, _) = Pubkey::find_program_address(&[b"metadata", program.as_ref(), mint.as_ref()], &program);
+    Ok(pda.to_string())
+}
+
+/// Borsh reader for the prefix of the Metaplex `Metadata` account we need
+struct Reader<'a>(&'a [u8]);
+
+impl Reader<'_> {
+    fn take(&mut self, n: usize) -> Option<&[u8]> {
+        if self.0.len() < n {
+            return None;
+        }
+        let (head, rest) = self.0.split_at(n);
+        self.0 = rest;
+        Some(head)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        self.take(1).map(|b| b[0])
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        self.take(4).map(|b| u32::from_le_bytes(b.try_into().unwrap()))
+    }
+
+    fn pubkey(&mut self) -> Option<String> {
+        self.take(32).and_then(|b| Pubkey::try_from(b).ok()).map(|k| k.to_string())
+    }
+
+    /// Metaplex pads strings with NULs to a fixed length
+    fn string(&mut self) -> Option<String> {
+        let len = self.u32()? as usize;
+        let bytes = self.take(len)?;
+        Some(String::from_utf8_lossy(bytes).trim_end_matches('\0').trim().to_string())
+    }
+}
+
+/// Decodes a base64 `getAccountInfo` value of a Metaplex metadata account
+pub fn decode_metaplex(value: &Value) -> Option<TokenMetadata> {
+    let data = base64::engine::general_purpose::STANDARD
+        .decode(value["data"][0].as_str()?)
+        .ok()?;
+    let mut r = Reader(&data);
+
+    r.u8()?; // key
+    let update_authority = r.pubkey()?;
+    r.pubkey()?; // mint
+    let name = r.string()?;
+    let symbol = r.string()?;
+    let uri = r.string()?;
+    r.take(2)?; // seller_fee_basis_points
+    if r.u8()? == 1 {
+        let creators = r.u32()? as usize;
+        r.take(creators * 34)?; // address, verified, share
+    }
+    r.u8()?; // primary_sale_happened
+    let is_mutable = r.u8().map(|b| b != 0);
+
+    Some(TokenMetadata {
+        name,
+        symbol,
+        uri,
+        update_authority: Some(update_authority),
+        is_mutable,
+    })
+}
@@ -0,0 +1,903 @@
+//! The native analyzer: fetches a mint's holders, activity, accounts, pools,
+//! launch and (optionally) market data over RPC, then hands the context to
+//! `score`.
+
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail, Result};
+use metrics::{counter, histogram};
+use tracing::{instrument, warn};
+use reqwest::Client;
+
+use super::accounts::{self, MintInfo, TokenMetadata};
+use super::benchmark::{self, Benchmark};
+use super::cache::{self, RpcCache};
+use super::cluster::Cluster;
+use super::completeness::{DataCompleteness, DataSource, MissingData};
+use super::das::AssetInfo;
+use super::detectors::DetectorRegistry;
+use super::fixture::{FixtureStore, RpcFixture};
+use super::freeze;
+use super::history::{self, ScoreHistory};
+use super::known::KnownAddresses;
+use super::launch::launch_window;
+use super::known_good::{self, KnownGoodMint};
+use super::lists::{self, ListMatch, ListSubject, MintLists, ALLOW_SKIPS};
+use super::market::{MarketData, MarketDataProvider, MarketDataSource};
+#[cfg(feature = "ml")]
+use super::ml::{self, MlModel};
+use super::model::Model;
+use super::patterns::{PatternDetector, TokenContext, HolderInfo, TransactionInfo};
+use super::pool::pool_usage;
+use super::postmortem::{self, Postmortem, POSTMORTEM_SKIPS};
+use super::reasons::Severity;
+use super::rpc::{ApiKey, EndpointUsage, RetryPolicy, RpcPool};
+use super::scoring::{RiskConfig, ScoringConfig};
+use super::trades::{classify, CLASSIFIED_TRANSACTIONS};
+use super::{score, AnalysisOutput, SafetyAnalysis};
+
+pub const DEFAULT_RPC_URL: &str = "https://mainnet.helius-rpc.com/?api-key=YOUR_API_KEY";
+/// Recent signatures fetched per analysis unless configured otherwise
+pub const DEFAULT_TX_DEPTH: usize = 100;
+/// Longest an analysis may take unless configured otherwise
+pub const DEFAULT_ANALYSIS_TIMEOUT: Duration = Duration::from_secs(300);
+/// Most signatures `getSignaturesForAddress` returns per call
+pub(super) const SIGNATURE_PAGE: usize = 1000;
+
+#[derive(Debug, Clone)]
+pub struct AnalyzerConfig {
+    /// RPC endpoints, rotated round-robin with failover
+    pub rpc_urls: Vec<String>,
+    /// Helius API keys rotated ahead of `rpc_urls`, each within its own
+    /// credit budget and rate limit
+    pub api_keys: Vec<ApiKey>,
+    /// Cluster the endpoints serve, which picks program ids, the Helius URL
+    /// and the bundled known addresses
+    pub cluster: Cluster,
+    /// Per-request HTTP timeout
+    pub timeout: Duration,
+    /// Retries, and the timeout of one RPC call across them
+    pub retry: RetryPolicy,
+    /// Longest a whole analysis may take before it fails with
+    /// `AnalysisTimeout`; `None` leaves it to the RPC timeouts
+    pub analysis_timeout: Option<Duration>,
+    /// Detector ids to run (see `DetectorRegistry::ids`); `None` runs all
+    pub detectors: Option<Vec<String>>,
+    /// Bundled scoring preset the analysis is versioned by
+    pub model: Model,
+    /// Detector weights, thresholds and on/off flags over `model`'s preset
+    pub scoring: ScoringConfig,
+    /// RPC response cache; `None` always hits the RPC
+    pub cache: Option<CacheConfig>,
+    /// Score history database; `None` records nothing and reports no trend
+    pub history: Option<PathBuf>,
+    /// Off-chain price/volume provider; `None` skips market data
+    pub market_data: Option<MarketDataSource>,
+    /// JSON file of extra known addresses, merged over the bundled registry
+    pub known_addresses: Option<PathBuf>,
+    /// JSON file of mints that skip the expensive checks (see `lists`)
+    pub allow_list: Option<PathBuf>,
+    /// JSON file of mints and deployer wallets rated critical outright
+    pub deny_list: Option<PathBuf>,
+    /// Rate the bundled stablecoins and bluechips outright, without fetching
+    /// anything (see `known_good`); mainnet addresses, so ignored on devnet
+    pub known_good: bool,
+    /// Ask the endpoint for Helius DAS `getAsset` before the raw mint and
+    /// metadata accounts (see `das`)
+    pub das: bool,
+    /// Recent signatures fetched for the activity detectors, paged past 1000
+    pub tx_depth: usize,
+    /// Report what could be fetched when a fetch fails, with the detectors
+    /// missing their inputs at no data and a `data_completeness` section,
+    /// instead of failing the analysis
+    pub partial: bool,
+    /// ONNX model scoring the detector features; `None` reports no learned score
+    #[cfg(feature = "ml")]
+    pub ml_model: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CacheConfig {
+    pub path: PathBuf,
+    pub ttl: Duration,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            path: cache::default_path(),
+            ttl: cache::DEFAULT_TTL,
+        }
+    }
+}
+
+impl Default for AnalyzerConfig {
+    fn default() -> Self {
+        Self {
+            rpc_urls: std::env::var("SOLANA_RPC_URL")
+                .unwrap_or_else(|_| DEFAULT_RPC_URL.to_string())
+                .split(',')
+                .map(|url| url.trim().to_string())
+                .collect(),
+            api_keys: Vec::new(),
+            cluster: Cluster::default(),
+            timeout: Duration::from_secs(30),
+            retry: RetryPolicy::default(),
+            analysis_timeout: Some(DEFAULT_ANALYSIS_TIMEOUT),
+            detectors: None,
+            model: Model::default(),
+            scoring: ScoringConfig::default(),
+            cache: Some(CacheConfig::default()),
+            history: Some(history::default_path()),
+            market_data: None,
+            known_addresses: None,
+            allow_list: None,
+            deny_list: None,
+            known_good: true,
+            das: true,
+            tx_depth: DEFAULT_TX_DEPTH,
+            partial: false,
+            #[cfg(feature = "ml")]
+            ml_model: None,
+        }
+    }
+}
+
+/// An analysis cut off by `AnalyzerConfig::analysis_timeout`; downcast the
+/// `anyhow::Error` to tell it apart from a failed fetch
+#[derive(Debug, Clone)]
+pub struct AnalysisTimeout {
+    pub mint: String,
+    pub after: Duration,
+}
+
+impl fmt::Display for AnalysisTimeout {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "analysis of {} timed out after {:?}", self.mint, self.after)
+    }
+}
+
+impl std::error::Error for AnalysisTimeout {}
+
+pub struct TokenAnalyzer {
+    rpc: RpcPool,
+    analysis_timeout: Option<Duration>,
+    detectors: Vec<Box<dyn PatternDetector>>,
+    risk: RiskConfig,
+    model_version: String,
+    cache: Option<RpcCache>,
+    history: Option<ScoreHistory>,
+    fixture: Option<FixtureStore>,
+    market: Option<Box<dyn MarketDataProvider>>,
+    pub(super) known: KnownAddresses,
+    lists: MintLists,
+    known_good: bool,
+    /// Cleared once the endpoint turns out not to speak DAS
+    das: AtomicBool,
+    cache_hits: AtomicU64,
+    pub(super) cluster: Cluster,
+    tx_depth: usize,
+    partial: bool,
+    #[cfg(feature = "ml")]
+    ml: Option<MlModel>,
+}
+
+impl TokenAnalyzer {
+    /// An analyzer running the built-in detectors
+    pub fn with_config(config: AnalyzerConfig) -> Result<Self> {
+        Self::with_registry(config, DetectorRegistry::default())
+    }
+
+    /// An analyzer running the detectors in `registry`, as `config.scoring`
+    /// configures them and `config.detectors` selects them
+    pub fn with_registry(config: AnalyzerConfig, registry: DetectorRegistry) -> Result<Self> {
+        if let Some(selected) = &config.detectors {
+            let known = registry.ids();
+            if let Some(unknown) = selected.iter().find(|id| !known.contains(id)) {
+                bail!("unknown detector `{}` (available: {})", unknown, known.join(", "));
+            }
+        }
+        // Anything on top of the bundled preset makes scores incomparable with
+        // the plain model's, so the version says so
+        let custom = config.detectors.is_some()
+            || !config.scoring.detectors.is_empty()
+            || config.scoring.risk != RiskConfig::default();
+        let model_version = match custom {
+            true => format!("{}+custom", config.model),
+            false => config.model.to_string(),
+        };
+        let scoring = config.scoring.over(config.model.scoring());
+        let detectors = registry
+            .configure(&scoring)?
+            .into_iter()
+            .filter(|d| config.detectors.as_ref().is_none_or(|ids| ids.contains(&d.id())))
+            .collect();
+
+        let cache = config
+            .cache
+            .map(|c| RpcCache::open(&c.path, c.ttl))
+            .transpose()?;
+        let history = config.history.as_deref().map(ScoreHistory::open).transpose()?;
+
+        let known = match &config.known_addresses {
+            Some(path) => KnownAddresses::load(path, config.cluster)?,
+            None => KnownAddresses::bundled(config.cluster),
+        };
+        let lists = MintLists::load(config.allow_list.as_deref(), config.deny_list.as_deref())?;
+        #[cfg(feature = "ml")]
+        let ml = config.ml_model.as_deref().map(MlModel::load).transpose()?;
+
+        let client = Client::builder().timeout(config.timeout).build()?;
+        Ok(Self {
+            market: config.market_data.map(|source| source.provider(client.clone())),
+            rpc: RpcPool::new(client, config.rpc_urls, config.api_keys, config.cluster, config.retry)?,
+            analysis_timeout: config.analysis_timeout,
+            detectors,
+            risk: scoring.risk,
+            model_version,
+            cache,
+            history,
+            fixture: None,
+            known,
+            lists,
+            known_good: config.known_good && config.cluster.has_mainnet_accounts(),
+            das: AtomicBool::new(config.das),
+            cache_hits: AtomicU64::new(0),
+            cluster: config.cluster,
+            tx_depth: config.tx_depth,
+            partial: config.partial,
+            #[cfg(feature = "ml")]
+            ml,
+        })
+    }
+
+    /// JSON-RPC `result` of `method`, served from the cache when fresh.
+    /// Cache failures are logged and fall through to the RPC.
+    #[instrument(level = "debug", skip_all, fields(method = %method, cached = false))]
+    pub(super) async fn rpc_call(&self, mint: &str, method: &str, params: serde_json::Value) -> Result<serde_json::Value> {
+        if let Some(replayed) = self.fixture.as_ref().and_then(|f| f.replay(method, &params)) {
+            return replayed;
+        }
+        if let Some(cache) = &self.cache {
+            match cache.get(method, &params) {
+                Ok(Some(result)) => {
+                    counter!("analyzer_cache_hits_total").increment(1);
+                    self.cache_hits.fetch_add(1, Ordering::Relaxed);
+                    tracing::Span::current().record("cached", true);
+                    if let Some(fixture) = &self.fixture {
+                        fixture.record(method, &params, &result);
+                    }
+                    return Ok(result);
+                }
+                Ok(None) => counter!("analyzer_cache_misses_total").increment(1),
+                Err(e) => warn!("cache read failed: {}", e),
+            }
+        }
+
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        });
+
+        counter!("analyzer_rpc_requests_total", "method" => method.to_string()).increment(1);
+        let rpc_error = || counter!("analyzer_rpc_errors_total", "method" => method.to_string()).increment(1);
+        let mut response = self.rpc.call(&body).await.inspect_err(|_| rpc_error())?;
+
+        if let Some(error) = response.get("error") {
+            rpc_error();
+            return Err(anyhow!("RPC error: {}", error));
+        }
+        let result = response["result"].take();
+
+        if let Some(cache) = &self.cache {
+            if let Err(e) = cache.put(mint, method, &params, &result) {
+                warn!("cache write failed: {}", e);
+            }
+        }
+        if let Some(fixture) = &self.fixture {
+            fixture.record(method, &params, &result);
+        }
+        Ok(result)
+    }
+
+    /// Records every RPC response for a fixture of `mint` (see `fixture`).
+    /// Market data doesn't come over RPC, so recording turns it off.
+    pub fn with_fixture_recording(mut self, mint: &str) -> Self {
+        self.fixture = Some(FixtureStore::recording(mint, chrono::Utc::now().timestamp()));
+        self.market = None;
+        self
+    }
+
+    /// Answers RPC calls from `fixture` instead of the network, with the
+    /// clock pinned to when it was recorded
+    pub fn with_fixture_replay(mut self, fixture: RpcFixture) -> Self {
+        self.fixture = Some(FixtureStore::replaying(fixture));
+        self.market = None;
+        self
+    }
+
+    pub(super) fn das_enabled(&self) -> bool {
+        self.das.load(Ordering::Relaxed)
+    }
+
+    pub(super) fn disable_das(&self) {
+        self.das.store(false, Ordering::Relaxed);
+    }
+
+    /// RPC responses served from the cache so far
+    pub fn cache_hits(&self) -> u64 {
+        self.cache_hits.load(Ordering::Relaxed)
+    }
+
+    /// Requests and credits spent per RPC endpoint so far
+    pub fn rpc_usage(&self) -> Vec<EndpointUsage> {
+        self.rpc.usage()
+    }
+
+    /// Version of the scoring model in use, `+custom` when configured beyond it
+    pub fn model_version(&self) -> &str {
+        &self.model_version
+    }
+
+    /// `analyze` wrapped in the envelope the front ends emit
+    pub async fn analyze_output(&self, mint_address: &str) -> AnalysisOutput {
+        AnalysisOutput::new(self.analyze(mint_address).await, &self.model_version)
+    }
+
+    /// The responses recorded so far, when recording or replaying
+    pub fn fixture(&self) -> Option<RpcFixture> {
+        self.fixture.as_ref().map(FixtureStore::fixture)
+    }
+
+    /// Unix time the analysis runs at: the fixture's, if there is one
+    fn now(&self) -> i64 {
+        self.fixture
+            .as_ref()
+            .map_or_else(|| chrono::Utc::now().timestamp(), FixtureStore::recorded_at)
+    }
+    
+    /// Replaces the market data provider, e.g. with one not built in
+    pub fn with_market_provider(mut self, provider: Box<dyn MarketDataProvider>) -> Self {
+        self.market = Some(provider);
+        self
+    }
+
+    /// Re-probe every RPC endpoint; long-running modes call this periodically
+    pub async fn check_rpc_health(&self) {
+        self.rpc.check_health().await
+    }
+
+    /// Log lines from the fetches carry the mint through this span
+    #[instrument(skip_all, fields(mint = %mint_address))]
+    pub async fn analyze(&self, mint_address: &str) -> Result<SafetyAnalysis> {
+        let started = Instant::now();
+        let result = match self.analysis_timeout {
+            Some(after) => match tokio::time::timeout(after, self.run_analysis(mint_address)).await {
+                Ok(result) => result,
+                Err(_) => Err(AnalysisTimeout { mint: mint_address.to_string(), after }.into()),
+            },
+            None => self.run_analysis(mint_address).await,
+        };
+        histogram!("analyzer_analysis_duration_seconds").record(started.elapsed());
+        match &result {
+            Ok(analysis) => {
+                counter!("analyzer_analyses_total", "outcome" => "success").increment(1);
+                histogram!("analyzer_score").record(analysis.safe_score);
+            }
+            Err(e) if e.is::<AnalysisTimeout>() => {
+                counter!("analyzer_analyses_total", "outcome" => "timeout").increment(1)
+            }
+            Err(_) => counter!("analyzer_analyses_total", "outcome" => "error").increment(1),
+        }
+        result
+    }
+
+    async fn run_analysis(&self, mint_address: &str) -> Result<SafetyAnalysis> {
+        if let Some(denied) = self.lists.denied(ListSubject::Mint, mint_address) {
+            return Ok(self.denied_outright(mint_address, denied));
+        }
+        if let Some(known) = known_good::lookup(mint_address).filter(|_| self.known_good) {
+            return Ok(self.known_good_outright(mint_address, known));
+        }
+        let allowed = self.lists.allowed(mint_address);
+        let skipped = if allowed.is_some() { ALLOW_SKIPS.to_vec() } else { Vec::new() };
+
+        // Independent fetches run concurrently; metadata, liquidity, the launch
+        // (deployer history, snipers), market data and Missout pools are best-effort
+        let (holders, transactions, (mint_info, metadata, asset), liquidity, launch, market, missout_pools) = tokio::join!(
+            self.fetch_token_holders(mint_address),
+            async {
+                let mut transactions = self.fetch_recent_transactions(mint_address).await?;
+                let freezes = self.classify_transactions(mint_address, &mut transactions).await;
+                anyhow::Ok((transactions, freezes))
+            },
+            self.fetch_mint(mint_address),
+            self.fetch_liquidity(mint_address),
+            async {
+                if allowed.is_some() {
+                    return anyhow::Ok(None);
+                }
+                let signatures = self.fetch_launch_signatures(mint_address).await?;
+                anyhow::Ok(Some(tokio::join!(
+                    self.fetch_deployer_history(mint_address, &signatures[0]),
+                    self.fetch_launch_window(mint_address, &signatures),
+                )))
+            },
+            self.fetch_market_data(mint_address),
+            self.fetch_mint_pools(mint_address),
+        );
+        // A compressed asset has no token accounts, just its owner
+        let holders = match (holders, asset.as_ref().and_then(|a| a.owner.clone())) {
+            (Err(_), Some(owner)) => Ok(vec![(owner, 1)]),
+            (holders, _) => holders,
+        };
+        // The mint, holders and transactions fail the analysis unless it is partial
+        let mut missing = Vec::new();
+        let mint_info = self.required(mint_info, DataSource::Mint, &mut missing)?;
+        let (transactions, mut freeze_events) =
+            self.required(transactions, DataSource::Transactions, &mut missing)?.unwrap_or_default();
+        let holders = match &mint_info {
+            Some(mint_info) => holders.and_then(|raw| holders_from_raw(raw, mint_info.decimals)),
+            None => holders.and_then(|_| Err(anyhow!("holder balances need the mint's decimals"))),
+        };
+        let holders = self.required(holders, DataSource::Holders, &mut missing)?.unwrap_or_default();
+        let (holders, excluded_holders) = match self.resolve_holders(mint_address, holders.clone()).await {
+            Ok(split) => split,
+            Err(e) => {
+                warn!("holder owners unavailable: {:#}", e);
+                (holders, Vec::new())
+            }
+        };
+        let metadata = self
+            .optional(metadata, DataSource::Metadata, &mut missing, "metadata lookup failed")
+            .flatten()
+            .or_else(|| mint_info.as_ref().and_then(MintInfo::embedded_metadata));
+        let liquidity = self.optional(liquidity, DataSource::Liquidity, &mut missing, "liquidity lookup failed");
+        let market = self
+            .optional(market, DataSource::Market, &mut missing, "market data unavailable")
+            .flatten();
+        let missout_pools = missout_pools
+            .map_err(|e| warn!("Missout pools unavailable: {:#}", e))
+            .ok()
+            .zip(mint_info.as_ref())
+            .map(|(pools, mint_info)| pool_usage(&pools, mint_info.decimals));
+        let (deployer, launch) = match launch {
+            Ok(None) => (None, None),
+            Ok(Some((deployer, window))) => {
+                let window = window.map(|(window, freezes)| {
+                    freeze_events.extend(freezes);
+                    window
+                });
+                (
+                    self.optional(deployer, DataSource::Deployer, &mut missing, "deployer history unavailable"),
+                    self.optional(window, DataSource::Launch, &mut missing, "launch window unavailable"),
+                )
+            }
+            Err(e) => {
+                warn!("launch history unavailable: {:#}", e);
+                if self.partial {
+                    for source in [DataSource::Deployer, DataSource::Launch] {
+                        missing.push(MissingData { source, error: format!("{:#}", e) });
+                    }
+                }
+                (None, None)
+            }
+        };
+
+        // These need the holders (and the pools), so they run after the fetches above
+        let simulate = async {
+            match (&liquidity, &allowed) {
+                (Some(l), None) => Some(self.simulate_sell(mint_address, &l.pools, &holders).await),
+                _ => None,
+            }
+        };
+        let funding = async {
+            match allowed {
+                None => Some(self.fetch_holder_funding(mint_address, &holders).await),
+                Some(_) => None,
+            }
+        };
+        let (sell_simulation, holder_funding) = tokio::join!(simulate, funding);
+        let sell_simulation = sell_simulation
+            .and_then(|simulation| self.optional(simulation, DataSource::SellSimulation, &mut missing, "sell simulation skipped"))
+            .flatten();
+        let holder_funding = holder_funding.and_then(|funding| {
+            self.optional(funding, DataSource::HolderFunding, &mut missing, "holder funding unavailable")
+        });
+        
+        // Estimate creation time (oldest transaction)
+        let creation_time = transactions
+            .iter()
+            .map(|tx| tx.timestamp)
+            .filter(|&t| t > 0)
+            .min()
+            .unwrap_or_else(|| self.now());
+        
+        let current_time = self.now();
+        
+        // Build context for pattern analysis
+        let context = TokenContext {
+            mint: mint_address.to_string(),
+            holders,
+            excluded_holders,
+            transactions,
+            creation_time,
+            current_time,
+            mint_info,
+            metadata,
+            liquidity,
+            sell_simulation,
+            deployer,
+            holder_funding,
+            launch,
+            freeze_events: freeze::merge(freeze_events),
+            market,
+            missing,
+            skipped,
+        };
+        
+        let mut analysis = score(context, &self.detectors, &self.risk);
+        analysis.asset = asset;
+        analysis.missout_pools = missout_pools;
+        let deployer_denied = analysis
+            .deployer
+            .as_ref()
+            .and_then(|deployer| self.lists.denied(ListSubject::Deployer, &deployer.address));
+        match deployer_denied {
+            Some(denied) => lists::deny(&mut analysis, denied, &self.risk),
+            None => analysis.list_match = allowed,
+        }
+        if self.partial {
+            analysis.data_completeness.get_or_insert_with(DataCompleteness::complete);
+        }
+        #[cfg(feature = "ml")]
+        if let Some(model) = &self.ml {
+            match model.predict(&ml::features(&analysis.score_breakdown.detectors)) {
+                Ok(ml_score) => analysis.ml_score = Some(ml_score),
+                Err(e) => warn!("ML score unavailable: {:#}", e),
+            }
+        }
+        if let Some(history) = &self.history {
+            let since = chrono::Utc::now().timestamp() - benchmark::WINDOW_DAYS * 86_400;
+            match history.population(mint_address, since, benchmark::MAX_POPULATION) {
+                Ok(population) => analysis.benchmark = Benchmark::against(&analysis.metrics, &population),
+                Err(e) => warn!("benchmark population unavailable: {:#}", e),
+            }
+            match history.record(&analysis, &self.model_version) {
+                Ok(trend) => analysis.trend = trend,
+                Err(e) => warn!("score history unavailable: {:#}", e),
+            }
+        }
+        Ok(analysis)
+    }
+    
+    /// The analysis as of `at` (unix time), rebuilt from the mint's
+    /// transactions up to then (see `postmortem`). Lists, the score history
+    /// and the ML model are left out: it judges the detectors alone.
+    #[instrument(skip(self), fields(mint = %mint_address))]
+    pub async fn postmortem(&self, mint_address: &str, at: i64) -> Result<Postmortem> {
+        let replay = self.fetch_replay(mint_address, at).await?;
+        let creation = replay.launch().and_then(|(signatures, _)| signatures.first());
+        let ((mint_info, metadata, asset), deployer) = tokio::join!(self.fetch_mint(mint_address), async {
+            match creation {
+                Some(creation) => Some(self.fetch_deployer_history(mint_address, creation).await),
+                None => None,
+            }
+        });
+        let mint_info = mint_info?;
+        let mut missing = Vec::new();
+        let metadata = self
+            .optional(metadata, DataSource::Metadata, &mut missing, "metadata lookup failed")
+            .flatten()
+            .or_else(|| mint_info.embedded_metadata());
+        let deployer = deployer.and_then(|d| self.optional(d, DataSource::Deployer, &mut missing, "deployer history unavailable"));
+
+        let holders = postmortem::holders_at(replay.transactions.iter().flatten(), mint_address);
+        if holders.is_empty() {
+            bail!("no balances of the mint among the {} transactions replayed", replay.replayed());
+        }
+        let (holders, excluded_holders) = match self.split_known(mint_address, holders.clone()).await {
+            Ok(split) => split,
+            Err(e) => {
+                warn!("holder labels unavailable: {:#}", e);
+                (holders, Vec::new())
+            }
+        };
+        let holder_funding = self.fetch_holder_funding(mint_address, &holders).await;
+        let holder_funding = self.optional(holder_funding, DataSource::HolderFunding, &mut missing, "holder funding unavailable");
+
+        // The newest `tx_depth` up to `at`, classified like recent ones are live
+        let recent = replay.signatures.len().saturating_sub(self.tx_depth);
+        let classified = replay.signatures.len().saturating_sub(CLASSIFIED_TRANSACTIONS);
+        let mut transactions = Vec::new();
+        let mut freeze_events = Vec::new();
+        for (i, (signature, tx)) in replay.signatures.iter().zip(&replay.transactions).enumerate() {
+            let mut info = TransactionInfo {
+                signature: signature.signature.clone(),
+                timestamp: signature.block_time.unwrap_or(0),
+                tx_type: "unknown".to_string(),
+                wallet: None,
+                token_amount: 0.0,
+                sol_amount: 0.0,
+            };
+            if let Some(tx) = tx {
+                freeze_events.extend(freeze::freeze_events(tx, mint_address, &info.signature, info.timestamp));
+                if i >= classified {
+                    classify(tx, mint_address, &mut info);
+                }
+            }
+            if i >= recent {
+                transactions.push(info);
+            }
+        }
+        let launch = replay.launch().map(|(signatures, fetched)| launch_window(mint_address, signatures, fetched));
+        let mut launch = launch
+            .and_then(|window| self.optional(window, DataSource::Launch, &mut missing, "launch window unavailable"))
+            .map(|(window, _)| window);
+        if let Some(window) = &mut launch {
+            self.trace_bundle_wallets(mint_address, window).await;
+        }
+
+        let creation_time = transactions.iter().map(|tx| tx.timestamp).filter(|&t| t > 0).min().unwrap_or(at);
+        let context = TokenContext {
+            mint: mint_address.to_string(),
+            holders,
+            excluded_holders,
+            transactions,
+            creation_time,
+            current_time: at,
+            mint_info: Some(mint_info),
+            metadata,
+            liquidity: None,
+            sell_simulation: None,
+            deployer,
+            holder_funding,
+            launch,
+            freeze_events: freeze::merge(freeze_events),
+            market: None,
+            missing,
+            skipped: POSTMORTEM_SKIPS.to_vec(),
+        };
+        let mut analysis = score(context, &self.detectors, &self.risk);
+        analysis.asset = asset;
+        let fired = analysis
+            .reason_codes
+            .iter()
+            .filter(|r| r.severity >= Severity::High)
+            .cloned()
+            .collect();
+        Ok(Postmortem {
+            at,
+            transactions_before: replay.signatures.len(),
+            replayed_transactions: replay.replayed(),
+            reaches_creation: replay.reaches_creation,
+            skipped: POSTMORTEM_SKIPS.to_vec(),
+            fired,
+            analysis,
+        })
+    }
+
+    /// A deny-listed mint's analysis: nothing fetched, every detector skipped,
+    /// rated critical. Not recorded in the score history, it observed nothing.
+    fn denied_outright(&self, mint_address: &str, denied: ListMatch) -> SafetyAnalysis {
+        let mut analysis = self.unfetched(mint_address);
+        lists::deny(&mut analysis, denied, &self.risk);
+        analysis
+    }
+
+    /// A known-good mint's analysis: like a denied one, but rated at
+    /// `KNOWN_GOOD_SCORE` with the registry's provenance note
+    fn known_good_outright(&self, mint_address: &str, known: KnownGoodMint) -> SafetyAnalysis {
+        let mut analysis = self.unfetched(mint_address);
+        known_good::rate(&mut analysis, known, &self.risk);
+        analysis
+    }
+
+    /// The analysis of a mint with nothing fetched and every detector skipped
+    fn unfetched(&self, mint_address: &str) -> SafetyAnalysis {
+        let now = self.now();
+        let context = TokenContext {
+            mint: mint_address.to_string(),
+            holders: Vec::new(),
+            excluded_holders: Vec::new(),
+            transactions: Vec::new(),
+            creation_time: now,
+            current_time: now,
+            mint_info: None,
+            metadata: None,
+            liquidity: None,
+            sell_simulation: None,
+            deployer: None,
+            holder_funding: None,
+            launch: None,
+            freeze_events: Vec::new(),
+            market: None,
+            missing: Vec::new(),
+            skipped: DataSource::ALL.to_vec(),
+        };
+        let mut analysis = score(context, &self.detectors, &self.risk);
+        // Skipped detectors have nothing to say
+        analysis.reasons.clear();
+        analysis.reason_codes.clear();
+        analysis
+    }
+
+    /// An input the analysis can't go without: `None` with the failure recorded
+    /// in `missing` when partial, the error otherwise
+    fn required<T>(&self, result: Result<T>, source: DataSource, missing: &mut Vec<MissingData>) -> Result<Option<T>> {
+        match result {
+            Ok(value) => Ok(Some(value)),
+            Err(e) if self.partial => {
+                warn!("{} unavailable: {:#}", source.as_str(), e);
+                missing.push(MissingData { source, error: format!("{:#}", e) });
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// A best-effort input: `None` with a warning on failure, recorded in
+    /// `missing` when partial
+    fn optional<T>(&self, result: Result<T>, source: DataSource, missing: &mut Vec<MissingData>, what: &str) -> Option<T> {
+        match result {
+            Ok(value) => Some(value),
+            Err(e) => {
+                warn!("{}: {:#}", what, e);
+                if self.partial {
+                    missing.push(MissingData { source, error: format!("{:#}", e) });
+                }
+                None
+            }
+        }
+    }
+
+    /// Largest token accounts as (address, raw amount); UI conversion waits
+    /// for the mint's decimals, which are fetched concurrently
+    async fn fetch_token_holders(&self, mint: &str) -> Result<Vec<(String, u64)>> {
+        let result = self
+            .rpc_call(mint, "getTokenLargestAccounts", serde_json::json!([mint]))
+            .await?;
+        
+        let accounts = result["value"]
+            .as_array()
+            .ok_or_else(|| anyhow!("Invalid response format"))?;
+        
+        Ok(accounts
+            .iter()
+            .filter_map(|account| {
+                let amount = account["amount"].as_str()?.parse::<u64>().ok()?;
+                Some((account["address"].as_str()?.to_string(), amount))
+            })
+            .collect())
+    }
+    
+    async fn fetch_market_data(&self, mint: &str) -> Result<Option<MarketData>> {
+        match &self.market {
+            Some(provider) => provider.market_data(mint).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// The mint and its metadata from one `getAsset` where DAS answers, else
+    /// from the `jsonParsed` mint and the Metaplex PDA
+    async fn fetch_mint(&self, mint: &str) -> (Result<MintInfo>, Result<Option<TokenMetadata>>, Option<AssetInfo>) {
+        match self.fetch_asset(mint).await {
+            Some(asset) => {
+                let mint_info = match asset.is_token_2022() {
+                    true => self.fetch_mint_info(mint).await,
+                    false => Ok(asset.mint),
+                };
+                (mint_info, Ok(asset.metadata), Some(asset.info))
+            }
+            None => {
+                let (mint_info, metadata) = tokio::join!(self.fetch_mint_info(mint), self.fetch_metadata(mint));
+                (mint_info, metadata, None)
+            }
+        }
+    }
+
+    async fn fetch_mint_info(&self, mint: &str) -> Result<MintInfo> {
+        let result = self
+            .rpc_call(mint, "getAccountInfo", serde_json::json!([mint, {"encoding": "jsonParsed"}]))
+            .await?;
+        MintInfo::from_parsed(&result["value"])
+    }
+
+    /// Metaplex metadata, if the PDA exists
+    async fn fetch_metadata(&self, mint: &str) -> Result<Option<TokenMetadata>> {
+        let address = accounts::metadata_address(mint)?;
+        let result = self
+            .rpc_call(mint, "getAccountInfo", serde_json::json!([address, {"encoding": "base64"}]))
+            .await?;
+        if result["value"].is_null() {
+            return Ok(None);
+        }
+        Ok(accounts::decode_metaplex(&result["value"]))
+    }
+    
+    /// The newest `tx_depth` signatures, paged back with `before` cursors
+    async fn fetch_recent_transactions(&self, mint: &str) -> Result<Vec<TransactionInfo>> {
+        let mut sigs = Vec::new();
+        while sigs.len() < self.tx_depth {
+            let limit = (self.tx_depth - sigs.len()).min(SIGNATURE_PAGE);
+            let mut config = serde_json::json!({ "limit": limit });
+            if let Some(before) = sigs.last().and_then(|s: &serde_json::Value| s["signature"].as_str()) {
+                config["before"] = serde_json::json!(before);
+            }
+            let mut result = self
+                .rpc_call(mint, "getSignaturesForAddress", serde_json::json!([mint, config]))
+                .await?;
+            let page = match result.take() {
+                serde_json::Value::Array(page) => page,
+                _ => return Err(anyhow!("Invalid response format")),
+            };
+            let exhausted = page.len() < limit;
+            sigs.extend(page);
+            if exhausted {
+                break;
+            }
+        }
+        
+        let mut transactions = Vec::new();
+        for sig in &sigs {
+            if let Some(signature) = sig["signature"].as_str() {
+                let timestamp = sig["blockTime"].as_i64().unwrap_or(0);
+                
+                transactions.push(TransactionInfo {
+                    signature: signature.to_string(),
+                    timestamp,
+                    tx_type: "unknown".to_string(), // Set by `classify_transactions`
+                    wallet: None,
+                    token_amount: 0.0,
+                    sol_amount: 0.0,
+                });
+            }
+        }
+        
+        // Sort by timestamp (oldest first)
+        transactions.sort_by_key(|tx| tx.timestamp);
+        
+        Ok(transactions)
+    }
+}
+
+/// UI balances and each account's share of the largest-accounts total, sorted
+/// by balance
+fn holders_from_raw(raw: Vec<(String, u64)>, decimals: u8) -> Result<Vec<HolderInfo>> {
+    let scale = 10f64.powi(decimals as i32);
+    let raw_holders: Vec<(String, f64)> = raw
+        .into_iter()
+        .map(|(address, amount)| (address, amount as f64 / scale))
+        .collect();
+    
+    // Calculate total held by the largest accounts
+    let total_supply: f64 = raw_holders.iter().map(|(_, balance)| balance).sum();
+    if total_supply == 0.0 {
+        return Err(anyhow!("Zero total supply"));
+    }
+    
+    // Calculate percentages and sort by balance
+    let mut holders: Vec<HolderInfo> = raw_holders
+        .into_iter()
+        .map(|(address, balance)| HolderInfo {
+            address,
+            balance,
+            percent: (balance / total_supply) * 100.0,
+            owner: None,
+            label: None,
+            delegate: None,
+            delegated_amount: None,
+            close_authority: None,
+            merged_accounts: Vec::new(),
+        })
+        .collect();
+    
+    holders.sort_by(|a, b| b.percent.partial_cmp(&a.percent).unwrap());
+    
+    Ok(holders)
+}
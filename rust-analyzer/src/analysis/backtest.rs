@@ -0,0 +1,356 @@
+//! Backtesting: analyses of tokens whose outcome is known (rugged or still
+//! legit), graded per detector and overall by how well their scores separate
+//! the two, so weights are tuned against outcomes rather than intuition.
+//! `calibrate` closes the loop, searching for the weights that separate them best.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::scoring::{DetectorConfig, ScoringConfig};
+use super::SafetyAnalysis;
+
+/// Weights `calibrate` tries for each detector by default
+pub const WEIGHT_GRID: [f64; 9] = [0.0, 0.05, 0.1, 0.15, 0.2, 0.25, 0.3, 0.4, 0.5];
+
+/// Detector scores below this count as the detector flagging a rug (the
+/// `high` and `critical` severities)
+pub const DETECTOR_CUTOFF: f64 = 0.5;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LabeledToken {
+    pub mint: String,
+    /// Whether the token rugged
+    pub rug: bool,
+}
+
+/// `<mint> <label>` per line, comma or whitespace separated, the label being
+/// `rug` or `legit`. Blank lines and `#` comments are skipped.
+pub fn parse_dataset(text: &str) -> Result<Vec<LabeledToken>> {
+    text.lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(number, line)| {
+            let fields: Vec<&str> = line.split(|c: char| c == ',' || c.is_whitespace()).filter(|f| !f.is_empty()).collect();
+            let [mint, label] = fields[..] else {
+                bail!("line {}: expected `<mint> <rug|legit>`, got `{}`", number, line);
+            };
+            let rug = match label.to_ascii_lowercase().as_str() {
+                "rug" => true,
+                "legit" => false,
+                _ => bail!("line {}: unknown label `{}` (use rug or legit)", number, label),
+            };
+            Ok(LabeledToken { mint: mint.to_string(), rug })
+        })
+        .collect()
+}
+
+pub fn load_dataset(path: &Path) -> Result<Vec<LabeledToken>> {
+    let text = std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+    parse_dataset(&text).with_context(|| format!("parsing {}", path.display()))
+}
+
+/// One analyzed token of the dataset, with enough of its analysis to rescore
+/// it under other weights
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Sample {
+    pub mint: String,
+    pub rug: bool,
+    pub safe_score: f64,
+    pub model_version: String,
+    /// Signal of every detector that ran, by id
+    pub detectors: BTreeMap<String, SampleSignal>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SampleSignal {
+    pub score: f64,
+    pub confidence: f64,
+    pub weight: f64,
+}
+
+impl Sample {
+    pub fn new(token: &LabeledToken, analysis: &SafetyAnalysis, model_version: &str) -> Self {
+        let detectors = analysis
+            .score_breakdown
+            .detectors
+            .iter()
+            .map(|d| (d.id.clone(), SampleSignal { score: d.score, confidence: d.confidence, weight: d.weight }))
+            .collect();
+        Self {
+            mint: token.mint.clone(),
+            rug: token.rug,
+            safe_score: analysis.safe_score,
+            model_version: model_version.to_string(),
+            detectors,
+        }
+    }
+
+    /// `safe_score` recomputed the way `calculate_composite_score` does, with
+    /// `weights` in place of the recorded weights of the detectors it lists
+    pub fn rescore(&self, weights: &BTreeMap<String, f64>) -> f64 {
+        let weight = |id: &String, signal: &SampleSignal| weights.get(id).copied().unwrap_or(signal.weight);
+        let total_weight: f64 = self.detectors.iter().map(|(id, s)| weight(id, s)).sum();
+        let weighted_sum: f64 = self.detectors.iter().map(|(id, s)| s.score * s.confidence * weight(id, s)).sum();
+        if total_weight > 0.0 {
+            (weighted_sum / total_weight * 100.0).clamp(0.0, 100.0)
+        } else {
+            50.0
+        }
+    }
+}
+
+/// Samples written by `backtest --samples`, one JSON object per line
+pub fn load_samples(path: &Path) -> Result<Vec<Sample>> {
+    let text = std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| serde_json::from_str(line).with_context(|| format!("{} line {}", path.display(), i + 1)))
+        .collect()
+}
+
+/// How well a score separates rugs from legit tokens. Low scores mean risk,
+/// so a token is flagged as a rug when its score is below the cutoff.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Classification {
+    /// Area under the ROC curve: the chance a random rug scores lower than a
+    /// random legit token (0.5 is a coin flip); `None` without both labels
+    pub auc: Option<f64>,
+    /// Flagged tokens that rugged; `None` when nothing was flagged
+    pub precision: Option<f64>,
+    /// Rugs that were flagged; `None` when there were no rugs
+    pub recall: Option<f64>,
+    pub true_positives: usize,
+    pub false_positives: usize,
+    pub false_negatives: usize,
+    pub true_negatives: usize,
+}
+
+impl Classification {
+    /// Grades `(score, rug)` pairs against `cutoff`
+    pub fn new(points: &[(f64, bool)], cutoff: f64) -> Self {
+        let count = |flagged: bool, rug: bool| points.iter().filter(|&&(s, r)| (s < cutoff) == flagged && r == rug).count();
+        let (true_positives, false_positives) = (count(true, true), count(true, false));
+        let (false_negatives, true_negatives) = (count(false, true), count(false, false));
+        let ratio = |part: usize, whole: usize| (whole > 0).then(|| part as f64 / whole as f64);
+        Self {
+            auc: auc(points),
+            precision: ratio(true_positives, true_positives + false_positives),
+            recall: ratio(true_positives, true_positives + false_negatives),
+            true_positives,
+            false_positives,
+            false_negatives,
+            true_negatives,
+        }
+    }
+}
+
+/// Mann-Whitney U over ranks, ties counting half
+fn auc(points: &[(f64, bool)]) -> Option<f64> {
+    let rugs = points.iter().filter(|(_, rug)| *rug).count();
+    let legit = points.len() - rugs;
+    if rugs == 0 || legit == 0 {
+        return None;
+    }
+    let mut sorted = points.to_vec();
+    sorted.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    // Sum of the legit tokens' ranks, tied scores sharing their average rank
+    let mut legit_ranks = 0.0;
+    let mut start = 0;
+    while start < sorted.len() {
+        let end = start + sorted[start..].iter().take_while(|(s, _)| *s == sorted[start].0).count();
+        let rank = (start + end + 1) as f64 / 2.0;
+        legit_ranks += rank * sorted[start..end].iter().filter(|(_, rug)| !rug).count() as f64;
+        start = end;
+    }
+    let u = legit_ranks - (legit * (legit + 1)) as f64 / 2.0;
+    Some(u / (rugs * legit) as f64)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RocPoint {
+    /// Scores at or below this are flagged
+    pub cutoff: f64,
+    pub true_positive_rate: f64,
+    pub false_positive_rate: f64,
+}
+
+/// One point per distinct score, lowest cutoff first
+fn roc(points: &[(f64, bool)]) -> Vec<RocPoint> {
+    let rugs = points.iter().filter(|(_, rug)| *rug).count();
+    let legit = points.len() - rugs;
+    if rugs == 0 || legit == 0 {
+        return Vec::new();
+    }
+    let mut cutoffs: Vec<f64> = points.iter().map(|(s, _)| *s).collect();
+    cutoffs.sort_by(f64::total_cmp);
+    cutoffs.dedup();
+    cutoffs
+        .into_iter()
+        .map(|cutoff| {
+            let flagged = |rug: bool| points.iter().filter(|&&(s, r)| s <= cutoff && r == rug).count();
+            RocPoint {
+                cutoff,
+                true_positive_rate: flagged(true) as f64 / rugs as f64,
+                false_positive_rate: flagged(false) as f64 / legit as f64,
+            }
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetectorClassification {
+    pub id: String,
+    /// Samples the detector ran on
+    pub samples: usize,
+    #[serde(flatten)]
+    pub classification: Classification,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BacktestReport {
+    pub samples: usize,
+    pub rugs: usize,
+    pub legit: usize,
+    /// Tokens whose analysis failed, left out of every figure
+    pub failed: usize,
+    /// `safe_score` below which the overall figures flag a rug
+    pub cutoff: f64,
+    pub overall: Classification,
+    /// ROC curve of `safe_score`
+    pub roc: Vec<RocPoint>,
+    /// Per detector, flagging scores below `DETECTOR_CUTOFF`; best AUC first
+    pub detectors: Vec<DetectorClassification>,
+}
+
+impl BacktestReport {
+    pub fn new(samples: &[Sample], failed: usize, cutoff: f64) -> Self {
+        let overall: Vec<(f64, bool)> = samples.iter().map(|s| (s.safe_score, s.rug)).collect();
+
+        let mut by_detector: BTreeMap<&str, Vec<(f64, bool)>> = BTreeMap::new();
+        for sample in samples {
+            for (id, signal) in &sample.detectors {
+                by_detector.entry(id).or_default().push((signal.score, sample.rug));
+            }
+        }
+        let mut detectors: Vec<DetectorClassification> = by_detector
+            .into_iter()
+            .map(|(id, points)| DetectorClassification {
+                id: id.to_string(),
+                samples: points.len(),
+                classification: Classification::new(&points, DETECTOR_CUTOFF),
+            })
+            .collect();
+        detectors.sort_by(|a, b| b.classification.auc.unwrap_or(0.0).total_cmp(&a.classification.auc.unwrap_or(0.0)));
+
+        let rugs = samples.iter().filter(|s| s.rug).count();
+        Self {
+            samples: samples.len(),
+            rugs,
+            legit: samples.len() - rugs,
+            failed,
+            cutoff,
+            overall: Classification::new(&overall, cutoff),
+            roc: roc(&overall),
+            detectors,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Calibration {
+    /// Scoring model the samples were analyzed with, which the weights apply over
+    pub model_version: String,
+    pub samples: usize,
+    /// Every detector's weight, calibrated or not
+    pub weights: BTreeMap<String, f64>,
+    /// AUC of `safe_score` under the recorded weights
+    pub auc_before: f64,
+    pub auc_after: f64,
+    /// Search passes run before the weights settled (or the limit was hit)
+    pub passes: usize,
+}
+
+/// Coordinate search over `grid`: each detector's weight in turn is set to the
+/// grid value that maximizes the AUC of the rescored samples, the others held
+/// fixed, until a pass changes nothing or `max_passes` is reached. A weight
+/// only moves when that strictly improves the AUC, so detectors the samples
+/// say nothing about keep their recorded weight.
+pub fn calibrate(samples: &[Sample], grid: &[f64], max_passes: usize) -> Result<Calibration> {
+    let Some(first) = samples.first() else { bail!("no samples to calibrate on") };
+    if let Some(other) = samples.iter().find(|s| s.model_version != first.model_version) {
+        bail!("samples mix scoring models {} and {}; calibrate one at a time", first.model_version, other.model_version);
+    }
+    if samples.iter().all(|s| s.rug) || samples.iter().all(|s| !s.rug) {
+        bail!("calibrating needs both rug and legit samples");
+    }
+    if let Some(weight) = grid.iter().find(|w| !w.is_finite() || **w < 0.0) {
+        bail!("grid weights must be non-negative numbers, got {}", weight);
+    }
+
+    let mut weights: BTreeMap<String, f64> = BTreeMap::new();
+    for sample in samples {
+        for (id, signal) in &sample.detectors {
+            weights.entry(id.clone()).or_insert(signal.weight);
+        }
+    }
+    let auc_with = |weights: &BTreeMap<String, f64>| {
+        let points: Vec<(f64, bool)> = samples.iter().map(|s| (s.rescore(weights), s.rug)).collect();
+        auc(&points).unwrap_or(0.5)
+    };
+
+    let auc_before = auc_with(&weights);
+    let mut best = auc_before;
+    let mut passes = 0;
+    let ids: Vec<String> = weights.keys().cloned().collect();
+    while passes < max_passes {
+        passes += 1;
+        let mut changed = false;
+        for id in &ids {
+            let mut kept = weights[id];
+            for &candidate in grid {
+                if candidate == kept {
+                    continue;
+                }
+                weights.insert(id.clone(), candidate);
+                let candidate_auc = auc_with(&weights);
+                if candidate_auc > best + 1e-12 {
+                    best = candidate_auc;
+                    kept = candidate;
+                    changed = true;
+                } else {
+                    weights.insert(id.clone(), kept);
+                }
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    Ok(Calibration {
+        model_version: first.model_version.clone(),
+        samples: samples.len(),
+        weights,
+        auc_before,
+        auc_after: best,
+        passes,
+    })
+}
+
+impl Calibration {
+    /// A scoring config setting every calibrated weight, to load with `--config`
+    pub fn scoring(&self) -> ScoringConfig {
+        let detectors = self
+            .weights
+            .iter()
+            .map(|(id, weight)| (id.clone(), DetectorConfig { weight: Some(*weight), ..Default::default() }))
+            .collect();
+        ScoringConfig { detectors, ..Default::default() }
+    }
+}
@@ -0,0 +1,137 @@
+//! Batch progress: counts of the results a `batch` run has printed so far,
+//! reported on stderr while it runs (a progress bar at a terminal, NDJSON
+//! `progress` events otherwise) and as a closing `summary` with the score
+//! distribution. Results themselves stay on stdout.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::AnalysisOutput;
+
+/// Seconds between NDJSON progress events
+pub const PROGRESS_INTERVAL_SECS: u64 = 10;
+/// Width of a score histogram bucket, in points
+pub const BUCKET_POINTS: usize = 10;
+
+/// What a batch has produced so far
+#[derive(Debug, Clone, Default)]
+pub struct BatchTally {
+    failed: usize,
+    scores: Vec<f64>,
+    risk_levels: BTreeMap<String, usize>,
+}
+
+impl BatchTally {
+    pub fn record(&mut self, output: &AnalysisOutput) {
+        match &output.data {
+            Some(analysis) => {
+                self.scores.push(analysis.safe_score);
+                *self.risk_levels.entry(analysis.risk_level.clone()).or_default() += 1;
+            }
+            None => self.failed += 1,
+        }
+    }
+
+    /// Successful analyses
+    pub fn analyzed(&self) -> usize {
+        self.scores.len()
+    }
+
+    pub fn failed(&self) -> usize {
+        self.failed
+    }
+
+    pub fn done(&self) -> usize {
+        self.analyzed() + self.failed
+    }
+
+    pub fn progress(&self, total: Option<usize>, elapsed_secs: f64) -> BatchEvent {
+        BatchEvent::Progress { done: self.done(), total, analyzed: self.analyzed(), failed: self.failed, elapsed_secs }
+    }
+
+    /// The closing summary; `cache_hits` and `rpc_requests` are the
+    /// analyzer's over the whole run
+    pub fn summary(&self, cache_hits: u64, rpc_requests: u64, elapsed_secs: f64) -> BatchSummary {
+        BatchSummary {
+            analyzed: self.analyzed(),
+            failed: self.failed,
+            cache_hits,
+            rpc_requests,
+            elapsed_secs,
+            risk_levels: self.risk_levels.clone(),
+            scores: ScoreDistribution::of(&self.scores),
+        }
+    }
+}
+
+/// A line of the NDJSON stream on stderr
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "lowercase")]
+pub enum BatchEvent {
+    Progress {
+        /// Results printed so far
+        done: usize,
+        /// Mints in the input file; `None` reading stdin
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        total: Option<usize>,
+        analyzed: usize,
+        failed: usize,
+        elapsed_secs: f64,
+    },
+    Summary(BatchSummary),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchSummary {
+    pub analyzed: usize,
+    pub failed: usize,
+    /// RPC responses served from the cache
+    pub cache_hits: u64,
+    /// RPC requests the endpoints answered (and billed)
+    pub rpc_requests: u64,
+    pub elapsed_secs: f64,
+    /// Successful analyses per risk level
+    pub risk_levels: BTreeMap<String, usize>,
+    /// `None` when nothing was analyzed
+    pub scores: Option<ScoreDistribution>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoreDistribution {
+    pub min: f64,
+    pub median: f64,
+    pub mean: f64,
+    pub max: f64,
+    /// Analyses per `BUCKET_POINTS`-point bucket, `[0, 10)` first; 100 falls
+    /// in the last
+    pub histogram: Vec<usize>,
+}
+
+impl ScoreDistribution {
+    pub fn of(scores: &[f64]) -> Option<Self> {
+        if scores.is_empty() {
+            return None;
+        }
+        let mut sorted = scores.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let middle = sorted.len() / 2;
+        let median = match sorted.len() % 2 {
+            0 => (sorted[middle - 1] + sorted[middle]) / 2.0,
+            _ => sorted[middle],
+        };
+        let buckets = 100 / BUCKET_POINTS;
+        let mut histogram = vec![0; buckets];
+        for score in &sorted {
+            let bucket = (score.max(0.0) as usize / BUCKET_POINTS).min(buckets - 1);
+            histogram[bucket] += 1;
+        }
+        Some(Self {
+            min: sorted[0],
+            median,
+            mean: sorted.iter().sum::<f64>() / sorted.len() as f64,
+            max: sorted[sorted.len() - 1],
+            histogram,
+        })
+    }
+}
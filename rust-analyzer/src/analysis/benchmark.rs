@@ -0,0 +1,93 @@
+//! Benchmark percentiles: each headline metric placed within the tokens
+//! analyzed recently on this machine, so "38% top 10 holders" reads as
+//! typical or unusual for what is actually being launched, not just against
+//! the detectors' fixed thresholds.
+//!
+//! The population is the latest analysis of every other mint in the score
+//! history over the last `WINDOW_DAYS` (see `ScoreHistory::population`).
+//! Percentiles are informational; they don't feed the score.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::SafetyMetrics;
+
+/// Days of score history the population is drawn from
+pub const WINDOW_DAYS: i64 = 30;
+/// Most recent tokens in the population
+pub const MAX_POPULATION: usize = 1000;
+/// Fewer tokens than this make percentiles too coarse to report
+pub const MIN_POPULATION: usize = 20;
+
+/// Percentile (0-100) of each metric among the population: the share of
+/// tokens below the value, ties counting half
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Benchmark {
+    /// Tokens compared against
+    pub population: usize,
+    pub holder_count: f64,
+    /// Top 3 holders
+    pub whale_concentration: f64,
+    /// Top 10 holders
+    pub distribution_top10: f64,
+    pub token_age_hours: f64,
+    /// `None` when too few tokens in the population recorded it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transactions_last_hour: Option<f64>,
+}
+
+impl Benchmark {
+    /// `metrics` against `population`; `None` below `MIN_POPULATION`
+    pub fn against(metrics: &SafetyMetrics, population: &[SafetyMetrics]) -> Option<Self> {
+        if population.len() < MIN_POPULATION {
+            return None;
+        }
+        let rank = |value: fn(&SafetyMetrics) -> f64| percentile(value(metrics), population.iter().map(value));
+        let velocities: Vec<f64> = population
+            .iter()
+            .filter_map(|m| m.transactions_last_hour)
+            .map(|t| t as f64)
+            .collect();
+        let transactions_last_hour = metrics
+            .transactions_last_hour
+            .filter(|_| velocities.len() >= MIN_POPULATION)
+            .map(|t| percentile(t as f64, velocities.iter().copied()));
+        Some(Self {
+            population: population.len(),
+            holder_count: rank(|m| m.holder_count as f64),
+            whale_concentration: rank(|m| m.whale_concentration),
+            distribution_top10: rank(|m| m.distribution_top10),
+            token_age_hours: rank(|m| m.token_age_hours),
+            transactions_last_hour,
+        })
+    }
+
+    /// Percentile of the `SafetyMetrics` field named `metric`, if benchmarked
+    pub fn percentile_of(&self, metric: &str) -> Option<f64> {
+        match metric {
+            "holder_count" => Some(self.holder_count),
+            "whale_concentration" => Some(self.whale_concentration),
+            "distribution_top10" => Some(self.distribution_top10),
+            "token_age_hours" => Some(self.token_age_hours),
+            "transactions_last_hour" => self.transactions_last_hour,
+            _ => None,
+        }
+    }
+}
+
+/// Share (0-100) of `population` below `value`, ties counting half
+pub fn percentile(value: f64, population: impl Iterator<Item = f64>) -> f64 {
+    let (mut below, mut equal, mut total) = (0.0, 0.0, 0usize);
+    for other in population {
+        total += 1;
+        if other < value {
+            below += 1.0;
+        } else if other == value {
+            equal += 1.0;
+        }
+    }
+    if total == 0 {
+        return 50.0;
+    }
+    100.0 * (below + equal / 2.0) / total as f64
+}
@@ -0,0 +1,94 @@
+//! Score breakdown: how each detector's signal turned into points of the
+//! composite score, the normalization that produced it and the thresholds the
+//! detector judged against, so a score of 43 can be audited rather than trusted.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::detectors::calculate_composite_score;
+use super::patterns::{PatternDetector, PatternSignal};
+
+/// Score reported when there is nothing to weigh
+const NEUTRAL_SCORE: f64 = 50.0;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ScoreBreakdown {
+    /// Σ weight over all detectors that ran
+    pub total_weight: f64,
+    /// Σ score × confidence × weight
+    pub weighted_sum: f64,
+    /// `weighted_sum / total_weight × 100`, before clamping to 0-100
+    pub raw_score: f64,
+    pub score: f64,
+    /// The computation with this analysis' numbers filled in
+    pub formula: String,
+    /// Largest loss of points first
+    pub detectors: Vec<DetectorContribution>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DetectorContribution {
+    pub id: String,
+    pub name: String,
+    pub score: f64,
+    pub confidence: f64,
+    pub weight: f64,
+    /// score × confidence × weight
+    pub weighted: f64,
+    /// Points of the 0-100 score this detector contributed
+    pub points: f64,
+    /// Points it would have added had it scored 1.0 with full confidence
+    pub points_lost: f64,
+    /// The band the detector put the token in (`CRITICAL`, `HIGH`, `SAFE`, ...)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub band: Option<String>,
+    /// Threshold values the detector judged against, as configured
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub thresholds: Map<String, Value>,
+}
+
+impl ScoreBreakdown {
+    /// Explains `calculate_composite_score(signals)`; `detectors[i]` produced `signals[i]`
+    pub fn new(detectors: &[Box<dyn PatternDetector>], signals: &[PatternSignal]) -> Self {
+        let total_weight: f64 = signals.iter().map(|s| s.weight).sum();
+        let weighted_sum: f64 = signals.iter().map(|s| s.score * s.confidence * s.weight).sum();
+        let score = calculate_composite_score(signals);
+        let share = |value: f64| if total_weight > 0.0 { value / total_weight * 100.0 } else { 0.0 };
+
+        let (raw_score, formula) = if total_weight > 0.0 {
+            let raw_score = share(weighted_sum);
+            let formula = format!(
+                "Σ(score × confidence × weight) / Σweight × 100 = {:.4} / {:.4} × 100 = {:.2}",
+                weighted_sum, total_weight, raw_score,
+            );
+            (raw_score, formula)
+        } else {
+            (NEUTRAL_SCORE, format!("no weighted detectors ran, so the score is neutral ({})", NEUTRAL_SCORE))
+        };
+        let formula = if raw_score != score { format!("{}, clamped to {:.2}", formula, score) } else { formula };
+
+        let mut contributions: Vec<DetectorContribution> = detectors
+            .iter()
+            .zip(signals)
+            .map(|(detector, signal)| {
+                let weighted = signal.score * signal.confidence * signal.weight;
+                DetectorContribution {
+                    id: detector.id(),
+                    name: signal.name.clone(),
+                    score: signal.score,
+                    confidence: signal.confidence,
+                    weight: signal.weight,
+                    weighted,
+                    points: share(weighted),
+                    points_lost: share(signal.weight - weighted),
+                    band: signal.band().map(str::to_string),
+                    thresholds: detector.thresholds(),
+                }
+            })
+            .collect();
+        contributions.sort_by(|a, b| b.points_lost.total_cmp(&a.points_lost));
+
+        Self { total_weight, weighted_sum, raw_score, score, formula, detectors: contributions }
+    }
+}
@@ -0,0 +1,96 @@
+//! On-disk RPC response cache. Successful responses are stored per
+//! (method, params) with the mint they were fetched for, so re-analyzing a
+//! token within the TTL makes no Helius calls.
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+
+pub const DEFAULT_TTL: Duration = Duration::from_secs(300);
+
+/// `$XDG_CACHE_HOME/analyze-token/cache.sqlite3`, falling back to `~/.cache`
+/// and then the temp dir
+pub fn default_path() -> PathBuf {
+    std::env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+        .unwrap_or_else(std::env::temp_dir)
+        .join("analyze-token")
+        .join("cache.sqlite3")
+}
+
+pub struct RpcCache {
+    conn: Mutex<Connection>,
+    ttl: Duration,
+}
+
+impl RpcCache {
+    pub fn open(path: &Path, ttl: Duration) -> Result<Self> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).with_context(|| format!("creating {}", dir.display()))?;
+        }
+        let conn = Connection::open(path).with_context(|| format!("opening cache {}", path.display()))?;
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             CREATE TABLE IF NOT EXISTS rpc_cache (
+                 method     TEXT NOT NULL,
+                 params     TEXT NOT NULL,
+                 mint       TEXT NOT NULL,
+                 response   TEXT NOT NULL,
+                 fetched_at INTEGER NOT NULL,
+                 PRIMARY KEY (method, params)
+             );
+             CREATE INDEX IF NOT EXISTS rpc_cache_mint ON rpc_cache (mint);",
+        )?;
+        Ok(Self { conn: Mutex::new(conn), ttl })
+    }
+
+    fn now() -> i64 {
+        chrono::Utc::now().timestamp()
+    }
+
+    /// Cached response for the call, unless older than the TTL
+    pub fn get(&self, method: &str, params: &serde_json::Value) -> Result<Option<serde_json::Value>> {
+        let cutoff = Self::now() - self.ttl.as_secs() as i64;
+        let conn = self.conn.lock().unwrap();
+        let response: Option<String> = conn
+            .query_row(
+                "SELECT response FROM rpc_cache WHERE method = ?1 AND params = ?2 AND fetched_at >= ?3",
+                params![method, params.to_string(), cutoff],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(response.map(|r| serde_json::from_str(&r)).transpose()?)
+    }
+
+    pub fn put(&self, mint: &str, method: &str, params: &serde_json::Value, response: &serde_json::Value) -> Result<()> {
+        self.conn.lock().unwrap().execute(
+            "INSERT OR REPLACE INTO rpc_cache (method, params, mint, response, fetched_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![method, params.to_string(), mint, response.to_string(), Self::now()],
+        )?;
+        Ok(())
+    }
+
+    /// Drops entries past the TTL; returns how many were removed
+    pub fn evict_expired(&self) -> Result<usize> {
+        let cutoff = Self::now() - self.ttl.as_secs() as i64;
+        let conn = self.conn.lock().unwrap();
+        Ok(conn.execute("DELETE FROM rpc_cache WHERE fetched_at < ?1", params![cutoff])?)
+    }
+
+    pub fn evict_mint(&self, mint: &str) -> Result<usize> {
+        let conn = self.conn.lock().unwrap();
+        Ok(conn.execute("DELETE FROM rpc_cache WHERE mint = ?1", params![mint])?)
+    }
+
+    pub fn evict_all(&self) -> Result<usize> {
+        let conn = self.conn.lock().unwrap();
+        let removed = conn.execute("DELETE FROM rpc_cache", [])?;
+        conn.execute_batch("VACUUM")?;
+        Ok(removed)
+    }
+}
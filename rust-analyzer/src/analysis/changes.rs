@@ -0,0 +1,176 @@
+//! Holder changes between two analyses of the same mint, which `watch` alerts
+//! on: a top holder exiting, a new whale accumulating, liquidity withdrawn.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::patterns::HolderInfo;
+use super::SafetyAnalysis;
+
+/// Holders `SafetyAnalysis::top_holders` keeps for the next comparison
+pub const TOP_HOLDERS: usize = 10;
+
+/// Holders whose exits and entries are alerted on
+const WHALES: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ChangeKind {
+    /// A previous top-3 holder cut its position
+    WhaleExit,
+    /// A current top-3 holder entered or grew its position
+    WhaleAccumulation,
+    /// Pool depth fell, liquidity was pulled
+    LpWithdrawal,
+}
+
+impl ChangeKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ChangeKind::WhaleExit => "WHALE_EXIT",
+            ChangeKind::WhaleAccumulation => "WHALE_ACCUMULATION",
+            ChangeKind::LpWithdrawal => "LP_WITHDRAWAL",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HolderChange {
+    pub kind: ChangeKind,
+    /// Wallet (or token account when the owner is unknown) that moved; `None`
+    /// for liquidity changes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub holder: Option<String>,
+    /// Percent change of the position or pool depth, negative when it shrank;
+    /// `None` for a holder new to the largest accounts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub change_percent: Option<f64>,
+    pub message: String,
+}
+
+/// Percent moves that count as a change worth alerting on
+#[derive(Debug, Clone, Copy)]
+pub struct ChangeThresholds {
+    /// A top-3 holder's balance falling by more than this
+    pub whale_exit: f64,
+    /// A top-3 holder's balance growing by more than this
+    pub whale_accumulation: f64,
+    /// SOL or USD pool depth falling by more than this
+    pub lp_withdrawal: f64,
+}
+
+impl Default for ChangeThresholds {
+    fn default() -> Self {
+        Self { whale_exit: 50.0, whale_accumulation: 100.0, lp_withdrawal: 30.0 }
+    }
+}
+
+/// What changed from `previous` to `current` beyond `thresholds`
+pub fn holder_changes(previous: &SafetyAnalysis, current: &SafetyAnalysis, thresholds: &ChangeThresholds) -> Vec<HolderChange> {
+    let mut changes = Vec::new();
+    // Holders missing from either run (a partial analysis) say nothing about moves
+    if !previous.top_holders.is_empty() && !current.top_holders.is_empty() {
+        whale_changes(previous, current, thresholds, &mut changes);
+    }
+
+    let depths = [
+        ("SOL", previous.metrics.liquidity_sol, current.metrics.liquidity_sol),
+        ("USD", previous.metrics.liquidity_usd, current.metrics.liquidity_usd),
+    ];
+    for (quote, before, after) in depths {
+        let change = percent_change(before, after);
+        if before > 0.0 && change < -thresholds.lp_withdrawal {
+            changes.push(HolderChange {
+                kind: ChangeKind::LpWithdrawal,
+                holder: None,
+                change_percent: Some(change),
+                message: format!("{} pool depth fell {:.0}% ({:.2} -> {:.2})", quote, -change, before, after),
+            });
+        }
+    }
+    changes
+}
+
+fn whale_changes(previous: &SafetyAnalysis, current: &SafetyAnalysis, thresholds: &ChangeThresholds, changes: &mut Vec<HolderChange>) {
+    let before = balances(&previous.top_holders);
+    let after = balances(&current.top_holders);
+
+    for id in whales(&previous.top_holders) {
+        let balance = after.get(id).copied().unwrap_or_else(|| below_list(&current.top_holders));
+        let change = percent_change(before[id], balance);
+        if change < -thresholds.whale_exit {
+            changes.push(HolderChange {
+                kind: ChangeKind::WhaleExit,
+                holder: Some(id.to_string()),
+                change_percent: Some(change),
+                message: format!("top holder {} cut its position by {:.0}%", short(id), -change),
+            });
+        }
+    }
+
+    for id in whales(&current.top_holders) {
+        let share = share(&current.top_holders, id);
+        let (change, message) = match before.get(id) {
+            Some(&balance) => {
+                let change = percent_change(balance, after[id]);
+                if change <= thresholds.whale_accumulation {
+                    continue;
+                }
+                (Some(change), format!("{} grew its position by {:.0}% to {:.1}% of holdings", short(id), change, share))
+            }
+            // Wasn't among the largest accounts at all last run
+            None => (None, format!("new whale {} holds {:.1}% of holdings", short(id), share)),
+        };
+        changes.push(HolderChange { kind: ChangeKind::WhaleAccumulation, holder: Some(id.to_string()), change_percent: change, message });
+    }
+}
+
+/// Holders are matched by wallet, so moving tokens between a wallet's own
+/// token accounts isn't an exit
+fn holder_id(holder: &HolderInfo) -> &str {
+    holder.owner.as_deref().unwrap_or(&holder.address)
+}
+
+/// The distinct wallets behind the largest `WHALES` accounts
+fn whales(holders: &[HolderInfo]) -> Vec<&str> {
+    let mut ids: Vec<&str> = Vec::new();
+    for id in holders.iter().take(WHALES).map(holder_id) {
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+    ids
+}
+
+fn share(holders: &[HolderInfo], id: &str) -> f64 {
+    holders.iter().filter(|h| holder_id(h) == id).map(|h| h.percent).sum()
+}
+
+fn balances(holders: &[HolderInfo]) -> HashMap<&str, f64> {
+    let mut balances = HashMap::new();
+    for holder in holders {
+        *balances.entry(holder_id(holder)).or_default() += holder.balance;
+    }
+    balances
+}
+
+/// Upper bound on the balance of a holder that dropped out of the list
+fn below_list(holders: &[HolderInfo]) -> f64 {
+    if holders.len() < TOP_HOLDERS {
+        // A full list was returned, so a missing holder sold out
+        return 0.0;
+    }
+    holders.last().map_or(0.0, |h| h.balance)
+}
+
+fn percent_change(before: f64, after: f64) -> f64 {
+    if before <= 0.0 {
+        return 0.0;
+    }
+    (after - before) / before * 100.0
+}
+
+fn short(address: &str) -> &str {
+    address.get(..8).unwrap_or(address)
+}
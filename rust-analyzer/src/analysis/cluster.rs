@@ -0,0 +1,107 @@
+//! Solana clusters. Devnet has endpoints of its own and runs some programs
+//! (Raydium's) at other addresses than mainnet, and mainnet's exchange wallets
+//! and pool authorities mean nothing there. `custom` is any other endpoint,
+//! such as a local validator cloning mainnet programs: it keeps mainnet's
+//! addresses and needs an explicit `--rpc-url`.
+//!
+//! The Missout program has the same id on every cluster.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{bail, Result};
+
+use super::analyzer::DEFAULT_RPC_URL;
+
+/// Programs deployed elsewhere on devnet, as (mainnet, devnet)
+const DEVNET_PROGRAMS: &[(&str, &str)] = &[
+    ("675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8", "HWy1jotHpo6UqeQxx49dpYYdQB8wj9Qk9MdxwjLvDHB8"), // Raydium AMM v4
+    ("CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C", "CPMDWBwJDtYax9qW7AyRuVC19Cc4L4Vcy4n2BHAbHkCW"), // Raydium CPMM
+    ("CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK", "devi51mZmdwUJGU9hjN27vEz64Gps7uUefqxg27EAtH"), // Raydium CLMM
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Cluster {
+    #[default]
+    Mainnet,
+    Devnet,
+    Custom,
+}
+
+impl Cluster {
+    pub const ALL: [Cluster; 3] = [Cluster::Mainnet, Cluster::Devnet, Cluster::Custom];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Mainnet => "mainnet",
+            Self::Devnet => "devnet",
+            Self::Custom => "custom",
+        }
+    }
+
+    /// Endpoint used without `--rpc-url` or API keys; `None` for `custom`
+    pub fn default_rpc_url(self) -> Option<&'static str> {
+        match self {
+            Self::Mainnet => Some(DEFAULT_RPC_URL),
+            Self::Devnet => Some("https://api.devnet.solana.com"),
+            Self::Custom => None,
+        }
+    }
+
+    /// Helius RPC URL an API key is appended to; `None` for `custom`
+    pub fn helius_url(self) -> Option<&'static str> {
+        match self {
+            Self::Mainnet => Some("https://mainnet.helius-rpc.com/?api-key="),
+            Self::Devnet => Some("https://devnet.helius-rpc.com/?api-key="),
+            Self::Custom => None,
+        }
+    }
+
+    /// Address of the mainnet program `mainnet` on this cluster
+    pub fn program(self, mainnet: &'static str) -> &'static str {
+        match self {
+            Self::Devnet => DEVNET_PROGRAMS.iter().find(|(m, _)| *m == mainnet).map_or(mainnet, |(_, d)| d),
+            Self::Mainnet | Self::Custom => mainnet,
+        }
+    }
+
+    /// Whether mainnet's wallets and pool authorities are meaningful here
+    pub fn has_mainnet_accounts(self) -> bool {
+        self != Self::Devnet
+    }
+
+    /// `path` for this cluster: unchanged on mainnet, else with the cluster
+    /// after the file stem, so caches and histories of clusters never mix
+    pub fn namespaced(self, path: &Path) -> PathBuf {
+        if self == Self::Mainnet {
+            return path.to_path_buf();
+        }
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        let name = match path.extension() {
+            Some(ext) => format!("{}-{}.{}", stem, self, ext.to_string_lossy()),
+            None => format!("{}-{}", stem, self),
+        };
+        path.with_file_name(name)
+    }
+}
+
+impl fmt::Display for Cluster {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Cluster {
+    type Err = anyhow::Error;
+
+    fn from_str(name: &str) -> Result<Self> {
+        match Self::ALL.into_iter().find(|c| c.as_str() == name) {
+            Some(cluster) => Ok(cluster),
+            None => {
+                let names: Vec<&str> = Self::ALL.iter().map(|c| c.as_str()).collect();
+                bail!("unknown cluster `{}` (available: {})", name, names.join(", "))
+            }
+        }
+    }
+}
@@ -0,0 +1,120 @@
+//! Partial results: in partial mode a failed fetch no longer fails the
+//! analysis. The source is recorded as missing, the detectors reading it
+//! report no data (confidence 0), and `DataCompleteness` says what was lost.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::patterns::{Evidence, PatternDetector, PatternSignal};
+
+/// A fetched input of the detectors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DataSource {
+    /// The mint account: authorities, extensions, decimals and supply
+    Mint,
+    /// The largest token accounts
+    Holders,
+    /// Recent transactions and their classification
+    Transactions,
+    Metadata,
+    Liquidity,
+    SellSimulation,
+    Deployer,
+    Launch,
+    HolderFunding,
+    Market,
+}
+
+impl DataSource {
+    pub const ALL: [DataSource; 10] = [
+        DataSource::Mint,
+        DataSource::Holders,
+        DataSource::Transactions,
+        DataSource::Metadata,
+        DataSource::Liquidity,
+        DataSource::SellSimulation,
+        DataSource::Deployer,
+        DataSource::Launch,
+        DataSource::HolderFunding,
+        DataSource::Market,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DataSource::Mint => "mint",
+            DataSource::Holders => "holders",
+            DataSource::Transactions => "transactions",
+            DataSource::Metadata => "metadata",
+            DataSource::Liquidity => "liquidity",
+            DataSource::SellSimulation => "sell_simulation",
+            DataSource::Deployer => "deployer",
+            DataSource::Launch => "launch",
+            DataSource::HolderFunding => "holder_funding",
+            DataSource::Market => "market",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MissingData {
+    pub source: DataSource,
+    /// Why the fetch failed
+    pub error: String,
+}
+
+/// What a partial analysis went without
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DataCompleteness {
+    /// Every source was fetched
+    pub complete: bool,
+    pub missing: Vec<MissingData>,
+    /// Detectors that reported no data because an input was missing
+    pub no_data_detectors: Vec<String>,
+}
+
+impl DataCompleteness {
+    pub fn complete() -> Self {
+        Self { complete: true, missing: Vec::new(), no_data_detectors: Vec::new() }
+    }
+}
+
+/// The sources among `detector`'s inputs in `sources`
+pub fn inputs_among(detector: &dyn PatternDetector, sources: &[DataSource]) -> Vec<DataSource> {
+    let inputs = detector.inputs();
+    sources.iter().copied().filter(|source| inputs.contains(source)).collect()
+}
+
+/// The missing sources among `detector`'s inputs
+pub fn missing_inputs(detector: &dyn PatternDetector, missing: &[MissingData]) -> Vec<DataSource> {
+    let inputs = detector.inputs();
+    missing.iter().map(|m| m.source).filter(|source| inputs.contains(source)).collect()
+}
+
+/// The signal of a detector whose `sources` are missing: neutral score, no
+/// confidence, so it can't pass or fail the token on data it never saw
+pub fn no_data(detector: &dyn PatternDetector, sources: &[DataSource]) -> PatternSignal {
+    let sources: Vec<&str> = sources.iter().map(|s| s.as_str()).collect();
+    PatternSignal {
+        name: detector.name().to_string(),
+        score: 0.5,
+        confidence: 0.0,
+        details: format!("UNKNOWN: no data, {} unavailable", sources.join(", ")),
+        weight: detector.weight(),
+        evidence: Evidence::default(),
+    }
+}
+
+/// The signal of a detector whose `sources` were deliberately not fetched (an
+/// allow-listed or deny-listed mint): weight 0, so it doesn't move the score
+pub fn skipped(detector: &dyn PatternDetector, sources: &[DataSource]) -> PatternSignal {
+    let sources: Vec<&str> = sources.iter().map(|s| s.as_str()).collect();
+    PatternSignal {
+        name: detector.name().to_string(),
+        score: 0.5,
+        confidence: 0.0,
+        details: format!("SKIPPED: {} not fetched", sources.join(", ")),
+        weight: 0.0,
+        evidence: Evidence::default(),
+    }
+}
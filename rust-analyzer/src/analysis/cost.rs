@@ -0,0 +1,106 @@
+//! RPC credit accounting. Helius bills per call, by method: what each one
+//! costs, and what one analysis is expected to spend under a config, for
+//! `--dry-run-cost` and for keeping API keys within their budgets (see `rpc`).
+//!
+//! The estimate is an upper bound for one uncached analysis of a token with one
+//! pool and a deployer found by the launch scan. Cached responses cost nothing,
+//! and an allow-listed mint skips the launch, deployer, funding and simulation
+//! calls.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use super::analyzer::SIGNATURE_PAGE;
+use super::deployer::{CHECKED_LAUNCHES, DEPLOYER_SIGNATURE_PAGES, FUNDING_TRANSACTIONS, SCANNED_TRANSACTIONS};
+use super::funding::TRACED_HOLDERS;
+use super::launch::{LAUNCH_TRANSACTIONS, MINT_SIGNATURE_PAGES, TRACED_BUNDLE_WALLETS};
+use super::liquidity::POOL_SEARCHES;
+use super::trades::CLASSIFIED_TRANSACTIONS;
+use super::AnalyzerConfig;
+
+/// Credits Helius bills for `method`: 10 for `getProgramAccounts` and DAS,
+/// 1 for standard RPC
+pub fn credits(method: &str) -> u64 {
+    match method {
+        "getProgramAccounts" | "getAsset" | "getAssetBatch" => 10,
+        _ => 1,
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MethodCost {
+    pub method: String,
+    pub calls: u64,
+    pub credits: u64,
+}
+
+/// Calls and credits one analysis is expected to spend, per method
+#[derive(Debug, Clone, Serialize)]
+pub struct CostEstimate {
+    /// Most credits first
+    pub methods: Vec<MethodCost>,
+    pub calls: u64,
+    pub credits: u64,
+}
+
+/// The calls `fetch_liquidity` makes for one mint with a single LP pool
+fn liquidity(calls: &mut BTreeMap<&'static str, u64>) {
+    *calls.entry("getProgramAccounts").or_default() += POOL_SEARCHES as u64;
+    // Vault balances, then the LP holders and their authorities
+    *calls.entry("getMultipleAccounts").or_default() += 3;
+    *calls.entry("getTokenSupply").or_default() += 1;
+    *calls.entry("getTokenLargestAccounts").or_default() += 1;
+}
+
+pub fn estimate(config: &AnalyzerConfig) -> CostEstimate {
+    let mut calls: BTreeMap<&'static str, u64> = BTreeMap::new();
+    let mut add = |method, count: usize| *calls.entry(method).or_default() += count as u64;
+
+    // Holders, recent signatures and the trades among them
+    add("getTokenLargestAccounts", 1);
+    add("getSignaturesForAddress", config.tx_depth.div_ceil(SIGNATURE_PAGE).max(1));
+    add("getTransaction", config.tx_depth.min(CLASSIFIED_TRANSACTIONS));
+    // The mint and its metadata
+    match config.das {
+        true => add("getAsset", 1),
+        false => add("getAccountInfo", 2),
+    }
+    // Missout pools of the mint
+    add("getProgramAccounts", 1);
+    // Launch window
+    add("getSignaturesForAddress", MINT_SIGNATURE_PAGES);
+    add("getTransaction", LAUNCH_TRANSACTIONS);
+    // Ages of wallets buying in a bundled launch, a signature page each
+    add("getSignaturesForAddress", TRACED_BUNDLE_WALLETS);
+    // Deployer history: its creation transaction, its own history, the funding
+    // transfer and the names of its earlier launches
+    add("getTransaction", 1 + SCANNED_TRANSACTIONS + FUNDING_TRANSACTIONS);
+    add("getSignaturesForAddress", DEPLOYER_SIGNATURE_PAGES);
+    if config.das {
+        add("getAssetBatch", 1);
+    }
+    // Holder funding: a signature page and the oldest transfers per holder
+    add("getSignaturesForAddress", TRACED_HOLDERS);
+    add("getTransaction", TRACED_HOLDERS * FUNDING_TRANSACTIONS);
+    // Sell simulation: pool state, AMM config, holder accounts, the simulation
+    add("getAccountInfo", 2);
+    add("getMultipleAccounts", 1);
+    add("simulateTransaction", 1);
+
+    // The mint's pools, then those of every earlier launch checked
+    for _ in 0..=CHECKED_LAUNCHES {
+        liquidity(&mut calls);
+    }
+
+    let mut methods: Vec<MethodCost> = calls
+        .into_iter()
+        .map(|(method, calls)| MethodCost { method: method.to_string(), calls, credits: calls * credits(method) })
+        .collect();
+    methods.sort_by(|a, b| b.credits.cmp(&a.credits).then_with(|| a.method.cmp(&b.method)));
+    CostEstimate {
+        calls: methods.iter().map(|m| m.calls).sum(),
+        credits: methods.iter().map(|m| m.credits).sum(),
+        methods,
+    }
+}
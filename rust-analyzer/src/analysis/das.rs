@@ -0,0 +1,177 @@
+//! Helius DAS (Digital Asset Standard). One `getAsset` call returns a mint's
+//! authorities, supply, token program, metadata and compression status, in
+//! place of the `jsonParsed` mint and Metaplex metadata lookups, and for
+//! Token-2022 metadata and compressed assets alike. `getAssetBatch` names a
+//! deployer's earlier launches.
+//!
+//! Endpoints without DAS answer "method not found"; the analyzer then falls
+//! back to plain RPC for good. Token-2022 extension state is still read from
+//! `jsonParsed`, which the extension detector's rules are written against.
+
+use anyhow::{anyhow, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::accounts::{MintInfo, TokenMetadata, TOKEN_2022_PROGRAM_ID};
+#[cfg(feature = "native")]
+use super::TokenAnalyzer;
+
+/// Owner of compressed NFTs, which have no mint account
+pub const BUBBLEGUM_PROGRAM_ID: &str = "BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY";
+/// Most ids `getAssetBatch` takes per call
+#[cfg(feature = "native")]
+const ASSET_BATCH: usize = 1000;
+
+/// What DAS says about the asset beyond the mint and metadata
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AssetInfo {
+    /// DAS interface, e.g. `FungibleToken`, `FungibleAsset`, `V1_NFT`
+    pub interface: String,
+    /// Metaplex token standard, e.g. `Fungible`; `None` without Metaplex metadata
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_standard: Option<String>,
+    /// A compressed (Bubblegum) asset, living in a Merkle tree, not a mint account
+    pub compressed: bool,
+    pub mutable: bool,
+    pub burnt: bool,
+    /// Holder of a compressed asset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+}
+
+/// A decoded `getAsset` result
+#[derive(Debug, Clone)]
+pub struct Asset {
+    pub info: AssetInfo,
+    pub mint: MintInfo,
+    pub metadata: Option<TokenMetadata>,
+}
+
+impl Asset {
+    /// Decodes a `getAsset` result
+    pub fn from_das(value: &Value) -> Result<Self> {
+        if value.is_null() {
+            return Err(anyhow!("asset not found"));
+        }
+        let compressed = value["compression"]["compressed"].as_bool().unwrap_or(false);
+        let info = AssetInfo {
+            interface: value["interface"].as_str().unwrap_or_default().to_string(),
+            token_standard: value["content"]["metadata"]["token_standard"].as_str().map(str::to_string),
+            compressed,
+            mutable: value["mutable"].as_bool().unwrap_or(false),
+            burnt: value["burnt"].as_bool().unwrap_or(false),
+            owner: value["ownership"]["owner"].as_str().filter(|_| compressed).map(str::to_string),
+        };
+
+        let token = &value["token_info"];
+        let authority = |key: &str| token[key].as_str().filter(|a| !a.is_empty()).map(str::to_string);
+        let mint = match (compressed, token["decimals"].as_u64()) {
+            (_, Some(decimals)) => MintInfo {
+                program: token["token_program"].as_str().unwrap_or_default().to_string(),
+                decimals: decimals as u8,
+                supply: token["supply"].as_u64().ok_or_else(|| anyhow!("asset has no supply"))?,
+                mint_authority: authority("mint_authority"),
+                freeze_authority: authority("freeze_authority"),
+                extensions: Vec::new(),
+            },
+            // One leaf, no authority able to mint or freeze more
+            (true, None) => MintInfo {
+                program: BUBBLEGUM_PROGRAM_ID.to_string(),
+                decimals: 0,
+                supply: 1,
+                mint_authority: None,
+                freeze_authority: None,
+                extensions: Vec::new(),
+            },
+            (false, None) => return Err(anyhow!("asset is not a token (interface {})", info.interface)),
+        };
+
+        let content = &value["content"]["metadata"];
+        let metadata = content["name"].as_str().map(|name| TokenMetadata {
+            name: name.trim().to_string(),
+            symbol: content["symbol"].as_str().unwrap_or_default().trim().to_string(),
+            uri: value["content"]["json_uri"].as_str().unwrap_or_default().to_string(),
+            update_authority: update_authority(&value["authorities"]),
+            is_mutable: value["mutable"].as_bool(),
+        });
+        Ok(Self { info, mint, metadata })
+    }
+
+    /// Whether the extension state has to come from `jsonParsed`
+    pub fn is_token_2022(&self) -> bool {
+        self.mint.program == TOKEN_2022_PROGRAM_ID
+    }
+}
+
+/// The authority with `full` or `metadata` scope
+fn update_authority(authorities: &Value) -> Option<String> {
+    authorities.as_array()?.iter().find_map(|a| {
+        let scopes = a["scopes"].as_array()?;
+        scopes
+            .iter()
+            .any(|s| s == "full" || s == "metadata")
+            .then(|| a["address"].as_str().map(str::to_string))
+            .flatten()
+    })
+}
+
+/// Whether an RPC error means the endpoint doesn't speak DAS
+#[cfg(feature = "native")]
+fn unsupported(error: &anyhow::Error) -> bool {
+    let message = format!("{:#}", error);
+    message.contains("-32601") || message.to_lowercase().contains("method not found")
+}
+
+#[cfg(feature = "native")]
+impl TokenAnalyzer {
+    /// `mint` through `getAsset`; `None` when DAS is off, unsupported or failed,
+    /// and plain RPC has to do
+    pub(super) async fn fetch_asset(&self, mint: &str) -> Option<Asset> {
+        let result = self.das_call(mint, "getAsset", serde_json::json!({ "id": mint })).await?;
+        Asset::from_das(&result)
+            .map_err(|e| tracing::debug!("getAsset unusable, falling back to plain RPC: {:#}", e))
+            .ok()
+    }
+
+    /// Name and symbol of each of `ids` DAS knows, in one `getAssetBatch` per
+    /// thousand; empty when DAS is unavailable
+    pub(super) async fn fetch_asset_names(&self, mint: &str, ids: &[&str]) -> Vec<(String, String, String)> {
+        let mut names = Vec::new();
+        for chunk in ids.chunks(ASSET_BATCH) {
+            let Some(result) = self.das_call(mint, "getAssetBatch", serde_json::json!({ "ids": chunk })).await else {
+                break;
+            };
+            let assets = result.as_array().into_iter().flatten();
+            names.extend(assets.filter_map(|asset| {
+                let metadata = &asset["content"]["metadata"];
+                Some((
+                    asset["id"].as_str()?.to_string(),
+                    metadata["name"].as_str()?.trim().to_string(),
+                    metadata["symbol"].as_str().unwrap_or_default().trim().to_string(),
+                ))
+            }));
+        }
+        names
+    }
+
+    /// A DAS call, remembering an endpoint that doesn't support DAS so later
+    /// analyses don't ask again
+    async fn das_call(&self, mint: &str, method: &str, params: Value) -> Option<Value> {
+        if !self.das_enabled() {
+            return None;
+        }
+        match self.rpc_call(mint, method, params).await {
+            Ok(result) => Some(result),
+            Err(e) if unsupported(&e) => {
+                tracing::info!("RPC endpoint has no DAS support, using plain RPC");
+                self.disable_das();
+                None
+            }
+            Err(e) => {
+                tracing::debug!("{} failed, falling back to plain RPC: {:#}", method, e);
+                None
+            }
+        }
+    }
+}
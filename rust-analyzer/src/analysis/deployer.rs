@@ -0,0 +1,244 @@
+//! Deployer history: finds the wallet that paid for the mint's
+//! `initializeMint`, then looks through that wallet's own transactions for
+//! earlier mints it created and checks whether their liquidity survived. The
+//! wallet's first incoming SOL transfer is reported as its funding origin.
+//!
+//! Everything here is plain RPC, so the scan is bounded: a token whose creation
+//! is out of the launch scan's reach (see `launch`) has no deployer history,
+//! and only the deployer's newest `SCANNED_TRANSACTIONS` are searched.
+
+use anyhow::{anyhow, Result};
+#[cfg(feature = "native")]
+use futures::{stream, StreamExt};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+#[cfg(feature = "native")]
+use super::TokenAnalyzer;
+
+/// Signature pages walked back through the deployer's history
+pub(super) const DEPLOYER_SIGNATURE_PAGES: usize = 2;
+/// Deployer transactions fetched in full to look for earlier mints
+pub(super) const SCANNED_TRANSACTIONS: usize = 50;
+/// Earlier launches whose liquidity is checked
+pub(super) const CHECKED_LAUNCHES: usize = 5;
+/// Oldest deployer transactions searched for the funding transfer
+pub(super) const FUNDING_TRANSACTIONS: usize = 3;
+/// A launch with less quote-side depth than this is considered dead
+const COLLAPSED_SOL: f64 = 1.0;
+const COLLAPSED_USD: f64 = 200.0;
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DeployerHistory {
+    /// Fee payer of the mint's creation transaction
+    pub address: String,
+    /// Sender of the deployer's first incoming SOL transfer
+    pub funder: Option<String>,
+    /// Mints the deployer created before this one, newest first
+    pub prior_launches: Vec<PriorLaunch>,
+    /// Deployer transactions searched for earlier mints
+    pub scanned_transactions: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PriorLaunch {
+    pub mint: String,
+    /// Name and symbol, when DAS knows the mint
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
+    pub launched_at: Option<i64>,
+    /// Whether its pools have since drained; `None` when not checked
+    pub collapsed: Option<bool>,
+}
+
+impl DeployerHistory {
+    /// Checked launches, and how many of them collapsed
+    pub fn collapse_counts(&self) -> (usize, usize) {
+        let checked = self.prior_launches.iter().filter_map(|l| l.collapsed);
+        checked.fold((0, 0), |(n, rugs), collapsed| (n + 1, rugs + collapsed as usize))
+    }
+}
+
+pub(super) struct Signature {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+}
+
+/// A wallet's first incoming SOL transfer
+pub(super) struct Funding {
+    pub source: String,
+    pub block_time: Option<i64>,
+}
+
+#[cfg(feature = "native")]
+impl TokenAnalyzer {
+    /// History of whoever paid for `creation`, the mint's oldest transaction
+    pub(super) async fn fetch_deployer_history(&self, mint: &str, creation: &Signature) -> Result<DeployerHistory> {
+        let tx = self.fetch_transaction(mint, &creation.signature).await?;
+        if !initialized_mints(&tx).iter().any(|m| m == mint) {
+            return Err(anyhow!("oldest mint transaction does not initialize it"));
+        }
+        let address = fee_payer(&tx).ok_or_else(|| anyhow!("creation transaction has no fee payer"))?;
+
+        let (signatures, _) = self.fetch_signatures(mint, &address, DEPLOYER_SIGNATURE_PAGES).await?;
+
+        // Newest first, so recent launches are the ones found and checked
+        let scanned: Vec<&Signature> = signatures
+            .iter()
+            .filter(|s| s.signature != creation.signature)
+            .take(SCANNED_TRANSACTIONS)
+            .collect();
+        // Collected up front: a lazy `Map` held across the await trips
+        // rustc's higher-ranked `Send` check in the server's handler
+        let lookups: Vec<_> = scanned
+            .iter()
+            .map(|&s| async move {
+                let launched = match self.fetch_transaction(mint, &s.signature).await {
+                    Ok(tx) => initialized_mints(&tx),
+                    Err(e) => {
+                        tracing::warn!(signature = %s.signature, "deployer transaction skipped: {:#}", e);
+                        Vec::new()
+                    }
+                };
+                launched
+                    .into_iter()
+                    .filter(|m| m != mint)
+                    .map(|m| PriorLaunch { mint: m, name: None, symbol: None, launched_at: s.block_time, collapsed: None })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        let mut prior_launches: Vec<PriorLaunch> = stream::iter(lookups).buffered(8).concat().await;
+
+        let checks = prior_launches.iter().take(CHECKED_LAUNCHES).map(|launch| async move {
+            self.fetch_liquidity(&launch.mint)
+                .await
+                .map(|l| l.sol_depth() < COLLAPSED_SOL && l.usd_depth() < COLLAPSED_USD)
+                .map_err(|e| tracing::warn!(launch = %launch.mint, "liquidity check failed: {:#}", e))
+                .ok()
+        });
+        let collapsed = futures::future::join_all(checks).await;
+        for (launch, collapsed) in prior_launches.iter_mut().zip(collapsed) {
+            launch.collapsed = collapsed;
+        }
+
+        if !prior_launches.is_empty() {
+            let ids: Vec<&str> = prior_launches.iter().map(|l| l.mint.as_str()).collect();
+            for (id, name, symbol) in self.fetch_asset_names(mint, &ids).await {
+                if let Some(launch) = prior_launches.iter_mut().find(|l| l.mint == id) {
+                    (launch.name, launch.symbol) = (Some(name), Some(symbol));
+                }
+            }
+        }
+
+        let funder = self
+            .fetch_funding(mint, &address, &signatures)
+            .await
+            .unwrap_or_else(|e| {
+                tracing::warn!(deployer = %address, "funding lookup failed: {:#}", e);
+                None
+            })
+            .map(|f| f.source);
+
+        Ok(DeployerHistory {
+            address,
+            funder,
+            prior_launches,
+            scanned_transactions: scanned.len(),
+        })
+    }
+
+    /// First SOL transfer into `wallet` among its oldest `signatures` (newest
+    /// first, as returned by `fetch_signatures`)
+    pub(super) async fn fetch_funding(&self, mint: &str, wallet: &str, signatures: &[Signature]) -> Result<Option<Funding>> {
+        for s in signatures.iter().rev().take(FUNDING_TRANSACTIONS) {
+            let tx = self.fetch_transaction(mint, &s.signature).await?;
+            let source = sol_transfers(&tx).find(|(_, to)| *to == wallet).map(|(from, _)| from.to_string());
+            if let Some(source) = source {
+                return Ok(Some(Funding { source, block_time: s.block_time }));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Successful signatures involving `address`, newest first, walking back at
+    /// most `max_pages` pages. The flag is false when history was left unread.
+    pub(super) async fn fetch_signatures(&self, mint: &str, address: &str, max_pages: usize) -> Result<(Vec<Signature>, bool)> {
+        let mut signatures = Vec::new();
+        let mut before: Option<String> = None;
+        for _ in 0..max_pages {
+            let mut config = json!({ "limit": 1000 });
+            if let Some(before) = &before {
+                config["before"] = json!(before);
+            }
+            let result = self
+                .rpc_call(mint, "getSignaturesForAddress", json!([address, config]))
+                .await?;
+            let page = result
+                .as_array()
+                .ok_or_else(|| anyhow!("Invalid response format"))?;
+
+            before = page.last().and_then(|s| s["signature"].as_str()).map(str::to_string);
+            signatures.extend(page.iter().filter(|s| s["err"].is_null()).filter_map(|s| {
+                Some(Signature {
+                    signature: s["signature"].as_str()?.to_string(),
+                    slot: s["slot"].as_u64().unwrap_or(0),
+                    block_time: s["blockTime"].as_i64(),
+                })
+            }));
+            if page.len() < 1000 {
+                return Ok((signatures, true));
+            }
+        }
+        Ok((signatures, false))
+    }
+
+    pub(super) async fn fetch_transaction(&self, mint: &str, signature: &str) -> Result<Value> {
+        let params = json!([signature, { "encoding": "jsonParsed", "maxSupportedTransactionVersion": 0 }]);
+        let tx = self.rpc_call(mint, "getTransaction", params).await?;
+        if tx.is_null() {
+            return Err(anyhow!("transaction {} not found", signature));
+        }
+        Ok(tx)
+    }
+}
+
+pub(super) fn fee_payer(tx: &Value) -> Option<String> {
+    let key = &tx["transaction"]["message"]["accountKeys"][0];
+    key["pubkey"].as_str().or(key.as_str()).map(str::to_string)
+}
+
+/// Top-level and inner instructions of a `jsonParsed` transaction
+pub(super) fn instructions(tx: &Value) -> impl Iterator<Item = &Value> {
+    let outer = tx["transaction"]["message"]["instructions"].as_array().into_iter().flatten();
+    let inner = tx["meta"]["innerInstructions"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .flat_map(|set| set["instructions"].as_array().into_iter().flatten());
+    outer.chain(inner)
+}
+
+fn parsed_instructions(tx: &Value) -> impl Iterator<Item = &Value> {
+    instructions(tx).map(|ix| &ix["parsed"])
+}
+
+/// Mints created by `initializeMint`/`initializeMint2` (either token program)
+fn initialized_mints(tx: &Value) -> Vec<String> {
+    let mut mints: Vec<String> = parsed_instructions(tx)
+        .filter(|p| p["type"].as_str().is_some_and(|t| t.starts_with("initializeMint")))
+        .filter_map(|p| p["info"]["mint"].as_str().map(str::to_string))
+        .collect();
+    mints.dedup();
+    mints
+}
+
+/// `(source, destination)` of every system transfer
+pub(super) fn sol_transfers(tx: &Value) -> impl Iterator<Item = (&str, &str)> {
+    parsed_instructions(tx)
+        .filter(|p| p["type"] == "transfer" && !p["info"]["lamports"].is_null())
+        .filter_map(|p| Some((p["info"]["source"].as_str()?, p["info"]["destination"].as_str()?)))
+}
@@ -1,15 +1,25 @@
-/**
- * ALL Pattern Detectors from profit/ trading bot
- * Ported and adapted for on-chain Helius RPC data
- */
+//! ALL Pattern Detectors from profit/ trading bot
+//! Ported and adapted for on-chain Helius RPC data
 
-use super::patterns::{PatternDetector, PatternSignal, TokenContext};
+use anyhow::{bail, Context, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use super::accounts::MintExtension;
+use super::completeness::DataSource;
+use super::freeze;
+use super::launch::SNIPE_SLOTS;
+use super::patterns::{Evidence, PatternDetector, PatternSignal, TokenContext};
+use super::scoring::{DetectorConfig, ScoringConfig};
+use super::velocity;
 
 // ============================================
 // CRITICAL FILTERS
 // ============================================
 
 /// Whale concentration detector (top holders %)
+#[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct WhaleConcentrationDetector {
     pub critical_threshold: f64,  // >80% = critical
     pub high_threshold: f64,      // >60% = high risk
@@ -35,6 +45,10 @@ impl PatternDetector for WhaleConcentrationDetector {
         0.25  // Critical importance
     }
 
+    fn inputs(&self) -> &[DataSource] {
+        &[DataSource::Holders]
+    }
+
     fn detect(&self, ctx: &TokenContext) -> PatternSignal {
         let concentration = ctx.whale_concentration(3);  // Top 3 holders
         
@@ -56,11 +70,14 @@ impl PatternDetector for WhaleConcentrationDetector {
             confidence: 0.95,
             details,
             weight: self.weight(),
+            evidence: Evidence::default().with("top3_percent", concentration),
         }
     }
 }
 
 /// Single wallet dominance detector
+#[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct SingleWalletDominanceDetector {
     pub critical_threshold: f64,  // >50%
     pub high_threshold: f64,      // >30%
@@ -84,6 +101,10 @@ impl PatternDetector for SingleWalletDominanceDetector {
         0.20
     }
 
+    fn inputs(&self) -> &[DataSource] {
+        &[DataSource::Holders]
+    }
+
     fn detect(&self, ctx: &TokenContext) -> PatternSignal {
         let top_holder = ctx.top_holder_percent();
         
@@ -103,11 +124,94 @@ impl PatternDetector for SingleWalletDominanceDetector {
             confidence: 0.90,
             details,
             weight: self.weight(),
+            evidence: Evidence::default().with("top_holder_percent", top_holder),
+        }
+    }
+}
+
+/// Common funder detector: top holders seeded by one wallet in a short window
+/// are one actor, however healthy the nominal concentration looks
+#[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CommonFunderDetector {
+    pub sybil_wallets: usize,   // this many wallets from one funder...
+    pub sybil_percent: f64,     // ...holding this much = sybil supply
+    pub warn_percent: f64,
+}
+
+impl Default for CommonFunderDetector {
+    fn default() -> Self {
+        Self {
+            sybil_wallets: 3,
+            sybil_percent: 30.0,
+            warn_percent: 15.0,
+        }
+    }
+}
+
+impl PatternDetector for CommonFunderDetector {
+    fn name(&self) -> &str {
+        "Common Funder"
+    }
+
+    fn weight(&self) -> f64 {
+        0.25
+    }
+
+    fn inputs(&self) -> &[DataSource] {
+        &[DataSource::HolderFunding, DataSource::Holders]
+    }
+
+    fn detect(&self, ctx: &TokenContext) -> PatternSignal {
+        let (score, confidence, details) = match &ctx.holder_funding {
+            Some(funding) if funding.traced_wallets > 0 => match funding.largest() {
+                Some(c) => {
+                    let cluster = format!(
+                        "{} wallets holding {:.1}% funded by {} within {}m",
+                        c.wallets.len(),
+                        c.percent,
+                        c.funder,
+                        c.span_secs / 60
+                    );
+                    if c.wallets.len() >= self.sybil_wallets && c.percent >= self.sybil_percent {
+                        (0.0, 0.85, format!("SYBIL: {}", cluster))
+                    } else if c.wallets.len() >= self.sybil_wallets || c.percent >= self.warn_percent {
+                        (0.3, 0.7, format!("CLUSTERED: {}", cluster))
+                    } else {
+                        (0.7, 0.5, format!("PAIRED: {}", cluster))
+                    }
+                }
+                None => (
+                    1.0,
+                    0.6,
+                    format!("INDEPENDENT: {} traced holder wallets have distinct funders", funding.traced_wallets),
+                ),
+            },
+            _ => (0.5, 0.2, "UNKNOWN: no holder wallet funding traced".to_string()),
+        };
+
+        let funding = ctx.holder_funding.as_ref();
+        let cluster = funding.and_then(|f| f.largest());
+        let evidence = Evidence::default()
+            .with("traced_wallets", funding.map(|f| f.traced_wallets as f64))
+            .with("cluster_wallets", cluster.map(|c| c.wallets.len() as f64))
+            .with("cluster_percent", cluster.map(|c| c.percent))
+            .with("cluster_span_secs", cluster.map(|c| c.span_secs as f64));
+
+        PatternSignal {
+            name: self.name().to_string(),
+            score,
+            confidence,
+            details,
+            weight: self.weight(),
+            evidence,
         }
     }
 }
 
 /// Coordinated pump detector
+#[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct CoordinatedPumpDetector {
     pub min_txs: usize,
     pub time_window: i64,  // seconds
@@ -131,6 +235,10 @@ impl PatternDetector for CoordinatedPumpDetector {
         0.30  // Very critical
     }
 
+    fn inputs(&self) -> &[DataSource] {
+        &[DataSource::Transactions]
+    }
+
     fn detect(&self, ctx: &TokenContext) -> PatternSignal {
         let has_pump = ctx.has_coordinated_pump(self.min_txs, self.time_window);
         
@@ -146,11 +254,301 @@ impl PatternDetector for CoordinatedPumpDetector {
             confidence: 0.85,
             details,
             weight: self.weight(),
+            evidence: Evidence::default().with("transactions", ctx.transaction_count() as f64),
+        }
+    }
+}
+
+/// Launch sniper detector: a few wallets buying a large share of supply in
+/// the first slots is a bundled launch waiting to dump
+#[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SniperDetector {
+    pub heavy_percent: f64,      // >20% of supply sniped
+    pub critical_percent: f64,   // >40%
+}
+
+impl Default for SniperDetector {
+    fn default() -> Self {
+        Self {
+            heavy_percent: 20.0,
+            critical_percent: 40.0,
+        }
+    }
+}
+
+impl PatternDetector for SniperDetector {
+    fn name(&self) -> &str {
+        "Launch Snipers"
+    }
+
+    fn weight(&self) -> f64 {
+        0.20
+    }
+
+    fn inputs(&self) -> &[DataSource] {
+        &[DataSource::Launch, DataSource::Mint]
+    }
+
+    fn detect(&self, ctx: &TokenContext) -> PatternSignal {
+        let supply = ctx.ui_supply();
+        let (score, confidence, details) = match &ctx.launch {
+            Some(launch) if supply > 0.0 && launch.launch_slot.is_some() => {
+                let percent = launch.sniped_tokens() / supply * 100.0;
+                let sniped = format!(
+                    "{} wallet(s) bought {:.1}% of supply within {} slots of launch",
+                    launch.snipers.len(),
+                    percent,
+                    SNIPE_SLOTS
+                );
+                if percent >= self.critical_percent {
+                    (0.0, 0.85, format!("SNIPED: {}", sniped))
+                } else if percent >= self.heavy_percent {
+                    (0.3, 0.75, format!("HEAVY SNIPING: {}", sniped))
+                } else {
+                    (1.0, 0.6, format!("FAIR LAUNCH: {}", sniped))
+                }
+            }
+            _ => (0.5, 0.2, "UNKNOWN: launch window out of reach".to_string()),
+        };
+
+        let launch = ctx.launch.as_ref().filter(|l| supply > 0.0 && l.launch_slot.is_some());
+        let evidence = Evidence::default()
+            .with("sniped_percent", launch.map(|l| l.sniped_tokens() / supply * 100.0))
+            .with("snipers", launch.map(|l| l.snipers.len() as f64));
+
+        PatternSignal {
+            name: self.name().to_string(),
+            score,
+            confidence,
+            details,
+            weight: self.weight(),
+            evidence,
+        }
+    }
+}
+
+/// Bundled launch detector: launch buys from several wallets in one
+/// Jito-tipped slot are one operator, and freshly funded wallets among them
+/// are there to make the buying look organic
+#[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BundlerDetector {
+    pub heavy_percent: f64,      // >10% of supply bought in bundles
+    pub critical_percent: f64,   // >25%
+    pub fresh_wallets: usize,    // bundled wallets funded within a day
+}
+
+impl Default for BundlerDetector {
+    fn default() -> Self {
+        Self {
+            heavy_percent: 10.0,
+            critical_percent: 25.0,
+            fresh_wallets: 2,
+        }
+    }
+}
+
+impl PatternDetector for BundlerDetector {
+    fn name(&self) -> &str {
+        "Bundled Launch"
+    }
+
+    fn weight(&self) -> f64 {
+        0.20
+    }
+
+    fn inputs(&self) -> &[DataSource] {
+        &[DataSource::Launch, DataSource::Mint]
+    }
+
+    fn detect(&self, ctx: &TokenContext) -> PatternSignal {
+        let supply = ctx.ui_supply();
+        let launch = ctx.launch.as_ref().filter(|l| supply > 0.0 && l.launch_slot.is_some());
+        let (score, confidence, details) = match launch {
+            Some(launch) if launch.bundles.is_empty() => (
+                1.0,
+                0.6,
+                format!("SAFE: no Jito-tipped multi-wallet buys within {} slots of launch", SNIPE_SLOTS),
+            ),
+            Some(launch) => {
+                let percent = launch.bundled_tokens() / supply * 100.0;
+                let fresh = launch.fresh_bundled_wallets();
+                let wallets: usize = launch.bundles.iter().map(|b| b.buyers.len()).sum();
+                let bundled = format!(
+                    "{} wallet(s) in {} bundle(s) bought {:.1}% of supply{}",
+                    wallets,
+                    launch.bundles.len(),
+                    percent,
+                    fresh.map(|f| format!(", {} freshly funded", f)).unwrap_or_default()
+                );
+                let fresh = fresh.is_some_and(|f| f >= self.fresh_wallets);
+                if percent >= self.critical_percent && fresh {
+                    (0.0, 0.85, format!("CRITICAL: {}", bundled))
+                } else if percent >= self.heavy_percent || fresh {
+                    (0.3, 0.75, format!("BUNDLED: {}", bundled))
+                } else {
+                    (0.7, 0.6, format!("MINOR: {}", bundled))
+                }
+            }
+            None => (0.5, 0.2, "UNKNOWN: launch window out of reach".to_string()),
+        };
+
+        let evidence = Evidence::default()
+            .with("bundled_percent", launch.map(|l| l.bundled_tokens() / supply * 100.0))
+            .with("bundles", launch.map(|l| l.bundles.len() as f64))
+            .with("fresh_wallets", launch.and_then(|l| l.fresh_bundled_wallets()).map(|f| f as f64));
+
+        PatternSignal {
+            name: self.name().to_string(),
+            score,
+            confidence,
+            details,
+            weight: self.weight(),
+            evidence,
+        }
+    }
+}
+
+/// Insider allocation detector: supply the deployer sent to other wallets
+/// before anyone could buy is team/insider supply waiting to be sold
+#[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct InsiderDetector {
+    pub notable_percent: f64,   // >5%
+    pub heavy_percent: f64,     // >15%
+    pub critical_percent: f64,  // >30%
+}
+
+impl Default for InsiderDetector {
+    fn default() -> Self {
+        Self {
+            notable_percent: 5.0,
+            heavy_percent: 15.0,
+            critical_percent: 30.0,
+        }
+    }
+}
+
+impl PatternDetector for InsiderDetector {
+    fn name(&self) -> &str {
+        "Insider Allocation"
+    }
+
+    fn weight(&self) -> f64 {
+        0.20
+    }
+
+    fn inputs(&self) -> &[DataSource] {
+        &[DataSource::Launch, DataSource::Mint]
+    }
+
+    fn detect(&self, ctx: &TokenContext) -> PatternSignal {
+        let (score, confidence, details) = match (ctx.insider_percent(), &ctx.launch) {
+            (Some(percent), Some(launch)) => {
+                let allocation = format!(
+                    "{:.1}% of supply sent to {} wallet(s) before launch",
+                    percent,
+                    launch.insiders.len()
+                );
+                if percent >= self.critical_percent {
+                    (0.0, 0.8, format!("INSIDER HEAVY: {}", allocation))
+                } else if percent >= self.heavy_percent {
+                    (0.3, 0.7, format!("LARGE ALLOCATION: {}", allocation))
+                } else if percent >= self.notable_percent {
+                    (0.6, 0.6, format!("ALLOCATED: {}", allocation))
+                } else {
+                    (1.0, 0.6, format!("CLEAN: {}", allocation))
+                }
+            }
+            _ => (0.5, 0.2, "UNKNOWN: launch window out of reach".to_string()),
+        };
+
+        let evidence = Evidence::default()
+            .with("insider_percent", ctx.insider_percent())
+            .with("insiders", ctx.launch.as_ref().map(|l| l.insiders.len() as f64));
+
+        PatternSignal {
+            name: self.name().to_string(),
+            score,
+            confidence,
+            details,
+            weight: self.weight(),
+            evidence,
+        }
+    }
+}
+
+/// Insider extraction detector: early wallets that already took a large part
+/// of what buyers paid in as realized profit have cashed out on them
+#[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct InsiderExtractionDetector {
+    pub heavy_percent: f64,      // >25% of buy volume taken as profit
+    pub critical_percent: f64,   // >50%
+    pub min_profit_sol: f64,     // profits below this are noise
+}
+
+impl Default for InsiderExtractionDetector {
+    fn default() -> Self {
+        Self {
+            heavy_percent: 25.0,
+            critical_percent: 50.0,
+            min_profit_sol: 1.0,
+        }
+    }
+}
+
+impl PatternDetector for InsiderExtractionDetector {
+    fn name(&self) -> &str {
+        "Insider Extraction"
+    }
+
+    fn weight(&self) -> f64 {
+        0.15
+    }
+
+    fn inputs(&self) -> &[DataSource] {
+        &[DataSource::Transactions, DataSource::Launch]
+    }
+
+    fn detect(&self, ctx: &TokenContext) -> PatternSignal {
+        let extraction = ctx.insider_extraction();
+        let (score, confidence, details) = match (extraction, &ctx.launch) {
+            (Some((profit, percent)), Some(_)) => {
+                let extracted = format!("early wallets realized {:.2} SOL, {:.1}% of what buyers spent", profit, percent);
+                if profit < self.min_profit_sol {
+                    (1.0, 0.5, format!("SAFE: {}", extracted))
+                } else if percent >= self.critical_percent {
+                    (0.0, 0.8, format!("EXTRACTED: {}", extracted))
+                } else if percent >= self.heavy_percent {
+                    (0.3, 0.7, format!("HEAVY EXTRACTION: {}", extracted))
+                } else {
+                    (0.8, 0.5, format!("TAKING PROFIT: {}", extracted))
+                }
+            }
+            (None, Some(_)) => (0.5, 0.3, "UNKNOWN: no buys classified".to_string()),
+            (_, None) => (0.5, 0.2, "UNKNOWN: launch window out of reach".to_string()),
+        };
+
+        let evidence = Evidence::default()
+            .with("insider_profit_sol", extraction.map(|(profit, _)| profit))
+            .with("insider_extraction_percent", extraction.map(|(_, percent)| percent));
+
+        PatternSignal {
+            name: self.name().to_string(),
+            score,
+            confidence,
+            details,
+            weight: self.weight(),
+            evidence,
         }
     }
 }
 
 /// Bot activity detector
+#[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct BotActivityDetector {
     pub min_repeats: usize,
 }
@@ -172,6 +570,10 @@ impl PatternDetector for BotActivityDetector {
         0.15
     }
 
+    fn inputs(&self) -> &[DataSource] {
+        &[DataSource::Transactions]
+    }
+
     fn detect(&self, ctx: &TokenContext) -> PatternSignal {
         let has_bots = ctx.has_bot_activity(self.min_repeats);
         
@@ -187,6 +589,7 @@ impl PatternDetector for BotActivityDetector {
             confidence: 0.75,
             details,
             weight: self.weight(),
+            evidence: Evidence::default().with("transactions", ctx.transaction_count() as f64),
         }
     }
 }
@@ -196,6 +599,8 @@ impl PatternDetector for BotActivityDetector {
 // ============================================
 
 /// Holder count validator
+#[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct HolderCountDetector {
     pub critical_min: usize,  // <10
     pub low_min: usize,       // <50
@@ -221,6 +626,10 @@ impl PatternDetector for HolderCountDetector {
         0.12
     }
 
+    fn inputs(&self) -> &[DataSource] {
+        &[DataSource::Holders]
+    }
+
     fn detect(&self, ctx: &TokenContext) -> PatternSignal {
         let count = ctx.unique_wallets();
         
@@ -240,11 +649,14 @@ impl PatternDetector for HolderCountDetector {
             confidence: 0.90,
             details,
             weight: self.weight(),
+            evidence: Evidence::default().with("holders", count as f64),
         }
     }
 }
 
 /// Transaction volume validator
+#[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct TransactionVolumeDetector {
     pub critical_min: usize,  // <20
     pub low_min: usize,       // <100
@@ -270,6 +682,10 @@ impl PatternDetector for TransactionVolumeDetector {
         0.08
     }
 
+    fn inputs(&self) -> &[DataSource] {
+        &[DataSource::Transactions]
+    }
+
     fn detect(&self, ctx: &TokenContext) -> PatternSignal {
         let count = ctx.transaction_count();
         
@@ -289,167 +705,1590 @@ impl PatternDetector for TransactionVolumeDetector {
             confidence: 0.80,
             details,
             weight: self.weight(),
+            evidence: Evidence::default().with("transactions", count as f64),
         }
     }
 }
 
 // ============================================
-// TIMING PATTERNS
+// MINT CONTROLS
 // ============================================
 
-/// Token age detector (maturity indicator)
-pub struct TokenAgeDetector {
-    pub very_new_hours: f64,      // <1h
-    pub new_hours: f64,           // <24h
-    pub established_hours: f64,   // >168h (1 week)
-}
+/// Mint/freeze authority detector. `create_pool` rejects mints with either
+/// authority set (the all-zero key counts as revoked), so does this.
+#[derive(Default, Serialize, Deserialize)]
+pub struct AuthorityDetector;
 
-impl Default for TokenAgeDetector {
-    fn default() -> Self {
-        Self {
-            very_new_hours: 1.0,
-            new_hours: 24.0,
-            established_hours: 168.0,
-        }
-    }
-}
+/// System program id; a mint authority set to it can never sign
+const ZERO_PUBKEY: &str = "11111111111111111111111111111111";
 
-impl PatternDetector for TokenAgeDetector {
+impl PatternDetector for AuthorityDetector {
     fn name(&self) -> &str {
-        "Token Age"
+        "Mint Authorities"
     }
 
     fn weight(&self) -> f64 {
-        0.10
+        0.30  // Heaviest: an active authority can void every other signal
+    }
+
+    fn inputs(&self) -> &[DataSource] {
+        &[DataSource::Mint]
     }
 
     fn detect(&self, ctx: &TokenContext) -> PatternSignal {
-        let age_hours = ctx.age_hours();
-        
-        let (score, details) = if age_hours < self.very_new_hours {
-            (0.3, format!("VERY NEW: {:.1}h old (high risk)", age_hours))
-        } else if age_hours < self.new_hours {
-            (0.6, format!("NEW: {:.1}h old", age_hours))
-        } else if age_hours > self.established_hours {
-            (1.0, format!("ESTABLISHED: {:.0}h old", age_hours))
-        } else {
-            (0.8, format!("MATURING: {:.1}h old", age_hours))
+        let active = |authority: &Option<String>| authority.as_deref().is_some_and(|a| a != ZERO_PUBKEY);
+
+        let (score, confidence, details) = match &ctx.mint_info {
+            None => (0.5, 0.5, "UNKNOWN: mint account not inspected".to_string()),
+            Some(mint) => match (active(&mint.mint_authority), active(&mint.freeze_authority)) {
+                (true, true) => (0.0, 1.0, "CRITICAL: mint and freeze authorities active".to_string()),
+                (true, false) => (0.0, 1.0, "CRITICAL: mint authority active (unlimited minting)".to_string()),
+                (false, true) => (0.1, 1.0, "CRITICAL: freeze authority active (holders can be frozen)".to_string()),
+                (false, false) => (1.0, 1.0, "SAFE: mint and freeze authorities revoked".to_string()),
+            },
         };
 
+        let mint = ctx.mint_info.as_ref();
+        let evidence = Evidence::default()
+            .with("mint_authority_active", mint.map(|m| f64::from(active(&m.mint_authority))))
+            .with("freeze_authority_active", mint.map(|m| f64::from(active(&m.freeze_authority))));
+
         PatternSignal {
             name: self.name().to_string(),
             score,
-            confidence: 1.0,  // Age is always accurate
+            confidence,
             details,
             weight: self.weight(),
+            evidence,
         }
     }
 }
 
-// ============================================
-// DISTRIBUTION ANALYSIS
-// ============================================
+/// Token-2022 extension detector, mirroring the program's
+/// `validate_token2022_mint`: each risky extension gets its own score and the
+/// signal takes the worst of them.
+#[derive(Default, Serialize, Deserialize)]
+pub struct Token2022ExtensionDetector;
 
-/// Holder balance distribution (Gini coefficient approximation)
-pub struct DistributionQualityDetector {
-    pub top10_healthy_max: f64,  // Top 10 holders shouldn't exceed this
-}
+impl Token2022ExtensionDetector {
+    /// Score and label of one `jsonParsed` extension; `None` when harmless
+    fn extension_risk(ext: &MintExtension) -> Option<(f64, String)> {
+        let state = &ext.state;
+        let set = |key: &str| state[key].as_str().is_some_and(|v| !v.is_empty());
 
-impl Default for DistributionQualityDetector {
-    fn default() -> Self {
-        Self {
-            top10_healthy_max: 60.0,  // Top 10 < 60% = healthy
+        match ext.name.as_str() {
+            "nonTransferable" => Some((0.0, "NonTransferable (tokens cannot move)".to_string())),
+            "transferHook" if set("programId") => {
+                Some((0.0, format!("TransferHook (program {})", state["programId"].as_str().unwrap_or_default())))
+            }
+            "transferHook" if set("authority") => Some((0.6, "TransferHook (unset, but can be enabled)".to_string())),
+            "permanentDelegate" if set("delegate") => {
+                Some((0.0, "PermanentDelegate (balances can be moved by the delegate)".to_string()))
+            }
+            "transferFeeConfig" => {
+                let bps = state["newerTransferFee"]["transferFeeBasisPoints"].as_u64().unwrap_or(0);
+                if bps > 0 {
+                    Some((0.2, format!("TransferFee ({:.2}% per transfer)", bps as f64 / 100.0)))
+                } else {
+                    Some((0.6, "TransferFee (0% now, adjustable)".to_string()))
+                }
+            }
+            "confidentialTransferMint" => Some((0.2, "ConfidentialTransfer (hidden balances)".to_string())),
+            "defaultAccountState" if state["accountState"] != "initialized" => {
+                Some((0.1, "DefaultAccountState (new accounts start frozen)".to_string()))
+            }
+            "mintCloseAuthority" if set("closeAuthority") => Some((0.3, "MintCloseAuthority (mint can be closed)".to_string())),
+            "interestBearingConfig" => Some((0.4, "InterestBearing (balances change over time)".to_string())),
+            _ => None,
         }
     }
 }
 
-impl PatternDetector for DistributionQualityDetector {
+impl PatternDetector for Token2022ExtensionDetector {
     fn name(&self) -> &str {
-        "Distribution Quality"
+        "Token-2022 Extensions"
     }
 
     fn weight(&self) -> f64 {
-        0.08
+        0.20
+    }
+
+    fn inputs(&self) -> &[DataSource] {
+        &[DataSource::Mint]
     }
 
     fn detect(&self, ctx: &TokenContext) -> PatternSignal {
-        let top10 = ctx.whale_concentration(10);
-        
-        let (score, details) = if top10 > 90.0 {
-            (0.0, format!("TERRIBLE: Top 10 hold {:.1}%", top10))
-        } else if top10 > 80.0 {
-            (0.3, format!("POOR: Top 10 hold {:.1}%", top10))
-        } else if top10 < self.top10_healthy_max {
-            (1.0, format!("EXCELLENT: Top 10 hold {:.1}%", top10))
-        } else {
-            (0.7, format!("FAIR: Top 10 hold {:.1}%", top10))
+        let (score, confidence, details) = match &ctx.mint_info {
+            None => (0.5, 0.5, "UNKNOWN: mint account not inspected".to_string()),
+            Some(mint) if mint.extensions.is_empty() => (1.0, 1.0, "SAFE: no mint extensions".to_string()),
+            Some(mint) => {
+                let risks: Vec<(f64, String)> = mint.extensions.iter().filter_map(Self::extension_risk).collect();
+                let worst = risks.iter().map(|(score, _)| *score).fold(1.0, f64::min);
+                let labels: Vec<&str> = risks.iter().map(|(_, label)| label.as_str()).collect();
+
+                let details = if risks.is_empty() {
+                    format!("SAFE: {} benign extension(s)", mint.extensions.len())
+                } else if worst <= 0.1 {
+                    format!("CRITICAL: {}", labels.join(", "))
+                } else {
+                    format!("RISKY: {}", labels.join(", "))
+                };
+                (worst, 1.0, details)
+            }
         };
 
+        let evidence = Evidence::default()
+            .with("extensions", ctx.mint_info.as_ref().map(|m| m.extensions.len() as f64));
+
         PatternSignal {
             name: self.name().to_string(),
             score,
-            confidence: 0.85,
+            confidence,
             details,
             weight: self.weight(),
+            evidence,
         }
     }
 }
 
-// ============================================
-// COMPOSITE SCORING
-// ============================================
+/// Freeze history detector: an authority that has frozen holders before is
+/// penalized even once revoked, since the active authority check alone would
+/// pass it
+#[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FreezeHistoryDetector {
+    pub mass_freeze: usize,   // this many accounts frozen = mass freeze
+}
 
-pub fn get_all_detectors() -> Vec<Box<dyn PatternDetector>> {
-    vec![
-        // Critical filters (high weight)
-        Box::new(WhaleConcentrationDetector::default()),
-        Box::new(CoordinatedPumpDetector::default()),
-        Box::new(SingleWalletDominanceDetector::default()),
-        
-        // Bot detection
-        Box::new(BotActivityDetector::default()),
-        
-        // Holder analysis
-        Box::new(HolderCountDetector::default()),
-        Box::new(TransactionVolumeDetector::default()),
-        Box::new(DistributionQualityDetector::default()),
-        
-        // Timing
-        Box::new(TokenAgeDetector::default()),
-    ]
+impl Default for FreezeHistoryDetector {
+    fn default() -> Self {
+        Self { mass_freeze: 3 }
+    }
 }
 
-pub fn calculate_composite_score(signals: &[PatternSignal]) -> f64 {
-    if signals.is_empty() {
-        return 50.0;  // Default neutral score
+impl PatternDetector for FreezeHistoryDetector {
+    fn name(&self) -> &str {
+        "Freeze History"
     }
 
-    // Weighted average
-    let total_weight: f64 = signals.iter().map(|s| s.weight).sum();
-    let weighted_sum: f64 = signals.iter()
-        .map(|s| s.score * s.confidence * s.weight)
-        .sum();
+    fn weight(&self) -> f64 {
+        0.25
+    }
 
-    let normalized_score = if total_weight > 0.0 {
-        (weighted_sum / total_weight) * 100.0
-    } else {
-        50.0
-    };
+    fn inputs(&self) -> &[DataSource] {
+        &[DataSource::Transactions]
+    }
 
-    // Clamp to 0-100
-    normalized_score.max(0.0).min(100.0)
-}
+    fn detect(&self, ctx: &TokenContext) -> PatternSignal {
+        let (frozen, still_frozen) = freeze::frozen_accounts(&ctx.freeze_events);
+        let authority = match ctx.mint_info.as_ref().map(|m| m.freeze_authority.is_some()) {
+            Some(true) => ", authority still active",
+            Some(false) => ", authority since revoked",
+            None => "",
+        };
 
-pub fn generate_recommendation(score: f64, _signals: &[PatternSignal]) -> String {
-    if score >= 70.0 {
-        "✅ SAFE - Token appears legitimate. Proceed with normal caution.".to_string()
-    } else if score >= 50.0 {
-        "⚠️ MEDIUM RISK - Exercise caution. Consider smaller position size.".to_string()
-    } else if score >= 30.0 {
-        "🚨 HIGH RISK - Significant red flags detected. Avoid or use minimal amounts.".to_string()
-    } else {
-        "❌ CRITICAL DANGER - DO NOT USE THIS TOKEN. High probability of rug pull.".to_string()
+        let (score, confidence, details) = if frozen >= self.mass_freeze {
+            let details = format!(
+                "CRITICAL: MASS FREEZE, {} accounts frozen ({} still frozen{})",
+                frozen, still_frozen, authority
+            );
+            (0.0, 0.9, details)
+        } else if frozen > 0 {
+            let details = format!(
+                "CRITICAL: {} account(s) frozen before ({} still frozen{})",
+                frozen, still_frozen, authority
+            );
+            (0.1, 0.9, details)
+        } else if ctx.transactions.is_empty() && ctx.launch.is_none() {
+            (0.5, 0.2, "UNKNOWN: no transactions scanned".to_string())
+        } else {
+            // Only the recent and launch transactions are scanned, not everything between
+            (1.0, 0.6, format!("SAFE: no freezes in {} recent transactions", ctx.transactions.len()))
+        };
+
+        let evidence = Evidence::default()
+            .with("frozen_accounts", frozen as f64)
+            .with("still_frozen", still_frozen as f64)
+            .with("freeze_events", ctx.freeze_events.len() as f64);
+
+        PatternSignal {
+            name: self.name().to_string(),
+            score,
+            confidence,
+            details,
+            weight: self.weight(),
+            evidence,
+        }
+    }
+}
+
+/// Holder delegate detector, mirroring the strict `validate_token_account`
+/// the program applies to accounts it pays out to: a delegate can move an
+/// approved amount of a top holder's tokens without the holder signing, and a
+/// close authority other than the owner fails the check too
+#[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HolderDelegateDetector {
+    pub critical_percent: f64,   // >=10% of supply movable by delegates
+    pub risky_percent: f64,      // >=1%
+}
+
+impl Default for HolderDelegateDetector {
+    fn default() -> Self {
+        Self {
+            critical_percent: 10.0,
+            risky_percent: 1.0,
+        }
+    }
+}
+
+impl PatternDetector for HolderDelegateDetector {
+    fn name(&self) -> &str {
+        "Holder Delegates"
+    }
+
+    fn weight(&self) -> f64 {
+        0.15
+    }
+
+    fn inputs(&self) -> &[DataSource] {
+        &[DataSource::Holders]
+    }
+
+    fn detect(&self, ctx: &TokenContext) -> PatternSignal {
+        // Owners are only known once the token accounts were read
+        let inspected = ctx.holders.iter().filter(|h| h.owner.is_some()).count();
+        let delegated = ctx.holders.iter().filter(|h| h.delegate.is_some()).count();
+        let closable = ctx.holders.iter().filter(|h| h.close_authority.is_some()).count();
+        let percent = ctx.delegated_percent();
+
+        let close_note = match closable {
+            0 => String::new(),
+            n => format!(", {} with a close authority", n),
+        };
+        let (score, confidence, details) = if inspected == 0 {
+            (0.5, 0.2, "UNKNOWN: holder token accounts not inspected".to_string())
+        } else if delegated > 0 {
+            let (score, band) = match percent {
+                Some(p) if p >= self.critical_percent => (0.0, "CRITICAL"),
+                Some(p) if p >= self.risky_percent => (0.3, "RISKY"),
+                Some(_) => (0.6, "MINOR"),
+                None => (0.3, "RISKY"),
+            };
+            let share = percent.map_or_else(|| "unknown share".to_string(), |p| format!("{:.1}%", p));
+            let details = format!(
+                "{}: {} of supply movable by delegates of {} top holder account(s){}",
+                band, share, delegated, close_note
+            );
+            (score, 0.9, details)
+        } else if closable > 0 {
+            (0.7, 0.8, format!("CLOSE AUTHORITY: {} top holder account(s) fail the strict token account check", closable))
+        } else {
+            (1.0, 0.9, format!("SAFE: no delegates or close authorities on {} top holder accounts", inspected))
+        };
+
+        let evidence = Evidence::default()
+            .with("delegated_percent", percent)
+            .with("delegated_accounts", delegated as f64)
+            .with("close_authority_accounts", closable as f64);
+
+        PatternSignal {
+            name: self.name().to_string(),
+            score,
+            confidence,
+            details,
+            weight: self.weight(),
+            evidence,
+        }
+    }
+}
+
+// ============================================
+// LIQUIDITY
+// ============================================
+
+/// Liquidity depth detector (quote side of all SOL / stable pools)
+#[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LiquidityDepthDetector {
+    pub thin_sol: f64,        // <25 SOL = thin
+    pub healthy_sol: f64,     // >100 SOL = healthy
+    pub thin_usd: f64,
+    pub healthy_usd: f64,
+}
+
+impl Default for LiquidityDepthDetector {
+    fn default() -> Self {
+        Self {
+            thin_sol: 25.0,
+            healthy_sol: 100.0,
+            thin_usd: 5_000.0,
+            healthy_usd: 20_000.0,
+        }
+    }
+}
+
+impl PatternDetector for LiquidityDepthDetector {
+    fn name(&self) -> &str {
+        "Liquidity Depth"
+    }
+
+    fn weight(&self) -> f64 {
+        0.20
+    }
+
+    fn inputs(&self) -> &[DataSource] {
+        &[DataSource::Liquidity]
+    }
+
+    fn detect(&self, ctx: &TokenContext) -> PatternSignal {
+        let (score, confidence, details) = match &ctx.liquidity {
+            None => (0.5, 0.5, "UNKNOWN: pool discovery failed".to_string()),
+            Some(liquidity) if liquidity.pools.is_empty() => {
+                (0.0, 0.8, "NO LIQUIDITY: no Raydium/Orca/Meteora pool found".to_string())
+            }
+            Some(liquidity) => {
+                let (sol, usd) = (liquidity.sol_depth(), liquidity.usd_depth());
+                let depth = format!("{:.1} SOL + ${:.0} across {} pool(s)", sol, usd, liquidity.pools.len());
+                if sol >= self.healthy_sol || usd >= self.healthy_usd {
+                    (1.0, 0.9, format!("DEEP: {}", depth))
+                } else if sol >= self.thin_sol || usd >= self.thin_usd {
+                    (0.7, 0.9, format!("MODERATE: {}", depth))
+                } else if sol > 0.0 || usd > 0.0 {
+                    (0.3, 0.9, format!("THIN: {}", depth))
+                } else {
+                    (0.2, 0.7, format!("EXOTIC: only non-SOL/stable pairs ({} pool(s))", liquidity.pools.len()))
+                }
+            }
+        };
+
+        let liquidity = ctx.liquidity.as_ref();
+        let evidence = Evidence::default()
+            .with("pools", liquidity.map(|l| l.pools.len() as f64))
+            .with("liquidity_sol", liquidity.map(|l| l.sol_depth()))
+            .with("liquidity_usd", liquidity.map(|l| l.usd_depth()));
+
+        PatternSignal {
+            name: self.name().to_string(),
+            score,
+            confidence,
+            details,
+            weight: self.weight(),
+            evidence,
+        }
+    }
+}
+
+/// LP lock/burn detector: LP that its holder can pull is the strongest rug
+/// predictor there is
+#[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LpLockDetector {
+    pub secured_min: f64,   // >=95% burned or locked = safe
+    pub partial_min: f64,   // >=80%
+    pub risky_min: f64,     // >=50%
+}
+
+impl Default for LpLockDetector {
+    fn default() -> Self {
+        Self {
+            secured_min: 95.0,
+            partial_min: 80.0,
+            risky_min: 50.0,
+        }
+    }
+}
+
+impl PatternDetector for LpLockDetector {
+    fn name(&self) -> &str {
+        "LP Lock"
+    }
+
+    fn weight(&self) -> f64 {
+        0.30  // Heaviest, with mint authority
+    }
+
+    fn inputs(&self) -> &[DataSource] {
+        &[DataSource::Liquidity]
+    }
+
+    fn detect(&self, ctx: &TokenContext) -> PatternSignal {
+        let pool = ctx.liquidity.as_ref().and_then(|l| l.primary_with_lp());
+
+        let (score, confidence, details) = match pool.and_then(|p| p.lp.as_ref().map(|lp| (p, lp))) {
+            None => (0.5, 0.3, "UNKNOWN: no pool with a fungible LP mint".to_string()),
+            Some((pool, lp)) => {
+                let secured = lp.secured_percent();
+                let summary = format!(
+                    "{:.1}% burned, {:.1}% locked in {} pool",
+                    lp.burned_percent, lp.locked_percent, pool.dex
+                );
+                if secured >= self.secured_min {
+                    (1.0, 0.9, format!("SECURED: {}", summary))
+                } else if secured >= self.partial_min {
+                    (0.7, 0.9, format!("MOSTLY SECURED: {}", summary))
+                } else if secured >= self.risky_min {
+                    (0.3, 0.9, format!("PARTIAL: {}", summary))
+                } else {
+                    let holder = lp.largest_unlocked_owner.as_deref().unwrap_or("unknown");
+                    (0.0, 0.9, format!("UNLOCKED: {} (largest holder {})", summary, holder))
+                }
+            }
+        };
+
+        let lp = pool.and_then(|p| p.lp.as_ref());
+        let evidence = Evidence::default()
+            .with("burned_percent", lp.map(|lp| lp.burned_percent))
+            .with("locked_percent", lp.map(|lp| lp.locked_percent))
+            .with("secured_percent", lp.map(|lp| lp.secured_percent()));
+
+        PatternSignal {
+            name: self.name().to_string(),
+            score,
+            confidence,
+            details,
+            weight: self.weight(),
+            evidence,
+        }
+    }
+}
+
+/// Honeypot detector: a simulated sell that fails or is heavily taxed
+#[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HoneypotDetector {
+    pub max_normal_tax: f64,  // % lost beyond the pool fee before it's suspicious
+    pub critical_tax: f64,
+}
+
+impl Default for HoneypotDetector {
+    fn default() -> Self {
+        Self {
+            max_normal_tax: 10.0,
+            critical_tax: 50.0,
+        }
+    }
+}
+
+impl PatternDetector for HoneypotDetector {
+    fn name(&self) -> &str {
+        "Honeypot Simulation"
+    }
+
+    fn weight(&self) -> f64 {
+        0.25
+    }
+
+    fn inputs(&self) -> &[DataSource] {
+        &[DataSource::SellSimulation, DataSource::Liquidity]
+    }
+
+    fn detect(&self, ctx: &TokenContext) -> PatternSignal {
+        let (score, confidence, details) = match &ctx.sell_simulation {
+            None => (0.5, 0.2, "UNKNOWN: no CPMM pool or seller to simulate with".to_string()),
+            Some(sim) => match (&sim.error, sim.tax_percent()) {
+                (Some(error), _) => (0.0, 0.9, format!("HONEYPOT: simulated sell failed ({})", error)),
+                (None, Some(tax)) if tax >= self.critical_tax => (0.0, 0.9, format!("HONEYPOT: sell taxed {:.1}%", tax)),
+                (None, Some(tax)) if tax >= self.max_normal_tax => (0.3, 0.9, format!("TAXED: sell loses {:.1}%", tax)),
+                (None, Some(tax)) => (1.0, 0.9, format!("SELLABLE: sell loses {:.1}% beyond pool fee", tax)),
+                (None, None) => (0.5, 0.2, "UNKNOWN: pool quoted nothing for the sell".to_string()),
+            },
+        };
+
+        let simulation = ctx.sell_simulation.as_ref();
+        let evidence = Evidence::default()
+            .with("sell_failed", simulation.map(|s| f64::from(s.error.is_some())))
+            .with("tax_percent", simulation.and_then(|s| s.tax_percent()));
+
+        PatternSignal {
+            name: self.name().to_string(),
+            score,
+            confidence,
+            details,
+            weight: self.weight(),
+            evidence,
+        }
+    }
+}
+
+/// Liquidity vs market cap: a large cap propped up by a tiny pool can't be exited
+#[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LiquidityMarketCapDetector {
+    pub critical_ratio: f64,  // <1% = unexitable
+    pub low_ratio: f64,       // <3%
+    pub healthy_ratio: f64,   // >10%
+}
+
+impl Default for LiquidityMarketCapDetector {
+    fn default() -> Self {
+        Self {
+            critical_ratio: 0.01,
+            low_ratio: 0.03,
+            healthy_ratio: 0.10,
+        }
+    }
+}
+
+impl PatternDetector for LiquidityMarketCapDetector {
+    fn name(&self) -> &str {
+        "Liquidity/MCap Ratio"
+    }
+
+    fn weight(&self) -> f64 {
+        0.10
+    }
+
+    fn inputs(&self) -> &[DataSource] {
+        &[DataSource::Liquidity, DataSource::Mint]
+    }
+
+    fn detect(&self, ctx: &TokenContext) -> PatternSignal {
+        // Both sides of the primary pool over supply at the pool's price, in quote units
+        let ratio = ctx.liquidity.as_ref().and_then(|l| l.primary()).and_then(|pool| {
+            let supply = ctx.mint_info.as_ref()?.ui_supply();
+            let market_cap = pool.price()? * supply;
+            (market_cap > 0.0).then(|| 2.0 * pool.quote_reserve / market_cap)
+        });
+
+        let (score, confidence, details) = match ratio {
+            None => (0.5, 0.3, "UNKNOWN: no priced pool".to_string()),
+            Some(r) if r < self.critical_ratio => (0.1, 0.85, format!("MISMATCH: liquidity is {:.2}% of market cap", r * 100.0)),
+            Some(r) if r < self.low_ratio => (0.4, 0.85, format!("LOW: liquidity is {:.1}% of market cap", r * 100.0)),
+            Some(r) if r > self.healthy_ratio => (1.0, 0.85, format!("HEALTHY: liquidity is {:.1}% of market cap", r * 100.0)),
+            Some(r) => (0.8, 0.85, format!("ACCEPTABLE: liquidity is {:.1}% of market cap", r * 100.0)),
+        };
+
+        PatternSignal {
+            name: self.name().to_string(),
+            score,
+            confidence,
+            details,
+            weight: self.weight(),
+            evidence: Evidence::default().with("liquidity_mcap_percent", ratio.map(|r| r * 100.0)),
+        }
+    }
+}
+
+// ============================================
+// DEPLOYER
+// ============================================
+
+/// Deployer history detector: a wallet whose earlier launches drained is
+/// likely to drain this one too
+#[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DeployerHistoryDetector {
+    pub serial_rugs: usize,          // this many collapsed launches = serial rugger
+    pub risky_collapse_ratio: f64,   // share of checked launches that collapsed
+}
+
+impl Default for DeployerHistoryDetector {
+    fn default() -> Self {
+        Self {
+            serial_rugs: 3,
+            risky_collapse_ratio: 0.5,
+        }
+    }
+}
+
+impl PatternDetector for DeployerHistoryDetector {
+    fn name(&self) -> &str {
+        "Deployer History"
+    }
+
+    fn weight(&self) -> f64 {
+        0.25
+    }
+
+    fn inputs(&self) -> &[DataSource] {
+        &[DataSource::Deployer]
+    }
+
+    fn detect(&self, ctx: &TokenContext) -> PatternSignal {
+        let (score, confidence, details) = match &ctx.deployer {
+            None => (0.5, 0.2, "UNKNOWN: creation transaction out of reach".to_string()),
+            Some(deployer) => {
+                let (checked, rugs) = deployer.collapse_counts();
+                let launches = deployer.prior_launches.len();
+                let (score, confidence, verdict) = if launches == 0 {
+                    (0.7, 0.4, format!("FIRST LAUNCH: none in {} deployer transactions", deployer.scanned_transactions))
+                } else if rugs >= self.serial_rugs {
+                    (0.0, 0.9, format!("SERIAL RUGGER: {}/{} earlier launches collapsed", rugs, checked))
+                } else if checked > 0 && rugs as f64 / checked as f64 >= self.risky_collapse_ratio {
+                    (0.2, 0.8, format!("RISKY DEPLOYER: {}/{} earlier launches collapsed", rugs, checked))
+                } else if rugs > 0 {
+                    (0.5, 0.6, format!("MIXED: {}/{} earlier launches collapsed", rugs, checked))
+                } else if checked > 0 {
+                    (0.9, 0.7, format!("TRACK RECORD: {} earlier launches still liquid", checked))
+                } else {
+                    (0.5, 0.3, format!("UNCHECKED: {} earlier launches", launches))
+                };
+                let funder = deployer
+                    .funder
+                    .as_deref()
+                    .map_or_else(String::new, |f| format!(", funded by {}", f));
+                (score, confidence, format!("{} (deployer {}{})", verdict, deployer.address, funder))
+            }
+        };
+
+        let deployer = ctx.deployer.as_ref();
+        let (checked, rugs) = deployer.map(|d| d.collapse_counts()).unzip();
+        let evidence = Evidence::default()
+            .with("prior_launches", deployer.map(|d| d.prior_launches.len() as f64))
+            .with("checked_launches", checked.map(|c| c as f64))
+            .with("collapsed_launches", rugs.map(|r| r as f64));
+
+        PatternSignal {
+            name: self.name().to_string(),
+            score,
+            confidence,
+            details,
+            weight: self.weight(),
+            evidence,
+        }
+    }
+}
+
+// ============================================
+// TRADE FLOW
+// ============================================
+
+/// Buy/sell ratio detector: buys with no sells at all is what a honeypot's
+/// chart looks like; sells only is an exit in progress
+#[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BuySellRatioDetector {
+    pub min_trades: usize,
+    pub one_sided: f64,   // >90% buys = one-sided
+    pub dumping: f64,     // <20% buys = dumping
+}
+
+impl Default for BuySellRatioDetector {
+    fn default() -> Self {
+        Self {
+            min_trades: 10,
+            one_sided: 0.9,
+            dumping: 0.2,
+        }
+    }
+}
+
+impl PatternDetector for BuySellRatioDetector {
+    fn name(&self) -> &str {
+        "Buy/Sell Ratio"
+    }
+
+    fn weight(&self) -> f64 {
+        0.15
+    }
+
+    fn inputs(&self) -> &[DataSource] {
+        &[DataSource::Transactions]
+    }
+
+    fn detect(&self, ctx: &TokenContext) -> PatternSignal {
+        let (buys, sells) = ctx.trade_counts();
+        let trades = buys + sells;
+        let ratio = buys as f64 / trades.max(1) as f64;
+        let counts = format!("{} buys / {} sells", buys, sells);
+
+        let (score, confidence, details) = if trades < self.min_trades {
+            (0.5, 0.2, format!("UNKNOWN: only {} classified trades", trades))
+        } else if sells == 0 {
+            (0.1, 0.7, format!("NO SELLS: {}", counts))
+        } else if ratio > self.one_sided {
+            (0.3, 0.6, format!("ONE-SIDED: {}", counts))
+        } else if ratio < self.dumping {
+            (0.2, 0.7, format!("DUMPING: {}", counts))
+        } else {
+            (1.0, 0.6, format!("BALANCED: {}", counts))
+        };
+
+        let evidence = Evidence::default()
+            .with("buys", buys as f64)
+            .with("sells", sells as f64)
+            .with("buy_ratio", ratio);
+
+        PatternSignal {
+            name: self.name().to_string(),
+            score,
+            confidence,
+            details,
+            weight: self.weight(),
+            evidence,
+        }
+    }
+}
+
+/// Net SOL flow detector: SOL leaving through sells faster than it comes in
+#[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NetFlowDetector {
+    pub min_gross_sol: f64,
+    pub outflow: f64,   // net below -50% of gross = heavy outflow
+}
+
+impl Default for NetFlowDetector {
+    fn default() -> Self {
+        Self {
+            min_gross_sol: 1.0,
+            outflow: -0.5,
+        }
+    }
+}
+
+impl PatternDetector for NetFlowDetector {
+    fn name(&self) -> &str {
+        "Net Flow"
+    }
+
+    fn weight(&self) -> f64 {
+        0.15
+    }
+
+    fn inputs(&self) -> &[DataSource] {
+        &[DataSource::Transactions]
+    }
+
+    fn detect(&self, ctx: &TokenContext) -> PatternSignal {
+        let (net, gross) = ctx.net_flow_sol();
+        let flow = format!("{:+.2} SOL net of {:.2} SOL traded", net, gross);
+
+        let (score, confidence, details) = if gross < self.min_gross_sol {
+            (0.5, 0.2, format!("UNKNOWN: {}", flow))
+        } else if net / gross < self.outflow {
+            (0.2, 0.7, format!("OUTFLOW: {}", flow))
+        } else if net < 0.0 {
+            (0.6, 0.6, format!("NET SELLING: {}", flow))
+        } else {
+            (0.9, 0.6, format!("INFLOW: {}", flow))
+        };
+
+        PatternSignal {
+            name: self.name().to_string(),
+            score,
+            confidence,
+            details,
+            weight: self.weight(),
+            evidence: Evidence::default().with("net_flow_sol", net).with("gross_sol", gross),
+        }
+    }
+}
+
+/// Wash trading detector: volume a wallet (or funding cluster) trades with
+/// itself says nothing about demand
+#[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WashTradingDetector {
+    pub min_round_trips: usize,
+    pub min_match: f64,        // bought vs sold token amounts within 20%
+    pub min_gross_sol: f64,
+    pub suspicious: f64,       // % of volume
+    pub critical: f64,
+}
+
+impl Default for WashTradingDetector {
+    fn default() -> Self {
+        Self {
+            min_round_trips: 2,
+            min_match: 0.8,
+            min_gross_sol: 1.0,
+            suspicious: 25.0,
+            critical: 50.0,
+        }
+    }
+}
+
+impl PatternDetector for WashTradingDetector {
+    fn name(&self) -> &str {
+        "Wash Trading"
+    }
+
+    fn weight(&self) -> f64 {
+        0.20
+    }
+
+    fn inputs(&self) -> &[DataSource] {
+        &[DataSource::Transactions]
+    }
+
+    fn detect(&self, ctx: &TokenContext) -> PatternSignal {
+        let (_, gross) = ctx.net_flow_sol();
+        let (wash, groups) = ctx.wash_volume_sol(self.min_round_trips, self.min_match);
+        let percent = wash / gross.max(f64::EPSILON) * 100.0;
+        let volume = format!("{:.1}% of {:.2} SOL volume from {} wallet group(s)", percent, gross, groups);
+
+        let (score, confidence, details) = if gross < self.min_gross_sol {
+            (0.5, 0.2, format!("UNKNOWN: only {:.2} SOL classified volume", gross))
+        } else if percent >= self.critical {
+            (0.0, 0.8, format!("WASH TRADING: {}", volume))
+        } else if percent >= self.suspicious {
+            (0.3, 0.7, format!("SUSPICIOUS: {}", volume))
+        } else if groups > 0 {
+            (0.8, 0.6, format!("MINOR: {}", volume))
+        } else {
+            (1.0, 0.6, format!("ORGANIC: no round-trip trading in {:.2} SOL volume", gross))
+        };
+
+        let evidence = Evidence::default()
+            .with("wash_percent", percent)
+            .with("wash_sol", wash)
+            .with("gross_sol", gross)
+            .with("wallet_groups", groups as f64);
+
+        PatternSignal {
+            name: self.name().to_string(),
+            score,
+            confidence,
+            details,
+            weight: self.weight(),
+            evidence,
+        }
+    }
+}
+
+// ============================================
+// MARKET
+// ============================================
+
+/// Volume/holder mismatch: heavy volume traded by very few holders is bots or
+/// wash trading, not demand
+#[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct VolumeHolderMismatchDetector {
+    pub few_holders: u64,        // <100 holders...
+    pub high_volume_usd: f64,    // ...trading >$100k a day
+    pub per_holder_usd: f64,     // >$20k/day per holder
+}
+
+impl Default for VolumeHolderMismatchDetector {
+    fn default() -> Self {
+        Self {
+            few_holders: 100,
+            high_volume_usd: 100_000.0,
+            per_holder_usd: 20_000.0,
+        }
+    }
+}
+
+impl PatternDetector for VolumeHolderMismatchDetector {
+    fn name(&self) -> &str {
+        "Volume/Holder Mismatch"
+    }
+
+    fn weight(&self) -> f64 {
+        0.15
+    }
+
+    fn inputs(&self) -> &[DataSource] {
+        &[DataSource::Market, DataSource::Holders]
+    }
+
+    fn detect(&self, ctx: &TokenContext) -> PatternSignal {
+        let volume = ctx.market.as_ref().and_then(|m| m.volume_24h_usd);
+        let (score, confidence, details) = match volume {
+            None => (0.5, 0.2, "UNKNOWN: no market data".to_string()),
+            Some(volume) => {
+                // The RPC only returns the largest accounts, so without an
+                // indexed count the holder figure is a floor
+                let (holders, confidence) = match ctx.market.as_ref().and_then(|m| m.holders) {
+                    Some(holders) => (holders, 0.8),
+                    None => (ctx.unique_wallets() as u64, 0.4),
+                };
+                let per_holder = volume / holders.max(1) as f64;
+                let figures = format!("${:.0} 24h volume across {} holders", volume, holders);
+                if holders < self.few_holders && volume >= self.high_volume_usd {
+                    (0.2, confidence, format!("MISMATCH: {}", figures))
+                } else if per_holder >= self.per_holder_usd {
+                    (0.4, confidence, format!("HEAVY PER HOLDER: {}", figures))
+                } else {
+                    (1.0, confidence, format!("CONSISTENT: {}", figures))
+                }
+            }
+        };
+
+        let evidence = Evidence::default()
+            .with("volume_24h_usd", volume)
+            .with("indexed_holders", ctx.market.as_ref().and_then(|m| m.holders).map(|h| h as f64));
+
+        PatternSignal {
+            name: self.name().to_string(),
+            score,
+            confidence,
+            details,
+            weight: self.weight(),
+            evidence,
+        }
+    }
+}
+
+/// Parabolic price detector: vertical charts are where late buyers get dumped on
+#[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ParabolicPriceDetector {
+    pub parabolic_1h: f64,    // +100% in an hour
+    pub parabolic_24h: f64,   // +500% in a day
+    pub pumping_24h: f64,     // +200% in a day
+}
+
+impl Default for ParabolicPriceDetector {
+    fn default() -> Self {
+        Self {
+            parabolic_1h: 100.0,
+            parabolic_24h: 500.0,
+            pumping_24h: 200.0,
+        }
+    }
+}
+
+impl PatternDetector for ParabolicPriceDetector {
+    fn name(&self) -> &str {
+        "Parabolic Price"
+    }
+
+    fn weight(&self) -> f64 {
+        0.15
+    }
+
+    fn inputs(&self) -> &[DataSource] {
+        &[DataSource::Market]
+    }
+
+    fn detect(&self, ctx: &TokenContext) -> PatternSignal {
+        let changes = ctx.market.as_ref().map(|m| (m.price_change_1h, m.price_change_24h));
+        let (score, confidence, details) = match changes {
+            None | Some((None, None)) => (0.5, 0.2, "UNKNOWN: no price history".to_string()),
+            Some((h1, h24)) => {
+                let (h1, h24) = (h1.unwrap_or(0.0), h24.unwrap_or(0.0));
+                let moves = format!("{:+.0}% 1h, {:+.0}% 24h", h1, h24);
+                if h1 >= self.parabolic_1h || h24 >= self.parabolic_24h {
+                    (0.2, 0.8, format!("PARABOLIC: {}", moves))
+                } else if h24 >= self.pumping_24h {
+                    (0.5, 0.7, format!("PUMPING: {}", moves))
+                } else {
+                    (1.0, 0.6, format!("STABLE: {}", moves))
+                }
+            }
+        };
+
+        let evidence = Evidence::default()
+            .with("price_change_1h", changes.and_then(|(h1, _)| h1))
+            .with("price_change_24h", changes.and_then(|(_, h24)| h24));
+
+        PatternSignal {
+            name: self.name().to_string(),
+            score,
+            confidence,
+            details,
+            weight: self.weight(),
+            evidence,
+        }
+    }
+}
+
+// ============================================
+// TIMING PATTERNS
+// ============================================
+
+/// Token age detector (maturity indicator)
+#[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TokenAgeDetector {
+    pub very_new_hours: f64,      // <1h
+    pub new_hours: f64,           // <24h
+    pub established_hours: f64,   // >168h (1 week)
+}
+
+impl Default for TokenAgeDetector {
+    fn default() -> Self {
+        Self {
+            very_new_hours: 1.0,
+            new_hours: 24.0,
+            established_hours: 168.0,
+        }
+    }
+}
+
+impl PatternDetector for TokenAgeDetector {
+    fn name(&self) -> &str {
+        "Token Age"
+    }
+
+    fn weight(&self) -> f64 {
+        0.10
+    }
+
+    fn inputs(&self) -> &[DataSource] {
+        &[DataSource::Transactions]
+    }
+
+    fn detect(&self, ctx: &TokenContext) -> PatternSignal {
+        let age_hours = ctx.age_hours();
+        
+        let (score, details) = if age_hours < self.very_new_hours {
+            (0.3, format!("VERY NEW: {:.1}h old (high risk)", age_hours))
+        } else if age_hours < self.new_hours {
+            (0.6, format!("NEW: {:.1}h old", age_hours))
+        } else if age_hours > self.established_hours {
+            (1.0, format!("ESTABLISHED: {:.0}h old", age_hours))
+        } else {
+            (0.8, format!("MATURING: {:.1}h old", age_hours))
+        };
+
+        PatternSignal {
+            name: self.name().to_string(),
+            score,
+            confidence: 1.0,  // Age is always accurate
+            details,
+            weight: self.weight(),
+            evidence: Evidence::default().with("age_hours", age_hours),
+        }
+    }
+}
+
+/// Velocity spike detector: a burst of transactions against the token's usual
+/// pace, the footprint of a coordinated pump or a bot swarm
+#[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct VelocitySpikeDetector {
+    pub min_transactions: usize,  // Fewer can't tell a spike from noise
+    pub spike_ratio: f64,         // Busiest bucket at 6x the mean
+    pub extreme_ratio: f64,       // Busiest bucket at 12x the mean
+}
+
+impl Default for VelocitySpikeDetector {
+    fn default() -> Self {
+        Self {
+            min_transactions: 20,
+            spike_ratio: 6.0,
+            extreme_ratio: 12.0,
+        }
+    }
+}
+
+impl PatternDetector for VelocitySpikeDetector {
+    fn name(&self) -> &str {
+        "Velocity Spike"
+    }
+
+    fn weight(&self) -> f64 {
+        0.10
+    }
+
+    fn inputs(&self) -> &[DataSource] {
+        &[DataSource::Transactions]
+    }
+
+    fn detect(&self, ctx: &TokenContext) -> PatternSignal {
+        let velocity = ctx.velocity();
+        let series = velocity.as_ref().and_then(|v| v.series(self.min_transactions));
+        let ratio = series.map(|(_, series)| velocity::spike_ratio(series));
+
+        let (score, confidence, details) = match (series, ratio) {
+            (Some((unit, series)), Some(ratio)) => {
+                let peak = series.iter().map(|b| b.transactions).max().unwrap_or(0);
+                let burst = format!("{} txs in one {} ({:.1}x the mean)", peak, unit, ratio);
+                if ratio >= self.extreme_ratio {
+                    (0.2, 0.7, format!("EXTREME: {}", burst))
+                } else if ratio >= self.spike_ratio {
+                    (0.5, 0.7, format!("SPIKE: {}", burst))
+                } else {
+                    (1.0, 0.6, format!("STEADY: {}", burst))
+                }
+            }
+            _ => (0.5, 0.2, format!("UNKNOWN: fewer than {} recent transactions", self.min_transactions)),
+        };
+
+        PatternSignal {
+            name: self.name().to_string(),
+            score,
+            confidence,
+            details,
+            weight: self.weight(),
+            evidence: Evidence::default().with("spike_ratio", ratio),
+        }
+    }
+}
+
+/// Dead token detector: activity that collapsed to nothing. Nobody left to
+/// sell to is a risk of its own, whatever the holders look like.
+#[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DeadTokenDetector {
+    pub quiet_hours: f64,     // No transaction in 6h
+    pub dead_hours: f64,      // No transaction in 24h
+    pub recent_hours: usize,  // Window compared with the hours before it
+    pub decay_ratio: f64,     // Recent rate at 10% of the earlier one
+}
+
+impl Default for DeadTokenDetector {
+    fn default() -> Self {
+        Self {
+            quiet_hours: 6.0,
+            dead_hours: 24.0,
+            recent_hours: 6,
+            decay_ratio: 0.1,
+        }
+    }
+}
+
+impl PatternDetector for DeadTokenDetector {
+    fn name(&self) -> &str {
+        "Dead Token"
+    }
+
+    fn weight(&self) -> f64 {
+        0.10
+    }
+
+    fn inputs(&self) -> &[DataSource] {
+        &[DataSource::Transactions]
+    }
+
+    fn detect(&self, ctx: &TokenContext) -> PatternSignal {
+        let Some(velocity) = ctx.velocity() else {
+            return PatternSignal {
+                name: self.name().to_string(),
+                score: 0.5,
+                confidence: 0.2,
+                details: "UNKNOWN: no timestamped transactions".to_string(),
+                weight: self.weight(),
+                evidence: Evidence::default(),
+            };
+        };
+        let idle_hours = velocity.idle_seconds(ctx.current_time) as f64 / 3600.0;
+        let decay = velocity::decay_ratio(&velocity.per_hour, self.recent_hours);
+
+        let (score, details) = if idle_hours >= self.dead_hours {
+            (0.1, format!("DEAD: no transactions in {:.0}h", idle_hours))
+        } else if idle_hours >= self.quiet_hours {
+            (0.4, format!("FADING: quiet for {:.1}h", idle_hours))
+        } else if let Some(decay) = decay.filter(|&d| d <= self.decay_ratio) {
+            (0.5, format!("DECAYING: last {}h at {:.0}% of the earlier pace", self.recent_hours, decay * 100.0))
+        } else {
+            (1.0, format!("ACTIVE: last transaction {:.0}m ago", idle_hours * 60.0))
+        };
+
+        PatternSignal {
+            name: self.name().to_string(),
+            score,
+            confidence: 0.7,
+            details,
+            weight: self.weight(),
+            evidence: Evidence::default().with("idle_hours", idle_hours).with("decay_ratio", decay),
+        }
+    }
+}
+
+// ============================================
+// DISTRIBUTION ANALYSIS
+// ============================================
+
+/// Holder balance distribution (Gini coefficient approximation)
+#[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DistributionQualityDetector {
+    pub top10_healthy_max: f64,  // Top 10 holders shouldn't exceed this
+}
+
+impl Default for DistributionQualityDetector {
+    fn default() -> Self {
+        Self {
+            top10_healthy_max: 60.0,  // Top 10 < 60% = healthy
+        }
+    }
+}
+
+impl PatternDetector for DistributionQualityDetector {
+    fn name(&self) -> &str {
+        "Distribution Quality"
+    }
+
+    fn weight(&self) -> f64 {
+        0.08
+    }
+
+    fn inputs(&self) -> &[DataSource] {
+        &[DataSource::Holders]
+    }
+
+    fn detect(&self, ctx: &TokenContext) -> PatternSignal {
+        let top10 = ctx.whale_concentration(10);
+        
+        let (score, details) = if top10 > 90.0 {
+            (0.0, format!("TERRIBLE: Top 10 hold {:.1}%", top10))
+        } else if top10 > 80.0 {
+            (0.3, format!("POOR: Top 10 hold {:.1}%", top10))
+        } else if top10 < self.top10_healthy_max {
+            (1.0, format!("EXCELLENT: Top 10 hold {:.1}%", top10))
+        } else {
+            (0.7, format!("FAIR: Top 10 hold {:.1}%", top10))
+        };
+
+        PatternSignal {
+            name: self.name().to_string(),
+            score,
+            confidence: 0.85,
+            details,
+            weight: self.weight(),
+            evidence: Evidence::default().with("top10_percent", top10),
+        }
+    }
+}
+
+/// Concentration across every fetched holder, not just the top 10: HHI bands
+/// (antitrust convention) first, then the Gini coefficient for tokens that
+/// are spread over many wallets but still lopsided
+#[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HolderInequalityDetector {
+    pub hhi_moderate: f64,    // >1500 = moderately concentrated
+    pub hhi_high: f64,        // >2500 = highly concentrated
+    pub hhi_critical: f64,
+    pub gini_uneven: f64,
+}
+
+impl Default for HolderInequalityDetector {
+    fn default() -> Self {
+        Self {
+            hhi_moderate: 1500.0,
+            hhi_high: 2500.0,
+            hhi_critical: 5000.0,
+            gini_uneven: 0.7,
+        }
+    }
+}
+
+impl PatternDetector for HolderInequalityDetector {
+    fn name(&self) -> &str {
+        "Holder Inequality"
+    }
+
+    fn weight(&self) -> f64 {
+        0.1
+    }
+
+    fn inputs(&self) -> &[DataSource] {
+        &[DataSource::Holders]
+    }
+
+    fn detect(&self, ctx: &TokenContext) -> PatternSignal {
+        let (hhi, gini, holders) = (ctx.hhi(), ctx.gini_coefficient(), ctx.holders.len());
+        let measured = format!("HHI {:.0}, Gini {:.2} across {} holders", hhi, gini, holders);
+
+        let (score, confidence, details) = if holders == 0 {
+            (0.5, 0.2, "UNKNOWN: no holders to measure".to_string())
+        } else if hhi > self.hhi_critical {
+            (0.0, 0.85, format!("CRITICAL: {}", measured))
+        } else if hhi > self.hhi_high {
+            (0.3, 0.85, format!("HIGH: {}", measured))
+        } else if hhi > self.hhi_moderate {
+            (0.6, 0.8, format!("MODERATE: {}", measured))
+        } else if gini > self.gini_uneven {
+            (0.7, 0.7, format!("UNEVEN: {}", measured))
+        } else {
+            (1.0, 0.8, format!("DISPERSED: {}", measured))
+        };
+
+        let evidence = Evidence::default()
+            .with("hhi", hhi)
+            .with("gini_coefficient", gini)
+            .with("holders", holders as f64);
+
+        PatternSignal {
+            name: self.name().to_string(),
+            score,
+            confidence,
+            details,
+            weight: self.weight(),
+            evidence,
+        }
+    }
+}
+
+// ============================================
+// COMPOSITE SCORING
+// ============================================
+
+/// A built-in detector's constructors: with its default thresholds, and with
+/// thresholds read from a scoring config over those defaults
+struct BuiltIn {
+    default: fn() -> Box<dyn PatternDetector>,
+    configure: fn(&toml::Table) -> Result<Box<dyn PatternDetector>>,
+    thresholds: fn() -> toml::Table,
+}
+
+const fn built_in<D>() -> BuiltIn
+where
+    D: PatternDetector + Default + Serialize + DeserializeOwned + 'static,
+{
+    BuiltIn {
+        default: || Box::new(Configurable(D::default())),
+        configure: |thresholds| {
+            // Detectors without threshold fields serialize to nothing
+            if toml::Table::try_from(D::default()).unwrap_or_default().is_empty() {
+                bail!("detector has no thresholds");
+            }
+            Ok(Box::new(Configurable(thresholds.clone().try_into::<D>()?)))
+        },
+        thresholds: || toml::Table::try_from(D::default()).unwrap_or_default(),
+    }
+}
+
+const BUILT_IN: &[BuiltIn] = &[
+    // Critical filters (high weight)
+    built_in::<AuthorityDetector>(),
+    built_in::<Token2022ExtensionDetector>(),
+    built_in::<FreezeHistoryDetector>(),
+    built_in::<HolderDelegateDetector>(),
+    built_in::<WhaleConcentrationDetector>(),
+    built_in::<CoordinatedPumpDetector>(),
+    built_in::<SniperDetector>(),
+    built_in::<BundlerDetector>(),
+    built_in::<InsiderDetector>(),
+    built_in::<InsiderExtractionDetector>(),
+    built_in::<SingleWalletDominanceDetector>(),
+    built_in::<CommonFunderDetector>(),
+
+    // Liquidity
+    built_in::<LpLockDetector>(),
+    built_in::<HoneypotDetector>(),
+    built_in::<LiquidityDepthDetector>(),
+    built_in::<LiquidityMarketCapDetector>(),
+
+    // Deployer
+    built_in::<DeployerHistoryDetector>(),
+
+    // Trade flow
+    built_in::<BuySellRatioDetector>(),
+    built_in::<NetFlowDetector>(),
+    built_in::<WashTradingDetector>(),
+
+    // Market
+    built_in::<VolumeHolderMismatchDetector>(),
+    built_in::<ParabolicPriceDetector>(),
+
+    // Bot detection
+    built_in::<BotActivityDetector>(),
+
+    // Holder analysis
+    built_in::<HolderCountDetector>(),
+    built_in::<TransactionVolumeDetector>(),
+    built_in::<DistributionQualityDetector>(),
+    built_in::<HolderInequalityDetector>(),
+
+    // Timing
+    built_in::<TokenAgeDetector>(),
+    built_in::<VelocitySpikeDetector>(),
+    built_in::<DeadTokenDetector>(),
+];
+
+/// The detectors an analysis runs, in evaluation order. Starts out with the
+/// built-ins (`default()`) or empty (`empty()`); downstream crates register
+/// their own `PatternDetector`s and drop the built-ins they don't want.
+pub struct DetectorRegistry {
+    entries: Vec<Entry>,
+}
+
+struct Entry {
+    detector: Box<dyn PatternDetector>,
+    /// Set for built-ins, whose thresholds the scoring config can override
+    built_in: Option<&'static BuiltIn>,
+}
+
+impl Default for DetectorRegistry {
+    fn default() -> Self {
+        let entries = BUILT_IN
+            .iter()
+            .map(|built_in| Entry { detector: (built_in.default)(), built_in: Some(built_in) })
+            .collect();
+        Self { entries }
+    }
+}
+
+impl DetectorRegistry {
+    /// A registry without any detectors
+    pub fn empty() -> Self {
+        Self { entries: Vec::new() }
+    }
+
+    /// Adds `detector` at the end, replacing any detector with the same id in
+    /// its place
+    pub fn register(&mut self, detector: impl PatternDetector + 'static) -> &mut Self {
+        let entry = Entry { detector: Box::new(detector), built_in: None };
+        let id = entry.detector.id();
+        match self.entries.iter_mut().find(|e| e.detector.id() == id) {
+            Some(existing) => *existing = entry,
+            None => self.entries.push(entry),
+        }
+        self
+    }
+
+    /// Removes the detector with this id, returning it if there was one
+    pub fn deregister(&mut self, id: &str) -> Option<Box<dyn PatternDetector>> {
+        let index = self.entries.iter().position(|e| e.detector.id() == id)?;
+        Some(self.entries.remove(index).detector)
+    }
+
+    /// Ids of the registered detectors, in evaluation order
+    pub fn ids(&self) -> Vec<String> {
+        self.entries.iter().map(|e| e.detector.id()).collect()
+    }
+
+    pub fn detectors(&self) -> impl Iterator<Item = &dyn PatternDetector> {
+        self.entries.iter().map(|e| e.detector.as_ref())
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The registered detectors as `scoring` configures them: disabled ones
+    /// left out, thresholds (built-ins only) and weights overridden where set
+    pub fn configure(self, scoring: &ScoringConfig) -> Result<Vec<Box<dyn PatternDetector>>> {
+        let ids = self.ids();
+        if let Some(unknown) = scoring.detectors.keys().find(|id| !ids.contains(id)) {
+            bail!("unknown detector `{}` in scoring config (available: {})", unknown, ids.join(", "));
+        }
+        scoring.risk.thresholds.validate()?;
+
+        let mut detectors = Vec::new();
+        for (entry, id) in self.entries.into_iter().zip(&ids) {
+            let Some(config) = scoring.detectors.get(id) else {
+                detectors.push(entry.detector);
+                continue;
+            };
+            if !config.enabled.unwrap_or(true) {
+                continue;
+            }
+            let mut detector = match (config.thresholds.is_empty(), entry.built_in) {
+                (true, _) => entry.detector,
+                (false, Some(built_in)) => {
+                    (built_in.configure)(&config.thresholds).with_context(|| format!("thresholds for `{}`", id))?
+                }
+                (false, None) => bail!("thresholds for `{}`: custom detectors take no thresholds", id),
+            };
+            if let Some(weight) = config.weight {
+                if !weight.is_finite() || weight < 0.0 {
+                    bail!("weight for `{}` must be a non-negative number, got {}", id, weight);
+                }
+                detector = Box::new(Weighted { inner: detector, weight });
+            }
+            detectors.push(detector);
+        }
+        Ok(detectors)
+    }
+
+    /// A scoring config spelling out every registered detector's weight and
+    /// (for built-ins) default thresholds, as a starting point for tuning
+    pub fn scoring_template(&self) -> ScoringConfig {
+        let detectors = self
+            .entries
+            .iter()
+            .map(|entry| {
+                let config = DetectorConfig {
+                    enabled: Some(true),
+                    weight: Some(entry.detector.weight()),
+                    thresholds: entry.built_in.map(|b| (b.thresholds)()).unwrap_or_default(),
+                };
+                (entry.detector.id(), config)
+            })
+            .collect();
+        ScoringConfig { detectors, risk: Default::default() }
+    }
+}
+
+/// A built-in detector, which reports its threshold fields as its thresholds
+struct Configurable<D>(D);
+
+impl<D: PatternDetector + Serialize> PatternDetector for Configurable<D> {
+    fn name(&self) -> &str {
+        self.0.name()
+    }
+
+    fn id(&self) -> String {
+        self.0.id()
+    }
+
+    fn weight(&self) -> f64 {
+        self.0.weight()
+    }
+
+    fn detect(&self, ctx: &TokenContext) -> PatternSignal {
+        self.0.detect(ctx)
+    }
+
+    fn thresholds(&self) -> serde_json::Map<String, serde_json::Value> {
+        match serde_json::to_value(&self.0) {
+            Ok(serde_json::Value::Object(thresholds)) => thresholds,
+            _ => serde_json::Map::new(),
+        }
+    }
+
+    fn inputs(&self) -> &[DataSource] {
+        self.0.inputs()
+    }
+}
+
+/// A detector with its weight replaced by a configured one
+struct Weighted {
+    inner: Box<dyn PatternDetector>,
+    weight: f64,
+}
+
+impl PatternDetector for Weighted {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn id(&self) -> String {
+        self.inner.id()
     }
+
+    fn weight(&self) -> f64 {
+        self.weight
+    }
+
+    fn detect(&self, ctx: &TokenContext) -> PatternSignal {
+        PatternSignal {
+            weight: self.weight,
+            ..self.inner.detect(ctx)
+        }
+    }
+
+    fn thresholds(&self) -> serde_json::Map<String, serde_json::Value> {
+        self.inner.thresholds()
+    }
+
+    fn inputs(&self) -> &[DataSource] {
+        self.inner.inputs()
+    }
+}
+
+pub fn calculate_composite_score(signals: &[PatternSignal]) -> f64 {
+    if signals.is_empty() {
+        return 50.0;  // Default neutral score
+    }
+
+    // Weighted average
+    let total_weight: f64 = signals.iter().map(|s| s.weight).sum();
+    let weighted_sum: f64 = signals.iter()
+        .map(|s| s.score * s.confidence * s.weight)
+        .sum();
+
+    let normalized_score = if total_weight > 0.0 {
+        (weighted_sum / total_weight) * 100.0
+    } else {
+        50.0
+    };
+
+    // Clamp to 0-100
+    normalized_score.clamp(0.0, 100.0)
 }
 
 pub fn extract_key_reasons(signals: &[PatternSignal]) -> Vec<String> {
@@ -0,0 +1,213 @@
+//! Analysis diff: what changed between an earlier analysis of a mint and a
+//! fresh one (`diff --against`). Post-mortems and monitoring reviews want the
+//! score move spelled out: which detectors started or stopped firing, which
+//! changed their verdict, and how each metric moved.
+//!
+//! The earlier analysis is a saved `SafetyAnalysis` (or its `AnalysisOutput`
+//! envelope), or the mint's latest score history entry, which keeps the score
+//! and metrics but not the signals.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::history::HistoryEntry;
+use super::patterns::band;
+use super::{AnalysisOutput, PatternSignalOutput, SafetyAnalysis, SafetyMetrics};
+
+/// The earlier analysis a diff starts from
+#[derive(Debug)]
+pub struct Baseline {
+    /// Unix time of the analysis, when recorded
+    pub analyzed_at: Option<i64>,
+    pub model_version: Option<String>,
+    pub safe_score: f64,
+    pub risk_level: String,
+    pub metrics: SafetyMetrics,
+    /// `None` from the score history, which doesn't keep signals
+    pub pattern_signals: Option<Vec<PatternSignalOutput>>,
+}
+
+impl Baseline {
+    /// A saved analysis of `mint`: `analyze --format json` output or its `data`
+    pub fn from_json(json: &str, mint: &str) -> Result<Self> {
+        let value: Value = serde_json::from_str(json).context("parsing the saved analysis")?;
+        let (analysis, model_version) = if value.get("success").is_some() {
+            let output: AnalysisOutput = serde_json::from_value(value).context("parsing the saved analysis")?;
+            let Some(analysis) = output.data else {
+                bail!("the saved analysis failed: {}", output.error.as_deref().unwrap_or("unknown error"));
+            };
+            (analysis, Some(output.model_version).filter(|v| !v.is_empty()))
+        } else {
+            (serde_json::from_value::<SafetyAnalysis>(value).context("parsing the saved analysis")?, None)
+        };
+        if analysis.mint_address != mint {
+            bail!("the saved analysis is of {}, not {}", analysis.mint_address, mint);
+        }
+        Ok(Self {
+            analyzed_at: None,
+            model_version,
+            safe_score: analysis.safe_score,
+            risk_level: analysis.risk_level,
+            metrics: analysis.metrics,
+            pattern_signals: Some(analysis.pattern_signals),
+        })
+    }
+}
+
+impl From<HistoryEntry> for Baseline {
+    fn from(entry: HistoryEntry) -> Self {
+        Self {
+            analyzed_at: Some(entry.analyzed_at),
+            model_version: entry.model_version,
+            safe_score: entry.safe_score,
+            risk_level: entry.risk_level,
+            metrics: entry.metrics,
+            pattern_signals: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SignalChangeKind {
+    /// The detector didn't report before
+    Added,
+    /// The detector no longer reports
+    Removed,
+    /// Its score or band moved
+    Changed,
+}
+
+/// A detector whose signal appeared, disappeared or moved
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignalChange {
+    pub name: String,
+    pub kind: SignalChangeKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_score: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_band: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub band: Option<String>,
+    /// The current details, or the last ones of a removed signal
+    pub details: String,
+}
+
+/// A `SafetyMetrics` field whose value changed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricChange {
+    pub metric: String,
+    /// `null` when the analysis didn't get the metric
+    pub previous: Value,
+    pub current: Value,
+    /// `current - previous`, for numbers present in both
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delta: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalysisDiff {
+    pub mint: String,
+    /// Unix time of the earlier analysis, when recorded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_at: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_model_version: Option<String>,
+    pub model_version: String,
+    pub previous_score: f64,
+    pub score: f64,
+    pub score_delta: f64,
+    pub previous_risk_level: String,
+    pub risk_level: String,
+    /// `None` when the earlier analysis kept no signals to compare
+    pub signals: Option<Vec<SignalChange>>,
+    pub metrics: Vec<MetricChange>,
+}
+
+impl AnalysisDiff {
+    /// What changed from `baseline` to `current`, scored by `model_version`
+    pub fn between(baseline: &Baseline, current: &SafetyAnalysis, model_version: &str) -> Self {
+        Self {
+            mint: current.mint_address.clone(),
+            previous_at: baseline.analyzed_at,
+            previous_model_version: baseline.model_version.clone(),
+            model_version: model_version.to_string(),
+            previous_score: baseline.safe_score,
+            score: current.safe_score,
+            score_delta: current.safe_score - baseline.safe_score,
+            previous_risk_level: baseline.risk_level.clone(),
+            risk_level: current.risk_level.clone(),
+            signals: baseline.pattern_signals.as_ref().map(|previous| signal_changes(previous, &current.pattern_signals)),
+            metrics: metric_changes(&baseline.metrics, &current.metrics),
+        }
+    }
+
+    /// Scores from different models aren't directly comparable
+    pub fn model_changed(&self) -> bool {
+        self.previous_model_version.as_ref().is_some_and(|v| *v != self.model_version)
+    }
+}
+
+/// Signals matched by detector name: current ones in report order, then the
+/// removed ones
+fn signal_changes(previous: &[PatternSignalOutput], current: &[PatternSignalOutput]) -> Vec<SignalChange> {
+    let band = |s: &PatternSignalOutput| band(&s.details).map(str::to_string);
+    let mut changes = Vec::new();
+    for signal in current {
+        let before = previous.iter().find(|p| p.name == signal.name);
+        let kind = match before {
+            None => SignalChangeKind::Added,
+            Some(before) if before.score != signal.score || band(before) != band(signal) => SignalChangeKind::Changed,
+            Some(_) => continue,
+        };
+        changes.push(SignalChange {
+            name: signal.name.clone(),
+            kind,
+            previous_score: before.map(|b| b.score),
+            score: Some(signal.score),
+            previous_band: before.and_then(band),
+            band: band(signal),
+            details: signal.details.clone(),
+        });
+    }
+    for signal in previous.iter().filter(|p| !current.iter().any(|c| c.name == p.name)) {
+        changes.push(SignalChange {
+            name: signal.name.clone(),
+            kind: SignalChangeKind::Removed,
+            previous_score: Some(signal.score),
+            score: None,
+            previous_band: band(signal),
+            band: None,
+            details: signal.details.clone(),
+        });
+    }
+    changes
+}
+
+/// Fields compared through their JSON form, so new metrics are diffed without
+/// listing them here
+fn metric_changes(previous: &SafetyMetrics, current: &SafetyMetrics) -> Vec<MetricChange> {
+    let fields = |metrics: &SafetyMetrics| match serde_json::to_value(metrics) {
+        Ok(Value::Object(fields)) => fields,
+        _ => serde_json::Map::new(),
+    };
+    let (before, after) = (fields(previous), fields(current));
+    let mut names: Vec<&String> = after.keys().collect();
+    names.extend(before.keys().filter(|k| !after.contains_key(*k)));
+
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let previous = before.get(name).cloned().unwrap_or(Value::Null);
+            let current = after.get(name).cloned().unwrap_or(Value::Null);
+            if previous == current {
+                return None;
+            }
+            let delta = previous.as_f64().zip(current.as_f64()).map(|(p, c)| c - p);
+            Some(MetricChange { metric: name.clone(), previous, current, delta })
+        })
+        .collect()
+}
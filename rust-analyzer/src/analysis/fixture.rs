@@ -0,0 +1,105 @@
+//! RPC fixtures: raw responses recorded for one mint, replayed later in place
+//! of the network so the whole pipeline (decoding, detectors, scoring) can be
+//! regression-tested offline. The analysis clock is pinned to the recording
+//! time, so age-dependent detectors score the same on replay.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Mutex;
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// File in a fixture directory holding the recorded responses
+pub const RPC_FILE: &str = "rpc.json";
+/// File in a fixture directory holding the analysis the responses produced
+pub const EXPECTED_FILE: &str = "expected.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcFixture {
+    pub mint: String,
+    /// Unix time the analysis clock reads during recording and replay
+    pub recorded_at: i64,
+    pub calls: Vec<RecordedCall>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedCall {
+    pub method: String,
+    pub params: Value,
+    /// The JSON-RPC `result`
+    pub result: Value,
+}
+
+impl RpcFixture {
+    pub fn load(dir: &Path) -> Result<Self> {
+        let path = dir.join(RPC_FILE);
+        let text = std::fs::read_to_string(&path).with_context(|| format!("reading {}", path.display()))?;
+        serde_json::from_str(&text).with_context(|| format!("parsing {}", path.display()))
+    }
+
+    pub fn save(&self, dir: &Path) -> Result<()> {
+        std::fs::create_dir_all(dir).with_context(|| format!("creating {}", dir.display()))?;
+        let path = dir.join(RPC_FILE);
+        std::fs::write(&path, serde_json::to_string_pretty(self)?).with_context(|| format!("writing {}", path.display()))
+    }
+}
+
+/// `(method, params)` as the cache keys them
+fn key(method: &str, params: &Value) -> (String, String) {
+    (method.to_string(), params.to_string())
+}
+
+/// The analyzer's side of a fixture: collects responses while recording,
+/// serves them while replaying
+pub(super) struct FixtureStore {
+    replay: bool,
+    mint: String,
+    recorded_at: i64,
+    calls: Mutex<BTreeMap<(String, String), RecordedCall>>,
+}
+
+impl FixtureStore {
+    pub(super) fn recording(mint: &str, recorded_at: i64) -> Self {
+        Self { replay: false, mint: mint.to_string(), recorded_at, calls: Mutex::new(BTreeMap::new()) }
+    }
+
+    pub(super) fn replaying(fixture: RpcFixture) -> Self {
+        let calls = fixture.calls.into_iter().map(|c| (key(&c.method, &c.params), c)).collect();
+        Self { replay: true, mint: fixture.mint, recorded_at: fixture.recorded_at, calls: Mutex::new(calls) }
+    }
+
+    pub(super) fn recorded_at(&self) -> i64 {
+        self.recorded_at
+    }
+
+    /// The recorded result when replaying; `None` when recording
+    pub(super) fn replay(&self, method: &str, params: &Value) -> Option<Result<Value>> {
+        if !self.replay {
+            return None;
+        }
+        let calls = self.calls.lock().unwrap();
+        Some(
+            calls
+                .get(&key(method, params))
+                .map(|c| c.result.clone())
+                .ok_or_else(|| anyhow!("no recorded response for {} {}", method, params)),
+        )
+    }
+
+    pub(super) fn record(&self, method: &str, params: &Value, result: &Value) {
+        if !self.replay {
+            let call = RecordedCall { method: method.to_string(), params: params.clone(), result: result.clone() };
+            self.calls.lock().unwrap().insert(key(method, params), call);
+        }
+    }
+
+    pub(super) fn fixture(&self) -> RpcFixture {
+        RpcFixture {
+            mint: self.mint.clone(),
+            recorded_at: self.recorded_at,
+            calls: self.calls.lock().unwrap().values().cloned().collect(),
+        }
+    }
+}
@@ -0,0 +1,88 @@
+//! Freeze history: `FreezeAccount`/`ThawAccount` instructions on the mint among
+//! the transactions already fetched for trade classification and the launch
+//! window. An authority that froze holders once is likely to again, and a
+//! revoked freeze authority doesn't undo what it did before it was revoked.
+
+use std::collections::{BTreeMap, HashSet};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::deployer::instructions;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum FreezeAction {
+    Freeze,
+    Thaw,
+}
+
+/// One `freezeAccount` or `thawAccount` of a token account of the mint
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct FreezeEvent {
+    pub signature: String,
+    /// Block time; 0 when the node didn't report one
+    pub timestamp: i64,
+    pub action: FreezeAction,
+    /// The token account frozen or thawed
+    pub account: String,
+    /// The freeze authority (or its multisig) that signed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub authority: Option<String>,
+}
+
+/// Freezes and thaws of `mint`'s token accounts in the successful `jsonParsed`
+/// transaction `signature`, inner instructions included
+pub fn freeze_events(tx: &Value, mint: &str, signature: &str, timestamp: i64) -> Vec<FreezeEvent> {
+    if !tx["meta"]["err"].is_null() {
+        return Vec::new();
+    }
+    instructions(tx)
+        .filter_map(|ix| {
+            let parsed = &ix["parsed"];
+            let action = match parsed["type"].as_str()? {
+                "freezeAccount" => FreezeAction::Freeze,
+                "thawAccount" => FreezeAction::Thaw,
+                _ => return None,
+            };
+            let info = &parsed["info"];
+            if info["mint"].as_str() != Some(mint) {
+                return None;
+            }
+            let authority = info["freezeAuthority"].as_str().or_else(|| info["multisigFreezeAuthority"].as_str());
+            Some(FreezeEvent {
+                signature: signature.to_string(),
+                timestamp,
+                action,
+                account: info["account"].as_str()?.to_string(),
+                authority: authority.map(str::to_string),
+            })
+        })
+        .collect()
+}
+
+/// `events` from overlapping scans, each once, oldest first
+pub fn merge(events: impl IntoIterator<Item = FreezeEvent>) -> Vec<FreezeEvent> {
+    let mut seen = HashSet::new();
+    let mut merged: Vec<FreezeEvent> = events
+        .into_iter()
+        .filter(|e| seen.insert((e.signature.clone(), e.account.clone(), e.action)))
+        .collect();
+    merged.sort_by_key(|e| e.timestamp);
+    merged
+}
+
+/// Accounts frozen at least once, and those whose last event is a freeze
+pub fn frozen_accounts(events: &[FreezeEvent]) -> (usize, usize) {
+    let mut last: BTreeMap<&str, FreezeAction> = BTreeMap::new();
+    let mut frozen = HashSet::new();
+    for event in events {
+        if event.action == FreezeAction::Freeze {
+            frozen.insert(event.account.as_str());
+        }
+        last.insert(&event.account, event.action);
+    }
+    let still_frozen = last.values().filter(|a| **a == FreezeAction::Freeze).count();
+    (frozen.len(), still_frozen)
+}
@@ -0,0 +1,131 @@
+//! Holder funding clusters: takes the owner wallets of the largest token
+//! accounts, traces each wallet's first incoming SOL transfer and groups
+//! wallets funded by the same source within a short window. Ten "independent"
+//! holders that one wallet topped up minutes apart are one holder.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use anyhow::Result;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use solana_pubkey::Pubkey;
+
+use super::patterns::HolderInfo;
+#[cfg(feature = "native")]
+use super::TokenAnalyzer;
+
+/// Largest holders whose funding is traced
+pub(super) const TRACED_HOLDERS: usize = 10;
+/// Funding transfers further apart than this don't count as one campaign
+pub const FUNDING_WINDOW_SECS: i64 = 3600;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct HolderFunding {
+    /// Groups of two or more wallets sharing a funder, largest share first
+    pub clusters: Vec<FundingCluster>,
+    /// Owner wallets whose funding source was found
+    pub traced_wallets: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FundingCluster {
+    pub funder: String,
+    pub wallets: Vec<String>,
+    /// Combined share of the largest-accounts total held by `wallets`
+    pub percent: f64,
+    /// Seconds between the first and last funding transfer
+    pub span_secs: i64,
+}
+
+impl HolderFunding {
+    pub fn largest(&self) -> Option<&FundingCluster> {
+        self.clusters.first()
+    }
+}
+
+struct Funded {
+    wallet: String,
+    percent: f64,
+    at: i64,
+}
+
+#[cfg(feature = "native")]
+impl TokenAnalyzer {
+    pub(super) async fn fetch_holder_funding(&self, mint: &str, holders: &[HolderInfo]) -> Result<HolderFunding> {
+        // One wallet may own several of the largest accounts. Off-curve owners
+        // are program PDAs (pool vaults, lockers) and have no funder.
+        let mut wallets: HashMap<String, f64> = HashMap::new();
+        for holder in holders.iter().take(TRACED_HOLDERS) {
+            let Some(owner) = holder.owner.as_deref() else {
+                continue;
+            };
+            if Pubkey::from_str(owner).is_ok_and(|k| k.is_on_curve()) {
+                *wallets.entry(owner.to_string()).or_default() += holder.percent;
+            }
+        }
+
+        let traces: Vec<_> = wallets
+            .iter()
+            .map(|(wallet, &percent)| async move {
+                let funding = async {
+                    let (signatures, complete) = self.fetch_signatures(mint, wallet, 1).await?;
+                    // A busy wallet's first transfer is beyond the first page
+                    if !complete {
+                        return Ok(None);
+                    }
+                    self.fetch_funding(mint, wallet, &signatures).await
+                };
+                match funding.await {
+                    Ok(funding) => funding.map(|f| {
+                        (f.source, Funded { wallet: wallet.clone(), percent, at: f.block_time.unwrap_or(0) })
+                    }),
+                    Err(e) => {
+                        tracing::warn!(%wallet, "funding lookup failed: {:#}", e);
+                        None
+                    }
+                }
+            })
+            .collect();
+        let funded: Vec<(String, Funded)> = futures::future::join_all(traces).await.into_iter().flatten().collect();
+        let traced_wallets = funded.len();
+
+        let mut by_funder: HashMap<String, Vec<Funded>> = HashMap::new();
+        for (funder, wallet) in funded {
+            by_funder.entry(funder).or_default().push(wallet);
+        }
+
+        let mut clusters: Vec<FundingCluster> = by_funder
+            .into_iter()
+            .filter_map(|(funder, wallets)| densest_window(funder, wallets))
+            .collect();
+        clusters.sort_by(|a, b| b.percent.total_cmp(&a.percent));
+
+        Ok(HolderFunding { clusters, traced_wallets })
+    }
+}
+
+/// The largest-share run of wallets funded within `FUNDING_WINDOW_SECS` of
+/// each other, if it has at least two wallets
+fn densest_window(funder: String, mut wallets: Vec<Funded>) -> Option<FundingCluster> {
+    wallets.sort_by_key(|w| w.at);
+    let mut best: Option<(usize, usize, f64)> = None;
+    let mut start = 0;
+    for end in 0..wallets.len() {
+        while wallets[end].at - wallets[start].at > FUNDING_WINDOW_SECS {
+            start += 1;
+        }
+        let percent = wallets[start..=end].iter().fold(0.0, |sum, w| sum + w.percent);
+        if end > start && best.is_none_or(|(_, _, p)| percent > p) {
+            best = Some((start, end, percent));
+        }
+    }
+
+    let (start, end, percent) = best?;
+    Some(FundingCluster {
+        funder,
+        span_secs: wallets[end].at - wallets[start].at,
+        wallets: wallets.drain(start..=end).map(|w| w.wallet).collect(),
+        percent,
+    })
+}
@@ -0,0 +1,185 @@
+//! Score history: every successful analysis is appended to a local SQLite
+//! database, and the next one for the same mint reports how it moved since.
+//! A token whose score slides or whose holders concentrate between runs is
+//! flagged as deteriorating even while its absolute score still looks fine.
+//! Trends only compare runs of the same scoring model version.
+
+#[cfg(feature = "native")]
+use std::path::{Path, PathBuf};
+#[cfg(feature = "native")]
+use std::sync::Mutex;
+
+#[cfg(feature = "native")]
+use anyhow::{Context, Result};
+#[cfg(feature = "native")]
+use rusqlite::{params, Connection};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::{SafetyAnalysis, SafetyMetrics};
+
+/// Score drop (points out of 100) since the previous run that counts as deteriorating
+pub const DETERIORATION_SCORE_DROP: f64 = 10.0;
+/// Top-3 concentration rise (percentage points) that counts as deteriorating
+pub const DETERIORATION_CONCENTRATION_RISE: f64 = 5.0;
+
+/// Change since the previous recorded analysis of the same mint
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Trend {
+    /// Unix time of the previous analysis
+    pub previous_at: i64,
+    pub previous_score: f64,
+    pub score_delta: f64,
+    pub holder_delta: i64,
+    /// Top-3 holder share change, in percentage points
+    pub whale_concentration_delta: f64,
+    pub top_holder_delta: f64,
+    pub liquidity_sol_delta: f64,
+    /// Score fell or concentration rose past the deterioration thresholds
+    pub deteriorating: bool,
+}
+
+impl Trend {
+    pub fn between(previous: &HistoryEntry, current: &SafetyAnalysis) -> Self {
+        let (metrics, previous_metrics) = (&current.metrics, &previous.metrics);
+        let score_delta = current.safe_score - previous.safe_score;
+        let whale_concentration_delta = metrics.whale_concentration - previous_metrics.whale_concentration;
+        Self {
+            previous_at: previous.analyzed_at,
+            previous_score: previous.safe_score,
+            score_delta,
+            holder_delta: metrics.holder_count as i64 - previous_metrics.holder_count as i64,
+            whale_concentration_delta,
+            top_holder_delta: metrics.top_holder_percent - previous_metrics.top_holder_percent,
+            liquidity_sol_delta: metrics.liquidity_sol - previous_metrics.liquidity_sol,
+            deteriorating: score_delta <= -DETERIORATION_SCORE_DROP
+                || whale_concentration_delta >= DETERIORATION_CONCENTRATION_RISE,
+        }
+    }
+}
+
+/// One recorded analysis
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub analyzed_at: i64,
+    pub safe_score: f64,
+    pub risk_level: String,
+    /// `None` for entries recorded before scores were versioned
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_version: Option<String>,
+    pub metrics: SafetyMetrics,
+}
+
+/// `$XDG_DATA_HOME/analyze-token/history.sqlite3`, falling back to
+/// `~/.local/share` and then the temp dir. Unlike the cache this is data
+/// worth keeping, so it doesn't live under `~/.cache`.
+#[cfg(feature = "native")]
+pub fn default_path() -> PathBuf {
+    std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local").join("share")))
+        .unwrap_or_else(std::env::temp_dir)
+        .join("analyze-token")
+        .join("history.sqlite3")
+}
+
+#[cfg(feature = "native")]
+pub struct ScoreHistory {
+    conn: Mutex<Connection>,
+}
+
+#[cfg(feature = "native")]
+impl ScoreHistory {
+    pub fn open(path: &Path) -> Result<Self> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).with_context(|| format!("creating {}", dir.display()))?;
+        }
+        let conn = Connection::open(path).with_context(|| format!("opening history {}", path.display()))?;
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             CREATE TABLE IF NOT EXISTS analyses (
+                 id          INTEGER PRIMARY KEY,
+                 mint        TEXT NOT NULL,
+                 analyzed_at INTEGER NOT NULL,
+                 safe_score  REAL NOT NULL,
+                 risk_level  TEXT NOT NULL,
+                 metrics     TEXT NOT NULL
+             );
+             CREATE INDEX IF NOT EXISTS analyses_mint ON analyses (mint, analyzed_at);",
+        )?;
+        // Databases created before scores were versioned lack the column
+        let versioned = conn
+            .prepare("SELECT 1 FROM pragma_table_info('analyses') WHERE name = 'model_version'")?
+            .exists([])?;
+        if !versioned {
+            conn.execute("ALTER TABLE analyses ADD COLUMN model_version TEXT", [])?;
+        }
+        Ok(Self { conn: Mutex::new(conn) })
+    }
+
+    /// Appends `analysis` and returns its trend against the mint's previous
+    /// entry under the same model version, if there is one
+    pub fn record(&self, analysis: &SafetyAnalysis, model_version: &str) -> Result<Option<Trend>> {
+        let previous = self
+            .query(&analysis.mint_address, Some(model_version), 1)?
+            .pop();
+        self.conn.lock().unwrap().execute(
+            "INSERT INTO analyses (mint, analyzed_at, safe_score, risk_level, model_version, metrics)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                analysis.mint_address,
+                chrono::Utc::now().timestamp(),
+                analysis.safe_score,
+                analysis.risk_level,
+                model_version,
+                serde_json::to_string(&analysis.metrics)?,
+            ],
+        )?;
+
+        Ok(previous.map(|previous| Trend::between(&previous, analysis)))
+    }
+
+    /// Metrics of the latest analysis of up to `limit` mints other than
+    /// `mint` analyzed since `since`, most recent first: the benchmark
+    /// population
+    pub fn population(&self, mint: &str, since: i64, limit: usize) -> Result<Vec<SafetyMetrics>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT metrics FROM analyses WHERE id IN (
+                 SELECT MAX(id) FROM analyses WHERE mint != ?1 AND analyzed_at >= ?2 GROUP BY mint
+             )
+             ORDER BY id DESC LIMIT ?3",
+        )?;
+        let rows = stmt.query_map(params![mint, since, limit as i64], |row| row.get::<_, String>(0))?;
+        rows.map(|metrics| Ok(serde_json::from_str(&metrics?)?)).collect()
+    }
+
+    /// The mint's `limit` most recent entries, newest first
+    pub fn entries(&self, mint: &str, limit: usize) -> Result<Vec<HistoryEntry>> {
+        self.query(mint, None, limit)
+    }
+
+    /// Like `entries`, restricted to one model version when given
+    fn query(&self, mint: &str, model_version: Option<&str>, limit: usize) -> Result<Vec<HistoryEntry>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT analyzed_at, safe_score, risk_level, model_version, metrics FROM analyses
+             WHERE mint = ?1 AND (?2 IS NULL OR model_version = ?2)
+             ORDER BY analyzed_at DESC, id DESC LIMIT ?3",
+        )?;
+        let rows = stmt.query_map(params![mint, model_version, limit as i64], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get::<_, String>(4)?))
+        })?;
+        rows.map(|row| {
+            let (analyzed_at, safe_score, risk_level, model_version, metrics) = row?;
+            Ok(HistoryEntry {
+                analyzed_at,
+                safe_score,
+                risk_level,
+                model_version,
+                metrics: serde_json::from_str(&metrics)?,
+            })
+        })
+        .collect()
+    }
+}
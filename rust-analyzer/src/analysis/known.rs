@@ -0,0 +1,168 @@
+//! Known-address registry. Pool vaults, burn addresses, lockers and exchange
+//! wallets show up among the largest accounts without being "holders" in the
+//! sense the concentration detectors mean, so they are labelled and left out.
+//!
+//! A holder matches on its token account address, its owner, or (for
+//! program-derived owners) the program that owns the owner. The bundled list
+//! can be extended with a JSON file of `{"address", "kind", "label"}` entries.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use solana_pubkey::Pubkey;
+
+use super::cluster::Cluster;
+use super::liquidity::{INCINERATOR, LOCKER_PROGRAMS};
+use super::patterns::{merge_by_owner, HolderInfo};
+use super::trades::DEX_PROGRAMS;
+use super::TokenAnalyzer;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KnownKind {
+    /// AMM vault, authority or program
+    Amm,
+    /// Tokens nobody can move again
+    Burn,
+    /// Vesting / LP lock escrow
+    Locker,
+    /// Centralized exchange wallet holding for its users
+    Exchange,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KnownAddress {
+    pub address: String,
+    pub kind: KnownKind,
+    pub label: String,
+}
+
+/// Address labels bundled with the analyzer
+const BUNDLED: &[(&str, KnownKind, &str)] = &[
+    (INCINERATOR, KnownKind::Burn, "Incinerator"),
+    ("11111111111111111111111111111111", KnownKind::Burn, "System Program"),
+    ("5Q544fKrFoe6tsEbD7S8EmxGTJYAKtTVhAW5Q5pge4j1", KnownKind::Amm, "Raydium AMM v4 authority"),
+    ("GpMZbSM2GgvTKHJirzeGfMFoaZ8UR2X7F4v8vHTvxFbL", KnownKind::Amm, "Raydium CPMM authority"),
+    ("5tzFkiKscXHK5ZXCGbXZxdw7gTjjD1mBwuoFbhUvuAi9", KnownKind::Exchange, "Binance"),
+    ("H8sMJSCQxfKiFTCfDR3DUMLPwcRbM61LGFJ8N4dK3WjS", KnownKind::Exchange, "Coinbase"),
+    ("5VCwKtCXgCJ6kit5FybXjvriW3xELsFDhYrPSqtJNmcD", KnownKind::Exchange, "OKX"),
+    ("AC5RDfQFmDS1deWZos921JfqscXdByf8BKHs5ACWjtW2", KnownKind::Exchange, "Bybit"),
+    ("FWznbcNXWQuHTawe9RxvQ2LdCENssh12dsznf4RiouN5", KnownKind::Exchange, "Kraken"),
+];
+
+#[derive(Debug, Clone)]
+pub struct KnownAddresses {
+    by_address: HashMap<String, KnownAddress>,
+}
+
+impl KnownAddresses {
+    /// The bundled registry: burn addresses, AMM authorities and programs,
+    /// locker programs and major exchange wallets. Off mainnet only the burn
+    /// addresses and the programs, at their `cluster` addresses, apply.
+    pub fn bundled(cluster: Cluster) -> Self {
+        let programs = DEX_PROGRAMS
+            .iter()
+            .map(|p| (cluster.program(p), KnownKind::Amm, "AMM program"))
+            .chain(LOCKER_PROGRAMS.iter().map(|p| (*p, KnownKind::Locker, "Locker program")));
+        let by_address = BUNDLED
+            .iter()
+            .copied()
+            .filter(|(_, kind, _)| cluster.has_mainnet_accounts() || *kind == KnownKind::Burn)
+            .chain(programs)
+            .map(|(address, kind, label)| {
+                let known = KnownAddress { address: address.to_string(), kind, label: label.to_string() };
+                (known.address.clone(), known)
+            })
+            .collect();
+        Self { by_address }
+    }
+
+    /// The bundled registry plus the entries of a JSON file, which win on conflict
+    pub fn load(path: &Path, cluster: Cluster) -> Result<Self> {
+        let text = std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        let extra: Vec<KnownAddress> =
+            serde_json::from_str(&text).with_context(|| format!("parsing {}", path.display()))?;
+        let mut known = Self::bundled(cluster);
+        known.by_address.extend(extra.into_iter().map(|k| (k.address.clone(), k)));
+        Ok(known)
+    }
+
+    pub fn get(&self, address: &str) -> Option<&KnownAddress> {
+        self.by_address.get(address)
+    }
+}
+
+impl TokenAnalyzer {
+    /// Fills in each holder's owner, delegate and close authority and splits
+    /// off the ones the registry knows, merging the rest per owner and
+    /// rescaling their percentages among themselves
+    pub(super) async fn resolve_holders(
+        &self,
+        mint: &str,
+        mut holders: Vec<HolderInfo>,
+    ) -> Result<(Vec<HolderInfo>, Vec<HolderInfo>)> {
+        let addresses: Vec<&str> = holders.iter().map(|h| h.address.as_str()).collect();
+        let accounts = self.fetch_accounts(mint, &addresses, "jsonParsed").await?;
+        for (holder, account) in holders.iter_mut().zip(&accounts) {
+            let info = &account["data"]["parsed"]["info"];
+            holder.owner = info["owner"].as_str().map(str::to_string);
+            holder.delegate = info["delegate"].as_str().map(str::to_string);
+            holder.delegated_amount = info["delegatedAmount"]["uiAmountString"].as_str().and_then(|a| a.parse().ok());
+            holder.close_authority = info["closeAuthority"].as_str().map(str::to_string);
+        }
+        self.split_known(mint, holders).await
+    }
+
+    /// Labels the holders the registry knows by account, owner or owner
+    /// program, and splits them off like `resolve_holders`; owners already set.
+    /// The rest are merged per owner wallet before rescaling.
+    pub(super) async fn split_known(
+        &self,
+        mint: &str,
+        mut holders: Vec<HolderInfo>,
+    ) -> Result<(Vec<HolderInfo>, Vec<HolderInfo>)> {
+        // Program-derived owners (pool authorities, escrows) are matched by
+        // the program that owns them
+        let mut pda_owners: Vec<&str> = holders
+            .iter()
+            .filter_map(|h| h.owner.as_deref())
+            .filter(|o| self.known.get(o).is_none() && Pubkey::from_str(o).is_ok_and(|k| !k.is_on_curve()))
+            .collect();
+        pda_owners.sort_unstable();
+        pda_owners.dedup();
+        let owner_programs: HashMap<String, String> = if pda_owners.is_empty() {
+            HashMap::new()
+        } else {
+            let accounts = self.fetch_accounts(mint, &pda_owners, "base64").await?;
+            pda_owners
+                .iter()
+                .zip(&accounts)
+                .filter_map(|(owner, account)| Some((owner.to_string(), account["owner"].as_str()?.to_string())))
+                .collect()
+        };
+
+        for holder in &mut holders {
+            let owner = holder.owner.as_deref();
+            let known = self
+                .known
+                .get(&holder.address)
+                .or_else(|| self.known.get(owner?))
+                .or_else(|| self.known.get(owner_programs.get(owner?)?));
+            holder.label = known.map(|k| k.label.clone());
+        }
+
+        let (excluded, kept): (Vec<HolderInfo>, Vec<HolderInfo>) =
+            holders.into_iter().partition(|h| h.label.is_some());
+        let mut kept = merge_by_owner(kept);
+        let total = kept.iter().fold(0.0, |sum, h| sum + h.balance);
+        if total > 0.0 {
+            for holder in &mut kept {
+                holder.percent = holder.balance / total * 100.0;
+            }
+        }
+        Ok((kept, excluded))
+    }
+}
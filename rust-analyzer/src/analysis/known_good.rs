@@ -0,0 +1,201 @@
+//! Known-good mints: stablecoins, wrapped SOL and established tokens whose
+//! provenance is public. Running the detectors over them only spends credits
+//! to report that USDC has a freeze authority or that wrapped SOL is "very
+//! new", so a bundled mint is rated outright with a note on who issues it.
+//!
+//! The fast path scores `KNOWN_GOOD_SCORE` rather than 100: issuers of
+//! stablecoins and bridged assets keep powers a holder depends on them not to
+//! use, which the note spells out. A deny list entry still wins.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::reasons::{Reason, Severity};
+use super::scoring::RiskConfig;
+use super::SafetyAnalysis;
+
+/// Score a known-good mint is rated with
+pub const KNOWN_GOOD_SCORE: f64 = 95.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MintCategory {
+    /// Fiat-backed, redeemable with its issuer
+    Stablecoin,
+    /// Wrapped SOL, a token account holding lamports
+    Native,
+    /// A liquid staking token of a stake pool
+    LiquidStaking,
+    /// An asset bridged from another chain
+    Bridged,
+    /// A protocol or community token with deep liquidity and a long history
+    Bluechip,
+}
+
+impl MintCategory {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MintCategory::Stablecoin => "stablecoin",
+            MintCategory::Native => "native",
+            MintCategory::LiquidStaking => "liquid staking token",
+            MintCategory::Bridged => "bridged asset",
+            MintCategory::Bluechip => "bluechip",
+        }
+    }
+}
+
+/// The registry entry an analysis was decided by
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct KnownGoodMint {
+    pub address: String,
+    pub symbol: String,
+    pub name: String,
+    pub category: MintCategory,
+    /// Who issues the token and what a holder trusts them with
+    pub provenance: String,
+}
+
+/// Mainnet mints bundled with the analyzer
+const BUNDLED: &[(&str, &str, &str, MintCategory, &str)] = &[
+    (
+        "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+        "USDC",
+        "USD Coin",
+        MintCategory::Stablecoin,
+        "Issued by Circle; Circle holds the mint and freeze authorities",
+    ),
+    (
+        "Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB",
+        "USDT",
+        "Tether USD",
+        MintCategory::Stablecoin,
+        "Issued by Tether; Tether holds the mint and freeze authorities",
+    ),
+    (
+        "2b1kV6DkPAnxd5ixfnxCpjxmKwqjjaYmCZfHsFu24GXo",
+        "PYUSD",
+        "PayPal USD",
+        MintCategory::Stablecoin,
+        "Issued by Paxos as a Token-2022 mint; Paxos holds the mint, freeze and permanent delegate authorities",
+    ),
+    (
+        "So11111111111111111111111111111111111111112",
+        "SOL",
+        "Wrapped SOL",
+        MintCategory::Native,
+        "The SPL Token program's native mint; every token is a lamport held in the account",
+    ),
+    (
+        "mSoLzYCxHdYgdzU16g5QSh3i5K3z3KZK7ytfqcJm7So",
+        "mSOL",
+        "Marinade staked SOL",
+        MintCategory::LiquidStaking,
+        "Minted by the Marinade stake pool program against staked SOL",
+    ),
+    (
+        "J1toso1uCk3RLmjorhTtrVwY9HJ7X8V9yYac6Y7kGCPn",
+        "JitoSOL",
+        "Jito Staked SOL",
+        MintCategory::LiquidStaking,
+        "Minted by the SPL stake pool run by Jito against staked SOL",
+    ),
+    (
+        "bSo13r4TkiE4KumL71LsHTPpL2euBYLFx6h9HP3piy1",
+        "bSOL",
+        "BlazeStake Staked SOL",
+        MintCategory::LiquidStaking,
+        "Minted by the SPL stake pool run by BlazeStake against staked SOL",
+    ),
+    (
+        "3NZ9JMVBmGAqocybic2c7LQCJScmgsAZ6vQqTDzcqmJh",
+        "WBTC",
+        "Wrapped BTC (Wormhole)",
+        MintCategory::Bridged,
+        "Minted by the Wormhole token bridge against WBTC locked on Ethereum",
+    ),
+    (
+        "7vfCXTUXx5WJV5JADk17DUJ4ksgau7utNKj4b963voxs",
+        "WETH",
+        "Wrapped Ether (Wormhole)",
+        MintCategory::Bridged,
+        "Minted by the Wormhole token bridge against ETH locked on Ethereum",
+    ),
+    (
+        "JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN",
+        "JUP",
+        "Jupiter",
+        MintCategory::Bluechip,
+        "Governance token of the Jupiter aggregator; fixed supply, mint authority revoked",
+    ),
+    (
+        "jtojtomepa8beP8AuQc6eXt5FriJwfFMwQx2v2f9mCL",
+        "JTO",
+        "Jito",
+        MintCategory::Bluechip,
+        "Governance token of the Jito stake pool and block engine",
+    ),
+    (
+        "HZ1JovNiVvGrGNiiYvEozEVgZ58xaU3RKwX8eACQBCt3",
+        "PYTH",
+        "Pyth Network",
+        MintCategory::Bluechip,
+        "Governance token of the Pyth oracle network",
+    ),
+    (
+        "4k3Dyjzvzp8eMZWUXbBCjEvwSkkk59S5iCNLY3QrkX6R",
+        "RAY",
+        "Raydium",
+        MintCategory::Bluechip,
+        "Token of the Raydium AMM",
+    ),
+    (
+        "DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263",
+        "Bonk",
+        "Bonk",
+        MintCategory::Bluechip,
+        "Community token airdropped at launch; mint authority revoked",
+    ),
+];
+
+/// The bundled entry for `mint`
+pub fn lookup(mint: &str) -> Option<KnownGoodMint> {
+    BUNDLED
+        .iter()
+        .find(|(address, ..)| *address == mint)
+        .map(|&(address, symbol, name, category, provenance)| KnownGoodMint {
+            address: address.to_string(),
+            symbol: symbol.to_string(),
+            name: name.to_string(),
+            category,
+            provenance: provenance.to_string(),
+        })
+}
+
+/// Rates `analysis` at `KNOWN_GOOD_SCORE` for a registry entry, putting its
+/// provenance first
+pub fn rate(analysis: &mut SafetyAnalysis, known: KnownGoodMint, risk: &RiskConfig) {
+    let message = format!(
+        "Known Mint: {} ({}) is a known {}. {}",
+        known.symbol,
+        known.name,
+        known.category.as_str(),
+        known.provenance,
+    );
+    analysis.safe_score = KNOWN_GOOD_SCORE;
+    analysis.risk_level = risk.thresholds.level(KNOWN_GOOD_SCORE).to_string();
+    analysis.recommendation = risk.recommendations.render(&analysis.risk_level, KNOWN_GOOD_SCORE);
+    analysis.score_breakdown.score = KNOWN_GOOD_SCORE;
+    analysis.score_breakdown.formula.push_str(&format!("; known-good mint, so {}", KNOWN_GOOD_SCORE));
+    analysis.reasons.insert(0, format!("✅ {}", message));
+    analysis.reason_codes.insert(
+        0,
+        Reason {
+            code: format!("KNOWN_GOOD_{}", known.category.as_str().to_uppercase().replace(' ', "_")),
+            severity: Severity::Pass,
+            detector: "known-good".to_string(),
+            message,
+            evidence: Default::default(),
+        },
+    );
+    analysis.known_good = Some(known);
+}
@@ -0,0 +1,321 @@
+//! Launch window: walks the mint's signatures back to its creation and
+//! classifies the oldest transactions. Buys landing within a few slots of the
+//! first one are snipes; a handful of wallets taking a big share of supply
+//! that fast is a bundled or sniped launch. Tokens the deployer handed out
+//! before that first buy, outside any DEX, are insider allocations.
+//!
+//! Several wallets buying in the same launch slot with a Jito tip among their
+//! transactions were sent as one bundle: a single operator splitting the
+//! launch buy across wallets, usually funded just before to look organic.
+
+use std::collections::HashMap;
+#[cfg(feature = "native")]
+use std::collections::{BTreeMap, HashSet};
+
+use anyhow::{anyhow, Result};
+#[cfg(feature = "native")]
+use futures::{stream, StreamExt};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[cfg(feature = "native")]
+use super::deployer::sol_transfers;
+use super::deployer::{fee_payer, Signature};
+#[cfg(feature = "native")]
+use super::freeze::{freeze_events, FreezeEvent};
+use super::patterns::TransactionInfo;
+#[cfg(feature = "native")]
+use super::trades::{classify, is_swap, token_changes};
+#[cfg(feature = "native")]
+use super::TokenAnalyzer;
+
+/// Signature pages (of 1000) walked back to reach the mint's creation
+pub(super) const MINT_SIGNATURE_PAGES: usize = 5;
+/// Oldest transactions fetched to find the first buy and the snipes after it
+pub(super) const LAUNCH_TRANSACTIONS: usize = 50;
+/// Slots after the first buy (~400ms each) that still count as the launch
+pub const SNIPE_SLOTS: u64 = 5;
+/// Jito's tip accounts; a transfer to one pays for a bundle
+pub const JITO_TIP_ACCOUNTS: &[&str] = &[
+    "96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5",
+    "HFqU5x63VTqvQss8hp11i4wVV8bD44PvwucfZ2bU7gRe",
+    "Cw8CFyM9FkoMi7K7Crf6HNQqf4uEMzpKw6QNghXLvLkY",
+    "ADaUMid9yfUytqMBgopwjb2DTLSokTSzL1zt6iGPaS49",
+    "DfXygSm4jCyNCybVYYK6DwvWqjKee8pbDmJGcLWNDXjh",
+    "ADuUkR4vqLUMWXxW9gh6D6L8pMSawimctcNZ5pGwDcEt",
+    "DttWaMuVvTiduZRnguLF7jNxTgiMBZ1hyAumKUiL2KRL",
+    "3AVi9Tg9Uo68tJfuvoKvqKNWKkC5wPdSSdeBnizKZ6jT",
+];
+/// A bundled wallet whose oldest transaction is at most this old at the
+/// bundle was funded for it
+pub const FRESH_WALLET_SECS: i64 = 86_400;
+/// Bundled wallets whose age is looked up, one signature page each
+pub(super) const TRACED_BUNDLE_WALLETS: usize = 10;
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct LaunchWindow {
+    /// Slot of the first buy; `None` when none is among the oldest transactions
+    pub launch_slot: Option<u64>,
+    /// Wallets that bought within `SNIPE_SLOTS` of the launch, largest first
+    pub snipers: Vec<WalletTokens>,
+    /// Wallets the deployer sent or minted tokens to before the launch,
+    /// largest first
+    pub insiders: Vec<WalletTokens>,
+    /// Jito-tipped slots within `SNIPE_SLOTS` of the launch where several
+    /// wallets bought, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bundles: Vec<LaunchBundle>,
+}
+
+/// Buys from several wallets landing in one slot with a Jito tip
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct LaunchBundle {
+    pub slot: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_time: Option<i64>,
+    /// Wallets that bought in the slot, largest first
+    pub buyers: Vec<WalletTokens>,
+    /// Buyers whose oldest transaction is at most `FRESH_WALLET_SECS` older
+    /// than the bundle; `None` when none of their histories could be read
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fresh_wallets: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct WalletTokens {
+    pub wallet: String,
+    /// UI units
+    pub tokens: f64,
+}
+
+impl LaunchWindow {
+    /// Tokens bought in the launch window
+    pub fn sniped_tokens(&self) -> f64 {
+        self.snipers.iter().fold(0.0, |sum, s| sum + s.tokens)
+    }
+
+    /// Tokens handed to insiders before the launch
+    pub fn insider_tokens(&self) -> f64 {
+        self.insiders.iter().fold(0.0, |sum, s| sum + s.tokens)
+    }
+
+    /// Tokens bought in bundles
+    pub fn bundled_tokens(&self) -> f64 {
+        let buyers = self.bundles.iter().flat_map(|b| &b.buyers);
+        buyers.fold(0.0, |sum, b| sum + b.tokens)
+    }
+
+    /// Fresh wallets across the bundles; `None` when no bundle's buyers could
+    /// be traced
+    pub fn fresh_bundled_wallets(&self) -> Option<usize> {
+        let traced = self.bundles.iter().filter_map(|b| b.fresh_wallets);
+        traced.fold(None, |sum, fresh| Some(sum.unwrap_or(0) + fresh))
+    }
+}
+
+#[cfg(feature = "native")]
+impl TokenAnalyzer {
+    /// The mint's oldest successful signatures, oldest (its creation) first
+    pub(super) async fn fetch_launch_signatures(&self, mint: &str) -> Result<Vec<Signature>> {
+        let (mut signatures, complete) = self.fetch_signatures(mint, mint, MINT_SIGNATURE_PAGES).await?;
+        if !complete {
+            return Err(anyhow!("mint creation is more than {} signature pages back", MINT_SIGNATURE_PAGES));
+        }
+        if signatures.is_empty() {
+            return Err(anyhow!("mint has no transactions"));
+        }
+        signatures.reverse();
+        signatures.truncate(LAUNCH_TRANSACTIONS);
+        Ok(signatures)
+    }
+
+    /// Snipes and insider allocations among `signatures`, oldest first, and
+    /// the freezes and thaws among them
+    pub(super) async fn fetch_launch_window(
+        &self,
+        mint: &str,
+        signatures: &[Signature],
+    ) -> Result<(LaunchWindow, Vec<FreezeEvent>)> {
+        let lookups: Vec<_> = signatures
+            .iter()
+            .map(|s| self.fetch_transaction(mint, &s.signature))
+            .collect();
+        let fetched: Vec<Option<Value>> = stream::iter(lookups)
+            .buffered(10)
+            .map(|tx| tx.map_err(|e| tracing::warn!("launch transaction skipped: {:#}", e)).ok())
+            .collect()
+            .await;
+        let (mut window, freezes) = launch_window(mint, signatures, &fetched)?;
+        self.trace_bundle_wallets(mint, &mut window).await;
+        Ok((window, freezes))
+    }
+
+    /// Counts each bundle's fresh wallets, looking up the oldest transaction
+    /// of at most `TRACED_BUNDLE_WALLETS` buyers. A wallet with a full page of
+    /// history isn't fresh; one whose history can't be read isn't counted.
+    pub(super) async fn trace_bundle_wallets(&self, mint: &str, window: &mut LaunchWindow) {
+        let mut seen = HashSet::new();
+        let wallets: Vec<String> = window
+            .bundles
+            .iter()
+            .flat_map(|b| &b.buyers)
+            .filter(|b| seen.insert(b.wallet.as_str()))
+            .take(TRACED_BUNDLE_WALLETS)
+            .map(|b| b.wallet.clone())
+            .collect();
+        let lookups: Vec<_> = wallets
+            .iter()
+            .map(|wallet| async move {
+                let first_seen = match self.fetch_signatures(mint, wallet, 1).await {
+                    Ok((signatures, true)) => Some(signatures.last().and_then(|s| s.block_time)),
+                    Ok((_, false)) => Some(None),
+                    Err(e) => {
+                        tracing::warn!(wallet = %wallet, "bundled wallet history unavailable: {:#}", e);
+                        None
+                    }
+                };
+                (wallet.as_str(), first_seen)
+            })
+            .collect();
+        // Wallet -> its oldest block time, `None` for a long history
+        let first_seen: HashMap<&str, Option<i64>> = stream::iter(lookups)
+            .buffered(10)
+            .filter_map(|(wallet, first_seen)| async move { first_seen.map(|f| (wallet, f)) })
+            .collect()
+            .await;
+
+        for bundle in &mut window.bundles {
+            let traced: Vec<Option<i64>> = bundle
+                .buyers
+                .iter()
+                .filter_map(|b| first_seen.get(b.wallet.as_str()).copied())
+                .collect();
+            if traced.is_empty() {
+                continue;
+            }
+            let cutoff = bundle.block_time.map(|t| t - FRESH_WALLET_SECS);
+            let fresh = traced
+                .iter()
+                .filter(|first| first.zip(cutoff).is_some_and(|(first, cutoff)| first >= cutoff))
+                .count();
+            bundle.fresh_wallets = Some(fresh);
+        }
+    }
+}
+
+/// `fetch_launch_window` over already fetched transactions: `fetched[i]` is
+/// `signatures[i]`'s, `None` where it couldn't be fetched
+#[cfg(feature = "native")]
+pub(super) fn launch_window(
+    mint: &str,
+    signatures: &[Signature],
+    fetched: &[Option<Value>],
+) -> Result<(LaunchWindow, Vec<FreezeEvent>)> {
+    let deployer = fetched
+        .first()
+        .and_then(Option::as_ref)
+        .and_then(fee_payer)
+        .ok_or_else(|| anyhow!("creation transaction unavailable"))?;
+
+    let classified: Vec<(u64, &Value, TransactionInfo)> = signatures
+        .iter()
+        .zip(fetched)
+        .filter_map(|(s, tx)| {
+            let tx = tx.as_ref()?;
+            let mut info = TransactionInfo {
+                signature: s.signature.clone(),
+                timestamp: s.block_time.unwrap_or(0),
+                tx_type: "unknown".to_string(),
+                wallet: None,
+                token_amount: 0.0,
+                sol_amount: 0.0,
+            };
+            classify(tx, mint, &mut info);
+            Some((s.slot, tx, info))
+        })
+        .collect();
+    let launch = classified.iter().position(|(_, _, info)| info.tx_type == "buy");
+    let launch_slot = launch.map(|i| classified[i].0);
+
+    let mut insiders: HashMap<String, f64> = HashMap::new();
+    for (_, tx, _) in &classified[..launch.unwrap_or(classified.len())] {
+        if fee_payer(tx).as_ref() != Some(&deployer) || is_swap(tx) {
+            continue;
+        }
+        for (owner, change) in token_changes(tx, mint) {
+            if owner != deployer && change > 0.0 {
+                *insiders.entry(owner).or_default() += change;
+            }
+        }
+    }
+
+    let mut snipers: HashMap<String, f64> = HashMap::new();
+    if let Some(launch_slot) = launch_slot {
+        let window = classified.iter().filter(|(slot, _, _)| *slot <= launch_slot + SNIPE_SLOTS);
+        for (_, _, info) in window.filter(|(_, _, info)| info.tx_type == "buy") {
+            if let Some(wallet) = &info.wallet {
+                *snipers.entry(wallet.clone()).or_default() += info.token_amount;
+            }
+        }
+    }
+
+    let window = LaunchWindow {
+        launch_slot,
+        snipers: largest_first(snipers),
+        insiders: largest_first(insiders),
+        bundles: launch_slot.map(|slot| bundles(&classified, slot)).unwrap_or_default(),
+    };
+    let freezes = classified
+        .iter()
+        .flat_map(|(_, tx, info)| freeze_events(tx, mint, &info.signature, info.timestamp))
+        .collect();
+    Ok((window, freezes))
+}
+
+/// Slots within `SNIPE_SLOTS` of `launch_slot` where two or more wallets
+/// bought and one of the slot's transactions tipped Jito
+#[cfg(feature = "native")]
+fn bundles(classified: &[(u64, &Value, TransactionInfo)], launch_slot: u64) -> Vec<LaunchBundle> {
+    #[derive(Default)]
+    struct Slot {
+        block_time: Option<i64>,
+        tipped: bool,
+        buyers: HashMap<String, f64>,
+    }
+
+    let mut slots: BTreeMap<u64, Slot> = BTreeMap::new();
+    for (slot, tx, info) in classified.iter().filter(|(slot, _, _)| *slot <= launch_slot + SNIPE_SLOTS) {
+        let entry = slots.entry(*slot).or_default();
+        entry.block_time = entry.block_time.or(Some(info.timestamp).filter(|&t| t > 0));
+        entry.tipped |= pays_jito_tip(tx);
+        if let (true, Some(wallet)) = (info.tx_type == "buy", &info.wallet) {
+            *entry.buyers.entry(wallet.clone()).or_default() += info.token_amount;
+        }
+    }
+    slots
+        .into_iter()
+        .filter(|(_, s)| s.tipped && s.buyers.len() >= 2)
+        .map(|(slot, s)| LaunchBundle {
+            slot,
+            block_time: s.block_time,
+            buyers: largest_first(s.buyers),
+            fresh_wallets: None,
+        })
+        .collect()
+}
+
+/// Whether a successful transaction transfers SOL to a Jito tip account
+#[cfg(feature = "native")]
+fn pays_jito_tip(tx: &Value) -> bool {
+    tx["meta"]["err"].is_null() && sol_transfers(tx).any(|(_, to)| JITO_TIP_ACCOUNTS.contains(&to))
+}
+
+fn largest_first(tokens: HashMap<String, f64>) -> Vec<WalletTokens> {
+    let mut wallets: Vec<WalletTokens> = tokens
+        .into_iter()
+        .map(|(wallet, tokens)| WalletTokens { wallet, tokens })
+        .collect();
+    wallets.sort_by(|a, b| b.tokens.total_cmp(&a.tokens));
+    wallets
+}
@@ -0,0 +1,382 @@
+//! Liquidity discovery: finds the token's Raydium (AMM v4, CPMM), Orca
+//! Whirlpool and Meteora DLMM pools with `getProgramAccounts` memcmp filters on
+//! the mint fields, then reads both vault balances to size each pool.
+//!
+//! Pools with a fungible LP mint also get their LP position checked: burned
+//! (minted by the AMM but no longer in supply, or sent to the incinerator),
+//! locked (held through a known locker program) or free to pull.
+
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Result};
+use base64::Engine;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use solana_pubkey::Pubkey;
+
+#[cfg(feature = "native")]
+use super::TokenAnalyzer;
+
+pub const WSOL_MINT: &str = "So11111111111111111111111111111111111111112";
+pub const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+pub const USDT_MINT: &str = "Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB";
+pub const INCINERATOR: &str = "1nc1nerator11111111111111111111111111111111";
+
+/// Programs whose escrows hold LP tokens until a release date
+pub const LOCKER_PROGRAMS: &[&str] = &[
+    "strmRqUCoQUgGUan5YhzUZa6KqdzwX5L6FpUxfmKg5m", // Streamflow
+    "LockrWmn6K5twhz3y9w1dQERbmgSaRkfnTeTKbpofwE", // Raydium LP lock
+    "LocpQgucEQHbqNABEYvBvwoxCPsSbG91A1QaQhQQqjn", // Jupiter Lock
+];
+
+/// Where a DEX keeps the fields we need in its pool account
+struct DexLayout {
+    dex: &'static str,
+    program: &'static str,
+    size: u64,
+    mint_a: usize,
+    mint_b: usize,
+    vault_a: usize,
+    vault_b: usize,
+    /// Fungible LP mint; concentrated-liquidity DEXes use position NFTs
+    lp_mint: Option<usize>,
+    /// u64 the AMM keeps of LP it has minted; SPL burns don't touch it
+    lp_minted: Option<usize>,
+}
+
+/// `getProgramAccounts` searches per mint: each layout, with the mint on
+/// either side
+pub(super) const POOL_SEARCHES: usize = 2 * LAYOUTS.len();
+
+const LAYOUTS: &[DexLayout] = &[
+    DexLayout {
+        dex: "raydium-amm-v4",
+        program: "675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8",
+        size: 752,
+        mint_a: 400,
+        mint_b: 432,
+        vault_a: 336,
+        vault_b: 368,
+        lp_mint: Some(464),
+        lp_minted: Some(720),
+    },
+    DexLayout {
+        dex: "raydium-cpmm",
+        program: "CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C",
+        size: 637,
+        mint_a: 168,
+        mint_b: 200,
+        vault_a: 72,
+        vault_b: 104,
+        lp_mint: Some(136),
+        lp_minted: Some(333),
+    },
+    DexLayout {
+        dex: "orca-whirlpool",
+        program: "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc",
+        size: 653,
+        mint_a: 101,
+        mint_b: 181,
+        vault_a: 133,
+        vault_b: 213,
+        lp_mint: None,
+        lp_minted: None,
+    },
+    DexLayout {
+        dex: "meteora-dlmm",
+        program: "LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo",
+        size: 904,
+        mint_a: 88,
+        mint_b: 120,
+        vault_a: 152,
+        vault_b: 184,
+        lp_mint: None,
+        lp_minted: None,
+    },
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PoolLiquidity {
+    pub dex: String,
+    pub address: String,
+    pub quote_mint: String,
+    /// The analyzed token's side, in UI units
+    pub base_reserve: f64,
+    pub quote_reserve: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lp_mint: Option<String>,
+    /// Where the LP supply sits; `None` without a fungible LP mint or when
+    /// the lookup failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lp: Option<LpStatus>,
+}
+
+/// Shares of all LP ever minted, in percent
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct LpStatus {
+    pub burned_percent: f64,
+    pub locked_percent: f64,
+    pub unlocked_percent: f64,
+    /// Owner of the largest unlocked LP position
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub largest_unlocked_owner: Option<String>,
+}
+
+impl LpStatus {
+    /// Burned or locked
+    pub fn secured_percent(&self) -> f64 {
+        self.burned_percent + self.locked_percent
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct LiquidityInfo {
+    pub pools: Vec<PoolLiquidity>,
+}
+
+impl PoolLiquidity {
+    pub fn is_sol(&self) -> bool {
+        self.quote_mint == WSOL_MINT
+    }
+
+    pub fn is_usd(&self) -> bool {
+        self.quote_mint == USDC_MINT || self.quote_mint == USDT_MINT
+    }
+
+    /// Token price in quote units implied by the reserves
+    pub fn price(&self) -> Option<f64> {
+        (self.base_reserve > 0.0).then(|| self.quote_reserve / self.base_reserve)
+    }
+}
+
+impl LiquidityInfo {
+    /// Quote-side SOL across all SOL-paired pools
+    pub fn sol_depth(&self) -> f64 {
+        self.pools.iter().filter(|p| p.is_sol()).fold(0.0, |sum, p| sum + p.quote_reserve)
+    }
+
+    /// Quote-side USDC/USDT across all stable-paired pools
+    pub fn usd_depth(&self) -> f64 {
+        self.pools.iter().filter(|p| p.is_usd()).fold(0.0, |sum, p| sum + p.quote_reserve)
+    }
+
+    /// Deepest pool whose LP status is known
+    pub fn primary_with_lp(&self) -> Option<&PoolLiquidity> {
+        self.pools
+            .iter()
+            .filter(|p| p.lp.is_some())
+            .max_by(|a, b| a.quote_reserve.total_cmp(&b.quote_reserve))
+    }
+
+    /// Deepest SOL pool, else deepest stable pool; the one prices come from
+    pub fn primary(&self) -> Option<&PoolLiquidity> {
+        let deepest = |pred: fn(&PoolLiquidity) -> bool| {
+            self.pools
+                .iter()
+                .filter(|p| pred(p))
+                .max_by(|a, b| a.quote_reserve.total_cmp(&b.quote_reserve))
+        };
+        deepest(PoolLiquidity::is_sol).or_else(|| deepest(PoolLiquidity::is_usd))
+    }
+}
+
+/// A pool account found by discovery, before its vaults are read
+struct Candidate {
+    dex: &'static str,
+    address: String,
+    quote_mint: String,
+    base_vault: String,
+    quote_vault: String,
+    lp_mint: Option<String>,
+    lp_minted: Option<u64>,
+}
+
+fn pubkey_at(data: &[u8], offset: usize) -> Option<String> {
+    let bytes = data.get(offset..offset + 32)?;
+    Pubkey::try_from(bytes).ok().map(|k| k.to_string())
+}
+
+fn u64_at(data: &[u8], offset: usize) -> Option<u64> {
+    Some(u64::from_le_bytes(data.get(offset..offset + 8)?.try_into().ok()?))
+}
+
+#[cfg(feature = "native")]
+impl TokenAnalyzer {
+    /// Every supported AMM pool holding `mint`, sized from its vaults
+    pub(super) async fn fetch_liquidity(&self, mint: &str) -> Result<LiquidityInfo> {
+        let searches = LAYOUTS
+            .iter()
+            .flat_map(|layout| [(layout, true), (layout, false)])
+            .map(|(layout, mint_is_a)| self.find_pools(mint, layout, mint_is_a));
+        let candidates: Vec<Candidate> = futures::future::try_join_all(searches)
+            .await?
+            .into_iter()
+            .flatten()
+            .collect();
+        if candidates.is_empty() {
+            return Ok(LiquidityInfo::default());
+        }
+
+        let vaults: Vec<&str> = candidates
+            .iter()
+            .flat_map(|c| [c.base_vault.as_str(), c.quote_vault.as_str()])
+            .collect();
+        let balances = self.fetch_token_balances(mint, &vaults).await?;
+
+        let lp = futures::future::join_all(candidates.iter().map(|c| async move {
+            let lp_mint = c.lp_mint.as_deref()?;
+            self.fetch_lp_status(mint, lp_mint, c.lp_minted)
+                .await
+                .map_err(|e| tracing::warn!(pool = %c.address, "LP lookup failed: {:#}", e))
+                .ok()
+        }))
+        .await;
+
+        let pools = candidates
+            .into_iter()
+            .zip(lp)
+            .filter_map(|(c, lp)| {
+                Some(PoolLiquidity {
+                    base_reserve: *balances.get(&c.base_vault)?,
+                    quote_reserve: *balances.get(&c.quote_vault)?,
+                    dex: c.dex.to_string(),
+                    address: c.address,
+                    quote_mint: c.quote_mint,
+                    lp_mint: c.lp_mint,
+                    lp,
+                })
+            })
+            .collect();
+        Ok(LiquidityInfo { pools })
+    }
+
+    /// Splits all LP ever minted into burned, locked and unlocked. Only the
+    /// largest LP accounts are inspected; the remainder counts as unlocked.
+    async fn fetch_lp_status(&self, mint: &str, lp_mint: &str, lp_minted: Option<u64>) -> Result<LpStatus> {
+        let supply = self.rpc_call(mint, "getTokenSupply", json!([lp_mint])).await?;
+        let supply: u64 = supply["value"]["amount"]
+            .as_str()
+            .and_then(|a| a.parse().ok())
+            .ok_or_else(|| anyhow!("Invalid response format"))?;
+        // Some AMMs don't track minted LP; then only incinerator transfers count as burns
+        let minted = lp_minted.filter(|&m| m >= supply).unwrap_or(supply);
+        if minted == 0 {
+            return Err(anyhow!("LP mint has no supply"));
+        }
+
+        let largest = self.rpc_call(mint, "getTokenLargestAccounts", json!([lp_mint])).await?;
+        let largest: Vec<(&str, u64)> = largest["value"]
+            .as_array()
+            .ok_or_else(|| anyhow!("Invalid response format"))?
+            .iter()
+            .filter_map(|a| Some((a["address"].as_str()?, a["amount"].as_str()?.parse().ok()?)))
+            .filter(|(_, amount)| *amount > 0)
+            .collect();
+
+        // Token account -> authority, then authority -> owning program
+        let addresses: Vec<&str> = largest.iter().map(|(address, _)| *address).collect();
+        let token_accounts = self.fetch_accounts(mint, &addresses, "jsonParsed").await?;
+        let authorities: Vec<Option<String>> = token_accounts
+            .iter()
+            .map(|a| a["data"]["parsed"]["info"]["owner"].as_str().map(str::to_string))
+            .collect();
+        let authority_keys: Vec<&str> = authorities.iter().flatten().map(String::as_str).collect();
+        let authority_accounts = self.fetch_accounts(mint, &authority_keys, "base64").await?;
+        let authority_program: HashMap<&str, &str> = authority_keys
+            .iter()
+            .zip(&authority_accounts)
+            .filter_map(|(key, account)| Some((*key, account["owner"].as_str()?)))
+            .collect();
+
+        let (mut burned, mut locked) = (minted - supply, 0u64);
+        let mut largest_unlocked: Option<(&str, u64)> = None;
+        for ((_, amount), authority) in largest.iter().zip(&authorities) {
+            let Some(authority) = authority.as_deref() else { continue };
+            let program = authority_program.get(authority).copied();
+            if authority == INCINERATOR {
+                burned += amount;
+            } else if LOCKER_PROGRAMS.contains(&authority) || program.is_some_and(|p| LOCKER_PROGRAMS.contains(&p)) {
+                locked += amount;
+            } else if largest_unlocked.is_none_or(|(_, max)| *amount > max) {
+                largest_unlocked = Some((authority, *amount));
+            }
+        }
+
+        let percent = |amount: u64| amount as f64 / minted as f64 * 100.0;
+        Ok(LpStatus {
+            burned_percent: percent(burned),
+            locked_percent: percent(locked),
+            unlocked_percent: percent(minted.saturating_sub(burned + locked)),
+            largest_unlocked_owner: largest_unlocked.map(|(owner, _)| owner.to_string()),
+        })
+    }
+
+    /// `getMultipleAccounts` values (null for missing accounts), in order
+    pub(super) async fn fetch_accounts(&self, mint: &str, addresses: &[&str], encoding: &str) -> Result<Vec<Value>> {
+        let mut accounts = Vec::with_capacity(addresses.len());
+        for chunk in addresses.chunks(100) {
+            let mut config = json!({ "encoding": encoding });
+            if encoding == "base64" {
+                // Only the owner is needed
+                config["dataSlice"] = json!({ "offset": 0, "length": 0 });
+            }
+            let mut result = self.rpc_call(mint, "getMultipleAccounts", json!([chunk, config])).await?;
+            match result["value"].take() {
+                Value::Array(values) if values.len() == chunk.len() => accounts.extend(values),
+                _ => return Err(anyhow!("Invalid response format")),
+            }
+        }
+        Ok(accounts)
+    }
+
+    async fn find_pools(&self, mint: &str, layout: &DexLayout, mint_is_a: bool) -> Result<Vec<Candidate>> {
+        let (mint_offset, quote_offset, base_vault, quote_vault) = if mint_is_a {
+            (layout.mint_a, layout.mint_b, layout.vault_a, layout.vault_b)
+        } else {
+            (layout.mint_b, layout.mint_a, layout.vault_b, layout.vault_a)
+        };
+        let params = json!([self.cluster.program(layout.program), {
+            "encoding": "base64",
+            "filters": [
+                { "dataSize": layout.size },
+                { "memcmp": { "offset": mint_offset, "bytes": mint } },
+            ],
+        }]);
+        let result = self.rpc_call(mint, "getProgramAccounts", params).await?;
+        let accounts = result.as_array().ok_or_else(|| anyhow!("Invalid response format"))?;
+
+        Ok(accounts
+            .iter()
+            .filter_map(|account| {
+                let data = base64::engine::general_purpose::STANDARD
+                    .decode(account["account"]["data"][0].as_str()?)
+                    .ok()?;
+                Some(Candidate {
+                    dex: layout.dex,
+                    address: account["pubkey"].as_str()?.to_string(),
+                    quote_mint: pubkey_at(&data, quote_offset)?,
+                    base_vault: pubkey_at(&data, base_vault)?,
+                    quote_vault: pubkey_at(&data, quote_vault)?,
+                    lp_mint: layout.lp_mint.and_then(|offset| pubkey_at(&data, offset)),
+                    lp_minted: layout.lp_minted.and_then(|offset| u64_at(&data, offset)),
+                })
+            })
+            .collect())
+    }
+
+    /// UI balances of token accounts, keyed by address
+    async fn fetch_token_balances(&self, mint: &str, accounts: &[&str]) -> Result<HashMap<String, f64>> {
+        let values = self.fetch_accounts(mint, accounts, "jsonParsed").await?;
+        Ok(accounts
+            .iter()
+            .zip(&values)
+            .filter_map(|(address, value)| Some((address.to_string(), ui_amount(value)?)))
+            .collect())
+    }
+}
+
+fn ui_amount(account: &Value) -> Option<f64> {
+    let amount = &account["data"]["parsed"]["info"]["tokenAmount"];
+    amount["uiAmountString"].as_str()?.parse().ok()
+}
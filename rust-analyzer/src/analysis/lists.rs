@@ -0,0 +1,136 @@
+//! User-maintained allow and deny lists of mints and deployer wallets, for
+//! bots screening a curated universe. A denied mint is rated critical without
+//! a single fetch, and a mint whose deployer is denied is rated critical once
+//! the deployer is known. An allow-listed mint skips the launch walk, deployer
+//! history, holder funding and sell simulation.
+//!
+//! Each list is a JSON file of `{"address", "reason"}` entries, `reason`
+//! optional. A mint on both lists is denied.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::completeness::DataSource;
+use super::reasons::{Reason, Severity};
+use super::scoring::RiskConfig;
+use super::SafetyAnalysis;
+
+/// Sources an allow-listed mint isn't fetched for: the expensive ones
+pub const ALLOW_SKIPS: &[DataSource] =
+    &[DataSource::Launch, DataSource::Deployer, DataSource::HolderFunding, DataSource::SellSimulation];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListEntry {
+    pub address: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ListKind {
+    Allow,
+    Deny,
+}
+
+/// What an entry matched
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ListSubject {
+    Mint,
+    Deployer,
+}
+
+impl ListSubject {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ListSubject::Mint => "mint",
+            ListSubject::Deployer => "deployer",
+        }
+    }
+}
+
+/// The list entry an analysis was decided by
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ListMatch {
+    pub list: ListKind,
+    pub subject: ListSubject,
+    pub address: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MintLists {
+    allow: HashMap<String, ListEntry>,
+    deny: HashMap<String, ListEntry>,
+}
+
+impl MintLists {
+    pub fn load(allow: Option<&Path>, deny: Option<&Path>) -> Result<Self> {
+        Ok(Self { allow: read(allow)?, deny: read(deny)? })
+    }
+
+    /// The deny entry for `address` as `subject`
+    pub fn denied(&self, subject: ListSubject, address: &str) -> Option<ListMatch> {
+        self.matched(ListKind::Deny, subject, address)
+    }
+
+    /// The allow entry for `mint`, unless it is also denied
+    pub fn allowed(&self, mint: &str) -> Option<ListMatch> {
+        match self.deny.contains_key(mint) {
+            true => None,
+            false => self.matched(ListKind::Allow, ListSubject::Mint, mint),
+        }
+    }
+
+    fn matched(&self, list: ListKind, subject: ListSubject, address: &str) -> Option<ListMatch> {
+        let entries = match list {
+            ListKind::Allow => &self.allow,
+            ListKind::Deny => &self.deny,
+        };
+        let entry = entries.get(address)?;
+        Some(ListMatch { list, subject, address: entry.address.clone(), reason: entry.reason.clone() })
+    }
+}
+
+fn read(path: Option<&Path>) -> Result<HashMap<String, ListEntry>> {
+    let Some(path) = path else {
+        return Ok(HashMap::new());
+    };
+    let text = std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+    let entries: Vec<ListEntry> = serde_json::from_str(&text).with_context(|| format!("parsing {}", path.display()))?;
+    Ok(entries.into_iter().map(|e| (e.address.clone(), e)).collect())
+}
+
+/// Rates `analysis` critical at score 0 for a deny-list `matched`, putting the
+/// entry's reason first
+pub fn deny(analysis: &mut SafetyAnalysis, matched: ListMatch, risk: &RiskConfig) {
+    let message = format!(
+        "Deny List: {} {} is denied{}",
+        matched.subject.as_str(),
+        matched.address,
+        matched.reason.as_deref().map(|r| format!(" ({})", r)).unwrap_or_default(),
+    );
+    analysis.safe_score = 0.0;
+    analysis.risk_level = risk.thresholds.level(0.0).to_string();
+    analysis.recommendation = risk.recommendations.render(&analysis.risk_level, 0.0);
+    analysis.score_breakdown.score = 0.0;
+    analysis.score_breakdown.formula.push_str("; deny-listed, so 0");
+    analysis.reasons.insert(0, format!("❌ {}", message));
+    analysis.reason_codes.insert(
+        0,
+        Reason {
+            code: format!("DENY_LIST_{}", matched.subject.as_str().to_uppercase()),
+            severity: Severity::Critical,
+            detector: "deny-list".to_string(),
+            message,
+            evidence: Default::default(),
+        },
+    );
+    analysis.list_match = Some(matched);
+}
@@ -0,0 +1,153 @@
+//! Off-chain market data: price, volume, market cap and FDV from an
+//! aggregator. Nothing here is needed for the on-chain checks, so the provider
+//! is optional and its failures only cost the detectors that read it.
+
+use anyhow::{anyhow, Result};
+#[cfg(feature = "native")]
+use futures::future::BoxFuture;
+#[cfg(feature = "native")]
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MarketData {
+    /// Provider the figures came from
+    pub source: String,
+    pub price_usd: Option<f64>,
+    pub volume_24h_usd: Option<f64>,
+    pub market_cap_usd: Option<f64>,
+    pub fdv_usd: Option<f64>,
+    /// Percent
+    pub price_change_1h: Option<f64>,
+    /// Percent
+    pub price_change_24h: Option<f64>,
+    /// Holder count, where the provider indexes it
+    pub holders: Option<u64>,
+}
+
+/// Source of off-chain market data for a mint
+#[cfg(feature = "native")]
+pub trait MarketDataProvider: Send + Sync {
+    fn name(&self) -> &str;
+    fn market_data<'a>(&'a self, mint: &'a str) -> BoxFuture<'a, Result<MarketData>>;
+}
+
+/// Built-in providers selectable from the command line
+#[cfg(feature = "native")]
+#[derive(Debug, Clone)]
+pub enum MarketDataSource {
+    DexScreener,
+    Birdeye { api_key: String },
+}
+
+#[cfg(feature = "native")]
+impl MarketDataSource {
+    pub fn provider(&self, client: Client) -> Box<dyn MarketDataProvider> {
+        match self {
+            Self::DexScreener => Box::new(DexScreener { client }),
+            Self::Birdeye { api_key } => Box::new(Birdeye { client, api_key: api_key.clone() }),
+        }
+    }
+}
+
+/// DexScreener's public pairs API; figures come from the token's most liquid pair
+#[cfg(feature = "native")]
+pub struct DexScreener {
+    client: Client,
+}
+
+#[cfg(feature = "native")]
+const DEXSCREENER_URL: &str = "https://api.dexscreener.com/latest/dex/tokens";
+
+#[cfg(feature = "native")]
+impl MarketDataProvider for DexScreener {
+    fn name(&self) -> &str {
+        "dexscreener"
+    }
+
+    fn market_data<'a>(&'a self, mint: &'a str) -> BoxFuture<'a, Result<MarketData>> {
+        Box::pin(async move {
+            let body: Value = self
+                .client
+                .get(format!("{}/{}", DEXSCREENER_URL, mint))
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+            let pair = body["pairs"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter(|p| p["chainId"] == "solana")
+                .max_by(|a, b| number(&a["liquidity"]["usd"]).unwrap_or(0.0).total_cmp(&number(&b["liquidity"]["usd"]).unwrap_or(0.0)))
+                .ok_or_else(|| anyhow!("no DexScreener pair for {}", mint))?;
+
+            Ok(MarketData {
+                source: self.name().to_string(),
+                price_usd: number(&pair["priceUsd"]),
+                volume_24h_usd: number(&pair["volume"]["h24"]),
+                market_cap_usd: number(&pair["marketCap"]),
+                fdv_usd: number(&pair["fdv"]),
+                price_change_1h: number(&pair["priceChange"]["h1"]),
+                price_change_24h: number(&pair["priceChange"]["h24"]),
+                holders: None,
+            })
+        })
+    }
+}
+
+/// Birdeye's token overview (needs an API key)
+#[cfg(feature = "native")]
+pub struct Birdeye {
+    client: Client,
+    api_key: String,
+}
+
+#[cfg(feature = "native")]
+const BIRDEYE_URL: &str = "https://public-api.birdeye.so/defi/token_overview";
+
+#[cfg(feature = "native")]
+impl MarketDataProvider for Birdeye {
+    fn name(&self) -> &str {
+        "birdeye"
+    }
+
+    fn market_data<'a>(&'a self, mint: &'a str) -> BoxFuture<'a, Result<MarketData>> {
+        Box::pin(async move {
+            let body: Value = self
+                .client
+                .get(BIRDEYE_URL)
+                .query(&[("address", mint)])
+                .header("X-API-KEY", &self.api_key)
+                .header("x-chain", "solana")
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+            if body["success"] != true {
+                return Err(anyhow!("Birdeye error: {}", body["message"]));
+            }
+            let data = &body["data"];
+
+            Ok(MarketData {
+                source: self.name().to_string(),
+                price_usd: number(&data["price"]),
+                volume_24h_usd: number(&data["v24hUSD"]),
+                market_cap_usd: number(&data["marketCap"]).or_else(|| number(&data["mc"])),
+                fdv_usd: number(&data["fdv"]),
+                price_change_1h: number(&data["priceChange1hPercent"]),
+                price_change_24h: number(&data["priceChange24hPercent"]),
+                holders: data["holder"].as_u64(),
+            })
+        })
+    }
+}
+
+/// A JSON number, or a number sent as a string
+#[cfg(feature = "native")]
+fn number(value: &Value) -> Option<f64> {
+    value.as_f64().or_else(|| value.as_str()?.parse().ok())
+}
@@ -0,0 +1,117 @@
+//! Optional learned scoring: the detectors' scores and confidences fed to an
+//! ONNX model that outputs a probability of rug, reported next to (never in
+//! place of) the heuristic composite score. Inference needs the `ml` feature;
+//! the feature vector is always built the same way, so training pipelines can
+//! reproduce a model's inputs from recorded analyses.
+
+use std::collections::BTreeMap;
+#[cfg(feature = "ml")]
+use std::path::Path;
+#[cfg(feature = "ml")]
+use std::sync::Mutex;
+
+#[cfg(feature = "ml")]
+use anyhow::{anyhow, Context, Result};
+#[cfg(feature = "ml")]
+use ort::{session::Session, value::Tensor};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::breakdown::DetectorContribution;
+
+/// ONNX metadata key naming the model's input features in order,
+/// comma-separated; without it the model takes every feature sorted by name
+pub const FEATURES_KEY: &str = "features";
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MlScore {
+    /// Probability (0-1) that the token rugs, per the model
+    pub rug_probability: f64,
+    /// File name of the model that produced it
+    pub model: String,
+}
+
+/// `<detector id>.score` and `<detector id>.confidence` for every detector
+/// that ran, sorted by name
+pub fn features(detectors: &[DetectorContribution]) -> BTreeMap<String, f32> {
+    detectors
+        .iter()
+        .flat_map(|d| {
+            [
+                (format!("{}.score", d.id), d.score as f32),
+                (format!("{}.confidence", d.id), d.confidence as f32),
+            ]
+        })
+        .collect()
+}
+
+/// A loaded ONNX model taking a `[1, n]` f32 feature tensor
+#[cfg(feature = "ml")]
+pub struct MlModel {
+    session: Mutex<Session>,
+    name: String,
+    /// Input layout from the model's metadata, if it declares one
+    features: Option<Vec<String>>,
+}
+
+#[cfg(feature = "ml")]
+impl MlModel {
+    /// Loads `path` with the ONNX Runtime library found at `ORT_DYLIB_PATH`
+    /// (or on the library search path)
+    pub fn load(path: &Path) -> Result<Self> {
+        // ort panics rather than erroring when the runtime library is missing;
+        // the hook is silenced so the message is only reported once, as the error
+        let hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(|_| {}));
+        let loaded = std::panic::catch_unwind(|| Session::builder().and_then(|builder| builder.commit_from_file(path)));
+        std::panic::set_hook(hook);
+        let session = loaded
+            .map_err(|panic| {
+                let message = panic
+                    .downcast_ref::<String>()
+                    .cloned()
+                    .or_else(|| panic.downcast_ref::<&str>().map(|s| s.to_string()))
+                    .unwrap_or_default();
+                anyhow!("ONNX Runtime unavailable (set ORT_DYLIB_PATH): {}", message)
+            })?
+            .with_context(|| format!("loading ONNX model {}", path.display()))?;
+        let features = session
+            .metadata()
+            .and_then(|metadata| metadata.custom(FEATURES_KEY))
+            .with_context(|| format!("reading {} metadata", path.display()))?
+            .map(|list| list.split(',').map(|name| name.trim().to_string()).collect());
+        let name = path.file_name().map_or_else(|| path.display().to_string(), |n| n.to_string_lossy().into_owned());
+        Ok(Self { session: Mutex::new(session), name, features })
+    }
+
+    /// The model's rug probability for `features` (see `features()`). Takes the
+    /// first f32 output; a two-column `[p(safe), p(rug)]` output yields the second.
+    pub fn predict(&self, features: &BTreeMap<String, f32>) -> Result<MlScore> {
+        let input: Vec<f32> = match &self.features {
+            Some(names) => names
+                .iter()
+                .map(|name| {
+                    features
+                        .get(name)
+                        .copied()
+                        .ok_or_else(|| anyhow!("model {} expects feature `{}`, which this analysis lacks", self.name, name))
+                })
+                .collect::<Result<_>>()?,
+            None => features.values().copied().collect(),
+        };
+        let tensor = Tensor::from_array(([1, input.len()], input))?;
+
+        let mut session = self.session.lock().unwrap();
+        let outputs = session.run(ort::inputs![tensor])?;
+        let probability = outputs
+            .values()
+            .find_map(|value| {
+                let (shape, data) = value.try_extract_tensor::<f32>().ok()?;
+                let rug_column = usize::from(shape.last() == Some(&2));
+                data.get(rug_column).copied()
+            })
+            .ok_or_else(|| anyhow!("model {} produced no f32 output", self.name))?;
+
+        Ok(MlScore { rug_probability: f64::from(probability).clamp(0.0, 1.0), model: self.name.clone() })
+    }
+}
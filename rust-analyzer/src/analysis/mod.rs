@@ -1,32 +1,204 @@
-/**
- * COMPREHENSIVE TOKEN SAFETY ANALYSIS
- * 
- * Ported from profit/ trading bot with 20+ pattern detectors
- * Adapted for on-chain Helius RPC data
- */
+//! COMPREHENSIVE TOKEN SAFETY ANALYSIS
+//!
+//! Ported from profit/ trading bot with 20+ pattern detectors
+//! Adapted for on-chain Helius RPC data
 
 pub mod patterns;
 pub mod detectors;
+pub mod accounts;
+pub mod liquidity;
+pub mod simulation;
+pub mod deployer;
+pub mod funding;
+#[cfg(feature = "native")]
+pub mod trades;
+pub mod launch;
+pub mod pnl;
+pub mod freeze;
+pub mod market;
+#[cfg(feature = "native")]
+pub mod known;
+pub mod scoring;
+pub mod model;
+pub mod history;
+pub mod breakdown;
+pub mod reasons;
+pub mod ml;
+pub mod backtest;
+pub mod batch;
+pub mod benchmark;
+pub mod pool;
+pub mod completeness;
+pub mod das;
+pub mod lists;
+pub mod known_good;
+pub mod changes;
+pub mod diff;
+pub mod postmortem;
+pub mod velocity;
+#[cfg(feature = "native")]
+pub mod cache;
+#[cfg(feature = "native")]
+pub mod rpc;
+#[cfg(feature = "native")]
+pub mod cost;
+#[cfg(feature = "native")]
+pub mod cluster;
+#[cfg(feature = "native")]
+pub mod fixture;
+#[cfg(feature = "native")]
+mod analyzer;
 
-use anyhow::{anyhow, Result};
-use reqwest::Client;
+use anyhow::Result;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use patterns::{TokenContext, HolderInfo, TransactionInfo};
-use detectors::{get_all_detectors, calculate_composite_score, generate_recommendation, extract_key_reasons};
+use accounts::TokenMetadata;
+use benchmark::Benchmark;
+use breakdown::ScoreBreakdown;
+use changes::TOP_HOLDERS;
+use das::AssetInfo;
+use completeness::{inputs_among, missing_inputs, no_data, DataCompleteness};
+use deployer::DeployerHistory;
+use freeze::FreezeEvent;
+use funding::HolderFunding;
+use history::Trend;
+use launch::LaunchWindow;
+use known_good::KnownGoodMint;
+use lists::ListMatch;
+use liquidity::LiquidityInfo;
+use ml::MlScore;
+use pool::PoolUsage;
+use patterns::{PatternDetector, TokenContext, HolderInfo};
+use pnl::{TraderPnl, TOP_TRADERS};
+use reasons::{reason_codes, Reason};
+use scoring::{RiskConfig, RiskThresholds};
+use velocity::Velocity;
+use detectors::extract_key_reasons;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[cfg(feature = "native")]
+pub use analyzer::{
+    AnalysisTimeout, AnalyzerConfig, CacheConfig, TokenAnalyzer, DEFAULT_ANALYSIS_TIMEOUT, DEFAULT_RPC_URL,
+    DEFAULT_TX_DEPTH,
+};
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct SafetyAnalysis {
     pub mint_address: String,
     pub safe_score: f64,         // 0-100
     pub risk_level: String,       // "low", "medium", "high", "critical"
+    /// The cutoffs `risk_level` was assigned with
+    #[serde(default)]
+    pub risk_thresholds: RiskThresholds,
     pub recommendation: String,
     pub reasons: Vec<String>,
+    /// Every detector's finding with a stable code and severity, in `reasons` order
+    #[serde(default)]
+    pub reason_codes: Vec<Reason>,
     pub metrics: SafetyMetrics,
     pub pattern_signals: Vec<PatternSignalOutput>,
+    /// How the detectors' signals add up to `safe_score`
+    #[serde(default)]
+    pub score_breakdown: ScoreBreakdown,
+    /// Learned rug probability from `--ml-model`, reported next to `safe_score`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ml_score: Option<MlScore>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<TokenMetadata>,
+    /// Interface, token standard and compression, when DAS answered
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asset: Option<AssetInfo>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub liquidity: Option<LiquidityInfo>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deployer: Option<DeployerHistory>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub holder_funding: Option<HolderFunding>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub launch: Option<LaunchWindow>,
+    /// Wallets with the largest realized profit over the classified trades
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub top_traders: Vec<TraderPnl>,
+    /// Freezes and thaws of the mint's accounts seen, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub freeze_events: Vec<FreezeEvent>,
+    /// Recent transactions per minute and per hour, for charting
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub velocity: Option<Velocity>,
+    /// Largest holders after exclusions, kept so runs can be compared (see
+    /// `changes::holder_changes`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub top_holders: Vec<HolderInfo>,
+    /// Largest accounts excluded from concentration as known infrastructure
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub excluded_holders: Vec<HolderInfo>,
+    /// Sources a partial analysis went without (`--partial` only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_completeness: Option<DataCompleteness>,
+    /// Allow or deny list entry the mint or its deployer matched
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub list_match: Option<ListMatch>,
+    /// Known-good registry entry the mint was rated outright by
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub known_good: Option<KnownGoodMint>,
+    /// Missout pools already running on this mint
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub missout_pools: Option<PoolUsage>,
+    /// Change since the previous recorded analysis of this mint
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trend: Option<Trend>,
+    /// Percentiles of the headline metrics among recently analyzed tokens
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub benchmark: Option<Benchmark>,
+}
+
+/// Envelope every front end (CLI, batch NDJSON, HTTP) emits: the analysis on
+/// success, the error chain on failure
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AnalysisOutput {
+    pub success: bool,
+    /// Scoring model the score came from (see `model::Model`); scores are only
+    /// comparable between equal versions
+    #[serde(default)]
+    pub model_version: String,
+    /// Set in batch mode, where results arrive out of input order
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mint: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<SafetyAnalysis>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl AnalysisOutput {
+    /// JSON Schema of the envelope, for clients generating types from it
+    /// (`--schema`)
+    pub fn schema() -> serde_json::Value {
+        schemars::schema_for!(AnalysisOutput).to_value()
+    }
+
+    pub fn new(result: Result<SafetyAnalysis>, model_version: &str) -> Self {
+        let model_version = model_version.to_string();
+        match result {
+            Ok(analysis) => AnalysisOutput {
+                success: true,
+                model_version,
+                mint: None,
+                data: Some(analysis),
+                error: None,
+            },
+            Err(e) => AnalysisOutput {
+                success: false,
+                model_version,
+                mint: None,
+                data: None,
+                error: Some(format!("{:#}", e)),
+            },
+        }
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct PatternSignalOutput {
     pub name: String,
     pub score: f64,
@@ -34,7 +206,9 @@ pub struct PatternSignalOutput {
     pub details: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Fields missing from older recorded metrics read as their defaults
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
 pub struct SafetyMetrics {
     pub whale_concentration: f64,  // Top 3 holders %
     pub holder_count: usize,
@@ -44,282 +218,162 @@ pub struct SafetyMetrics {
     pub bot_activity_detected: bool,
     pub coordinated_pump: bool,
     pub distribution_top10: f64,
+    /// Gini coefficient of holder balances, 0 (equal) to 1
+    pub gini_coefficient: f64,
+    /// Herfindahl–Hirschman index of holder shares, 0 to 10,000
+    pub hhi: f64,
+    pub buy_count: usize,
+    pub sell_count: usize,
+    /// SOL into buys minus SOL out of sells over the classified transactions
+    pub net_flow_sol: f64,
+    /// Supply the deployer handed out before the first buy, in percent
+    pub insider_percent: f64,
+    /// Realized profit of the deployer and launch wallets as a percentage of
+    /// the SOL buyers spent; `None` without the launch window or any buys
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub insider_extraction_percent: Option<f64>,
+    pub decimals: u8,
+    /// Mint supply in UI units
+    pub total_supply: f64,
+    pub pool_count: usize,
+    /// Quote-side depth across SOL pools
+    pub liquidity_sol: f64,
+    /// Quote-side depth across USDC/USDT pools
+    pub liquidity_usd: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price_usd: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub volume_24h_usd: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub market_cap_usd: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fdv_usd: Option<f64>,
+    /// Transactions in the hour before the analysis; `None` without any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transactions_last_hour: Option<usize>,
 }
 
-#[derive(Debug, Deserialize)]
-struct HeliusTokenAccount {
-    account: String,
-    amount: String,
-}
-
-#[derive(Debug, Deserialize)]
-struct HeliusLargestAccounts {
-    value: Vec<HeliusTokenAccount>,
-}
-
-#[derive(Debug, Deserialize)]
-struct HeliusAccountInfo {
-    data: HeliusAccountData,
-}
-
-#[derive(Debug, Deserialize)]
-struct HeliusAccountData {
-    parsed: HeliusParsedData,
-}
-
-#[derive(Debug, Deserialize)]
-struct HeliusParsedData {
-    info: HeliusTokenInfo,
-}
-
-#[derive(Debug, Deserialize)]
-struct HeliusTokenInfo {
-    #[serde(rename = "tokenAmount")]
-    token_amount: TokenAmount,
-    owner: String,
-}
-
-#[derive(Debug, Deserialize)]
-struct TokenAmount {
-    amount: String,
-    decimals: u8,
-    #[serde(rename = "uiAmount")]
-    ui_amount: Option<f64>,
-}
-
-#[derive(Debug, Deserialize)]
-struct HeliusSignature {
-    signature: String,
-    #[serde(rename = "blockTime")]
-    block_time: Option<i64>,
-}
-
-pub struct TokenAnalyzer {
-    client: Client,
-    rpc_url: String,
-}
-
-impl TokenAnalyzer {
-    pub fn new() -> Result<Self> {
-        let rpc_url = std::env::var("SOLANA_RPC_URL")
-            .unwrap_or_else(|_| "https://mainnet.helius-rpc.com/?api-key=YOUR_API_KEY".to_string());
-        
-        Ok(Self {
-            client: Client::new(),
-            rpc_url,
-        })
-    }
+/// Runs `detectors` over an already-fetched context and assembles the report,
+/// rating the score against `risk`: everything `TokenAnalyzer::analyze` does
+/// after its RPC calls. Builds
+/// without the `native` feature (WASM) call this directly.
+pub fn score(context: TokenContext, detectors: &[Box<dyn PatternDetector>], risk: &RiskConfig) -> SafetyAnalysis {
+    // Run the selected, enabled pattern detectors (all by default)
+    let mut signals = Vec::new();
+    let mut no_data_detectors = Vec::new();
     
-    pub async fn analyze(&self, mint_address: &str) -> Result<SafetyAnalysis> {
-        // Fetch token holders
-        let holders = self.fetch_token_holders(mint_address).await?;
-        
-        // Fetch recent transactions
-        let transactions = self.fetch_recent_transactions(mint_address).await?;
-        
-        // Estimate creation time (oldest transaction)
-        let creation_time = transactions
-            .iter()
-            .map(|tx| tx.timestamp)
-            .filter(|&t| t > 0)
-            .min()
-            .unwrap_or_else(|| chrono::Utc::now().timestamp());
-        
-        let current_time = chrono::Utc::now().timestamp();
-        
-        // Build context for pattern analysis
-        let context = self.build_context(
-            mint_address,
-            &holders,
-            &transactions,
-            creation_time,
-            current_time,
-        )?;
-        
-        // Run all pattern detectors
-        let detectors = get_all_detectors();
-        let mut signals = Vec::new();
-        
-        for detector in detectors {
-            let signal = detector.detect(&context);
-            signals.push(signal);
+    for detector in detectors {
+        let id = detector.id();
+        let skipped = inputs_among(detector.as_ref(), &context.skipped);
+        if !skipped.is_empty() {
+            signals.push(completeness::skipped(detector.as_ref(), &skipped));
+            continue;
         }
-        
-        // Calculate composite score
-        let safe_score = calculate_composite_score(&signals);
-        
-        // Determine risk level
-        let risk_level = self.determine_risk_level(safe_score);
-        
-        // Generate recommendation
-        let recommendation = generate_recommendation(safe_score, &signals);
-        
-        // Extract key reasons
-        let reasons = extract_key_reasons(&signals);
-        
-        // Build metrics
-        let metrics = SafetyMetrics {
-            whale_concentration: context.whale_concentration(3),
-            holder_count: context.unique_wallets(),
-            transaction_count: context.transaction_count(),
-            top_holder_percent: context.top_holder_percent(),
-            token_age_hours: context.age_hours(),
-            bot_activity_detected: context.has_bot_activity(5),
-            coordinated_pump: context.has_coordinated_pump(5, 10),
-            distribution_top10: context.whale_concentration(10),
-        };
-        
-        // Convert signals for output
-        let pattern_signals: Vec<PatternSignalOutput> = signals
-            .iter()
-            .map(|s| PatternSignalOutput {
-                name: s.name.clone(),
-                score: s.score,
-                confidence: s.confidence,
-                details: s.details.clone(),
-            })
-            .collect();
-        
-        Ok(SafetyAnalysis {
-            mint_address: mint_address.to_string(),
-            safe_score,
-            risk_level,
-            recommendation,
-            reasons,
-            metrics,
-            pattern_signals,
-        })
+        let missing = missing_inputs(detector.as_ref(), &context.missing);
+        if !missing.is_empty() {
+            no_data_detectors.push(id);
+            signals.push(no_data(detector.as_ref(), &missing));
+            continue;
+        }
+        // No clock on wasm32-unknown-unknown, so only native builds time detectors
+        #[cfg(feature = "native")]
+        let started = std::time::Instant::now();
+        let signal = tracing::debug_span!("detector", id = %id).in_scope(|| detector.detect(&context));
+        #[cfg(feature = "native")]
+        metrics::histogram!("analyzer_detector_duration_seconds", "detector" => id).record(started.elapsed());
+        signals.push(signal);
     }
     
-    fn build_context(
-        &self,
-        mint: &str,
-        holders: &[HolderInfo],
-        transactions: &[TransactionInfo],
-        creation_time: i64,
-        current_time: i64,
-    ) -> Result<TokenContext> {
-        Ok(TokenContext {
-            mint: mint.to_string(),
-            holders: holders.to_vec(),
-            transactions: transactions.to_vec(),
-            creation_time,
-            current_time,
-        })
-    }
+    // Calculate composite score, keeping the working
+    let score_breakdown = ScoreBreakdown::new(detectors, &signals);
+    let safe_score = score_breakdown.score;
     
-    async fn fetch_token_holders(&self, mint: &str) -> Result<Vec<HolderInfo>> {
-        let body = serde_json::json!({
-            "jsonrpc": "2.0",
-            "id": 1,
-            "method": "getTokenLargestAccounts",
-            "params": [mint]
-        });
-        
-        let response: serde_json::Value = self.client
-            .post(&self.rpc_url)
-            .json(&body)
-            .send()
-            .await?
-            .json()
-            .await?;
-        
-        if let Some(error) = response.get("error") {
-            return Err(anyhow!("RPC error: {}", error));
-        }
-        
-        let accounts = response["result"]["value"]
-            .as_array()
-            .ok_or_else(|| anyhow!("Invalid response format"))?;
-        
-        // Calculate total supply
-        let mut total_supply: f64 = 0.0;
-        let mut raw_holders = Vec::new();
-        
-        for account in accounts {
-            if let Some(amount_str) = account["amount"].as_str() {
-                if let Ok(amount) = amount_str.parse::<f64>() {
-                    let ui_amount = amount / 1_000_000.0; // Assuming 6 decimals
-                    total_supply += ui_amount;
-                    
-                    if let Some(address) = account["address"].as_str() {
-                        raw_holders.push((address.to_string(), ui_amount));
-                    }
-                }
-            }
-        }
-        
-        if total_supply == 0.0 {
-            return Err(anyhow!("Zero total supply"));
-        }
-        
-        // Calculate percentages and sort by balance
-        let mut holders: Vec<HolderInfo> = raw_holders
-            .into_iter()
-            .map(|(address, balance)| HolderInfo {
-                address,
-                balance,
-                percent: (balance / total_supply) * 100.0,
-            })
-            .collect();
-        
-        holders.sort_by(|a, b| b.percent.partial_cmp(&a.percent).unwrap());
-        
-        Ok(holders)
-    }
+    // Determine risk level
+    let risk_level = risk.thresholds.level(safe_score).to_string();
     
-    async fn fetch_recent_transactions(&self, mint: &str) -> Result<Vec<TransactionInfo>> {
-        let body = serde_json::json!({
-            "jsonrpc": "2.0",
-            "id": 1,
-            "method": "getSignaturesForAddress",
-            "params": [mint, {"limit": 100}]
-        });
-        
-        let response: serde_json::Value = self.client
-            .post(&self.rpc_url)
-            .json(&body)
-            .send()
-            .await?
-            .json()
-            .await?;
-        
-        if let Some(error) = response.get("error") {
-            return Err(anyhow!("RPC error: {}", error));
-        }
-        
-        let sigs = response["result"]
-            .as_array()
-            .ok_or_else(|| anyhow!("Invalid response format"))?;
-        
-        let mut transactions = Vec::new();
-        for sig in sigs {
-            if let Some(signature) = sig["signature"].as_str() {
-                let timestamp = sig["blockTime"].as_i64().unwrap_or(0);
-                
-                transactions.push(TransactionInfo {
-                    signature: signature.to_string(),
-                    timestamp,
-                    tx_type: "unknown".to_string(), // We don't parse tx type for now
-                });
-            }
-        }
-        
-        // Sort by timestamp (oldest first)
-        transactions.sort_by_key(|tx| tx.timestamp);
-        
-        Ok(transactions)
-    }
+    // Generate recommendation
+    let recommendation = risk.recommendations.render(&risk_level, safe_score);
     
-    fn determine_risk_level(&self, score: f64) -> String {
-        if score >= 70.0 {
-            "low".to_string()
-        } else if score >= 50.0 {
-            "medium".to_string()
-        } else if score >= 30.0 {
-            "high".to_string()
-        } else {
-            "critical".to_string()
-        }
+    // Extract key reasons
+    let reasons = extract_key_reasons(&signals);
+    let reason_codes = reason_codes(detectors, &signals);
+    
+    // Build metrics
+    let (decimals, total_supply) = context.mint_info.as_ref().map_or((0, 0.0), |m| (m.decimals, m.ui_supply()));
+    let (buy_count, sell_count) = context.trade_counts();
+    let velocity = context.velocity();
+    let top_traders: Vec<TraderPnl> = context.trader_pnl().into_iter().take(TOP_TRADERS).collect();
+    let metrics = SafetyMetrics {
+        whale_concentration: context.whale_concentration(3),
+        holder_count: context.unique_wallets(),
+        transaction_count: context.transaction_count(),
+        top_holder_percent: context.top_holder_percent(),
+        token_age_hours: context.age_hours(),
+        bot_activity_detected: context.has_bot_activity(5),
+        coordinated_pump: context.has_coordinated_pump(5, 10),
+        distribution_top10: context.whale_concentration(10),
+        gini_coefficient: context.gini_coefficient(),
+        hhi: context.hhi(),
+        buy_count,
+        sell_count,
+        net_flow_sol: context.net_flow_sol().0,
+        insider_percent: context.insider_percent().unwrap_or(0.0),
+        insider_extraction_percent: context.insider_extraction().map(|(_, percent)| percent),
+        decimals,
+        total_supply,
+        pool_count: context.liquidity.as_ref().map_or(0, |l| l.pools.len()),
+        liquidity_sol: context.liquidity.as_ref().map_or(0.0, LiquidityInfo::sol_depth),
+        liquidity_usd: context.liquidity.as_ref().map_or(0.0, LiquidityInfo::usd_depth),
+        price_usd: context.market.as_ref().and_then(|m| m.price_usd),
+        volume_24h_usd: context.market.as_ref().and_then(|m| m.volume_24h_usd),
+        market_cap_usd: context.market.as_ref().and_then(|m| m.market_cap_usd),
+        fdv_usd: context.market.as_ref().and_then(|m| m.fdv_usd),
+        transactions_last_hour: velocity.as_ref().map(|v| velocity::total(&v.per_minute)),
+    };
+
+    // Convert signals for output
+    let pattern_signals: Vec<PatternSignalOutput> = signals
+        .iter()
+        .map(|s| PatternSignalOutput {
+            name: s.name.clone(),
+            score: s.score,
+            confidence: s.confidence,
+            details: s.details.clone(),
+        })
+        .collect();
+    
+    SafetyAnalysis {
+        mint_address: context.mint,
+        safe_score,
+        risk_level,
+        risk_thresholds: risk.thresholds,
+        recommendation,
+        reasons,
+        reason_codes,
+        metrics,
+        pattern_signals,
+        score_breakdown,
+        ml_score: None,
+        metadata: context.metadata,
+        asset: None,
+        liquidity: context.liquidity,
+        deployer: context.deployer,
+        holder_funding: context.holder_funding,
+        launch: context.launch,
+        top_traders,
+        freeze_events: context.freeze_events,
+        velocity,
+        top_holders: context.holders.iter().take(TOP_HOLDERS).cloned().collect(),
+        excluded_holders: context.excluded_holders,
+        data_completeness: (!context.missing.is_empty()).then_some(DataCompleteness {
+            complete: false,
+            missing: context.missing,
+            no_data_detectors,
+        }),
+        list_match: None,
+        known_good: None,
+        missout_pools: None,
+        trend: None,
+        benchmark: None,
     }
 }
@@ -0,0 +1,85 @@
+//! Scoring model versions. Each version is a bundled scoring config pinning
+//! every detector's on/off flag, weight and thresholds, so a score recorded
+//! under `v1` can be reproduced after the defaults move on. Changing a
+//! detector's weight or logic means adding a new version here, never editing
+//! a released preset.
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{bail, Result};
+
+use super::scoring::ScoringConfig;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Model {
+    /// The original 23 detectors
+    V1,
+    /// Adds holder inequality (Gini / HHI)
+    V2,
+    /// Adds transaction velocity spikes and dead token decay
+    V3,
+    /// Adds freeze history
+    V4,
+    /// Adds holder delegates and close authorities
+    V5,
+    /// Adds Jito bundle launch detection
+    V6,
+    /// Adds insider extraction (early wallets' realized profit)
+    #[default]
+    V7,
+}
+
+impl Model {
+    /// Every bundled model, oldest first
+    pub const ALL: [Model; 7] = [Model::V1, Model::V2, Model::V3, Model::V4, Model::V5, Model::V6, Model::V7];
+
+    pub fn version(self) -> &'static str {
+        match self {
+            Self::V1 => "v1",
+            Self::V2 => "v2",
+            Self::V3 => "v3",
+            Self::V4 => "v4",
+            Self::V5 => "v5",
+            Self::V6 => "v6",
+            Self::V7 => "v7",
+        }
+    }
+
+    fn preset(self) -> &'static str {
+        match self {
+            Self::V1 => include_str!("models/v1.toml"),
+            Self::V2 => include_str!("models/v2.toml"),
+            Self::V3 => include_str!("models/v3.toml"),
+            Self::V4 => include_str!("models/v4.toml"),
+            Self::V5 => include_str!("models/v5.toml"),
+            Self::V6 => include_str!("models/v6.toml"),
+            Self::V7 => include_str!("models/v7.toml"),
+        }
+    }
+
+    /// The model's pinned scoring config
+    pub fn scoring(self) -> ScoringConfig {
+        toml::from_str(self.preset()).unwrap_or_else(|e| panic!("bundled model {} is invalid: {}", self, e))
+    }
+}
+
+impl fmt::Display for Model {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.version())
+    }
+}
+
+impl FromStr for Model {
+    type Err = anyhow::Error;
+
+    fn from_str(version: &str) -> Result<Self> {
+        match Self::ALL.into_iter().find(|m| m.version() == version) {
+            Some(model) => Ok(model),
+            None => {
+                let versions: Vec<&str> = Self::ALL.iter().map(|m| m.version()).collect();
+                bail!("unknown model `{}` (available: {})", version, versions.join(", "))
+            }
+        }
+    }
+}
@@ -1,34 +1,140 @@
-/**
- * Pattern Detection Framework
- * 
- * Ported from profit/ trading bot analysis system
- * Adapted to work with Helius RPC on-chain data
- */
+//! Pattern Detection Framework
+//!
+//! Ported from profit/ trading bot analysis system
+//! Adapted to work with Helius RPC on-chain data
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use super::accounts::{MintInfo, TokenMetadata};
+use super::completeness::{DataSource, MissingData};
+use super::deployer::DeployerHistory;
+use super::funding::HolderFunding;
+use super::freeze::FreezeEvent;
+use super::launch::LaunchWindow;
+use super::market::MarketData;
+use super::pnl::{self, TraderPnl};
+use super::liquidity::LiquidityInfo;
+use super::simulation::SellSimulation;
+use super::velocity::Velocity;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TokenContext {
     pub mint: String,
     pub holders: Vec<HolderInfo>,
+    /// Largest accounts left out of `holders` as known infrastructure
+    #[serde(default)]
+    pub excluded_holders: Vec<HolderInfo>,
     pub transactions: Vec<TransactionInfo>,
     pub creation_time: i64,
     pub current_time: i64,
+    #[serde(default)]
+    pub mint_info: Option<MintInfo>,
+    #[serde(default)]
+    pub metadata: Option<TokenMetadata>,
+    /// AMM pools holding the token; `None` when discovery failed
+    #[serde(default)]
+    pub liquidity: Option<LiquidityInfo>,
+    /// Simulated sell into the primary CPMM pool; `None` when not run or inconclusive
+    #[serde(default)]
+    pub sell_simulation: Option<SellSimulation>,
+    /// Creator wallet and its earlier launches; `None` when it couldn't be traced
+    #[serde(default)]
+    pub deployer: Option<DeployerHistory>,
+    /// Top holders grouped by who funded their wallets
+    #[serde(default)]
+    pub holder_funding: Option<HolderFunding>,
+    /// Snipes and insider allocations; `None` when the launch is out of reach
+    #[serde(default)]
+    pub launch: Option<LaunchWindow>,
+    /// Freezes and thaws of the mint's accounts among the recent and launch
+    /// transactions, oldest first
+    #[serde(default)]
+    pub freeze_events: Vec<FreezeEvent>,
+    /// Aggregator price and volume; `None` without a provider
+    #[serde(default)]
+    pub market: Option<MarketData>,
+    /// Sources whose fetch failed in partial mode; detectors reading them
+    /// report no data
+    #[serde(default)]
+    pub missing: Vec<MissingData>,
+    /// Sources deliberately not fetched (see `lists`); detectors reading them
+    /// are left out of the score
+    #[serde(default)]
+    pub skipped: Vec<DataSource>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct HolderInfo {
     pub address: String,
     pub balance: f64,
     pub percent: f64,
+    /// Wallet or program account that owns the token account
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    /// Known-address label (pool vault, burn, exchange...)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Account approved to transfer `delegated_amount` without the owner signing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delegate: Option<String>,
+    /// UI units the delegate may still move
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delegated_amount: Option<f64>,
+    /// Account allowed to close the token account once it is empty
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub close_authority: Option<String>,
+    /// The owner's other token accounts among the largest, whose balances are
+    /// counted in this one's
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub merged_accounts: Vec<String>,
+}
+
+/// Folds token accounts with the same owner into the largest of them, so a
+/// wallet spreading its tokens over several accounts counts as one holder.
+/// Takes and returns holders sorted by balance; accounts whose owner is
+/// unknown stay as they are.
+pub fn merge_by_owner(holders: Vec<HolderInfo>) -> Vec<HolderInfo> {
+    let mut merged: Vec<HolderInfo> = Vec::with_capacity(holders.len());
+    let mut by_owner: HashMap<String, usize> = HashMap::new();
+    for holder in holders {
+        let Some(&i) = holder.owner.as_ref().and_then(|owner| by_owner.get(owner)) else {
+            if let Some(owner) = &holder.owner {
+                by_owner.insert(owner.clone(), merged.len());
+            }
+            merged.push(holder);
+            continue;
+        };
+        let into = &mut merged[i];
+        into.balance += holder.balance;
+        into.percent += holder.percent;
+        into.delegated_amount = match (into.delegated_amount, holder.delegated_amount) {
+            (Some(a), Some(b)) => Some(a + b),
+            (a, b) => a.or(b),
+        };
+        into.delegate = into.delegate.take().or(holder.delegate);
+        into.close_authority = into.close_authority.take().or(holder.close_authority);
+        into.merged_accounts.push(holder.address);
+    }
+    merged.sort_by(|a, b| b.balance.total_cmp(&a.balance));
+    merged
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TransactionInfo {
     pub signature: String,
     pub timestamp: i64,
-    pub tx_type: String, // "buy", "sell", "transfer"
+    pub tx_type: String, // "buy", "sell", "transfer", "unknown"
+    /// Fee payer, when the transaction was fetched
+    #[serde(default)]
+    pub wallet: Option<String>,
+    /// Tokens the wallet gained or lost (UI units)
+    #[serde(default)]
+    pub token_amount: f64,
+    /// SOL the wallet paid or received, fee excluded
+    #[serde(default)]
+    pub sol_amount: f64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -38,6 +144,43 @@ pub struct PatternSignal {
     pub confidence: f64,  // 0.0 to 1.0
     pub details: String,
     pub weight: f64,
+    /// The measured values the verdict rests on
+    #[serde(default, skip_serializing_if = "Evidence::is_empty")]
+    pub evidence: Evidence,
+}
+
+impl PatternSignal {
+    /// The leading upper-case label of `details` (`"HIGH: ..."` → `HIGH`), if any
+    pub fn band(&self) -> Option<&str> {
+        band(&self.details)
+    }
+}
+
+/// The leading upper-case label of signal `details`, if any
+pub fn band(details: &str) -> Option<&str> {
+    let (label, _) = details.split_once(':')?;
+    let is_label = label.chars().any(|c| c.is_ascii_uppercase())
+        && label.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || matches!(c, ' ' | '-' | '_'));
+    is_label.then_some(label)
+}
+
+/// Named numbers behind a signal, e.g. `top3_percent` for whale concentration
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(transparent)]
+pub struct Evidence(pub BTreeMap<String, f64>);
+
+impl Evidence {
+    /// Adds `value` under `name`; `None` (not measured) adds nothing
+    pub fn with(mut self, name: &str, value: impl Into<Option<f64>>) -> Self {
+        if let Some(value) = value.into() {
+            self.0.insert(name.to_string(), value);
+        }
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
 }
 
 impl TokenContext {
@@ -60,6 +203,31 @@ impl TokenContext {
             .sum()
     }
 
+    /// Gini coefficient of holder balances: 0 when every holder holds the
+    /// same, approaching 1 as one holder holds everything. 0 with fewer than
+    /// two holders, where there is nothing to compare.
+    pub fn gini_coefficient(&self) -> f64 {
+        let mut balances: Vec<f64> = self.holders.iter().map(|h| h.balance).collect();
+        let (n, total) = (balances.len() as f64, balances.iter().sum::<f64>());
+        if balances.len() < 2 || total <= 0.0 {
+            return 0.0;
+        }
+        balances.sort_by(f64::total_cmp);
+        // G = Σ (2i - n - 1)·x_i / (n·Σx), i from 1 over ascending balances
+        let ranked: f64 = balances
+            .iter()
+            .enumerate()
+            .map(|(i, x)| (2.0 * (i as f64 + 1.0) - n - 1.0) * x)
+            .sum();
+        ranked / (n * total)
+    }
+
+    /// Herfindahl–Hirschman index: the sum of squared holder shares in
+    /// percent, from near 0 (dispersed) to 10,000 (a single holder)
+    pub fn hhi(&self) -> f64 {
+        self.holders.iter().map(|h| h.percent * h.percent).sum()
+    }
+
     /// Get top holder percentage
     pub fn top_holder_percent(&self) -> f64 {
         self.holders
@@ -78,6 +246,127 @@ impl TokenContext {
         self.transactions.len()
     }
 
+    /// Transactions bucketed per minute and per hour up to `current_time`
+    pub fn velocity(&self) -> Option<Velocity> {
+        Velocity::new(&self.transactions, self.current_time)
+    }
+
+    /// Mint supply in UI units, 0 when unknown
+    pub fn ui_supply(&self) -> f64 {
+        self.mint_info.as_ref().map_or(0.0, |m| m.ui_supply())
+    }
+
+    /// Share of supply delegates of the top holders can move without the
+    /// holders signing
+    pub fn delegated_percent(&self) -> Option<f64> {
+        let supply = self.ui_supply();
+        if supply <= 0.0 {
+            return None;
+        }
+        let delegated = self
+            .holders
+            .iter()
+            .filter(|h| h.delegate.is_some())
+            .fold(0.0, |sum, h| sum + h.delegated_amount.unwrap_or(0.0).min(h.balance));
+        Some(delegated / supply * 100.0)
+    }
+
+    /// Share of supply the deployer handed out before the launch
+    pub fn insider_percent(&self) -> Option<f64> {
+        let supply = self.ui_supply();
+        let launch = self.launch.as_ref().filter(|_| supply > 0.0)?;
+        Some(launch.insider_tokens() / supply * 100.0)
+    }
+
+    /// The deployer and the launch window's insiders, snipers and bundlers;
+    /// `None` without the launch window
+    pub fn early_wallets(&self) -> Option<HashSet<&str>> {
+        let launch = self.launch.as_ref()?;
+        let bundlers = launch.bundles.iter().flat_map(|b| &b.buyers);
+        let wallets = launch.insiders.iter().chain(&launch.snipers).chain(bundlers).map(|w| w.wallet.as_str());
+        Some(wallets.chain(self.deployer.as_ref().map(|d| d.address.as_str())).collect())
+    }
+
+    /// Realized PnL of every wallet trading in the classified transactions,
+    /// largest profit first
+    pub fn trader_pnl(&self) -> Vec<TraderPnl> {
+        pnl::trader_pnl(&self.transactions, &self.early_wallets().unwrap_or_default())
+    }
+
+    /// Early wallets' realized profit in SOL and as a percentage of the SOL
+    /// buyers spent; `None` without the launch window or any buys
+    pub fn insider_extraction(&self) -> Option<(f64, f64)> {
+        self.launch.as_ref()?;
+        pnl::insider_extraction(&self.trader_pnl())
+    }
+
+    /// Classified buys and sells
+    pub fn trade_counts(&self) -> (usize, usize) {
+        self.transactions.iter().fold((0, 0), |(buys, sells), tx| match tx.tx_type.as_str() {
+            "buy" => (buys + 1, sells),
+            "sell" => (buys, sells + 1),
+            _ => (buys, sells),
+        })
+    }
+
+    /// SOL spent on buys minus SOL received from sells, and the gross traded
+    pub fn net_flow_sol(&self) -> (f64, f64) {
+        self.transactions.iter().fold((0.0, 0.0), |(net, gross), tx| match tx.tx_type.as_str() {
+            "buy" => (net + tx.sol_amount, gross + tx.sol_amount),
+            "sell" => (net - tx.sol_amount, gross + tx.sol_amount),
+            _ => (net, gross),
+        })
+    }
+
+    /// SOL volume traded back and forth, and the number of trading groups doing
+    /// it. A group is one wallet, or every wallet in a holder funding cluster;
+    /// it washes when it both bought and sold `min_round_trips` times with
+    /// token amounts within `min_match` of each other. Its matched buy and
+    /// sell legs count as wash volume.
+    pub fn wash_volume_sol(&self, min_round_trips: usize, min_match: f64) -> (f64, usize) {
+        let clusters = self.holder_funding.iter().flat_map(|f| &f.clusters);
+        let group_of = |wallet: &str| {
+            clusters
+                .clone()
+                .find(|c| c.wallets.iter().any(|w| w == wallet))
+                .map_or_else(|| wallet.to_string(), |c| c.funder.clone())
+        };
+
+        #[derive(Default)]
+        struct Legs {
+            buys: usize,
+            sells: usize,
+            bought: f64,
+            sold: f64,
+            buy_sol: f64,
+            sell_sol: f64,
+        }
+        let mut groups: HashMap<String, Legs> = HashMap::new();
+        for tx in &self.transactions {
+            let Some(wallet) = &tx.wallet else { continue };
+            let legs = groups.entry(group_of(wallet)).or_default();
+            match tx.tx_type.as_str() {
+                "buy" => {
+                    legs.buys += 1;
+                    legs.bought += tx.token_amount;
+                    legs.buy_sol += tx.sol_amount;
+                }
+                "sell" => {
+                    legs.sells += 1;
+                    legs.sold += tx.token_amount;
+                    legs.sell_sol += tx.sol_amount;
+                }
+                _ => {}
+            }
+        }
+
+        groups
+            .values()
+            .filter(|l| l.buys.min(l.sells) >= min_round_trips)
+            .filter(|l| l.bought.min(l.sold) >= min_match * l.bought.max(l.sold))
+            .fold((0.0, 0), |(volume, n), l| (volume + 2.0 * l.buy_sol.min(l.sell_sol), n + 1))
+    }
+
     /// Detect coordinated pump (many txs in short time)
     pub fn has_coordinated_pump(&self, min_txs: usize, time_window_secs: i64) -> bool {
         if self.transactions.len() < min_txs {
@@ -116,8 +405,22 @@ impl TokenContext {
     }
 }
 
-pub trait PatternDetector {
+pub trait PatternDetector: Send + Sync {
     fn name(&self) -> &str;
+    /// Stable kebab-case id used to select detectors from the command line
+    fn id(&self) -> String {
+        self.name().to_lowercase().replace(' ', "-")
+    }
     fn detect(&self, ctx: &TokenContext) -> PatternSignal;
     fn weight(&self) -> f64;
+    /// Threshold values the detector compares against, as configured; shown
+    /// in the score breakdown. Detectors without tunable thresholds have none.
+    fn thresholds(&self) -> serde_json::Map<String, serde_json::Value> {
+        serde_json::Map::new()
+    }
+    /// Fetched sources the detector reads; in partial mode it reports no data
+    /// when one of them is missing. Detectors declaring none always run.
+    fn inputs(&self) -> &[DataSource] {
+        &[]
+    }
 }
@@ -0,0 +1,96 @@
+//! Trader PnL: realized profit per wallet over the classified buys and sells.
+//! Each wallet's sells are set against the average price of its buys in the
+//! same transactions; tokens sold beyond those bought there (launch buys and
+//! insider allocations older than the fetched history) count at no cost,
+//! which for early wallets is close to what they paid.
+//!
+//! Insider extraction is the early wallets' (deployer, insiders, snipers and
+//! bundlers of the launch window) realized profit as a share of the SOL every
+//! buyer paid in: how much of the money coming in has already left with them.
+
+use std::collections::{BTreeMap, HashSet};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::patterns::TransactionInfo;
+
+/// Traders kept in the report, largest realized profit first
+pub const TOP_TRADERS: usize = 10;
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TraderPnl {
+    pub wallet: String,
+    pub buys: usize,
+    pub sells: usize,
+    pub sol_spent: f64,
+    pub sol_received: f64,
+    /// SOL received minus the cost of the tokens sold
+    pub realized_pnl_sol: f64,
+    /// Deployer, insider, sniper or bundler of the launch window
+    #[serde(default)]
+    pub early: bool,
+}
+
+/// Realized PnL of every wallet that bought or sold in `transactions`,
+/// largest profit first
+pub fn trader_pnl(transactions: &[TransactionInfo], early: &HashSet<&str>) -> Vec<TraderPnl> {
+    #[derive(Default)]
+    struct Flow {
+        buys: usize,
+        sells: usize,
+        bought: f64,
+        sold: f64,
+        sol_spent: f64,
+        sol_received: f64,
+    }
+
+    let mut flows: BTreeMap<&str, Flow> = BTreeMap::new();
+    for tx in transactions {
+        let Some(wallet) = tx.wallet.as_deref() else { continue };
+        match tx.tx_type.as_str() {
+            "buy" => {
+                let flow = flows.entry(wallet).or_default();
+                flow.buys += 1;
+                flow.bought += tx.token_amount;
+                flow.sol_spent += tx.sol_amount;
+            }
+            "sell" => {
+                let flow = flows.entry(wallet).or_default();
+                flow.sells += 1;
+                flow.sold += tx.token_amount;
+                flow.sol_received += tx.sol_amount;
+            }
+            _ => {}
+        }
+    }
+
+    let mut traders: Vec<TraderPnl> = flows
+        .into_iter()
+        .map(|(wallet, flow)| {
+            let sold_share = if flow.bought > 0.0 { (flow.sold / flow.bought).min(1.0) } else { 0.0 };
+            TraderPnl {
+                wallet: wallet.to_string(),
+                buys: flow.buys,
+                sells: flow.sells,
+                sol_spent: flow.sol_spent,
+                sol_received: flow.sol_received,
+                realized_pnl_sol: flow.sol_received - flow.sol_spent * sold_share,
+                early: early.contains(wallet),
+            }
+        })
+        .collect();
+    traders.sort_by(|a, b| b.realized_pnl_sol.total_cmp(&a.realized_pnl_sol));
+    traders
+}
+
+/// Early wallets' realized profit, in SOL and as a percentage of the SOL all
+/// buyers spent; `None` without any buys
+pub fn insider_extraction(traders: &[TraderPnl]) -> Option<(f64, f64)> {
+    let spent = traders.iter().fold(0.0, |sum, t| sum + t.sol_spent);
+    let profit = traders
+        .iter()
+        .filter(|t| t.early)
+        .fold(0.0, |sum, t| sum + t.realized_pnl_sol.max(0.0));
+    (spent > 0.0).then(|| (profit, profit / spent * 100.0))
+}
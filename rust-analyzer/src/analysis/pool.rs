@@ -0,0 +1,315 @@
+//! Missout pool checks: decodes an `ml` program `Pool` account with the shared
+//! `ml-types` layouts, flags pool-level red flags (fees eating the pot, mock
+//! randomness, the creator collecting the dev fee) and combines them with the
+//! safety analysis of the pool's mint. `assert_safe_for_pool` gates pool
+//! creation on the mint's score, and `PoolUsage` summarizes the pools already
+//! running on an analyzed mint.
+
+use std::fmt;
+
+use anyhow::{anyhow, bail, Result};
+use base64::Engine;
+use ml_types::state::{Pool, PoolStatus};
+use ml_types::AccountData;
+#[cfg(feature = "native")]
+use ml_types::Discriminator;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::reasons::Severity;
+use super::AnalysisOutput;
+#[cfg(feature = "native")]
+use super::{AnalyzerConfig, SafetyAnalysis, TokenAnalyzer};
+
+/// Total fee above which a pool is flagged unless configured otherwise (10%)
+pub const DEFAULT_MAX_FEE_BPS: u16 = 1_000;
+/// Total fee at which the flag turns critical: half the pot or more
+const CRITICAL_FEE_BPS: u16 = 5_000;
+/// Offset of `Pool::mint` in the account data: discriminator, `pool_id`, `salt`
+#[cfg(feature = "native")]
+const MINT_OFFSET: usize = 8 + 8 + 32;
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PoolFlag {
+    /// Stable upper snake case code, like `Reason::code`
+    pub code: String,
+    pub severity: Severity,
+    pub message: String,
+}
+
+/// Pool-level red flags of `pool`, worst first
+pub fn pool_flags(pool: &Pool, max_fee_bps: u16) -> Vec<PoolFlag> {
+    let mut flags = Vec::new();
+    let flag = |code: &str, severity, message: String| PoolFlag { code: code.to_string(), severity, message };
+
+    let total_fee_bps = total_fee_bps(pool);
+    if total_fee_bps > max_fee_bps {
+        let severity = if total_fee_bps >= CRITICAL_FEE_BPS { Severity::Critical } else { Severity::High };
+        let message = format!(
+            "fees take {:.1}% of the pot (dev {}, burn {}, treasury {} bps; limit {} bps)",
+            f64::from(total_fee_bps) / 100.0,
+            pool.dev_fee_bps,
+            pool.burn_fee_bps,
+            pool.treasury_fee_bps,
+            max_fee_bps,
+        );
+        flags.push(flag("EXCESSIVE_FEES", severity, message));
+    }
+    if pool.allow_mock {
+        let message = "mock randomness is enabled, so the winner can be chosen instead of drawn".to_string();
+        flags.push(flag("MOCK_RANDOMNESS", Severity::Critical, message));
+    }
+    if pool.dev_wallet == pool.creator && pool.dev_fee_bps > 0 {
+        let message = format!("the creator collects the {} bps dev fee", pool.dev_fee_bps);
+        flags.push(flag("DEV_IS_CREATOR", Severity::Medium, message));
+    }
+
+    flags.sort_by_key(|flag| std::cmp::Reverse(flag.severity));
+    flags
+}
+
+fn total_fee_bps(pool: &Pool) -> u16 {
+    pool.dev_fee_bps.saturating_add(pool.burn_fee_bps).saturating_add(pool.treasury_fee_bps)
+}
+
+/// A pool's own settings and red flags next to its mint's analysis
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PoolReport {
+    pub pool: String,
+    pub mint: String,
+    pub creator: String,
+    pub status: String,
+    /// Unix time the pool expires
+    pub expire_time: i64,
+    /// Unix time entries close; donations stay open until `expire_time`
+    pub join_deadline: i64,
+    pub dev_wallet: String,
+    pub dev_fee_bps: u16,
+    pub burn_fee_bps: u16,
+    pub treasury_fee_bps: u16,
+    pub total_fee_bps: u16,
+    pub allow_mock: bool,
+    /// The worse of the token's risk level and the flags' severities;
+    /// `unknown` when the token analysis failed and nothing was flagged
+    pub risk_level: String,
+    pub flags: Vec<PoolFlag>,
+    /// Safety analysis of `mint`
+    pub token: AnalysisOutput,
+}
+
+impl PoolReport {
+    pub fn new(address: &str, pool: &Pool, token: AnalysisOutput, max_fee_bps: u16) -> Self {
+        let flags = pool_flags(pool, max_fee_bps);
+        let token_level = token.data.as_ref().map(|analysis| analysis.risk_level.as_str());
+        let risk_level = flags
+            .iter()
+            .filter_map(|flag| level(flag.severity))
+            .chain(token_level)
+            .max_by_key(|level| rank(level))
+            .unwrap_or("unknown")
+            .to_string();
+
+        Self {
+            pool: address.to_string(),
+            mint: pool.mint.to_string(),
+            creator: pool.creator.to_string(),
+            status: pool.status.as_str().to_string(),
+            expire_time: pool.expire_time,
+            join_deadline: pool.join_deadline,
+            dev_wallet: pool.dev_wallet.to_string(),
+            dev_fee_bps: pool.dev_fee_bps,
+            burn_fee_bps: pool.burn_fee_bps,
+            treasury_fee_bps: pool.treasury_fee_bps,
+            total_fee_bps: total_fee_bps(pool),
+            allow_mock: pool.allow_mock,
+            risk_level,
+            flags,
+            token,
+        }
+    }
+}
+
+/// The risk level a flag of `severity` amounts to
+fn level(severity: Severity) -> Option<&'static str> {
+    match severity {
+        Severity::Critical => Some("critical"),
+        Severity::High => Some("high"),
+        Severity::Medium => Some("medium"),
+        Severity::Low => Some("low"),
+        Severity::Pass | Severity::Unknown => None,
+    }
+}
+
+fn rank(level: &str) -> u8 {
+    match level {
+        "critical" => 3,
+        "high" => 2,
+        "medium" => 1,
+        _ => 0,
+    }
+}
+
+/// Missout pools of an analyzed mint, for ecosystem-level visibility; it
+/// doesn't affect the score
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct PoolUsage {
+    /// Pools of the mint in any status
+    pub pools: usize,
+    /// Pools not yet ended, cancelled or closed
+    pub active_pools: usize,
+    /// Tokens in the active pools' pots, in UI units
+    pub total_value_locked: f64,
+    /// Active pools with red flags (at `DEFAULT_MAX_FEE_BPS`), worst first
+    pub unsafe_pools: Vec<FlaggedPool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FlaggedPool {
+    pub pool: String,
+    pub status: String,
+    pub flags: Vec<PoolFlag>,
+}
+
+impl FlaggedPool {
+    fn severity(&self) -> Severity {
+        self.flags.first().map_or(Severity::Pass, |flag| flag.severity)
+    }
+}
+
+fn is_active(status: PoolStatus) -> bool {
+    !matches!(status, PoolStatus::Ended | PoolStatus::Cancelled | PoolStatus::Closed)
+}
+
+/// Summarizes `(address, pool)` pairs of one mint with `decimals`
+pub fn pool_usage(pools: &[(String, Pool)], decimals: u8) -> PoolUsage {
+    let active: Vec<_> = pools.iter().filter(|(_, pool)| is_active(pool.status)).collect();
+    let locked: u64 = active.iter().map(|(_, pool)| pool.total_amount).fold(0, u64::saturating_add);
+    let mut unsafe_pools: Vec<FlaggedPool> = active
+        .iter()
+        .filter_map(|(address, pool)| {
+            let flags = pool_flags(pool, DEFAULT_MAX_FEE_BPS);
+            (!flags.is_empty()).then(|| FlaggedPool {
+                pool: address.clone(),
+                status: pool.status.as_str().to_string(),
+                flags,
+            })
+        })
+        .collect();
+    unsafe_pools.sort_by_key(|pool| std::cmp::Reverse(pool.severity()));
+
+    PoolUsage {
+        pools: pools.len(),
+        active_pools: active.len(),
+        total_value_locked: locked as f64 / 10f64.powi(i32::from(decimals)),
+        unsafe_pools,
+    }
+}
+
+/// Why `assert_safe_for_pool` refused a mint; downcast the `anyhow::Error` to
+/// tell a refusal apart from a failed analysis
+#[derive(Debug, Clone)]
+pub struct UnsafeMint {
+    pub mint: String,
+    pub score: f64,
+    pub min_score: f64,
+    pub risk_level: String,
+    /// The analysis' top reasons
+    pub reasons: Vec<String>,
+}
+
+impl fmt::Display for UnsafeMint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} scores {:.1}/100 ({} risk), below the {} required to create a pool",
+            self.mint, self.score, self.risk_level, self.min_score,
+        )?;
+        if let Some(reason) = self.reasons.first() {
+            write!(f, ": {}", reason)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for UnsafeMint {}
+
+/// Decodes the `value` of a base64 `getAccountInfo` response as a `Pool`
+pub fn decode_pool(value: &serde_json::Value) -> Result<Pool> {
+    if value.is_null() {
+        bail!("pool account not found");
+    }
+    let owner = value["owner"].as_str().unwrap_or_default();
+    if owner != ml_types::PROGRAM_ID.to_string() {
+        bail!("account is not a Missout pool (owner {})", owner);
+    }
+    let data = value["data"][0].as_str().ok_or_else(|| anyhow!("pool account has no data"))?;
+    let data = base64::engine::general_purpose::STANDARD.decode(data)?;
+    Pool::try_from_account_data(&data).map_err(|e| anyhow!("decoding pool account: {}", e))
+}
+
+#[cfg(feature = "native")]
+impl TokenAnalyzer {
+    /// Fetches and decodes the pool at `address`, then analyzes its mint. A
+    /// failed token analysis is reported in `token`, not returned as an error,
+    /// so the pool's own flags still come through.
+    pub async fn analyze_pool(&self, address: &str, max_fee_bps: u16) -> Result<PoolReport> {
+        let result = self
+            .rpc_call(address, "getAccountInfo", serde_json::json!([address, {"encoding": "base64"}]))
+            .await?;
+        let pool = decode_pool(&result["value"])?;
+        let token = self.analyze_output(&pool.mint.to_string()).await;
+        Ok(PoolReport::new(address, &pool, token, max_fee_bps))
+    }
+
+    /// Every Missout pool of `mint`, as `(address, pool)`; accounts that don't
+    /// decode (older layouts) are skipped
+    pub(super) async fn fetch_mint_pools(&self, mint: &str) -> Result<Vec<(String, Pool)>> {
+        let discriminator = base64::engine::general_purpose::STANDARD.encode(Pool::DISCRIMINATOR);
+        let params = serde_json::json!([ml_types::PROGRAM_ID.to_string(), {
+            "encoding": "base64",
+            "filters": [
+                { "memcmp": { "offset": 0, "bytes": discriminator, "encoding": "base64" } },
+                { "memcmp": { "offset": MINT_OFFSET, "bytes": mint } },
+            ],
+        }]);
+        let result = self.rpc_call(mint, "getProgramAccounts", params).await?;
+        let accounts = result.as_array().ok_or_else(|| anyhow!("Invalid response format"))?;
+        Ok(accounts
+            .iter()
+            .filter_map(|account| {
+                let pool = decode_pool(&account["account"]).ok()?;
+                Some((account["pubkey"].as_str()?.to_string(), pool))
+            })
+            .collect())
+    }
+
+    /// The mint's analysis if it scores at least `min_score`, an `UnsafeMint`
+    /// error if not. Fails closed: a mint that can't be analyzed is refused too.
+    pub async fn assert_safe_for_pool(&self, mint: &str, min_score: f64) -> Result<SafetyAnalysis> {
+        let analysis = self
+            .analyze(mint)
+            .await
+            .map_err(|e| e.context(format!("refusing to create a pool: {} could not be analyzed", mint)))?;
+        if analysis.safe_score < min_score {
+            return Err(UnsafeMint {
+                mint: mint.to_string(),
+                score: analysis.safe_score,
+                min_score,
+                risk_level: analysis.risk_level.clone(),
+                reasons: analysis.reasons.clone(),
+            }
+            .into());
+        }
+        Ok(analysis)
+    }
+}
+
+/// `TokenAnalyzer::assert_safe_for_pool` with the default analyzer
+/// (`SOLANA_RPC_URL`, the built-in detectors), for clients that build
+/// `create_pool` transactions: call it first and don't build on an error
+#[cfg(feature = "native")]
+pub async fn assert_safe_for_pool(mint: &str, min_score: f64) -> Result<SafetyAnalysis> {
+    TokenAnalyzer::with_config(AnalyzerConfig::default())?
+        .assert_safe_for_pool(mint, min_score)
+        .await
+}
@@ -0,0 +1,172 @@
+//! Rug post-mortems: the analysis as it would have come out at an earlier
+//! time, to see which detectors would have fired before a token collapsed.
+//! The mint's signatures up to then are replayed: each token account holds
+//! its post-balance in the newest replayed transaction touching it, and the
+//! trades, freezes and launch come from the same transactions.
+//!
+//! Only what can be rebuilt as of then is scored. Pool depth, the sell
+//! simulation and market data describe the present and are skipped; the mint
+//! account (authorities, supply) and the deployer's earlier launches are read
+//! as they are now. A mint with more history than `REPLAYED_TRANSACTIONS`
+//! before the time misses the accounts last touched earlier than those.
+
+use std::collections::HashMap;
+
+#[cfg(feature = "native")]
+use anyhow::{anyhow, Result};
+#[cfg(feature = "native")]
+use futures::{stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::completeness::DataSource;
+#[cfg(feature = "native")]
+use super::deployer::Signature;
+#[cfg(feature = "native")]
+use super::launch::{LAUNCH_TRANSACTIONS, MINT_SIGNATURE_PAGES};
+use super::patterns::HolderInfo;
+use super::reasons::Reason;
+use super::SafetyAnalysis;
+#[cfg(feature = "native")]
+use super::TokenAnalyzer;
+
+/// Newest transactions before the time fetched to rebuild holders and trades,
+/// on top of the launch window's oldest
+pub const REPLAYED_TRANSACTIONS: usize = 300;
+/// Largest rebuilt accounts kept, as many as `getTokenLargestAccounts` returns
+pub const HOLDERS: usize = 20;
+/// Sources describing the present rather than the time of the post-mortem
+pub const POSTMORTEM_SKIPS: &[DataSource] = &[DataSource::Liquidity, DataSource::SellSimulation, DataSource::Market];
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Postmortem {
+    /// Unix time the analysis was rebuilt as of
+    pub at: i64,
+    /// The mint's successful transactions up to `at`
+    pub transactions_before: usize,
+    /// Those fetched and replayed; holders are exact when this equals
+    /// `transactions_before` and the walk reached the mint's creation
+    pub replayed_transactions: usize,
+    /// Whether the signature walk reached the mint's creation, so the launch
+    /// window and deployer history could be rebuilt
+    pub reaches_creation: bool,
+    /// Sources left out of the score
+    pub skipped: Vec<DataSource>,
+    /// Findings of high severity or worse, most important first
+    pub fired: Vec<Reason>,
+    pub analysis: SafetyAnalysis,
+}
+
+/// The largest token accounts of `mint` after `transactions` (oldest first),
+/// each at its post-balance in the newest one touching it. Percentages are of
+/// the kept accounts' total, like live holders'.
+pub fn holders_at<'a>(transactions: impl IntoIterator<Item = &'a Value>, mint: &str) -> Vec<HolderInfo> {
+    let mut accounts: HashMap<String, (String, f64)> = HashMap::new();
+    for tx in transactions.into_iter().filter(|tx| tx["meta"]["err"].is_null()) {
+        let keys = &tx["transaction"]["message"]["accountKeys"];
+        // Token accounts closed in the transaction only appear before it
+        for balance in tx["meta"]["preTokenBalances"].as_array().into_iter().flatten().filter(|b| b["mint"] == mint) {
+            if let Some(account) = token_account(keys, balance) {
+                accounts.remove(&account);
+            }
+        }
+        for balance in tx["meta"]["postTokenBalances"].as_array().into_iter().flatten().filter(|b| b["mint"] == mint) {
+            let amount = balance["uiTokenAmount"]["uiAmountString"].as_str().and_then(|a| a.parse::<f64>().ok());
+            if let (Some(account), Some(owner), Some(amount)) = (token_account(keys, balance), balance["owner"].as_str(), amount) {
+                accounts.insert(account, (owner.to_string(), amount));
+            }
+        }
+    }
+
+    let mut holders: Vec<HolderInfo> = accounts
+        .into_iter()
+        .filter(|(_, (_, balance))| *balance > 0.0)
+        .map(|(address, (owner, balance))| HolderInfo {
+            address,
+            balance,
+            percent: 0.0,
+            owner: Some(owner),
+            label: None,
+            delegate: None,
+            delegated_amount: None,
+            close_authority: None,
+            merged_accounts: Vec::new(),
+        })
+        .collect();
+    holders.sort_by(|a, b| b.balance.total_cmp(&a.balance).then_with(|| a.address.cmp(&b.address)));
+    holders.truncate(HOLDERS);
+    let total = holders.iter().fold(0.0, |sum, h| sum + h.balance);
+    for holder in &mut holders {
+        holder.percent = holder.balance / total * 100.0;
+    }
+    holders
+}
+
+/// The token account a `jsonParsed` token balance entry is for; its owner
+/// when the account keys don't cover the index
+fn token_account(keys: &Value, balance: &Value) -> Option<String> {
+    let key = balance["accountIndex"].as_u64().map(|i| &keys[i as usize]);
+    key.and_then(|k| k["pubkey"].as_str().or(k.as_str()))
+        .or_else(|| balance["owner"].as_str())
+        .map(str::to_string)
+}
+
+/// The mint's transactions up to a time, as fetched for a post-mortem
+#[cfg(feature = "native")]
+pub(super) struct Replay {
+    /// Successful signatures up to the time, oldest first
+    pub signatures: Vec<Signature>,
+    /// `signatures[i]`'s transaction when it was replayed and fetched
+    pub transactions: Vec<Option<Value>>,
+    pub reaches_creation: bool,
+}
+
+#[cfg(feature = "native")]
+impl Replay {
+    /// The launch window's signatures and transactions, when the walk got there
+    pub fn launch(&self) -> Option<(&[Signature], &[Option<Value>])> {
+        let end = self.signatures.len().min(LAUNCH_TRANSACTIONS);
+        self.reaches_creation.then(|| (&self.signatures[..end], &self.transactions[..end]))
+    }
+
+    pub fn replayed(&self) -> usize {
+        self.transactions.iter().filter(|tx| tx.is_some()).count()
+    }
+}
+
+#[cfg(feature = "native")]
+impl TokenAnalyzer {
+    /// Walks the mint's signatures back past `at` and fetches the launch
+    /// window's and the newest `REPLAYED_TRANSACTIONS` up to it
+    pub(super) async fn fetch_replay(&self, mint: &str, at: i64) -> Result<Replay> {
+        let (signatures, complete) = self.fetch_signatures(mint, mint, MINT_SIGNATURE_PAGES).await?;
+        // Newest first. Nothing up to `at` means the mint is younger, or the
+        // walk stopped short of it
+        let mut signatures: Vec<Signature> = signatures.into_iter().filter(|s| s.block_time.is_some_and(|t| t <= at)).collect();
+        if signatures.is_empty() {
+            return Err(match complete {
+                true => anyhow!("mint has no transactions before {}", at),
+                false => anyhow!("{} is more than {} signature pages back", at, MINT_SIGNATURE_PAGES),
+            });
+        }
+        signatures.reverse();
+
+        let launch = if complete { LAUNCH_TRANSACTIONS } else { 0 };
+        let recent = signatures.len().saturating_sub(REPLAYED_TRANSACTIONS);
+        let lookups: Vec<_> = signatures
+            .iter()
+            .enumerate()
+            .map(|(i, s)| async move {
+                if i >= launch && i < recent {
+                    return None;
+                }
+                self.fetch_transaction(mint, &s.signature)
+                    .await
+                    .map_err(|e| tracing::warn!(signature = %s.signature, "transaction not replayed: {:#}", e))
+                    .ok()
+            })
+            .collect();
+        let transactions = stream::iter(lookups).buffered(10).collect().await;
+        Ok(Replay { signatures, transactions, reaches_creation: complete })
+    }
+}
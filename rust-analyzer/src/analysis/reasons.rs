@@ -0,0 +1,95 @@
+//! Machine-readable counterparts of `reasons`: one entry per detector with a
+//! stable code, a severity and the numbers behind it, so bots can branch on
+//! `COMMON_FUNDER_SYBIL` instead of matching emoji-decorated strings.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::patterns::{Evidence, PatternDetector, PatternSignal};
+
+/// How bad a finding is, from the detector's 0-1 score. Ordered, so
+/// `severity >= Severity::High` selects red flags.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Passed (score 1.0)
+    Pass,
+    /// The detector lacked the data to judge, or was skipped
+    Unknown,
+    /// Score 0.8-1.0
+    Low,
+    /// Score 0.5-0.8
+    Medium,
+    /// Score 0.1-0.5
+    High,
+    /// Score 0.1 or below, or a `CRITICAL` band
+    Critical,
+}
+
+impl Severity {
+    pub fn of(signal: &PatternSignal) -> Self {
+        match signal.band() {
+            Some("UNKNOWN" | "SKIPPED") => return Self::Unknown,
+            Some("CRITICAL") => return Self::Critical,
+            _ => {}
+        }
+        if signal.score <= 0.1 {
+            Self::Critical
+        } else if signal.score < 0.5 {
+            Self::High
+        } else if signal.score < 0.8 {
+            Self::Medium
+        } else if signal.score < 1.0 {
+            Self::Low
+        } else {
+            Self::Pass
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Reason {
+    /// `<DETECTOR>_<BAND>` in upper snake case, e.g. `WHALE_CONCENTRATION_HIGH`;
+    /// just `<DETECTOR>` when the signal has no band
+    pub code: String,
+    pub severity: Severity,
+    /// Detector id, as `--detectors` takes it
+    pub detector: String,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Evidence::is_empty")]
+    pub evidence: Evidence,
+}
+
+/// A reason per signal, most important first in the same order as `reasons`
+/// (whose first five they line up with); `detectors[i]` produced `signals[i]`
+pub fn reason_codes(detectors: &[Box<dyn PatternDetector>], signals: &[PatternSignal]) -> Vec<Reason> {
+    let mut ranked: Vec<(&Box<dyn PatternDetector>, &PatternSignal)> = detectors.iter().zip(signals).collect();
+    ranked.sort_by(|(_, a), (_, b)| (a.score * a.weight).total_cmp(&(b.score * b.weight)));
+
+    ranked
+        .into_iter()
+        .map(|(detector, signal)| {
+            let detector = detector.id();
+            let code = match signal.band() {
+                Some(band) => format!("{}_{}", code_part(&detector), code_part(band)),
+                None => code_part(&detector),
+            };
+            Reason {
+                code,
+                severity: Severity::of(signal),
+                detector,
+                message: format!("{}: {}", signal.name, signal.details),
+                evidence: signal.evidence.clone(),
+            }
+        })
+        .collect()
+}
+
+/// `buy/sell-ratio` → `BUY_SELL_RATIO`
+fn code_part(text: &str) -> String {
+    text.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_ascii_uppercase)
+        .collect::<Vec<_>>()
+        .join("_")
+}
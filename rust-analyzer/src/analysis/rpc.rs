@@ -0,0 +1,371 @@
+//! JSON-RPC transport over one or more endpoints. Calls rotate round-robin;
+//! an endpoint answering 429/5xx (or not answering) is benched for a cooldown,
+//! honouring `Retry-After`, and the call fails over to the next one. When every
+//! endpoint fails, the whole pass is retried with jittered exponential backoff,
+//! all within the call's overall timeout.
+//!
+//! Helius API keys join the rotation as endpoints of their own on the
+//! cluster's Helius URL, each with an
+//! optional credit budget and requests-per-second limit. A key nearing its
+//! rate limit goes to the back of the rotation for the rest of the second, and
+//! a key that has spent its budget is left out (see `cost` for the prices).
+
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail, Context, Result};
+use metrics::counter;
+use rand::Rng;
+use reqwest::{Client, StatusCode};
+use serde::Serialize;
+use serde_json::Value;
+
+use super::cluster::Cluster;
+use super::cost;
+
+/// How long a failing endpoint is skipped when it gives no `Retry-After`
+pub const DEFAULT_COOLDOWN: Duration = Duration::from_secs(30);
+/// Share of a key's rate limit after which other keys are preferred
+const RATE_HEADROOM: f64 = 0.8;
+
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// Passes over the endpoint list before giving up (at least 1)
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubles on each further one
+    pub base_delay: Duration,
+    /// Cap on any single delay, including a server's `Retry-After`
+    pub max_delay: Duration,
+    /// Longest one call may take over every attempt and backoff, so endpoints
+    /// that keep hanging up to the HTTP timeout can't hold a call forever
+    pub call_timeout: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(250),
+            max_delay: Duration::from_secs(10),
+            call_timeout: Duration::from_secs(60),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Wait before retry number `retry` (0-based): exponential with jitter in
+    /// `[d/2, d]`, raised to `retry_after` when the server asked for longer
+    fn delay(&self, retry: u32, retry_after: Option<Duration>) -> Duration {
+        let exp = self.base_delay.saturating_mul(1 << retry.min(16)).min(self.max_delay);
+        let half = exp / 2;
+        let jittered = half + rand::thread_rng().gen_range(Duration::ZERO..=half);
+        retry_after.map_or(jittered, |ra| jittered.max(ra)).min(self.max_delay)
+    }
+}
+
+/// A Helius API key, parsed from `KEY`, `KEY:CREDITS` or `KEY:CREDITS:RPS`
+/// (either limit may be left empty)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiKey {
+    pub key: String,
+    /// Credits the key may spend in this process; unlimited when `None`
+    pub credits: Option<u64>,
+    /// Requests per second the key's plan allows
+    pub rate_limit: Option<u32>,
+}
+
+impl ApiKey {
+    /// The key's Helius endpoint on `cluster`
+    pub fn url(&self, cluster: Cluster) -> Result<String> {
+        match cluster.helius_url() {
+            Some(url) => Ok(format!("{}{}", url, self.key)),
+            None => bail!("Helius API keys need a {} cluster; use --rpc-url", Cluster::Mainnet),
+        }
+    }
+}
+
+impl FromStr for ApiKey {
+    type Err = anyhow::Error;
+
+    fn from_str(spec: &str) -> Result<Self> {
+        let mut parts = spec.trim().split(':');
+        let key = parts.next().unwrap_or_default().to_string();
+        if key.is_empty() {
+            bail!("empty API key in {:?}", spec);
+        }
+        let credits = match parts.next().filter(|p| !p.is_empty()) {
+            Some(credits) => Some(credits.parse().with_context(|| format!("credit budget {:?}", credits))?),
+            None => None,
+        };
+        let rate_limit = match parts.next().filter(|p| !p.is_empty()) {
+            Some(rps) => Some(rps.parse().with_context(|| format!("rate limit {:?}", rps))?),
+            None => None,
+        };
+        if parts.next().is_some() {
+            bail!("expected KEY[:CREDITS[:RPS]], got {:?}", spec);
+        }
+        Ok(Self { key, credits, rate_limit })
+    }
+}
+
+/// What an endpoint has spent so far
+#[derive(Debug, Clone, Serialize)]
+pub struct EndpointUsage {
+    pub endpoint: String,
+    pub requests: u64,
+    pub credits: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub budget: Option<u64>,
+}
+
+struct Endpoint {
+    url: String,
+    /// Shown in logs in place of the URL, which usually carries an API key
+    label: String,
+    down_until: Mutex<Option<Instant>>,
+    budget: Option<u64>,
+    rate_limit: Option<u32>,
+    requests: AtomicU64,
+    credits: AtomicU64,
+    /// Start of the current one-second window and the requests sent in it
+    window: Mutex<(Instant, u32)>,
+}
+
+impl Endpoint {
+    fn new(url: String, label: String, budget: Option<u64>, rate_limit: Option<u32>) -> Self {
+        Self {
+            url,
+            label,
+            down_until: Mutex::new(None),
+            budget,
+            rate_limit,
+            requests: AtomicU64::new(0),
+            credits: AtomicU64::new(0),
+            window: Mutex::new((Instant::now(), 0)),
+        }
+    }
+
+    fn is_up(&self, now: Instant) -> bool {
+        self.down_until.lock().unwrap().is_none_or(|until| until <= now)
+    }
+
+    /// Whether the budget still covers a call of `cost` credits
+    fn can_afford(&self, cost: u64) -> bool {
+        self.budget.is_none_or(|budget| self.credits.load(Ordering::Relaxed) + cost <= budget)
+    }
+
+    /// Whether this second's requests are close to the rate limit
+    fn is_busy(&self, now: Instant) -> bool {
+        let Some(limit) = self.rate_limit else { return false };
+        let (start, sent) = *self.window.lock().unwrap();
+        now.duration_since(start) < Duration::from_secs(1) && sent as f64 >= limit as f64 * RATE_HEADROOM
+    }
+
+    /// Counts a request of `cost` credits against the budget and rate window
+    fn charge(&self, cost: u64) {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        let spent = self.credits.fetch_add(cost, Ordering::Relaxed) + cost;
+        counter!("analyzer_rpc_credits_total", "endpoint" => self.label.clone()).increment(cost);
+        if self.budget.is_some_and(|budget| spent >= budget && spent - cost < budget) {
+            tracing::warn!(endpoint = %self.label, "credit budget spent, rotating to the other endpoints");
+        }
+
+        let now = Instant::now();
+        let mut window = self.window.lock().unwrap();
+        if now.duration_since(window.0) >= Duration::from_secs(1) {
+            *window = (now, 0);
+        }
+        window.1 += 1;
+    }
+
+    fn mark_down(&self, cooldown: Duration) {
+        *self.down_until.lock().unwrap() = Some(Instant::now() + cooldown);
+    }
+
+    fn mark_up(&self) {
+        *self.down_until.lock().unwrap() = None;
+    }
+
+    fn usage(&self) -> EndpointUsage {
+        EndpointUsage {
+            endpoint: self.label.clone(),
+            requests: self.requests.load(Ordering::Relaxed),
+            credits: self.credits.load(Ordering::Relaxed),
+            budget: self.budget,
+        }
+    }
+}
+
+/// Origin only; the path and query usually carry an API key
+fn origin(url: &str) -> String {
+    reqwest::Url::parse(url)
+        .map(|u| u.origin().ascii_serialization())
+        .unwrap_or_else(|_| "<invalid url>".to_string())
+}
+
+/// Outcome of one request against one endpoint
+enum Attempt {
+    Ok(Value),
+    /// Worth trying elsewhere (or later)
+    Transient { error: anyhow::Error, retry_after: Option<Duration> },
+    Fatal(anyhow::Error),
+}
+
+pub struct RpcPool {
+    client: Client,
+    endpoints: Vec<Endpoint>,
+    next: AtomicUsize,
+    retry: RetryPolicy,
+}
+
+impl RpcPool {
+    /// Rotates over `keys` (on `cluster`'s Helius URL) first, then `urls`
+    pub fn new(client: Client, urls: Vec<String>, keys: Vec<ApiKey>, cluster: Cluster, retry: RetryPolicy) -> Result<Self> {
+        if urls.is_empty() && keys.is_empty() {
+            bail!("at least one RPC endpoint is required");
+        }
+        let mut endpoints = Vec::new();
+        for (i, key) in keys.into_iter().enumerate() {
+            let url = key.url(cluster)?;
+            let label = format!("{} key {}", origin(&url), i + 1);
+            endpoints.push(Endpoint::new(url, label, key.credits, key.rate_limit));
+        }
+        endpoints.extend(urls.into_iter().map(|url| {
+            let label = origin(&url);
+            Endpoint::new(url, label, None, None)
+        }));
+        Ok(Self { client, endpoints, next: AtomicUsize::new(0), retry })
+    }
+
+    /// Requests and credits per endpoint since the pool was created
+    pub fn usage(&self) -> Vec<EndpointUsage> {
+        self.endpoints.iter().map(Endpoint::usage).collect()
+    }
+
+    /// Endpoint indices in the order a call of `cost` credits should try them:
+    /// healthy ones round-robin from the rotation point, those close to their
+    /// rate limit after them, then benched ones soonest-back first. Endpoints
+    /// whose budget can't cover the call are left out.
+    fn order(&self, cost: u64) -> Vec<usize> {
+        let n = self.endpoints.len();
+        let start = self.next.fetch_add(1, Ordering::Relaxed) % n;
+        let now = Instant::now();
+        let affordable = (0..n).map(|i| (start + i) % n).filter(|&i| self.endpoints[i].can_afford(cost));
+        let (up, mut down): (Vec<usize>, Vec<usize>) = affordable.partition(|&i| self.endpoints[i].is_up(now));
+        let (mut order, busy): (Vec<usize>, Vec<usize>) = up.into_iter().partition(|&i| !self.endpoints[i].is_busy(now));
+        down.sort_by_key(|&i| *self.endpoints[i].down_until.lock().unwrap());
+        order.extend(busy);
+        order.extend(down);
+        order
+    }
+
+    async fn attempt(&self, endpoint: &Endpoint, body: &Value) -> Attempt {
+        let response = match self.client.post(&endpoint.url).json(body).send().await {
+            Ok(response) => response,
+            Err(e) => return Attempt::Transient { error: e.without_url().into(), retry_after: None },
+        };
+
+        // Whatever got an answer other than "slow down" was billed
+        let status = response.status();
+        if status != StatusCode::TOO_MANY_REQUESTS {
+            endpoint.charge(cost::credits(body["method"].as_str().unwrap_or_default()));
+        }
+        if status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error() {
+            let retry_after = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .map(Duration::from_secs);
+            return Attempt::Transient { error: anyhow!("HTTP {}", status), retry_after };
+        }
+        if !status.is_success() {
+            return Attempt::Fatal(anyhow!("HTTP {}", status));
+        }
+
+        match response.json().await {
+            Ok(value) => Attempt::Ok(value),
+            Err(e) => Attempt::Transient { error: e.without_url().into(), retry_after: None },
+        }
+    }
+
+    /// Sends `body` to each endpoint in turn until one answers, retrying the
+    /// round per the [`RetryPolicy`]; returns the raw JSON-RPC response
+    pub async fn call(&self, body: &Value) -> Result<Value> {
+        let timeout = self.retry.call_timeout;
+        match tokio::time::timeout(timeout, self.call_with_retries(body)).await {
+            Ok(result) => result,
+            Err(_) => bail!("RPC {} timed out after {:?}", body["method"].as_str().unwrap_or("call"), timeout),
+        }
+    }
+
+    async fn call_with_retries(&self, body: &Value) -> Result<Value> {
+        let attempts = self.retry.max_attempts.max(1);
+        let mut retry = 0;
+        loop {
+            match self.call_once(body).await? {
+                Ok(value) => return Ok(value),
+                Err((error, _)) if retry + 1 >= attempts => {
+                    return Err(error.context(format!("all RPC endpoints failed after {} attempts", attempts)));
+                }
+                Err((error, retry_after)) => {
+                    let delay = self.retry.delay(retry, retry_after);
+                    tracing::warn!("rpc attempt {}/{} failed ({:#}), retrying in {:?}", retry + 1, attempts, error, delay);
+                    tokio::time::sleep(delay).await;
+                    retry += 1;
+                }
+            }
+        }
+    }
+
+    /// One pass over the endpoints. The outer error is fatal; the inner one
+    /// means every endpoint failed transiently, with the shortest `Retry-After`
+    /// any of them asked for.
+    async fn call_once(&self, body: &Value) -> Result<Result<Value, (anyhow::Error, Option<Duration>)>> {
+        let cost = cost::credits(body["method"].as_str().unwrap_or_default());
+        let order = self.order(cost);
+        if order.is_empty() {
+            bail!("every RPC endpoint has spent its credit budget");
+        }
+        let mut last_error = None;
+        let mut retry_after: Option<Duration> = None;
+        for i in order {
+            let endpoint = &self.endpoints[i];
+            match self.attempt(endpoint, body).await {
+                Attempt::Ok(value) => {
+                    endpoint.mark_up();
+                    return Ok(Ok(value));
+                }
+                Attempt::Transient { error, retry_after: asked } => {
+                    if self.endpoints.len() > 1 {
+                        tracing::warn!(endpoint = %endpoint.label, "rpc failed ({:#}), failing over", error);
+                    }
+                    endpoint.mark_down(asked.unwrap_or(DEFAULT_COOLDOWN));
+                    if let Some(asked) = asked {
+                        retry_after = Some(retry_after.map_or(asked, |r| r.min(asked)));
+                    }
+                    last_error = Some(error);
+                }
+                Attempt::Fatal(error) => return Err(error.context(endpoint.label.clone())),
+            }
+        }
+        Ok(Err((last_error.unwrap_or_else(|| anyhow!("no RPC endpoints")), retry_after)))
+    }
+
+    /// Probes every endpoint with `getHealth`, benching the ones that fail and
+    /// restoring the ones that recovered
+    pub async fn check_health(&self) {
+        let body = serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": "getHealth" });
+        for endpoint in &self.endpoints {
+            match self.attempt(endpoint, &body).await {
+                Attempt::Ok(response) if response.get("error").is_none() => endpoint.mark_up(),
+                Attempt::Ok(_) | Attempt::Fatal(_) => endpoint.mark_down(DEFAULT_COOLDOWN),
+                Attempt::Transient { retry_after, .. } => {
+                    endpoint.mark_down(retry_after.unwrap_or(DEFAULT_COOLDOWN))
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,231 @@
+//! Scoring config: per-detector on/off flags, weights and thresholds, read
+//! from a TOML file so the model can be tuned without recompiling. Anything
+//! left out keeps the detector's built-in default.
+//!
+//! ```toml
+//! [detectors.whale-concentration]
+//! weight = 0.3
+//! thresholds = { critical_threshold = 70.0 }
+//!
+//! [detectors.token-age]
+//! enabled = false
+//!
+//! [risk.thresholds]
+//! low = 75.0
+//!
+//! [risk.recommendations]
+//! critical = "Blocked ({score}/100)"
+//! ```
+//!
+//! `ANALYZER_DETECTOR_<ID>_<KEY>` environment variables override the file,
+//! with the id upper-cased and `-`/`/` turned into `_`:
+//! `ANALYZER_DETECTOR_WHALE_CONCENTRATION_WEIGHT=0.3`,
+//! `ANALYZER_DETECTOR_TOKEN_AGE_ENABLED=false`,
+//! `ANALYZER_DETECTOR_WHALE_CONCENTRATION_CRITICAL_THRESHOLD=70`.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Context, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::detectors::DetectorRegistry;
+
+/// Prefix of the environment variables overriding the file
+pub const ENV_PREFIX: &str = "ANALYZER_DETECTOR_";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScoringConfig {
+    /// Keyed by detector id (see `DetectorRegistry::ids`)
+    #[serde(default)]
+    pub detectors: BTreeMap<String, DetectorConfig>,
+    /// How the composite score maps to a risk level and recommendation
+    #[serde(default)]
+    pub risk: RiskConfig,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DetectorConfig {
+    /// `false` leaves the detector out of the score
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    /// Share of the composite score, relative to the other detectors' weights
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<f64>,
+    /// The detector's threshold fields; unset ones keep their defaults
+    #[serde(default, skip_serializing_if = "toml::Table::is_empty")]
+    pub thresholds: toml::Table,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RiskConfig {
+    pub thresholds: RiskThresholds,
+    pub recommendations: Recommendations,
+}
+
+/// Lowest score of each risk level; anything below `high` is `critical`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct RiskThresholds {
+    pub low: f64,
+    pub medium: f64,
+    pub high: f64,
+}
+
+impl Default for RiskThresholds {
+    fn default() -> Self {
+        Self { low: 70.0, medium: 50.0, high: 30.0 }
+    }
+}
+
+impl RiskThresholds {
+    /// `low`, `medium`, `high` or `critical`
+    pub fn level(&self, score: f64) -> &'static str {
+        if score >= self.low {
+            "low"
+        } else if score >= self.medium {
+            "medium"
+        } else if score >= self.high {
+            "high"
+        } else {
+            "critical"
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        let ordered = 0.0 <= self.high && self.high < self.medium && self.medium < self.low && self.low <= 100.0;
+        if !ordered {
+            bail!(
+                "risk thresholds must satisfy 0 <= high < medium < low <= 100 (got low {}, medium {}, high {})",
+                self.low,
+                self.medium,
+                self.high
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Recommendation per risk level; `{score}` is replaced with the score
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Recommendations {
+    pub low: String,
+    pub medium: String,
+    pub high: String,
+    pub critical: String,
+}
+
+impl Default for Recommendations {
+    fn default() -> Self {
+        Self {
+            low: "✅ SAFE - Token appears legitimate. Proceed with normal caution.".to_string(),
+            medium: "⚠️ MEDIUM RISK - Exercise caution. Consider smaller position size.".to_string(),
+            high: "🚨 HIGH RISK - Significant red flags detected. Avoid or use minimal amounts.".to_string(),
+            critical: "❌ CRITICAL DANGER - DO NOT USE THIS TOKEN. High probability of rug pull.".to_string(),
+        }
+    }
+}
+
+impl Recommendations {
+    /// The template for `risk_level`, filled in
+    pub fn render(&self, risk_level: &str, score: f64) -> String {
+        let template = match risk_level {
+            "low" => &self.low,
+            "medium" => &self.medium,
+            "high" => &self.high,
+            _ => &self.critical,
+        };
+        template.replace("{score}", &format!("{:.1}", score))
+    }
+
+    /// Sets one level's template from `<level>=<text>`
+    pub fn set(&mut self, spec: &str) -> Result<()> {
+        let (level, text) = spec
+            .split_once('=')
+            .ok_or_else(|| anyhow!("recommendation `{}` must look like <level>=<text>", spec))?;
+        let template = match level {
+            "low" => &mut self.low,
+            "medium" => &mut self.medium,
+            "high" => &mut self.high,
+            "critical" => &mut self.critical,
+            _ => bail!("unknown risk level `{}` (low, medium, high or critical)", level),
+        };
+        *template = text.to_string();
+        Ok(())
+    }
+}
+
+impl ScoringConfig {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        toml::from_str(&text).with_context(|| format!("parsing {}", path.display()))
+    }
+
+    /// This config layered over `base` (a model's preset): whatever is set
+    /// here wins, anything left unset falls through to `base`
+    pub fn over(mut self, base: ScoringConfig) -> Self {
+        for (id, base) in base.detectors {
+            let config = self.detectors.entry(id).or_default();
+            config.enabled = config.enabled.or(base.enabled);
+            config.weight = config.weight.or(base.weight);
+            for (key, value) in base.thresholds {
+                config.thresholds.entry(key).or_insert(value);
+            }
+        }
+        self
+    }
+
+    /// Applies `ANALYZER_DETECTOR_*` variables from the process environment
+    /// for the detectors in `registry`
+    pub fn with_env_overrides(mut self, registry: &DetectorRegistry) -> Result<Self> {
+        let ids: Vec<(String, String)> = registry.ids().into_iter().map(|id| (env_name(&id), id)).collect();
+
+        for (name, value) in std::env::vars() {
+            let Some(rest) = name.strip_prefix(ENV_PREFIX) else {
+                continue;
+            };
+            // Longest id first, so one id being a prefix of another can't misroute
+            let (key, id) = ids
+                .iter()
+                .filter_map(|(env_id, id)| Some((rest.strip_prefix(env_id.as_str())?.strip_prefix('_')?, id)))
+                .min_by_key(|(key, _)| key.len())
+                .ok_or_else(|| anyhow!("{} names no detector", name))?;
+
+            let config = self.detectors.entry(id.clone()).or_default();
+            match key.to_lowercase().as_str() {
+                "enabled" => config.enabled = Some(value.parse().with_context(|| format!("{} must be true or false", name))?),
+                "weight" => config.weight = Some(value.parse().with_context(|| format!("{} must be a number", name))?),
+                threshold => {
+                    config.thresholds.insert(threshold.to_string(), env_value(&value));
+                }
+            }
+        }
+        Ok(self)
+    }
+}
+
+/// `whale-concentration` -> `WHALE_CONCENTRATION`
+fn env_name(id: &str) -> String {
+    id.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+        .collect()
+}
+
+/// Typed the way a TOML literal would be, so integer fields accept "3" and
+/// float fields accept "3" or "2.5"
+fn env_value(value: &str) -> toml::Value {
+    if let Ok(integer) = value.parse::<i64>() {
+        toml::Value::Integer(integer)
+    } else if let Ok(float) = value.parse::<f64>() {
+        toml::Value::Float(float)
+    } else if let Ok(boolean) = value.parse::<bool>() {
+        toml::Value::Boolean(boolean)
+    } else {
+        toml::Value::String(value.to_string())
+    }
+}
@@ -0,0 +1,295 @@
+//! Honeypot sell simulation. Builds a small `swap_base_input` sell on the
+//! token's deepest Raydium CPMM pool, signed (unverified) by a real holder, and
+//! runs it through `simulateTransaction`. A sell that fails, or returns far
+//! less than the pool's constant-product quote, is what a honeypot looks like.
+
+use std::str::FromStr;
+
+use anyhow::{anyhow, Result};
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use solana_pubkey::Pubkey;
+
+use super::liquidity::PoolLiquidity;
+use super::patterns::HolderInfo;
+#[cfg(feature = "native")]
+use super::TokenAnalyzer;
+
+const CPMM_PROGRAM: &str = "CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C";
+const ATA_PROGRAM: &str = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL";
+const SYSTEM_PROGRAM: &str = "11111111111111111111111111111111";
+/// sha256("global:swap_base_input")[..8]
+const SWAP_BASE_INPUT: [u8; 8] = [143, 190, 90, 218, 196, 30, 51, 222];
+/// CPMM fee rates are parts per million
+const FEE_RATE_DENOMINATOR: u128 = 1_000_000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SellSimulation {
+    pub pool: String,
+    /// Raw token amount sold
+    pub amount_in: u64,
+    /// Raw quote amount the pool's curve promises, after its trade fee
+    pub expected_out: u64,
+    /// Raw quote amount received; `None` when the sell failed
+    pub received_out: Option<u64>,
+    /// Simulation error when the sell failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl SellSimulation {
+    /// Share of the expected proceeds lost beyond the pool fee, in percent
+    pub fn tax_percent(&self) -> Option<f64> {
+        let received = self.received_out?;
+        (self.expected_out > 0).then(|| (1.0 - received as f64 / self.expected_out as f64).max(0.0) * 100.0)
+    }
+}
+
+struct Meta {
+    key: Pubkey,
+    signer: bool,
+    writable: bool,
+}
+
+struct Instruction {
+    program: Pubkey,
+    accounts: Vec<Meta>,
+    data: Vec<u8>,
+}
+
+fn key(s: &str) -> Result<Pubkey> {
+    Pubkey::from_str(s).map_err(|e| anyhow!("invalid pubkey {}: {}", s, e))
+}
+
+fn key_at(data: &[u8], offset: usize) -> Result<Pubkey> {
+    data.get(offset..offset + 32)
+        .and_then(|b| Pubkey::try_from(b).ok())
+        .ok_or_else(|| anyhow!("account data too short"))
+}
+
+fn u64_at(data: &[u8], offset: usize) -> Result<u64> {
+    data.get(offset..offset + 8)
+        .and_then(|b| b.try_into().ok())
+        .map(u64::from_le_bytes)
+        .ok_or_else(|| anyhow!("account data too short"))
+}
+
+fn compact_u16(out: &mut Vec<u8>, mut n: usize) {
+    loop {
+        let mut byte = (n & 0x7f) as u8;
+        n >>= 7;
+        if n != 0 {
+            byte |= 0x80;
+        }
+        out.push(byte);
+        if n == 0 {
+            break;
+        }
+    }
+}
+
+/// Serialized legacy transaction with a zeroed signature and blockhash, for
+/// `simulateTransaction` with `sigVerify: false, replaceRecentBlockhash: true`
+fn legacy_transaction(payer: Pubkey, instructions: &[Instruction]) -> Vec<u8> {
+    // Unique keys, payer first, then by (signer, writable) class as the runtime expects
+    let mut keys: Vec<(Pubkey, bool, bool)> = vec![(payer, true, true)];
+    let mut upsert = |key: Pubkey, signer: bool, writable: bool| match keys.iter_mut().find(|(k, _, _)| *k == key) {
+        Some(entry) => {
+            entry.1 |= signer;
+            entry.2 |= writable;
+        }
+        None => keys.push((key, signer, writable)),
+    };
+    for ix in instructions {
+        for meta in &ix.accounts {
+            upsert(meta.key, meta.signer, meta.writable);
+        }
+        upsert(ix.program, false, false);
+    }
+    keys[1..].sort_by_key(|(_, signer, writable)| (!signer, !writable));
+
+    let signers = keys.iter().filter(|(_, s, _)| *s).count();
+    let readonly_signed = keys.iter().filter(|(_, s, w)| *s && !*w).count();
+    let readonly_unsigned = keys.iter().filter(|(_, s, w)| !*s && !*w).count();
+    let index = |key: &Pubkey| keys.iter().position(|(k, _, _)| k == key).unwrap() as u8;
+
+    let mut message = vec![signers as u8, readonly_signed as u8, readonly_unsigned as u8];
+    compact_u16(&mut message, keys.len());
+    for (key, _, _) in &keys {
+        message.extend_from_slice(key.as_ref());
+    }
+    message.extend_from_slice(&[0; 32]); // blockhash, replaced by the node
+    compact_u16(&mut message, instructions.len());
+    for ix in instructions {
+        message.push(index(&ix.program));
+        compact_u16(&mut message, ix.accounts.len());
+        message.extend(ix.accounts.iter().map(|m| index(&m.key)));
+        compact_u16(&mut message, ix.data.len());
+        message.extend_from_slice(&ix.data);
+    }
+
+    let mut tx = Vec::with_capacity(1 + 64 * signers + message.len());
+    compact_u16(&mut tx, signers);
+    tx.resize(tx.len() + 64 * signers, 0);
+    tx.extend(message);
+    tx
+}
+
+fn associated_token_address(owner: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Result<Pubkey> {
+    let ata_program = key(ATA_PROGRAM)?;
+    Ok(Pubkey::find_program_address(&[owner.as_ref(), token_program.as_ref(), mint.as_ref()], &ata_program).0)
+}
+
+fn raw_amount(account: &Value) -> Option<u64> {
+    account["data"]["parsed"]["info"]["tokenAmount"]["amount"].as_str()?.parse().ok()
+}
+
+/// Simulation errors that come from our setup rather than from the token
+fn is_environmental(err: &Value) -> bool {
+    let text = err.to_string();
+    ["InsufficientFundsForFee", "AccountNotFound", "InsufficientFundsForRent", "BlockhashNotFound"]
+        .iter()
+        .any(|e| text.contains(e))
+}
+
+#[cfg(feature = "native")]
+impl TokenAnalyzer {
+    /// Sells a sliver of a top holder's balance into the deepest CPMM pool.
+    /// `None` when there is no CPMM pool or no wallet to sell from; errors
+    /// (including ones caused by the seller, like missing fee SOL) are
+    /// inconclusive rather than evidence of a honeypot.
+    pub(super) async fn simulate_sell(
+        &self,
+        mint: &str,
+        pools: &[PoolLiquidity],
+        holders: &[HolderInfo],
+    ) -> Result<Option<SellSimulation>> {
+        let Some(pool) = pools
+            .iter()
+            .filter(|p| p.dex == "raydium-cpmm")
+            .max_by(|a, b| a.quote_reserve.total_cmp(&b.quote_reserve))
+        else {
+            return Ok(None);
+        };
+
+        let state = self.rpc_call(mint, "getAccountInfo", json!([pool.address, { "encoding": "base64" }])).await?;
+        let data = base64::engine::general_purpose::STANDARD.decode(
+            state["value"]["data"][0].as_str().ok_or_else(|| anyhow!("pool account not found"))?,
+        )?;
+        let mint_key = key(mint)?;
+        let (mint_0, mint_1) = (key_at(&data, 168)?, key_at(&data, 200)?);
+        let token_is_0 = mint_0 == mint_key;
+        let side = |zero: usize, one: usize| key_at(&data, if token_is_0 { zero } else { one });
+        let amm_config = key_at(&data, 8)?;
+        let (input_vault, output_vault) = (side(72, 104)?, side(104, 72)?);
+        let (input_program, output_program) = (side(232, 264)?, side(264, 232)?);
+        let output_mint = if token_is_0 { mint_1 } else { mint_0 };
+        let observation = key_at(&data, 296)?;
+
+        let config = self.rpc_call(mint, "getAccountInfo", json!([amm_config.to_string(), { "encoding": "base64" }])).await?;
+        let config = base64::engine::general_purpose::STANDARD.decode(
+            config["value"]["data"][0].as_str().ok_or_else(|| anyhow!("amm config not found"))?,
+        )?;
+        let trade_fee_rate = u64_at(&config, 12)? as u128;
+
+        // Seller: the largest holder that's a wallet, not the pool or another program
+        let holder_accounts: Vec<&str> = holders.iter().map(|h| h.address.as_str()).collect();
+        let holder_states = self.fetch_accounts(mint, &holder_accounts, "jsonParsed").await?;
+        let seller = holder_accounts.iter().zip(&holder_states).find_map(|(address, state)| {
+            let owner = key(state["data"]["parsed"]["info"]["owner"].as_str()?).ok()?;
+            let amount = raw_amount(state)?;
+            (owner.is_on_curve() && amount > 0 && *address != input_vault.to_string())
+                .then_some((key(address).ok()?, owner, amount))
+        });
+        let Some((seller_account, seller, seller_balance)) = seller else {
+            return Ok(None);
+        };
+        let output_account = associated_token_address(&seller, &output_mint, &output_program)?;
+
+        let vaults = [input_vault.to_string(), output_vault.to_string(), output_account.to_string()];
+        let vault_refs: Vec<&str> = vaults.iter().map(String::as_str).collect();
+        let pre = self.fetch_accounts(mint, &vault_refs, "jsonParsed").await?;
+        let (reserve_in, reserve_out) = (
+            raw_amount(&pre[0]).ok_or_else(|| anyhow!("input vault unreadable"))? as u128,
+            raw_amount(&pre[1]).ok_or_else(|| anyhow!("output vault unreadable"))? as u128,
+        );
+        let pre_out = raw_amount(&pre[2]).unwrap_or(0);
+
+        // Small enough to leave the price mostly alone: 0.1% of the pool side
+        let amount_in = seller_balance.min((reserve_in / 1000).max(1) as u64);
+        let net_in = amount_in as u128 * (FEE_RATE_DENOMINATOR - trade_fee_rate) / FEE_RATE_DENOMINATOR;
+        let expected_out = (reserve_out * net_in / (reserve_in + net_in)) as u64;
+
+        let cpmm = key(self.cluster.program(CPMM_PROGRAM))?;
+        let authority = Pubkey::find_program_address(&[b"vault_and_lp_mint_auth_seed"], &cpmm).0;
+        let meta = |key: Pubkey, writable: bool| Meta { key, signer: false, writable };
+        let mut swap_data = SWAP_BASE_INPUT.to_vec();
+        swap_data.extend_from_slice(&amount_in.to_le_bytes());
+        swap_data.extend_from_slice(&0u64.to_le_bytes()); // minimum_amount_out
+
+        let instructions = [
+            Instruction {
+                program: key(ATA_PROGRAM)?,
+                accounts: vec![
+                    Meta { key: seller, signer: true, writable: true },
+                    meta(output_account, true),
+                    meta(seller, false),
+                    meta(output_mint, false),
+                    meta(key(SYSTEM_PROGRAM)?, false),
+                    meta(output_program, false),
+                ],
+                data: vec![1], // CreateIdempotent
+            },
+            Instruction {
+                program: cpmm,
+                accounts: vec![
+                    Meta { key: seller, signer: true, writable: true },
+                    meta(authority, false),
+                    meta(amm_config, false),
+                    meta(key(&pool.address)?, true),
+                    meta(seller_account, true),
+                    meta(output_account, true),
+                    meta(input_vault, true),
+                    meta(output_vault, true),
+                    meta(input_program, false),
+                    meta(output_program, false),
+                    meta(mint_key, false),
+                    meta(output_mint, false),
+                    meta(observation, true),
+                ],
+                data: swap_data,
+            },
+        ];
+        let tx = base64::engine::general_purpose::STANDARD.encode(legacy_transaction(seller, &instructions));
+
+        let result = self
+            .rpc_call(mint, "simulateTransaction", json!([tx, {
+                "encoding": "base64",
+                "sigVerify": false,
+                "replaceRecentBlockhash": true,
+                "commitment": "processed",
+                "accounts": { "encoding": "jsonParsed", "addresses": [output_account.to_string()] },
+            }]))
+            .await?;
+        let value = &result["value"];
+
+        let mut simulation = SellSimulation {
+            pool: pool.address.clone(),
+            amount_in,
+            expected_out,
+            received_out: None,
+            error: None,
+        };
+        if !value["err"].is_null() {
+            if is_environmental(&value["err"]) {
+                return Err(anyhow!("simulation inconclusive: {}", value["err"]));
+            }
+            simulation.error = Some(value["err"].to_string());
+            return Ok(Some(simulation));
+        }
+        let post_out = raw_amount(&value["accounts"][0]).ok_or_else(|| anyhow!("simulation returned no output account"))?;
+        simulation.received_out = Some(post_out.saturating_sub(pre_out));
+        Ok(Some(simulation))
+    }
+}
@@ -0,0 +1,108 @@
+//! Trade classification. Each recent signature is fetched as a `jsonParsed`
+//! transaction and labelled from the fee payer's point of view: its balance of
+//! the token went up through a DEX (buy), down through a DEX (sell), or moved
+//! without one (transfer). The fee payer's SOL change, net of the fee, sizes
+//! the trade.
+
+use std::collections::HashMap;
+
+use futures::{stream, StreamExt};
+use serde_json::Value;
+
+use super::cluster::Cluster;
+use super::deployer::{fee_payer, instructions};
+use super::freeze::{freeze_events, FreezeEvent};
+use super::patterns::TransactionInfo;
+use super::TokenAnalyzer;
+
+/// Transactions fetched in full per analysis, newest first
+pub(super) const CLASSIFIED_TRANSACTIONS: usize = 100;
+
+/// AMMs, launchpads and aggregators a swap goes through
+pub const DEX_PROGRAMS: &[&str] = &[
+    "675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8", // Raydium AMM v4
+    "CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C", // Raydium CPMM
+    "CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK", // Raydium CLMM
+    "LanMV9sAd7wArD4vJFi2qDdfnVhFxYSUg6eADduJ3uj", // Raydium LaunchLab
+    "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc", // Orca Whirlpool
+    "LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo", // Meteora DLMM
+    "Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB", // Meteora pools
+    "6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P", // pump.fun
+    "pAMMBay6oceH9fJKBRHGP5D4bD4sWpmSwMn52FMfXEA", // PumpSwap
+    "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4", // Jupiter v6
+];
+
+impl TokenAnalyzer {
+    /// Fills in `tx_type`, `wallet` and amounts; transactions that can't be
+    /// fetched stay "unknown". Returns the freezes and thaws among them.
+    pub(super) async fn classify_transactions(&self, mint: &str, transactions: &mut [TransactionInfo]) -> Vec<FreezeEvent> {
+        // `transactions` is oldest first; the newest are the ones classified
+        let skip = transactions.len().saturating_sub(CLASSIFIED_TRANSACTIONS);
+        let lookups: Vec<_> = transactions[skip..]
+            .iter()
+            .map(|t| self.fetch_transaction(mint, &t.signature))
+            .collect();
+        let fetched: Vec<_> = stream::iter(lookups).buffered(10).collect().await;
+
+        let mut freezes = Vec::new();
+        for (info, tx) in transactions[skip..].iter_mut().zip(fetched) {
+            match tx {
+                Ok(tx) => {
+                    classify(&tx, mint, info);
+                    freezes.extend(freeze_events(&tx, mint, &info.signature, info.timestamp));
+                }
+                Err(e) => tracing::warn!(signature = %info.signature, "transaction unclassified: {:#}", e),
+            }
+        }
+        freezes
+    }
+}
+
+pub(super) fn classify(tx: &Value, mint: &str, info: &mut TransactionInfo) {
+    let Some(wallet) = fee_payer(tx) else {
+        return;
+    };
+    let meta = &tx["meta"];
+    if !meta["err"].is_null() {
+        info.wallet = Some(wallet);
+        return;
+    }
+
+    let token_change = token_changes(tx, mint).get(&wallet).copied().unwrap_or(0.0);
+    let lamports = |key: &str| meta[key][0].as_i64().unwrap_or(0);
+    let sol_change = (lamports("postBalances") - lamports("preBalances") + meta["fee"].as_i64().unwrap_or(0)) as f64
+        / 1_000_000_000.0;
+    info.tx_type = match (token_change, is_swap(tx)) {
+        (c, true) if c > 0.0 => "buy",
+        (c, true) if c < 0.0 => "sell",
+        (c, false) if c != 0.0 => "transfer",
+        _ => "unknown",
+    }
+    .to_string();
+    info.wallet = Some(wallet);
+    info.token_amount = token_change.abs();
+    info.sol_amount = sol_change.abs();
+}
+
+/// Whether the transaction goes through any of `DEX_PROGRAMS`, at its mainnet
+/// or devnet address
+pub(super) fn is_swap(tx: &Value) -> bool {
+    instructions(tx)
+        .filter_map(|ix| ix["programId"].as_str())
+        .any(|program| DEX_PROGRAMS.iter().any(|&dex| program == dex || program == Cluster::Devnet.program(dex)))
+}
+
+/// Change in each owner's UI balance of `mint` over the transaction
+pub(super) fn token_changes(tx: &Value, mint: &str) -> HashMap<String, f64> {
+    let mut changes: HashMap<String, f64> = HashMap::new();
+    for (key, sign) in [("preTokenBalances", -1.0), ("postTokenBalances", 1.0)] {
+        for balance in tx["meta"][key].as_array().into_iter().flatten().filter(|b| b["mint"] == mint) {
+            let owner = balance["owner"].as_str();
+            let amount = balance["uiTokenAmount"]["uiAmountString"].as_str().and_then(|a| a.parse::<f64>().ok());
+            if let (Some(owner), Some(amount)) = (owner, amount) {
+                *changes.entry(owner.to_string()).or_default() += sign * amount;
+            }
+        }
+    }
+    changes
+}
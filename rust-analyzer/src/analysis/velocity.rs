@@ -0,0 +1,107 @@
+//! Transaction velocity: recent transactions counted per minute and per hour,
+//! ending at the analysis time. The series feed the velocity spike and dead
+//! token detectors and are reported for charting.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::patterns::TransactionInfo;
+
+/// Per-minute buckets kept, the last hour
+pub const MINUTE_BUCKETS: usize = 60;
+/// Per-hour buckets kept, the last two days
+pub const HOUR_BUCKETS: usize = 48;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct VelocityBucket {
+    /// Unix time the bucket starts at
+    pub start: i64,
+    pub transactions: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Velocity {
+    /// Oldest and newest fetched transaction. Buckets before `first_seen` are
+    /// left out: quiet and not fetched can't be told apart there.
+    pub first_seen: i64,
+    pub last_seen: i64,
+    /// Oldest first, empty buckets included
+    pub per_minute: Vec<VelocityBucket>,
+    pub per_hour: Vec<VelocityBucket>,
+}
+
+impl Velocity {
+    /// `None` without any timestamped transaction
+    pub fn new(transactions: &[TransactionInfo], now: i64) -> Option<Self> {
+        let timestamps: Vec<i64> = transactions.iter().map(|tx| tx.timestamp).filter(|&t| t > 0 && t <= now).collect();
+        let first_seen = *timestamps.iter().min()?;
+        let last_seen = *timestamps.iter().max()?;
+        Some(Self {
+            first_seen,
+            last_seen,
+            per_minute: buckets(&timestamps, first_seen, now, 60, MINUTE_BUCKETS),
+            per_hour: buckets(&timestamps, first_seen, now, 3600, HOUR_BUCKETS),
+        })
+    }
+
+    /// Seconds between the newest transaction and `now`
+    pub fn idle_seconds(&self, now: i64) -> i64 {
+        now - self.last_seen
+    }
+
+    /// The finest series holding at least `min_transactions`, with its bucket
+    /// unit: per minute for busy tokens, per hour for slow ones
+    pub fn series(&self, min_transactions: usize) -> Option<(&'static str, &[VelocityBucket])> {
+        [("minute", &self.per_minute), ("hour", &self.per_hour)]
+            .into_iter()
+            .find(|(_, series)| total(series) >= min_transactions)
+            .map(|(unit, series)| (unit, series.as_slice()))
+    }
+}
+
+/// Transactions across `series`
+pub fn total(series: &[VelocityBucket]) -> usize {
+    series.iter().map(|b| b.transactions).sum()
+}
+
+/// Busiest bucket over the series mean: 1 for steady activity, up to the
+/// bucket count when everything happened at once
+pub fn spike_ratio(series: &[VelocityBucket]) -> f64 {
+    let peak = series.iter().map(|b| b.transactions).max().unwrap_or(0);
+    if peak == 0 {
+        return 0.0;
+    }
+    peak as f64 / (total(series) as f64 / series.len() as f64)
+}
+
+/// Rate over the newest `recent` buckets relative to the buckets before them;
+/// `None` when either side is missing or the earlier side saw nothing
+pub fn decay_ratio(series: &[VelocityBucket], recent: usize) -> Option<f64> {
+    if recent == 0 || series.len() <= recent {
+        return None;
+    }
+    let (earlier, latest) = series.split_at(series.len() - recent);
+    let earlier_rate = total(earlier) as f64 / earlier.len() as f64;
+    if earlier_rate == 0.0 {
+        return None;
+    }
+    Some(total(latest) as f64 / latest.len() as f64 / earlier_rate)
+}
+
+/// Up to `count` buckets of `width` seconds ending at `now`, back to the one
+/// holding `first_seen`. Aligned to `now` rather than the clock, so the newest
+/// bucket is as long as the others.
+fn buckets(timestamps: &[i64], first_seen: i64, now: i64, width: i64, count: usize) -> Vec<VelocityBucket> {
+    let span = (now - first_seen + width - 1) / width;
+    let len = (span.max(1) as usize).min(count);
+    let first = now - len as i64 * width;
+    let mut series: Vec<VelocityBucket> = (0..len)
+        .map(|i| VelocityBucket { start: first + i as i64 * width, transactions: 0 })
+        .collect();
+    for &timestamp in timestamps.iter().filter(|&&t| t >= first) {
+        // A transaction at `now` itself closes the newest bucket
+        let index = (((timestamp - first) / width) as usize).min(len - 1);
+        series[index].transactions += 1;
+    }
+    series
+}
@@ -0,0 +1,88 @@
+//! Token safety analysis for Missout, usable in-process. The `analyze-token`
+//! binary is a thin CLI over this crate; the indexer, crank bot and API server
+//! can link it instead of spawning the binary per mint.
+//!
+//! ```no_run
+//! use token_analyzer::{AnalyzerConfig, TokenAnalyzer};
+//!
+//! # async fn run() -> anyhow::Result<()> {
+//! let analyzer = TokenAnalyzer::with_config(AnalyzerConfig::default())?;
+//! let analysis = analyzer.analyze("<MINT>").await?;
+//! println!("{} ({})", analysis.safe_score, analysis.risk_level);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Custom detectors plug in through [`DetectorRegistry`]:
+//!
+//! ```no_run
+//! use token_analyzer::{AnalyzerConfig, DetectorRegistry, PatternDetector, PatternSignal, TokenAnalyzer, TokenContext};
+//!
+//! struct Blocklist;
+//!
+//! impl PatternDetector for Blocklist {
+//!     fn name(&self) -> &str {
+//!         "Blocklist"
+//!     }
+//!
+//!     fn weight(&self) -> f64 {
+//!         0.30
+//!     }
+//!
+//!     fn detect(&self, ctx: &TokenContext) -> PatternSignal {
+//!         let blocked = ctx.mint.ends_with("pump");
+//!         PatternSignal {
+//!             name: self.name().to_string(),
+//!             score: if blocked { 0.0 } else { 1.0 },
+//!             confidence: 1.0,
+//!             details: if blocked { "CRITICAL: blocklisted" } else { "SAFE: not blocklisted" }.to_string(),
+//!             weight: self.weight(),
+//!             evidence: Default::default(),
+//!         }
+//!     }
+//! }
+//!
+//! # async fn run() -> anyhow::Result<()> {
+//! let mut registry = DetectorRegistry::default();
+//! registry.register(Blocklist);
+//! registry.deregister("token-age");
+//! let analyzer = TokenAnalyzer::with_registry(AnalyzerConfig::default(), registry)?;
+//! let analysis = analyzer.analyze("<MINT>").await?;
+//! # Ok(())
+//! # }
+//! ```
+
+// Without `native` the RPC decoding helpers are only half reachable (their
+// callers live on `TokenAnalyzer`); they stay compiled rather than gated one by one
+#![cfg_attr(not(feature = "native"), allow(dead_code, unused_imports))]
+
+#[cfg(feature = "native")]
+pub mod alerts;
+pub mod analysis;
+pub mod report;
+#[cfg(feature = "native")]
+pub mod server;
+#[cfg(target_arch = "wasm32")]
+mod wasm;
+
+pub use analysis::detectors::DetectorRegistry;
+pub use analysis::market::MarketData;
+pub use analysis::model::Model;
+pub use analysis::patterns::{PatternDetector, PatternSignal, TokenContext};
+pub use analysis::pool::UnsafeMint;
+pub use analysis::scoring::ScoringConfig;
+pub use analysis::{score, AnalysisOutput, SafetyAnalysis, SafetyMetrics};
+
+#[cfg(feature = "native")]
+pub use analysis::market::{MarketDataProvider, MarketDataSource};
+#[cfg(feature = "native")]
+pub use analysis::pool::assert_safe_for_pool;
+#[cfg(feature = "native")]
+pub use analysis::cluster::Cluster;
+#[cfg(feature = "native")]
+pub use analysis::rpc::{ApiKey, RetryPolicy};
+#[cfg(feature = "native")]
+pub use analysis::{
+    AnalysisTimeout, AnalyzerConfig, CacheConfig, TokenAnalyzer, DEFAULT_ANALYSIS_TIMEOUT, DEFAULT_RPC_URL,
+    DEFAULT_TX_DEPTH,
+};
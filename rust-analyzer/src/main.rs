@@ -1,49 +1,1101 @@
-use anyhow::Result;
-use serde::Serialize;
+use std::io::IsTerminal;
+use std::net::SocketAddr;
+use std::num::NonZeroU32;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
 
-mod analysis;
-use analysis::SafetyAnalysis;
-use analysis::TokenAnalyzer;
+use anyhow::{bail, Context, Result};
+use clap::{Parser, Subcommand, ValueEnum};
+use futures::{stream, StreamExt};
+use indicatif::{ProgressBar, ProgressStyle};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWriteExt, BufReader};
+use tracing_subscriber::filter::{EnvFilter, LevelFilter};
+use tracing_subscriber::fmt::format::FmtSpan;
 
-#[derive(Debug, Serialize)]
-struct AnalysisOutput {
-    success: bool,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    data: Option<SafetyAnalysis>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    error: Option<String>,
+use token_analyzer::analysis::backtest::{self, BacktestReport, Sample};
+use token_analyzer::analysis::batch::{BatchEvent, BatchTally, PROGRESS_INTERVAL_SECS};
+use token_analyzer::analysis::changes::{holder_changes, ChangeThresholds};
+use token_analyzer::analysis::diff::{AnalysisDiff, Baseline};
+use token_analyzer::analysis::{self, cache::RpcCache, fixture, history::ScoreHistory, pool};
+use token_analyzer::alerts::{AlertSink, Alerter};
+use token_analyzer::{
+    report, server, AnalysisOutput, ApiKey, Cluster, AnalyzerConfig, CacheConfig, DetectorRegistry, MarketDataSource, RetryPolicy,
+    Model, SafetyAnalysis, ScoringConfig, TokenAnalyzer, DEFAULT_ANALYSIS_TIMEOUT, DEFAULT_RPC_URL, DEFAULT_TX_DEPTH,
+};
+
+#[derive(Parser)]
+#[command(name = "analyze-token", version, about = "Token safety analysis for Missout")]
+struct Cli {
+    /// Solana RPC endpoint (Helius recommended); repeat or comma-separate for failover
+    #[arg(
+        long,
+        env = "SOLANA_RPC_URL",
+        default_value = DEFAULT_RPC_URL,
+        value_delimiter = ',',
+        global = true,
+        hide_env_values = true
+    )]
+    rpc_url: Vec<String>,
+
+    /// Cluster the mints live on: picks the default `--rpc-url`, the Helius
+    /// URL of `--helius-key`, program ids and the bundled known addresses.
+    /// `custom` keeps mainnet's addresses and needs `--rpc-url`.
+    #[arg(long, env = "ANALYZER_CLUSTER", default_value_t = Cluster::default(), global = true)]
+    cluster: Cluster,
+
+    /// Helius API key for the rotation: `KEY`, `KEY:CREDITS` or `KEY:CREDITS:RPS`
+    /// to cap the credits it spends and the requests per second it's sent;
+    /// repeat or comma-separate for a pool. Replaces the placeholder default
+    /// `--rpc-url`.
+    #[arg(long = "helius-key", env = "HELIUS_API_KEYS", value_delimiter = ',', global = true, hide_env_values = true)]
+    helius_keys: Vec<ApiKey>,
+
+    /// Print the RPC calls and Helius credits one analysis is expected to take
+    /// under the other options, without analyzing anything
+    #[arg(long, global = true)]
+    dry_run_cost: bool,
+
+    /// Print the JSON Schema of the analysis output and exit
+    #[arg(long)]
+    schema: bool,
+
+    #[arg(long, value_enum, default_value_t = OutputFormat::Json, global = true)]
+    format: OutputFormat,
+
+    /// Log verbosity on stderr: a level (`debug` also logs RPC call and
+    /// detector spans with their timings) or a full `tracing` filter
+    #[arg(long, env = "ANALYZER_LOG", default_value = "info", global = true)]
+    log_level: String,
+
+    #[arg(long, value_enum, env = "ANALYZER_LOG_FORMAT", default_value_t = LogFormat::Text, global = true)]
+    log_format: LogFormat,
+
+    /// Per-request RPC timeout in seconds
+    #[arg(long, default_value_t = 30, global = true)]
+    timeout: u64,
+
+    /// Seconds one RPC call may take over all its retries and failovers
+    #[arg(long, default_value_t = 60, global = true, value_parser = clap::value_parser!(u64).range(1..))]
+    rpc_timeout: u64,
+
+    /// Seconds a whole analysis may take before it fails; 0 for no limit
+    #[arg(long, default_value_t = DEFAULT_ANALYSIS_TIMEOUT.as_secs(), global = true)]
+    analysis_timeout: u64,
+
+    /// Passes over the RPC endpoints before an RPC call fails
+    #[arg(long, default_value_t = 3, global = true, value_parser = clap::value_parser!(u32).range(1..))]
+    max_attempts: u32,
+
+    /// First retry delay in milliseconds, doubled per retry (with jitter)
+    #[arg(long, default_value_t = 250, global = true)]
+    backoff_ms: u64,
+
+    /// Longest single retry delay in milliseconds, `Retry-After` included
+    #[arg(long, default_value_t = 10_000, global = true)]
+    max_backoff_ms: u64,
+
+    /// Recent signatures sampled for the activity detectors (pages of 1000)
+    #[arg(long, default_value_t = DEFAULT_TX_DEPTH, global = true, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+    tx_depth: usize,
+
+    /// Report what could be fetched when an RPC fetch fails: the detectors
+    /// missing data report none, and `data_completeness` lists what was lost
+    #[arg(long, global = true)]
+    partial: bool,
+
+    /// Comma-separated detector ids to run (default: all)
+    #[arg(long, value_delimiter = ',', global = true)]
+    detectors: Option<Vec<String>>,
+
+    /// Scoring model version: a bundled preset of every weight and threshold,
+    /// so scores stay comparable with earlier runs of the same version
+    #[arg(long, env = "ANALYZER_MODEL", default_value_t = Model::default(), global = true)]
+    model: Model,
+
+    /// ONNX model reporting a learned rug probability next to the score
+    /// (needs the ONNX Runtime library, see `ORT_DYLIB_PATH`)
+    #[cfg(feature = "ml")]
+    #[arg(long, env = "ANALYZER_ML_MODEL", global = true)]
+    ml_model: Option<PathBuf>,
+
+    /// TOML scoring config: per-detector `enabled`, `weight` and `thresholds`
+    /// (`ANALYZER_DETECTOR_<ID>_<KEY>` variables override it)
+    #[arg(long, env = "ANALYZER_CONFIG", global = true)]
+    config: Option<PathBuf>,
+
+    /// Lowest score rated low risk (default 70; overrides `[risk.thresholds]`)
+    #[arg(long, global = true)]
+    risk_low: Option<f64>,
+
+    /// Lowest score rated medium risk (default 50)
+    #[arg(long, global = true)]
+    risk_medium: Option<f64>,
+
+    /// Lowest score rated high risk (default 30); anything below is critical
+    #[arg(long, global = true)]
+    risk_high: Option<f64>,
+
+    /// Recommendation text for a risk level, repeatable: `<level>=<text>`,
+    /// where `{score}` in the text is replaced with the score
+    #[arg(long = "recommendation", global = true)]
+    recommendations: Vec<String>,
+
+    /// Always query the RPC, bypassing the response cache
+    #[arg(long, global = true)]
+    no_cache: bool,
+
+    /// Read mints through plain RPC only, never Helius DAS `getAsset`
+    #[arg(long, global = true)]
+    no_das: bool,
+
+    /// Seconds a cached RPC response stays valid
+    #[arg(long, default_value_t = 300, global = true)]
+    cache_ttl: u64,
+
+    /// Cache database (default: ~/.cache/analyze-token/cache.sqlite3)
+    #[arg(long, env = "ANALYZER_CACHE_PATH", global = true)]
+    cache_path: Option<PathBuf>,
+
+    /// Don't record results or report trends against earlier runs
+    #[arg(long, global = true)]
+    no_history: bool,
+
+    /// Score history database (default: ~/.local/share/analyze-token/history.sqlite3)
+    #[arg(long, env = "ANALYZER_HISTORY_PATH", global = true)]
+    history_path: Option<PathBuf>,
+
+    /// JSON list of `{"address", "kind", "label"}` to exclude from holder
+    /// concentration, on top of the bundled registry
+    #[arg(long, env = "ANALYZER_KNOWN_ADDRESSES", global = true)]
+    known_addresses: Option<PathBuf>,
+
+    /// JSON list of `{"address", "reason"}` mints that skip the launch walk,
+    /// deployer history, holder funding and sell simulation
+    #[arg(long, env = "ANALYZER_ALLOW_LIST", global = true)]
+    allow_list: Option<PathBuf>,
+
+    /// JSON list of `{"address", "reason"}` mints and deployer wallets rated
+    /// critical at score 0
+    #[arg(long, env = "ANALYZER_DENY_LIST", global = true)]
+    deny_list: Option<PathBuf>,
+
+    /// Analyze the bundled stablecoins and bluechips (USDC, USDT, SOL, JUP...)
+    /// like any other mint instead of rating them outright
+    #[arg(long, global = true)]
+    no_known_good: bool,
+
+    /// Where `batch` and `watch` send alerts, repeatable: `discord=<url>`,
+    /// `slack=<url>`, `telegram=<bot token>/<chat id>` or `webhook=<url>`
+    #[arg(long = "alert", env = "ANALYZER_ALERTS", value_delimiter = ',', global = true, hide_env_values = true)]
+    alerts: Vec<AlertSink>,
+
+    /// Alert on scores below this (CRITICAL findings always alert)
+    #[arg(long, global = true)]
+    alert_below: Option<f64>,
+
+    /// Enrich the analysis with price, volume and market cap
+    #[arg(long, value_enum, global = true)]
+    market_data: Option<MarketProvider>,
+
+    /// API key for `--market-data birdeye`
+    #[arg(long, env = "BIRDEYE_API_KEY", global = true, hide_env_values = true)]
+    birdeye_api_key: Option<String>,
+
+    /// Exit with the risk level's code (see `Gate`) when a score is below this
+    #[arg(long, global = true)]
+    min_score: Option<f64>,
+
+    /// Exit with the critical code (13) when a result is rated critical
+    #[arg(long, global = true)]
+    fail_on_critical: bool,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Shorthand for `analyze <MINT>`
+    mint: Option<String>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Analyze a single mint
+    Analyze { mint: String },
+    /// Analyze a Missout pool: its mint's safety plus pool-level red flags
+    AnalyzePool {
+        pool: String,
+        /// Total fee (dev + burn + treasury) above which the pool is flagged
+        #[arg(long, default_value_t = pool::DEFAULT_MAX_FEE_BPS, value_parser = clap::value_parser!(u16).range(..=10_000))]
+        max_fee_bps: u16,
+    },
+    /// Analyze mints read from a file (or stdin), one per line, emitting NDJSON
+    /// (or CSV rows / reports per `--format`); progress and a closing summary
+    /// go to stderr
+    Batch {
+        /// Input file; `-` or omitted reads stdin
+        input: Option<PathBuf>,
+        /// Analyses in flight at once
+        #[arg(long, short = 'j', default_value_t = 8, value_parser = clap::value_parser!(u16).range(1..))]
+        jobs: u16,
+        /// No progress bar or `progress` events on stderr; the closing summary
+        /// is still printed
+        #[arg(long)]
+        no_progress: bool,
+    },
+    /// Serve `POST /analyze`, `GET /health` and `GET /metrics` over HTTP
+    Serve {
+        #[arg(long, env = "ANALYZER_ADDR", default_value = "127.0.0.1:8787")]
+        addr: SocketAddr,
+        /// Requests per minute per client (`x-api-key` header, else IP)
+        #[arg(long, default_value = "60")]
+        rate_limit: NonZeroU32,
+        /// Analyses running at once
+        #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u16).range(1..))]
+        max_concurrent: u16,
+        /// Requests allowed to wait for a free slot before 503s
+        #[arg(long, default_value_t = 64)]
+        queue: usize,
+    },
+    /// Re-analyze a mint periodically, printing only results that changed
+    Watch {
+        mint: String,
+        /// Time between analyses: `90`, `90s`, `5m` or `1h`
+        #[arg(long, default_value = "60s", value_parser = parse_interval)]
+        interval: Duration,
+        /// Score to alert on when the token drops below it
+        #[arg(long)]
+        threshold: Option<f64>,
+        /// Exit non-zero once the score drops below `--threshold`
+        #[arg(long, requires = "threshold")]
+        exit_on_alert: bool,
+        /// Shell command run when the score drops below `--threshold`, with
+        /// the result as JSON on stdin and `ANALYZER_MINT`, `ANALYZER_SCORE`
+        /// and `ANALYZER_THRESHOLD` set
+        #[arg(long, requires = "threshold")]
+        on_alert: Option<String>,
+        /// Alert when a top-3 holder cuts its balance by more than this percent
+        /// between runs
+        #[arg(long, default_value_t = ChangeThresholds::default().whale_exit)]
+        whale_exit: f64,
+        /// Alert when a top-3 holder grows its balance by more than this
+        /// percent between runs, or enters the largest holders
+        #[arg(long, default_value_t = ChangeThresholds::default().whale_accumulation)]
+        whale_accumulation: f64,
+        /// Alert when SOL or USD pool depth falls by more than this percent
+        /// between runs
+        #[arg(long, default_value_t = ChangeThresholds::default().lp_withdrawal)]
+        lp_withdrawal: f64,
+    },
+    /// List detector ids usable with `--detectors`
+    Detectors {
+        /// Print a scoring config with every default weight and threshold instead
+        #[arg(long)]
+        defaults: bool,
+    },
+    /// Record a mint's raw RPC responses and the analysis they produce as a
+    /// replay fixture (see `tests/replay.rs`)
+    Record {
+        mint: String,
+        /// Fixture directory (default: tests/fixtures/<MINT>)
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Analyze a labeled list of rugs and legit tokens and report how well the
+    /// score and each detector separate them (precision, recall, ROC)
+    Backtest {
+        /// `<mint> <rug|legit>` per line
+        dataset: PathBuf,
+        /// Replay `<DIR>/<MINT>` fixtures where recorded instead of querying the RPC
+        #[arg(long)]
+        fixtures: Option<PathBuf>,
+        /// Also write each token's detector signals as NDJSON, for `calibrate`
+        #[arg(long)]
+        samples: Option<PathBuf>,
+        /// Scores below this count as flagging a rug (default: the `--risk-medium` cutoff)
+        #[arg(long)]
+        cutoff: Option<f64>,
+        /// Analyses in flight at once
+        #[arg(long, short = 'j', default_value_t = 8, value_parser = clap::value_parser!(u16).range(1..))]
+        jobs: u16,
+    },
+    /// Search for the detector weights that best separate the rugs from the
+    /// legit tokens in `backtest --samples` output, and write them as a
+    /// scoring config for `--config`
+    Calibrate {
+        /// NDJSON samples from `backtest --samples`
+        samples: PathBuf,
+        /// Config file to write (default: stdout)
+        #[arg(long)]
+        out: Option<PathBuf>,
+        /// Comma-separated weights tried for each detector
+        #[arg(long, value_delimiter = ',', default_values_t = backtest::WEIGHT_GRID)]
+        grid: Vec<f64>,
+        /// Most passes over the detectors before stopping
+        #[arg(long, default_value_t = 10, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+        passes: usize,
+    },
+    /// Print a mint's recorded scores and metrics as NDJSON, newest first
+    History {
+        mint: String,
+        /// Entries shown
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+    },
+    /// Analyze a mint and print what changed since an earlier analysis of it:
+    /// score, risk level, detector signals and metrics
+    Diff {
+        mint: String,
+        /// A saved analysis (`--format json` output, or its `data`), or `db`
+        /// for the mint's latest run in the score history
+        #[arg(long)]
+        against: String,
+    },
+    /// Rebuild a mint's analysis as of an earlier time from its transactions
+    /// up to then, and report which detectors would have fired
+    Postmortem {
+        mint: String,
+        /// Unix seconds or RFC 3339 (`2024-05-01T12:00:00Z`)
+        #[arg(long, value_parser = parse_time)]
+        at: i64,
+    },
+    /// Evict cached RPC responses (expired ones by default)
+    Cache {
+        /// Evict everything, fresh or not
+        #[arg(long, conflicts_with = "mint")]
+        all: bool,
+        /// Evict every response cached for this mint
+        #[arg(long)]
+        mint: Option<String>,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum OutputFormat {
+    /// One JSON document per result
+    Json,
+    /// Indented JSON
+    JsonPretty,
+    /// Colored terminal report (color only on a TTY without `NO_COLOR`)
+    Pretty,
+    /// CSV, header first, one row per result
+    Csv,
+    /// Markdown for chat posts
+    Markdown,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum LogFormat {
+    Text,
+    /// One JSON object per line, with the enclosing spans' fields
+    Json,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum MarketProvider {
+    Dexscreener,
+    Birdeye,
+}
+
+/// Exit code of a batch stopped by Ctrl-C, as shells report SIGINT
+const INTERRUPTED: u8 = 130;
+
+/// `--min-score` / `--fail-on-critical` for `analyze`, `analyze-pool` and
+/// `batch`. A result that fails the gate exits with its risk level's code: 10
+/// low, 11 medium, 12 high, 13 critical, or 14 when the analysis failed (the
+/// gate fails closed). Batches exit with the highest code of their results.
+/// Without either flag every result passes and the exit code stays 0.
+struct Gate {
+    min_score: Option<f64>,
+    fail_on_critical: bool,
+}
+
+impl Gate {
+    const FAILED: u8 = 14;
+
+    fn is_set(&self) -> bool {
+        self.min_score.is_some() || self.fail_on_critical
+    }
+
+    /// The exit code for a result; 0 when it passes. `score` is `None` when the
+    /// analysis failed.
+    fn check(&self, score: Option<f64>, risk_level: &str) -> u8 {
+        if !self.is_set() {
+            return 0;
+        }
+        let Some(score) = score else { return Self::FAILED };
+        let below = self.min_score.is_some_and(|min| score < min);
+        let critical = self.fail_on_critical && risk_level == "critical";
+        if !below && !critical {
+            return 0;
+        }
+        match risk_level {
+            "low" => 10,
+            "medium" => 11,
+            "high" => 12,
+            "critical" => 13,
+            _ => Self::FAILED,
+        }
+    }
+
+    fn check_output(&self, output: &AnalysisOutput) -> u8 {
+        match &output.data {
+            Some(analysis) => self.check(Some(analysis.safe_score), &analysis.risk_level),
+            None => self.check(None, "unknown"),
+        }
+    }
+}
+
+/// Writes results to stdout; CSV gets its header before the first row only
+struct Printer {
+    format: OutputFormat,
+    color: bool,
+    header_printed: bool,
+}
+
+impl Printer {
+    fn new(format: OutputFormat) -> Self {
+        let color = std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+        Self { format, color, header_printed: false }
+    }
+
+    fn print(&mut self, output: &AnalysisOutput) -> Result<()> {
+        match self.format {
+            OutputFormat::Json => println!("{}", serde_json::to_string(output)?),
+            OutputFormat::JsonPretty => println!("{}", serde_json::to_string_pretty(output)?),
+            OutputFormat::Pretty => println!("{}", report::pretty(output, self.color)),
+            OutputFormat::Csv => {
+                if !std::mem::replace(&mut self.header_printed, true) {
+                    println!("{}", report::csv_header());
+                }
+                println!("{}", report::csv_row(output));
+            }
+            OutputFormat::Markdown => println!("{}", report::markdown(output)),
+        }
+        Ok(())
+    }
+}
+
+/// Logs go to stderr so they never mix with results on stdout. A bare level
+/// applies to this crate only; dependencies stay at `warn`.
+fn init_logging(level: &str, format: LogFormat) -> Result<()> {
+    let directives = match level.parse::<LevelFilter>() {
+        Ok(level) => format!("warn,token_analyzer={0},analyze_token={0}", level),
+        Err(_) => level.to_string(),
+    };
+    let filter = EnvFilter::try_new(&directives).with_context(|| format!("invalid --log-level `{}`", level))?;
+    // Span close events carry `time.busy`/`time.idle`, which is the profile
+    let span_events = if filter.max_level_hint().is_some_and(|l| l >= LevelFilter::DEBUG) {
+        FmtSpan::CLOSE
+    } else {
+        FmtSpan::NONE
+    };
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_span_events(span_events)
+        .with_ansi(std::io::stderr().is_terminal() && std::env::var_os("NO_COLOR").is_none())
+        .with_writer(std::io::stderr);
+    match format {
+        LogFormat::Text => builder.init(),
+        LogFormat::Json => builder.json().init(),
+    }
+    Ok(())
+}
+
+/// Writes the fixture only when the analysis succeeds, so a flaky RPC can't
+/// leave a half-recorded one behind
+async fn record(analyzer: TokenAnalyzer, mint: &str, out: &Path) -> Result<()> {
+    let analysis = analyzer.analyze(mint).await?;
+    let fixture = analyzer.fixture().context("analyzer is not recording")?;
+    fixture.save(out)?;
+    let expected = out.join(fixture::EXPECTED_FILE);
+    std::fs::write(&expected, serde_json::to_string_pretty(&analysis)? + "\n")
+        .with_context(|| format!("writing {}", expected.display()))?;
+    eprintln!("recorded {} RPC responses to {}", fixture.calls.len(), out.display());
+    Ok(())
+}
+
+/// Analyzes every labeled token, replaying its fixture under `fixtures` when
+/// one was recorded. Failed analyses are logged and counted, not fatal.
+async fn run_backtest(
+    config: AnalyzerConfig,
+    registry: DetectorRegistry,
+    dataset: &Path,
+    fixtures: Option<&Path>,
+    samples_out: Option<&Path>,
+    cutoff: f64,
+    jobs: usize,
+) -> Result<BacktestReport> {
+    let tokens = backtest::load_dataset(dataset)?;
+    let replay_config = AnalyzerConfig { cache: None, market_data: None, ..config.clone() };
+    let live = TokenAnalyzer::with_registry(config, registry)?;
+
+    let analyses = stream::iter(&tokens)
+        .map(|token| {
+            let (live, replay_config) = (&live, &replay_config);
+            let fixture = fixtures.map(|dir| dir.join(&token.mint)).filter(|dir| dir.join(fixture::RPC_FILE).exists());
+            async move {
+                let analysis = match fixture {
+                    Some(dir) => replay(replay_config, &dir, &token.mint).await,
+                    None => live.analyze(&token.mint).await,
+                };
+                (token, analysis)
+            }
+        })
+        .buffered(jobs);
+    futures::pin_mut!(analyses);
+
+    let mut samples = Vec::new();
+    let mut failed = 0;
+    while let Some((token, analysis)) = analyses.next().await {
+        match analysis {
+            Ok(analysis) => samples.push(Sample::new(token, &analysis, live.model_version())),
+            Err(e) => {
+                tracing::warn!("backtest {}: {:#}", token.mint, e);
+                failed += 1;
+            }
+        }
+    }
+
+    if let Some(path) = samples_out {
+        let mut lines = String::new();
+        for sample in &samples {
+            lines += &serde_json::to_string(sample)?;
+            lines.push('\n');
+        }
+        std::fs::write(path, lines).with_context(|| format!("writing {}", path.display()))?;
+    }
+    Ok(BacktestReport::new(&samples, failed, cutoff))
+}
+
+async fn replay(config: &AnalyzerConfig, dir: &Path, mint: &str) -> Result<SafetyAnalysis> {
+    let fixture = fixture::RpcFixture::load(dir)?;
+    TokenAnalyzer::with_config(config.clone())?.with_fixture_replay(fixture).analyze(mint).await
+}
+
+/// Seconds, optionally suffixed with `s`, `m` or `h`
+fn parse_interval(value: &str) -> Result<Duration, String> {
+    let (number, unit) = match value.find(|c: char| !c.is_ascii_digit()) {
+        Some(at) => value.split_at(at),
+        None => (value, "s"),
+    };
+    let secs: u64 = number.parse().map_err(|_| format!("`{}` is not a duration", value))?;
+    let secs = match unit {
+        "s" => secs,
+        "m" => secs * 60,
+        "h" => secs * 3600,
+        _ => return Err(format!("unknown unit `{}` (use s, m or h)", unit)),
+    };
+    if secs == 0 {
+        return Err("interval must be positive".to_string());
+    }
+    Ok(Duration::from_secs(secs))
+}
+
+/// Unix seconds, or an RFC 3339 date and time
+fn parse_time(value: &str) -> Result<i64, String> {
+    if let Ok(secs) = value.parse() {
+        return Ok(secs);
+    }
+    chrono::DateTime::parse_from_rfc3339(value)
+        .map(|t| t.timestamp())
+        .map_err(|_| format!("`{}` is neither unix seconds nor RFC 3339", value))
+}
+
+/// What `watch` compares between runs: the score to two decimals, the risk
+/// level and each detector's score, or the error
+fn fingerprint(output: &AnalysisOutput) -> String {
+    match &output.data {
+        Some(analysis) => {
+            let signals: Vec<String> = analysis
+                .pattern_signals
+                .iter()
+                .map(|s| format!("{}={:.2}", s.name, s.score))
+                .collect();
+            format!("{:.2} {} {}", analysis.safe_score, analysis.risk_level, signals.join(","))
+        }
+        None => output.error.clone().unwrap_or_default(),
+    }
+}
+
+/// Runs `--on-alert` to completion, so `--exit-on-alert` doesn't cut it short
+async fn run_alert_command(command: &str, mint: &str, score: f64, threshold: f64, output: &AnalysisOutput) -> Result<()> {
+    let mut child = tokio::process::Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .env("ANALYZER_MINT", mint)
+        .env("ANALYZER_SCORE", score.to_string())
+        .env("ANALYZER_THRESHOLD", threshold.to_string())
+        .stdin(std::process::Stdio::piped())
+        .spawn()
+        .with_context(|| format!("running `{}`", command))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // A command that ignores its input may close the pipe early; that's fine
+        let _ = stdin.write_all(&serde_json::to_vec(output)?).await;
+    }
+    let status = child.wait().await?;
+    if !status.success() {
+        bail!("alert command `{}` exited with {}", command, status);
+    }
+    Ok(())
+}
+
+/// Mints from `input` (stdin for `-` or `None`), read lazily so a live feed
+/// can be piped in. Blank lines and `#` comments are skipped.
+async fn read_mints(input: Option<PathBuf>) -> Result<impl futures::Stream<Item = String>> {
+    let reader: Box<dyn AsyncRead + Unpin + Send> = match input.filter(|p| p.as_os_str() != "-") {
+        Some(path) => Box::new(
+            tokio::fs::File::open(&path)
+                .await
+                .with_context(|| format!("reading {}", path.display()))?,
+        ),
+        None => Box::new(tokio::io::stdin()),
+    };
+    let lines = BufReader::new(reader).lines();
+
+    Ok(stream::unfold(lines, |mut lines| async move {
+        match lines.next_line().await {
+            Ok(Some(line)) => Some((line, lines)),
+            Ok(None) => None,
+            Err(e) => {
+                tracing::warn!("batch input: {}", e);
+                None
+            }
+        }
+    })
+    .map(|line| line.trim().to_string())
+    .filter(|line| futures::future::ready(is_mint_line(line))))
+}
+
+/// Blank lines and `#` comments in batch input are skipped
+fn is_mint_line(line: &str) -> bool {
+    !line.is_empty() && !line.starts_with('#')
+}
+
+/// Mints in a batch input file, for the progress total; `None` for stdin
+async fn count_mints(input: Option<&Path>) -> Option<usize> {
+    let path = input.filter(|p| p.as_os_str() != "-")?;
+    let text = tokio::fs::read_to_string(path).await.ok()?;
+    Some(text.lines().map(str::trim).filter(|line| is_mint_line(line)).count())
+}
+
+/// A bar over `total` mints, or a spinner counting them when reading stdin
+fn progress_bar(total: Option<usize>) -> ProgressBar {
+    let (bar, template) = match total {
+        Some(total) => (
+            ProgressBar::new(total as u64),
+            "{bar:40} {pos}/{len} done, {msg} [{elapsed_precise}, eta {eta}]",
+        ),
+        None => (ProgressBar::new_spinner(), "{spinner} {pos} done, {msg} [{elapsed_precise}]"),
+    };
+    if let Ok(style) = ProgressStyle::with_template(template) {
+        bar.set_style(style);
+    }
+    bar.set_message("0 failed");
+    bar.enable_steady_tick(Duration::from_millis(200));
+    bar
 }
 
 #[tokio::main]
-async fn main() -> Result<()> {
-    let args: Vec<String> = std::env::args().collect();
-    
-    if args.len() < 2 {
-        eprintln!("Usage: analyze-token <MINT_ADDRESS>");
-        std::process::exit(1);
-    }
-    
-    let mint_address = &args[1];
-    
-    // Initialize analyzer
-    let analyzer = TokenAnalyzer::new()?;
-    
-    // Perform analysis
-    let result = match analyzer.analyze(mint_address).await {
-        Ok(analysis) => AnalysisOutput {
-            success: true,
-            data: Some(analysis),
-            error: None,
+async fn main() -> Result<ExitCode> {
+    let cli = Cli::parse();
+    init_logging(&cli.log_level, cli.log_format)?;
+    if cli.schema {
+        println!("{}", serde_json::to_string_pretty(&AnalysisOutput::schema())?);
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let command = match (cli.command, cli.mint) {
+        (Some(command), _) => command,
+        (None, Some(mint)) => Command::Analyze { mint },
+        // The estimate is the same for any mint
+        (None, None) if cli.dry_run_cost => Command::Analyze { mint: String::new() },
+        (None, None) => bail!("missing mint address or subcommand (see --help)"),
+    };
+
+    let registry = DetectorRegistry::default();
+    if let Command::Detectors { defaults } = command {
+        if defaults {
+            print!("{}", toml::to_string_pretty(&registry.scoring_template())?);
+        } else {
+            for id in registry.ids() {
+                println!("{}", id);
+            }
+        }
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    if let Command::Calibrate { samples, out, grid, passes } = &command {
+        let calibration = backtest::calibrate(&backtest::load_samples(samples)?, grid, *passes)?;
+        let config = format!(
+            "# Calibrated on {} samples over model {}: AUC {:.3} -> {:.3}\n# Use with --model {} --config <this file>\n\n{}",
+            calibration.samples,
+            calibration.model_version,
+            calibration.auc_before,
+            calibration.auc_after,
+            calibration.model_version.trim_end_matches("+custom"),
+            toml::to_string_pretty(&calibration.scoring())?,
+        );
+        match out {
+            Some(path) => std::fs::write(path, config).with_context(|| format!("writing {}", path.display()))?,
+            None => print!("{}", config),
+        }
+        eprintln!(
+            "AUC {:.3} -> {:.3} after {} passes over {} samples",
+            calibration.auc_before, calibration.auc_after, calibration.passes, calibration.samples,
+        );
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let mut scoring = match &cli.config {
+        Some(path) => ScoringConfig::load(path)?,
+        None => ScoringConfig::default(),
+    }
+    .with_env_overrides(&registry)?;
+    let thresholds = &mut scoring.risk.thresholds;
+    thresholds.low = cli.risk_low.unwrap_or(thresholds.low);
+    thresholds.medium = cli.risk_medium.unwrap_or(thresholds.medium);
+    thresholds.high = cli.risk_high.unwrap_or(thresholds.high);
+    for spec in &cli.recommendations {
+        scoring.risk.recommendations.set(spec)?;
+    }
+
+    let history_path = cli.history_path.unwrap_or_else(|| cli.cluster.namespaced(&analysis::history::default_path()));
+    if let Command::History { mint, limit } = &command {
+        for entry in ScoreHistory::open(&history_path)?.entries(mint, *limit)? {
+            println!("{}", serde_json::to_string(&entry)?);
+        }
+        return Ok(ExitCode::SUCCESS);
+    }
+    // Read before the fresh analysis is recorded over it
+    let baseline = match &command {
+        Command::Diff { mint, against } => Some(load_baseline(mint, against, &history_path)?),
+        _ => None,
+    };
+
+    let cache = CacheConfig {
+        path: cli.cache_path.unwrap_or_else(|| cli.cluster.namespaced(&analysis::cache::default_path())),
+        ttl: Duration::from_secs(cli.cache_ttl),
+    };
+
+    if let Command::Cache { all, mint } = command {
+        let cache = RpcCache::open(&cache.path, cache.ttl)?;
+        let removed = match (all, mint) {
+            (true, _) => cache.evict_all()?,
+            (false, Some(mint)) => cache.evict_mint(&mint)?,
+            (false, None) => cache.evict_expired()?,
+        };
+        eprintln!("evicted {} cached responses", removed);
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let market_data = match (cli.market_data, cli.birdeye_api_key) {
+        (None, _) => None,
+        (Some(MarketProvider::Dexscreener), _) => Some(MarketDataSource::DexScreener),
+        (Some(MarketProvider::Birdeye), Some(api_key)) => Some(MarketDataSource::Birdeye { api_key }),
+        (Some(MarketProvider::Birdeye), None) => bail!("--market-data birdeye needs --birdeye-api-key or BIRDEYE_API_KEY"),
+    };
+
+    // Watch exists to see fresh data, so it never reads from the cache
+    let use_cache = !cli.no_cache && !matches!(command, Command::Watch { .. });
+
+    // The default URL is mainnet's placeholder: dropped when keys are given
+    // instead, and replaced by the cluster's own elsewhere
+    let rpc_urls = match cli.rpc_url == [DEFAULT_RPC_URL] {
+        false => cli.rpc_url,
+        true if !cli.helius_keys.is_empty() => Vec::new(),
+        true => match cli.cluster.default_rpc_url() {
+            Some(url) => vec![url.to_string()],
+            None => bail!("--cluster {} needs --rpc-url", cli.cluster),
         },
-        Err(e) => AnalysisOutput {
-            success: false,
-            data: None,
-            error: Some(e.to_string()),
+    };
+    let config = AnalyzerConfig {
+        rpc_urls,
+        api_keys: cli.helius_keys,
+        cluster: cli.cluster,
+        timeout: Duration::from_secs(cli.timeout),
+        retry: RetryPolicy {
+            max_attempts: cli.max_attempts,
+            base_delay: Duration::from_millis(cli.backoff_ms),
+            max_delay: Duration::from_millis(cli.max_backoff_ms),
+            call_timeout: Duration::from_secs(cli.rpc_timeout),
         },
+        analysis_timeout: (cli.analysis_timeout > 0).then(|| Duration::from_secs(cli.analysis_timeout)),
+        detectors: cli.detectors,
+        model: cli.model,
+        scoring,
+        cache: use_cache.then_some(cache),
+        // A fixture's expected analysis must not carry a trend from earlier runs,
+        // and a backtest's analyses aren't live observations worth trending
+        history: (!cli.no_history && !matches!(command, Command::Record { .. } | Command::Backtest { .. }))
+            .then_some(history_path),
+        market_data,
+        known_addresses: cli.known_addresses,
+        allow_list: cli.allow_list,
+        deny_list: cli.deny_list,
+        // A recorded fixture needs the calls a full analysis makes
+        known_good: !cli.no_known_good && !matches!(command, Command::Record { .. }),
+        das: !cli.no_das,
+        tx_depth: cli.tx_depth,
+        partial: cli.partial,
+        #[cfg(feature = "ml")]
+        ml_model: cli.ml_model,
     };
-    
-    // Output JSON to stdout
-    println!("{}", serde_json::to_string(&result)?);
-    
-    Ok(())
+    if cli.dry_run_cost {
+        let estimate = analysis::cost::estimate(&config);
+        match cli.format {
+            OutputFormat::Json => println!("{}", serde_json::to_string(&estimate)?),
+            OutputFormat::JsonPretty => println!("{}", serde_json::to_string_pretty(&estimate)?),
+            _ => print!("{}", report::cost(&estimate)),
+        }
+        return Ok(ExitCode::SUCCESS);
+    }
+    if let Command::Backtest { dataset, fixtures, samples, cutoff, jobs } = command {
+        let cutoff = cutoff.unwrap_or(config.scoring.risk.thresholds.medium);
+        let report = run_backtest(config, registry, &dataset, fixtures.as_deref(), samples.as_deref(), cutoff, jobs.into()).await?;
+        match cli.format {
+            OutputFormat::Json => println!("{}", serde_json::to_string(&report)?),
+            OutputFormat::JsonPretty => println!("{}", serde_json::to_string_pretty(&report)?),
+            _ => print!("{}", report::backtest(&report)),
+        }
+        return Ok(ExitCode::SUCCESS);
+    }
+    let analyzer = TokenAnalyzer::with_registry(config, registry)?;
+    let alerter = Alerter::new(cli.alerts, cli.alert_below, Duration::from_secs(cli.timeout))?;
+    let gate = Gate { min_score: cli.min_score, fail_on_critical: cli.fail_on_critical };
+    run(analyzer, alerter, gate, command, baseline, cli.format).await.map(ExitCode::from)
+}
+
+/// The analysis `diff` compares against: the mint's latest run in the score
+/// history for `db`, a saved analysis otherwise
+fn load_baseline(mint: &str, against: &str, history_path: &Path) -> Result<Baseline> {
+    if against == "db" {
+        let entry = ScoreHistory::open(history_path)?
+            .entries(mint, 1)?
+            .pop()
+            .with_context(|| format!("no analysis of {} recorded in {}", mint, history_path.display()))?;
+        return Ok(Baseline::from(entry));
+    }
+    let json = std::fs::read_to_string(against).with_context(|| format!("reading {}", against))?;
+    Baseline::from_json(&json, mint).with_context(|| format!("reading {}", against))
+}
+
+/// The gate's exit code (see `Gate`) on success
+async fn run(
+    analyzer: TokenAnalyzer,
+    alerter: Alerter,
+    gate: Gate,
+    command: Command,
+    baseline: Option<Baseline>,
+    format: OutputFormat,
+) -> Result<u8> {
+    if let Command::Record { mint, out } = command {
+        let out = out.unwrap_or_else(|| PathBuf::from("tests/fixtures").join(&mint));
+        record(analyzer.with_fixture_recording(&mint), &mint, &out).await?;
+        return Ok(0);
+    }
+    let analyzer = match command {
+        Command::Serve { addr, rate_limit, max_concurrent, queue } => {
+            let config = server::ServeConfig {
+                addr,
+                rate_limit,
+                max_concurrent: max_concurrent.into(),
+                queue,
+            };
+            server::serve(analyzer, config).await?;
+            return Ok(0);
+        }
+        _ => &analyzer,
+    };
+
+    let mut code = 0;
+    match command {
+        Command::Analyze { mint } => {
+            let output = analyzer.analyze_output(&mint).await;
+            Printer::new(format).print(&output)?;
+            code = gate.check_output(&output);
+        }
+        Command::AnalyzePool { pool, max_fee_bps } => {
+            let report = analyzer.analyze_pool(&pool, max_fee_bps).await?;
+            // The token's score, but the pool's risk level, which its flags can raise
+            let score = report.token.data.as_ref().map(|analysis| analysis.safe_score);
+            code = gate.check(score, &report.risk_level);
+            match format {
+                OutputFormat::Json => println!("{}", serde_json::to_string(&report)?),
+                OutputFormat::JsonPretty => println!("{}", serde_json::to_string_pretty(&report)?),
+                OutputFormat::Pretty => {
+                    let color = std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+                    println!("{}", report::pool_pretty(&report, color));
+                }
+                OutputFormat::Markdown => println!("{}", report::pool_markdown(&report)),
+                OutputFormat::Csv => bail!("analyze-pool has no CSV output; use json, pretty or markdown"),
+            }
+        }
+        Command::Diff { mint, .. } => {
+            let baseline = baseline.context("no analysis to diff against")?;
+            let output = analyzer.analyze_output(&mint).await;
+            let Some(analysis) = &output.data else {
+                bail!("analysis failed: {}", output.error.as_deref().unwrap_or("unknown error"));
+            };
+            let diff = AnalysisDiff::between(&baseline, analysis, &output.model_version);
+            match format {
+                OutputFormat::Json => println!("{}", serde_json::to_string(&diff)?),
+                OutputFormat::JsonPretty => println!("{}", serde_json::to_string_pretty(&diff)?),
+                OutputFormat::Pretty => {
+                    let color = std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+                    print!("{}", report::diff_pretty(&diff, color));
+                }
+                OutputFormat::Markdown => print!("{}", report::diff_markdown(&diff)),
+                OutputFormat::Csv => bail!("diff has no CSV output; use json, pretty or markdown"),
+            }
+        }
+        Command::Postmortem { mint, at } => {
+            let postmortem = analyzer.postmortem(&mint, at).await?;
+            match format {
+                OutputFormat::Json => println!("{}", serde_json::to_string(&postmortem)?),
+                OutputFormat::JsonPretty => println!("{}", serde_json::to_string_pretty(&postmortem)?),
+                OutputFormat::Pretty => {
+                    let color = std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+                    print!("{}", report::postmortem_pretty(&postmortem, color));
+                }
+                OutputFormat::Markdown => print!("{}", report::postmortem_markdown(&postmortem)),
+                OutputFormat::Csv => bail!("postmortem has no CSV output; use json, pretty or markdown"),
+            }
+        }
+        Command::Batch { input, jobs, no_progress } => {
+            // JSON output stays NDJSON, one document per line
+            let mut printer = Printer::new(match format {
+                OutputFormat::JsonPretty => OutputFormat::Json,
+                format => format,
+            });
+            // Progress goes to stderr: a bar at a terminal, NDJSON events otherwise
+            let terminal = std::io::stderr().is_terminal();
+            let total = count_mints(input.as_deref()).await;
+            let bar = (terminal && !no_progress).then(|| progress_bar(total));
+            let started = Instant::now();
+            let mut last_event = started;
+            let mut tally = BatchTally::default();
+            // Ctrl-C stops reading mints; the analyses in flight still finish
+            // (within `--analysis-timeout`) and are printed
+            let interrupted = std::cell::Cell::new(false);
+            let results = read_mints(input)
+                .await?
+                .take_until(async {
+                    let _ = tokio::signal::ctrl_c().await;
+                    tracing::warn!("interrupted, finishing the analyses in flight");
+                    interrupted.set(true);
+                })
+                .map(|mint| async move {
+                    let mut output = analyzer.analyze_output(&mint).await;
+                    output.mint = Some(mint);
+                    output
+                })
+                .buffer_unordered(jobs.into());
+            futures::pin_mut!(results);
+
+            while let Some(output) = results.next().await {
+                match &bar {
+                    Some(bar) => bar.suspend(|| printer.print(&output))?,
+                    None => printer.print(&output)?,
+                }
+                tally.record(&output);
+                if let Some(bar) = &bar {
+                    bar.set_position(tally.done() as u64);
+                    bar.set_message(format!("{} failed", tally.failed()));
+                } else if !no_progress && last_event.elapsed() >= Duration::from_secs(PROGRESS_INTERVAL_SECS) {
+                    eprintln!("{}", serde_json::to_string(&tally.progress(total, started.elapsed().as_secs_f64()))?);
+                    last_event = Instant::now();
+                }
+                code = code.max(gate.check_output(&output));
+                if let Some(analysis) = output.data.as_ref().filter(|_| !alerter.is_empty()) {
+                    let triggers = alerter.triggers(analysis);
+                    if !triggers.is_empty() {
+                        alerter.send(&output, &triggers).await;
+                    }
+                }
+            }
+            if interrupted.get() {
+                code = INTERRUPTED;
+            }
+
+            if let Some(bar) = &bar {
+                bar.finish_and_clear();
+            }
+            let requests = analyzer.rpc_usage().iter().map(|u| u.requests).sum();
+            let summary = tally.summary(analyzer.cache_hits(), requests, started.elapsed().as_secs_f64());
+            match terminal {
+                true => eprint!("{}", report::batch_summary(&summary)),
+                false => eprintln!("{}", serde_json::to_string(&BatchEvent::Summary(summary))?),
+            }
+        }
+        Command::Watch { mint, interval, threshold, exit_on_alert, on_alert, whale_exit, whale_accumulation, lp_withdrawal } => {
+            let mut printer = Printer::new(format);
+            let mut last_printed = None;
+            // Starts armed, so a token already below the threshold alerts at once
+            let mut below = false;
+            let mut last_triggers = Vec::new();
+            let thresholds = ChangeThresholds { whale_exit, whale_accumulation, lp_withdrawal };
+            // Last successful analysis, the baseline holder changes are measured from
+            let mut previous: Option<SafetyAnalysis> = None;
+            loop {
+                let output = analyzer.analyze_output(&mint).await;
+                let current = fingerprint(&output);
+                if last_printed.as_ref() != Some(&current) {
+                    printer.print(&output)?;
+                    last_printed = Some(current);
+                }
+
+                let changes: Vec<String> = match (&previous, &output.data) {
+                    (Some(previous), Some(analysis)) => holder_changes(previous, analysis, &thresholds)
+                        .into_iter()
+                        .map(|c| format!("{}: {}", c.kind.as_str(), c.message))
+                        .collect(),
+                    _ => Vec::new(),
+                };
+                for change in &changes {
+                    eprintln!("{}: {}", mint, change);
+                }
+
+                // Sinks hear about a trigger once, not every interval it persists;
+                // holder changes are news every time they happen
+                if let Some(analysis) = output.data.as_ref().filter(|_| !alerter.is_empty()) {
+                    let triggers = alerter.triggers(analysis);
+                    if (!triggers.is_empty() && triggers != last_triggers) || !changes.is_empty() {
+                        alerter.send(&output, &[triggers.as_slice(), &changes].concat()).await;
+                    }
+                    last_triggers = triggers;
+                }
+
+                // Failed analyses say nothing about the score, so they neither alert nor re-arm
+                if let (Some(threshold), Some(analysis)) = (threshold, &output.data) {
+                    let was_below = std::mem::replace(&mut below, analysis.safe_score < threshold);
+                    if below && !was_below {
+                        eprintln!("{}: score {:.1} dropped below {}", mint, analysis.safe_score, threshold);
+                        if let Some(command) = &on_alert {
+                            if let Err(e) = run_alert_command(command, &mint, analysis.safe_score, threshold, &output).await {
+                                tracing::warn!("{:#}", e);
+                            }
+                        }
+                        if exit_on_alert {
+                            bail!("{}: score {:.1} is below the threshold {}", mint, analysis.safe_score, threshold);
+                        }
+                    }
+                }
+                if output.data.is_some() {
+                    previous = output.data;
+                }
+                tokio::time::sleep(interval).await;
+            }
+        }
+        Command::Serve { .. }
+        | Command::Record { .. }
+        | Command::Detectors { .. }
+        | Command::History { .. }
+        | Command::Cache { .. }
+        | Command::Backtest { .. }
+        | Command::Calibrate { .. } => {}
+    }
+    for usage in analyzer.rpc_usage().into_iter().filter(|u| u.budget.is_some()) {
+        tracing::info!(endpoint = %usage.endpoint, requests = usage.requests, credits = usage.credits, budget = ?usage.budget, "rpc usage");
+    }
+    Ok(code)
 }
@@ -0,0 +1,731 @@
+//! Human-facing renderings of an `AnalysisOutput`: a colored terminal report,
+//! CSV rows for spreadsheets and Markdown for chat posts, plus pool reports,
+//! the backtest summary table, the `batch` summary and the `--dry-run-cost` estimate. JSON stays the machine format; these are lossy on purpose.
+
+use std::fmt::Write;
+
+use crate::analysis::backtest::BacktestReport;
+use crate::analysis::batch::{BatchSummary, BUCKET_POINTS};
+use crate::analysis::benchmark::{Benchmark, WINDOW_DAYS};
+use crate::analysis::breakdown::{DetectorContribution, ScoreBreakdown};
+use crate::analysis::completeness::DataCompleteness;
+#[cfg(feature = "native")]
+use crate::analysis::cost::CostEstimate;
+use crate::analysis::diff::{AnalysisDiff, MetricChange, SignalChange, SignalChangeKind};
+use crate::analysis::history::Trend;
+use crate::analysis::known_good::KnownGoodMint;
+use crate::analysis::lists::{ListKind, ListMatch};
+use crate::analysis::pool::{PoolReport, PoolUsage};
+use crate::analysis::postmortem::Postmortem;
+use crate::analysis::reasons::Severity;
+use crate::analysis::{AnalysisOutput, PatternSignalOutput, SafetyAnalysis, SafetyMetrics};
+
+/// Width of the per-detector score bar, in cells
+const BAR_WIDTH: usize = 10;
+/// Top reasons shown
+const REASONS: usize = 5;
+/// Largest score deductions shown
+const DEDUCTIONS: usize = 5;
+
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "\x1b[1m";
+const DIM: &str = "\x1b[2m";
+const RED: &str = "\x1b[31m";
+const GREEN: &str = "\x1b[32m";
+const YELLOW: &str = "\x1b[33m";
+
+/// `(csv column, label, value)`; `None` for metrics the analysis didn't get
+fn metric_rows(m: &SafetyMetrics) -> Vec<(&'static str, &'static str, Option<String>)> {
+    let usd = |v: Option<f64>| v.map(|v| format!("${:.2}", v));
+    vec![
+        ("holder_count", "Holders", Some(m.holder_count.to_string())),
+        ("top_holder_percent", "Top holder", Some(format!("{:.1}%", m.top_holder_percent))),
+        ("whale_concentration", "Top 3 holders", Some(format!("{:.1}%", m.whale_concentration))),
+        ("distribution_top10", "Top 10 holders", Some(format!("{:.1}%", m.distribution_top10))),
+        ("gini_coefficient", "Gini coefficient", Some(format!("{:.3}", m.gini_coefficient))),
+        ("hhi", "HHI", Some(format!("{:.0}", m.hhi))),
+        ("insider_percent", "Insider allocation", Some(format!("{:.1}%", m.insider_percent))),
+        ("insider_extraction_percent", "Insider extraction", m.insider_extraction_percent.map(|p| format!("{:.1}%", p))),
+        ("transaction_count", "Transactions", Some(m.transaction_count.to_string())),
+        ("transactions_last_hour", "Transactions (last hour)", m.transactions_last_hour.map(|t| t.to_string())),
+        ("buy_count", "Buys", Some(m.buy_count.to_string())),
+        ("sell_count", "Sells", Some(m.sell_count.to_string())),
+        ("net_flow_sol", "Net flow", Some(format!("{:+.2} SOL", m.net_flow_sol))),
+        ("token_age_hours", "Age", Some(format!("{:.1}h", m.token_age_hours))),
+        ("total_supply", "Supply", Some(format!("{}", m.total_supply))),
+        ("pool_count", "Pools", Some(m.pool_count.to_string())),
+        ("liquidity_sol", "Liquidity (SOL)", Some(format!("{:.2}", m.liquidity_sol))),
+        ("liquidity_usd", "Liquidity (USD)", Some(format!("{:.2}", m.liquidity_usd))),
+        ("price_usd", "Price", m.price_usd.map(|v| format!("${}", v))),
+        ("volume_24h_usd", "24h volume", usd(m.volume_24h_usd)),
+        ("market_cap_usd", "Market cap", usd(m.market_cap_usd)),
+        ("fdv_usd", "FDV", usd(m.fdv_usd)),
+    ]
+}
+
+/// `p72`: the metric's percentile among recently analyzed tokens, if benchmarked
+fn percentile_note(benchmark: Option<&Benchmark>, column: &str) -> Option<String> {
+    benchmark?.percentile_of(column).map(|p| format!("p{:.0}", p))
+}
+
+/// `score -12.3, holders +2, top 3 holders +6.0 pts, liquidity -1.50 SOL since the previous run`
+fn trend_summary(trend: &Trend) -> String {
+    format!(
+        "score {:+.1}, holders {:+}, top 3 holders {:+.1} pts, liquidity {:+.2} SOL since the previous run",
+        trend.score_delta, trend.holder_delta, trend.whale_concentration_delta, trend.liquidity_sol_delta,
+    )
+}
+
+fn benchmark_summary(benchmark: &Benchmark) -> String {
+    format!("pNN: percentile among the {} tokens analyzed in the last {} days", benchmark.population, WINDOW_DAYS)
+}
+
+/// ANSI styling that disappears when color is off
+struct Style(bool);
+
+impl Style {
+    fn paint(&self, code: &str, text: &str) -> String {
+        if self.0 {
+            format!("{}{}{}", code, text, RESET)
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+fn risk_color(risk_level: &str) -> &'static str {
+    match risk_level {
+        "low" => GREEN,
+        "medium" => YELLOW,
+        _ => RED,
+    }
+}
+
+fn signal_color(score: f64) -> &'static str {
+    if score >= 0.8 {
+        GREEN
+    } else if score >= 0.5 {
+        YELLOW
+    } else {
+        RED
+    }
+}
+
+fn signal_icon(score: f64) -> &'static str {
+    if score >= 0.8 {
+        "✅"
+    } else if score >= 0.5 {
+        "⚠️"
+    } else {
+        "❌"
+    }
+}
+
+/// Terminal report: header, metrics table, a bar per detector, top reasons
+pub fn pretty(output: &AnalysisOutput, color: bool) -> String {
+    let style = Style(color);
+    let Some(analysis) = &output.data else {
+        let failed = style.paint(&format!("{}{}", BOLD, RED), "✗ ANALYSIS FAILED");
+        let error = output.error.as_deref().unwrap_or("unknown error");
+        return match &output.mint {
+            Some(mint) => format!("{} {}: {}\n", failed, mint, error),
+            None => format!("{} {}\n", failed, error),
+        };
+    };
+
+    let mut out = String::new();
+    let risk = format!("{} RISK", analysis.risk_level.to_uppercase());
+    let _ = writeln!(out, "{} {}", style.paint(BOLD, "Token Safety Report"), analysis.mint_address);
+    let _ = writeln!(
+        out,
+        "Score {}  {}",
+        style.paint(BOLD, &format!("{:.1}/100", analysis.safe_score)),
+        style.paint(&format!("{}{}", BOLD, risk_color(&analysis.risk_level)), &risk),
+    );
+    if let Some(ml) = &analysis.ml_score {
+        let _ = writeln!(out, "ML rug probability {:.0}% {}", ml.rug_probability * 100.0, style.paint(DIM, &format!("({})", ml.model)));
+    }
+    if let Some(trend) = &analysis.trend {
+        let _ = write!(out, "Trend {}", trend_summary(trend));
+        if trend.deteriorating {
+            let _ = write!(out, "  {}", style.paint(&format!("{}{}", BOLD, RED), "DETERIORATING"));
+        }
+        let _ = writeln!(out);
+    }
+    if let Some(completeness) = analysis.data_completeness.as_ref().filter(|c| !c.complete) {
+        let _ = writeln!(out, "{}", style.paint(&format!("{}{}", BOLD, YELLOW), &format!("PARTIAL: {}", missing_summary(completeness))));
+    }
+    if let Some(matched) = &analysis.list_match {
+        let color = match matched.list {
+            ListKind::Allow => GREEN,
+            ListKind::Deny => RED,
+        };
+        let _ = writeln!(out, "{}", style.paint(&format!("{}{}", BOLD, color), &list_summary(matched)));
+    }
+    if let Some(known) = &analysis.known_good {
+        let _ = writeln!(out, "{}", style.paint(&format!("{}{}", BOLD, GREEN), &known_good_summary(known)));
+    }
+    let _ = writeln!(out, "{}\n", analysis.recommendation);
+
+    let _ = writeln!(out, "{}", style.paint(BOLD, "Metrics"));
+    let benchmark = analysis.benchmark.as_ref();
+    let rows: Vec<(&str, String, Option<String>)> = metric_rows(&analysis.metrics)
+        .into_iter()
+        .filter_map(|(column, label, value)| Some((label, value?, percentile_note(benchmark, column))))
+        .collect();
+    let label_width = rows.iter().map(|(label, _, _)| label.len()).max().unwrap_or(0);
+    for (label, value, percentile) in &rows {
+        let note = percentile.as_ref().map(|p| format!("  {}", style.paint(DIM, p))).unwrap_or_default();
+        let _ = writeln!(out, "  {:<width$}  {}{}", label, value, note, width = label_width);
+    }
+    if let Some(benchmark) = benchmark {
+        let _ = writeln!(out, "  {}", style.paint(DIM, &benchmark_summary(benchmark)));
+    }
+
+    let _ = writeln!(out, "\n{}", style.paint(BOLD, "Detectors"));
+    let name_width = analysis.pattern_signals.iter().map(|s| s.name.chars().count()).max().unwrap_or(0);
+    for signal in &analysis.pattern_signals {
+        let _ = writeln!(out, "  {}", detector_line(signal, name_width, &style));
+    }
+
+    let _ = writeln!(out, "\n{}", style.paint(BOLD, "Score breakdown"));
+    let _ = writeln!(out, "  {}", style.paint(DIM, &analysis.score_breakdown.formula));
+    for contribution in deductions(&analysis.score_breakdown) {
+        let _ = writeln!(out, "  {}  {}", style.paint(RED, &format!("{:>6.1} pts", -contribution.points_lost)), deduction(contribution));
+    }
+
+    if let Some(usage) = analysis.missout_pools.as_ref().filter(|u| u.pools > 0) {
+        let _ = writeln!(out, "\n{}", style.paint(BOLD, "Missout pools"));
+        let _ = writeln!(out, "  {}", pool_usage_summary(usage));
+        for pool in &usage.unsafe_pools {
+            let codes: Vec<&str> = pool.flags.iter().map(|f| f.code.as_str()).collect();
+            let _ = writeln!(out, "  {} {} ({})", style.paint(RED, &codes.join(", ")), pool.pool, pool.status);
+        }
+    }
+
+    let _ = writeln!(out, "\n{}", style.paint(BOLD, "Top reasons"));
+    for reason in analysis.reasons.iter().take(REASONS) {
+        let _ = writeln!(out, "  {}", reason);
+    }
+    out
+}
+
+/// The detectors that cost the most points, largest first
+fn deductions(breakdown: &ScoreBreakdown) -> impl Iterator<Item = &DetectorContribution> {
+    breakdown.detectors.iter().filter(|c| c.points_lost >= 0.05).take(DEDUCTIONS)
+}
+
+/// `Whale Concentration (HIGH): score 0.30 × confidence 0.90 × weight 1.5`
+fn deduction(c: &DetectorContribution) -> String {
+    let band = c.band.as_deref().map(|b| format!(" ({})", b)).unwrap_or_default();
+    format!("{}{}: score {:.2} × confidence {:.2} × weight {}", c.name, band, c.score, c.confidence, c.weight)
+}
+
+fn detector_line(signal: &PatternSignalOutput, name_width: usize, style: &Style) -> String {
+    let filled = (signal.score.clamp(0.0, 1.0) * BAR_WIDTH as f64).round() as usize;
+    let bar = format!("{}{}", "█".repeat(filled), "░".repeat(BAR_WIDTH - filled));
+    format!(
+        "{:<width$}  {} {:.2}  {}",
+        signal.name,
+        style.paint(signal_color(signal.score), &bar),
+        signal.score,
+        style.paint(DIM, &signal.details),
+        width = name_width,
+    )
+}
+
+fn metric_columns() -> impl Iterator<Item = &'static str> {
+    metric_rows(&SafetyMetrics::default()).into_iter().map(|(column, _, _)| column)
+}
+
+/// CSV header matching `csv_row`
+pub fn csv_header() -> String {
+    ["mint", "success", "safe_score", "risk_level"]
+        .into_iter()
+        .chain(metric_columns())
+        .chain(["score_delta", "deteriorating", "error"])
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// One CSV row of raw numbers (not the display strings, so spreadsheets can
+/// sum them); metric columns are empty for failed analyses
+pub fn csv_row(output: &AnalysisOutput) -> String {
+    let data = output.data.as_ref();
+    let mint = output.mint.as_deref().or(data.map(|a| a.mint_address.as_str()));
+    let mut fields = vec![
+        mint.unwrap_or_default().to_string(),
+        output.success.to_string(),
+        data.map(|a| format!("{:.2}", a.safe_score)).unwrap_or_default(),
+        data.map(|a| a.risk_level.clone()).unwrap_or_default(),
+    ];
+    let metrics = data.and_then(|a| serde_json::to_value(&a.metrics).ok()).unwrap_or_default();
+    fields.extend(metric_columns().map(|column| match &metrics[column] {
+        serde_json::Value::Null => String::new(),
+        value => value.to_string(),
+    }));
+    let trend = data.and_then(|a| a.trend.as_ref());
+    fields.push(trend.map(|t| format!("{:.2}", t.score_delta)).unwrap_or_default());
+    fields.push(trend.map(|t| t.deteriorating.to_string()).unwrap_or_default());
+    fields.push(output.error.clone().unwrap_or_default());
+    fields.iter().map(|f| csv_field(f)).collect::<Vec<_>>().join(",")
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Markdown without tables, which chat clients like Discord don't render
+pub fn markdown(output: &AnalysisOutput) -> String {
+    let Some(analysis) = &output.data else {
+        let mint = output.mint.as_deref().map(|m| format!(" `{}`", m)).unwrap_or_default();
+        return format!("**✗ Analysis failed**{}: {}\n", mint, output.error.as_deref().unwrap_or("unknown error"));
+    };
+    markdown_analysis(analysis)
+}
+
+fn markdown_analysis(analysis: &SafetyAnalysis) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "## Token safety: `{}`", analysis.mint_address);
+    let _ = writeln!(out, "**Score:** {:.1}/100 ({} risk)", analysis.safe_score, analysis.risk_level);
+    if let Some(ml) = &analysis.ml_score {
+        let _ = writeln!(out, "**ML rug probability:** {:.0}% (`{}`)", ml.rug_probability * 100.0, ml.model);
+    }
+    if let Some(trend) = &analysis.trend {
+        let flag = if trend.deteriorating { " ⚠️ **deteriorating**" } else { "" };
+        let _ = writeln!(out, "**Trend:** {}{}", trend_summary(trend), flag);
+    }
+    if let Some(completeness) = analysis.data_completeness.as_ref().filter(|c| !c.complete) {
+        let _ = writeln!(out, "⚠️ **Partial:** {}", missing_summary(completeness));
+    }
+    if let Some(matched) = &analysis.list_match {
+        let _ = writeln!(out, "**{}**", list_summary(matched));
+    }
+    if let Some(known) = &analysis.known_good {
+        let _ = writeln!(out, "**{}**", known_good_summary(known));
+    }
+    let _ = writeln!(out, "> {}\n", analysis.recommendation);
+
+    let _ = writeln!(out, "**Metrics**");
+    let benchmark = analysis.benchmark.as_ref();
+    for (column, label, value) in metric_rows(&analysis.metrics) {
+        if let Some(value) = value {
+            let note = percentile_note(benchmark, column).map(|p| format!(" ({})", p)).unwrap_or_default();
+            let _ = writeln!(out, "- {}: {}{}", label, value, note);
+        }
+    }
+    if let Some(benchmark) = benchmark {
+        let _ = writeln!(out, "_{}_", benchmark_summary(benchmark));
+    }
+
+    let _ = writeln!(out, "\n**Detectors**");
+    for signal in &analysis.pattern_signals {
+        let _ = writeln!(out, "- {} **{}** {:.2}: {}", signal_icon(signal.score), signal.name, signal.score, signal.details);
+    }
+
+    let _ = writeln!(out, "\n**Score breakdown:** `{}`", analysis.score_breakdown.formula);
+    for contribution in deductions(&analysis.score_breakdown) {
+        let _ = writeln!(out, "- {:.1} pts {}", -contribution.points_lost, deduction(contribution));
+    }
+
+    if let Some(usage) = analysis.missout_pools.as_ref().filter(|u| u.pools > 0) {
+        let _ = writeln!(out, "\n**Missout pools:** {}", pool_usage_summary(usage));
+        for pool in &usage.unsafe_pools {
+            let codes: Vec<&str> = pool.flags.iter().map(|f| f.code.as_str()).collect();
+            let _ = writeln!(out, "- ❌ `{}` ({}): {}", pool.pool, pool.status, codes.join(", "));
+        }
+    }
+
+    let _ = writeln!(out, "\n**Top reasons**");
+    for reason in analysis.reasons.iter().take(REASONS) {
+        let _ = writeln!(out, "- {}", reason);
+    }
+    out
+}
+
+/// Pool header, fees and flags, followed by the mint's terminal report
+pub fn pool_pretty(report: &PoolReport, color: bool) -> String {
+    let style = Style(color);
+    let mut out = String::new();
+    let risk = format!("{} RISK", report.risk_level.to_uppercase());
+    let _ = writeln!(out, "{} {}", style.paint(BOLD, "Pool Report"), report.pool);
+    let _ = writeln!(out, "{}", style.paint(&format!("{}{}", BOLD, risk_color(&report.risk_level)), &risk));
+    let _ = writeln!(out, "Mint {}  status {}  creator {}", report.mint, report.status, report.creator);
+    let _ = writeln!(out, "{}", pool_fees(report));
+    for flag in &report.flags {
+        let color = if flag.severity >= Severity::High { RED } else { YELLOW };
+        let _ = writeln!(out, "{} {}", style.paint(&format!("{}{}", BOLD, color), &flag.code), flag.message);
+    }
+    let _ = writeln!(out);
+    out + &pretty(&report.token, color)
+}
+
+/// `analyze-pool` as Markdown, the mint's report below the pool's
+pub fn pool_markdown(report: &PoolReport) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "## Missout pool: `{}`", report.pool);
+    let _ = writeln!(out, "**Risk:** {}", report.risk_level);
+    let _ = writeln!(out, "**Mint:** `{}` · **Status:** {} · **Creator:** `{}`", report.mint, report.status, report.creator);
+    let _ = writeln!(out, "{}", pool_fees(report));
+    for flag in &report.flags {
+        let icon = if flag.severity >= Severity::High { "❌" } else { "⚠️" };
+        let _ = writeln!(out, "- {} **{}**: {}", icon, flag.code, flag.message);
+    }
+    let _ = writeln!(out);
+    out + &markdown(&report.token)
+}
+
+/// `no holders, transactions data; 9 detectors report no data`
+fn missing_summary(completeness: &DataCompleteness) -> String {
+    let sources: Vec<&str> = completeness.missing.iter().map(|m| m.source.as_str()).collect();
+    format!(
+        "no {} data; {} detectors report no data",
+        sources.join(", "),
+        completeness.no_data_detectors.len(),
+    )
+}
+
+/// `DENIED: deployer <address> (rugged BONK2)`
+fn list_summary(matched: &ListMatch) -> String {
+    let reason = matched.reason.as_deref().map(|r| format!(" ({})", r)).unwrap_or_default();
+    match matched.list {
+        ListKind::Allow => format!("ALLOWED: mint {}{}, expensive checks skipped", matched.address, reason),
+        ListKind::Deny => format!("DENIED: {} {}{}", matched.subject.as_str(), matched.address, reason),
+    }
+}
+
+/// `KNOWN GOOD: USDC (USD Coin), stablecoin, checks skipped`
+fn known_good_summary(known: &KnownGoodMint) -> String {
+    format!("KNOWN GOOD: {} ({}), {}, checks skipped", known.symbol, known.name, known.category.as_str())
+}
+
+/// `3 pools, 2 active holding 1500 tokens, 1 flagged unsafe`
+fn pool_usage_summary(usage: &PoolUsage) -> String {
+    format!(
+        "{} pools, {} active holding {} tokens, {} flagged unsafe",
+        usage.pools,
+        usage.active_pools,
+        usage.total_value_locked,
+        usage.unsafe_pools.len(),
+    )
+}
+
+fn pool_fees(report: &PoolReport) -> String {
+    format!(
+        "Fees {:.1}% (dev {}, burn {}, treasury {} bps){}",
+        f64::from(report.total_fee_bps) / 100.0,
+        report.dev_fee_bps,
+        report.burn_fee_bps,
+        report.treasury_fee_bps,
+        if report.allow_mock { ", mock randomness allowed" } else { "" },
+    )
+}
+
+/// `diff` report: the score and risk move, then the changed signals and metrics
+pub fn diff_pretty(diff: &AnalysisDiff, color: bool) -> String {
+    let style = Style(color);
+    let mut out = String::new();
+    let _ = writeln!(out, "{} {}", style.paint(BOLD, "Analysis diff"), diff.mint);
+    let _ = writeln!(
+        out,
+        "Score {} ({:+.1})  {} -> {}",
+        style.paint(BOLD, &format!("{:.1} -> {:.1}", diff.previous_score, diff.score)),
+        diff.score_delta,
+        style.paint(risk_color(&diff.previous_risk_level), &diff.previous_risk_level.to_uppercase()),
+        style.paint(&format!("{}{}", BOLD, risk_color(&diff.risk_level)), &format!("{} RISK", diff.risk_level.to_uppercase())),
+    );
+    if let Some(note) = model_note(diff) {
+        let _ = writeln!(out, "{}", style.paint(YELLOW, &note));
+    }
+
+    let _ = writeln!(out, "\n{}", style.paint(BOLD, "Detectors"));
+    match &diff.signals {
+        None => {
+            let _ = writeln!(out, "  {}", style.paint(DIM, "not compared: the score history keeps no signals"));
+        }
+        Some(signals) if signals.is_empty() => {
+            let _ = writeln!(out, "  {}", style.paint(DIM, "no changes"));
+        }
+        Some(signals) => {
+            let width = signals.iter().map(|s| s.name.chars().count()).max().unwrap_or(0);
+            for signal in signals {
+                let (mark, color) = match signal.kind {
+                    SignalChangeKind::Added => ("+", signal_color(signal.score.unwrap_or(1.0))),
+                    SignalChangeKind::Removed => ("-", DIM),
+                    SignalChangeKind::Changed => ("~", signal_color(signal.score.unwrap_or(1.0))),
+                };
+                let line = format!("{} {:<width$}  {}", mark, signal.name, signal_move(signal), width = width);
+                let _ = writeln!(out, "  {}  {}", style.paint(color, &line), style.paint(DIM, &signal.details));
+            }
+        }
+    }
+
+    let _ = writeln!(out, "\n{}", style.paint(BOLD, "Metrics"));
+    if diff.metrics.is_empty() {
+        let _ = writeln!(out, "  {}", style.paint(DIM, "no changes"));
+    }
+    let rows: Vec<(String, String)> = diff.metrics.iter().map(|m| (metric_label(&m.metric), metric_move(m))).collect();
+    let width = rows.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+    for (label, change) in &rows {
+        let _ = writeln!(out, "  {:<width$}  {}", label, change, width = width);
+    }
+    out
+}
+
+/// `diff` report as Markdown, for pasting into a post-mortem
+pub fn diff_markdown(diff: &AnalysisDiff) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "## Analysis diff: `{}`", diff.mint);
+    let _ = writeln!(
+        out,
+        "**Score:** {:.1} -> {:.1} ({:+.1}), {} -> {} risk",
+        diff.previous_score, diff.score, diff.score_delta, diff.previous_risk_level, diff.risk_level,
+    );
+    if let Some(note) = model_note(diff) {
+        let _ = writeln!(out, "⚠️ {}", note);
+    }
+
+    let _ = writeln!(out, "\n**Detectors**");
+    match &diff.signals {
+        None => {
+            let _ = writeln!(out, "_Not compared: the score history keeps no signals_");
+        }
+        Some(signals) if signals.is_empty() => {
+            let _ = writeln!(out, "_No changes_");
+        }
+        Some(signals) => {
+            for signal in signals {
+                let kind = match signal.kind {
+                    SignalChangeKind::Added => "new",
+                    SignalChangeKind::Removed => "gone",
+                    SignalChangeKind::Changed => "changed",
+                };
+                let _ = writeln!(out, "- {} **{}** ({}) {}: {}", signal_icon(signal.score.unwrap_or(1.0)), signal.name, kind, signal_move(signal), signal.details);
+            }
+        }
+    }
+
+    let _ = writeln!(out, "\n**Metrics**");
+    if diff.metrics.is_empty() {
+        let _ = writeln!(out, "_No changes_");
+    }
+    for metric in &diff.metrics {
+        let _ = writeln!(out, "- {}: {}", metric_label(&metric.metric), metric_move(metric));
+    }
+    out
+}
+
+fn model_note(diff: &AnalysisDiff) -> Option<String> {
+    let previous = diff.previous_model_version.as_ref().filter(|_| diff.model_changed())?;
+    Some(format!("Scored by model {} before and {} now; the scores aren't directly comparable", previous, diff.model_version))
+}
+
+/// `0.80 -> 0.40 (SAFE -> HIGH)`, or the one score of an added or removed signal
+fn signal_move(signal: &SignalChange) -> String {
+    let score = |score: Option<f64>| score.map_or_else(|| "-".to_string(), |s| format!("{:.2}", s));
+    let band = |band: &Option<String>| band.as_deref().unwrap_or("-").to_string();
+    match signal.kind {
+        SignalChangeKind::Added => score(signal.score),
+        SignalChangeKind::Removed => score(signal.previous_score),
+        SignalChangeKind::Changed if signal.previous_band == signal.band => {
+            format!("{} -> {}", score(signal.previous_score), score(signal.score))
+        }
+        SignalChangeKind::Changed => format!(
+            "{} -> {} ({} -> {})",
+            score(signal.previous_score),
+            score(signal.score),
+            band(&signal.previous_band),
+            band(&signal.band),
+        ),
+    }
+}
+
+/// The report's label for a `SafetyMetrics` field, or the field name
+fn metric_label(metric: &str) -> String {
+    metric_rows(&SafetyMetrics::default())
+        .into_iter()
+        .find(|(column, _, _)| *column == metric)
+        .map_or_else(|| metric.to_string(), |(_, label, _)| label.to_string())
+}
+
+/// `120 -> 98 (-22)`
+fn metric_move(metric: &MetricChange) -> String {
+    let value = |value: &serde_json::Value| match value.as_f64() {
+        Some(number) => format_number(number, false),
+        None if value.is_null() => "-".to_string(),
+        None => value.to_string(),
+    };
+    let delta = metric.delta.map(|d| format!(" ({})", format_number(d, true))).unwrap_or_default();
+    format!("{} -> {}{}", value(&metric.previous), value(&metric.current), delta)
+}
+
+/// Whole numbers as such, small ones (prices) to 3 significant digits, the
+/// rest to 2 places
+fn format_number(number: f64, signed: bool) -> String {
+    let places = match number.abs() {
+        _ if number.fract() == 0.0 => 0,
+        abs if abs < 0.01 => (2 - abs.log10().floor() as i32) as usize,
+        _ => 2,
+    };
+    match signed {
+        true => format!("{:+.*}", places, number),
+        false => format!("{:.*}", places, number),
+    }
+}
+
+/// `postmortem` report: the rebuilt score, how much was replayed, the
+/// detectors that would have fired, then every detector
+#[cfg(feature = "native")]
+pub fn postmortem_pretty(postmortem: &Postmortem, color: bool) -> String {
+    let style = Style(color);
+    let analysis = &postmortem.analysis;
+    let mut out = String::new();
+    let _ = writeln!(out, "{} {} as of {}", style.paint(BOLD, "Post-mortem"), analysis.mint_address, utc(postmortem.at));
+    let risk = format!("{} RISK", analysis.risk_level.to_uppercase());
+    let _ = writeln!(
+        out,
+        "Score {}  {}",
+        style.paint(BOLD, &format!("{:.1}/100", analysis.safe_score)),
+        style.paint(&format!("{}{}", BOLD, risk_color(&analysis.risk_level)), &risk),
+    );
+    let _ = writeln!(out, "{}", style.paint(DIM, &replay_summary(postmortem)));
+
+    let _ = writeln!(out, "\n{}", style.paint(BOLD, "Would have fired"));
+    if postmortem.fired.is_empty() {
+        let _ = writeln!(out, "  {}", style.paint(DIM, "nothing rated high or worse"));
+    }
+    for reason in &postmortem.fired {
+        let severity = format!("{:<8}", format!("{:?}", reason.severity).to_uppercase());
+        let _ = writeln!(out, "  {}  {}", style.paint(RED, &severity), reason.message);
+    }
+
+    let _ = writeln!(out, "\n{}", style.paint(BOLD, "Detectors"));
+    let name_width = analysis.pattern_signals.iter().map(|s| s.name.chars().count()).max().unwrap_or(0);
+    for signal in &analysis.pattern_signals {
+        let _ = writeln!(out, "  {}", detector_line(signal, name_width, &style));
+    }
+    out
+}
+
+/// `postmortem` report as Markdown
+#[cfg(feature = "native")]
+pub fn postmortem_markdown(postmortem: &Postmortem) -> String {
+    let analysis = &postmortem.analysis;
+    let mut out = String::new();
+    let _ = writeln!(out, "## Post-mortem: `{}` as of {}", analysis.mint_address, utc(postmortem.at));
+    let _ = writeln!(out, "**Score:** {:.1}/100 ({} risk)", analysis.safe_score, analysis.risk_level);
+    let _ = writeln!(out, "_{}_", replay_summary(postmortem));
+
+    let _ = writeln!(out, "\n**Would have fired**");
+    if postmortem.fired.is_empty() {
+        let _ = writeln!(out, "_Nothing rated high or worse_");
+    }
+    for reason in &postmortem.fired {
+        let _ = writeln!(out, "- `{}` ({:?}): {}", reason.code, reason.severity, reason.message);
+    }
+
+    let _ = writeln!(out, "\n**Detectors**");
+    for signal in &analysis.pattern_signals {
+        let _ = writeln!(out, "- {} **{}** {:.2}: {}", signal_icon(signal.score), signal.name, signal.score, signal.details);
+    }
+    out
+}
+
+/// `Rebuilt from 300 of 1200 transactions; no launch or deployer history ...`
+#[cfg(feature = "native")]
+fn replay_summary(postmortem: &Postmortem) -> String {
+    let reach = match postmortem.reaches_creation {
+        true => "back to the mint's creation",
+        false => "not reaching the mint's creation, so without its launch or deployer",
+    };
+    let skipped: Vec<&str> = postmortem.skipped.iter().map(|s| s.as_str()).collect();
+    format!(
+        "Rebuilt from {} of {} transactions, {}; {} describe the present and are skipped",
+        postmortem.replayed_transactions,
+        postmortem.transactions_before,
+        reach,
+        skipped.join(", "),
+    )
+}
+
+#[cfg(feature = "native")]
+fn utc(timestamp: i64) -> String {
+    chrono::DateTime::from_timestamp(timestamp, 0)
+        .map_or_else(|| timestamp.to_string(), |t| t.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+}
+
+/// Backtest summary: the overall figures, then a row per detector
+pub fn backtest(report: &BacktestReport) -> String {
+    let percent = |value: Option<f64>| value.map_or_else(|| "-".to_string(), |v| format!("{:.1}%", v * 100.0));
+    let auc = |value: Option<f64>| value.map_or_else(|| "-".to_string(), |v| format!("{:.3}", v));
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "Backtest over {} tokens ({} rugs, {} legit, {} failed)",
+        report.samples, report.rugs, report.legit, report.failed,
+    );
+    let overall = &report.overall;
+    let _ = writeln!(
+        out,
+        "Overall: AUC {}, precision {}, recall {} (score below {})\n",
+        auc(overall.auc), percent(overall.precision), percent(overall.recall), report.cutoff,
+    );
+
+    let width = report.detectors.iter().map(|d| d.id.len()).max().unwrap_or(0).max("detector".len());
+    let _ = writeln!(out, "{:<width$}  {:>7}  {:>9}  {:>7}  {:>7}", "detector", "AUC", "precision", "recall", "samples");
+    for detector in &report.detectors {
+        let c = &detector.classification;
+        let _ = writeln!(
+            out,
+            "{:<width$}  {:>7}  {:>9}  {:>7}  {:>7}",
+            detector.id, auc(c.auc), percent(c.precision), percent(c.recall), detector.samples,
+        );
+    }
+    out
+}
+
+/// The closing summary of a `batch` run at a terminal
+pub fn batch_summary(summary: &BatchSummary) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "Batch: {} analyzed, {} failed in {:.1}s",
+        summary.analyzed, summary.failed, summary.elapsed_secs,
+    );
+    let _ = writeln!(out, "RPC: {} requests, {} cache hits", summary.rpc_requests, summary.cache_hits);
+    if !summary.risk_levels.is_empty() {
+        let levels: Vec<String> = summary.risk_levels.iter().map(|(level, count)| format!("{} {}", level, count)).collect();
+        let _ = writeln!(out, "Risk levels: {}", levels.join(", "));
+    }
+    let Some(scores) = &summary.scores else { return out };
+    let _ = writeln!(
+        out,
+        "Scores: min {:.1}, median {:.1}, mean {:.1}, max {:.1}",
+        scores.min, scores.median, scores.mean, scores.max,
+    );
+    let most = scores.histogram.iter().copied().max().unwrap_or(0).max(1);
+    for (bucket, &count) in scores.histogram.iter().enumerate() {
+        let from = bucket * BUCKET_POINTS;
+        let width = (count * BAR_WIDTH * 2).div_ceil(most);
+        let _ = writeln!(out, "  {:>3}-{:<3} {} {}", from, from + BUCKET_POINTS, "█".repeat(width), count);
+    }
+    out
+}
+
+/// `--dry-run-cost` table, most credits first
+#[cfg(feature = "native")]
+pub fn cost(estimate: &CostEstimate) -> String {
+    let width = estimate.methods.iter().map(|m| m.method.len()).max().unwrap_or(0).max("method".len());
+    let mut out = String::new();
+    let _ = writeln!(out, "{:<width$}  {:>5}  {:>7}", "method", "calls", "credits");
+    for method in &estimate.methods {
+        let _ = writeln!(out, "{:<width$}  {:>5}  {:>7}", method.method, method.calls, method.credits);
+    }
+    let _ = writeln!(out, "{:<width$}  {:>5}  {:>7}", "total", estimate.calls, estimate.credits);
+    out
+}
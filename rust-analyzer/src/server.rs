@@ -0,0 +1,193 @@
+//! HTTP mode (`analyze-token serve`): one warm analyzer shared by the trading
+//! bot and web UI instead of a process spawn per request.
+//!
+//! Clients are keyed by `x-api-key` (peer IP when absent) and rate limited per
+//! key. At most `max_concurrent` analyses run at once; up to `queue` more wait
+//! for a slot, and anything beyond that is rejected with 503. An analysis
+//! running past `--analysis-timeout` answers 504. On Ctrl-C the server stops
+//! accepting connections, rejects queued requests with 503 and lets the running
+//! analyses finish.
+//!
+//! `GET /metrics` serves Prometheus metrics: analyses by outcome, RPC requests
+//! and errors by method, cache hits and misses, and histograms of scores,
+//! analysis time and per-detector time.
+
+use std::net::SocketAddr;
+use std::num::NonZeroU32;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use axum::{
+    extract::{ConnectInfo, State},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use governor::{DefaultKeyedRateLimiter, Quota, RateLimiter};
+use metrics::{describe_counter, describe_histogram, Unit};
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+use serde::Deserialize;
+use serde_json::json;
+use tokio::sync::Semaphore;
+
+use crate::analysis::{AnalysisOutput, AnalysisTimeout, TokenAnalyzer};
+
+/// Score histogram buckets, one per ten points
+const SCORE_BUCKETS: &[f64] = &[10.0, 20.0, 30.0, 40.0, 50.0, 60.0, 70.0, 80.0, 90.0, 100.0];
+/// Buckets for the `_seconds` histograms: detectors take microseconds,
+/// analyses take seconds
+const LATENCY_BUCKETS: &[f64] = &[0.00001, 0.0001, 0.001, 0.01, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0];
+
+pub struct ServeConfig {
+    pub addr: SocketAddr,
+    /// Requests per minute per client key
+    pub rate_limit: NonZeroU32,
+    pub max_concurrent: usize,
+    pub queue: usize,
+}
+
+struct AppState {
+    analyzer: TokenAnalyzer,
+    limiter: DefaultKeyedRateLimiter<String>,
+    slots: Semaphore,
+    /// Requests running or waiting for a slot
+    pending: AtomicUsize,
+    capacity: usize,
+    metrics: PrometheusHandle,
+}
+
+#[derive(Deserialize)]
+struct AnalyzeRequest {
+    mint: String,
+}
+
+/// Decrements `pending` when the request finishes, however it finishes
+struct Pending<'a>(&'a AtomicUsize);
+
+impl Drop for Pending<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+fn reject(status: StatusCode, error: &str) -> Response {
+    (status, Json(json!({ "success": false, "error": error }))).into_response()
+}
+
+fn client_key(headers: &HeaderMap, peer: SocketAddr) -> String {
+    headers
+        .get("x-api-key")
+        .and_then(|v| v.to_str().ok())
+        .map(|key| format!("key:{}", key))
+        .unwrap_or_else(|| format!("ip:{}", peer.ip()))
+}
+
+async fn health() -> Json<serde_json::Value> {
+    Json(json!({ "status": "ok" }))
+}
+
+async fn metrics(State(state): State<Arc<AppState>>) -> Response {
+    let headers = [(axum::http::header::CONTENT_TYPE, "text/plain; version=0.0.4")];
+    (headers, state.metrics.render()).into_response()
+}
+
+/// Installs the global Prometheus recorder the analyzer's metrics go to
+fn install_metrics() -> Result<PrometheusHandle> {
+    let handle = PrometheusBuilder::new()
+        .set_buckets_for_metric(Matcher::Full("analyzer_score".to_string()), SCORE_BUCKETS)?
+        .set_buckets_for_metric(Matcher::Suffix("_seconds".to_string()), LATENCY_BUCKETS)?
+        .install_recorder()?;
+    describe_counter!("analyzer_analyses_total", "Analyses performed, by outcome");
+    describe_counter!("analyzer_rpc_requests_total", "RPC calls made (cache misses), by method");
+    describe_counter!("analyzer_rpc_errors_total", "RPC calls that failed on every endpoint or returned an error, by method");
+    describe_counter!("analyzer_rpc_credits_total", "RPC credits spent, by endpoint (per Helius API key)");
+    describe_counter!("analyzer_cache_hits_total", "RPC calls answered from the cache");
+    describe_counter!("analyzer_cache_misses_total", "RPC calls the cache had no fresh response for");
+    describe_histogram!("analyzer_score", "Safety scores of successful analyses (0-100)");
+    describe_histogram!("analyzer_analysis_duration_seconds", Unit::Seconds, "Time per analysis, RPC included");
+    describe_histogram!("analyzer_detector_duration_seconds", Unit::Seconds, "Time per detector run, by detector");
+    Ok(handle)
+}
+
+async fn analyze(
+    State(state): State<Arc<AppState>>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Json(request): Json<AnalyzeRequest>,
+) -> Response {
+    let mint = request.mint.trim().to_string();
+    if mint.is_empty() {
+        return reject(StatusCode::BAD_REQUEST, "missing mint");
+    }
+    if state.limiter.check_key(&client_key(&headers, peer)).is_err() {
+        return reject(StatusCode::TOO_MANY_REQUESTS, "rate limit exceeded");
+    }
+
+    if state.pending.fetch_add(1, Ordering::AcqRel) >= state.capacity {
+        state.pending.fetch_sub(1, Ordering::AcqRel);
+        return reject(StatusCode::SERVICE_UNAVAILABLE, "analysis queue is full");
+    }
+    let _pending = Pending(&state.pending);
+    let Ok(_slot) = state.slots.acquire().await else {
+        return reject(StatusCode::SERVICE_UNAVAILABLE, "server is shutting down");
+    };
+
+    let result = state.analyzer.analyze(&mint).await;
+    let status = match &result {
+        Ok(_) => StatusCode::OK,
+        Err(e) if e.is::<AnalysisTimeout>() => StatusCode::GATEWAY_TIMEOUT,
+        Err(_) => StatusCode::BAD_GATEWAY,
+    };
+    let mut output = AnalysisOutput::new(result, state.analyzer.model_version());
+    output.mint = Some(mint);
+    (status, Json(output)).into_response()
+}
+
+pub async fn serve(analyzer: TokenAnalyzer, config: ServeConfig) -> Result<()> {
+    let state = Arc::new(AppState {
+        analyzer,
+        limiter: RateLimiter::keyed(Quota::per_minute(config.rate_limit)),
+        slots: Semaphore::new(config.max_concurrent),
+        pending: AtomicUsize::new(0),
+        capacity: config.max_concurrent + config.queue,
+        metrics: install_metrics()?,
+    });
+
+    // Forget idle clients so the key map doesn't grow without bound, and
+    // bring benched RPC endpoints back once they answer `getHealth` again;
+    // the recorder also needs periodic upkeep to drain its histograms
+    let housekeeping = state.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(30));
+        loop {
+            interval.tick().await;
+            housekeeping.limiter.retain_recent();
+            housekeeping.limiter.shrink_to_fit();
+            housekeeping.metrics.run_upkeep();
+            housekeeping.analyzer.check_rpc_health().await;
+        }
+    });
+
+    let app = Router::new()
+        .route("/health", get(health))
+        .route("/analyze", post(analyze))
+        .route("/metrics", get(metrics))
+        .with_state(state.clone());
+
+    let listener = tokio::net::TcpListener::bind(config.addr)
+        .await
+        .with_context(|| format!("binding {}", config.addr))?;
+    tracing::info!("analyze-token listening on http://{}", config.addr);
+
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+        .with_graceful_shutdown(async move {
+            let _ = tokio::signal::ctrl_c().await;
+            tracing::info!("shutting down, finishing the analyses in flight");
+            // Requests still waiting for a slot get 503 instead of an analysis
+            state.slots.close();
+        })
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,30 @@
+//! wasm32 entry points for the web front-end: the CLI's detectors and scoring,
+//! run over a `TokenContext` the page assembled from data it already has, so a
+//! threshold tweak re-scores instantly instead of round-tripping to the backend.
+
+use wasm_bindgen::prelude::*;
+
+use crate::analysis::patterns::TokenContext;
+use crate::analysis::scoring::ScoringConfig;
+use crate::analysis::{detectors::DetectorRegistry, score};
+
+/// Scores a JSON `TokenContext` and returns the `SafetyAnalysis` as JSON.
+/// `scoring` is an optional TOML scoring config, as for `--config`.
+#[wasm_bindgen(js_name = scoreToken)]
+pub fn score_token(context: &str, scoring: Option<String>) -> Result<String, JsError> {
+    let context: TokenContext = serde_json::from_str(context)?;
+    let scoring = match scoring {
+        Some(toml) => toml::from_str(&toml)?,
+        None => ScoringConfig::default(),
+    };
+    let detectors = DetectorRegistry::default()
+        .configure(&scoring)
+        .map_err(|e| JsError::new(&format!("{:#}", e)))?;
+    Ok(serde_json::to_string(&score(context, &detectors, &scoring.risk))?)
+}
+
+/// The built-in scoring config (every weight and threshold) as TOML
+#[wasm_bindgen(js_name = defaultScoring)]
+pub fn default_scoring() -> Result<String, JsError> {
+    Ok(toml::to_string_pretty(&DetectorRegistry::default().scoring_template())?)
+}
@@ -0,0 +1,119 @@
+//! Backtest grading and weight calibration on hand-built samples, where the
+//! right precision, recall, AUC and weights can be worked out by hand.
+
+use std::collections::BTreeMap;
+
+use token_analyzer::analysis::backtest::{
+    calibrate, parse_dataset, BacktestReport, Classification, LabeledToken, Sample, SampleSignal, WEIGHT_GRID,
+};
+
+fn sample(mint: &str, rug: bool, safe_score: f64, detector_score: f64) -> Sample {
+    let signal = SampleSignal { score: detector_score, confidence: 1.0, weight: 1.0 };
+    Sample {
+        mint: mint.to_string(),
+        rug,
+        safe_score,
+        model_version: "v2".to_string(),
+        detectors: BTreeMap::from([("holder-count".to_string(), signal)]),
+    }
+}
+
+#[test]
+fn dataset_accepts_commas_whitespace_and_comments() {
+    let tokens = parse_dataset("# outcomes\nMintA rug\n\nMintB,legit\n  MintC\tRUG  \n").unwrap();
+    assert_eq!(
+        tokens,
+        vec![
+            LabeledToken { mint: "MintA".to_string(), rug: true },
+            LabeledToken { mint: "MintB".to_string(), rug: false },
+            LabeledToken { mint: "MintC".to_string(), rug: true },
+        ]
+    );
+    assert!(parse_dataset("MintA scam").unwrap_err().to_string().contains("line 1"));
+    assert!(parse_dataset("MintA").is_err());
+}
+
+#[test]
+fn perfectly_separating_scores_grade_perfectly() {
+    let samples = [
+        sample("rug1", true, 10.0, 0.0),
+        sample("rug2", true, 30.0, 0.2),
+        sample("legit1", false, 60.0, 0.9),
+        sample("legit2", false, 90.0, 1.0),
+    ];
+    let report = BacktestReport::new(&samples, 1, 50.0);
+    assert_eq!((report.samples, report.rugs, report.legit, report.failed), (4, 2, 2, 1));
+    assert_eq!(report.overall.auc, Some(1.0));
+    assert_eq!(report.overall.precision, Some(1.0));
+    assert_eq!(report.overall.recall, Some(1.0));
+    assert_eq!(report.detectors[0].id, "holder-count");
+    assert_eq!(report.detectors[0].classification.auc, Some(1.0));
+
+    let last = report.roc.last().unwrap();
+    assert_eq!((last.true_positive_rate, last.false_positive_rate), (1.0, 1.0));
+    let at_rugs = report.roc.iter().find(|p| p.cutoff == 30.0).unwrap();
+    assert_eq!((at_rugs.true_positive_rate, at_rugs.false_positive_rate), (1.0, 0.0));
+}
+
+#[test]
+fn classification_counts_and_ties() {
+    // Flagged: 20 (rug), 40 (legit); missed: 70 (rug); cleared: 80 (legit)
+    let points = [(20.0, true), (40.0, false), (70.0, true), (80.0, false)];
+    let c = Classification::new(&points, 50.0);
+    assert_eq!((c.true_positives, c.false_positives, c.false_negatives, c.true_negatives), (1, 1, 1, 1));
+    assert_eq!(c.precision, Some(0.5));
+    assert_eq!(c.recall, Some(0.5));
+    // Rug-below-legit pairs: 20<40, 20<80, 70<80 of 4
+    assert_eq!(c.auc, Some(0.75));
+
+    let tied = Classification::new(&[(50.0, true), (50.0, false)], 50.0);
+    assert_eq!(tied.auc, Some(0.5));
+    assert_eq!(tied.precision, None, "nothing scored below the cutoff");
+
+    let one_label = Classification::new(&[(10.0, true)], 50.0);
+    assert_eq!(one_label.auc, None);
+}
+
+/// A sample where `holder-count` tells rugs apart and `token-age` points the
+/// wrong way
+fn mixed(mint: &str, rug: bool) -> Sample {
+    let signal = |score: f64, weight: f64| SampleSignal { score, confidence: 1.0, weight };
+    let (holders, age) = if rug { (0.1, 0.9) } else { (0.9, 0.1) };
+    Sample {
+        mint: mint.to_string(),
+        rug,
+        safe_score: 50.0,
+        model_version: "v2".to_string(),
+        detectors: BTreeMap::from([
+            ("holder-count".to_string(), signal(holders, 0.1)),
+            ("token-age".to_string(), signal(age, 0.3)),
+        ]),
+    }
+}
+
+#[test]
+fn rescore_matches_the_composite_formula() {
+    let sample = mixed("rug", true);
+    // (0.1 × 0.1 + 0.9 × 0.3) / 0.4 × 100
+    assert!((sample.rescore(&BTreeMap::new()) - 70.0).abs() < 1e-9);
+    let weights = BTreeMap::from([("token-age".to_string(), 0.0)]);
+    assert!((sample.rescore(&weights) - 10.0).abs() < 1e-9);
+}
+
+#[test]
+fn calibration_drops_the_misleading_detector() {
+    let samples = [mixed("rug1", true), mixed("rug2", true), mixed("legit1", false), mixed("legit2", false)];
+    // holder-count needs a weight above token-age's 0.3 to win on its own,
+    // which this grid doesn't offer, so token-age has to go
+    let calibration = calibrate(&samples, &[0.0, 0.1, 0.2], 10).unwrap();
+    assert_eq!(calibration.auc_before, 0.0);
+    assert_eq!(calibration.auc_after, 1.0);
+    assert_eq!(calibration.weights["token-age"], 0.0);
+    assert!(calibration.weights["holder-count"] > 0.0);
+    assert_eq!(calibration.scoring().detectors["token-age"].weight, Some(0.0));
+
+    assert!(calibrate(&samples[..2], &WEIGHT_GRID, 10).is_err(), "rugs only");
+    let mut other_model = samples.to_vec();
+    other_model[3].model_version = "v1".to_string();
+    assert!(calibrate(&other_model, &WEIGHT_GRID, 10).is_err());
+}
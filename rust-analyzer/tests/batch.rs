@@ -0,0 +1,57 @@
+//! Batch progress: the tally of printed results, the score distribution of the
+//! closing summary, and the NDJSON events on stderr.
+
+use anyhow::anyhow;
+use serde_json::json;
+use token_analyzer::analysis::batch::{BatchEvent, BatchTally, ScoreDistribution};
+use token_analyzer::{AnalysisOutput, SafetyAnalysis};
+
+const FIXTURE: &str = "tests/fixtures/7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU/expected.json";
+
+/// The fixture analysis rescored, or a failure for `None`
+fn output(score: Option<(f64, &str)>) -> AnalysisOutput {
+    let Some((safe_score, risk_level)) = score else {
+        return AnalysisOutput::new(Err(anyhow!("timed out")), "v7");
+    };
+    let mut analysis: SafetyAnalysis = serde_json::from_str(&std::fs::read_to_string(FIXTURE).unwrap()).unwrap();
+    analysis.safe_score = safe_score;
+    analysis.risk_level = risk_level.to_string();
+    AnalysisOutput::new(Ok(analysis), "v7")
+}
+
+#[test]
+fn scores_are_summarized_into_ten_point_buckets() {
+    let distribution = ScoreDistribution::of(&[95.0, 12.0, 100.0, 40.0]).unwrap();
+    assert_eq!((distribution.min, distribution.median, distribution.max), (12.0, 67.5, 100.0));
+    assert_eq!(distribution.mean, 61.75);
+    // 100 lands in the top bucket with 95
+    assert_eq!(distribution.histogram, [0, 1, 0, 0, 1, 0, 0, 0, 0, 2]);
+
+    assert_eq!(ScoreDistribution::of(&[30.0, 10.0, 20.0]).unwrap().median, 20.0);
+    assert!(ScoreDistribution::of(&[]).is_none());
+}
+
+#[test]
+fn progress_and_summary_are_tagged_json_lines() {
+    let mut tally = BatchTally::default();
+    for result in [Some((80.0, "low")), None, Some((20.0, "critical")), Some((85.0, "low"))] {
+        tally.record(&output(result));
+    }
+    assert_eq!((tally.done(), tally.analyzed(), tally.failed()), (4, 3, 1));
+
+    let progress = serde_json::to_value(tally.progress(Some(10), 2.5)).unwrap();
+    assert_eq!(
+        progress,
+        json!({ "event": "progress", "done": 4, "total": 10, "analyzed": 3, "failed": 1, "elapsed_secs": 2.5 }),
+    );
+    // Reading stdin there is no total
+    let progress = serde_json::to_value(tally.progress(None, 2.5)).unwrap();
+    assert!(progress.get("total").is_none());
+
+    let summary = serde_json::to_value(BatchEvent::Summary(tally.summary(7, 120, 3.0))).unwrap();
+    assert_eq!(summary["event"], "summary");
+    assert_eq!((summary["analyzed"].as_u64(), summary["failed"].as_u64()), (Some(3), Some(1)));
+    assert_eq!((summary["cache_hits"].as_u64(), summary["rpc_requests"].as_u64()), (Some(7), Some(120)));
+    assert_eq!(summary["risk_levels"], json!({ "critical": 1, "low": 2 }));
+    assert_eq!(summary["scores"]["median"], 80.0);
+}
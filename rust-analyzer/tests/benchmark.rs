@@ -0,0 +1,57 @@
+//! Benchmark percentiles: ranking metrics against a population, and the score
+//! history supplying the latest analysis of each other recent mint as that
+//! population.
+
+use token_analyzer::analysis::benchmark::{self, Benchmark, MIN_POPULATION};
+use token_analyzer::analysis::history::ScoreHistory;
+use token_analyzer::{SafetyAnalysis, SafetyMetrics};
+
+const FIXTURE: &str = "tests/fixtures/7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU/expected.json";
+
+fn metrics(holders: usize, top10: f64) -> SafetyMetrics {
+    SafetyMetrics { holder_count: holders, distribution_top10: top10, ..SafetyMetrics::default() }
+}
+
+#[test]
+fn percentiles_count_ties_as_half() {
+    let population = [1.0, 2.0, 3.0, 4.0];
+    assert_eq!(benchmark::percentile(0.5, population.into_iter()), 0.0);
+    assert_eq!(benchmark::percentile(2.0, population.into_iter()), 37.5);
+    assert_eq!(benchmark::percentile(9.0, population.into_iter()), 100.0);
+}
+
+#[test]
+fn metrics_are_ranked_once_the_population_is_large_enough() {
+    let population: Vec<SafetyMetrics> = (0..MIN_POPULATION).map(|i| metrics(i * 10, i as f64 * 5.0)).collect();
+    assert!(Benchmark::against(&metrics(50, 38.0), &population[1..]).is_none());
+
+    let benchmark = Benchmark::against(&metrics(50, 38.0), &population).unwrap();
+    assert_eq!(benchmark.population, MIN_POPULATION);
+    // 5 of 20 hold fewer, one ties
+    assert_eq!(benchmark.holder_count, 27.5);
+    // 38% top 10 is above 0%..35% (8 tokens)
+    assert_eq!(benchmark.distribution_top10, 40.0);
+    assert_eq!(benchmark.percentile_of("distribution_top10"), Some(40.0));
+    // Nobody in the population recorded velocity
+    assert_eq!(benchmark.transactions_last_hour, None);
+}
+
+#[test]
+fn the_population_is_the_latest_run_of_each_other_mint() {
+    let path = std::env::temp_dir().join(format!("benchmark-{}.sqlite3", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let history = ScoreHistory::open(&path).unwrap();
+    let mut analysis: SafetyAnalysis = serde_json::from_str(&std::fs::read_to_string(FIXTURE).unwrap()).unwrap();
+
+    for (mint, holders) in [("A", 1), ("A", 2), ("B", 3), ("C", 4)] {
+        analysis.mint_address = mint.to_string();
+        analysis.metrics.holder_count = holders;
+        history.record(&analysis, "v1").unwrap();
+    }
+    let holders = |population: Vec<SafetyMetrics>| population.iter().map(|m| m.holder_count).collect::<Vec<_>>();
+    assert_eq!(holders(history.population("C", 0, 10).unwrap()), [3, 2]);
+    assert_eq!(holders(history.population("C", 0, 1).unwrap()), [3]);
+    // Nothing recorded in the future
+    assert!(history.population("C", i64::MAX, 10).unwrap().is_empty());
+    let _ = std::fs::remove_file(&path);
+}
@@ -0,0 +1,102 @@
+//! Bundled launches: Jito-tipped slots where several wallets bought at launch,
+//! the age of the wallets in them, and the detector scoring both.
+
+mod common;
+
+use common::{band, mint_fixture, replay_analyzer, token_context, MINT};
+use serde_json::{json, Value};
+use token_analyzer::analysis::detectors::BundlerDetector;
+use token_analyzer::analysis::fixture::{RecordedCall, RpcFixture};
+use token_analyzer::analysis::launch::JITO_TIP_ACCOUNTS;
+use token_analyzer::TokenContext;
+
+const TRADER: &str = "Trader1111111111111111111111111111111111111";
+const BUNDLER: &str = "Bund1er1111111111111111111111111111111111111";
+
+/// A launch at slot 100 out of a 1,000,000 supply, with a bundle of two
+/// wallets buying `tokens` each
+fn context(bundle: Option<(f64, Option<usize>)>) -> TokenContext {
+    let bundles: Vec<Value> = bundle
+        .into_iter()
+        .map(|(tokens, fresh)| {
+            json!({
+                "slot": 100,
+                "block_time": 1_700_000_000,
+                "buyers": [{ "wallet": "W1", "tokens": tokens }, { "wallet": "W2", "tokens": tokens }],
+                "fresh_wallets": fresh,
+            })
+        })
+        .collect();
+    token_context(json!({
+        "launch": { "launch_slot": 100, "snipers": [], "insiders": [], "bundles": bundles },
+    }))
+}
+
+#[test]
+fn bundles_are_scored_by_the_supply_bought_and_fresh_wallets() {
+    let detector = BundlerDetector::default();
+
+    assert_eq!(band(&detector, &context(None)), (1.0, "SAFE".to_string()));
+    assert_eq!(band(&detector, &context(Some((1_000.0, Some(0))))), (0.7, "MINOR".to_string()));
+    assert_eq!(band(&detector, &context(Some((1_000.0, Some(2))))), (0.3, "BUNDLED".to_string()));
+    assert_eq!(band(&detector, &context(Some((60_000.0, None)))), (0.3, "BUNDLED".to_string()));
+    assert_eq!(band(&detector, &context(Some((150_000.0, Some(2))))), (0.0, "CRITICAL".to_string()));
+
+    let mut unreached = context(None);
+    unreached.launch = None;
+    assert_eq!(band(&detector, &unreached).1, "UNKNOWN");
+}
+
+/// The mint signature page the launch walk reads
+fn launch_signatures(fixture: &mut RpcFixture) -> &mut Vec<Value> {
+    let call = fixture
+        .calls
+        .iter_mut()
+        .find(|c| c.method == "getSignaturesForAddress" && c.params == json!([MINT, { "limit": 1000 }]))
+        .unwrap();
+    call.result.as_array_mut().unwrap()
+}
+
+fn transaction<'a>(fixture: &'a mut RpcFixture, signature: &str) -> &'a mut Value {
+    let call = fixture
+        .calls
+        .iter_mut()
+        .find(|c| c.method == "getTransaction" && c.params[0] == signature)
+        .unwrap();
+    &mut call.result
+}
+
+#[tokio::test]
+async fn same_slot_buys_with_a_jito_tip_are_a_bundle() {
+    let mut fixture = mint_fixture();
+    // `s3`, a buy two slots after the launch buy `s1`, moves into its slot
+    // from another wallet, and `s1` tips Jito
+    let s3 = launch_signatures(&mut fixture).iter_mut().find(|s| s["signature"] == "s3").unwrap();
+    s3["slot"] = json!(1001);
+    let bought = transaction(&mut fixture, "s3");
+    *bought = serde_json::from_str(&bought.to_string().replace(TRADER, BUNDLER)).unwrap();
+    let tip = json!({
+        "program": "system",
+        "parsed": { "type": "transfer", "info": { "source": TRADER, "destination": JITO_TIP_ACCOUNTS[0], "lamports": 10_000 } },
+    });
+    let instructions = transaction(&mut fixture, "s1")["transaction"]["message"]["instructions"].as_array_mut().unwrap();
+    instructions.push(tip);
+    // The bundler's first transaction came an hour before, the trader's long before
+    for (wallet, first_seen) in [(BUNDLER, 1_699_996_400), (TRADER, 1_600_000_000)] {
+        fixture.calls.push(RecordedCall {
+            method: "getSignaturesForAddress".to_string(),
+            params: json!([wallet, { "limit": 1000 }]),
+            result: json!([{ "signature": format!("first-{}", wallet), "slot": 1, "blockTime": first_seen, "err": null }]),
+        });
+    }
+
+    let analysis = replay_analyzer(fixture, |_| {}).analyze(MINT).await.unwrap();
+    let bundles = &analysis.launch.as_ref().unwrap().bundles;
+    assert_eq!(bundles.len(), 1);
+    assert_eq!((bundles[0].slot, bundles[0].buyers.len(), bundles[0].fresh_wallets), (1001, 2, Some(1)));
+
+    // 10 of the 78 tokens bundled
+    let signal = analysis.pattern_signals.iter().find(|s| s.name == "Bundled Launch").unwrap();
+    assert_eq!(signal.score, 0.3, "{}", signal.details);
+    assert!(signal.details.contains("1 freshly funded"), "{}", signal.details);
+}
@@ -0,0 +1,80 @@
+//! Watch mode's holder changes: comparing two runs of the recorded fixture's
+//! analysis, with holders and pool depth moved between them.
+
+use token_analyzer::analysis::changes::{holder_changes, ChangeKind, ChangeThresholds};
+use token_analyzer::SafetyAnalysis;
+
+const FIXTURE: &str = "tests/fixtures/7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU/expected.json";
+
+fn analysis() -> SafetyAnalysis {
+    serde_json::from_str(&std::fs::read_to_string(FIXTURE).unwrap()).unwrap()
+}
+
+fn kinds(previous: &SafetyAnalysis, current: &SafetyAnalysis) -> Vec<ChangeKind> {
+    holder_changes(previous, current, &ChangeThresholds::default()).iter().map(|c| c.kind).collect()
+}
+
+#[test]
+fn unchanged_runs_raise_nothing() {
+    assert!(kinds(&analysis(), &analysis()).is_empty());
+}
+
+#[test]
+fn whale_exits_are_matched_by_wallet() {
+    let previous = analysis();
+    let mut current = analysis();
+    // The largest wallet holds H11 and H7, merged into one holder of 20;
+    // emptying only H11 leaves it H7's 8
+    let whale = current.top_holders.iter_mut().find(|h| h.address == "H11").unwrap();
+    assert_eq!(whale.merged_accounts, ["H7"]);
+    whale.address = whale.merged_accounts.remove(0);
+    whale.balance = 8.0;
+    let changes = holder_changes(&previous, &current, &ChangeThresholds::default());
+    assert_eq!(changes.len(), 1, "{:?}", changes);
+    assert_eq!(changes[0].kind, ChangeKind::WhaleExit);
+    assert_eq!(changes[0].holder.as_deref(), Some("4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi"));
+    assert!((changes[0].change_percent.unwrap() + 60.0).abs() < 1e-9);
+
+    // Under a 60% bar the same move is no exit
+    let lenient = ChangeThresholds { whale_exit: 60.0, ..ChangeThresholds::default() };
+    assert!(holder_changes(&previous, &current, &lenient).is_empty());
+}
+
+#[test]
+fn new_whales_and_grown_positions_alert() {
+    let previous = analysis();
+    let mut current = analysis();
+    current.top_holders[1].owner = Some("NewWhaLe11111111111111111111111111111111111".to_string());
+    current.top_holders[2].balance *= 3.0;
+    let changes = holder_changes(&previous, &current, &ChangeThresholds::default());
+
+    let accumulated: Vec<_> = changes.iter().filter(|c| c.kind == ChangeKind::WhaleAccumulation).collect();
+    assert_eq!(accumulated.len(), 2, "{:?}", changes);
+    assert_eq!(accumulated[0].change_percent, None);
+    assert!(accumulated[0].message.starts_with("new whale NewWhaLe"));
+    assert!((accumulated[1].change_percent.unwrap() - 200.0).abs() < 1e-9);
+    // The wallet pushed out of H10 sold out of the largest accounts
+    assert!(changes.iter().any(|c| c.kind == ChangeKind::WhaleExit));
+}
+
+#[test]
+fn pool_depth_drops_are_lp_withdrawals() {
+    let previous = analysis();
+    let mut current = analysis();
+    current.metrics.liquidity_sol = 10.0;
+    assert!(kinds(&previous, &current).is_empty());
+
+    current.metrics.liquidity_sol = 2.5;
+    let changes = holder_changes(&previous, &current, &ChangeThresholds::default());
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].kind, ChangeKind::LpWithdrawal);
+    assert_eq!(changes[0].message, "SOL pool depth fell 80% (12.50 -> 2.50)");
+}
+
+#[test]
+fn missing_holders_say_nothing_about_moves() {
+    let previous = analysis();
+    let mut current = analysis();
+    current.top_holders.clear();
+    assert!(kinds(&previous, &current).is_empty());
+}
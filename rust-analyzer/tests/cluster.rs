@@ -0,0 +1,63 @@
+//! Devnet support: program ids, storage paths and known addresses per cluster,
+//! and the recorded fixture analyzed as a devnet mint.
+
+mod common;
+
+use std::path::Path;
+
+use common::{mint_fixture, replay_analyzer, MINT};
+use token_analyzer::analysis::cluster::Cluster;
+use token_analyzer::analysis::known::{KnownAddresses, KnownKind};
+
+const RAYDIUM_AMM_V4: &str = "675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8";
+const RAYDIUM_CPMM: &str = "CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C";
+const ORCA_WHIRLPOOL: &str = "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc";
+const BINANCE: &str = "5tzFkiKscXHK5ZXCGbXZxdw7gTjjD1mBwuoFbhUvuAi9";
+
+#[test]
+fn devnet_moves_raydium_and_keeps_the_rest() {
+    assert_eq!(Cluster::Mainnet.program(RAYDIUM_AMM_V4), RAYDIUM_AMM_V4);
+    assert_eq!(Cluster::Devnet.program(RAYDIUM_AMM_V4), "HWy1jotHpo6UqeQxx49dpYYdQB8wj9Qk9MdxwjLvDHB8");
+    assert_eq!(Cluster::Devnet.program(ORCA_WHIRLPOOL), ORCA_WHIRLPOOL);
+    assert_eq!(Cluster::Custom.program(RAYDIUM_AMM_V4), RAYDIUM_AMM_V4);
+
+    assert_eq!("devnet".parse::<Cluster>().unwrap(), Cluster::Devnet);
+    assert!("testnet".parse::<Cluster>().is_err());
+    assert_eq!(Cluster::Custom.default_rpc_url(), None);
+}
+
+#[test]
+fn storage_is_kept_apart_per_cluster() {
+    let path = Path::new("/tmp/analyze-token/cache.sqlite3");
+    assert_eq!(Cluster::Mainnet.namespaced(path), path);
+    assert_eq!(Cluster::Devnet.namespaced(path), Path::new("/tmp/analyze-token/cache-devnet.sqlite3"));
+}
+
+#[test]
+fn devnet_knows_no_mainnet_wallets() {
+    let mainnet = KnownAddresses::bundled(Cluster::Mainnet);
+    let devnet = KnownAddresses::bundled(Cluster::Devnet);
+    assert_eq!(mainnet.get(BINANCE).unwrap().kind, KnownKind::Exchange);
+    assert!(devnet.get(BINANCE).is_none());
+    assert!(devnet.get(RAYDIUM_AMM_V4).is_none());
+    assert!(devnet.get("HWy1jotHpo6UqeQxx49dpYYdQB8wj9Qk9MdxwjLvDHB8").is_some());
+    assert!(devnet.get("1nc1nerator11111111111111111111111111111111").is_some());
+}
+
+#[tokio::test]
+async fn devnet_analyses_search_devnet_pools() {
+    let mut fixture = mint_fixture();
+    // The recorded pool searches ran against mainnet Raydium; on devnet the
+    // same answers have to come from the devnet programs
+    for call in fixture.calls.iter_mut().filter(|c| c.method == "getProgramAccounts") {
+        if let Some(program) = [RAYDIUM_AMM_V4, RAYDIUM_CPMM].into_iter().find(|p| call.params[0] == *p) {
+            call.params[0] = Cluster::Devnet.program(program).into();
+        }
+    }
+
+    let analyzer = replay_analyzer(fixture, |config| config.cluster = Cluster::Devnet);
+    let analysis = analyzer.analyze(MINT).await.unwrap();
+    let liquidity = analysis.liquidity.unwrap();
+    assert_eq!(liquidity.pools.len(), 1);
+    assert_eq!(liquidity.pools[0].dex, "raydium-amm-v4");
+}
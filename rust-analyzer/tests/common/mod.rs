@@ -0,0 +1,70 @@
+//! Helpers shared by the integration tests: a bare `TokenContext` each test
+//! fills in with the fields its detector reads, the band a detector reports,
+//! and analyzers replaying a recorded fixture.
+
+#![allow(dead_code)]
+
+#[cfg(feature = "native")]
+use std::path::Path;
+
+use serde_json::{json, Value};
+#[cfg(feature = "native")]
+use token_analyzer::analysis::fixture::RpcFixture;
+use token_analyzer::{PatternDetector, TokenContext};
+#[cfg(feature = "native")]
+use token_analyzer::{AnalyzerConfig, TokenAnalyzer};
+
+/// The recorded fixture's mint, see `tests/fixtures`
+pub const MINT: &str = "7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU";
+
+/// A day-old SPL Token mint of 1,000,000 tokens with no decimals, authorities,
+/// holders or transactions; top-level `fields` replace the defaults
+pub fn token_context(fields: Value) -> TokenContext {
+    let mut context = json!({
+        "mint": MINT,
+        "holders": [],
+        "transactions": [],
+        "creation_time": 1_700_000_000,
+        "current_time": 1_700_086_400,
+        "mint_info": {
+            "program": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+            "decimals": 0,
+            "supply": 1_000_000u64,
+            "mint_authority": null,
+            "freeze_authority": null,
+        },
+    });
+    if let (Some(context), Value::Object(fields)) = (context.as_object_mut(), fields) {
+        context.extend(fields);
+    }
+    serde_json::from_value(context).unwrap()
+}
+
+/// `detector`'s score on `ctx` and its band, the details up to the first `:`
+pub fn band(detector: &impl PatternDetector, ctx: &TokenContext) -> (f64, String) {
+    let signal = detector.detect(ctx);
+    (signal.score, signal.details.split(':').next().unwrap().to_string())
+}
+
+/// The recorded fixture of `MINT`
+#[cfg(feature = "native")]
+pub fn mint_fixture() -> RpcFixture {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("fixtures").join(MINT);
+    RpcFixture::load(&dir).unwrap()
+}
+
+/// An analyzer answering every call from `fixture`, without cache, history or
+/// market data; `configure` adjusts the rest of its config
+#[cfg(feature = "native")]
+pub fn replay_analyzer(fixture: RpcFixture, configure: impl FnOnce(&mut AnalyzerConfig)) -> TokenAnalyzer {
+    let mut config = AnalyzerConfig {
+        // Never contacted: every call is answered from the fixture
+        rpc_urls: vec!["http://127.0.0.1:9".to_string()],
+        cache: None,
+        history: None,
+        market_data: None,
+        ..AnalyzerConfig::default()
+    };
+    configure(&mut config);
+    TokenAnalyzer::with_config(config).unwrap().with_fixture_replay(fixture)
+}
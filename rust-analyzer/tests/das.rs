@@ -0,0 +1,95 @@
+//! Helius DAS: decoding `getAsset` for SPL, Token-2022 and compressed assets,
+//! and the recorded fixture analyzed from `getAsset` in place of the raw mint
+//! and metadata accounts.
+
+mod common;
+
+use common::{mint_fixture, replay_analyzer, MINT};
+use serde_json::{json, Value};
+use token_analyzer::analysis::accounts::TOKEN_2022_PROGRAM_ID;
+use token_analyzer::analysis::das::{Asset, BUBBLEGUM_PROGRAM_ID};
+use token_analyzer::analysis::fixture::RecordedCall;
+
+const SPL_TOKEN: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+
+fn fungible(program: &str) -> Value {
+    json!({
+        "interface": "FungibleToken",
+        "id": MINT,
+        "content": {
+            "json_uri": "https://x/y.json",
+            "metadata": { "name": "Test Token ", "symbol": "TST", "token_standard": "Fungible" },
+        },
+        "authorities": [{ "address": "Upd8teAuthority1111111111111111111111111111", "scopes": ["full"] }],
+        "compression": { "compressed": false },
+        "ownership": { "owner": "" },
+        "mutable": true,
+        "burnt": false,
+        "token_info": {
+            "supply": 78_000_000u64,
+            "decimals": 6,
+            "token_program": program,
+            "mint_authority": "",
+            "freeze_authority": "Fr33ze11111111111111111111111111111111111111",
+        },
+    })
+}
+
+#[test]
+fn fungible_assets_carry_mint_and_metadata() {
+    let asset = Asset::from_das(&fungible(SPL_TOKEN)).unwrap();
+    assert!(!asset.is_token_2022() && !asset.info.compressed);
+    assert_eq!(asset.info.token_standard.as_deref(), Some("Fungible"));
+    assert_eq!((asset.mint.decimals, asset.mint.supply), (6, 78_000_000));
+    assert_eq!(asset.mint.mint_authority, None, "an empty authority is none");
+    assert!(asset.mint.freeze_authority.is_some());
+
+    let metadata = asset.metadata.unwrap();
+    assert_eq!(metadata.name, "Test Token");
+    assert_eq!(metadata.update_authority.as_deref(), Some("Upd8teAuthority1111111111111111111111111111"));
+    assert_eq!(metadata.is_mutable, Some(true));
+
+    assert!(Asset::from_das(&fungible(TOKEN_2022_PROGRAM_ID)).unwrap().is_token_2022());
+}
+
+#[test]
+fn compressed_assets_are_one_leaf_owned_by_one_wallet() {
+    let value = json!({
+        "interface": "V1_NFT",
+        "id": "cNFT111111111111111111111111111111111111111",
+        "content": { "json_uri": "", "metadata": { "name": "Leaf", "symbol": "" } },
+        "authorities": [],
+        "compression": { "compressed": true, "tree": "Tree1111111111111111111111111111111111111111" },
+        "ownership": { "owner": "Owner11111111111111111111111111111111111111" },
+        "mutable": false,
+        "burnt": false,
+    });
+    let asset = Asset::from_das(&value).unwrap();
+    assert!(asset.info.compressed);
+    assert_eq!(asset.info.owner.as_deref(), Some("Owner11111111111111111111111111111111111111"));
+    assert_eq!((asset.mint.program.as_str(), asset.mint.supply), (BUBBLEGUM_PROGRAM_ID, 1));
+
+    // An uncompressed NFT without token_info isn't something to score
+    let mut nft = value;
+    nft["compression"]["compressed"] = json!(false);
+    assert!(Asset::from_das(&nft).is_err());
+    assert!(Asset::from_das(&Value::Null).is_err());
+}
+
+#[tokio::test]
+async fn get_asset_replaces_the_mint_and_metadata_accounts() {
+    let mut fixture = mint_fixture();
+    // Neither the mint account nor the metadata PDA is left to read
+    fixture.calls.retain(|c| c.method != "getAccountInfo");
+    let mut asset = fungible(SPL_TOKEN);
+    asset["token_info"]["freeze_authority"] = json!(null);
+    fixture.calls.push(RecordedCall { method: "getAsset".to_string(), params: json!({ "id": MINT }), result: asset });
+
+    let analysis = replay_analyzer(fixture.clone(), |_| {}).analyze(MINT).await.unwrap();
+    assert_eq!(analysis.asset.unwrap().interface, "FungibleToken");
+    assert_eq!(analysis.metadata.unwrap().update_authority.as_deref(), Some("Upd8teAuthority1111111111111111111111111111"));
+
+    // With DAS off the same fixture is missing the mint
+    let analyzer = replay_analyzer(fixture, |config| config.das = false);
+    assert!(analyzer.analyze(MINT).await.is_err());
+}
@@ -0,0 +1,74 @@
+//! Holder delegates: delegated supply and close authorities on the top holder
+//! token accounts, read from the same `jsonParsed` accounts as their owners.
+
+mod common;
+
+use common::{band, mint_fixture, replay_analyzer, token_context, MINT};
+use serde_json::json;
+use token_analyzer::analysis::detectors::HolderDelegateDetector;
+use token_analyzer::TokenContext;
+
+const DELEGATE: &str = "De1egate1111111111111111111111111111111111";
+
+/// Ten holders of 10,000 tokens each out of a 1,000,000 supply
+fn context(delegated: &[Option<f64>], close_authority: bool) -> TokenContext {
+    let holders: Vec<_> = (0..10)
+        .map(|i| {
+            let amount = delegated.get(i).copied().flatten();
+            json!({
+                "address": format!("H{}", i),
+                "balance": 10_000.0,
+                "percent": 10.0,
+                "owner": format!("Owner{}", i),
+                "delegate": amount.map(|_| DELEGATE),
+                "delegated_amount": amount,
+                "close_authority": (close_authority && i == 0).then_some(DELEGATE),
+            })
+        })
+        .collect();
+    token_context(json!({ "holders": holders }))
+}
+
+#[test]
+fn delegated_supply_is_capped_by_each_balance() {
+    // A delegate approved for more than the account holds can only move the balance
+    let ctx = context(&[Some(50_000.0), Some(5_000.0)], false);
+    assert_eq!(ctx.delegated_percent(), Some(1.5));
+}
+
+#[test]
+fn delegates_are_scored_by_the_supply_they_can_move() {
+    let detector = HolderDelegateDetector::default();
+
+    assert_eq!(band(&detector, &context(&[], false)), (1.0, "SAFE".to_string()));
+    assert_eq!(band(&detector, &context(&[Some(1_000.0)], false)), (0.6, "MINOR".to_string()));
+    assert_eq!(band(&detector, &context(&[Some(10_000.0)], false)), (0.3, "RISKY".to_string()));
+    let all = vec![Some(10_000.0); 10];
+    assert_eq!(band(&detector, &context(&all, false)), (0.0, "CRITICAL".to_string()));
+    assert_eq!(band(&detector, &context(&[], true)), (0.7, "CLOSE AUTHORITY".to_string()));
+
+    // Without owners the token accounts were never read
+    let mut unread = context(&[], false);
+    unread.holders.iter_mut().for_each(|h| h.owner = None);
+    assert_eq!(band(&detector, &unread).1, "UNKNOWN");
+}
+
+#[tokio::test]
+async fn delegates_are_read_with_the_holder_owners() {
+    let mut fixture = mint_fixture();
+    let call = fixture
+        .calls
+        .iter_mut()
+        .find(|c| c.method == "getMultipleAccounts" && c.params[0][0] == "H11")
+        .unwrap();
+    for account in call.result["value"].as_array_mut().unwrap() {
+        let info = &mut account["data"]["parsed"]["info"];
+        info["delegate"] = json!(DELEGATE);
+        info["delegatedAmount"] = json!({ "uiAmountString": "1000000000" });
+    }
+
+    let analysis = replay_analyzer(fixture, |_| {}).analyze(MINT).await.unwrap();
+    assert!(analysis.top_holders.iter().all(|h| h.delegate.as_deref() == Some(DELEGATE)));
+    let signal = analysis.pattern_signals.iter().find(|s| s.name == "Holder Delegates").unwrap();
+    assert!(signal.details.starts_with("CRITICAL"), "{}", signal.details);
+}
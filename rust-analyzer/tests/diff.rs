@@ -0,0 +1,98 @@
+//! Analysis diffs: reading the earlier analysis from a saved output or the
+//! score history, and matching signals and metrics between the two runs.
+
+use serde_json::json;
+use token_analyzer::analysis::diff::{AnalysisDiff, Baseline, SignalChangeKind};
+use token_analyzer::analysis::history::ScoreHistory;
+use token_analyzer::analysis::PatternSignalOutput;
+use token_analyzer::{AnalysisOutput, SafetyAnalysis};
+
+const MINT: &str = "7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU";
+const FIXTURE: &str = "tests/fixtures/7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU/expected.json";
+
+fn analysis() -> SafetyAnalysis {
+    serde_json::from_str(&std::fs::read_to_string(FIXTURE).unwrap()).unwrap()
+}
+
+#[test]
+fn saved_analyses_are_read_bare_or_in_their_envelope() {
+    let bare = std::fs::read_to_string(FIXTURE).unwrap();
+    let baseline = Baseline::from_json(&bare, MINT).unwrap();
+    assert_eq!(baseline.model_version, None);
+
+    let envelope = serde_json::to_string(&AnalysisOutput::new(Ok(analysis()), "v5")).unwrap();
+    let baseline = Baseline::from_json(&envelope, MINT).unwrap();
+    assert_eq!(baseline.model_version.as_deref(), Some("v5"));
+    assert_eq!(baseline.pattern_signals.as_ref().map(Vec::len), Some(analysis().pattern_signals.len()));
+
+    // Against itself nothing changed
+    let diff = AnalysisDiff::between(&baseline, &analysis(), "v5");
+    assert_eq!(diff.score_delta, 0.0);
+    assert_eq!(diff.signals.as_ref().map(Vec::len), Some(0));
+    assert!(diff.metrics.is_empty());
+    assert!(!diff.model_changed());
+
+    let other = Baseline::from_json(&bare, "Other111111111111111111111111111111111111111").unwrap_err();
+    assert!(format!("{:#}", other).contains("not Other"), "{:#}", other);
+    let failed = serde_json::to_string(&json!({ "success": false, "error": "timed out" })).unwrap();
+    assert!(format!("{:#}", Baseline::from_json(&failed, MINT).unwrap_err()).contains("timed out"));
+}
+
+#[test]
+fn signals_are_matched_by_name_and_metrics_by_field() {
+    let baseline = Baseline::from_json(&std::fs::read_to_string(FIXTURE).unwrap(), MINT).unwrap();
+    let mut current = analysis();
+    current.safe_score -= 12.5;
+    current.metrics.holder_count += 3;
+    current.metrics.price_usd = Some(0.0042);
+    let removed = current.pattern_signals.remove(0);
+    let changed = &mut current.pattern_signals[0];
+    changed.score = 0.0;
+    changed.details = "CRITICAL: made up".to_string();
+    let changed = changed.name.clone();
+    current.pattern_signals.push(PatternSignalOutput {
+        name: "Brand New".to_string(),
+        score: 0.5,
+        confidence: 1.0,
+        details: "RISKY: new".to_string(),
+    });
+
+    let diff = AnalysisDiff::between(&baseline, &current, "v5");
+    assert_eq!(diff.score_delta, -12.5);
+    let signals = diff.signals.unwrap();
+    let kinds: Vec<(&str, SignalChangeKind)> = signals.iter().map(|s| (s.name.as_str(), s.kind)).collect();
+    assert_eq!(
+        kinds,
+        [
+            (changed.as_str(), SignalChangeKind::Changed),
+            ("Brand New", SignalChangeKind::Added),
+            (removed.name.as_str(), SignalChangeKind::Removed),
+        ]
+    );
+    assert_eq!(signals[0].band.as_deref(), Some("CRITICAL"));
+    assert_eq!(signals[2].previous_score, Some(removed.score));
+
+    let metrics: Vec<(&str, Option<f64>)> = diff.metrics.iter().map(|m| (m.metric.as_str(), m.delta)).collect();
+    assert_eq!(metrics, [("holder_count", Some(3.0)), ("price_usd", None)], "a metric missing before has no delta");
+    assert!(diff.metrics[1].previous.is_null());
+}
+
+#[test]
+fn the_score_history_diffs_scores_and_metrics_but_not_signals() {
+    let path = std::env::temp_dir().join(format!("diff-{}.sqlite3", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let history = ScoreHistory::open(&path).unwrap();
+    let mut previous = analysis();
+    previous.metrics.holder_count = 1;
+    history.record(&previous, "v4").unwrap();
+
+    let entry = history.entries(MINT, 1).unwrap().pop().unwrap();
+    let baseline = Baseline::from(entry);
+    assert!(baseline.analyzed_at.is_some());
+    let diff = AnalysisDiff::between(&baseline, &analysis(), "v5");
+    assert!(diff.signals.is_none());
+    assert!(diff.model_changed());
+    assert_eq!(diff.metrics.len(), 1);
+    assert_eq!(diff.metrics[0].metric, "holder_count");
+    let _ = std::fs::remove_file(&path);
+}
@@ -0,0 +1,111 @@
+//! Freeze history: parsing `freezeAccount`/`thawAccount`, merging the recent
+//! and launch scans, and the detector penalizing past freezes whether or not
+//! the authority has since been revoked.
+
+mod common;
+
+use common::{band, mint_fixture, replay_analyzer, token_context, MINT};
+use serde_json::{json, Value};
+use token_analyzer::analysis::detectors::FreezeHistoryDetector;
+use token_analyzer::analysis::freeze::{self, FreezeAction, FreezeEvent};
+use token_analyzer::{PatternDetector, TokenContext};
+
+const AUTHORITY: &str = "Fr33ze11111111111111111111111111111111111111";
+
+fn freeze_ix(kind: &str, account: &str, mint: &str) -> Value {
+    json!({
+        "program": "spl-token",
+        "parsed": { "type": kind, "info": { "account": account, "mint": mint, "freezeAuthority": AUTHORITY } },
+    })
+}
+
+fn event(account: &str, action: FreezeAction) -> FreezeEvent {
+    FreezeEvent {
+        signature: format!("sig-{}", account),
+        timestamp: 1_700_000_000,
+        action,
+        account: account.to_string(),
+        authority: Some(AUTHORITY.to_string()),
+    }
+}
+
+fn context(events: Vec<FreezeEvent>, freeze_authority: Option<&str>) -> TokenContext {
+    let mut context = token_context(json!({
+        "transactions": [{ "signature": "s0", "timestamp": 1_700_000_000, "tx_type": "buy" }],
+    }));
+    context.mint_info.as_mut().unwrap().freeze_authority = freeze_authority.map(String::from);
+    context.freeze_events = events;
+    context
+}
+
+#[test]
+fn freezes_and_thaws_of_the_mint_are_parsed_inner_instructions_included() {
+    let tx = json!({
+        "meta": {
+            "err": null,
+            "innerInstructions": [{ "index": 0, "instructions": [freeze_ix("thawAccount", "Acc1", MINT)] }],
+        },
+        "transaction": { "message": { "instructions": [
+            freeze_ix("freezeAccount", "Acc1", MINT),
+            freeze_ix("freezeAccount", "Acc2", "OtherMint1111111111111111111111111111111111"),
+        ] } },
+    });
+    let events = freeze::freeze_events(&tx, MINT, "sig", 42);
+    let actions: Vec<(FreezeAction, &str)> = events.iter().map(|e| (e.action, e.account.as_str())).collect();
+    assert_eq!(actions, [(FreezeAction::Freeze, "Acc1"), (FreezeAction::Thaw, "Acc1")]);
+    assert_eq!((events[0].signature.as_str(), events[0].timestamp), ("sig", 42));
+    assert_eq!(events[0].authority.as_deref(), Some(AUTHORITY));
+
+    // A failed freeze froze nothing
+    let mut failed = tx;
+    failed["meta"]["err"] = json!({ "InstructionError": [0, "Custom"] });
+    assert!(freeze::freeze_events(&failed, MINT, "sig", 42).is_empty());
+}
+
+#[test]
+fn overlapping_scans_count_each_event_once() {
+    let recent = vec![event("Acc1", FreezeAction::Freeze), event("Acc2", FreezeAction::Freeze)];
+    let launch = vec![event("Acc1", FreezeAction::Freeze)];
+    let merged = freeze::merge(launch.into_iter().chain(recent));
+    assert_eq!(merged.len(), 2);
+
+    let mut thawed = merged;
+    thawed.push(FreezeEvent { signature: "later".to_string(), ..event("Acc2", FreezeAction::Thaw) });
+    assert_eq!(freeze::frozen_accounts(&thawed), (2, 1), "Acc2 was thawed, Acc1 still frozen");
+}
+
+#[test]
+fn past_freezes_are_critical_even_once_the_authority_is_revoked() {
+    let detector = FreezeHistoryDetector::default();
+
+    assert_eq!(band(&detector, &context(Vec::new(), None)), (1.0, "SAFE".to_string()));
+
+    let revoked = context(vec![event("Acc1", FreezeAction::Freeze)], None);
+    let signal = detector.detect(&revoked);
+    assert_eq!(signal.score, 0.1);
+    assert!(signal.details.starts_with("CRITICAL") && signal.details.contains("since revoked"), "{}", signal.details);
+
+    let events = ["Acc1", "Acc2", "Acc3"].map(|a| event(a, FreezeAction::Freeze)).to_vec();
+    let signal = detector.detect(&context(events, Some(AUTHORITY)));
+    assert_eq!(signal.score, 0.0);
+    assert!(signal.details.contains("MASS FREEZE") && signal.details.contains("still active"), "{}", signal.details);
+}
+
+#[tokio::test]
+async fn freezes_in_fetched_transactions_reach_the_detector() {
+    let mut fixture = mint_fixture();
+    // `s5` is both a recent and a launch transaction
+    let call = fixture
+        .calls
+        .iter_mut()
+        .find(|c| c.method == "getTransaction" && c.params[0] == "s5")
+        .unwrap();
+    let instructions = call.result["transaction"]["message"]["instructions"].as_array_mut().unwrap();
+    instructions.push(freeze_ix("freezeAccount", "Acc1", MINT));
+
+    let analysis = replay_analyzer(fixture, |_| {}).analyze(MINT).await.unwrap();
+    assert_eq!(analysis.freeze_events.len(), 1);
+    assert_eq!(analysis.freeze_events[0].signature, "s5");
+    let signal = analysis.pattern_signals.iter().find(|s| s.name == "Freeze History").unwrap();
+    assert_eq!(signal.score, 0.1, "{}", signal.details);
+}
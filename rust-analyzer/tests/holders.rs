@@ -0,0 +1,35 @@
+//! Holders per owner wallet: the largest token accounts folded together when
+//! one wallet owns several of them.
+
+use serde_json::json;
+use token_analyzer::analysis::patterns::{merge_by_owner, HolderInfo};
+
+fn holder(address: &str, balance: f64, owner: Option<&str>) -> HolderInfo {
+    serde_json::from_value(json!({ "address": address, "balance": balance, "percent": balance, "owner": owner })).unwrap()
+}
+
+#[test]
+fn accounts_of_one_owner_count_as_one_holder() {
+    let mut delegated = holder("A3", 15.0, Some("Whale"));
+    delegated.delegate = Some("Delegate".to_string());
+    delegated.delegated_amount = Some(5.0);
+    let holders = vec![
+        holder("A1", 30.0, Some("Whale")),
+        holder("B1", 25.0, Some("Other")),
+        holder("A2", 20.0, Some("Whale")),
+        delegated,
+        holder("U1", 5.0, None),
+        holder("U2", 5.0, None),
+    ];
+
+    let merged = merge_by_owner(holders);
+    let balances: Vec<(&str, f64)> = merged.iter().map(|h| (h.address.as_str(), h.balance)).collect();
+    // Accounts without a known owner can't be matched and stay apart
+    assert_eq!(balances, [("A1", 65.0), ("B1", 25.0), ("U1", 5.0), ("U2", 5.0)]);
+
+    let whale = &merged[0];
+    assert_eq!(whale.percent, 65.0);
+    assert_eq!(whale.merged_accounts, ["A2", "A3"]);
+    assert_eq!((whale.delegate.as_deref(), whale.delegated_amount), (Some("Delegate"), Some(5.0)));
+    assert!(merged[1].merged_accounts.is_empty());
+}
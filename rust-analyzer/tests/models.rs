@@ -0,0 +1,26 @@
+//! Bundled scoring models must stay loadable, and the current one must match
+//! the built-in defaults: changing a default weight or threshold without
+//! adding a model version would silently move every `vN` score.
+
+use token_analyzer::{DetectorRegistry, Model};
+
+#[test]
+fn bundled_models_configure_the_built_in_detectors() {
+    for model in Model::ALL {
+        DetectorRegistry::default()
+            .configure(&model.scoring())
+            .unwrap_or_else(|e| panic!("model {}: {:#}", model, e));
+        assert_eq!(model.version().parse::<Model>().unwrap(), model);
+    }
+}
+
+#[test]
+fn current_model_pins_the_built_in_defaults() {
+    assert_eq!(Model::default(), *Model::ALL.last().unwrap(), "the default model should be the newest");
+    let defaults = toml::Value::try_from(DetectorRegistry::default().scoring_template().detectors).unwrap();
+    let pinned = toml::Value::try_from(Model::default().scoring().detectors).unwrap();
+    assert_eq!(
+        defaults, pinned,
+        "built-in detector defaults changed: add a new model version instead of editing a released one"
+    );
+}
@@ -0,0 +1,75 @@
+//! Trader PnL: realized profit against the average cost of each wallet's
+//! buys, and insider extraction scoring the early wallets' share of it.
+
+mod common;
+
+use std::collections::HashSet;
+
+use common::{band, token_context};
+use serde_json::json;
+use token_analyzer::analysis::detectors::InsiderExtractionDetector;
+use token_analyzer::analysis::patterns::TransactionInfo;
+use token_analyzer::analysis::pnl;
+use token_analyzer::TokenContext;
+
+fn trade(wallet: &str, tx_type: &str, token_amount: f64, sol_amount: f64) -> TransactionInfo {
+    TransactionInfo {
+        signature: format!("{}-{}-{}", wallet, tx_type, token_amount),
+        timestamp: 1_700_000_000,
+        tx_type: tx_type.to_string(),
+        wallet: Some(wallet.to_string()),
+        token_amount,
+        sol_amount,
+    }
+}
+
+/// Buyers spending 10 SOL, and an insider selling an allocation it never
+/// bought for `insider_sol`
+fn context(insider_sol: f64) -> TokenContext {
+    let mut context = token_context(json!({
+        "launch": {
+            "launch_slot": 100,
+            "snipers": [],
+            "insiders": [{ "wallet": "Insider", "tokens": 1_000.0 }],
+        },
+    }));
+    context.transactions = vec![trade("Buyer", "buy", 100.0, 10.0), trade("Insider", "sell", 100.0, insider_sol)];
+    context
+}
+
+#[test]
+fn sells_are_set_against_the_average_cost_of_the_wallets_buys() {
+    let transactions = [
+        trade("Alice", "buy", 100.0, 1.0),
+        trade("Alice", "buy", 100.0, 3.0),
+        trade("Alice", "sell", 50.0, 2.0),
+        // Bob sells more than he bought here: the rest came at no cost
+        trade("Bob", "buy", 10.0, 1.0),
+        trade("Bob", "sell", 40.0, 4.0),
+        trade("Carol", "transfer", 10.0, 0.0),
+    ];
+    let early = HashSet::from(["Bob"]);
+    let traders = pnl::trader_pnl(&transactions, &early);
+    let realized: Vec<(&str, f64, bool)> =
+        traders.iter().map(|t| (t.wallet.as_str(), t.realized_pnl_sol, t.early)).collect();
+    assert_eq!(realized, [("Bob", 3.0, true), ("Alice", 1.0, false)]);
+
+    // Bob's 3 SOL out of the 5 spent buying
+    assert_eq!(pnl::insider_extraction(&traders), Some((3.0, 60.0)));
+    assert_eq!(pnl::insider_extraction(&[]), None);
+}
+
+#[test]
+fn early_wallets_cashing_out_the_buyers_are_flagged() {
+    let detector = InsiderExtractionDetector::default();
+
+    assert_eq!(band(&detector, &context(0.5)), (1.0, "SAFE".to_string()), "under a SOL of profit");
+    assert_eq!(band(&detector, &context(2.0)), (0.8, "TAKING PROFIT".to_string()));
+    assert_eq!(band(&detector, &context(3.0)), (0.3, "HEAVY EXTRACTION".to_string()));
+    assert_eq!(band(&detector, &context(6.0)), (0.0, "EXTRACTED".to_string()));
+
+    let mut unreached = context(6.0);
+    unreached.launch = None;
+    assert_eq!(band(&detector, &unreached).1, "UNKNOWN");
+    assert_eq!(unreached.insider_extraction(), None);
+}
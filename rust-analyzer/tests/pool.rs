@@ -0,0 +1,174 @@
+//! Pool accounts encoded the way the `ml` program writes them decode through
+//! `ml-types`, their red flags combine with the token's risk level, a mint's
+//! pools add up, and the creation gate refuses mints scoring below the bar.
+
+mod common;
+
+use anyhow::anyhow;
+use base64::Engine;
+use ml_types::state::{Pool, PoolStatus};
+use ml_types::{BorshSerialize, Discriminator, Pubkey, PROGRAM_ID};
+use token_analyzer::analysis::pool::{decode_pool, pool_flags, pool_usage, PoolReport, DEFAULT_MAX_FEE_BPS};
+use token_analyzer::analysis::reasons::Severity;
+use token_analyzer::AnalysisOutput;
+
+fn key(byte: u8) -> Pubkey {
+    Pubkey::new_from_array([byte; 32])
+}
+
+/// An open pool with 1% fees paid to a dev wallet other than the creator,
+/// closing to entries a day before it expires
+fn pool() -> Pool {
+    Pool {
+        pool_id: 7,
+        salt: [1; 32],
+        mint: key(1),
+        pool_token: key(2),
+        creator: key(3),
+        start_time: 1_700_000_000,
+        duration: 3_600,
+        expire_time: 1_700_604_800,
+        end_time: 0,
+        unlock_time: 0,
+        close_time: 0,
+        max_participants: 20,
+        lock_duration: 600,
+        lock_start_time: 0,
+        amount: 1_000,
+        total_amount: 5_000,
+        total_volume: 5_000,
+        total_joins: 5,
+        total_donations: 0,
+        dev_wallet: key(4),
+        dev_fee_bps: 50,
+        burn_fee_bps: 25,
+        treasury_wallet: key(5),
+        treasury_fee_bps: 25,
+        randomness: 0,
+        randomness_account: key(0),
+        randomness_deadline_slot: 0,
+        bump: 255,
+        status: PoolStatus::Open,
+        paused: false,
+        version: 1,
+        schema: 1,
+        config_hash: [0; 32],
+        allow_mock: false,
+        randomness_commit_slot: 0,
+        initialized: true,
+        last_join_time: 1_700_000_100,
+        status_reason: 0,
+        participants_account: key(6),
+        winner: key(0),
+        processing: false,
+        end_amount: 1_000,
+        prize_mint: key(0),
+        prize_token: key(0),
+        prize_released: false,
+        min_bet_usd: 0,
+        creator_index: 0,
+        donated_prize: 0,
+        donated_burn: 0,
+        creator_bond: 50_000_000,
+        min_bet_native: 0,
+        merkle_tree: key(0),
+        max_entries: 0,
+        entry_count: 0,
+        entries_refunded: 0,
+        ticket_tree: key(0),
+        paused_at: 0,
+        paused_duration: 0,
+        max_donations_amount: 0,
+        total_donated: 0,
+        join_deadline: 1_700_518_400,
+    }
+}
+
+/// `getAccountInfo` value of `pool` in base64, as the RPC returns it
+fn account(pool: &Pool, owner: &Pubkey) -> serde_json::Value {
+    let mut data = Pool::DISCRIMINATOR.to_vec();
+    pool.serialize(&mut data).unwrap();
+    data.extend([0; 16]); // unused account space
+    let data = base64::engine::general_purpose::STANDARD.encode(data);
+    serde_json::json!({ "owner": owner.to_string(), "data": [data, "base64"] })
+}
+
+#[test]
+fn pool_accounts_decode_through_ml_types() {
+    let decoded = decode_pool(&account(&pool(), &PROGRAM_ID)).unwrap();
+    assert_eq!(decoded, pool());
+    assert_eq!(decoded.join_deadline, 1_700_518_400, "the last field lines up");
+
+    let foreign = decode_pool(&account(&pool(), &key(9))).unwrap_err();
+    assert!(foreign.to_string().contains("not a Missout pool"), "{}", foreign);
+    assert!(decode_pool(&serde_json::Value::Null).is_err());
+}
+
+#[test]
+fn red_flags_are_ranked_worst_first() {
+    assert!(pool_flags(&pool(), DEFAULT_MAX_FEE_BPS).is_empty());
+
+    let rigged = Pool { dev_wallet: key(3), dev_fee_bps: 1_500, allow_mock: true, ..pool() };
+    let flags = pool_flags(&rigged, DEFAULT_MAX_FEE_BPS);
+    let codes: Vec<(&str, Severity)> = flags.iter().map(|f| (f.code.as_str(), f.severity)).collect();
+    assert_eq!(
+        codes,
+        [
+            ("MOCK_RANDOMNESS", Severity::Critical),
+            ("EXCESSIVE_FEES", Severity::High),
+            ("DEV_IS_CREATOR", Severity::Medium),
+        ]
+    );
+
+    let greedy = Pool { treasury_fee_bps: 5_000, ..pool() };
+    assert_eq!(pool_flags(&greedy, DEFAULT_MAX_FEE_BPS)[0].severity, Severity::Critical);
+}
+
+#[test]
+fn report_risk_is_the_worse_of_token_and_pool() {
+    let failed = AnalysisOutput::new(Err(anyhow!("rpc down")), "v2");
+    let report = PoolReport::new("Pool1", &pool(), failed, DEFAULT_MAX_FEE_BPS);
+    assert_eq!(report.risk_level, "unknown");
+    assert_eq!(report.total_fee_bps, 100);
+    assert_eq!((report.join_deadline, report.expire_time), (1_700_518_400, 1_700_604_800));
+
+    let failed = AnalysisOutput::new(Err(anyhow!("rpc down")), "v2");
+    let mocked = Pool { allow_mock: true, ..pool() };
+    assert_eq!(PoolReport::new("Pool1", &mocked, failed, DEFAULT_MAX_FEE_BPS).risk_level, "critical");
+}
+
+#[test]
+fn pool_usage_counts_only_active_pots() {
+    let pools = [
+        ("Open".to_string(), pool()),
+        ("Mocked".to_string(), Pool { allow_mock: true, total_amount: 2_500, ..pool() }),
+        ("Greedy".to_string(), Pool { dev_fee_bps: 1_500, status: PoolStatus::Locked, ..pool() }),
+        ("Ended".to_string(), Pool { allow_mock: true, status: PoolStatus::Ended, ..pool() }),
+    ];
+    let usage = pool_usage(&pools, 3);
+    assert_eq!((usage.pools, usage.active_pools), (4, 3));
+    assert_eq!(usage.total_value_locked, 12.5);
+    let flagged: Vec<&str> = usage.unsafe_pools.iter().map(|p| p.pool.as_str()).collect();
+    assert_eq!(flagged, ["Mocked", "Greedy"], "active pools only, worst first");
+    assert_eq!(usage.unsafe_pools[1].status, "locked");
+
+    assert_eq!(pool_usage(&[], 6).pools, 0);
+}
+
+#[cfg(feature = "native")]
+#[tokio::test]
+async fn creation_gate_refuses_mints_below_the_minimum() {
+    use common::{mint_fixture, replay_analyzer, MINT};
+    use token_analyzer::UnsafeMint;
+
+    let analyzer = replay_analyzer(mint_fixture(), |_| {});
+    let analysis = analyzer.assert_safe_for_pool(MINT, 40.0).await.unwrap();
+    let error = analyzer.assert_safe_for_pool(MINT, 50.0).await.unwrap_err();
+    let refusal = error.downcast_ref::<UnsafeMint>().expect("a refusal, not a failed analysis");
+    assert_eq!(refusal.score, analysis.safe_score);
+    assert_eq!(refusal.min_score, 50.0);
+
+    let error = analyzer.assert_safe_for_pool("unrecorded", 0.0).await.unwrap_err();
+    assert!(error.downcast_ref::<UnsafeMint>().is_none());
+    assert!(format!("{:#}", error).contains("could not be analyzed"), "{:#}", error);
+}
@@ -0,0 +1,80 @@
+//! Post-mortems: holders rebuilt from token balances in replayed transactions,
+//! and the fixture's analysis rebuilt as of a time partway through its
+//! recorded history.
+
+mod common;
+
+use common::{mint_fixture, replay_analyzer, MINT};
+use serde_json::{json, Value};
+use token_analyzer::analysis::completeness::DataSource;
+use token_analyzer::analysis::postmortem;
+use token_analyzer::TokenAnalyzer;
+
+/// `(account index, owner, before, after)`, `None` where the account doesn't
+/// exist on that side
+type Balance = (u64, &'static str, Option<&'static str>, Option<&'static str>);
+
+/// A transaction moving the mint's balances
+fn tx(balances: &[Balance]) -> Value {
+    let side = |pick: fn(&Balance) -> Option<&'static str>| -> Vec<Value> {
+        balances
+            .iter()
+            .filter_map(|b| {
+                let amount = pick(b)?;
+                Some(json!({ "accountIndex": b.0, "mint": MINT, "owner": b.1, "uiTokenAmount": { "uiAmountString": amount } }))
+            })
+            .collect()
+    };
+    json!({
+        "meta": { "err": null, "preTokenBalances": side(|b| b.2), "postTokenBalances": side(|b| b.3) },
+        "transaction": { "message": { "accountKeys": [
+            { "pubkey": "Payer11111111111111111111111111111111111111" },
+            { "pubkey": "AccA" },
+            { "pubkey": "AccB" },
+        ] } },
+    })
+}
+
+fn analyzer() -> TokenAnalyzer {
+    replay_analyzer(mint_fixture(), |_| {})
+}
+
+#[test]
+fn each_account_holds_its_balance_after_the_last_transaction_touching_it() {
+    let transactions = [
+        tx(&[(1, "Alice", None, Some("100")), (2, "Bob", None, Some("50"))]),
+        tx(&[(1, "Alice", Some("100"), Some("30"))]),
+        // Bob's account is closed
+        tx(&[(2, "Bob", Some("50"), None)]),
+    ];
+    let holders = postmortem::holders_at(&transactions, MINT);
+    assert_eq!(holders.len(), 1);
+    assert_eq!((holders[0].address.as_str(), holders[0].owner.as_deref()), ("AccA", Some("Alice")));
+    assert_eq!((holders[0].balance, holders[0].percent), (30.0, 100.0));
+
+    // A failed transaction moved nothing
+    let mut failed = tx(&[(1, "Alice", Some("30"), Some("0"))]);
+    failed["meta"]["err"] = json!({ "InstructionError": [0, "Custom"] });
+    assert_eq!(postmortem::holders_at(transactions.iter().chain([&failed]), MINT)[0].balance, 30.0);
+}
+
+#[tokio::test]
+async fn the_analysis_is_rebuilt_from_the_transactions_up_to_the_time() {
+    // `s0` to `s10` of the fixture's 20 mint transactions
+    let at = 1_700_000_300;
+    let postmortem = analyzer().postmortem(MINT, at).await.unwrap();
+    assert_eq!((postmortem.transactions_before, postmortem.replayed_transactions), (11, 11));
+    assert!(postmortem.reaches_creation);
+    assert_eq!(postmortem.skipped, [DataSource::Liquidity, DataSource::SellSimulation, DataSource::Market]);
+
+    let analysis = &postmortem.analysis;
+    assert_eq!(analysis.metrics.transaction_count, 11);
+    assert!(analysis.launch.is_some() && analysis.deployer.is_some());
+    assert_eq!(analysis.top_holders.iter().map(|h| h.balance).sum::<f64>(), 78.0);
+    assert!(postmortem.fired.iter().any(|r| r.code == "WHALE_CONCENTRATION_CRITICAL"));
+    let liquidity = analysis.pattern_signals.iter().find(|s| s.name == "Liquidity Depth").unwrap();
+    assert!(liquidity.details.starts_with("SKIPPED"), "{}", liquidity.details);
+
+    let error = analyzer().postmortem(MINT, 1_600_000_000).await.unwrap_err();
+    assert!(error.to_string().contains("no transactions before"), "{:#}", error);
+}
@@ -0,0 +1,204 @@
+//! Replays every fixture under `tests/fixtures` through the full analyzer and
+//! compares the analysis with the one recorded next to it, so decoding or
+//! scoring changes show up without network access. Partial mode is checked
+//! against a fixture with calls taken out, allow and deny lists against the
+//! same fixture, and known-good mints against no fixture at all.
+//!
+//! Record a fixture with `analyze-token record <MINT>`. After an intentional
+//! scoring change, `UPDATE_FIXTURES=1 cargo test --test replay` rewrites the
+//! expectations from the replayed analyses.
+
+mod common;
+
+use std::path::{Path, PathBuf};
+
+use common::replay_analyzer;
+use serde_json::Value;
+use token_analyzer::analysis::fixture::{RpcFixture, EXPECTED_FILE};
+use token_analyzer::analysis::known_good::{MintCategory, KNOWN_GOOD_SCORE};
+use token_analyzer::analysis::lists::ListKind;
+use token_analyzer::analysis::reasons::Severity;
+use token_analyzer::TokenAnalyzer;
+
+/// Differences listed per failing fixture
+const MAX_DIFFS: usize = 10;
+/// Relative tolerance for numbers: serde_json's default float parsing can be
+/// off by an ulp from what was written
+const EPSILON: f64 = 1e-9;
+
+fn fixture_dirs() -> Vec<PathBuf> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("fixtures");
+    let mut dirs: Vec<PathBuf> = std::fs::read_dir(&root)
+        .unwrap_or_else(|e| panic!("reading {}: {}", root.display(), e))
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.is_dir())
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+/// JSON paths where `actual` differs from `expected`
+fn diff(path: &str, expected: &Value, actual: &Value, out: &mut Vec<String>) {
+    match (expected, actual) {
+        (Value::Object(e), Value::Object(a)) => {
+            for key in e.keys().chain(a.keys().filter(|k| !e.contains_key(*k))) {
+                let null = Value::Null;
+                diff(&format!("{}.{}", path, key), e.get(key).unwrap_or(&null), a.get(key).unwrap_or(&null), out);
+            }
+        }
+        (Value::Number(e), Value::Number(a)) => {
+            let (e, a) = (e.as_f64().unwrap(), a.as_f64().unwrap());
+            if (e - a).abs() > EPSILON * e.abs().max(1.0) {
+                out.push(format!("{}: expected {}, got {}", path, e, a));
+            }
+        }
+        (Value::Array(e), Value::Array(a)) if e.len() == a.len() => {
+            for (i, (e, a)) in e.iter().zip(a).enumerate() {
+                diff(&format!("{}[{}]", path, i), e, a, out);
+            }
+        }
+        _ if expected != actual => out.push(format!("{}: expected {}, got {}", path, expected, actual)),
+        _ => {}
+    }
+}
+
+#[tokio::test]
+async fn fixtures_replay_to_expected_analysis() {
+    let update = std::env::var_os("UPDATE_FIXTURES").is_some();
+    let dirs = fixture_dirs();
+    assert!(!dirs.is_empty(), "no fixtures recorded");
+
+    let mut failures = Vec::new();
+    for dir in dirs {
+        let fixture = RpcFixture::load(&dir).unwrap();
+        let mint = fixture.mint.clone();
+        let analysis = match replay_analyzer(fixture, |_| {}).analyze(&mint).await {
+            Ok(analysis) => analysis,
+            Err(e) => {
+                failures.push(format!("{}: replay failed: {:#}", dir.display(), e));
+                continue;
+            }
+        };
+
+        let expected_path = dir.join(EXPECTED_FILE);
+        if update {
+            std::fs::write(&expected_path, serde_json::to_string_pretty(&analysis).unwrap() + "\n").unwrap();
+            continue;
+        }
+        let expected: Value = serde_json::from_str(&std::fs::read_to_string(&expected_path).unwrap()).unwrap();
+        let mut diffs = Vec::new();
+        diff("$", &expected, &serde_json::to_value(&analysis).unwrap(), &mut diffs);
+        if !diffs.is_empty() {
+            let shown: Vec<&str> = diffs.iter().take(MAX_DIFFS).map(String::as_str).collect();
+            failures.push(format!("{} ({} differences):\n  {}", dir.display(), diffs.len(), shown.join("\n  ")));
+        }
+    }
+    assert!(failures.is_empty(), "fixtures no longer replay to their expected analysis:\n{}", failures.join("\n"));
+}
+
+#[tokio::test]
+async fn replay_rejects_unrecorded_calls() {
+    let fixture = RpcFixture { mint: "unrecorded".to_string(), recorded_at: 0, calls: Vec::new() };
+    let error = replay_analyzer(fixture, |_| {}).analyze("unrecorded").await.unwrap_err();
+    assert!(format!("{:#}", error).contains("no recorded response"), "{:#}", error);
+}
+
+#[tokio::test]
+async fn partial_mode_reports_what_a_failed_fetch_cost() {
+    let dir = fixture_dirs().into_iter().next().unwrap();
+    let mut fixture = RpcFixture::load(&dir).unwrap();
+    let mint = fixture.mint.clone();
+    fixture.calls.retain(|c| !(c.method == "getTokenLargestAccounts" && c.params[0] == mint.as_str()));
+
+    let error = replay_analyzer(fixture.clone(), |_| {}).analyze(&mint).await.unwrap_err();
+    assert!(format!("{:#}", error).contains("getTokenLargestAccounts"), "{:#}", error);
+
+    let analysis = replay_analyzer(fixture, |config| config.partial = true).analyze(&mint).await.unwrap();
+    let completeness = analysis.data_completeness.unwrap();
+    assert!(!completeness.complete);
+    let sources: Vec<&str> = completeness.missing.iter().map(|m| m.source.as_str()).collect();
+    assert!(sources.contains(&"holders"), "{:?}", sources);
+    assert!(completeness.no_data_detectors.iter().any(|id| id == "whale-concentration"));
+
+    let whale = analysis.score_breakdown.detectors.iter().find(|d| d.id == "whale-concentration").unwrap();
+    assert_eq!(whale.confidence, 0.0);
+    assert!(analysis.reason_codes.iter().any(|r| r.code == "WHALE_CONCENTRATION_UNKNOWN"));
+    let age = analysis.score_breakdown.detectors.iter().find(|d| d.id == "token-age").unwrap();
+    assert!(age.confidence > 0.0, "token-age doesn't read the holders");
+}
+
+/// `entries` written as a list file under the temp dir
+fn list_file(name: &str, entries: Value) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("analyzer-{}-{}.json", name, std::process::id()));
+    std::fs::write(&path, entries.to_string()).unwrap();
+    path
+}
+
+fn listed_analyzer(fixture: RpcFixture, allow: Option<PathBuf>, deny: Option<PathBuf>) -> TokenAnalyzer {
+    replay_analyzer(fixture, |config| {
+        config.allow_list = allow;
+        config.deny_list = deny;
+    })
+}
+
+#[tokio::test]
+async fn deny_lists_rate_mints_and_deployers_critical() {
+    let dir = fixture_dirs().into_iter().next().unwrap();
+    let fixture = RpcFixture::load(&dir).unwrap();
+    let mint = fixture.mint.clone();
+
+    // A denied mint is decided without a single call
+    let empty = RpcFixture { mint: mint.clone(), recorded_at: fixture.recorded_at, calls: Vec::new() };
+    let deny = list_file("deny-mint", serde_json::json!([{ "address": mint, "reason": "known scam" }]));
+    let analysis = listed_analyzer(empty, None, Some(deny)).analyze(&mint).await.unwrap();
+    assert_eq!((analysis.safe_score, analysis.risk_level.as_str()), (0.0, "critical"));
+    assert_eq!(analysis.reason_codes[0].code, "DENY_LIST_MINT");
+    assert!(analysis.reasons[0].contains("known scam"), "{:?}", analysis.reasons);
+    assert!(analysis.score_breakdown.detectors.iter().all(|d| d.weight == 0.0));
+
+    let deployer = list_file("deny-deployer", serde_json::json!([{ "address": "Dep1oyer11111111111111111111111111111111111" }]));
+    let analysis = listed_analyzer(fixture, None, Some(deployer)).analyze(&mint).await.unwrap();
+    assert_eq!((analysis.safe_score, analysis.risk_level.as_str()), (0.0, "critical"));
+    assert_eq!(analysis.reason_codes[0].code, "DENY_LIST_DEPLOYER");
+    assert!(analysis.deployer.is_some(), "the rest of the analysis is kept");
+}
+
+#[tokio::test]
+async fn allow_listed_mints_skip_the_expensive_checks() {
+    let dir = fixture_dirs().into_iter().next().unwrap();
+    let fixture = RpcFixture::load(&dir).unwrap();
+    let mint = fixture.mint.clone();
+
+    let allow = list_file("allow", serde_json::json!([{ "address": mint }]));
+    let analysis = listed_analyzer(fixture, Some(allow), None).analyze(&mint).await.unwrap();
+    assert!(analysis.deployer.is_none() && analysis.launch.is_none() && analysis.holder_funding.is_none());
+    assert_eq!(analysis.list_match.unwrap().list, ListKind::Allow);
+
+    let deployer = analysis.score_breakdown.detectors.iter().find(|d| d.id == "deployer-history").unwrap();
+    assert_eq!(deployer.weight, 0.0);
+    assert_eq!(deployer.band.as_deref(), Some("SKIPPED"));
+    let skipped = analysis.reason_codes.iter().find(|r| r.detector == "deployer-history").unwrap();
+    assert_eq!(skipped.severity, Severity::Unknown);
+}
+
+#[tokio::test]
+async fn known_good_mints_are_rated_without_a_call() {
+    let usdc = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+    let empty = || RpcFixture { mint: usdc.to_string(), recorded_at: 1_700_000_000, calls: Vec::new() };
+
+    let analysis = replay_analyzer(empty(), |_| {}).analyze(usdc).await.unwrap();
+    assert_eq!((analysis.safe_score, analysis.risk_level.as_str()), (KNOWN_GOOD_SCORE, "low"));
+    assert_eq!(analysis.known_good.as_ref().map(|k| k.category), Some(MintCategory::Stablecoin));
+    assert_eq!(analysis.reason_codes[0].code, "KNOWN_GOOD_STABLECOIN");
+    assert!(analysis.reasons[0].contains("Circle"), "{:?}", analysis.reasons);
+    assert!(analysis.score_breakdown.detectors.iter().all(|d| d.weight == 0.0));
+
+    // A deny list entry still wins, and with the registry off it is analyzed
+    let deny = list_file("deny-usdc", serde_json::json!([{ "address": usdc }]));
+    let analysis = listed_analyzer(empty(), None, Some(deny)).analyze(usdc).await.unwrap();
+    assert_eq!(analysis.safe_score, 0.0);
+    assert!(analysis.known_good.is_none());
+    let analyzer = replay_analyzer(empty(), |config| config.known_good = false);
+    let error = analyzer.analyze(usdc).await.unwrap_err();
+    assert!(format!("{:#}", error).contains("no recorded response"), "{:#}", error);
+}
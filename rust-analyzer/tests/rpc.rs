@@ -0,0 +1,86 @@
+//! Helius key rotation: key specs, keys that spent their credit budget leaving
+//! the rotation, and the `--dry-run-cost` estimate.
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use token_analyzer::analysis::cluster::Cluster;
+use token_analyzer::analysis::cost;
+use token_analyzer::analysis::rpc::{ApiKey, RetryPolicy, RpcPool};
+use token_analyzer::AnalyzerConfig;
+
+/// Address of a server answering every request with an empty JSON-RPC result
+async fn answering_endpoint() -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        while let Ok((mut socket, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                let mut buf = [0; 4096];
+                let _ = socket.read(&mut buf).await;
+                let body = r#"{"jsonrpc":"2.0","id":1,"result":null}"#;
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    body.len(),
+                    body,
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            });
+        }
+    });
+    format!("http://{}", addr)
+}
+
+#[test]
+fn key_specs_carry_optional_budgets_and_rate_limits() {
+    let key: ApiKey = "abc".parse().unwrap();
+    assert_eq!((key.credits, key.rate_limit), (None, None));
+    assert_eq!(key.url(Cluster::Mainnet).unwrap(), "https://mainnet.helius-rpc.com/?api-key=abc");
+    assert_eq!(key.url(Cluster::Devnet).unwrap(), "https://devnet.helius-rpc.com/?api-key=abc");
+    assert!(key.url(Cluster::Custom).is_err(), "no Helius URL to put the key on");
+
+    let key: ApiKey = "abc:100000:10".parse().unwrap();
+    assert_eq!((key.key.as_str(), key.credits, key.rate_limit), ("abc", Some(100_000), Some(10)));
+    let key: ApiKey = "abc::10".parse().unwrap();
+    assert_eq!((key.credits, key.rate_limit), (None, Some(10)));
+
+    assert!("".parse::<ApiKey>().is_err());
+    assert!("abc:lots".parse::<ApiKey>().is_err());
+    assert!("abc:1:2:3".parse::<ApiKey>().is_err());
+}
+
+#[tokio::test]
+async fn spent_keys_leave_the_rotation() {
+    let spent = ApiKey { key: "spent".to_string(), credits: Some(0), rate_limit: None };
+    let body = serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": "getProgramAccounts" });
+
+    let keys = vec![spent.clone()];
+    let pool = RpcPool::new(reqwest::Client::new(), Vec::new(), keys, Cluster::Mainnet, RetryPolicy::default()).unwrap();
+    let error = pool.call(&body).await.unwrap_err();
+    assert!(error.to_string().contains("spent its credit budget"), "{:#}", error);
+
+    // With a URL to fall back on, the call goes there and the key isn't charged
+    let urls = vec![answering_endpoint().await];
+    let pool = RpcPool::new(reqwest::Client::new(), urls, vec![spent], Cluster::Mainnet, RetryPolicy::default()).unwrap();
+    pool.call(&body).await.unwrap();
+    let usage = pool.usage();
+    assert_eq!((usage[0].requests, usage[0].credits, usage[0].budget), (0, 0, Some(0)));
+    assert!(usage[0].endpoint.ends_with("key 1"));
+    assert_eq!((usage[1].requests, usage[1].credits), (1, 10));
+}
+
+#[test]
+fn estimates_follow_the_config() {
+    let config = AnalyzerConfig::default();
+    let estimate = cost::estimate(&config);
+    let calls = |estimate: &cost::CostEstimate, method: &str| {
+        estimate.methods.iter().find(|m| m.method == method).map_or(0, |m| m.calls)
+    };
+    assert_eq!(calls(&estimate, "getAsset"), 1);
+    assert_eq!(estimate.credits, estimate.methods.iter().map(|m| m.credits).sum::<u64>());
+    assert_eq!(estimate.methods[0].method, "getProgramAccounts", "the most credits come first");
+
+    // Deeper signature history costs a page per thousand, but classifies no more
+    let deep = cost::estimate(&AnalyzerConfig { tx_depth: 2_500, das: false, ..config });
+    assert_eq!(calls(&deep, "getAsset"), 0);
+    assert_eq!(calls(&deep, "getSignaturesForAddress"), calls(&estimate, "getSignaturesForAddress") + 2);
+    assert_eq!(calls(&deep, "getTransaction"), calls(&estimate, "getTransaction"));
+}
@@ -0,0 +1,98 @@
+//! The JSON Schema shipped for TypeScript consumers
+//! (`shared/analysis-output.schema.json`) must match the output types, and
+//! real output must validate against it. `UPDATE_FIXTURES=1 cargo test --test
+//! schema` rewrites it after an intentional output change.
+
+use std::path::Path;
+
+use serde_json::Value;
+use token_analyzer::AnalysisOutput;
+
+const EXPECTED: &str = "tests/fixtures/7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU/expected.json";
+
+fn shipped_path() -> std::path::PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join("shared").join("analysis-output.schema.json")
+}
+
+#[test]
+fn shipped_schema_matches_the_output_types() {
+    let schema = serde_json::to_string_pretty(&AnalysisOutput::schema()).unwrap() + "\n";
+    if std::env::var_os("UPDATE_FIXTURES").is_some() {
+        std::fs::write(shipped_path(), &schema).unwrap();
+        return;
+    }
+    let shipped = std::fs::read_to_string(shipped_path()).unwrap();
+    assert!(
+        shipped == schema,
+        "shared/analysis-output.schema.json is stale: run `analyze-token --schema` or UPDATE_FIXTURES=1"
+    );
+}
+
+fn is_type(value: &Value, name: &str) -> bool {
+    match name {
+        "null" => value.is_null(),
+        "boolean" => value.is_boolean(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "number" => value.is_number(),
+        "string" => value.is_string(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        _ => true,
+    }
+}
+
+/// `value` has the declared types, every key of it is declared by `schema`
+/// (recursively through `$ref`s) and every required key is present
+fn conforms(value: &Value, schema: &Value, defs: &Value, path: &str) -> Vec<String> {
+    if let Some(reference) = schema["$ref"].as_str() {
+        let name = reference.trim_start_matches("#/$defs/");
+        return conforms(value, &defs[name], defs, path);
+    }
+    if let Some(options) = schema["anyOf"].as_array().or(schema["oneOf"].as_array()) {
+        let errors: Vec<Vec<String>> = options.iter().map(|o| conforms(value, o, defs, path)).collect();
+        return match errors.iter().any(Vec::is_empty) {
+            true => Vec::new(),
+            false => errors.into_iter().flatten().collect(),
+        };
+    }
+    let types: Vec<&str> = match &schema["type"] {
+        Value::String(t) => vec![t.as_str()],
+        Value::Array(ts) => ts.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    };
+    if !types.is_empty() && !types.iter().any(|t| is_type(value, t)) {
+        return vec![format!("{}: not {}", path, types.join(" or "))];
+    }
+    let mut errors = Vec::new();
+    match value {
+        Value::Object(object) if schema["properties"].is_object() => {
+            for key in schema["required"].as_array().into_iter().flatten().filter_map(Value::as_str) {
+                if !object.contains_key(key) {
+                    errors.push(format!("{}: missing `{}`", path, key));
+                }
+            }
+            for (key, item) in object {
+                match schema["properties"].get(key) {
+                    Some(property) => errors.extend(conforms(item, property, defs, &format!("{}.{}", path, key))),
+                    None => errors.push(format!("{}: undeclared `{}`", path, key)),
+                }
+            }
+        }
+        Value::Array(items) if schema["items"].is_object() => {
+            for (i, item) in items.iter().enumerate() {
+                errors.extend(conforms(item, &schema["items"], defs, &format!("{}[{}]", path, i)));
+            }
+        }
+        _ => {}
+    }
+    errors
+}
+
+#[test]
+fn recorded_output_conforms_to_the_schema() {
+    let analysis: Value = serde_json::from_str(&std::fs::read_to_string(EXPECTED).unwrap()).unwrap();
+    let output = serde_json::json!({ "success": true, "model_version": "v5", "data": analysis });
+    let schema = AnalysisOutput::schema();
+    let errors = conforms(&output, &schema, &schema["$defs"], "$");
+    assert!(errors.is_empty(), "{:#?}", errors);
+}
@@ -0,0 +1,52 @@
+//! A hung endpoint (accepts connections, never answers) fails RPC calls and
+//! analyses within their timeouts instead of stalling them.
+
+use std::time::{Duration, Instant};
+
+use token_analyzer::analysis::cluster::Cluster;
+use token_analyzer::analysis::rpc::{RetryPolicy, RpcPool};
+use token_analyzer::{AnalysisTimeout, AnalyzerConfig, TokenAnalyzer};
+
+/// Address of a server that holds every connection open without responding
+async fn hung_endpoint() -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let mut held = Vec::new();
+        while let Ok((socket, _)) = listener.accept().await {
+            held.push(socket);
+        }
+    });
+    format!("http://{}", addr)
+}
+
+#[tokio::test]
+async fn rpc_calls_time_out_across_retries() {
+    let retry = RetryPolicy { call_timeout: Duration::from_millis(200), ..RetryPolicy::default() };
+    let pool = RpcPool::new(reqwest::Client::new(), vec![hung_endpoint().await], Vec::new(), Cluster::Mainnet, retry).unwrap();
+
+    let started = Instant::now();
+    let body = serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": "getHealth" });
+    let error = pool.call(&body).await.unwrap_err();
+    assert!(started.elapsed() < Duration::from_secs(5));
+    assert!(error.to_string().contains("getHealth timed out"), "{:#}", error);
+}
+
+#[tokio::test]
+async fn analyses_time_out_as_a_whole() {
+    let config = AnalyzerConfig {
+        rpc_urls: vec![hung_endpoint().await],
+        analysis_timeout: Some(Duration::from_millis(200)),
+        cache: None,
+        history: None,
+        market_data: None,
+        ..AnalyzerConfig::default()
+    };
+    let analyzer = TokenAnalyzer::with_config(config).unwrap();
+
+    let started = Instant::now();
+    let error = analyzer.analyze("7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU").await.unwrap_err();
+    assert!(started.elapsed() < Duration::from_secs(5));
+    let timeout = error.downcast_ref::<AnalysisTimeout>().expect("an AnalysisTimeout");
+    assert_eq!(timeout.after, Duration::from_millis(200));
+}
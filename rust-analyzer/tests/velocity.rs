@@ -0,0 +1,86 @@
+//! Transaction velocity: bucketing up to the analysis time, and the spike and
+//! dead token detectors reading the series.
+
+use serde_json::json;
+use token_analyzer::analysis::detectors::{DeadTokenDetector, VelocitySpikeDetector};
+use token_analyzer::analysis::velocity::{self, Velocity};
+use token_analyzer::{PatternDetector, TokenContext};
+
+/// An hour boundary, so bucket starts are easy to read
+const NOW: i64 = 1_700_002_800;
+
+fn context(timestamps: &[i64]) -> TokenContext {
+    let transactions: Vec<_> = timestamps
+        .iter()
+        .enumerate()
+        .map(|(i, &timestamp)| json!({ "signature": format!("sig{}", i), "timestamp": timestamp, "tx_type": "buy" }))
+        .collect();
+    serde_json::from_value(json!({
+        "mint": "mint",
+        "holders": [],
+        "transactions": transactions,
+        "creation_time": NOW - 30 * 86_400,
+        "current_time": NOW,
+    }))
+    .unwrap()
+}
+
+#[test]
+fn buckets_end_at_the_analysis_time_and_start_at_the_oldest_transaction() {
+    let velocity = Velocity::new(&context(&[NOW - 1, NOW - 61, NOW - 90, NOW - 7_200]).transactions, NOW).unwrap();
+
+    assert_eq!(velocity.per_minute.len(), 60);
+    let last = velocity.per_minute.last().unwrap();
+    assert_eq!((last.start, last.transactions), (NOW - 60, 1));
+    assert_eq!(velocity.per_minute[58].transactions, 2);
+    assert_eq!(velocity::total(&velocity.per_minute), 3);
+
+    // Per hour the series starts with the oldest transaction, two hours back
+    assert_eq!(velocity.per_hour.len(), 2);
+    assert_eq!(velocity.per_hour[0].start, NOW - 7_200);
+    let counts: Vec<usize> = velocity.per_hour.iter().map(|b| b.transactions).collect();
+    assert_eq!(counts, [1, 3]);
+}
+
+#[test]
+fn bursts_against_a_steady_pace_are_spikes() {
+    let detector = VelocitySpikeDetector::default();
+
+    // One transaction a minute for the hour
+    let steady: Vec<i64> = (0..60).map(|m| NOW - 30 - m * 60).collect();
+    assert!(detector.detect(&context(&steady)).details.starts_with("STEADY"));
+
+    // Then 10 more in a single minute
+    let mut burst = steady.clone();
+    burst.extend((0..10).map(|s| NOW - 601 - s));
+    let signal = detector.detect(&context(&burst));
+    assert!(signal.details.starts_with("SPIKE: 11 txs in one minute"), "{}", signal.details);
+
+    burst.extend((10..25).map(|s| NOW - 601 - s));
+    assert!(detector.detect(&context(&burst)).details.starts_with("EXTREME: 26 txs"));
+
+    let signal = detector.detect(&context(&steady[..5]));
+    assert!(signal.details.starts_with("UNKNOWN"));
+    assert_eq!(signal.confidence, 0.2);
+}
+
+#[test]
+fn idle_and_collapsing_activity_is_dead_or_decaying() {
+    let detector = DeadTokenDetector::default();
+
+    // Ten an hour for a day, then nothing for the last six hours
+    let fading: Vec<i64> = (6..30).flat_map(|h| (0..10).map(move |i| NOW - h * 3_600 - i * 60)).collect();
+    let signal = detector.detect(&context(&fading));
+    assert!(signal.details.starts_with("FADING"), "{}", signal.details);
+
+    // ...with a single straggler: active again, but at a tenth of the pace
+    let mut straggler = fading.clone();
+    straggler.push(NOW - 60);
+    let signal = detector.detect(&context(&straggler));
+    assert!(signal.details.starts_with("DECAYING"), "{}", signal.details);
+
+    let signal = detector.detect(&context(&[NOW - 2 * 86_400]));
+    assert!(signal.details.starts_with("DEAD: no transactions in 48h"), "{}", signal.details);
+
+    assert!(detector.detect(&context(&[NOW - 60, NOW - 120])).details.starts_with("ACTIVE"));
+}